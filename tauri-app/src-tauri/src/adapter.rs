@@ -1,6 +1,12 @@
 use crate::client::ClientConfig;
+use crate::client_plugins::{self, ClientManifest};
+use crate::client_target::ClientTarget;
 use crate::codex as codex_cmds;
+use crate::config_schema;
+use crate::continue_config;
+use crate::goose_config;
 use crate::json_manager::JsonManager;
+use crate::{quarantine, server_locks};
 use serde_json::Value;
 
 pub enum ClientAdapter<'a> {
@@ -9,17 +15,64 @@ pub enum ClientAdapter<'a> {
         path: Option<&'a str>,
     },
     Codex,
+    /// Continue's YAML config (see `continue_config`)
+    Continue {
+        path: Option<&'a str>,
+    },
+    /// Goose's YAML extensions (see `goose_config`)
+    Goose {
+        path: Option<&'a str>,
+    },
+    /// Declared by a manifest (see `client_plugins`)
+    Plugin {
+        manifest: ClientManifest,
+        path: Option<&'a str>,
+    },
 }
 
 impl<'a> ClientAdapter<'a> {
     pub fn new(client: &'a str, path: Option<&'a str>) -> Self {
         if client == "codex" {
             ClientAdapter::Codex
+        } else if client == continue_config::CLIENT {
+            ClientAdapter::Continue { path }
+        } else if client == goose_config::CLIENT {
+            ClientAdapter::Goose { path }
+        } else if let Some(manifest) = client_plugins::manifest(client) {
+            ClientAdapter::Plugin { manifest, path }
         } else {
             ClientAdapter::Json { client, path }
         }
     }
 
+    fn no_disabled_support(client: &str) -> String {
+        format!("Client '{}' doesn't support disabled servers", client)
+    }
+
+    /// The target this adapter writes, as locks and quarantines record it
+    fn target(&self) -> ClientTarget {
+        match self {
+            ClientAdapter::Codex => ClientTarget::client("codex", None),
+            ClientAdapter::Continue { path } => {
+                ClientTarget::client(continue_config::CLIENT, (*path).filter(|p| !p.is_empty()))
+            }
+            ClientAdapter::Goose { path } => {
+                ClientTarget::client(goose_config::CLIENT, (*path).filter(|p| !p.is_empty()))
+            }
+            ClientAdapter::Json { client, path } => {
+                ClientTarget::client(client, (*path).filter(|p| !p.is_empty()))
+            }
+            ClientAdapter::Plugin { manifest, path } => {
+                ClientTarget::client(&manifest.id, (*path).filter(|p| !p.is_empty()))
+            }
+        }
+    }
+
+    /// Fails when `name` is locked here (see `server_locks`)
+    fn ensure_unlocked(&self, name: &str) -> Result<(), String> {
+        server_locks::ensure_unlocked(&self.target(), name)
+    }
+
     fn json_path(&self) -> Option<(String, std::path::PathBuf)> {
         match self {
             ClientAdapter::Json { client, path } => {
@@ -31,6 +84,8 @@ impl<'a> ClientAdapter<'a> {
     }
 
     pub async fn add(&self, name: String, cfg: Value) -> Result<Value, String> {
+        self.ensure_unlocked(&name)?;
+        quarantine::ensure_not_quarantined(&self.target(), &name, "adding")?;
         match self {
             ClientAdapter::Codex => {
                 println!("[Adapter][Codex] add server: {}", name);
@@ -43,6 +98,11 @@ impl<'a> ClientAdapter<'a> {
             }
             ClientAdapter::Json { .. } => {
                 let (client_name, path) = self.json_path().unwrap();
+                config_schema::ensure_valid(config_schema::validate_server_entry(
+                    &client_name,
+                    &name,
+                    &cfg,
+                )?)?;
                 println!(
                     "[Adapter][JSON:{}] add server: {} -> {}",
                     client_name,
@@ -51,10 +111,25 @@ impl<'a> ClientAdapter<'a> {
                 );
                 JsonManager::add_mcp_server(&path, client_name.as_str(), &name, cfg).await
             }
+            ClientAdapter::Continue { path } => {
+                println!("[Adapter][Continue] add server: {}", name);
+                let servers = continue_config::write_server(*path, &name, &cfg, false)?;
+                Ok(serde_json::json!({"mcpServers": servers}))
+            }
+            ClientAdapter::Goose { path } => {
+                println!("[Adapter][Goose] add server: {}", name);
+                let servers = goose_config::write_server(*path, &name, &cfg, false)?;
+                Ok(serde_json::json!({"mcpServers": servers}))
+            }
+            ClientAdapter::Plugin { manifest, path } => {
+                println!("[Adapter][Plugin:{}] add server: {}", manifest.id, name);
+                client_plugins::write_server(manifest, *path, &name, &cfg, false).await
+            }
         }
     }
 
     pub async fn remove(&self, name: String) -> Result<Value, String> {
+        self.ensure_unlocked(&name)?;
         match self {
             ClientAdapter::Codex => {
                 println!("[Adapter][Codex] remove server: {}", name);
@@ -72,10 +147,26 @@ impl<'a> ClientAdapter<'a> {
                 );
                 JsonManager::remove_mcp_server(&path, client_name.as_str(), &name).await
             }
+            ClientAdapter::Continue { path } => {
+                println!("[Adapter][Continue] remove server: {}", name);
+                let servers = continue_config::remove_servers(*path, &[name])?;
+                Ok(serde_json::json!({"mcpServers": servers}))
+            }
+            ClientAdapter::Goose { path } => {
+                println!("[Adapter][Goose] remove server: {}", name);
+                let servers = goose_config::remove_servers(*path, &[name])?;
+                Ok(serde_json::json!({"mcpServers": servers}))
+            }
+            ClientAdapter::Plugin { manifest, path } => {
+                println!("[Adapter][Plugin:{}] remove server: {}", manifest.id, name);
+                client_plugins::remove_servers(manifest, *path, &[name]).await
+            }
         }
     }
 
     pub async fn update(&self, name: String, cfg: Value) -> Result<Value, String> {
+        self.ensure_unlocked(&name)?;
+        quarantine::ensure_not_quarantined(&self.target(), &name, "updating")?;
         match self {
             ClientAdapter::Codex => {
                 println!("[Adapter][Codex] update server: {}", name);
@@ -88,6 +179,11 @@ impl<'a> ClientAdapter<'a> {
             }
             ClientAdapter::Json { .. } => {
                 let (client_name, path) = self.json_path().unwrap();
+                config_schema::ensure_valid(config_schema::validate_server_entry(
+                    &client_name,
+                    &name,
+                    &cfg,
+                )?)?;
                 println!(
                     "[Adapter][JSON:{}] update server: {} -> {}",
                     client_name,
@@ -96,10 +192,27 @@ impl<'a> ClientAdapter<'a> {
                 );
                 JsonManager::update_mcp_server(&path, client_name.as_str(), &name, cfg).await
             }
+            ClientAdapter::Continue { path } => {
+                println!("[Adapter][Continue] update server: {}", name);
+                let servers = continue_config::write_server(*path, &name, &cfg, true)?;
+                Ok(serde_json::json!({"mcpServers": servers}))
+            }
+            ClientAdapter::Goose { path } => {
+                println!("[Adapter][Goose] update server: {}", name);
+                let servers = goose_config::write_server(*path, &name, &cfg, true)?;
+                Ok(serde_json::json!({"mcpServers": servers}))
+            }
+            ClientAdapter::Plugin { manifest, path } => {
+                println!("[Adapter][Plugin:{}] update server: {}", manifest.id, name);
+                client_plugins::write_server(manifest, *path, &name, &cfg, true).await
+            }
         }
     }
 
     pub async fn batch_delete(&self, names: Vec<String>) -> Result<Value, String> {
+        for name in &names {
+            self.ensure_unlocked(name)?;
+        }
         match self {
             ClientAdapter::Codex => {
                 println!("[Adapter][Codex] batch delete servers");
@@ -118,6 +231,20 @@ impl<'a> ClientAdapter<'a> {
                 );
                 JsonManager::batch_delete_mcp_servers(&path, client_name.as_str(), names).await
             }
+            ClientAdapter::Continue { path } => {
+                println!("[Adapter][Continue] batch delete servers");
+                let servers = continue_config::remove_servers(*path, &names)?;
+                Ok(serde_json::json!({"mcpServers": servers}))
+            }
+            ClientAdapter::Goose { path } => {
+                println!("[Adapter][Goose] batch delete servers");
+                let servers = goose_config::remove_servers(*path, &names)?;
+                Ok(serde_json::json!({"mcpServers": servers}))
+            }
+            ClientAdapter::Plugin { manifest, path } => {
+                println!("[Adapter][Plugin:{}] batch delete servers", manifest.id);
+                client_plugins::remove_servers(manifest, *path, &names).await
+            }
         }
     }
 
@@ -137,10 +264,19 @@ impl<'a> ClientAdapter<'a> {
                 );
                 JsonManager::list_disabled_servers(&path, client_name.as_str()).await
             }
+            ClientAdapter::Goose { path } => {
+                let disabled = goose_config::read_servers(*path, false)?;
+                println!("[Adapter][Goose] list disabled: {}", disabled.len());
+                Ok(serde_json::Value::Object(disabled))
+            }
+            ClientAdapter::Continue { .. } | ClientAdapter::Plugin { .. } => {
+                Ok(serde_json::json!({}))
+            }
         }
     }
 
     pub async fn disable(&self, name: String) -> Result<Value, String> {
+        self.ensure_unlocked(&name)?;
         match self {
             ClientAdapter::Codex => {
                 println!("[Adapter][Codex] disable: {}", name);
@@ -158,10 +294,20 @@ impl<'a> ClientAdapter<'a> {
                 );
                 JsonManager::disable_mcp_server(&path, client_name.as_str(), &name).await
             }
+            ClientAdapter::Goose { path } => {
+                println!("[Adapter][Goose] disable: {}", name);
+                let disabled = goose_config::set_enabled(*path, &name, false)?;
+                Ok(serde_json::Value::Object(disabled))
+            }
+            ClientAdapter::Continue { .. } => {
+                Err(Self::no_disabled_support(continue_config::CLIENT))
+            }
+            ClientAdapter::Plugin { manifest, .. } => Err(Self::no_disabled_support(&manifest.id)),
         }
     }
 
     pub async fn enable(&self, name: String) -> Result<Value, String> {
+        self.ensure_unlocked(&name)?;
         match self {
             ClientAdapter::Codex => {
                 println!("[Adapter][Codex] enable: {}", name);
@@ -179,10 +325,20 @@ impl<'a> ClientAdapter<'a> {
                 );
                 JsonManager::enable_mcp_server(&path, client_name.as_str(), &name).await
             }
+            ClientAdapter::Goose { path } => {
+                println!("[Adapter][Goose] enable: {}", name);
+                let disabled = goose_config::set_enabled(*path, &name, true)?;
+                Ok(serde_json::Value::Object(disabled))
+            }
+            ClientAdapter::Continue { .. } => {
+                Err(Self::no_disabled_support(continue_config::CLIENT))
+            }
+            ClientAdapter::Plugin { manifest, .. } => Err(Self::no_disabled_support(&manifest.id)),
         }
     }
 
     pub async fn update_disabled(&self, name: String, cfg: Value) -> Result<Value, String> {
+        self.ensure_unlocked(&name)?;
         match self {
             ClientAdapter::Codex => {
                 println!("[Adapter][Codex] update disabled: {}", name);
@@ -203,6 +359,15 @@ impl<'a> ClientAdapter<'a> {
                 JsonManager::update_disabled_mcp_server(&path, client_name.as_str(), &name, cfg)
                     .await
             }
+            ClientAdapter::Goose { path } => {
+                println!("[Adapter][Goose] update disabled: {}", name);
+                let disabled = goose_config::write_disabled_server(*path, &name, &cfg)?;
+                Ok(serde_json::Value::Object(disabled))
+            }
+            ClientAdapter::Continue { .. } => {
+                Err(Self::no_disabled_support(continue_config::CLIENT))
+            }
+            ClientAdapter::Plugin { manifest, .. } => Err(Self::no_disabled_support(&manifest.id)),
         }
     }
 }
@@ -0,0 +1,347 @@
+//! Known vulnerabilities of npm and PyPI server packages, from OSV.dev
+//!
+//! Packages are checked at their pinned version, or at the latest release (what `npx`/`uvx`
+//! would fetch) when unpinned. Results are cached in advisories.json in the app data dir for a
+//! day. `check_server_advisories` checks a single config, so a server can be vetted before it
+//! is installed.
+
+use crate::inventory;
+use crate::network;
+use crate::package_metadata;
+use crate::server_package::{self, Ecosystem, PackageRef};
+use crate::settings::{self, app_config_dir, RetryPolicy};
+use chrono::{DateTime, Duration, Utc};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use tauri::command;
+
+const OSV_QUERY_URL: &str = "https://api.osv.dev/v1/query";
+const CACHE_HOURS: i64 = 24;
+const CONCURRENT_QUERIES: usize = 8;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Advisory {
+    /// OSV id, e.g. `GHSA-xxxx-xxxx-xxxx`
+    pub id: String,
+    /// CVE and other ids of the same issue
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    pub summary: Option<String>,
+    /// Severity as rated by the source database, e.g. "HIGH"
+    pub severity: Option<String>,
+    /// First version with a fix, when one exists
+    pub fixed_in: Option<String>,
+    pub url: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PackageAdvisories {
+    pub package: PackageRef,
+    /// Version that was checked
+    pub version: String,
+    pub advisories: Vec<Advisory>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ServerAdvisories {
+    pub client: String,
+    pub scope: String,
+    pub name: String,
+    pub enabled: bool,
+    pub package: PackageRef,
+    pub version: String,
+    pub advisories: Vec<Advisory>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SecurityReport {
+    pub generated_at: String,
+    /// Servers whose package has known vulnerabilities
+    pub vulnerable: Vec<ServerAdvisories>,
+    /// Number of distinct packages checked
+    pub checked_packages: usize,
+    /// Packages that couldn't be checked, with the reason
+    pub unchecked: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct CachedAdvisories {
+    fetched_at: String,
+    advisories: Vec<Advisory>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct AdvisoryCache {
+    #[serde(default)]
+    packages: HashMap<String, CachedAdvisories>,
+}
+
+fn cache_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("advisories.json"))
+}
+
+fn read_cache() -> AdvisoryCache {
+    cache_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_cache(cache: &AdvisoryCache) -> Result<(), String> {
+    let path = cache_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(cache)
+        .map_err(|e| format!("Failed to serialize advisories: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write advisories: {}", e))
+}
+
+fn osv_ecosystem(ecosystem: Ecosystem) -> Option<&'static str> {
+    match ecosystem {
+        Ecosystem::Npm => Some("npm"),
+        Ecosystem::Pypi => Some("PyPI"),
+        Ecosystem::Docker => None,
+    }
+}
+
+/// One OSV vulnerability record as an advisory
+pub(crate) fn parse_vulnerability(vuln: &Value) -> Option<Advisory> {
+    let id = vuln["id"].as_str()?.to_string();
+    let strings = |value: &Value| -> Vec<String> {
+        value
+            .as_array()
+            .map(|a| {
+                a.iter()
+                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let fixed_in = vuln["affected"].as_array().and_then(|affected| {
+        affected
+            .iter()
+            .filter_map(|a| a["ranges"].as_array())
+            .flatten()
+            .filter_map(|r| r["events"].as_array())
+            .flatten()
+            .find_map(|e| e["fixed"].as_str().map(|s| s.to_string()))
+    });
+    Some(Advisory {
+        url: format!("https://osv.dev/vulnerability/{}", id),
+        aliases: strings(&vuln["aliases"]),
+        summary: vuln["summary"]
+            .as_str()
+            .or_else(|| vuln["details"].as_str())
+            .map(|s| s.lines().next().unwrap_or(s).to_string()),
+        severity: vuln["database_specific"]["severity"]
+            .as_str()
+            .map(|s| s.to_uppercase()),
+        fixed_in,
+        id,
+    })
+}
+
+async fn query_osv(
+    client: &reqwest::Client,
+    retry: &RetryPolicy,
+    ecosystem: &str,
+    name: &str,
+    version: &str,
+) -> Result<Vec<Advisory>, String> {
+    let body = json!({
+        "package": { "name": name, "ecosystem": ecosystem },
+        "version": version
+    });
+    let response = network::send_with_retry(retry, || client.post(OSV_QUERY_URL).json(&body))
+        .await
+        .map_err(|e| format!("Failed to reach OSV: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("OSV returned {}", response.status()));
+    }
+    let document: Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse OSV response: {}", e))?;
+    Ok(document["vulns"]
+        .as_array()
+        .map(|vulns| vulns.iter().filter_map(parse_vulnerability).collect())
+        .unwrap_or_default())
+}
+
+/// Versions to check: pinned ones as written, unpinned ones at their latest release
+async fn versions_to_check(packages: &[PackageRef]) -> HashMap<PackageRef, String> {
+    let unpinned: Vec<PackageRef> = packages
+        .iter()
+        .filter(|p| !p.is_pinned())
+        .cloned()
+        .collect();
+    let latest = if unpinned.is_empty() {
+        HashMap::new()
+    } else {
+        package_metadata::resolve(&unpinned).await
+    };
+    packages
+        .iter()
+        .filter_map(|p| {
+            let version = if p.is_pinned() {
+                p.version.clone()
+            } else {
+                latest.get(p).and_then(|m| m.version.clone())
+            };
+            version.map(|v| (p.clone(), v))
+        })
+        .collect()
+}
+
+/// Advisories for each npm/PyPI package, and the packages that couldn't be checked
+pub(crate) async fn lookup(packages: &[PackageRef]) -> (Vec<PackageAdvisories>, Vec<String>) {
+    let mut unique: Vec<PackageRef> = Vec::new();
+    for package in packages {
+        if osv_ecosystem(package.ecosystem).is_some() && !unique.contains(package) {
+            unique.push(package.clone());
+        }
+    }
+    let versions = versions_to_check(&unique).await;
+    let mut unchecked: Vec<String> = unique
+        .iter()
+        .filter(|p| !versions.contains_key(*p))
+        .map(|p| format!("{}: version could not be resolved", p.name))
+        .collect();
+
+    let mut cache = read_cache();
+    let now = Utc::now();
+    let is_fresh = |entry: &CachedAdvisories| {
+        DateTime::parse_from_rfc3339(&entry.fetched_at)
+            .is_ok_and(|t| now - t.with_timezone(&Utc) < Duration::hours(CACHE_HOURS))
+    };
+    let key = |package: &PackageRef, version: &str| {
+        format!(
+            "{}:{}@{}",
+            osv_ecosystem(package.ecosystem).unwrap_or_default(),
+            package.name,
+            version
+        )
+    };
+
+    let mut found = Vec::new();
+    let mut missing = Vec::new();
+    for (package, version) in versions {
+        match cache.packages.get(&key(&package, &version)) {
+            Some(entry) if is_fresh(entry) => found.push(PackageAdvisories {
+                package,
+                version,
+                advisories: entry.advisories.clone(),
+            }),
+            _ => missing.push((package, version)),
+        }
+    }
+
+    if !missing.is_empty() {
+        let network = settings::load_settings().network;
+        match network::http_client(&network) {
+            Ok(client) => {
+                let results: Vec<_> = futures::stream::iter(missing)
+                    .map(|(package, version)| {
+                        let client = client.clone();
+                        let retry = &network.retry;
+                        async move {
+                            let ecosystem = osv_ecosystem(package.ecosystem).unwrap_or_default();
+                            let result =
+                                query_osv(&client, retry, ecosystem, &package.name, &version).await;
+                            (package, version, result)
+                        }
+                    })
+                    .buffer_unordered(CONCURRENT_QUERIES)
+                    .collect()
+                    .await;
+                for (package, version, result) in results {
+                    match result {
+                        Ok(advisories) => {
+                            cache.packages.insert(
+                                key(&package, &version),
+                                CachedAdvisories {
+                                    fetched_at: now.to_rfc3339(),
+                                    advisories: advisories.clone(),
+                                },
+                            );
+                            found.push(PackageAdvisories {
+                                package,
+                                version,
+                                advisories,
+                            });
+                        }
+                        Err(e) => unchecked.push(format!("{}: {}", package.name, e)),
+                    }
+                }
+                if let Err(e) = write_cache(&cache) {
+                    println!("[Advisories] {}", e);
+                }
+            }
+            Err(e) => unchecked.extend(missing.iter().map(|(p, _)| format!("{}: {}", p.name, e))),
+        }
+    }
+    (found, unchecked)
+}
+
+/// Servers across all known targets whose package has known vulnerabilities
+#[command]
+pub async fn security_report() -> Result<SecurityReport, String> {
+    let inventory = inventory::collect_inventory().await;
+    let packages: Vec<PackageRef> = inventory
+        .servers
+        .iter()
+        .filter_map(|entry| entry.package.clone())
+        .collect();
+    let (results, unchecked) = lookup(&packages).await;
+    let by_package: HashMap<&PackageRef, &PackageAdvisories> =
+        results.iter().map(|r| (&r.package, r)).collect();
+
+    let vulnerable = inventory
+        .servers
+        .iter()
+        .filter_map(|entry| {
+            let result = by_package.get(entry.package.as_ref()?)?;
+            (!result.advisories.is_empty()).then(|| ServerAdvisories {
+                client: entry.client.clone(),
+                scope: entry.scope.clone(),
+                name: entry.name.clone(),
+                enabled: entry.enabled,
+                package: result.package.clone(),
+                version: result.version.clone(),
+                advisories: result.advisories.clone(),
+            })
+        })
+        .collect();
+    Ok(SecurityReport {
+        generated_at: Utc::now().to_rfc3339(),
+        vulnerable,
+        checked_packages: results.len(),
+        unchecked,
+    })
+}
+
+/// Known vulnerabilities of the package a server config would run, before installing it
+#[command]
+pub async fn check_server_advisories(config: Value) -> Result<Option<PackageAdvisories>, String> {
+    let Some(package) = server_package::package_ref(&config) else {
+        return Ok(None);
+    };
+    if osv_ecosystem(package.ecosystem).is_none() {
+        return Ok(None);
+    }
+    let (mut results, unchecked) = lookup(std::slice::from_ref(&package)).await;
+    match results.pop() {
+        Some(result) => Ok(Some(result)),
+        None => Err(unchecked
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| format!("Failed to check {}", package.name))),
+    }
+}
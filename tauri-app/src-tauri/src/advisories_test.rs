@@ -0,0 +1,39 @@
+// OSV advisory parsing tests
+use crate::advisories::parse_vulnerability;
+use serde_json::json;
+
+#[test]
+fn test_parse_vulnerability() {
+    let advisory = parse_vulnerability(&json!({
+        "id": "GHSA-3q26-f695-pp76",
+        "aliases": ["CVE-2025-53110"],
+        "summary": "Path validation bypass in filesystem server",
+        "database_specific": { "severity": "high" },
+        "affected": [{
+            "package": { "ecosystem": "npm", "name": "@modelcontextprotocol/server-filesystem" },
+            "ranges": [{
+                "type": "SEMVER",
+                "events": [{ "introduced": "0" }, { "fixed": "2025.7.1" }]
+            }]
+        }]
+    }))
+    .unwrap();
+    assert_eq!(advisory.aliases, vec!["CVE-2025-53110"]);
+    assert_eq!(advisory.severity.as_deref(), Some("HIGH"));
+    assert_eq!(advisory.fixed_in.as_deref(), Some("2025.7.1"));
+    assert_eq!(
+        advisory.url,
+        "https://osv.dev/vulnerability/GHSA-3q26-f695-pp76"
+    );
+
+    // Without a summary the first line of the details is used
+    let minimal = parse_vulnerability(&json!({
+        "id": "PYSEC-2025-1",
+        "details": "Command injection.\n\nLonger description."
+    }))
+    .unwrap();
+    assert_eq!(minimal.summary.as_deref(), Some("Command injection."));
+    assert_eq!(minimal.fixed_in, None);
+
+    assert!(parse_vulnerability(&json!({ "summary": "no id" })).is_none());
+}
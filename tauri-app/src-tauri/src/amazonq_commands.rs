@@ -0,0 +1,176 @@
+//! Amazon Q Developer CLI's MCP servers in the `ClaudeCodeServer` model
+//!
+//! Amazon Q reads ~/.aws/amazonq/mcp.json and a workspace's .amazonq/mcp.json, merging them
+//! when `q chat` starts. These commands take the same `project_dir` as the Claude Code ones,
+//! "Global" meaning the user file, and write through the generic client CRUD so warm-up and
+//! recording apply. Entries look like Claude Desktop's; stdio is the default transport and
+//! remote servers have `type: "http"` and a `url`. Fields this app doesn't model (`timeout`,
+//! `disabled`) are kept when a server is overwritten.
+
+use crate::claude_code_commands::{
+    self, is_global_config, AddOutcome, ClaudeCodeResponse, ClaudeCodeServer, GLOBAL_PROJECT_ID,
+};
+use crate::client::ClientConfig;
+use crate::client_target::{self, ClientTarget};
+use crate::mcp_crud;
+use serde_json::{json, Map, Value};
+use std::path::Path;
+use tauri::{command, AppHandle, Runtime};
+
+const CLIENT: &str = "amazonq";
+
+/// Fields `to_amazonq_entry` writes; the others of an overwritten entry are kept
+const MODELED_FIELDS: [&str; 6] = ["type", "command", "args", "env", "url", "headers"];
+
+fn amazonq_path(project_dir: &str) -> Option<String> {
+    (!is_global_config(project_dir)).then(|| project_dir.to_string())
+}
+
+/// An Amazon Q entry as a `ClaudeCodeServer`
+pub(crate) fn from_amazonq_entry(name: &str, config: &Value) -> Result<ClaudeCodeServer, String> {
+    let mut server = claude_code_commands::parse_server_config(name, config)?;
+    server.r#type = match (config["type"].as_str(), &server.command) {
+        (Some("sse"), _) => "sse".to_string(),
+        (Some("stdio"), _) | (None, Some(_)) => "stdio".to_string(),
+        _ => "http".to_string(),
+    };
+    Ok(server)
+}
+
+/// A `ClaudeCodeServer` as an Amazon Q entry, keeping the unmodeled fields of `existing`
+pub(crate) fn to_amazonq_entry(
+    server: &ClaudeCodeServer,
+    existing: Option<&Value>,
+) -> Result<Value, String> {
+    let mut entry = Map::new();
+    match server.r#type.as_str() {
+        "stdio" => {
+            let command = server
+                .command
+                .as_ref()
+                .ok_or("Command is required for stdio servers")?;
+            entry.insert("command".into(), json!(command));
+            entry.insert(
+                "args".into(),
+                json!(server.args.clone().unwrap_or_default()),
+            );
+            if let Some(env) = server.env.as_ref().filter(|e| !e.is_empty()) {
+                entry.insert("env".into(), json!(env));
+            }
+        }
+        "http" | "sse" => {
+            let url = server
+                .url
+                .as_ref()
+                .ok_or("URL is required for remote servers")?;
+            entry.insert("type".into(), json!(server.r#type));
+            entry.insert("url".into(), json!(url));
+            if let Some(headers) = server.headers.as_ref().filter(|h| !h.is_empty()) {
+                entry.insert("headers".into(), json!(headers));
+            }
+        }
+        other => return Err(format!("Unsupported server type '{}'", other)),
+    }
+    for (key, value) in existing.and_then(Value::as_object).into_iter().flatten() {
+        if !MODELED_FIELDS.contains(&key.as_str()) {
+            entry.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+    }
+    Ok(Value::Object(entry))
+}
+
+fn has_amazonq_config(dir: &Path) -> bool {
+    dir.join(".amazonq/mcp.json").is_file()
+}
+
+/// List the MCP servers of Amazon Q's user config ("Global") or of a workspace
+#[command]
+pub async fn amazonq_mcp_list(project_dir: String) -> Result<Vec<ClaudeCodeServer>, String> {
+    let target = ClientTarget::client(CLIENT, amazonq_path(&project_dir).as_deref());
+    client_target::list_servers(&target)
+        .await?
+        .iter()
+        .map(|(name, config)| from_amazonq_entry(name, config))
+        .collect()
+}
+
+/// Add a server to Amazon Q; an existing one with the same name is only replaced with
+/// `overwrite`
+#[command]
+pub async fn amazonq_mcp_add<R: Runtime>(
+    app: AppHandle<R>,
+    request: ClaudeCodeServer,
+    project_dir: String,
+    overwrite: Option<bool>,
+) -> Result<ClaudeCodeResponse, String> {
+    let path = amazonq_path(&project_dir);
+    let target = ClientTarget::client(CLIENT, path.as_deref());
+    let existing = client_target::list_servers(&target)
+        .await?
+        .remove(&request.name);
+    let outcome = match existing {
+        Some(existing) if overwrite.unwrap_or(false) => {
+            let entry = to_amazonq_entry(&request, Some(&existing))?;
+            mcp_crud::update_mcp_server(CLIENT.to_string(), path, request.name.clone(), entry)
+                .await?;
+            AddOutcome::Overwritten
+        }
+        _ => {
+            let entry = to_amazonq_entry(&request, None)?;
+            mcp_crud::add_mcp_server(app, CLIENT.to_string(), path, request.name.clone(), entry)
+                .await?;
+            AddOutcome::Added
+        }
+    };
+    Ok(ClaudeCodeResponse {
+        success: true,
+        message: format!(
+            "Server '{}' saved to Amazon Q ({})",
+            request.name, project_dir
+        ),
+        name: Some(request.name),
+        outcome: Some(outcome),
+        warnings: Vec::new(),
+    })
+}
+
+#[command]
+pub async fn amazonq_mcp_remove(
+    name: String,
+    project_dir: String,
+) -> Result<ClaudeCodeResponse, String> {
+    if !amazonq_mcp_list(project_dir.clone())
+        .await?
+        .iter()
+        .any(|server| server.name == name)
+    {
+        return Err(format!("Server '{}' not found", name));
+    }
+    mcp_crud::remove_mcp_server(CLIENT.to_string(), amazonq_path(&project_dir), name.clone())
+        .await?;
+    Ok(ClaudeCodeResponse {
+        success: true,
+        message: format!("Server '{}' removed from Amazon Q ({})", name, project_dir),
+        name: Some(name),
+        outcome: None,
+        warnings: Vec::new(),
+    })
+}
+
+/// "Global" when ~/.aws/amazonq/mcp.json exists, then the Claude Code projects with a
+/// .amazonq/mcp.json
+#[command]
+pub async fn amazonq_list_projects() -> Result<Vec<String>, String> {
+    let mut projects = Vec::new();
+    if ClientConfig::new(CLIENT, None).get_path().is_file() {
+        projects.push(GLOBAL_PROJECT_ID.to_string());
+    }
+    projects.extend(
+        claude_code_commands::claude_list_projects()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|project| !is_global_config(project) && has_amazonq_config(Path::new(project))),
+    );
+    Ok(projects)
+}
@@ -0,0 +1,47 @@
+// Amazon Q command tests
+use crate::amazonq_commands::{from_amazonq_entry, to_amazonq_entry};
+use crate::client::ClientConfig;
+use serde_json::json;
+use std::path::PathBuf;
+
+#[test]
+fn test_amazonq_overwrite_keeps_timeout_and_disabled() {
+    let existing = json!({
+        "command": "uvx",
+        "args": ["awslabs.core-mcp-server@latest"],
+        "env": { "FASTMCP_LOG_LEVEL": "ERROR" },
+        "timeout": 60000,
+        "disabled": true,
+    });
+    let mut server = from_amazonq_entry("core", &existing).unwrap();
+    assert_eq!(server.r#type, "stdio");
+    server.args = Some(vec!["awslabs.core-mcp-server@1.0".to_string()]);
+    server.env = None;
+    assert_eq!(
+        to_amazonq_entry(&server, Some(&existing)).unwrap(),
+        json!({
+            "command": "uvx",
+            "args": ["awslabs.core-mcp-server@1.0"],
+            "timeout": 60000,
+            "disabled": true,
+        })
+    );
+
+    let remote =
+        from_amazonq_entry("docs", &json!({ "type": "http", "url": "https://x/mcp" })).unwrap();
+    assert_eq!(
+        to_amazonq_entry(&remote, None).unwrap(),
+        json!({ "type": "http", "url": "https://x/mcp" })
+    );
+}
+
+#[test]
+fn test_amazonq_workspace_config_path() {
+    assert_eq!(
+        ClientConfig::new("amazonq", Some("/work/app")).get_path(),
+        PathBuf::from("/work/app/.amazonq/mcp.json")
+    );
+    assert!(ClientConfig::new("amazonq", None)
+        .get_path()
+        .ends_with(".aws/amazonq/mcp.json"));
+}
@@ -0,0 +1,350 @@
+//! Export and import of the app's own state
+//!
+//! A bundle holds the JSON stores of the app data dir (`STATE_FILES`): settings, registry
+//! sources, auth profiles, webhooks, server ownership, quarantine, autostart, drift
+//! baselines, server tags, role presets, server aliases, server locks, test presets and
+//! install defaults. Other profiles can be included too. Caches are left out since they are
+//! rebuilt. Secrets are only exported with a passphrase: they are decrypted from the
+//! per-machine store and sealed again under a key derived from the passphrase
+//! (PBKDF2-HMAC-SHA256), as the store key doesn't travel. Without a passphrase, auth
+//! profiles and webhooks arrive without their secrets.
+//!
+//! Imported files replace the existing ones and are written through the write journal, so an
+//! interrupted import can be rolled back.
+
+use crate::encryption::{decrypt_data, encrypt_data};
+use crate::settings::app_config_dir;
+use crate::{disk_space, profile, secrets, write_journal};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chrono::Utc;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::num::NonZeroU32;
+use std::path::Path;
+use tauri::command;
+
+const BUNDLE_VERSION: u32 = 1;
+const PBKDF2_ITERATIONS: u32 = 210_000;
+/// Bundle key of the default profile among `profiles`
+const DEFAULT_PROFILE: &str = "default";
+
+/// The app's stores that hold user state, as opposed to caches
+pub(crate) const STATE_FILES: [&str; 14] = [
+    "settings.json",
+    "registries.json",
+    "auth_profiles.json",
+    "webhooks.json",
+    "managed.json",
+    "quarantine.json",
+    "autostart.json",
+    "drift.json",
+    "server_tags.json",
+    "role_presets.json",
+    "server_aliases.json",
+    "server_locks.json",
+    "test_presets.json",
+    "install_defaults.json",
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ProfileState {
+    #[serde(default)]
+    pub files: BTreeMap<String, Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SealedSecrets {
+    pub salt: String,
+    pub iterations: u32,
+    pub data: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AppStateBundle {
+    pub version: u32,
+    pub exported_at: String,
+    /// Profile the bundle was exported from, `None` for the default one
+    pub profile: Option<String>,
+    pub files: BTreeMap<String, Value>,
+    /// Other profiles, by name
+    #[serde(default)]
+    pub profiles: BTreeMap<String, ProfileState>,
+    #[serde(default)]
+    pub secrets: Option<SealedSecrets>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub(crate) struct ExportedSecret {
+    pub id: String,
+    pub value: String,
+    pub expires_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct StateTransferReport {
+    pub path: String,
+    pub files: Vec<String>,
+    pub profiles: Vec<String>,
+    pub secrets: usize,
+    pub warnings: Vec<String>,
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> Result<String, String> {
+    let iterations =
+        NonZeroU32::new(iterations).ok_or_else(|| "Invalid iteration count".to_string())?;
+    let mut key = [0u8; 32];
+    ring::pbkdf2::derive(
+        ring::pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    Ok(BASE64.encode(key))
+}
+
+pub(crate) fn seal_secrets(
+    secrets: &[ExportedSecret],
+    passphrase: &str,
+) -> Result<SealedSecrets, String> {
+    let mut salt = [0u8; 16];
+    SystemRandom::new()
+        .fill(&mut salt)
+        .map_err(|_| "Failed to generate salt".to_string())?;
+    let key = derive_key(passphrase, &salt, PBKDF2_ITERATIONS)?;
+    let plain = serde_json::to_string(secrets)
+        .map_err(|e| format!("Failed to serialize secrets: {}", e))?;
+    Ok(SealedSecrets {
+        salt: BASE64.encode(salt),
+        iterations: PBKDF2_ITERATIONS,
+        data: encrypt_data(&plain, &key)?,
+    })
+}
+
+pub(crate) fn open_secrets(
+    sealed: &SealedSecrets,
+    passphrase: &str,
+) -> Result<Vec<ExportedSecret>, String> {
+    let salt = BASE64
+        .decode(&sealed.salt)
+        .map_err(|e| format!("Invalid salt: {}", e))?;
+    let key = derive_key(passphrase, &salt, sealed.iterations)?;
+    let plain = decrypt_data(&sealed.data, &key)
+        .map_err(|_| "Wrong passphrase or damaged bundle".to_string())?;
+    serde_json::from_str(&plain).map_err(|e| format!("Failed to parse secrets: {}", e))
+}
+
+pub(crate) fn is_state_file(name: &str) -> bool {
+    STATE_FILES.contains(&name)
+}
+
+/// State files of the data dir `dir`. Files that don't parse are skipped with a warning.
+pub(crate) fn read_state_files(dir: &Path, warnings: &mut Vec<String>) -> BTreeMap<String, Value> {
+    let mut files = BTreeMap::new();
+    for name in STATE_FILES {
+        let path = dir.join(name);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => {
+                warnings.push(format!("Skipped {}: {}", path.display(), e));
+                continue;
+            }
+        };
+        match serde_json::from_str(&content) {
+            Ok(value) => {
+                files.insert(name.to_string(), value);
+            }
+            Err(e) => warnings.push(format!("Skipped {}: invalid JSON: {}", path.display(), e)),
+        }
+    }
+    files
+}
+
+fn exported_secrets() -> Result<Vec<ExportedSecret>, String> {
+    let mut exported = Vec::new();
+    for info in secrets::secret_infos()? {
+        if let Some(value) = secrets::get_secret(&info.id)? {
+            exported.push(ExportedSecret {
+                id: info.id,
+                value,
+                expires_at: info.expires_at,
+            });
+        }
+    }
+    Ok(exported)
+}
+
+/// Write the app's state to `path`. Secrets are included only with a `passphrase`, other
+/// profiles only with `include_profiles`.
+#[command]
+pub async fn export_app_state(
+    path: String,
+    passphrase: Option<String>,
+    include_profiles: Option<bool>,
+) -> Result<StateTransferReport, String> {
+    let mut warnings = Vec::new();
+    let active = profile::active_profile();
+    let files = read_state_files(&app_config_dir()?, &mut warnings);
+
+    let mut profiles = BTreeMap::new();
+    if include_profiles.unwrap_or(false) {
+        let mut names: Vec<Option<String>> =
+            profile::named_profiles()?.into_iter().map(Some).collect();
+        names.push(None);
+        for name in names.into_iter().filter(|n| n.as_deref() != active) {
+            let dir = profile::data_dir_of(name.as_deref())?;
+            let state = ProfileState {
+                files: read_state_files(&dir, &mut warnings),
+            };
+            profiles.insert(name.unwrap_or_else(|| DEFAULT_PROFILE.to_string()), state);
+        }
+    }
+
+    let (secrets, secret_count) = match passphrase.as_deref().filter(|p| !p.is_empty()) {
+        Some(passphrase) => {
+            let exported = exported_secrets()?;
+            (Some(seal_secrets(&exported, passphrase)?), exported.len())
+        }
+        None => {
+            if !secrets::secret_infos()?.is_empty() {
+                warnings.push(
+                    "Secrets were left out; export with a passphrase to include them".to_string(),
+                );
+            }
+            (None, 0)
+        }
+    };
+
+    let bundle = AppStateBundle {
+        version: BUNDLE_VERSION,
+        exported_at: Utc::now().to_rfc3339(),
+        profile: active.map(|p| p.to_string()),
+        files,
+        profiles,
+        secrets,
+    };
+    let content = serde_json::to_string_pretty(&bundle)
+        .map_err(|e| format!("Failed to serialize app state: {}", e))?;
+    disk_space::ensure_space(Path::new(&path), content.len() as u64)?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write app state: {}", e))?;
+    println!(
+        "[AppState] Exported {} file(s), {} profile(s), {} secret(s) to {}",
+        bundle.files.len(),
+        bundle.profiles.len(),
+        secret_count,
+        path
+    );
+    Ok(StateTransferReport {
+        path,
+        files: bundle.files.into_keys().collect(),
+        profiles: bundle.profiles.into_keys().collect(),
+        secrets: secret_count,
+        warnings,
+    })
+}
+
+pub(crate) fn write_state_files(
+    dir: &Path,
+    files: &BTreeMap<String, Value>,
+    written: &mut Vec<String>,
+    warnings: &mut Vec<String>,
+) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create config directory: {}", e))?;
+    for (name, value) in files {
+        if !is_state_file(name) {
+            warnings.push(format!("Skipped unknown file '{}'", name));
+            continue;
+        }
+        let content = serde_json::to_string_pretty(value)
+            .map_err(|e| format!("Failed to serialize {}: {}", name, e))?;
+        let path = dir.join(name);
+        write_journal::write(&path, &content)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        written.push(path.display().to_string());
+    }
+    Ok(())
+}
+
+/// Restore a bundle written by `export_app_state` into this profile (and the bundled
+/// profiles). The `passphrase` is needed for the secrets; a wrong one fails before anything
+/// is written.
+#[command]
+pub async fn import_app_state(
+    path: String,
+    passphrase: Option<String>,
+) -> Result<StateTransferReport, String> {
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read app state: {}", e))?;
+    let bundle: AppStateBundle =
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse app state: {}", e))?;
+    if bundle.version > BUNDLE_VERSION {
+        return Err(format!(
+            "App state version {} is newer than supported version {}",
+            bundle.version, BUNDLE_VERSION
+        ));
+    }
+
+    let mut warnings = Vec::new();
+    let imported_secrets = match (&bundle.secrets, passphrase.as_deref()) {
+        (Some(sealed), Some(passphrase)) => open_secrets(sealed, passphrase)?,
+        (Some(_), None) => {
+            warnings.push("Secrets were not imported; a passphrase is required".to_string());
+            Vec::new()
+        }
+        (None, _) => Vec::new(),
+    };
+
+    // The bundle's files take about as much space as the bundle, and as much again in the
+    // journal
+    disk_space::ensure_space(&app_config_dir()?, 2 * content.len() as u64)?;
+    let batch = write_journal::begin_batch("Import app state", Vec::new());
+    let (files, profiles) = batch
+        .run(async {
+            let mut files = Vec::new();
+            write_state_files(&app_config_dir()?, &bundle.files, &mut files, &mut warnings)?;
+            let mut profiles = Vec::new();
+            for (name, state) in &bundle.profiles {
+                let profile_name = (name != DEFAULT_PROFILE).then_some(name.as_str());
+                if profile_name == profile::active_profile() {
+                    warnings.push(format!(
+                        "Skipped profile '{}': it is the one being imported into",
+                        name
+                    ));
+                    continue;
+                }
+                let dir = match profile::data_dir_of(profile_name) {
+                    Ok(dir) => dir,
+                    Err(e) => {
+                        warnings.push(e);
+                        continue;
+                    }
+                };
+                write_state_files(&dir, &state.files, &mut files, &mut warnings)?;
+                profiles.push(name.clone());
+            }
+            Ok::<_, String>((files, profiles))
+        })
+        .await?;
+    for secret in &imported_secrets {
+        secrets::put_secret(&secret.id, &secret.value)?;
+        secrets::set_expiry(&secret.id, secret.expires_at.clone())?;
+    }
+    println!(
+        "[AppState] Imported {} file(s), {} profile(s), {} secret(s) from {}",
+        files.len(),
+        profiles.len(),
+        imported_secrets.len(),
+        path
+    );
+    Ok(StateTransferReport {
+        path,
+        files,
+        profiles,
+        secrets: imported_secrets.len(),
+        warnings,
+    })
+}
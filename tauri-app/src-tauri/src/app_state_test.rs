@@ -0,0 +1,58 @@
+// App state export/import tests
+use crate::app_state::{
+    is_state_file, open_secrets, read_state_files, seal_secrets, write_state_files, ExportedSecret,
+    STATE_FILES,
+};
+use serde_json::json;
+
+#[test]
+fn test_secrets_roundtrip() {
+    let secrets = vec![ExportedSecret {
+        id: "auth:github".to_string(),
+        value: "ghp_example".to_string(),
+        expires_at: Some("2027-01-01T00:00:00Z".to_string()),
+    }];
+    let sealed = seal_secrets(&secrets, "correct horse").unwrap();
+    assert!(!sealed.data.contains("ghp_example"));
+    assert_eq!(open_secrets(&sealed, "correct horse").unwrap(), secrets);
+    assert!(open_secrets(&sealed, "wrong").is_err());
+}
+
+#[test]
+fn test_is_state_file() {
+    assert!(is_state_file("settings.json"));
+    assert!(is_state_file("role_presets.json"));
+    assert!(is_state_file("server_aliases.json"));
+    assert!(is_state_file("server_locks.json"));
+    assert!(is_state_file("test_presets.json"));
+    assert!(is_state_file("install_defaults.json"));
+    assert!(!is_state_file("secrets.json"));
+    assert!(!is_state_file("server_metadata.json"));
+    assert!(!is_state_file("../settings.json"));
+}
+
+#[test]
+fn test_state_files_roundtrip() {
+    let source = tempfile::tempdir().unwrap();
+    let target = tempfile::tempdir().unwrap();
+    for name in STATE_FILES {
+        std::fs::write(
+            source.path().join(name),
+            json!({ "file": name }).to_string(),
+        )
+        .unwrap();
+    }
+    std::fs::write(source.path().join("secrets.json"), "{}").unwrap();
+
+    let mut warnings = Vec::new();
+    let files = read_state_files(source.path(), &mut warnings);
+    assert!(files.contains_key("server_tags.json"));
+    assert!(!files.contains_key("secrets.json"));
+    let mut written = Vec::new();
+    write_state_files(target.path(), &files, &mut written, &mut warnings).unwrap();
+    assert_eq!(written.len(), STATE_FILES.len());
+    assert!(warnings.is_empty());
+
+    let imported = read_state_files(target.path(), &mut warnings);
+    assert_eq!(imported, files);
+}
@@ -0,0 +1,137 @@
+//! Append-only audit log of config changes
+//!
+//! One JSON object per line in audit.jsonl in the app data dir. Every command-level server
+//! mutation is logged through `op_recorder::record`; security-relevant actions such as trust
+//! changes add their own entries with the warnings shown to the user. Entries name servers but
+//! never contain config values. The file is rotated by size (see `log_rotation`) and read back
+//! across its rotations; lowered caps apply to the existing files as soon as they are saved.
+
+use crate::client_target::ClientTarget;
+use crate::log_rotation;
+use crate::settings::{self, app_config_dir, LogRetentionSettings};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::command;
+
+/// Serializes appends and the rotations before them
+static LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AuditEntry {
+    pub at: String,
+    pub action: String,
+    pub target: ClientTarget,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+fn audit_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("audit.jsonl"))
+}
+
+fn append_entry(entry: &AuditEntry) -> Result<(), String> {
+    let path = audit_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let line = serde_json::to_string(entry)
+        .map_err(|e| format!("Failed to serialize audit entry: {}", e))?;
+    let _guard = LOCK
+        .lock()
+        .map_err(|e| format!("Failed to lock audit log: {}", e))?;
+    let incoming = line.len() as u64 + 1;
+    log_rotation::rotate_if_needed(&path, incoming, &settings::current().log_retention)
+        .map_err(|e| format!("Failed to rotate audit log: {}", e))?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open audit log: {}", e))?;
+    writeln!(file, "{}", line).map_err(|e| format!("Failed to write audit log: {}", e))
+}
+
+/// Trim the audit log and its rotated files to the retention caps
+fn apply_retention(retention: &LogRetentionSettings) -> Result<(), String> {
+    let path = audit_path()?;
+    let _guard = LOCK
+        .lock()
+        .map_err(|e| format!("Failed to lock audit log: {}", e))?;
+    log_rotation::apply_caps(&path, retention)
+        .map_err(|e| format!("Failed to apply log retention: {}", e))
+}
+
+/// Trim the rotated files whenever the retention settings change
+pub fn spawn_retention_watch() {
+    settings::on_change(
+        |settings| settings.log_retention.clone(),
+        |retention| async move {
+            if let Err(e) = apply_retention(&retention) {
+                println!("[Audit] {}", e);
+            }
+        },
+    );
+}
+
+/// Append an entry. Logging failures are printed and never fail the action itself.
+pub(crate) fn log(
+    action: &str,
+    target: &ClientTarget,
+    server: Option<&str>,
+    detail: Option<String>,
+    warnings: Vec<String>,
+) {
+    let entry = AuditEntry {
+        at: Utc::now().to_rfc3339(),
+        action: action.to_string(),
+        target: target.clone(),
+        server: server.map(|s| s.to_string()),
+        detail,
+        warnings,
+    };
+    if let Err(e) = append_entry(&entry) {
+        println!("[Audit] {}", e);
+    }
+}
+
+/// All entries still kept, oldest first. Lines that don't parse are skipped.
+pub(crate) fn read_entries() -> Result<Vec<AuditEntry>, String> {
+    let mut entries = Vec::new();
+    for path in log_rotation::files_oldest_first(&audit_path()?) {
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            // Rotated away since it was listed
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(format!("Failed to read audit log: {}", e)),
+        };
+        entries.extend(
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str::<AuditEntry>(line).ok()),
+        );
+    }
+    Ok(entries)
+}
+
+/// Most recent entries first, optionally only those for one server
+#[command]
+pub async fn get_audit_log(
+    server: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<AuditEntry>, String> {
+    let mut entries = read_entries()?;
+    if let Some(server) = server {
+        entries.retain(|e| e.server.as_deref() == Some(server.as_str()));
+    }
+    entries.reverse();
+    entries.truncate(limit.unwrap_or(200));
+    Ok(entries)
+}
@@ -0,0 +1,353 @@
+//! Reusable auth profiles for remote servers
+//!
+//! A profile holds one set of credentials (bearer token, basic auth or custom headers) in the
+//! secrets store and remembers which servers it is attached to. Saving a profile rewrites the
+//! `headers` of every attached server, so rotating a token is a single edit. Profile metadata
+//! (kind, header names, attachments) lives in ~/.config/mcplinker/auth_profiles.json; the
+//! credentials themselves only ever exist encrypted on disk and materialized in client configs.
+//! Header writes to JSON clients go through `mcp_crud::update_mcp_server` like any other
+//! update, and attaching or detaching a profile is noted in the audit log.
+
+use crate::claude_code_commands::GLOBAL_PROJECT_ID;
+use crate::claude_edit::edit_claude_server;
+use crate::client_target::{self, ClientTarget};
+use crate::settings::app_config_dir;
+use crate::{audit_log, mcp_crud, secrets};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use tauri::command;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AuthCredentials {
+    Bearer { token: String },
+    Basic { username: String, password: String },
+    Headers { headers: BTreeMap<String, String> },
+}
+
+impl AuthCredentials {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AuthCredentials::Bearer { .. } => "bearer",
+            AuthCredentials::Basic { .. } => "basic",
+            AuthCredentials::Headers { .. } => "headers",
+        }
+    }
+
+    /// HTTP headers these credentials translate to
+    pub fn to_headers(&self) -> BTreeMap<String, String> {
+        match self {
+            AuthCredentials::Bearer { token } => {
+                BTreeMap::from([("Authorization".to_string(), format!("Bearer {}", token))])
+            }
+            AuthCredentials::Basic { username, password } => {
+                let encoded = BASE64.encode(format!("{}:{}", username, password));
+                BTreeMap::from([("Authorization".to_string(), format!("Basic {}", encoded))])
+            }
+            AuthCredentials::Headers { headers } => headers.clone(),
+        }
+    }
+}
+
+/// A server a profile's headers are written to
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ProfileTarget {
+    pub client: String,
+    pub server: String,
+    /// Claude Code scope ("Global" or a project path)
+    #[serde(default)]
+    pub working_dir: Option<String>,
+    /// Config path override for project-level JSON clients
+    #[serde(default)]
+    pub path: Option<String>,
+}
+
+impl ProfileTarget {
+    /// Client target of the server, for writes and the audit log
+    pub(crate) fn client_target(&self) -> ClientTarget {
+        if self.client == "claude_code" {
+            ClientTarget::claude_code(self.working_dir.as_deref().unwrap_or(GLOBAL_PROJECT_ID))
+        } else {
+            ClientTarget::client(&self.client, self.path.as_deref())
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AuthProfileInfo {
+    pub name: String,
+    pub kind: String,
+    pub header_names: Vec<String>,
+    pub attachments: Vec<ProfileTarget>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub(crate) struct ProfilesFile {
+    pub profiles: BTreeMap<String, AuthProfileInfo>,
+}
+
+/// Outcome of writing a profile's headers to its attached servers
+#[derive(Debug, Serialize, Clone)]
+pub struct ProfileApplyReport {
+    pub profile: AuthProfileInfo,
+    pub applied: usize,
+    pub errors: Vec<String>,
+}
+
+fn profiles_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("auth_profiles.json"))
+}
+
+pub(crate) fn secret_id(name: &str) -> String {
+    format!("auth_profile/{}", name)
+}
+
+pub(crate) fn read_profiles() -> Result<ProfilesFile, String> {
+    let path = profiles_path()?;
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse auth profiles: {}", e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(ProfilesFile::default()),
+        Err(e) => Err(format!("Failed to read auth profiles: {}", e)),
+    }
+}
+
+fn write_profiles(file: &ProfilesFile) -> Result<(), String> {
+    let path = profiles_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(file)
+        .map_err(|e| format!("Failed to serialize auth profiles: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write auth profiles: {}", e))
+}
+
+fn load_credentials(name: &str) -> Result<AuthCredentials, String> {
+    let raw = secrets::get_secret(&secret_id(name))?
+        .ok_or_else(|| format!("Credentials for auth profile '{}' are missing", name))?;
+    serde_json::from_str(&raw).map_err(|e| format!("Failed to parse auth profile: {}", e))
+}
+
+/// Materialized headers of a profile, for writes and health checks
+pub(crate) fn profile_headers(name: &str) -> Result<BTreeMap<String, String>, String> {
+    Ok(load_credentials(name)?.to_headers())
+}
+
+/// Replace the headers previously written by a profile (`previous`) with `headers`
+pub(crate) fn merge_headers(
+    server: &mut Map<String, Value>,
+    previous: &[String],
+    headers: &BTreeMap<String, String>,
+) -> Result<(), String> {
+    let target = server
+        .entry("headers")
+        .or_insert_with(|| Value::Object(Map::new()))
+        .as_object_mut()
+        .ok_or("Server 'headers' is not an object")?;
+    for name in previous {
+        target.shift_remove(name);
+    }
+    for (name, value) in headers {
+        target.insert(name.clone(), Value::String(value.clone()));
+    }
+    if target.is_empty() {
+        server.shift_remove("headers");
+    }
+    Ok(())
+}
+
+async fn write_target_headers(
+    target: &ProfileTarget,
+    previous: &[String],
+    headers: &BTreeMap<String, String>,
+) -> Result<(), String> {
+    match target.client.as_str() {
+        "claude_code" => {
+            let working_dir = target
+                .working_dir
+                .as_deref()
+                .ok_or("working_dir is required for claude_code")?;
+            edit_claude_server(&target.server, working_dir, |server| {
+                merge_headers(server, previous, headers)
+            })
+            .await
+            .map(|_| ())
+        }
+        "codex" => Err("Codex servers do not support custom headers".to_string()),
+        client => {
+            let mut server = client_target::list_servers(&target.client_target())
+                .await?
+                .get(&target.server)
+                .and_then(|server| server.as_object())
+                .cloned()
+                .ok_or_else(|| format!("Server '{}' not found in {}", target.server, client))?;
+            merge_headers(&mut server, previous, headers)?;
+            mcp_crud::update_mcp_server(
+                client.to_string(),
+                target.path.clone(),
+                target.server.clone(),
+                Value::Object(server),
+            )
+            .await
+            .map(|_| ())
+        }
+    }
+}
+
+/// Write `headers` to every target, collecting per-target failures
+async fn apply_to_targets(
+    targets: &[ProfileTarget],
+    previous: &[String],
+    headers: &BTreeMap<String, String>,
+) -> (usize, Vec<String>) {
+    let mut applied = 0;
+    let mut errors = Vec::new();
+    for target in targets {
+        match write_target_headers(target, previous, headers).await {
+            Ok(()) => applied += 1,
+            Err(e) => errors.push(format!("{}/{}: {}", target.client, target.server, e)),
+        }
+    }
+    (applied, errors)
+}
+
+#[command]
+pub async fn list_auth_profiles() -> Result<Vec<AuthProfileInfo>, String> {
+    Ok(read_profiles()?.profiles.into_values().collect())
+}
+
+/// Create or rotate a profile and rewrite the headers of all attached servers
+#[command]
+pub async fn save_auth_profile(
+    name: String,
+    credentials: AuthCredentials,
+) -> Result<ProfileApplyReport, String> {
+    if name.trim().is_empty() {
+        return Err("Profile name is required".to_string());
+    }
+    let raw = serde_json::to_string(&credentials)
+        .map_err(|e| format!("Failed to serialize auth profile: {}", e))?;
+    secrets::put_secret(&secret_id(&name), &raw)?;
+
+    let mut file = read_profiles()?;
+    let now = Utc::now().to_rfc3339();
+    let headers = credentials.to_headers();
+    let existing = file.profiles.get(&name).cloned();
+    let previous = existing
+        .as_ref()
+        .map(|p| p.header_names.clone())
+        .unwrap_or_default();
+    let profile = AuthProfileInfo {
+        name: name.clone(),
+        kind: credentials.kind().to_string(),
+        header_names: headers.keys().cloned().collect(),
+        attachments: existing
+            .as_ref()
+            .map(|p| p.attachments.clone())
+            .unwrap_or_default(),
+        created_at: existing
+            .map(|p| p.created_at)
+            .unwrap_or_else(|| now.clone()),
+        updated_at: now,
+    };
+    file.profiles.insert(name, profile.clone());
+    write_profiles(&file)?;
+
+    let (applied, errors) = apply_to_targets(&profile.attachments, &previous, &headers).await;
+    Ok(ProfileApplyReport {
+        profile,
+        applied,
+        errors,
+    })
+}
+
+/// Record or clear the expiry of a profile's credentials
+#[command]
+pub async fn set_auth_profile_expiry(
+    name: String,
+    expires_at: Option<String>,
+) -> Result<(), String> {
+    secrets::set_expiry(&secret_id(&name), expires_at)
+}
+
+/// Delete a profile; it must be detached from all servers first
+#[command]
+pub async fn delete_auth_profile(name: String) -> Result<(), String> {
+    let mut file = read_profiles()?;
+    let profile = file
+        .profiles
+        .get(&name)
+        .ok_or_else(|| format!("Auth profile '{}' not found", name))?;
+    if !profile.attachments.is_empty() {
+        return Err(format!(
+            "Auth profile '{}' is attached to {} server(s); detach it first",
+            name,
+            profile.attachments.len()
+        ));
+    }
+    file.profiles.remove(&name);
+    write_profiles(&file)?;
+    secrets::remove_secret(&secret_id(&name))?;
+    Ok(())
+}
+
+#[command]
+pub async fn attach_auth_profile(
+    name: String,
+    target: ProfileTarget,
+) -> Result<AuthProfileInfo, String> {
+    let mut file = read_profiles()?;
+    let profile = file
+        .profiles
+        .get_mut(&name)
+        .ok_or_else(|| format!("Auth profile '{}' not found", name))?;
+    let headers = profile_headers(&name)?;
+    write_target_headers(&target, &profile.header_names, &headers).await?;
+    audit_log::log(
+        "attach_auth_profile",
+        &target.client_target(),
+        Some(&target.server),
+        Some(name.clone()),
+        Vec::new(),
+    );
+    if !profile.attachments.contains(&target) {
+        profile.attachments.push(target);
+    }
+    let profile = profile.clone();
+    write_profiles(&file)?;
+    Ok(profile)
+}
+
+/// Detach a profile and remove its headers from the server
+#[command]
+pub async fn detach_auth_profile(
+    name: String,
+    target: ProfileTarget,
+) -> Result<AuthProfileInfo, String> {
+    let mut file = read_profiles()?;
+    let profile = file
+        .profiles
+        .get_mut(&name)
+        .ok_or_else(|| format!("Auth profile '{}' not found", name))?;
+    write_target_headers(&target, &profile.header_names, &BTreeMap::new()).await?;
+    audit_log::log(
+        "detach_auth_profile",
+        &target.client_target(),
+        Some(&target.server),
+        Some(name.clone()),
+        Vec::new(),
+    );
+    profile.attachments.retain(|t| t != &target);
+    let profile = profile.clone();
+    write_profiles(&file)?;
+    Ok(profile)
+}
@@ -0,0 +1,392 @@
+//! Heterogeneous server operations run as one batch
+//!
+//! `execute_batch` applies a list of operations (adds, removals, enables and disables across
+//! any clients, and trust edits) in order and reports each one's outcome. Failed operations
+//! don't stop the rest unless `stop_on_error` is set. The whole batch is journaled as one
+//! operation, and the files it wrote are kept as a single undo entry in batch_undo.json;
+//! `undo_batch` puts them back as they were before the batch.
+//!
+//! Features that apply several operations (replays, cloud downloads) build on
+//! `run_batch` rather than looping over commands.
+
+use crate::client_target::ClientTarget;
+use crate::op_recorder::{self, RecordedOp, RecordedStep};
+use crate::server_trust::{self, ServerTrust};
+use crate::settings::app_config_dir;
+use crate::write_journal::{self, FileSnapshot};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tauri::command;
+
+/// Undo entries kept; older ones are dropped
+const MAX_UNDO_ENTRIES: usize = 20;
+
+/// Serializes read-modify-write cycles of batch_undo.json
+static LOCK: Mutex<()> = Mutex::new(());
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Operation {
+    UpsertServer {
+        target: ClientTarget,
+        name: String,
+        config: serde_json::Value,
+    },
+    RemoveServer {
+        target: ClientTarget,
+        name: String,
+    },
+    DisableServer {
+        target: ClientTarget,
+        name: String,
+    },
+    EnableServer {
+        target: ClientTarget,
+        name: String,
+    },
+    /// Same arguments as `set_server_trust`
+    SetTrust {
+        name: String,
+        client: String,
+        #[serde(default)]
+        scope: Option<String>,
+        trust: ServerTrust,
+    },
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct OpResult {
+    pub index: usize,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Warnings of trust edits
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct BatchReport {
+    /// One result per operation that ran, in order
+    pub results: Vec<OpResult>,
+    pub applied: usize,
+    pub failed: usize,
+    /// Operations not run because an earlier one failed with `stop_on_error`
+    pub skipped: usize,
+    /// Id for `undo_batch`; `None` when nothing was written or the batch ran inside another
+    /// journaled operation
+    pub undo_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UndoEntry {
+    pub id: String,
+    pub label: String,
+    pub created_at: String,
+    pub files: Vec<FileSnapshot>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct UndoFile {
+    #[serde(default)]
+    entries: Vec<UndoEntry>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct UndoReport {
+    pub id: String,
+    pub files_restored: Vec<String>,
+    /// Files changed since the batch; left alone unless forced
+    pub conflicts: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+impl Operation {
+    /// The journaled step of a server operation; trust edits have none
+    pub(crate) fn server_step(&self) -> Option<(ClientTarget, RecordedOp)> {
+        let (target, op) = match self {
+            Operation::UpsertServer {
+                target,
+                name,
+                config,
+            } => (
+                target,
+                RecordedOp::UpsertServer {
+                    name: name.clone(),
+                    config: config.clone(),
+                },
+            ),
+            Operation::RemoveServer { target, name } => {
+                (target, RecordedOp::RemoveServer { name: name.clone() })
+            }
+            Operation::DisableServer { target, name } => {
+                (target, RecordedOp::DisableServer { name: name.clone() })
+            }
+            Operation::EnableServer { target, name } => {
+                (target, RecordedOp::EnableServer { name: name.clone() })
+            }
+            Operation::SetTrust { .. } => return None,
+        };
+        Some((target.clone(), op))
+    }
+
+    pub(crate) fn from_step(target: ClientTarget, op: RecordedOp) -> Self {
+        match op {
+            RecordedOp::UpsertServer { name, config } => Operation::UpsertServer {
+                target,
+                name,
+                config,
+            },
+            RecordedOp::RemoveServer { name } => Operation::RemoveServer { target, name },
+            RecordedOp::DisableServer { name } => Operation::DisableServer { target, name },
+            RecordedOp::EnableServer { name } => Operation::EnableServer { target, name },
+        }
+    }
+}
+
+/// Journal steps for the server operations of `ops`, in order
+pub(crate) fn planned_steps(ops: &[Operation]) -> Vec<RecordedStep> {
+    let recorded_at = Utc::now().to_rfc3339();
+    ops.iter()
+        .filter_map(Operation::server_step)
+        .map(|(target, op)| RecordedStep {
+            target,
+            op,
+            recorded_at: recorded_at.clone(),
+        })
+        .collect()
+}
+
+/// Files of an undo entry that no longer hold what the batch wrote
+pub(crate) fn changed_since(
+    files: &[FileSnapshot],
+    current: impl Fn(&Path) -> Option<String>,
+) -> Vec<PathBuf> {
+    files
+        .iter()
+        .filter(|f| current(&f.path).as_deref() != Some(f.after.as_str()))
+        .map(|f| f.path.clone())
+        .collect()
+}
+
+fn undo_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("batch_undo.json"))
+}
+
+fn read_undo() -> Result<UndoFile, String> {
+    let path = undo_path()?;
+    match fs::read_to_string(&path) {
+        Ok(content) => {
+            serde_json::from_str(&content).map_err(|e| format!("Failed to parse undo log: {}", e))
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(UndoFile::default()),
+        Err(e) => Err(format!("Failed to read undo log: {}", e)),
+    }
+}
+
+fn write_undo(file: &UndoFile) -> Result<(), String> {
+    let path = undo_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(file)
+        .map_err(|e| format!("Failed to serialize undo log: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write undo log: {}", e))
+}
+
+fn update_undo<T>(update: impl FnOnce(&mut Vec<UndoEntry>) -> T) -> Result<T, String> {
+    let _guard = LOCK
+        .lock()
+        .map_err(|e| format!("Failed to lock undo log: {}", e))?;
+    let mut file = read_undo()?;
+    let result = update(&mut file.entries);
+    write_undo(&file)?;
+    Ok(result)
+}
+
+fn keep_undo(label: &str, files: Vec<FileSnapshot>) -> Option<String> {
+    if files.is_empty() {
+        return None;
+    }
+    let entry = UndoEntry {
+        id: format!(
+            "{}-{}",
+            Utc::now().timestamp_millis(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ),
+        label: label.to_string(),
+        created_at: Utc::now().to_rfc3339(),
+        files,
+    };
+    let id = entry.id.clone();
+    let result = update_undo(|entries| {
+        entries.push(entry);
+        let excess = entries.len().saturating_sub(MAX_UNDO_ENTRIES);
+        entries.drain(..excess);
+    });
+    match result {
+        Ok(()) => Some(id),
+        Err(e) => {
+            println!("[Batch] Not keeping undo entry for '{}': {}", label, e);
+            None
+        }
+    }
+}
+
+async fn apply(op: &Operation, record: bool) -> Result<Vec<String>, String> {
+    if let Operation::SetTrust {
+        name,
+        client,
+        scope,
+        trust,
+    } = op
+    {
+        let change = server_trust::set_server_trust(
+            name.clone(),
+            client.clone(),
+            scope.clone(),
+            trust.clone(),
+        )
+        .await?;
+        return Ok(change.warnings);
+    }
+    let Some((target, step)) = op.server_step() else {
+        return Ok(Vec::new());
+    };
+    op_recorder::replay_step(&target, &step).await?;
+    if record {
+        op_recorder::record(target, step);
+    }
+    Ok(Vec::new())
+}
+
+/// Run `ops` as one journaled batch labelled `label`. With `record`, server operations are
+/// recorded like command-level mutations; replays pass `false`.
+pub(crate) async fn run_batch(
+    label: &str,
+    ops: &[Operation],
+    stop_on_error: bool,
+    record: bool,
+) -> BatchReport {
+    let mut report = BatchReport {
+        results: Vec::new(),
+        applied: 0,
+        failed: 0,
+        skipped: 0,
+        undo_id: None,
+    };
+    let batch = write_journal::begin_batch(label, planned_steps(ops));
+    batch
+        .run(async {
+            for (index, op) in ops.iter().enumerate() {
+                let result = apply(op, record).await;
+                if op.server_step().is_some() {
+                    batch.step_done();
+                }
+                match result {
+                    Ok(warnings) => {
+                        report.applied += 1;
+                        report.results.push(OpResult {
+                            index,
+                            ok: true,
+                            error: None,
+                            warnings,
+                        });
+                    }
+                    Err(message) => {
+                        println!(
+                            "[Batch] '{}' operation {} failed: {}",
+                            label, index, message
+                        );
+                        report.failed += 1;
+                        report.results.push(OpResult {
+                            index,
+                            ok: false,
+                            error: Some(message),
+                            warnings: Vec::new(),
+                        });
+                        if stop_on_error {
+                            report.skipped = ops.len() - index - 1;
+                            break;
+                        }
+                    }
+                }
+            }
+        })
+        .await;
+    report.undo_id = keep_undo(label, batch.files());
+    println!(
+        "[Batch] '{}': {} applied, {} failed, {} skipped",
+        label, report.applied, report.failed, report.skipped
+    );
+    report
+}
+
+/// Apply `ops` in order, reporting each one's outcome. With `stop_on_error`, the first
+/// failure ends the batch and the remaining operations are skipped.
+#[command]
+pub async fn execute_batch(
+    ops: Vec<Operation>,
+    stop_on_error: Option<bool>,
+    label: Option<String>,
+) -> Result<BatchReport, String> {
+    let label = label.unwrap_or_else(|| format!("Batch of {} operation(s)", ops.len()));
+    Ok(run_batch(&label, &ops, stop_on_error.unwrap_or(false), true).await)
+}
+
+/// Kept undo entries, oldest first
+pub(crate) fn undo_entries() -> Result<Vec<UndoEntry>, String> {
+    Ok(read_undo()?.entries)
+}
+
+#[command]
+pub async fn list_batch_undo() -> Result<Vec<UndoEntry>, String> {
+    undo_entries()
+}
+
+/// Put the files a batch wrote back as they were before it. Files changed since are reported
+/// as conflicts and left alone unless `force` is set.
+#[command]
+pub async fn undo_batch(id: String, force: Option<bool>) -> Result<UndoReport, String> {
+    let entry = read_undo()?
+        .entries
+        .into_iter()
+        .find(|e| e.id == id)
+        .ok_or_else(|| format!("No undo entry '{}'", id))?;
+    let mut report = UndoReport {
+        id: id.clone(),
+        files_restored: Vec::new(),
+        conflicts: changed_since(&entry.files, |path| fs::read_to_string(path).ok())
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect(),
+        errors: Vec::new(),
+    };
+    if !report.conflicts.is_empty() && !force.unwrap_or(false) {
+        return Ok(report);
+    }
+    for snapshot in entry.files.iter().rev() {
+        match write_journal::restore(snapshot, snapshot.before.as_deref()) {
+            Ok(()) => report
+                .files_restored
+                .push(snapshot.path.display().to_string()),
+            Err(e) => report.errors.push(e),
+        }
+    }
+    if report.errors.is_empty() {
+        update_undo(|entries| entries.retain(|e| e.id != id))?;
+    }
+    println!(
+        "[Batch] Undid '{}' ({} file(s), {} error(s))",
+        entry.label,
+        report.files_restored.len(),
+        report.errors.len()
+    );
+    Ok(report)
+}
@@ -0,0 +1,137 @@
+// Batch operation tests
+use crate::batch_ops::{
+    changed_since, planned_steps, run_batch, undo_batch, undo_entries, Operation,
+};
+use crate::client_target::ClientTarget;
+use crate::op_recorder::RecordedOp;
+use crate::write_journal::FileSnapshot;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+
+#[test]
+fn test_operation_parses_tagged_json() {
+    let ops: Vec<Operation> = serde_json::from_value(json!([
+        {
+            "op": "upsert_server",
+            "target": { "client": "cursor" },
+            "name": "fs",
+            "config": { "command": "npx" }
+        },
+        { "op": "remove_server", "target": { "client": "claude_code" }, "name": "old" },
+        { "op": "set_trust", "name": "fs", "client": "cline", "trust": { "tools": ["read"] } }
+    ]))
+    .unwrap();
+    assert_eq!(ops.len(), 3);
+    assert_eq!(
+        ops[1],
+        Operation::RemoveServer {
+            target: ClientTarget {
+                client: "claude_code".to_string(),
+                path: None,
+                working_dir: None,
+            },
+            name: "old".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_planned_steps_skip_trust_edits() {
+    let target = ClientTarget::client("cursor", None);
+    let ops = vec![
+        Operation::from_step(
+            target.clone(),
+            RecordedOp::DisableServer {
+                name: "fs".to_string(),
+            },
+        ),
+        Operation::SetTrust {
+            name: "fs".to_string(),
+            client: "cline".to_string(),
+            scope: None,
+            trust: Default::default(),
+        },
+        Operation::from_step(
+            target,
+            RecordedOp::EnableServer {
+                name: "git".to_string(),
+            },
+        ),
+    ];
+    let steps = planned_steps(&ops);
+    assert_eq!(
+        steps.iter().map(|s| s.op.name()).collect::<Vec<_>>(),
+        vec!["fs", "git"]
+    );
+    assert_eq!(steps[0].op.kind(), "disable");
+}
+
+#[test]
+fn test_changed_since() {
+    let files = vec![
+        FileSnapshot {
+            path: PathBuf::from("/a.json"),
+            before: None,
+            after: "{}".to_string(),
+        },
+        FileSnapshot {
+            path: PathBuf::from("/b.json"),
+            before: Some("{}".to_string()),
+            after: "{\"x\":1}".to_string(),
+        },
+    ];
+    let current = |path: &Path| (path == Path::new("/a.json")).then(|| "{}".to_string());
+    assert_eq!(
+        changed_since(&files, current),
+        vec![PathBuf::from("/b.json")]
+    );
+}
+
+#[tokio::test]
+async fn test_overlapping_batches_keep_their_own_files() {
+    let first_dir = tempfile::tempdir().unwrap();
+    let second_dir = tempfile::tempdir().unwrap();
+    let upsert = |dir: &Path, name: &str| {
+        vec![Operation::UpsertServer {
+            target: ClientTarget::client("cursor", dir.to_str()),
+            name: name.to_string(),
+            config: json!({ "command": "npx", "args": [name] }),
+        }]
+    };
+    let first_ops = upsert(first_dir.path(), "batch-overlap-first");
+    let second_ops = upsert(second_dir.path(), "batch-overlap-second");
+    let (first, second) = tokio::join!(
+        run_batch("Overlap test first", &first_ops, false, false),
+        run_batch("Overlap test second", &second_ops, false, false)
+    );
+    assert_eq!((first.applied, second.applied), (1, 1));
+    let first_id = first.undo_id.expect("first batch keeps an undo entry");
+    let second_id = second.undo_id.expect("second batch keeps an undo entry");
+
+    let entries = undo_entries().unwrap();
+    let files_of = |id: &str| -> Vec<PathBuf> {
+        entries
+            .iter()
+            .find(|e| e.id == id)
+            .unwrap()
+            .files
+            .iter()
+            .map(|f| f.path.clone())
+            .collect()
+    };
+    assert_eq!(
+        files_of(&first_id),
+        vec![first_dir.path().join(".cursor/mcp.json")]
+    );
+    assert_eq!(
+        files_of(&second_id),
+        vec![second_dir.path().join(".cursor/mcp.json")]
+    );
+
+    // Neither batch's undo sees the other's write as a conflict
+    for id in [first_id, second_id] {
+        let report = undo_batch(id, None).await.unwrap();
+        assert!(report.conflicts.is_empty());
+        assert!(report.errors.is_empty());
+    }
+}
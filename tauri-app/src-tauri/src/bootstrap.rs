@@ -0,0 +1,231 @@
+//! First-run check and install of the runtimes servers need
+//!
+//! Most servers are launched with `npx`, `uvx` or `docker`, and a missing runtime is the
+//! usual reason a first install fails. `bootstrap_check` reports which of node, uv and docker
+//! are found, with their versions and the package manager command that would install each
+//! missing one (winget, brew, or apt/dnf/pacman). `bootstrap_install` runs that command only
+//! when the user confirmed it, streaming its output as `bootstrap_progress` events, then
+//! checks the runtime again.
+
+use crate::event_bus::{self, AppEventKind, EventScope};
+use crate::{env_path, installer, platform_access};
+use serde::{Deserialize, Serialize};
+use std::process::Stdio;
+use tauri::{command, AppHandle, Runtime};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::Command;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RuntimeId {
+    Node,
+    Uv,
+    Docker,
+}
+
+pub(crate) const RUNTIMES: [RuntimeId; 3] = [RuntimeId::Node, RuntimeId::Uv, RuntimeId::Docker];
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RuntimeStatus {
+    pub runtime: RuntimeId,
+    pub found: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Command that installs it, shown for confirmation; `None` when found or unsupported
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub install_command: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct BootstrapStatus {
+    pub os: String,
+    pub package_manager: String,
+    pub runtimes: Vec<RuntimeStatus>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct BootstrapProgress {
+    pub runtime: RuntimeId,
+    /// "stdout" or "stderr"
+    pub stream: &'static str,
+    pub line: String,
+}
+
+impl RuntimeId {
+    /// Program run to check for it
+    fn program(self) -> &'static str {
+        match self {
+            RuntimeId::Node => "node",
+            RuntimeId::Uv => "uv",
+            RuntimeId::Docker => "docker",
+        }
+    }
+}
+
+fn words(command: &str) -> Vec<String> {
+    command.split(' ').map(|w| w.to_string()).collect()
+}
+
+/// Command installing `runtime` with `manager` on `os`
+pub(crate) fn install_plan(runtime: RuntimeId, os: &str, manager: &str) -> Option<Vec<String>> {
+    let command = match (os, manager, runtime) {
+        ("macos", "brew", RuntimeId::Node) => "brew install node",
+        ("macos", "brew", RuntimeId::Uv) => "brew install uv",
+        ("macos", "brew", RuntimeId::Docker) => "brew install --cask docker",
+        ("windows", "winget", runtime) => {
+            let id = match runtime {
+                RuntimeId::Node => "OpenJS.NodeJS.LTS",
+                RuntimeId::Uv => "astral-sh.uv",
+                RuntimeId::Docker => "Docker.DockerDesktop",
+            };
+            let mut command = words("winget install -e --accept-package-agreements --id");
+            command.push(id.to_string());
+            return Some(command);
+        }
+        // Distribution packages of uv are rare or old; use the official installer
+        ("linux", _, RuntimeId::Uv) => {
+            return Some(vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                "curl -LsSf https://astral.sh/uv/install.sh | sh".to_string(),
+            ]);
+        }
+        ("linux", "apt", RuntimeId::Node) => "sudo apt-get install -y nodejs npm",
+        ("linux", "apt", RuntimeId::Docker) => "sudo apt-get install -y docker.io",
+        ("linux", "dnf", RuntimeId::Node) => "sudo dnf install -y nodejs npm",
+        ("linux", "dnf", RuntimeId::Docker) => "sudo dnf install -y docker",
+        ("linux", "pacman", RuntimeId::Node) => "sudo pacman -S --noconfirm nodejs npm",
+        ("linux", "pacman", RuntimeId::Docker) => "sudo pacman -S --noconfirm docker",
+        _ => return None,
+    };
+    Some(words(command))
+}
+
+/// First line of `<program> --version`, e.g. "v20.11.0"; `None` when it doesn't run
+async fn version_of(runtime: RuntimeId) -> Option<String> {
+    let output = Command::new(platform_access::resolve_launcher(runtime.program()))
+        .arg("--version")
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .map(|line| line.trim().to_string())
+}
+
+async fn status_of(runtime: RuntimeId, manager: &str) -> RuntimeStatus {
+    let version = version_of(runtime).await;
+    let found = version.is_some();
+    RuntimeStatus {
+        runtime,
+        found,
+        version,
+        install_command: if found {
+            None
+        } else {
+            install_plan(runtime, std::env::consts::OS, manager)
+        },
+    }
+}
+
+async fn stream_lines<R: Runtime>(
+    app: &AppHandle<R>,
+    runtime: RuntimeId,
+    stream: &'static str,
+    reader: impl AsyncRead + Unpin,
+) {
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        let progress = BootstrapProgress {
+            runtime,
+            stream,
+            line,
+        };
+        event_bus::publish(
+            app,
+            AppEventKind::BootstrapProgress,
+            EventScope::none(),
+            &progress,
+        );
+    }
+}
+
+#[command]
+pub async fn bootstrap_check() -> Result<BootstrapStatus, String> {
+    let manager = installer::get_default_package_manager();
+    let mut runtimes = Vec::new();
+    for runtime in RUNTIMES {
+        runtimes.push(status_of(runtime, &manager).await);
+    }
+    Ok(BootstrapStatus {
+        os: std::env::consts::OS.to_string(),
+        package_manager: manager,
+        runtimes,
+    })
+}
+
+/// Install `runtime` with the command `bootstrap_check` showed. `confirmed` must be set, as
+/// the command may ask for elevated rights.
+#[command]
+pub async fn bootstrap_install<R: Runtime>(
+    app: AppHandle<R>,
+    runtime: RuntimeId,
+    confirmed: bool,
+) -> Result<RuntimeStatus, String> {
+    if !confirmed {
+        return Err("Installing a runtime needs the user's confirmation".to_string());
+    }
+    let manager = installer::get_default_package_manager();
+    let plan = install_plan(runtime, std::env::consts::OS, &manager).ok_or_else(|| {
+        format!(
+            "Can't install {} with {} here; install it manually",
+            runtime.program(),
+            manager
+        )
+    })?;
+    println!("[Bootstrap] Running: {}", plan.join(" "));
+    let mut child = Command::new(&plan[0])
+        .args(&plan[1..])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to start {}: {}", plan[0], e))?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or("Failed to capture installer output")?;
+    let stderr = child
+        .stderr
+        .take()
+        .ok_or("Failed to capture installer output")?;
+    tokio::join!(
+        stream_lines(&app, runtime, "stdout", stdout),
+        stream_lines(&app, runtime, "stderr", stderr),
+    );
+    let exit = child
+        .wait()
+        .await
+        .map_err(|e| format!("Failed to wait for the installer: {}", e))?;
+    if !exit.success() {
+        return Err(format!(
+            "Installing {} failed ({})",
+            runtime.program(),
+            exit
+        ));
+    }
+    // Installers put binaries in ~/.local/bin or the Homebrew prefix
+    env_path::update_env_path();
+    let status = status_of(runtime, &manager).await;
+    println!(
+        "[Bootstrap] {} installed: {}",
+        runtime.program(),
+        status.version.as_deref().unwrap_or("not found on PATH yet")
+    );
+    Ok(status)
+}
@@ -0,0 +1,34 @@
+// Runtime bootstrap tests
+use crate::bootstrap::{install_plan, RuntimeId, RUNTIMES};
+
+#[test]
+fn test_install_plan_per_platform() {
+    assert_eq!(
+        install_plan(RuntimeId::Docker, "macos", "brew").unwrap(),
+        vec!["brew", "install", "--cask", "docker"]
+    );
+    assert_eq!(
+        install_plan(RuntimeId::Node, "windows", "winget")
+            .unwrap()
+            .last()
+            .unwrap(),
+        "OpenJS.NodeJS.LTS"
+    );
+    assert_eq!(
+        install_plan(RuntimeId::Node, "linux", "apt").unwrap()[..2],
+        ["sudo", "apt-get"]
+    );
+    // uv comes from its installer whatever the distribution
+    assert_eq!(
+        install_plan(RuntimeId::Uv, "linux", "pacman").unwrap()[0],
+        "sh"
+    );
+}
+
+#[test]
+fn test_install_plan_unsupported_manager() {
+    for runtime in RUNTIMES {
+        assert_eq!(install_plan(runtime, "macos", "port"), None);
+        assert_eq!(install_plan(runtime, "freebsd", "pkg"), None);
+    }
+}
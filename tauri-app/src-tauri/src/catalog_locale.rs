@@ -0,0 +1,96 @@
+//! Localized names and descriptions of registry entries
+//!
+//! Registry entries are written in English. A publisher can add translations in the entry's
+//! `_meta`, under the `io.mcp-linker/localizations` key, by locale tag:
+//! `{ "de": { "title": "...", "description": "..." }, "pt-BR": { ... } }`. Browsed entries
+//! show the translation negotiated for the `locale` setting: the exact tag, then another tag
+//! of the same language ("de-AT" takes "de", "pt" takes "pt-BR"). Without one, a field keeps
+//! its English text. Only the displayed fields are translated; imported configs don't change.
+
+use crate::registry_import::RegistryServer;
+use crate::settings::load_settings;
+use serde_json::Value;
+
+pub(crate) const LOCALIZATIONS_KEY: &str = "io.mcp-linker/localizations";
+
+const DEFAULT_LOCALE: &str = "en";
+
+/// "pt_BR" and "pt-br" as "pt-BR"
+fn canonical(tag: &str) -> String {
+    let mut parts = tag.trim().split(['-', '_']);
+    let language = parts.next().unwrap_or_default().to_ascii_lowercase();
+    parts.fold(language, |tag, part| {
+        let part = if part.len() == 2 {
+            part.to_ascii_uppercase()
+        } else {
+            part.to_string()
+        };
+        format!("{}-{}", tag, part)
+    })
+}
+
+fn language(tag: &str) -> &str {
+    tag.split('-').next().unwrap_or(tag)
+}
+
+/// The locale of the settings, English when unset
+pub(crate) fn app_locale() -> String {
+    load_settings()
+        .locale
+        .filter(|locale| !locale.trim().is_empty())
+        .map(|locale| canonical(&locale))
+        .unwrap_or_else(|| DEFAULT_LOCALE.to_string())
+}
+
+/// The tag of `available` that best serves `wanted`, `None` when no tag shares its language
+pub(crate) fn negotiate<'a>(wanted: &str, available: &[&'a str]) -> Option<&'a str> {
+    let wanted = canonical(wanted);
+    available
+        .iter()
+        .find(|tag| canonical(tag) == wanted)
+        .or_else(|| {
+            available
+                .iter()
+                .find(|tag| canonical(tag) == language(&wanted))
+        })
+        .or_else(|| {
+            available
+                .iter()
+                .find(|tag| language(&canonical(tag)) == language(&wanted))
+        })
+        .copied()
+}
+
+/// `server` with the title and description of `entry` in `locale`, where translated
+pub(crate) fn localize(mut server: RegistryServer, entry: &Value, locale: &str) -> RegistryServer {
+    let inner = entry.get("server").unwrap_or(entry);
+    let Some(translations) = [inner, entry]
+        .iter()
+        .find_map(|e| {
+            e.pointer("/_meta")
+                .and_then(|meta| meta.get(LOCALIZATIONS_KEY))
+        })
+        .and_then(Value::as_object)
+    else {
+        return server;
+    };
+    let tags: Vec<&str> = translations.keys().map(String::as_str).collect();
+    let Some(tag) = negotiate(locale, &tags) else {
+        return server;
+    };
+    let translation = &translations[tag];
+    let text = |field: &str| {
+        translation[field]
+            .as_str()
+            .filter(|text| !text.trim().is_empty())
+            .map(str::to_string)
+    };
+    let title = text("title");
+    let description = text("description");
+    if title.is_some() || description.is_some() {
+        server.title = title.or(server.title);
+        server.description = description.or(server.description);
+        server.locale = Some(tag.to_string());
+    }
+    server
+}
@@ -0,0 +1,54 @@
+// Catalog locale tests
+use crate::catalog_locale::{localize, negotiate, LOCALIZATIONS_KEY};
+use crate::registry_import::convert_entry;
+use serde_json::json;
+
+#[test]
+fn test_negotiate_falls_back_to_the_language() {
+    let available = ["de", "pt-BR", "zh-Hant-TW"];
+    assert_eq!(negotiate("de-AT", &available), Some("de"));
+    assert_eq!(negotiate("pt_br", &available), Some("pt-BR"));
+    assert_eq!(negotiate("pt", &available), Some("pt-BR"));
+    assert_eq!(negotiate("fr", &available), None);
+}
+
+#[test]
+fn test_localize_keeps_english_when_untranslated() {
+    let entry = json!({
+        "server": {
+            "name": "io.github.x/weather",
+            "title": "Weather",
+            "description": "Forecasts for any city",
+            "remotes": [{ "type": "streamable-http", "url": "https://x/mcp" }],
+            "_meta": {
+                LOCALIZATIONS_KEY: {
+                    "de": { "description": "Wettervorhersagen für jede Stadt" },
+                    "ja": { "title": "天気", "description": "" }
+                }
+            }
+        }
+    });
+    let server = || convert_entry(&entry).unwrap();
+
+    let german = localize(server(), &entry, "de-DE");
+    assert_eq!(german.title.as_deref(), Some("Weather"));
+    assert_eq!(
+        german.description.as_deref(),
+        Some("Wettervorhersagen für jede Stadt")
+    );
+    assert_eq!(german.locale.as_deref(), Some("de"));
+
+    let japanese = localize(server(), &entry, "ja");
+    assert_eq!(japanese.title.as_deref(), Some("天気"));
+    assert_eq!(
+        japanese.description.as_deref(),
+        Some("Forecasts for any city")
+    );
+
+    let english = localize(server(), &entry, "en");
+    assert_eq!(english.locale, None);
+    assert_eq!(
+        english.description.as_deref(),
+        Some("Forecasts for any city")
+    );
+}
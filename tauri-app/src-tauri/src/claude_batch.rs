@@ -0,0 +1,214 @@
+//! Several Claude Code server changes written as one
+//!
+//! `claude_mcp_batch_apply` applies adds, updates and removals in order to one in-memory copy
+//! of ~/.claude.json and writes it once, with one backup and one integrity check covering every
+//! server touched. Any operation that fails (a taken name, a missing server, a lock, an invalid
+//! patch) fails the batch before anything is written; a failed write or integrity check
+//! restores the backup. Unlike `execute_batch`, which runs each operation as its own write
+//! across any clients, nothing is ever applied halfway.
+
+use crate::claude_code_commands::{
+    self, apply_patch, is_global_config, scope_servers_mut, server_to_json, ClaudeCodeServer,
+    ClaudeCodeServerPatch,
+};
+use crate::client_target::ClientTarget;
+use crate::op_recorder::{self, RecordedOp};
+use crate::{json_pointer, quarantine, server_locks, server_name, unicode_path};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs;
+use tauri::command;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum ClaudeBatchOp {
+    /// Fails when the name is taken, unless `overwrite` is set
+    Add {
+        server: ClaudeCodeServer,
+        #[serde(default)]
+        overwrite: bool,
+        /// Replace an invalid name by its suggestion instead of failing
+        #[serde(default)]
+        normalize_name: bool,
+    },
+    Update {
+        name: String,
+        patch: ClaudeCodeServerPatch,
+    },
+    Remove {
+        name: String,
+    },
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ClaudeBatchReport {
+    pub success: bool,
+    pub message: String,
+    /// Servers written or removed, in the order of the operations, as named in the config
+    pub servers: Vec<String>,
+    /// Non-fatal problems, e.g. names that differ from others only by case
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl ClaudeBatchOp {
+    pub(crate) fn name(&self) -> &str {
+        match self {
+            ClaudeBatchOp::Add { server, .. } => &server.name,
+            ClaudeBatchOp::Update { name, .. } | ClaudeBatchOp::Remove { name } => name,
+        }
+    }
+}
+
+fn scope_pointer(working_dir: &str) -> String {
+    if is_global_config(working_dir) {
+        json_pointer::join(&["mcpServers"])
+    } else {
+        json_pointer::join(&["projects", working_dir, "mcpServers"])
+    }
+}
+
+/// Apply `ops` in order to `config`, the scope of `working_dir` being resolved already.
+/// Returns the recorded step of each operation and the warnings; the first failing operation
+/// fails them all.
+pub(crate) fn apply_ops(
+    config: &mut Value,
+    working_dir: &str,
+    ops: &[ClaudeBatchOp],
+) -> Result<(Vec<RecordedOp>, Vec<String>), String> {
+    let scope = if is_global_config(working_dir) {
+        "user"
+    } else {
+        "project"
+    };
+    let mut steps = Vec::new();
+    let mut warnings = Vec::new();
+    for (index, op) in ops.iter().enumerate() {
+        let fail = |e: String| format!("Operation {} ('{}') failed: {}", index + 1, op.name(), e);
+        let existing = config
+            .pointer(&scope_pointer(working_dir))
+            .and_then(Value::as_object)
+            .map(|servers| servers.keys().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        match op {
+            ClaudeBatchOp::Add {
+                server,
+                overwrite,
+                normalize_name,
+            } => {
+                let (name, name_warnings) =
+                    server_name::accept_server_name(&server.name, &existing, *normalize_name)
+                        .map_err(fail)?;
+                if existing.contains(&name) && !overwrite {
+                    return Err(fail(format!("Server already exists in {} config", scope)));
+                }
+                let entry = server_to_json(server).map_err(fail)?;
+                scope_servers_mut(config, working_dir).insert(name.clone(), entry.clone());
+                warnings.extend(
+                    name_warnings
+                        .into_iter()
+                        .map(|w| format!("{}: {}", name, w)),
+                );
+                steps.push(RecordedOp::UpsertServer {
+                    name,
+                    config: entry,
+                });
+            }
+            ClaudeBatchOp::Update { name, patch } => {
+                let entry = config
+                    .pointer_mut(&json_pointer::claude_server_pointer(working_dir, name))
+                    .ok_or_else(|| fail(format!("Server not found in {} config", scope)))?;
+                let patched = apply_patch(entry, patch).map_err(fail)?;
+                *entry = patched.clone();
+                steps.push(RecordedOp::UpsertServer {
+                    name: name.clone(),
+                    config: patched,
+                });
+            }
+            ClaudeBatchOp::Remove { name } => {
+                config
+                    .pointer_mut(&scope_pointer(working_dir))
+                    .and_then(Value::as_object_mut)
+                    .and_then(|servers| servers.shift_remove(name))
+                    .ok_or_else(|| fail(format!("Server not found in {} config", scope)))?;
+                steps.push(RecordedOp::RemoveServer { name: name.clone() });
+            }
+        }
+    }
+    Ok((steps, warnings))
+}
+
+/// Apply adds, updates and removals to a Claude Code scope ("Global" or a project) in one
+/// write; when any of them fails, nothing is written
+#[command]
+pub async fn claude_mcp_batch_apply(
+    working_dir: String,
+    ops: Vec<ClaudeBatchOp>,
+) -> Result<ClaudeBatchReport, String> {
+    if ops.is_empty() {
+        return Err("No operations to apply".to_string());
+    }
+    let target = ClientTarget::claude_code(&working_dir);
+    for op in &ops {
+        server_locks::ensure_unlocked(&target, op.name())?;
+        if matches!(op, ClaudeBatchOp::Add { .. }) {
+            quarantine::ensure_not_quarantined(&target, op.name(), "adding")?;
+        }
+    }
+
+    let claude_config_path = claude_code_commands::get_claude_config_path(None).await?;
+    let config_exists = claude_config_path.exists();
+    let mut config: Value = if config_exists {
+        let content = fs::read_to_string(&claude_config_path)
+            .map_err(|e| format!("Failed to read Claude config: {}", e))?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse Claude config: {}", e))?
+    } else {
+        json!({})
+    };
+    let before = config_exists.then(|| config.clone());
+    let working_dir = if is_global_config(&working_dir) {
+        working_dir
+    } else {
+        unicode_path::resolve_project_key(&config, &working_dir)
+    };
+
+    let (steps, warnings) = apply_ops(&mut config, &working_dir, &ops)?;
+    // Added names may have been normalized, so the steps hold the names actually written
+    let servers: Vec<String> = steps.iter().map(|step| step.name().to_string()).collect();
+    let intended: Vec<String> = servers
+        .iter()
+        .map(|name| json_pointer::claude_server_pointer(&working_dir, name))
+        .collect();
+    let backup = if config_exists {
+        Some(claude_code_commands::create_backup(&claude_config_path)?)
+    } else {
+        None
+    };
+    claude_code_commands::write_claude_config_verified(
+        &claude_config_path,
+        before.as_ref(),
+        &config,
+        &intended,
+        backup.as_ref(),
+    )?;
+    if let Some(backup) = backup {
+        backup.discard();
+    }
+
+    let target = ClientTarget::claude_code(&working_dir);
+    for step in steps {
+        op_recorder::record(target.clone(), step);
+    }
+    println!(
+        "[Batch] Applied {} operation(s) to Claude Code ({})",
+        ops.len(),
+        working_dir
+    );
+    Ok(ClaudeBatchReport {
+        success: true,
+        message: format!("Applied {} operation(s) in one write", ops.len()),
+        servers,
+        warnings,
+    })
+}
@@ -0,0 +1,56 @@
+// Claude Code batch tests
+use crate::claude_batch::{apply_ops, ClaudeBatchOp};
+use serde_json::json;
+
+fn ops(value: serde_json::Value) -> Vec<ClaudeBatchOp> {
+    serde_json::from_value(value).unwrap()
+}
+
+#[test]
+fn test_apply_ops_in_one_config() {
+    let mut config = json!({
+        "mcpServers": {
+            "old": { "type": "stdio", "command": "old-server" },
+            "fetch": { "type": "stdio", "command": "uvx", "args": ["mcp-server-fetch"], "note": "kept" }
+        }
+    });
+    let batch = ops(json!([
+        { "op": "add", "server": { "name": "sentry", "type": "http", "url": "https://mcp.sentry.dev/mcp", "command": null, "args": null, "env": null } },
+        { "op": "update", "name": "fetch", "patch": { "args": ["mcp-server-fetch", "--ignore-robots-txt"] } },
+        { "op": "remove", "name": "old" }
+    ]));
+    let (steps, _) = apply_ops(&mut config, "Global", &batch).unwrap();
+    assert_eq!(steps.len(), 3);
+    let servers = config["mcpServers"].as_object().unwrap();
+    assert_eq!(servers.keys().collect::<Vec<_>>(), ["fetch", "sentry"]);
+    assert_eq!(servers["fetch"]["note"], "kept");
+    assert_eq!(servers["fetch"]["args"][1], "--ignore-robots-txt");
+}
+
+#[test]
+fn test_apply_ops_fails_as_a_whole() {
+    let original = json!({ "projects": { "/p": { "mcpServers": { "a": { "type": "stdio", "command": "a" } } } } });
+    let mut config = original.clone();
+    let batch = ops(json!([
+        { "op": "remove", "name": "a" },
+        { "op": "add", "server": { "name": "a", "type": "stdio", "command": "b", "url": null, "args": null, "env": null } },
+        { "op": "add", "server": { "name": "a", "type": "stdio", "command": "c", "url": null, "args": null, "env": null } }
+    ]));
+    let error = apply_ops(&mut config, "/p", &batch).unwrap_err();
+    assert!(error.starts_with("Operation 3 ('a') failed"), "{}", error);
+
+    let missing = ops(json!([{ "op": "update", "name": "nope", "patch": {} }]));
+    assert!(apply_ops(&mut original.clone(), "/p", &missing).is_err());
+}
+
+#[test]
+fn test_steps_use_normalized_names() {
+    let mut config = json!({ "mcpServers": {} });
+    let batch = ops(json!([
+        { "op": "add", "normalize_name": true, "server": { "name": "my server", "type": "stdio", "command": "a", "url": null, "args": null, "env": null } }
+    ]));
+    let (steps, warnings) = apply_ops(&mut config, "Global", &batch).unwrap();
+    assert_eq!(steps[0].name(), "my-server");
+    assert!(config["mcpServers"].get("my-server").is_some());
+    assert!(warnings[0].contains("was normalized to 'my-server'"));
+}
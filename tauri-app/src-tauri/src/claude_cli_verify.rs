@@ -0,0 +1,263 @@
+//! Checks a Claude Code write against what the `claude` CLI itself resolves
+//!
+//! A server written to the wrong project key or in a shape the CLI doesn't read looks fine in
+//! the app and only fails in the user's next Claude session. With `verify_with_claude_cli` set,
+//! every add and remove is followed by `claude mcp get <name>`, run in the project dir (or the
+//! home dir for "Global"), and the fields it prints are compared with what was written. The
+//! result is published as a `cli_verification` event; `verify_claude_server` runs the same
+//! check on demand.
+
+use crate::claude_code_commands::{self, is_global_config};
+use crate::event_bus::{self, AppEventKind, EventScope};
+use crate::{demo_mode, platform_access, settings};
+use serde::Serialize;
+use serde_json::Value;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+use tauri::{command, AppHandle, Runtime};
+use tokio::process::Command;
+
+/// `claude mcp get` health-checks the server, which may start a package download
+const CLI_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A server as printed by `claude mcp get`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CliView {
+    /// e.g. "Local config (private to you in this project)"
+    pub scope: String,
+    pub kind: String,
+    pub command: Option<String>,
+    /// Space-joined, as the CLI prints them
+    pub args: String,
+    pub url: Option<String>,
+    pub env_keys: Vec<String>,
+    pub header_keys: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CliVerification {
+    pub name: String,
+    pub working_dir: String,
+    /// False when the CLI couldn't be run; `discrepancies` is empty then
+    pub checked: bool,
+    pub discrepancies: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Parse `claude mcp get` output; `None` when it doesn't describe a server
+pub(crate) fn parse_mcp_get(output: &str) -> Option<CliView> {
+    let mut view = CliView::default();
+    let mut section = "";
+    for line in output.lines() {
+        let indented = line.starts_with("    ");
+        let line = line.trim();
+        if indented && !section.is_empty() {
+            let (separator, keys) = if section == "Environment" {
+                ('=', &mut view.env_keys)
+            } else {
+                (':', &mut view.header_keys)
+            };
+            if let Some((key, _)) = line.split_once(separator) {
+                keys.push(key.trim().to_string());
+            }
+            continue;
+        }
+        section = "";
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim().to_string();
+        match key {
+            "Scope" => view.scope = value,
+            "Type" => view.kind = value,
+            "Command" => view.command = Some(value),
+            "Args" => view.args = value,
+            "URL" => view.url = Some(value),
+            "Environment" => section = "Environment",
+            "Headers" => section = "Headers",
+            _ => {}
+        }
+    }
+    (!view.kind.is_empty()).then_some(view)
+}
+
+fn sorted_keys(object: &Value) -> Vec<String> {
+    let mut keys: Vec<String> = object
+        .as_object()
+        .map(|map| map.keys().cloned().collect())
+        .unwrap_or_default();
+    keys.sort();
+    keys
+}
+
+/// Differences between what was written (`None` after a remove) and what the CLI resolves
+pub(crate) fn compare(
+    written: Option<&Value>,
+    seen: Option<&CliView>,
+    user_scope: bool,
+) -> Vec<String> {
+    let (written, seen) = match (written, seen) {
+        (None, None) => return Vec::new(),
+        (None, Some(seen)) => {
+            return vec![format!("CLI still resolves the server from {}", seen.scope)]
+        }
+        (Some(_), None) => return vec!["CLI doesn't see the server".to_string()],
+        (Some(written), Some(seen)) => (written, seen),
+    };
+    let mut discrepancies = Vec::new();
+    let expected_scope = if user_scope { "User" } else { "Local" };
+    if !seen.scope.starts_with(expected_scope) {
+        discrepancies.push(format!(
+            "CLI resolves the server from {}, not the {} scope it was written to",
+            seen.scope,
+            expected_scope.to_lowercase()
+        ));
+    }
+    let kind = written["type"].as_str().unwrap_or("stdio");
+    if seen.kind != kind {
+        discrepancies.push(format!("type: wrote {}, CLI sees {}", kind, seen.kind));
+    }
+    let command = written["command"].as_str();
+    if command.is_some() && seen.command.as_deref() != command {
+        discrepancies.push(format!(
+            "command: wrote {}, CLI sees {}",
+            command.unwrap_or_default(),
+            seen.command.as_deref().unwrap_or("none")
+        ));
+    }
+    let args: Vec<&str> = written["args"]
+        .as_array()
+        .map(|args| args.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    if args.join(" ") != seen.args {
+        discrepancies.push(format!(
+            "args: wrote \"{}\", CLI sees \"{}\"",
+            args.join(" "),
+            seen.args
+        ));
+    }
+    let url = written["url"].as_str();
+    if url.is_some() && seen.url.as_deref() != url {
+        discrepancies.push(format!(
+            "url: wrote {}, CLI sees {}",
+            url.unwrap_or_default(),
+            seen.url.as_deref().unwrap_or("none")
+        ));
+    }
+    for (field, written_keys, seen_keys) in [
+        ("env", sorted_keys(&written["env"]), &seen.env_keys),
+        (
+            "headers",
+            sorted_keys(&written["headers"]),
+            &seen.header_keys,
+        ),
+    ] {
+        let mut seen_keys = seen_keys.clone();
+        seen_keys.sort();
+        if written_keys != seen_keys {
+            discrepancies.push(format!(
+                "{}: wrote keys [{}], CLI sees [{}]",
+                field,
+                written_keys.join(", "),
+                seen_keys.join(", ")
+            ));
+        }
+    }
+    discrepancies
+}
+
+/// `claude mcp get <name>` as seen from `working_dir`; `Ok(None)` when the CLI finds no server
+async fn cli_view(name: &str, working_dir: &str) -> Result<Option<CliView>, String> {
+    let dir = if is_global_config(working_dir) {
+        dirs::home_dir().ok_or("Unable to find home directory")?
+    } else {
+        PathBuf::from(working_dir)
+    };
+    let run = Command::new(platform_access::resolve_launcher("claude"))
+        .args(["mcp", "get", name])
+        .current_dir(&dir)
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    let output = tokio::time::timeout(CLI_TIMEOUT, run)
+        .await
+        .map_err(|_| "claude mcp get timed out".to_string())?
+        .map_err(|e| format!("Failed to run claude: {}", e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if output.status.success() {
+        return parse_mcp_get(&stdout)
+            .map(Some)
+            .ok_or_else(|| "Unexpected claude mcp get output".to_string());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if format!("{}{}", stdout, stderr).contains("No MCP server found") {
+        return Ok(None);
+    }
+    Err(format!("claude mcp get failed: {}", stderr.trim()))
+}
+
+/// Compare the server as the CLI sees it with `written` (`None` after a remove)
+pub(crate) async fn verify(
+    name: &str,
+    working_dir: &str,
+    written: Option<&Value>,
+) -> CliVerification {
+    let seen = cli_view(name, working_dir).await;
+    let (checked, discrepancies, error) = match seen {
+        Ok(seen) => (
+            true,
+            compare(written, seen.as_ref(), is_global_config(working_dir)),
+            None,
+        ),
+        Err(e) => (false, Vec::new(), Some(e)),
+    };
+    CliVerification {
+        name: name.to_string(),
+        working_dir: working_dir.to_string(),
+        checked,
+        discrepancies,
+        error,
+    }
+}
+
+/// After an add or remove: verify it in the background when `verify_with_claude_cli` is set
+pub(crate) fn spawn_verify<R: Runtime>(
+    app: &AppHandle<R>,
+    name: String,
+    working_dir: String,
+    written: Option<Value>,
+) {
+    // The CLI reads the real ~/.claude.json, not the demo sandbox
+    if !settings::load_settings().verify_with_claude_cli || demo_mode::is_active() {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let report = verify(&name, &working_dir, written.as_ref()).await;
+        let outcome = match (&report.error, report.discrepancies.len()) {
+            (Some(e), _) => e.clone(),
+            (None, 0) => "matches".to_string(),
+            (None, n) => format!("{} discrepancies", n),
+        };
+        println!("[ClaudeCli] Verified {}: {}", report.name, outcome);
+        event_bus::publish(
+            &app,
+            AppEventKind::CliVerification,
+            EventScope::client("claude_code"),
+            &report,
+        );
+    });
+}
+
+/// Check that the CLI resolves `name` in `working_dir` exactly as it is written in
+/// ~/.claude.json
+#[command]
+pub async fn verify_claude_server(
+    name: String,
+    working_dir: String,
+) -> Result<CliVerification, String> {
+    let written = claude_code_commands::written_config(&name, &working_dir).await?;
+    Ok(verify(&name, &working_dir, written.as_ref()).await)
+}
@@ -0,0 +1,66 @@
+// Claude CLI verification tests
+use crate::claude_cli_verify::{compare, parse_mcp_get, CliView};
+use serde_json::json;
+
+const GET_OUTPUT: &str = "fs:
+  Scope: Local config (private to you in this project)
+  Status: ✓ Connected
+  Type: stdio
+  Command: npx
+  Args: -y @modelcontextprotocol/server-filesystem /tmp
+  Environment:
+    DEBUG=1
+    TOKEN=abc
+
+To remove this server, run: claude mcp remove \"fs\" -s local
+";
+
+#[test]
+fn test_parse_mcp_get() {
+    let view = parse_mcp_get(GET_OUTPUT).unwrap();
+    assert_eq!(view.scope, "Local config (private to you in this project)");
+    assert_eq!(view.kind, "stdio");
+    assert_eq!(view.command.as_deref(), Some("npx"));
+    assert_eq!(view.args, "-y @modelcontextprotocol/server-filesystem /tmp");
+    assert_eq!(view.env_keys, vec!["DEBUG", "TOKEN"]);
+    assert!(view.header_keys.is_empty());
+    assert_eq!(parse_mcp_get("No MCP server found with name: fs"), None);
+}
+
+#[test]
+fn test_compare_matching_write() {
+    let seen = parse_mcp_get(GET_OUTPUT).unwrap();
+    let written = json!({
+        "type": "stdio",
+        "command": "npx",
+        "args": ["-y", "@modelcontextprotocol/server-filesystem", "/tmp"],
+        "env": { "TOKEN": "abc", "DEBUG": "1" }
+    });
+    assert!(compare(Some(&written), Some(&seen), false).is_empty());
+    assert_eq!(compare(None, None, false), Vec::<String>::new());
+}
+
+#[test]
+fn test_compare_reports_discrepancies() {
+    let seen = CliView {
+        scope: "Project config (shared via .mcp.json)".to_string(),
+        kind: "http".to_string(),
+        url: Some("https://old.example.com/mcp".to_string()),
+        header_keys: vec!["Authorization".to_string()],
+        ..Default::default()
+    };
+    let written = json!({ "type": "http", "url": "https://example.com/mcp" });
+    let discrepancies = compare(Some(&written), Some(&seen), true);
+    assert_eq!(discrepancies.len(), 3);
+    assert!(discrepancies[0].contains("not the user scope"));
+    assert!(discrepancies[1].starts_with("url:"));
+    assert!(discrepancies[2].starts_with("headers:"));
+    assert_eq!(
+        compare(None, Some(&seen), true),
+        vec!["CLI still resolves the server from Project config (shared via .mcp.json)"]
+    );
+    assert_eq!(
+        compare(Some(&written), None, true),
+        vec!["CLI doesn't see the server"]
+    );
+}
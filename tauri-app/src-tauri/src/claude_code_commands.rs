@@ -1,10 +1,19 @@
+use crate::client_target::ClientTarget;
+use crate::config_backups::{self, ConfigBackup};
+use crate::credential_expiry::{self, CredentialStatus};
+use crate::op_recorder::{self, RecordedOp};
+use crate::server_metadata;
+use crate::settings::{self, ConflictPolicy};
+use crate::{
+    claude_cli_verify, claude_integrity, claude_location, claude_scan, json_pointer, metrics,
+    quarantine, server_locks, server_name, unicode_path, write_journal,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::time::{SystemTime, UNIX_EPOCH};
-use tauri::command;
+use tauri::{command, AppHandle, Runtime};
 
 /// Special identifier for global MCP config (applies to all projects)
 pub const GLOBAL_PROJECT_ID: &str = "Global";
@@ -24,12 +33,50 @@ pub struct ClaudeCodeServer {
     pub command: Option<String>,
     pub args: Option<Vec<String>>,
     pub env: Option<HashMap<String, String>>,
+    /// HTTP headers for remote servers
+    #[serde(default)]
+    pub headers: Option<HashMap<String, String>>,
+    /// Set in list responses when an attached auth profile is expiring or expired
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credential_status: Option<CredentialStatus>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ClaudeCodeResponse {
     pub success: bool,
     pub message: String,
+    /// Name the server was written under (differs from the request when auto-suffixed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<AddOutcome>,
+    /// Non-fatal problems, e.g. a name that differs from another only by case
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Fields `claude_mcp_update` changes; the ones left out keep their current value
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ClaudeCodeServerPatch {
+    pub r#type: Option<String>,
+    pub url: Option<String>,
+    pub command: Option<String>,
+    pub args: Option<Vec<String>>,
+    pub env: Option<HashMap<String, String>>,
+    pub headers: Option<HashMap<String, String>>,
+}
+
+/// How `add_claude_server` resolved the requested name, or what `update_claude_server` did
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AddOutcome {
+    Added,
+    Overwritten,
+    Renamed,
+    /// A patch changed the entry
+    Updated,
+    /// A patch left the entry as it was, so nothing was written
+    Unchanged,
 }
 
 /// List all MCP servers configured in Claude Code
@@ -38,17 +85,12 @@ pub struct ClaudeCodeResponse {
 #[command]
 pub async fn claude_mcp_list(working_dir: String) -> Result<Vec<ClaudeCodeServer>, String> {
     let mut servers = Vec::new();
-    let claude_config_path = get_claude_config_path(None)?;
-
-    if !claude_config_path.exists() {
-        return Ok(Vec::new());
-    }
-
-    let config_content = fs::read_to_string(&claude_config_path)
-        .map_err(|e| format!("Failed to read Claude config: {}", e))?;
+    let claude_config_path = get_claude_config_path(None).await?;
 
-    let config: serde_json::Value = serde_json::from_str(&config_content)
-        .map_err(|e| format!("Failed to parse Claude config: {}", e))?;
+    let config = match claude_scan::load_json_cached("claude_code", &claude_config_path).await? {
+        Some(config) => config,
+        None => return Ok(Vec::new()),
+    };
 
     if is_global_config(&working_dir) {
         // Read from root-level mcpServers (user-scope config)
@@ -63,8 +105,9 @@ pub async fn claude_mcp_list(working_dir: String) -> Result<Vec<ClaudeCodeServer
         }
     } else {
         // Read from per-project config (local-scope)
+        let project_key = unicode_path::resolve_project_key(&config, &working_dir);
         if let Some(projects) = config.get("projects") {
-            if let Some(project_config) = projects.get(&working_dir) {
+            if let Some(project_config) = projects.get(&project_key) {
                 if let Some(mcp_servers) = project_config.get("mcpServers") {
                     if let Some(servers_obj) = mcp_servers.as_object() {
                         for (name, server_config) in servers_obj {
@@ -78,6 +121,14 @@ pub async fn claude_mcp_list(working_dir: String) -> Result<Vec<ClaudeCodeServer
         }
     }
 
+    let statuses = credential_expiry::statuses_for_client("claude_code");
+    if !statuses.is_empty() {
+        for server in &mut servers {
+            let key = (Some(working_dir.clone()), server.name.clone());
+            server.credential_status = statuses.get(&key).copied();
+        }
+    }
+
     Ok(servers)
 }
 
@@ -92,26 +143,74 @@ pub async fn claude_mcp_get(name: String, working_dir: String) -> Result<ClaudeC
         .ok_or_else(|| format!("Server '{}' not found", name))
 }
 
+/// A server as written in ~/.claude.json, in the shape `add_claude_server` writes it;
+/// `None` when it isn't there
+pub(crate) async fn written_config(
+    name: &str,
+    working_dir: &str,
+) -> Result<Option<serde_json::Value>, String> {
+    let servers = claude_mcp_list(working_dir.to_string()).await?;
+    servers
+        .iter()
+        .find(|server| server.name == name)
+        .map(server_to_json)
+        .transpose()
+}
+
 /// Add a new MCP server to Claude Code
+#[command]
+pub async fn claude_mcp_add<R: Runtime>(
+    app: AppHandle<R>,
+    request: ClaudeCodeServer,
+    working_dir: String,
+    conflict_policy: Option<ConflictPolicy>,
+    normalize_name: Option<bool>,
+) -> Result<ClaudeCodeResponse, String> {
+    let config = server_to_json(&request)?;
+    let response = add_claude_server(
+        request,
+        working_dir.clone(),
+        conflict_policy,
+        normalize_name,
+    )
+    .await?;
+    if let Some(name) = &response.name {
+        let target = ClientTarget::claude_code(&working_dir);
+        server_metadata::spawn_warm_up(&app, target.clone(), name.clone(), config.clone());
+        claude_cli_verify::spawn_verify(
+            &app,
+            name.clone(),
+            working_dir.clone(),
+            Some(config.clone()),
+        );
+        op_recorder::record(
+            target,
+            RecordedOp::UpsertServer {
+                name: name.clone(),
+                config,
+            },
+        );
+    }
+    Ok(response)
+}
+
+/// Add a server without recording it, for internal callers
 /// If working_dir is "Global", writes to ~/.claude.json root mcpServers (user-scope)
 /// Otherwise writes to ~/.claude.json projects[working_dir].mcpServers (local-scope)
-#[command]
-pub async fn claude_mcp_add(
+/// An existing server with the same name is handled by `conflict_policy`, defaulting to the
+/// `conflict_policy` setting. Invalid names are rejected unless `normalize_name` is set.
+pub(crate) async fn add_claude_server(
     request: ClaudeCodeServer,
     working_dir: String,
+    conflict_policy: Option<ConflictPolicy>,
+    normalize_name: Option<bool>,
 ) -> Result<ClaudeCodeResponse, String> {
     let server_json = server_to_json(&request)?;
-    let claude_config_path = get_claude_config_path(None)?;
-
-    // Create backup if config file exists
-    let backup_path = if claude_config_path.exists() {
-        Some(create_backup(&claude_config_path)?)
-    } else {
-        None
-    };
+    let claude_config_path = get_claude_config_path(None).await?;
+    let config_exists = claude_config_path.exists();
 
     // Read existing config or create new one
-    let mut config: serde_json::Value = if claude_config_path.exists() {
+    let mut config: serde_json::Value = if config_exists {
         let config_content = fs::read_to_string(&claude_config_path)
             .map_err(|e| format!("Failed to read Claude config: {}", e))?;
         serde_json::from_str(&config_content)
@@ -119,13 +218,37 @@ pub async fn claude_mcp_add(
     } else {
         serde_json::json!({})
     };
+    let before = config_exists.then(|| config.clone());
+    // Reuse the project's existing key, which may be spelled differently than `working_dir`
+    let working_dir = if is_global_config(&working_dir) {
+        working_dir
+    } else {
+        unicode_path::resolve_project_key(&config, &working_dir)
+    };
+
+    let existing = server_names(&config, &working_dir);
+    let (requested, warnings) =
+        server_name::accept_server_name(&request.name, &existing, normalize_name.unwrap_or(false))?;
+    let policy = conflict_policy.unwrap_or_else(|| settings::load_settings().conflict_policy);
+    let (name, outcome) = resolve_name_conflict(&config, &working_dir, &requested, policy)?;
+    quarantine::ensure_not_quarantined(&ClientTarget::claude_code(&working_dir), &name, "adding")?;
+    if outcome == AddOutcome::Overwritten {
+        server_locks::ensure_unlocked(&ClientTarget::claude_code(&working_dir), &name)?;
+    }
+
+    // Create backup if config file exists
+    let backup = if config_exists {
+        Some(create_backup(&claude_config_path)?)
+    } else {
+        None
+    };
 
     if is_global_config(&working_dir) {
         // Write to root-level mcpServers (user-scope)
         if !config["mcpServers"].is_object() {
             config["mcpServers"] = serde_json::json!({});
         }
-        config["mcpServers"][&request.name] = server_json;
+        config["mcpServers"][&name] = server_json;
     } else {
         // Write to per-project config (local-scope)
         if !config["projects"].is_object() {
@@ -137,54 +260,250 @@ pub async fn claude_mcp_add(
         if !config["projects"][&working_dir]["mcpServers"].is_object() {
             config["projects"][&working_dir]["mcpServers"] = serde_json::json!({});
         }
-        config["projects"][&working_dir]["mcpServers"][&request.name] = server_json;
+        config["projects"][&working_dir]["mcpServers"][&name] = server_json;
     }
 
     // Write back to file
-    if let Err(e) = fs::write(
+    write_claude_config_verified(
         &claude_config_path,
-        serde_json::to_string_pretty(&config).unwrap(),
-    ) {
-        if let Some(backup_path) = &backup_path {
-            let _ = restore_backup(&claude_config_path, backup_path);
+        before.as_ref(),
+        &config,
+        &[json_pointer::claude_server_pointer(&working_dir, &name)],
+        backup.as_ref(),
+    )?;
+
+    // Clean up backup file on success
+    if let Some(backup) = backup {
+        backup.discard();
+    }
+
+    let scope = if is_global_config(&working_dir) { "user" } else { "project" };
+    let message = match outcome {
+        AddOutcome::Added => format!("Server '{}' added to {} config successfully", name, scope),
+        AddOutcome::Renamed => format!(
+            "Server '{}' already exists, added as '{}' to {} config",
+            requested, name, scope
+        ),
+        _ => format!("Server '{}' in {} config was overwritten", name, scope),
+    };
+    Ok(ClaudeCodeResponse {
+        success: true,
+        message,
+        name: Some(name),
+        outcome: Some(outcome),
+        warnings,
+    })
+}
+
+/// Names of the servers configured in the given scope
+fn server_names(config: &serde_json::Value, working_dir: &str) -> Vec<String> {
+    let pointer = if is_global_config(working_dir) {
+        json_pointer::join(&["mcpServers"])
+    } else {
+        json_pointer::join(&["projects", working_dir, "mcpServers"])
+    };
+    config
+        .pointer(&pointer)
+        .and_then(|v| v.as_object())
+        .map(|m| m.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Decide which name an added server is written under
+pub(crate) fn resolve_name_conflict(
+    config: &serde_json::Value,
+    working_dir: &str,
+    name: &str,
+    policy: ConflictPolicy,
+) -> Result<(String, AddOutcome), String> {
+    let taken = |candidate: &str| {
+        config
+            .pointer(&json_pointer::claude_server_pointer(working_dir, candidate))
+            .is_some()
+    };
+    if !taken(name) {
+        return Ok((name.to_string(), AddOutcome::Added));
+    }
+
+    match policy {
+        ConflictPolicy::Error => {
+            let scope = if is_global_config(working_dir) { "user" } else { "project" };
+            Err(format!("Server '{}' already exists in {} config", name, scope))
+        }
+        ConflictPolicy::Overwrite => Ok((name.to_string(), AddOutcome::Overwritten)),
+        ConflictPolicy::KeepBoth => {
+            let candidate = (2..)
+                .map(|n| format!("{}-{}", name, n))
+                .find(|candidate| !taken(candidate))
+                .expect("unbounded range always yields a free name");
+            Ok((candidate, AddOutcome::Renamed))
         }
-        return Err(format!("Failed to write Claude config: {}", e));
     }
+}
 
-    // Clean up backup file on success
-    if let Some(backup_path) = backup_path {
-        let _ = fs::remove_file(backup_path);
+/// The servers object of the scope, created on the way when missing
+pub(crate) fn scope_servers_mut<'a>(
+    config: &'a mut serde_json::Value,
+    working_dir: &str,
+) -> &'a mut serde_json::Map<String, serde_json::Value> {
+    let scope = if is_global_config(working_dir) {
+        config
+    } else {
+        if !config["projects"].is_object() {
+            config["projects"] = serde_json::json!({});
+        }
+        if !config["projects"][working_dir].is_object() {
+            config["projects"][working_dir] = serde_json::json!({});
+        }
+        &mut config["projects"][working_dir]
+    };
+    if !scope["mcpServers"].is_object() {
+        scope["mcpServers"] = serde_json::json!({});
+    }
+    scope["mcpServers"]
+        .as_object_mut()
+        .expect("mcpServers was just made an object")
+}
+
+/// `entry` with its keys in the order `previous` has them; keys new to it come last
+pub(crate) fn in_previous_order(
+    previous: &serde_json::Value,
+    entry: serde_json::Value,
+) -> serde_json::Value {
+    let serde_json::Value::Object(mut entry) = entry else {
+        return entry;
+    };
+    let Some(previous) = previous.as_object() else {
+        return serde_json::Value::Object(entry);
+    };
+    let mut ordered = serde_json::Map::new();
+    for key in previous.keys() {
+        if let Some(value) = entry.shift_remove(key) {
+            ordered.insert(key.clone(), value);
+        }
+    }
+    ordered.extend(entry);
+    serde_json::Value::Object(ordered)
+}
+
+/// Create or replace a server from a raw entry, for internal callers
+/// The entry is written as given, keys this app doesn't model included, and a replaced entry
+/// keeps its key order. The name is written as given; an invalid one fails.
+pub(crate) async fn upsert_claude_entry(
+    name: &str,
+    mut entry: serde_json::Value,
+    working_dir: &str,
+) -> Result<ClaudeCodeResponse, String> {
+    if !entry.is_object() {
+        return Err(format!("Config of server '{}' is not an object", name));
+    }
+    if entry.get("type").is_none() && entry.get("url").is_some() {
+        entry["type"] = serde_json::json!("http");
+    }
+    let claude_config_path = get_claude_config_path(None).await?;
+    let config_exists = claude_config_path.exists();
+    let mut config: serde_json::Value = if config_exists {
+        let config_content = fs::read_to_string(&claude_config_path)
+            .map_err(|e| format!("Failed to read Claude config: {}", e))?;
+        serde_json::from_str(&config_content)
+            .map_err(|e| format!("Failed to parse Claude config: {}", e))?
+    } else {
+        serde_json::json!({})
+    };
+    let before = config_exists.then(|| config.clone());
+    let working_dir = if is_global_config(working_dir) {
+        working_dir.to_string()
+    } else {
+        unicode_path::resolve_project_key(&config, working_dir)
+    };
+
+    let existing = server_names(&config, &working_dir);
+    let (_, warnings) = server_name::accept_server_name(name, &existing, false)?;
+    quarantine::ensure_not_quarantined(&ClientTarget::claude_code(&working_dir), name, "updating")?;
+    let pointer = json_pointer::claude_server_pointer(&working_dir, name);
+    let (entry, outcome) = match config.pointer(&pointer) {
+        Some(previous) => {
+            server_locks::ensure_unlocked(&ClientTarget::claude_code(&working_dir), name)?;
+            (in_previous_order(previous, entry), AddOutcome::Overwritten)
+        }
+        None => (entry, AddOutcome::Added),
+    };
+
+    let backup = if config_exists {
+        Some(create_backup(&claude_config_path)?)
+    } else {
+        None
+    };
+    scope_servers_mut(&mut config, &working_dir).insert(name.to_string(), entry);
+    write_claude_config_verified(
+        &claude_config_path,
+        before.as_ref(),
+        &config,
+        &[pointer],
+        backup.as_ref(),
+    )?;
+    if let Some(backup) = backup {
+        backup.discard();
     }
 
     let scope = if is_global_config(&working_dir) { "user" } else { "project" };
+    let message = match outcome {
+        AddOutcome::Added => format!("Server '{}' added to {} config successfully", name, scope),
+        _ => format!("Server '{}' in {} config was replaced", name, scope),
+    };
     Ok(ClaudeCodeResponse {
         success: true,
-        message: format!("Server '{}' added to {} config successfully", request.name, scope),
+        message,
+        name: Some(name.to_string()),
+        outcome: Some(outcome),
+        warnings,
     })
 }
 
 /// Remove an MCP server from Claude Code
+#[command]
+pub async fn claude_mcp_remove<R: Runtime>(
+    app: AppHandle<R>,
+    name: String,
+    working_dir: String,
+) -> Result<ClaudeCodeResponse, String> {
+    let response = remove_claude_server(name.clone(), working_dir.clone()).await?;
+    claude_cli_verify::spawn_verify(&app, name.clone(), working_dir.clone(), None);
+    op_recorder::record(
+        ClientTarget::claude_code(&working_dir),
+        RecordedOp::RemoveServer { name },
+    );
+    Ok(response)
+}
+
+/// Remove a server without recording it, for internal callers
 /// If working_dir is "Global", removes from ~/.claude.json root mcpServers (user-scope)
 /// Otherwise removes from ~/.claude.json projects[working_dir].mcpServers (local-scope)
-#[command]
-pub async fn claude_mcp_remove(
+pub(crate) async fn remove_claude_server(
     name: String,
     working_dir: String,
 ) -> Result<ClaudeCodeResponse, String> {
-    let claude_config_path = get_claude_config_path(None)?;
+    server_locks::ensure_unlocked(&ClientTarget::claude_code(&working_dir), &name)?;
+    let claude_config_path = get_claude_config_path(None).await?;
 
     if !claude_config_path.exists() {
         return Err("Claude config file not found".to_string());
     }
 
     // Create backup before making changes
-    let backup_path = create_backup(&claude_config_path)?;
+    let backup = create_backup(&claude_config_path)?;
 
     let config_content = fs::read_to_string(&claude_config_path)
         .map_err(|e| format!("Failed to read Claude config: {}", e))?;
 
     let mut config: serde_json::Value = serde_json::from_str(&config_content)
         .map_err(|e| format!("Failed to parse Claude config: {}", e))?;
+    let before = config.clone();
+    let working_dir = if is_global_config(&working_dir) {
+        working_dir
+    } else {
+        unicode_path::resolve_project_key(&config, &working_dir)
+    };
 
     let mut found = false;
 
@@ -192,7 +511,7 @@ pub async fn claude_mcp_remove(
         // Remove from root-level mcpServers (user-scope)
         if let Some(mcp_servers) = config.get_mut("mcpServers") {
             if let Some(servers_obj) = mcp_servers.as_object_mut() {
-                if servers_obj.remove(&name).is_some() {
+                if servers_obj.shift_remove(&name).is_some() {
                     found = true;
                 }
             }
@@ -203,7 +522,7 @@ pub async fn claude_mcp_remove(
             if let Some(project) = projects.get_mut(&working_dir) {
                 if let Some(mcp_servers) = project.get_mut("mcpServers") {
                     if let Some(servers_obj) = mcp_servers.as_object_mut() {
-                        if servers_obj.remove(&name).is_some() {
+                        if servers_obj.shift_remove(&name).is_some() {
                             found = true;
                         }
                     }
@@ -214,44 +533,209 @@ pub async fn claude_mcp_remove(
 
     if found {
         // Write back to file
-        if let Err(e) = fs::write(
+        write_claude_config_verified(
             &claude_config_path,
-            serde_json::to_string_pretty(&config).unwrap(),
-        ) {
-            let _ = restore_backup(&claude_config_path, &backup_path);
-            return Err(format!("Failed to write Claude config: {}", e));
-        }
+            Some(&before),
+            &config,
+            &[json_pointer::claude_server_pointer(&working_dir, &name)],
+            Some(&backup),
+        )?;
 
-        let _ = fs::remove_file(backup_path);
+        backup.discard();
 
         let scope = if is_global_config(&working_dir) { "user" } else { "project" };
         Ok(ClaudeCodeResponse {
             success: true,
             message: format!("Server '{}' removed from {} config successfully", name, scope),
+            name: Some(name),
+            outcome: None,
+            warnings: Vec::new(),
         })
     } else {
-        let _ = fs::remove_file(backup_path);
+        backup.discard();
         let scope = if is_global_config(&working_dir) { "user" } else { "project" };
         Err(format!("Server '{}' not found in {} config", name, scope))
     }
 }
 
-/// List all projects configured in Claude Code
-/// Returns "Global" first (if user-scope mcpServers exists), followed by sorted project paths
+/// Update an existing MCP server in Claude Code in place
+/// Only the fields given in `patch` change; every other key of the entry, including the ones
+/// this app doesn't know about, is kept as it was.
 #[command]
-pub async fn claude_list_projects() -> Result<Vec<String>, String> {
-    let mut projects = Vec::new();
-    let claude_config_path = get_claude_config_path(None)?;
+pub async fn claude_mcp_update<R: Runtime>(
+    app: AppHandle<R>,
+    name: String,
+    patch: ClaudeCodeServerPatch,
+    working_dir: String,
+) -> Result<ClaudeCodeResponse, String> {
+    let updated = update_claude_server(&name, &patch, &working_dir).await?;
+    if updated.response.outcome == Some(AddOutcome::Unchanged) {
+        return Ok(updated.response);
+    }
+    claude_cli_verify::spawn_verify(
+        &app,
+        name.clone(),
+        updated.working_dir.clone(),
+        Some(updated.entry.clone()),
+    );
+    op_recorder::record(
+        ClientTarget::claude_code(&updated.working_dir),
+        RecordedOp::UpsertServer {
+            name,
+            config: updated.entry,
+        },
+    );
+    Ok(updated.response)
+}
+
+/// Result of `update_claude_server`
+pub(crate) struct UpdatedServer {
+    pub response: ClaudeCodeResponse,
+    /// The entry as it is now in the config
+    pub entry: serde_json::Value,
+    /// Project key the server was found under, which may be spelled differently than the
+    /// requested working dir
+    pub working_dir: String,
+}
+
+/// `entry` with the fields of `patch` applied, keeping its other keys and their order.
+/// Switching to a remote type drops `command`, `args` and `env`; switching to stdio drops
+/// `url` and `headers`.
+pub(crate) fn apply_patch(
+    entry: &serde_json::Value,
+    patch: &ClaudeCodeServerPatch,
+) -> Result<serde_json::Value, String> {
+    let mut patched = entry
+        .as_object()
+        .cloned()
+        .ok_or("Server entry is not an object")?;
+    let previous_type = entry
+        .get("type")
+        .and_then(|v| v.as_str())
+        .unwrap_or("stdio")
+        .to_string();
+    if let Some(server_type) = &patch.r#type {
+        if !matches!(server_type.as_str(), "stdio" | "http" | "sse") {
+            return Err(format!("Unsupported server type '{}'", server_type));
+        }
+        patched.insert("type".to_string(), serde_json::json!(server_type));
+        let stale: &[&str] = match (previous_type.as_str(), server_type.as_str()) {
+            ("stdio", "http" | "sse") => &["command", "args", "env"],
+            ("http" | "sse", "stdio") => &["url", "headers"],
+            _ => &[],
+        };
+        for key in stale {
+            patched.shift_remove(*key);
+        }
+    }
+    if let Some(url) = &patch.url {
+        patched.insert("url".to_string(), serde_json::json!(url));
+    }
+    if let Some(command) = &patch.command {
+        patched.insert("command".to_string(), serde_json::json!(command));
+    }
+    if let Some(args) = &patch.args {
+        patched.insert("args".to_string(), serde_json::json!(args));
+    }
+    if let Some(env) = &patch.env {
+        patched.insert("env".to_string(), serde_json::json!(env));
+    }
+    if let Some(headers) = &patch.headers {
+        patched.insert("headers".to_string(), serde_json::json!(headers));
+    }
+
+    let patched = serde_json::Value::Object(patched);
+    let server = parse_server_config("", &patched)?;
+    match server.r#type.as_str() {
+        "stdio" if server.command.is_none() => {
+            Err("Command is required for stdio servers".to_string())
+        }
+        "http" | "sse" if server.url.is_none() => {
+            Err("URL is required for remote servers".to_string())
+        }
+        _ => Ok(patched),
+    }
+}
 
+/// Patch a server without recording it, for internal callers. A patch that changes nothing
+/// writes nothing and reports `Unchanged`.
+/// Fails when the server isn't configured in the scope of `working_dir`.
+pub(crate) async fn update_claude_server(
+    name: &str,
+    patch: &ClaudeCodeServerPatch,
+    working_dir: &str,
+) -> Result<UpdatedServer, String> {
+    server_locks::ensure_unlocked(&ClientTarget::claude_code(working_dir), name)?;
+    let claude_config_path = get_claude_config_path(None).await?;
+    let scope = if is_global_config(working_dir) { "user" } else { "project" };
+    let not_found = || format!("Server '{}' not found in {} config", name, scope);
     if !claude_config_path.exists() {
-        return Ok(projects);
+        return Err(not_found());
     }
 
     let config_content = fs::read_to_string(&claude_config_path)
         .map_err(|e| format!("Failed to read Claude config: {}", e))?;
-
-    let config: serde_json::Value = serde_json::from_str(&config_content)
+    let mut config: serde_json::Value = serde_json::from_str(&config_content)
         .map_err(|e| format!("Failed to parse Claude config: {}", e))?;
+    let before = config.clone();
+    let working_dir = if is_global_config(working_dir) {
+        working_dir.to_string()
+    } else {
+        unicode_path::resolve_project_key(&config, working_dir)
+    };
+
+    let pointer = json_pointer::claude_server_pointer(&working_dir, name);
+    let entry = config.pointer_mut(&pointer).ok_or_else(not_found)?;
+    let patched = apply_patch(entry, patch)?;
+    if *entry == patched {
+        return Ok(UpdatedServer {
+            response: ClaudeCodeResponse {
+                success: true,
+                message: format!("Server '{}' in {} config is unchanged", name, scope),
+                name: Some(name.to_string()),
+                outcome: Some(AddOutcome::Unchanged),
+                warnings: Vec::new(),
+            },
+            entry: patched,
+            working_dir,
+        });
+    }
+    *entry = patched.clone();
+
+    let backup = create_backup(&claude_config_path)?;
+    write_claude_config_verified(
+        &claude_config_path,
+        Some(&before),
+        &config,
+        &[pointer],
+        Some(&backup),
+    )?;
+    backup.discard();
+
+    Ok(UpdatedServer {
+        response: ClaudeCodeResponse {
+            success: true,
+            message: format!("Server '{}' updated in {} config successfully", name, scope),
+            name: Some(name.to_string()),
+            outcome: Some(AddOutcome::Updated),
+            warnings: Vec::new(),
+        },
+        entry: patched,
+        working_dir,
+    })
+}
+
+/// List all projects configured in Claude Code
+/// Returns "Global" first (if user-scope mcpServers exists), followed by sorted project paths
+#[command]
+pub async fn claude_list_projects() -> Result<Vec<String>, String> {
+    let mut projects = Vec::new();
+    let claude_config_path = get_claude_config_path(None).await?;
+
+    let config = match claude_scan::load_json_cached("claude_code", &claude_config_path).await? {
+        Some(config) => config,
+        None => return Ok(projects),
+    };
 
     // Check if root-level mcpServers exists (user-scope) and add "Global" first
     if let Some(mcp_servers) = config.get("mcpServers") {
@@ -287,81 +771,120 @@ pub async fn check_claude_cli_available() -> Result<bool, String> {
 }
 
 #[tauri::command]
-pub fn check_claude_config_exists() -> Result<bool, String> {
-    // Check native path first
-    let home_dir = dirs::home_dir().ok_or("Unable to find home directory")?;
-    let path = home_dir.join(".claude.json");
-    if path.exists() {
-        return Ok(true);
-    }
-
-    // On Windows, also check WSL paths
-    #[cfg(target_os = "windows")]
-    {
-        if find_wsl_claude_config(".claude.json").is_some() {
-            return Ok(true);
-        }
-    }
-
-    Ok(false)
+pub async fn check_claude_config_exists() -> Result<bool, String> {
+    // Natively or, on Windows, in any WSL distro
+    Ok(claude_location::locations(false)
+        .await?
+        .iter()
+        .any(|location| location.exists))
 }
 
-fn get_claude_config_path(_working_dir: Option<String>) -> Result<PathBuf, String> {
-    // First try Windows native path
-    let home_dir = dirs::home_dir().ok_or("Unable to find home directory")?;
-    let native_path = home_dir.join(".claude.json");
+/// The ~/.claude.json chosen in `claude_location`; fails when several exist and none is chosen
+pub(crate) async fn get_claude_config_path(
+    _working_dir: Option<String>,
+) -> Result<PathBuf, String> {
+    claude_location::config_path().await
+}
 
-    if native_path.exists() {
-        return Ok(native_path);
-    }
+/// Common WSL distro names to check, in priority order
+#[cfg(target_os = "windows")]
+const WSL_DISTROS: [&str; 7] = [
+    "Ubuntu",
+    "Ubuntu-22.04",
+    "Ubuntu-24.04",
+    "Ubuntu-20.04",
+    "Debian",
+    "kali-linux",
+    "openSUSE-Leap-15.5",
+];
+
+/// WSL network share roots; Windows 11 serves `\\wsl.localhost`, older builds only `\\wsl$`
+#[cfg(target_os = "windows")]
+const WSL_SHARES: [&str; 2] = [r"\\wsl.localhost", r"\\wsl$"];
 
-    // On Windows, also check WSL paths if native doesn't exist
-    #[cfg(target_os = "windows")]
-    {
-        if let Some(wsl_path) = find_wsl_claude_config(".claude.json") {
-            return Ok(wsl_path);
+/// Distro roots to probe: the well-known names first, then whatever the shares list.
+/// Share listing only shows running distros, and their names may be non-ASCII.
+#[cfg(target_os = "windows")]
+fn wsl_distro_roots() -> Vec<PathBuf> {
+    let mut names: Vec<std::ffi::OsString> = WSL_DISTROS.iter().map(|d| d.into()).collect();
+    for share in WSL_SHARES {
+        if let Ok(entries) = fs::read_dir(share) {
+            for entry in entries.flatten() {
+                let name = entry.file_name();
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
         }
     }
+    let share = WSL_SHARES
+        .into_iter()
+        .find(|share| Path::new(share).exists())
+        .unwrap_or(WSL_SHARES[1]);
+    names
+        .into_iter()
+        .map(|name| Path::new(share).join(name))
+        .collect()
+}
 
-    // Return native path even if it doesn't exist (for creation)
-    Ok(native_path)
+/// On Windows, find the Claude configs in WSL, by distro name in priority order.
+/// Each WSL share can take seconds to answer, so all distros are probed concurrently.
+#[cfg(target_os = "windows")]
+pub(crate) async fn find_wsl_claude_configs(filename: &str) -> Vec<(String, PathBuf)> {
+    let started = std::time::Instant::now();
+    let roots = tokio::task::spawn_blocking(wsl_distro_roots)
+        .await
+        .unwrap_or_default();
+    let probes = roots.into_iter().map(|wsl_base| {
+        let filename = filename.to_string();
+        tokio::task::spawn_blocking(move || {
+            let distro = wsl_base.file_name()?.to_string_lossy().into_owned();
+            probe_wsl_distro(&wsl_base, &filename).map(|path| (distro, path))
+        })
+    });
+    let found: Vec<(String, PathBuf)> = futures::future::join_all(probes)
+        .await
+        .into_iter()
+        .filter_map(|probe| probe.ok().flatten())
+        .collect();
+
+    claude_scan::record_timing(
+        "wsl_probe",
+        found
+            .first()
+            .map_or(Path::new(r"\\wsl$"), |(_, path)| path.as_path()),
+        started,
+        false,
+        !found.is_empty(),
+        None,
+    );
+    found
 }
 
-/// On Windows, attempt to find Claude config in WSL
 #[cfg(target_os = "windows")]
-fn find_wsl_claude_config(filename: &str) -> Option<PathBuf> {
-    // Common WSL distro names to check
-    let distros = ["Ubuntu", "Ubuntu-22.04", "Ubuntu-24.04", "Ubuntu-20.04", "Debian", "kali-linux", "openSUSE-Leap-15.5"];
-
-    // Try to get WSL username by checking common paths
-    for distro in &distros {
-        let wsl_base = PathBuf::from(format!(r"\\wsl$\{}", distro));
-        if !wsl_base.exists() {
-            continue;
-        }
+fn probe_wsl_distro(wsl_base: &Path, filename: &str) -> Option<PathBuf> {
+    if !wsl_base.exists() {
+        return None;
+    }
 
-        // Check /home/* directories for .claude.json
-        let home_dir = wsl_base.join("home");
-        if let Ok(entries) = fs::read_dir(&home_dir) {
-            for entry in entries.flatten() {
-                let user_home = entry.path();
-                let config_path = user_home.join(filename);
-                if config_path.exists() {
-                    return Some(config_path);
-                }
-            }
+    // Check /home/* directories for the config file
+    let home_dir = wsl_base.join("home");
+    let entries = fs::read_dir(&home_dir).ok()?;
+    for entry in entries.flatten() {
+        let config_path = entry.path().join(filename);
+        if config_path.exists() {
+            return Some(config_path);
         }
     }
-
     None
 }
 
 /// Check if a working_dir refers to the global config
-fn is_global_config(working_dir: &str) -> bool {
+pub(crate) fn is_global_config(working_dir: &str) -> bool {
     working_dir == GLOBAL_PROJECT_ID
 }
 
-fn parse_server_config(name: &str, config: &serde_json::Value) -> Result<ClaudeCodeServer, String> {
+pub(crate) fn parse_server_config(name: &str, config: &serde_json::Value) -> Result<ClaudeCodeServer, String> {
     let server_type = config
         .get("type")
         .and_then(|v| v.as_str())
@@ -390,6 +913,12 @@ fn parse_server_config(name: &str, config: &serde_json::Value) -> Result<ClaudeC
             .collect()
     });
 
+    let headers = config.get("headers").and_then(|v| v.as_object()).map(|obj| {
+        obj.iter()
+            .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
+            .collect()
+    });
+
     Ok(ClaudeCodeServer {
         name: name.to_string(),
         r#type: server_type,
@@ -397,10 +926,12 @@ fn parse_server_config(name: &str, config: &serde_json::Value) -> Result<ClaudeC
         command,
         args,
         env,
+        headers,
+        credential_status: None,
     })
 }
 
-fn server_to_json(server: &ClaudeCodeServer) -> Result<serde_json::Value, String> {
+pub(crate) fn server_to_json(server: &ClaudeCodeServer) -> Result<serde_json::Value, String> {
     let mut json = serde_json::json!({
         "type": server.r#type
     });
@@ -429,32 +960,58 @@ fn server_to_json(server: &ClaudeCodeServer) -> Result<serde_json::Value, String
         );
     }
 
-    Ok(json)
-}
-
-fn create_backup(config_path: &PathBuf) -> Result<PathBuf, String> {
-    if !config_path.exists() {
-        return Err("Config file does not exist".to_string());
+    if let Some(headers) = &server.headers {
+        json["headers"] = serde_json::Value::Object(
+            headers
+                .iter()
+                .map(|(k, v)| (k.clone(), serde_json::Value::String(v.clone())))
+                .collect(),
+        );
     }
 
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
-
-    let backup_path = config_path.with_extension(format!("json.backup.{}", timestamp));
-
-    fs::copy(config_path, &backup_path).map_err(|e| format!("Failed to create backup: {}", e))?;
+    Ok(json)
+}
 
-    Ok(backup_path)
+/// Serialize ~/.claude.json the way every write path does.
+/// Claude Code writes the file with 2-space indentation and no trailing newline, which matches
+/// `to_string_pretty`; key order is preserved through serde_json's `preserve_order` feature.
+pub(crate) fn serialize_claude_config(config: &serde_json::Value) -> Result<String, String> {
+    serde_json::to_string_pretty(config)
+        .map_err(|e| format!("Failed to serialize Claude config: {}", e))
 }
 
-fn restore_backup(config_path: &PathBuf, backup_path: &PathBuf) -> Result<(), String> {
-    if !backup_path.exists() {
-        return Err("Backup file does not exist".to_string());
-    }
+/// Write ~/.claude.json, then read it back and verify that nothing outside `intended` changed
+/// compared to `before`. On a failed write or integrity check the backup is restored.
+pub(crate) fn write_claude_config_verified(
+    config_path: &PathBuf,
+    before: Option<&serde_json::Value>,
+    config: &serde_json::Value,
+    intended: &[String],
+    backup: Option<&ConfigBackup>,
+) -> Result<(), String> {
+    let result = write_journal::write(config_path, &serialize_claude_config(config)?)
+        .map_err(|e| format!("Failed to write Claude config: {}", e))
+        .and_then(|_| match before {
+            Some(before) => {
+                let written = fs::read_to_string(config_path)
+                    .map_err(|e| format!("Failed to re-read Claude config: {}", e))?;
+                claude_integrity::verify_unchanged_outside(before, &written, intended)
+                    .map_err(|e| format!("Integrity check failed, changes were rolled back: {}", e))
+            }
+            None => Ok(()),
+        });
 
-    fs::copy(backup_path, config_path).map_err(|e| format!("Failed to restore backup: {}", e))?;
+    if result.is_err() {
+        metrics::inc(metrics::WRITE_FAILURES, &[("client", "claude_code")]);
+        if let Some(backup) = backup {
+            let _ = config_backups::restore(backup);
+        }
+    }
+    claude_scan::invalidate(config_path);
+    result
+}
 
-    Ok(())
+/// Back up ~/.claude.json before a write (see `config_backups`)
+pub(crate) fn create_backup(config_path: &PathBuf) -> Result<ConfigBackup, String> {
+    config_backups::create(config_path)
 }
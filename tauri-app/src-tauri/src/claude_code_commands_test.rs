@@ -0,0 +1,127 @@
+// Claude Code command tests
+use crate::claude_code_commands::{
+    apply_patch, in_previous_order, resolve_name_conflict, AddOutcome, ClaudeCodeServerPatch,
+};
+use crate::settings::ConflictPolicy;
+use serde_json::json;
+
+fn config_with_taken_names() -> serde_json::Value {
+    json!({
+        "mcpServers": { "sentry": { "url": "https://a" }, "sentry-2": { "url": "https://b" } },
+        "projects": { "/work/app": { "mcpServers": { "sentry": { "command": "npx" } } } }
+    })
+}
+
+#[test]
+fn test_free_name_is_added_under_every_policy() {
+    let config = config_with_taken_names();
+    for policy in [
+        ConflictPolicy::Error,
+        ConflictPolicy::Overwrite,
+        ConflictPolicy::KeepBoth,
+    ] {
+        let resolved = resolve_name_conflict(&config, "Global", "github", policy).unwrap();
+        assert_eq!(resolved, ("github".to_string(), AddOutcome::Added));
+    }
+}
+
+#[test]
+fn test_error_policy_refuses_a_taken_name() {
+    let config = config_with_taken_names();
+    let err =
+        resolve_name_conflict(&config, "Global", "sentry", ConflictPolicy::Error).unwrap_err();
+    assert!(err.contains("already exists in user config"));
+    let err =
+        resolve_name_conflict(&config, "/work/app", "sentry", ConflictPolicy::Error).unwrap_err();
+    assert!(err.contains("already exists in project config"));
+}
+
+#[test]
+fn test_overwrite_policy_keeps_the_name() {
+    let config = config_with_taken_names();
+    let resolved =
+        resolve_name_conflict(&config, "/work/app", "sentry", ConflictPolicy::Overwrite).unwrap();
+    assert_eq!(resolved, ("sentry".to_string(), AddOutcome::Overwritten));
+}
+
+#[test]
+fn test_keep_both_policy_picks_the_first_free_suffix() {
+    let config = config_with_taken_names();
+    // `sentry-2` is taken at user scope, so the next free suffix is used
+    let resolved =
+        resolve_name_conflict(&config, "Global", "sentry", ConflictPolicy::KeepBoth).unwrap();
+    assert_eq!(resolved, ("sentry-3".to_string(), AddOutcome::Renamed));
+    // Scopes are independent
+    let resolved =
+        resolve_name_conflict(&config, "/work/app", "sentry", ConflictPolicy::KeepBoth).unwrap();
+    assert_eq!(resolved, ("sentry-2".to_string(), AddOutcome::Renamed));
+}
+
+#[test]
+fn test_apply_patch_keeps_unknown_fields() {
+    let entry = json!({
+        "type": "stdio",
+        "command": "npx",
+        "args": ["-y", "server"],
+        "alwaysAllow": ["read"],
+        "timeout": 30
+    });
+    let patch = ClaudeCodeServerPatch {
+        args: Some(vec!["-y".to_string(), "server@2".to_string()]),
+        ..Default::default()
+    };
+    let patched = apply_patch(&entry, &patch).unwrap();
+    assert_eq!(
+        patched,
+        json!({
+            "type": "stdio",
+            "command": "npx",
+            "args": ["-y", "server@2"],
+            "alwaysAllow": ["read"],
+            "timeout": 30
+        })
+    );
+    let keys: Vec<&String> = patched.as_object().unwrap().keys().collect();
+    assert_eq!(keys, ["type", "command", "args", "alwaysAllow", "timeout"]);
+}
+
+#[test]
+fn test_apply_patch_switches_transport() {
+    let entry = json!({ "type": "stdio", "command": "npx", "env": { "A": "1" }, "note": "x" });
+    let remote = ClaudeCodeServerPatch {
+        r#type: Some("http".to_string()),
+        url: Some("https://example.com/mcp".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        apply_patch(&entry, &remote).unwrap(),
+        json!({ "type": "http", "note": "x", "url": "https://example.com/mcp" })
+    );
+
+    let missing_url = ClaudeCodeServerPatch {
+        r#type: Some("sse".to_string()),
+        ..Default::default()
+    };
+    assert!(apply_patch(&entry, &missing_url).is_err());
+}
+
+#[test]
+fn test_replaced_entry_keeps_key_order_and_unknown_keys() {
+    let previous = json!({ "command": "uvx", "env": { "B": "1", "A": "2" }, "type": "stdio" });
+    let entry =
+        json!({ "type": "stdio", "command": "uvx", "env": { "B": "1", "A": "3" }, "timeout": 30 });
+    let ordered = in_previous_order(&previous, entry);
+    assert_eq!(
+        ordered.as_object().unwrap().keys().collect::<Vec<_>>(),
+        ["command", "env", "type", "timeout"]
+    );
+    assert_eq!(
+        ordered["env"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .collect::<Vec<_>>(),
+        ["B", "A"]
+    );
+    assert_eq!(ordered["env"]["A"], "3");
+}
@@ -1,4 +1,12 @@
-use dirs::home_dir;
+use crate::client_target::ClientTarget;
+use crate::demo_mode::home_dir;
+use crate::op_recorder::{self, RecordedOp};
+use crate::quarantine;
+use crate::server_locks;
+use crate::settings::ConflictPolicy;
+use crate::unicode_path;
+use crate::write_journal;
+use serde::Serialize;
 use serde_json::{json, Value};
 use std::fs;
 use std::path::PathBuf;
@@ -9,7 +17,7 @@ fn get_disabled_path() -> Result<PathBuf, String> {
     Ok(home.join(".claude.disabled.json"))
 }
 
-fn read_disabled_file() -> Result<Value, String> {
+pub(crate) fn read_disabled_file() -> Result<Value, String> {
     let path = get_disabled_path()?;
     if !path.exists() {
         return Ok(json!({"projects": {}}));
@@ -25,13 +33,14 @@ fn write_disabled_file(v: &Value) -> Result<(), String> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).map_err(|e| format!("Create dir failed: {}", e))?;
     }
-    fs::write(&path, serde_json::to_string_pretty(v).unwrap())
+    write_journal::write(&path, &serde_json::to_string_pretty(v).unwrap())
         .map_err(|e| format!("Write disabled file: {}", e))
 }
 
 #[command]
 pub async fn claude_list_disabled(working_dir: String) -> Result<Value, String> {
     let v = read_disabled_file()?;
+    let working_dir = unicode_path::resolve_project_key(&v, &working_dir);
     Ok(v.get("projects")
         .and_then(|p| p.get(&working_dir))
         .cloned()
@@ -40,8 +49,20 @@ pub async fn claude_list_disabled(working_dir: String) -> Result<Value, String>
 
 #[command]
 pub async fn claude_disable_server(working_dir: String, name: String) -> Result<Value, String> {
+    let disabled = disable_claude_server(&working_dir, &name).await?;
+    op_recorder::record(
+        ClientTarget::claude_code(&working_dir),
+        RecordedOp::DisableServer { name },
+    );
+    Ok(disabled)
+}
+
+pub(crate) async fn disable_claude_server(working_dir: &str, name: &str) -> Result<Value, String> {
+    server_locks::ensure_unlocked(&ClientTarget::claude_code(working_dir), name)?;
+    let name = name.to_string();
     // Read current disabled and Claude config to fetch config for the named server
     let mut disabled = read_disabled_file()?;
+    let working_dir = unicode_path::resolve_project_key(&disabled, working_dir);
     if !disabled["projects"].is_object() {
         disabled["projects"] = json!({});
     }
@@ -71,14 +92,56 @@ pub async fn claude_disable_server(working_dir: String, name: String) -> Result<
 
         // Remove from ~/.claude.json active list
         let _ =
-            crate::claude_code_commands::claude_mcp_remove(name.clone(), working_dir.clone()).await;
+            crate::claude_code_commands::remove_claude_server(name.clone(), working_dir.clone())
+                .await;
     }
     Ok(disabled["projects"][&working_dir].clone())
 }
 
+/// Outcome of re-enabling a Claude Code server
+#[derive(Debug, Serialize)]
+pub struct EnabledServer {
+    /// The project's remaining disabled servers
+    pub disabled: Value,
+    /// Name the server was added back under; differs from the requested one only when
+    /// `normalize_name` was set and the stored name was invalid
+    pub name: String,
+    pub warnings: Vec<String>,
+}
+
+/// Move a disabled server back into ~/.claude.json. Invalid names are rejected unless
+/// `normalize_name` is set, in which case the rename is reported in the result.
 #[command]
-pub async fn claude_enable_server(working_dir: String, name: String) -> Result<Value, String> {
+pub async fn claude_enable_server(
+    working_dir: String,
+    name: String,
+    normalize_name: Option<bool>,
+) -> Result<EnabledServer, String> {
+    quarantine::ensure_not_quarantined(
+        &ClientTarget::claude_code(&working_dir),
+        &name,
+        "enabling",
+    )?;
+    let enabled =
+        enable_claude_server(&working_dir, &name, normalize_name.unwrap_or(false)).await?;
+    op_recorder::record(
+        ClientTarget::claude_code(&working_dir),
+        RecordedOp::EnableServer { name },
+    );
+    Ok(enabled)
+}
+
+pub(crate) async fn enable_claude_server(
+    working_dir: &str,
+    name: &str,
+    normalize_name: bool,
+) -> Result<EnabledServer, String> {
+    server_locks::ensure_unlocked(&ClientTarget::claude_code(working_dir), name)?;
+    let name = name.to_string();
     let mut disabled = read_disabled_file()?;
+    let working_dir = unicode_path::resolve_project_key(&disabled, working_dir);
+    let mut enabled_name = name.clone();
+    let mut warnings = Vec::new();
 
     // Read config from disabled store to re-add
     let maybe_cfg = disabled
@@ -114,8 +177,28 @@ pub async fn claude_enable_server(working_dir: String, name: String) -> Result<V
                     .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
                     .collect()
             }),
+            headers: cfg.get("headers").and_then(|v| v.as_object()).map(|m| {
+                m.iter()
+                    .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
+                    .collect()
+            }),
+            credential_status: None,
         };
-        let _ = crate::claude_code_commands::claude_mcp_add(server, working_dir.clone()).await;
+        // Keep it in the disabled store when it can't be added back
+        let response = crate::claude_code_commands::add_claude_server(
+            server,
+            working_dir.clone(),
+            Some(ConflictPolicy::Overwrite),
+            Some(normalize_name),
+        )
+        .await?;
+        if let Some(added) = response.name {
+            if added != name {
+                println!("[Claude] Enabled '{}' as '{}'", name, added);
+                enabled_name = added;
+            }
+        }
+        warnings = response.warnings;
     }
 
     // Remove from disabled store
@@ -126,11 +209,15 @@ pub async fn claude_enable_server(working_dir: String, name: String) -> Result<V
     }
     write_disabled_file(&disabled)?;
 
-    Ok(disabled
-        .get("projects")
-        .and_then(|p| p.get(&working_dir))
-        .cloned()
-        .unwrap_or(json!({})))
+    Ok(EnabledServer {
+        disabled: disabled
+            .get("projects")
+            .and_then(|p| p.get(&working_dir))
+            .cloned()
+            .unwrap_or(json!({})),
+        name: enabled_name,
+        warnings,
+    })
 }
 
 #[command]
@@ -139,7 +226,9 @@ pub async fn claude_update_disabled(
     name: String,
     server_config: Value,
 ) -> Result<Value, String> {
+    server_locks::ensure_unlocked(&ClientTarget::claude_code(&working_dir), &name)?;
     let mut disabled = read_disabled_file()?;
+    let working_dir = unicode_path::resolve_project_key(&disabled, &working_dir);
     if !disabled["projects"].is_object() {
         disabled["projects"] = json!({});
     }
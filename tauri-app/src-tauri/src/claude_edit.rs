@@ -0,0 +1,177 @@
+//! Fine-grained edits of a single Claude Code server entry
+//!
+//! Each command touches one field of one server (an arg, an env var) so the UI and scripts
+//! don't have to resubmit the whole entry and risk clobbering fields they didn't load.
+
+use crate::claude_code_commands::{
+    create_backup, get_claude_config_path, parse_server_config, write_claude_config_verified,
+    ClaudeCodeServer,
+};
+use crate::client_target::ClientTarget;
+use crate::{json_pointer, server_locks};
+use serde_json::{Map, Value};
+use std::fs;
+use tauri::command;
+
+/// Apply `edit` to the server object at `name` in the given scope and write it back
+pub(crate) async fn edit_claude_server<F>(
+    name: &str,
+    working_dir: &str,
+    edit: F,
+) -> Result<ClaudeCodeServer, String>
+where
+    F: FnOnce(&mut Map<String, Value>) -> Result<(), String>,
+{
+    server_locks::ensure_unlocked(&ClientTarget::claude_code(working_dir), name)?;
+    let config_path = get_claude_config_path(None).await?;
+    if !config_path.exists() {
+        return Err("Claude config file not found".to_string());
+    }
+    let content = fs::read_to_string(&config_path)
+        .map_err(|e| format!("Failed to read Claude config: {}", e))?;
+    let mut config: Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse Claude config: {}", e))?;
+    let before = config.clone();
+
+    let pointer = json_pointer::claude_server_pointer(working_dir, name);
+    let server = config
+        .pointer_mut(&pointer)
+        .and_then(|v| v.as_object_mut())
+        .ok_or_else(|| format!("Server '{}' not found", name))?;
+    edit(server)?;
+    let updated = parse_server_config(name, &Value::Object(server.clone()))?;
+
+    let backup = create_backup(&config_path)?;
+    write_claude_config_verified(
+        &config_path,
+        Some(&before),
+        &config,
+        &[pointer],
+        Some(&backup),
+    )?;
+    backup.discard();
+    Ok(updated)
+}
+
+/// Insert `arg` at `position` (appended when omitted or past the end)
+#[command]
+pub async fn add_server_arg(
+    name: String,
+    working_dir: String,
+    arg: String,
+    position: Option<usize>,
+) -> Result<ClaudeCodeServer, String> {
+    edit_claude_server(&name, &working_dir, |server| {
+        insert_arg(server, &name, arg, position)
+    })
+    .await
+}
+
+/// Remove the arg at `position`, or the first arg equal to `arg`
+#[command]
+pub async fn remove_server_arg(
+    name: String,
+    working_dir: String,
+    arg: Option<String>,
+    position: Option<usize>,
+) -> Result<ClaudeCodeServer, String> {
+    edit_claude_server(&name, &working_dir, |server| {
+        remove_arg(server, &name, arg.as_deref(), position)
+    })
+    .await
+}
+
+#[command]
+pub async fn set_env_var(
+    name: String,
+    working_dir: String,
+    key: String,
+    value: String,
+) -> Result<ClaudeCodeServer, String> {
+    edit_claude_server(&name, &working_dir, |server| set_env(server, key, value)).await
+}
+
+#[command]
+pub async fn unset_env_var(
+    name: String,
+    working_dir: String,
+    key: String,
+) -> Result<ClaudeCodeServer, String> {
+    edit_claude_server(&name, &working_dir, |server| unset_env(server, &name, &key)).await
+}
+
+pub(crate) fn insert_arg(
+    server: &mut Map<String, Value>,
+    name: &str,
+    arg: String,
+    position: Option<usize>,
+) -> Result<(), String> {
+    if server
+        .get("type")
+        .and_then(|t| t.as_str())
+        .unwrap_or("stdio")
+        != "stdio"
+    {
+        return Err(format!("Server '{}' is not a stdio server", name));
+    }
+    let args = array_field(server, "args")?;
+    let index = position.unwrap_or(args.len()).min(args.len());
+    args.insert(index, Value::String(arg));
+    Ok(())
+}
+
+pub(crate) fn remove_arg(
+    server: &mut Map<String, Value>,
+    name: &str,
+    arg: Option<&str>,
+    position: Option<usize>,
+) -> Result<(), String> {
+    let args = array_field(server, "args")?;
+    let index = match (position, arg) {
+        (Some(index), _) => Some(index).filter(|i| *i < args.len()),
+        (None, Some(arg)) => args.iter().position(|a| a.as_str() == Some(arg)),
+        (None, None) => return Err("Either arg or position is required".to_string()),
+    };
+    let index = index.ok_or_else(|| format!("Arg not found in server '{}'", name))?;
+    args.remove(index);
+    Ok(())
+}
+
+pub(crate) fn set_env(
+    server: &mut Map<String, Value>,
+    key: String,
+    value: String,
+) -> Result<(), String> {
+    let env = server
+        .entry("env")
+        .or_insert_with(|| Value::Object(Map::new()));
+    let env = env.as_object_mut().ok_or("Server 'env' is not an object")?;
+    env.insert(key, Value::String(value));
+    Ok(())
+}
+
+pub(crate) fn unset_env(
+    server: &mut Map<String, Value>,
+    name: &str,
+    key: &str,
+) -> Result<(), String> {
+    let removed = server
+        .get_mut("env")
+        .and_then(|env| env.as_object_mut())
+        .and_then(|env| env.shift_remove(key));
+    match removed {
+        Some(_) => Ok(()),
+        None => Err(format!("Env var '{}' not set on server '{}'", key, name)),
+    }
+}
+
+fn array_field<'a>(
+    server: &'a mut Map<String, Value>,
+    key: &str,
+) -> Result<&'a mut Vec<Value>, String> {
+    server
+        .entry(key)
+        .or_insert_with(|| Value::Array(Vec::new()))
+        .as_array_mut()
+        .ok_or_else(|| format!("Server '{}' is not an array", key))
+}
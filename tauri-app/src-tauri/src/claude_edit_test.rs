@@ -0,0 +1,75 @@
+// Claude Code server field edit tests
+use crate::claude_edit::{insert_arg, remove_arg, set_env, unset_env};
+use serde_json::{json, Map, Value};
+
+fn server(value: Value) -> Map<String, Value> {
+    value.as_object().unwrap().clone()
+}
+
+#[test]
+fn test_insert_arg() {
+    let mut fs = server(json!({ "command": "npx", "args": ["-y", "/data"] }));
+    insert_arg(
+        &mut fs,
+        "fs",
+        "@modelcontextprotocol/server-filesystem".to_string(),
+        Some(1),
+    )
+    .unwrap();
+    insert_arg(&mut fs, "fs", "/logs".to_string(), Some(99)).unwrap();
+    assert_eq!(
+        fs["args"],
+        json!([
+            "-y",
+            "@modelcontextprotocol/server-filesystem",
+            "/data",
+            "/logs"
+        ])
+    );
+
+    let mut empty = server(json!({ "command": "uvx" }));
+    insert_arg(&mut empty, "git", "mcp-server-git".to_string(), None).unwrap();
+    assert_eq!(empty["args"], json!(["mcp-server-git"]));
+
+    let mut remote = server(json!({ "type": "http", "url": "https://mcp.example" }));
+    assert_eq!(
+        insert_arg(&mut remote, "remote", "x".to_string(), None).unwrap_err(),
+        "Server 'remote' is not a stdio server"
+    );
+}
+
+#[test]
+fn test_remove_arg() {
+    let mut fs = server(json!({ "args": ["-y", "pkg", "/data", "pkg"] }));
+    remove_arg(&mut fs, "fs", Some("pkg"), None).unwrap();
+    assert_eq!(fs["args"], json!(["-y", "/data", "pkg"]));
+    remove_arg(&mut fs, "fs", None, Some(0)).unwrap();
+    assert_eq!(fs["args"], json!(["/data", "pkg"]));
+
+    assert!(remove_arg(&mut fs, "fs", None, Some(5)).is_err());
+    assert!(remove_arg(&mut fs, "fs", Some("missing"), None).is_err());
+    assert_eq!(
+        remove_arg(&mut fs, "fs", None, None).unwrap_err(),
+        "Either arg or position is required"
+    );
+    let mut broken = server(json!({ "args": "-y pkg" }));
+    assert!(remove_arg(&mut broken, "broken", Some("-y"), None).is_err());
+}
+
+#[test]
+fn test_env_vars() {
+    let mut api = server(json!({ "command": "api-mcp" }));
+    set_env(&mut api, "API_KEY".to_string(), "secret".to_string()).unwrap();
+    set_env(&mut api, "REGION".to_string(), "eu".to_string()).unwrap();
+    assert_eq!(api["env"], json!({ "API_KEY": "secret", "REGION": "eu" }));
+
+    unset_env(&mut api, "api", "API_KEY").unwrap();
+    assert_eq!(api["env"], json!({ "REGION": "eu" }));
+    assert_eq!(
+        unset_env(&mut api, "api", "API_KEY").unwrap_err(),
+        "Env var 'API_KEY' not set on server 'api'"
+    );
+
+    let mut broken = server(json!({ "env": ["API_KEY=secret"] }));
+    assert!(set_env(&mut broken, "A".to_string(), "b".to_string()).is_err());
+}
@@ -0,0 +1,264 @@
+//! Claude Code slash commands and subagents
+//!
+//! Slash commands are Markdown files under `.claude/commands/` (subdirectories namespace them,
+//! so `frontend/test.md` is `/frontend:test`); subagents are Markdown files with YAML
+//! frontmatter under `.claude/agents/`. Both exist in the user dir and in each project.
+//! Files are addressed by their path relative to that directory, without the `.md`.
+
+use crate::claude_settings::{self, SettingsScope};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClaudeFileKind {
+    Command,
+    Agent,
+}
+
+impl ClaudeFileKind {
+    fn dir_name(self) -> &'static str {
+        match self {
+            ClaudeFileKind::Command => "commands",
+            ClaudeFileKind::Agent => "agents",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ClaudeFile {
+    pub kind: ClaudeFileKind,
+    pub scope: SettingsScope,
+    /// Path relative to the kind's directory, without `.md`
+    pub name: String,
+    pub path: String,
+    /// `description` from the frontmatter
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ClaudeFileTemplate {
+    pub id: &'static str,
+    pub kind: ClaudeFileKind,
+    pub description: &'static str,
+    #[serde(skip)]
+    body: &'static str,
+}
+
+const TEMPLATES: [ClaudeFileTemplate; 5] = [
+    ClaudeFileTemplate {
+        id: "blank",
+        kind: ClaudeFileKind::Command,
+        description: "Empty command that passes its arguments through",
+        body: "---\ndescription: {{description}}\n---\n\n$ARGUMENTS\n",
+    },
+    ClaudeFileTemplate {
+        id: "review",
+        kind: ClaudeFileKind::Command,
+        description: "Review the current changes",
+        body: "---\ndescription: {{description}}\nallowed-tools: Bash(git diff:*), Bash(git status:*)\n---\n\nReview the uncommitted changes (`git diff`) for bugs, missing tests and unclear naming. Focus on: $ARGUMENTS\n",
+    },
+    ClaudeFileTemplate {
+        id: "fix-issue",
+        kind: ClaudeFileKind::Command,
+        description: "Fix an issue by number",
+        body: "---\ndescription: {{description}}\nargument-hint: <issue-number>\n---\n\nFind and fix issue #$ARGUMENTS. Read the issue, locate the relevant code, make the fix and add a test that covers it.\n",
+    },
+    ClaudeFileTemplate {
+        id: "blank",
+        kind: ClaudeFileKind::Agent,
+        description: "Empty subagent with all tools",
+        body: "---\nname: {{name}}\ndescription: {{description}}\n---\n\nYou are a specialized assistant. Describe the role, the steps to follow and what to return.\n",
+    },
+    ClaudeFileTemplate {
+        id: "mcp-tester",
+        kind: ClaudeFileKind::Agent,
+        description: "Exercises the tools of configured MCP servers",
+        body: "---\nname: {{name}}\ndescription: {{description}}\n---\n\nYou test MCP servers. For each server the user names, list its tools, call each one with minimal valid input and report which calls failed and why. Do not call tools that delete or send data without asking first.\n",
+    },
+];
+
+/// Kind directory of a scope. The local scope only applies to settings files.
+fn kind_dir(
+    kind: ClaudeFileKind,
+    scope: SettingsScope,
+    working_dir: Option<&str>,
+) -> Result<PathBuf, String> {
+    if scope == SettingsScope::Local {
+        return Err("Commands and agents have no local scope; use project".to_string());
+    }
+    Ok(claude_settings::claude_dir(scope, working_dir)?.join(kind.dir_name()))
+}
+
+/// Check a relative file name. Agents can't be namespaced.
+pub(crate) fn check_file_name(kind: ClaudeFileKind, name: &str) -> Result<(), String> {
+    let segments: Vec<&str> = name.split('/').collect();
+    if kind == ClaudeFileKind::Agent && segments.len() > 1 {
+        return Err("Agent names can't contain '/'".to_string());
+    }
+    let valid = segments.iter().all(|s| {
+        !s.is_empty()
+            && s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    });
+    if !valid {
+        return Err(format!(
+            "Invalid name '{}': use letters, digits, '-' and '_'",
+            name
+        ));
+    }
+    Ok(())
+}
+
+/// `description` from a leading `---` frontmatter block
+pub(crate) fn frontmatter_description(content: &str) -> Option<String> {
+    let rest = content.strip_prefix("---")?;
+    let end = rest.find("\n---")?;
+    rest[..end].lines().find_map(|line| {
+        let value = line.strip_prefix("description:")?.trim();
+        let value = value.trim_matches('"').trim_matches('\'');
+        (!value.is_empty()).then(|| value.to_string())
+    })
+}
+
+fn collect(
+    dir: &Path,
+    prefix: &str,
+    kind: ClaudeFileKind,
+    scope: SettingsScope,
+    out: &mut Vec<ClaudeFile>,
+) -> Result<(), String> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(format!("Failed to read {}: {}", dir.display(), e)),
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if path.is_dir() {
+            if kind == ClaudeFileKind::Command {
+                collect(
+                    &path,
+                    &format!("{}{}/", prefix, file_name),
+                    kind,
+                    scope,
+                    out,
+                )?;
+            }
+            continue;
+        }
+        let Some(stem) = file_name.strip_suffix(".md") else {
+            continue;
+        };
+        let description = fs::read_to_string(&path)
+            .ok()
+            .and_then(|c| frontmatter_description(&c));
+        out.push(ClaudeFile {
+            kind,
+            scope,
+            name: format!("{}{}", prefix, stem),
+            path: path.to_string_lossy().to_string(),
+            description,
+        });
+    }
+    Ok(())
+}
+
+fn write_new_file(path: &Path, content: &str, overwrite: bool) -> Result<(), String> {
+    if path.exists() && !overwrite {
+        return Err(format!("{} already exists", path.display()));
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+#[command]
+pub async fn list_claude_files(
+    kind: ClaudeFileKind,
+    scope: SettingsScope,
+    working_dir: Option<String>,
+) -> Result<Vec<ClaudeFile>, String> {
+    let dir = kind_dir(kind, scope, working_dir.as_deref())?;
+    let mut files = Vec::new();
+    collect(&dir, "", kind, scope, &mut files)?;
+    files.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(files)
+}
+
+#[command]
+pub async fn list_claude_file_templates(
+    kind: ClaudeFileKind,
+) -> Result<Vec<ClaudeFileTemplate>, String> {
+    Ok(TEMPLATES
+        .iter()
+        .filter(|t| t.kind == kind)
+        .cloned()
+        .collect())
+}
+
+/// Create a command or agent from a template. Fails if the file already exists.
+#[command]
+pub async fn create_claude_file(
+    kind: ClaudeFileKind,
+    scope: SettingsScope,
+    working_dir: Option<String>,
+    name: String,
+    template: Option<String>,
+    description: Option<String>,
+) -> Result<ClaudeFile, String> {
+    check_file_name(kind, &name)?;
+    let template_id = template.as_deref().unwrap_or("blank");
+    let template = TEMPLATES
+        .iter()
+        .find(|t| t.kind == kind && t.id == template_id)
+        .ok_or_else(|| format!("Unknown template '{}'", template_id))?;
+    let description = description
+        .filter(|d| !d.trim().is_empty())
+        .unwrap_or_else(|| template.description.to_string());
+    // Frontmatter values stay on one line
+    let description = description.replace('\n', " ");
+    let content = template
+        .body
+        .replace("{{name}}", &name)
+        .replace("{{description}}", &description);
+
+    let path = kind_dir(kind, scope, working_dir.as_deref())?.join(format!("{}.md", name));
+    write_new_file(&path, &content, false)?;
+    Ok(ClaudeFile {
+        kind,
+        scope,
+        name,
+        path: path.to_string_lossy().to_string(),
+        description: Some(description),
+    })
+}
+
+/// Copy a user-level command or agent into a project's `.claude` directory
+#[command]
+pub async fn copy_claude_file_to_project(
+    kind: ClaudeFileKind,
+    name: String,
+    working_dir: String,
+    overwrite: Option<bool>,
+) -> Result<ClaudeFile, String> {
+    check_file_name(kind, &name)?;
+    let file = format!("{}.md", name);
+    let source = kind_dir(kind, SettingsScope::User, None)?.join(&file);
+    let content = fs::read_to_string(&source)
+        .map_err(|e| format!("Failed to read {}: {}", source.display(), e))?;
+    let dest = kind_dir(kind, SettingsScope::Project, Some(&working_dir))?.join(&file);
+    write_new_file(&dest, &content, overwrite.unwrap_or(false))?;
+    Ok(ClaudeFile {
+        kind,
+        scope: SettingsScope::Project,
+        name,
+        path: dest.to_string_lossy().to_string(),
+        description: frontmatter_description(&content),
+    })
+}
@@ -0,0 +1,41 @@
+// Claude Code command and agent file tests
+use crate::claude_files::{check_file_name, frontmatter_description, ClaudeFileKind};
+
+#[test]
+fn test_check_file_name() {
+    assert!(check_file_name(ClaudeFileKind::Command, "review").is_ok());
+    assert!(check_file_name(ClaudeFileKind::Command, "frontend/test_all").is_ok());
+    assert!(check_file_name(ClaudeFileKind::Agent, "code-reviewer").is_ok());
+
+    assert_eq!(
+        check_file_name(ClaudeFileKind::Agent, "team/reviewer").unwrap_err(),
+        "Agent names can't contain '/'"
+    );
+    assert!(check_file_name(ClaudeFileKind::Command, "../escape").is_err());
+    assert!(check_file_name(ClaudeFileKind::Command, "/abs").is_err());
+    assert!(check_file_name(ClaudeFileKind::Command, "a//b").is_err());
+    assert!(check_file_name(ClaudeFileKind::Command, "review.md").is_err());
+    assert!(check_file_name(ClaudeFileKind::Command, "").is_err());
+}
+
+#[test]
+fn test_frontmatter_description() {
+    let agent = "---\nname: reviewer\ndescription: Reviews diffs for bugs\ntools: Read\n---\nBody";
+    assert_eq!(
+        frontmatter_description(agent).as_deref(),
+        Some("Reviews diffs for bugs")
+    );
+    let quoted = "---\ndescription: \"Run the tests\"\n---\n";
+    assert_eq!(
+        frontmatter_description(quoted).as_deref(),
+        Some("Run the tests")
+    );
+
+    assert_eq!(frontmatter_description("---\ndescription:\n---\n"), None);
+    assert_eq!(
+        frontmatter_description("No frontmatter\ndescription: x"),
+        None
+    );
+    // An unterminated block isn't frontmatter
+    assert_eq!(frontmatter_description("---\ndescription: x\n"), None);
+}
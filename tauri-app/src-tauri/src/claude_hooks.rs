@@ -0,0 +1,310 @@
+//! Claude Code lifecycle hooks
+//!
+//! Hooks live under `hooks` in the Claude settings files, grouped by event and matcher:
+//! `{"hooks": {"PreToolUse": [{"matcher": "Bash", "hooks": [{"type": "command", ...}]}]}}`.
+//! For tool events the matcher is a regex over tool names; MCP tools are named
+//! `mcp__<server>__<tool>`.
+
+use crate::claude_code_commands::GLOBAL_PROJECT_ID;
+use crate::claude_settings::{self, SettingsScope};
+use crate::client_target::{self, ClientTarget};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use tauri::command;
+
+pub const HOOK_EVENTS: [&str; 9] = [
+    "PreToolUse",
+    "PostToolUse",
+    "Notification",
+    "UserPromptSubmit",
+    "Stop",
+    "SubagentStop",
+    "PreCompact",
+    "SessionStart",
+    "SessionEnd",
+];
+
+/// Events whose matcher is a tool-name regex
+const TOOL_EVENTS: [&str; 2] = ["PreToolUse", "PostToolUse"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct HookEntry {
+    pub event: String,
+    /// Empty when the group has no matcher (matches everything)
+    pub matcher: String,
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct MatcherValidation {
+    pub valid: bool,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+fn allowed_matchers(event: &str) -> Option<&'static [&'static str]> {
+    match event {
+        "PreCompact" => Some(&["manual", "auto"]),
+        "SessionStart" => Some(&["startup", "resume", "clear", "compact"]),
+        _ => None,
+    }
+}
+
+/// Check `matcher` for `event`. `servers` are the known MCP server names, used to flag
+/// `mcp__<server>__` patterns that can never match.
+pub(crate) fn check_matcher(event: &str, matcher: &str, servers: &[String]) -> MatcherValidation {
+    let mut result = MatcherValidation::default();
+    if !HOOK_EVENTS.contains(&event) {
+        result
+            .errors
+            .push(format!("Unknown hook event '{}'", event));
+    } else if let Some(allowed) = allowed_matchers(event) {
+        if !matcher.is_empty() && !allowed.contains(&matcher) {
+            result.errors.push(format!(
+                "{} matcher must be one of: {}",
+                event,
+                allowed.join(", ")
+            ));
+        }
+    } else if !TOOL_EVENTS.contains(&event) {
+        if !matcher.is_empty() {
+            result
+                .warnings
+                .push(format!("{} hooks ignore the matcher", event));
+        }
+    } else if matcher != "*" {
+        if let Err(e) = regex::Regex::new(matcher) {
+            result.errors.push(format!("Invalid matcher regex: {}", e));
+        }
+        if matcher.split('|').any(|part| part.is_empty()) {
+            result
+                .warnings
+                .push("Empty alternative matches every tool".to_string());
+        }
+        for part in matcher.split("mcp__").skip(1) {
+            let server: String = part
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+                .collect();
+            let server = server.split("__").next().unwrap_or_default();
+            // Patterns like `mcp__.*` don't name a server
+            if !server.is_empty()
+                && part[server.len()..].starts_with("__")
+                && !servers.iter().any(|s| s == server)
+            {
+                result
+                    .warnings
+                    .push(format!("No MCP server named '{}' is configured", server));
+            }
+        }
+    }
+    result.valid = result.errors.is_empty();
+    result
+}
+
+/// Flatten the `hooks` object into one entry per command
+pub(crate) fn list_entries(settings: &Map<String, Value>) -> Vec<HookEntry> {
+    let mut entries = Vec::new();
+    let Some(events) = settings.get("hooks").and_then(|v| v.as_object()) else {
+        return entries;
+    };
+    for (event, groups) in events {
+        for group in groups.as_array().into_iter().flatten() {
+            let matcher = group["matcher"].as_str().unwrap_or_default();
+            for hook in group["hooks"].as_array().into_iter().flatten() {
+                entries.push(HookEntry {
+                    event: event.clone(),
+                    matcher: matcher.to_string(),
+                    command: hook["command"].as_str().unwrap_or_default().to_string(),
+                    timeout: hook["timeout"].as_u64(),
+                });
+            }
+        }
+    }
+    entries
+}
+
+pub(crate) fn insert_entry(
+    settings: &mut Map<String, Value>,
+    entry: &HookEntry,
+) -> Result<(), String> {
+    if list_entries(settings)
+        .iter()
+        .any(|e| e.event == entry.event && e.matcher == entry.matcher && e.command == entry.command)
+    {
+        return Err(format!(
+            "Hook '{}' already exists for {} '{}'",
+            entry.command, entry.event, entry.matcher
+        ));
+    }
+    let hooks = settings.entry("hooks").or_insert_with(|| json!({}));
+    if !hooks.is_object() {
+        return Err("'hooks' in settings is not an object".to_string());
+    }
+    let groups = hooks
+        .as_object_mut()
+        .unwrap()
+        .entry(entry.event.clone())
+        .or_insert_with(|| json!([]));
+    let groups = groups
+        .as_array_mut()
+        .ok_or_else(|| format!("'hooks.{}' in settings is not an array", entry.event))?;
+
+    let mut hook = json!({ "type": "command", "command": entry.command });
+    if let Some(timeout) = entry.timeout {
+        hook["timeout"] = json!(timeout);
+    }
+    let existing = groups
+        .iter_mut()
+        .find(|g| g["matcher"].as_str().unwrap_or_default() == entry.matcher);
+    match existing
+        .and_then(|g| g.get_mut("hooks"))
+        .and_then(|h| h.as_array_mut())
+    {
+        Some(commands) => commands.push(hook),
+        None => {
+            let mut group = Map::new();
+            // Events without matchers are written without the key, as Claude Code does
+            if !entry.matcher.is_empty() {
+                group.insert("matcher".to_string(), json!(entry.matcher));
+            }
+            group.insert("hooks".to_string(), json!([hook]));
+            groups.push(Value::Object(group));
+        }
+    }
+    Ok(())
+}
+
+/// Remove a command, dropping groups and events left empty. Returns whether it was found.
+pub(crate) fn remove_entry(
+    settings: &mut Map<String, Value>,
+    event: &str,
+    matcher: &str,
+    command: &str,
+) -> bool {
+    let Some(events) = settings.get_mut("hooks").and_then(|v| v.as_object_mut()) else {
+        return false;
+    };
+    let Some(groups) = events.get_mut(event).and_then(|v| v.as_array_mut()) else {
+        return false;
+    };
+    let mut removed = false;
+    for group in groups.iter_mut() {
+        if group["matcher"].as_str().unwrap_or_default() != matcher {
+            continue;
+        }
+        if let Some(commands) = group.get_mut("hooks").and_then(|h| h.as_array_mut()) {
+            let before = commands.len();
+            commands.retain(|h| h["command"].as_str() != Some(command));
+            removed |= commands.len() != before;
+        }
+    }
+    groups.retain(|g| {
+        g["matcher"].as_str().unwrap_or_default() != matcher
+            || g["hooks"].as_array().is_some_and(|h| !h.is_empty())
+    });
+    if groups.is_empty() {
+        events.shift_remove(event);
+    }
+    if events.is_empty() {
+        settings.shift_remove("hooks");
+    }
+    removed
+}
+
+/// Names of the MCP servers Claude Code sees for this scope
+async fn known_servers(working_dir: Option<&str>) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut targets = vec![ClientTarget::claude_code(GLOBAL_PROJECT_ID)];
+    if let Some(dir) = working_dir.filter(|d| !d.is_empty()) {
+        targets.push(ClientTarget::claude_code(dir));
+    }
+    for target in targets {
+        if let Ok(servers) = client_target::list_servers(&target).await {
+            names.extend(servers.keys().cloned());
+        }
+    }
+    names
+}
+
+#[command]
+pub async fn list_claude_hooks(
+    scope: SettingsScope,
+    working_dir: Option<String>,
+) -> Result<Vec<HookEntry>, String> {
+    let path = claude_settings::settings_path(scope, working_dir.as_deref())?;
+    Ok(list_entries(&claude_settings::read_settings(&path)?))
+}
+
+#[command]
+pub async fn validate_hook_matcher(
+    event: String,
+    matcher: String,
+    working_dir: Option<String>,
+) -> Result<MatcherValidation, String> {
+    let servers = known_servers(working_dir.as_deref()).await;
+    Ok(check_matcher(&event, &matcher, &servers))
+}
+
+#[command]
+pub async fn add_claude_hook(
+    scope: SettingsScope,
+    working_dir: Option<String>,
+    event: String,
+    matcher: Option<String>,
+    command: String,
+    timeout: Option<u64>,
+) -> Result<Vec<HookEntry>, String> {
+    let matcher = matcher.unwrap_or_default();
+    let validation = check_matcher(
+        &event,
+        &matcher,
+        &known_servers(working_dir.as_deref()).await,
+    );
+    if !validation.valid {
+        return Err(format!("Invalid hook: {}", validation.errors.join("; ")));
+    }
+    if command.trim().is_empty() {
+        return Err("Hook command is required".to_string());
+    }
+    if timeout == Some(0) {
+        return Err("Hook timeout must be at least one second".to_string());
+    }
+
+    let path = claude_settings::settings_path(scope, working_dir.as_deref())?;
+    let mut settings = claude_settings::read_settings(&path)?;
+    insert_entry(
+        &mut settings,
+        &HookEntry {
+            event,
+            matcher,
+            command,
+            timeout,
+        },
+    )?;
+    claude_settings::write_settings(&path, &settings)?;
+    Ok(list_entries(&settings))
+}
+
+#[command]
+pub async fn remove_claude_hook(
+    scope: SettingsScope,
+    working_dir: Option<String>,
+    event: String,
+    matcher: Option<String>,
+    command: String,
+) -> Result<Vec<HookEntry>, String> {
+    let path = claude_settings::settings_path(scope, working_dir.as_deref())?;
+    let mut settings = claude_settings::read_settings(&path)?;
+    let matcher = matcher.unwrap_or_default();
+    if !remove_entry(&mut settings, &event, &matcher, &command) {
+        return Err(format!(
+            "Hook '{}' not found for {} '{}'",
+            command, event, matcher
+        ));
+    }
+    claude_settings::write_settings(&path, &settings)?;
+    Ok(list_entries(&settings))
+}
@@ -0,0 +1,66 @@
+// Claude Code hook editing and matcher validation tests
+use crate::claude_hooks::{check_matcher, insert_entry, list_entries, remove_entry, HookEntry};
+use serde_json::{json, Map, Value};
+
+fn entry(event: &str, matcher: &str, command: &str) -> HookEntry {
+    HookEntry {
+        event: event.to_string(),
+        matcher: matcher.to_string(),
+        command: command.to_string(),
+        timeout: None,
+    }
+}
+
+#[test]
+fn test_check_matcher_syntax_and_unknown_servers() {
+    let servers = vec!["github".to_string()];
+    assert!(check_matcher("PreToolUse", "Edit|Write", &servers).valid);
+    assert!(check_matcher("PreToolUse", "*", &servers).valid);
+    assert!(!check_matcher("PreToolUse", "(Edit|Write", &servers).valid);
+    assert!(!check_matcher("PreToolUse", "*Edit", &servers).valid);
+    assert!(!check_matcher("PreToolUse", "[a-z", &servers).valid);
+    assert!(!check_matcher("PreToolUse", "Bash\\", &servers).valid);
+    assert!(!check_matcher("BeforeTool", "", &servers).valid);
+    assert!(!check_matcher("SessionStart", "boot", &servers).valid);
+
+    let known = check_matcher("PreToolUse", "mcp__github__.*", &servers);
+    assert!(known.valid && known.warnings.is_empty());
+    let unknown = check_matcher("PostToolUse", "mcp__gitlab__create_issue", &servers);
+    assert!(unknown.valid);
+    assert_eq!(unknown.warnings.len(), 1);
+    assert!(check_matcher("PreToolUse", "mcp__.*", &servers)
+        .warnings
+        .is_empty());
+}
+
+#[test]
+fn test_insert_and_remove_keep_other_settings() {
+    let mut settings: Map<String, Value> =
+        json!({ "model": "sonnet" }).as_object().cloned().unwrap();
+    insert_entry(&mut settings, &entry("PreToolUse", "Bash", "lint.sh")).unwrap();
+    insert_entry(&mut settings, &entry("PreToolUse", "Bash", "audit.sh")).unwrap();
+    insert_entry(&mut settings, &entry("Stop", "", "notify.sh")).unwrap();
+    assert!(insert_entry(&mut settings, &entry("Stop", "", "notify.sh")).is_err());
+
+    assert_eq!(settings["hooks"]["PreToolUse"].as_array().unwrap().len(), 1);
+    assert!(settings["hooks"]["Stop"][0].get("matcher").is_none());
+    assert_eq!(list_entries(&settings).len(), 3);
+
+    assert!(remove_entry(&mut settings, "PreToolUse", "Bash", "lint.sh"));
+    assert!(!remove_entry(
+        &mut settings,
+        "PreToolUse",
+        "Bash",
+        "lint.sh"
+    ));
+    assert!(remove_entry(
+        &mut settings,
+        "PreToolUse",
+        "Bash",
+        "audit.sh"
+    ));
+    assert!(settings["hooks"].get("PreToolUse").is_none());
+    assert!(remove_entry(&mut settings, "Stop", "", "notify.sh"));
+    assert!(settings.get("hooks").is_none());
+    assert_eq!(settings["model"], "sonnet");
+}
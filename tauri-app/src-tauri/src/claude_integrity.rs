@@ -0,0 +1,117 @@
+//! Post-write integrity check for ~/.claude.json
+//!
+//! Claude Code keeps per-project history in the same file the app edits, so a write that
+//! drops part of the `projects` subtree is catastrophic. After every write the file is read
+//! back and everything outside the intended JSON pointer must equal the pre-write state;
+//! otherwise the caller restores its backup.
+
+use crate::json_pointer;
+use serde_json::Value;
+
+/// Most differences listed in an integrity error
+const MAX_REPORTED_DIFFERENCES: usize = 5;
+
+/// Verify that `written` differs from `before` only at the `intended` pointers.
+/// Ancestors of an intended pointer that did not exist before may be created empty.
+pub(crate) fn verify_unchanged_outside(
+    before: &Value,
+    written: &str,
+    intended: &[String],
+) -> Result<(), String> {
+    let after: Value = serde_json::from_str(written)
+        .map_err(|e| format!("written config does not parse: {}", e))?;
+    verify_values(before, after, intended)
+}
+
+pub(crate) fn verify_values(
+    before: &Value,
+    mut after: Value,
+    intended: &[String],
+) -> Result<(), String> {
+    let mut before = before.clone();
+    let mut created: Vec<&str> = intended
+        .iter()
+        .flat_map(|p| json_pointer::ancestors(p))
+        .filter(|a| before.pointer(a).is_none())
+        .collect();
+    created.sort_by_key(|a| std::cmp::Reverse(a.len()));
+    created.dedup();
+
+    for pointer in intended {
+        json_pointer::remove(&mut before, pointer);
+        json_pointer::remove(&mut after, pointer);
+    }
+    // Deepest first, so a freshly created projects[dir].mcpServers collapses fully
+    for ancestor in created {
+        let is_empty = after
+            .pointer(ancestor)
+            .and_then(|v| v.as_object())
+            .map(|m| m.is_empty())
+            .unwrap_or(false);
+        if is_empty {
+            json_pointer::remove(&mut after, ancestor);
+        }
+    }
+
+    if before == after {
+        return Ok(());
+    }
+    let differences = describe_differences(&before, &after);
+    Err(differences.join("; "))
+}
+
+/// Human-readable summary of what changed at the top level and under `projects`
+fn describe_differences(before: &Value, after: &Value) -> Vec<String> {
+    let (Some(before_map), Some(after_map)) = (before.as_object(), after.as_object()) else {
+        return vec!["config root was replaced".to_string()];
+    };
+
+    let mut differences = Vec::new();
+    for (key, old) in before_map {
+        match after_map.get(key) {
+            None => differences.push(format!("top-level '{}' was removed", key)),
+            Some(new) if key == "projects" => {
+                differences.extend(describe_projects(old, new));
+            }
+            Some(new) if new != old => differences.push(format!("top-level '{}' changed", key)),
+            _ => {}
+        }
+    }
+    for key in after_map.keys() {
+        if !before_map.contains_key(key) {
+            differences.push(format!("top-level '{}' was added", key));
+        }
+    }
+    differences.truncate(MAX_REPORTED_DIFFERENCES);
+    differences
+}
+
+fn describe_projects(before: &Value, after: &Value) -> Vec<String> {
+    let (Some(before_map), Some(after_map)) = (before.as_object(), after.as_object()) else {
+        return vec!["'projects' changed type".to_string()];
+    };
+
+    let mut differences = Vec::new();
+    if before_map.len() != after_map.len() {
+        differences.push(format!(
+            "project count changed: {} -> {}",
+            before_map.len(),
+            after_map.len()
+        ));
+    }
+    for (path, old) in before_map {
+        match after_map.get(path) {
+            None => differences.push(format!("project '{}' was removed", path)),
+            Some(new) if new != old => {
+                let old_keys = old.as_object().map(|m| m.len()).unwrap_or(0);
+                let new_keys = new.as_object().map(|m| m.len()).unwrap_or(0);
+                differences.push(format!(
+                    "project '{}' changed outside mcpServers ({} -> {} keys)",
+                    path, old_keys, new_keys
+                ));
+            }
+            _ => {}
+        }
+    }
+    differences
+}
@@ -0,0 +1,61 @@
+// Claude config integrity check tests
+use crate::claude_integrity::verify_unchanged_outside;
+use serde_json::json;
+
+fn before() -> serde_json::Value {
+    json!({
+        "numStartups": 12,
+        "mcpServers": { "fs": { "command": "npx" } },
+        "projects": {
+            "/work/app": { "history": [{ "display": "fix tests" }], "mcpServers": {} },
+            "/work/lib": { "history": [] }
+        }
+    })
+}
+
+#[test]
+fn test_valid_write_passes() {
+    let mut after = before();
+    after["mcpServers"]["git"] = json!({ "command": "uvx", "args": ["mcp-server-git"] });
+    after["projects"]["/work/lib"]["mcpServers"] = json!({ "db": { "command": "db-mcp" } });
+    let intended = [
+        "/mcpServers/git".to_string(),
+        "/projects/~1work~1lib/mcpServers/db".to_string(),
+    ];
+    let written = serde_json::to_string_pretty(&after).unwrap();
+    assert!(verify_unchanged_outside(&before(), &written, &intended).is_ok());
+}
+
+#[test]
+fn test_tampered_write_is_reported() {
+    let mut after = before();
+    after["mcpServers"]["git"] = json!({ "command": "uvx" });
+    after["numStartups"] = json!(0);
+    after["projects"]
+        .as_object_mut()
+        .unwrap()
+        .shift_remove("/work/app");
+    let written = serde_json::to_string(&after).unwrap();
+    let error = verify_unchanged_outside(&before(), &written, &["/mcpServers/git".to_string()])
+        .unwrap_err();
+    assert!(error.contains("top-level 'numStartups' changed"));
+    assert!(error.contains("project '/work/app' was removed"));
+    assert!(error.contains("project count changed: 2 -> 1"));
+}
+
+#[test]
+fn test_changes_inside_a_project_are_reported() {
+    let mut after = before();
+    after["projects"]["/work/app"]["history"] = json!([]);
+    let written = serde_json::to_string(&after).unwrap();
+    let error = verify_unchanged_outside(&before(), &written, &[]).unwrap_err();
+    assert!(error.contains("project '/work/app' changed outside mcpServers"));
+}
+
+#[test]
+fn test_truncated_write_is_rejected() {
+    let written = serde_json::to_string(&before()).unwrap();
+    let truncated = &written[..written.len() / 2];
+    let error = verify_unchanged_outside(&before(), truncated, &[]).unwrap_err();
+    assert!(error.starts_with("written config does not parse"));
+}
@@ -0,0 +1,366 @@
+//! Which ~/.claude.json the "Global" scope and every Claude Code project refer to
+//!
+//! On Windows, Claude Code may be installed natively and in one or more WSL distros, each
+//! with its own ~/.claude.json. The path used to be picked by a heuristic (native when it
+//! existed, otherwise the first distro found), so the app could edit the WSL config while the
+//! user meant Windows, and switched silently once a native config appeared. Locations now
+//! have explicit IDs, "native" and "wsl:<distro>", and the one in use is the
+//! `claude_config_location` setting. While it is unset and several configs exist, the path
+//! can't be resolved: commands fail with the candidates until the user picks one, which the
+//! frontend asks for at startup (`list_claude_config_locations` reports `needs_choice`).
+//!
+//! `spawn_location_migration` runs at startup and records the location the heuristic resolved
+//! to as long as that is unambiguous, so stored "Global" scopes (quarantine, locks, history)
+//! keep meaning the file they were created against. Once recorded, it never runs again. The
+//! frontend tags its own stored scope references with the recorded ID the same way.
+//!
+//! `explain_config_resolution` reports the same decision step by step, for support.
+
+use crate::claude_code_commands::is_global_config;
+use crate::settings::{self, load_settings};
+use crate::{demo_mode, unicode_path};
+use serde::Serialize;
+use serde_json::Value;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::command;
+
+pub const NATIVE_LOCATION: &str = "native";
+
+/// Locations found by the last probe; probing WSL shares can take seconds
+static FOUND: Mutex<Option<Vec<ClaudeConfigLocation>>> = Mutex::new(None);
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ClaudeConfigLocation {
+    /// "native" or "wsl:<distro>"
+    pub id: String,
+    pub label: String,
+    pub path: String,
+    pub exists: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct LocationReport {
+    pub locations: Vec<ClaudeConfigLocation>,
+    pub selected: Option<String>,
+    /// Several configs exist and none is selected, so Claude Code commands fail
+    pub needs_choice: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ResolutionCandidate {
+    #[serde(flatten)]
+    pub location: ClaudeConfigLocation,
+    pub chosen: bool,
+}
+
+/// Where the servers of a project live inside the chosen config
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ProjectLookup {
+    pub working_dir: String,
+    /// Key of the project in `projects`, `None` when the config has no entry for it
+    pub key: Option<String>,
+    /// "exact", "normalized" (spelled differently, see `unicode_path`) or "none"
+    pub matched: String,
+    pub servers: usize,
+}
+
+/// Every step from the candidates to the config a command would use
+#[derive(Debug, Serialize, Clone)]
+pub struct ConfigResolution {
+    pub candidates: Vec<ResolutionCandidate>,
+    /// The `claude_config_location` setting
+    pub selected: Option<String>,
+    pub chosen: Option<String>,
+    pub reason: String,
+    /// Set for a project working dir once a config is chosen and readable
+    pub project: Option<ProjectLookup>,
+}
+
+fn native_location() -> Result<ClaudeConfigLocation, String> {
+    let path = crate::demo_mode::home_dir()
+        .ok_or("Unable to find home directory")?
+        .join(".claude.json");
+    Ok(ClaudeConfigLocation {
+        id: NATIVE_LOCATION.to_string(),
+        label: if cfg!(target_os = "windows") {
+            "Windows".to_string()
+        } else {
+            "This machine".to_string()
+        },
+        exists: path.exists(),
+        path: path.display().to_string(),
+    })
+}
+
+#[cfg(target_os = "windows")]
+async fn wsl_locations() -> Vec<ClaudeConfigLocation> {
+    crate::claude_code_commands::find_wsl_claude_configs(".claude.json")
+        .await
+        .into_iter()
+        .map(|(distro, path)| ClaudeConfigLocation {
+            id: format!("wsl:{}", distro),
+            label: format!("WSL ({})", distro),
+            path: path.display().to_string(),
+            exists: true,
+        })
+        .collect()
+}
+
+#[cfg(not(target_os = "windows"))]
+async fn wsl_locations() -> Vec<ClaudeConfigLocation> {
+    Vec::new()
+}
+
+/// The native location and, on Windows, every WSL distro holding a config; WSL configs are
+/// real files, so demo mode leaves them out
+async fn probe() -> Result<Vec<ClaudeConfigLocation>, String> {
+    let mut locations = vec![native_location()?];
+    if !demo_mode::is_active() {
+        locations.extend(wsl_locations().await);
+    }
+    Ok(locations)
+}
+
+/// Known locations, probed again with `refresh` or on first use
+pub(crate) async fn locations(refresh: bool) -> Result<Vec<ClaudeConfigLocation>, String> {
+    if !refresh {
+        if let Some(found) = FOUND.lock().ok().and_then(|found| found.clone()) {
+            return Ok(found);
+        }
+    }
+    let found = probe().await?;
+    if let Ok(mut cache) = FOUND.lock() {
+        *cache = Some(found.clone());
+    }
+    Ok(found)
+}
+
+/// The config path for `selected`, or the only existing config when nothing is selected
+pub(crate) fn resolve(
+    locations: &[ClaudeConfigLocation],
+    selected: Option<&str>,
+) -> Result<PathBuf, String> {
+    if let Some(id) = selected {
+        return locations
+            .iter()
+            .find(|location| location.id == id)
+            .map(|location| PathBuf::from(&location.path))
+            .ok_or_else(|| {
+                format!(
+                    "Claude Code config location '{}' was not found; choose another one",
+                    id
+                )
+            });
+    }
+    let existing: Vec<&ClaudeConfigLocation> = locations.iter().filter(|l| l.exists).collect();
+    match existing.as_slice() {
+        [] => locations
+            .iter()
+            .find(|l| l.id == NATIVE_LOCATION)
+            .map(|l| PathBuf::from(&l.path))
+            .ok_or_else(|| "Unable to find home directory".to_string()),
+        [only] => Ok(PathBuf::from(&only.path)),
+        several => Err(format!(
+            "Claude Code configs exist in several places ({}); choose which one to manage",
+            several
+                .iter()
+                .map(|l| format!("{} at {}", l.id, l.path))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+/// Why `resolve` picks what it does for `locations` and `selected`, next to its result
+pub(crate) fn explain(
+    locations: &[ClaudeConfigLocation],
+    selected: Option<&str>,
+) -> (Result<PathBuf, String>, String) {
+    let resolved = resolve(locations, selected);
+    let chosen = resolved
+        .as_ref()
+        .ok()
+        .and_then(|path| locations.iter().find(|l| PathBuf::from(&l.path) == *path));
+    let reason = match (&resolved, chosen, selected) {
+        (Err(e), _, _) => e.clone(),
+        (Ok(_), Some(location), Some(id)) if location.exists => {
+            format!("'{}' is selected in settings (claude_config_location)", id)
+        }
+        (Ok(_), _, Some(id)) => format!(
+            "'{}' is selected in settings (claude_config_location); its file doesn't exist yet and is created on the first write",
+            id
+        ),
+        (Ok(_), Some(location), None) if location.exists => format!(
+            "No location is selected and {} holds the only config found",
+            location.id
+        ),
+        (Ok(_), _, None) => {
+            "No location is selected and no config exists yet; the native path is created on the first write"
+                .to_string()
+        }
+    };
+    (resolved, reason)
+}
+
+/// How the servers of `working_dir` are found in the config `config`
+pub(crate) fn project_lookup(config: &Value, working_dir: &str) -> ProjectLookup {
+    let projects = config.get("projects").and_then(Value::as_object);
+    let key = projects.and_then(|projects| unicode_path::find_project_key(projects, working_dir));
+    let matched = match key {
+        Some(key) if key == working_dir => "exact",
+        Some(_) => "normalized",
+        None => "none",
+    };
+    let servers = key
+        .and_then(|key| config["projects"][key]["mcpServers"].as_object())
+        .map_or(0, |servers| servers.len());
+    ProjectLookup {
+        working_dir: working_dir.to_string(),
+        key: key.map(str::to_string),
+        matched: matched.to_string(),
+        servers,
+    }
+}
+
+/// The location to record for a config resolved by the old heuristic, `None` when it was a
+/// guess between several
+pub(crate) fn legacy_location(locations: &[ClaudeConfigLocation]) -> Option<String> {
+    match locations
+        .iter()
+        .filter(|l| l.exists)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        [only] => Some(only.id.clone()),
+        _ => None,
+    }
+}
+
+/// `resolve` against the known locations from `locations(refresh)`. An unknown selection is
+/// tried again after a fresh probe: a distro that wasn't running at the last probe may be up
+/// now.
+pub(crate) async fn resolve_probing<F, Fut>(
+    selected: Option<&str>,
+    locations: F,
+) -> Result<PathBuf, String>
+where
+    F: Fn(bool) -> Fut,
+    Fut: Future<Output = Result<Vec<ClaudeConfigLocation>, String>>,
+{
+    match resolve(&locations(false).await?, selected) {
+        Err(_) if selected.is_some() => resolve(&locations(true).await?, selected),
+        resolved => resolved,
+    }
+}
+
+/// The ~/.claude.json in use
+pub(crate) async fn config_path() -> Result<PathBuf, String> {
+    let selected = load_settings().claude_config_location;
+    if selected.as_deref() == Some(NATIVE_LOCATION) {
+        return Ok(PathBuf::from(native_location()?.path));
+    }
+    resolve_probing(selected.as_deref(), locations).await
+}
+
+/// The location the startup migration records: none once one is selected, else the one the
+/// old heuristic resolved to when that wasn't a guess
+pub(crate) fn location_to_record(
+    selected: Option<&str>,
+    locations: &[ClaudeConfigLocation],
+) -> Option<String> {
+    match selected {
+        Some(_) => None,
+        None => legacy_location(locations),
+    }
+}
+
+/// Record the location in use once it is unambiguous
+pub fn spawn_location_migration() {
+    if load_settings().claude_config_location.is_some() {
+        return;
+    }
+    tauri::async_runtime::spawn(async {
+        let Ok(locations) = locations(true).await else {
+            return;
+        };
+        let selected = load_settings().claude_config_location;
+        let Some(id) = location_to_record(selected.as_deref(), &locations) else {
+            if selected.is_none() && locations.iter().filter(|l| l.exists).count() > 1 {
+                println!("[Location] Several Claude Code configs found; waiting for a choice");
+            }
+            return;
+        };
+        let mut settings = load_settings();
+        settings.claude_config_location = Some(id.clone());
+        match settings::save_settings(&settings) {
+            Ok(()) => println!("[Location] Using Claude Code config location {}", id),
+            Err(e) => println!("[Location] Failed to record location: {}", e),
+        }
+    });
+}
+
+/// The Claude Code configs found and the one in use
+#[command]
+pub async fn list_claude_config_locations() -> Result<LocationReport, String> {
+    let locations = locations(true).await?;
+    let selected = load_settings().claude_config_location;
+    let needs_choice = selected.is_none() && locations.iter().filter(|l| l.exists).count() > 1;
+    Ok(LocationReport {
+        locations,
+        selected,
+        needs_choice,
+    })
+}
+
+/// Manage the Claude Code config at `id` ("native" or "wsl:<distro>") from now on
+#[command]
+pub async fn set_claude_config_location(id: String) -> Result<LocationReport, String> {
+    let locations = locations(true).await?;
+    resolve(&locations, Some(&id))?;
+    let mut settings = load_settings();
+    settings.claude_config_location = Some(id.clone());
+    settings::save_settings(&settings)?;
+    println!("[Location] Claude Code config location set to {}", id);
+    Ok(LocationReport {
+        locations,
+        selected: Some(id),
+        needs_choice: false,
+    })
+}
+
+/// Which ~/.claude.json the commands for `working_dir` ("Global" or a project) would use and
+/// why: each candidate checked, whether it exists, the one chosen and, for a project, the key
+/// its servers are under. Resolution errors are reported instead of returned.
+#[command]
+pub async fn explain_config_resolution(working_dir: String) -> Result<ConfigResolution, String> {
+    let locations = locations(true).await?;
+    let selected = load_settings().claude_config_location;
+    let (resolved, reason) = explain(&locations, selected.as_deref());
+    let chosen = resolved.ok();
+
+    let project = match &chosen {
+        Some(path) if !is_global_config(&working_dir) => std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+            .map(|config| project_lookup(&config, &working_dir)),
+        _ => None,
+    };
+    let candidates = locations
+        .into_iter()
+        .map(|location| ResolutionCandidate {
+            chosen: chosen
+                .as_ref()
+                .is_some_and(|path| *path == PathBuf::from(&location.path)),
+            location,
+        })
+        .collect();
+    Ok(ConfigResolution {
+        candidates,
+        selected,
+        chosen: chosen.map(|path| path.display().to_string()),
+        reason,
+        project,
+    })
+}
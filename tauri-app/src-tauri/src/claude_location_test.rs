@@ -0,0 +1,139 @@
+// Claude config location tests
+use crate::claude_location::{
+    explain, legacy_location, location_to_record, project_lookup, resolve, resolve_probing,
+    ClaudeConfigLocation,
+};
+use serde_json::json;
+use std::path::PathBuf;
+
+fn location(id: &str, path: &str, exists: bool) -> ClaudeConfigLocation {
+    ClaudeConfigLocation {
+        id: id.to_string(),
+        label: id.to_string(),
+        path: path.to_string(),
+        exists,
+    }
+}
+
+#[test]
+fn test_resolve_single_config() {
+    let native_missing = [
+        location("native", r"C:\Users\me\.claude.json", false),
+        location("wsl:Ubuntu", r"\\wsl$\Ubuntu\home\me\.claude.json", true),
+    ];
+    assert_eq!(
+        resolve(&native_missing, None).unwrap(),
+        PathBuf::from(r"\\wsl$\Ubuntu\home\me\.claude.json")
+    );
+    assert_eq!(
+        legacy_location(&native_missing),
+        Some("wsl:Ubuntu".to_string())
+    );
+
+    // Nothing yet: the native path, for creation
+    let none = [location("native", "/home/me/.claude.json", false)];
+    assert_eq!(
+        resolve(&none, None).unwrap(),
+        PathBuf::from("/home/me/.claude.json")
+    );
+    assert_eq!(legacy_location(&none), None);
+}
+
+#[test]
+fn test_resolve_requires_a_choice_between_configs() {
+    let both = [
+        location("native", r"C:\Users\me\.claude.json", true),
+        location("wsl:Ubuntu", r"\\wsl$\Ubuntu\home\me\.claude.json", true),
+    ];
+    let error = resolve(&both, None).unwrap_err();
+    assert!(error.contains("native at"));
+    assert!(error.contains("wsl:Ubuntu at"));
+    assert_eq!(legacy_location(&both), None);
+
+    assert_eq!(
+        resolve(&both, Some("native")).unwrap(),
+        PathBuf::from(r"C:\Users\me\.claude.json")
+    );
+    assert!(resolve(&both, Some("wsl:Debian")).is_err());
+}
+
+#[test]
+fn test_explain_resolution() {
+    let both = [
+        location("native", "/home/me/.claude.json", true),
+        location("wsl:Ubuntu", r"\\wsl$\Ubuntu\home\me\.claude.json", true),
+    ];
+    let (resolved, reason) = explain(&both, None);
+    assert!(resolved.is_err());
+    assert!(reason.contains("several places"));
+
+    let (resolved, reason) = explain(&both, Some("wsl:Ubuntu"));
+    assert!(resolved.is_ok());
+    assert!(reason.contains("selected in settings"));
+
+    let (_, reason) = explain(&both[..1], None);
+    assert!(reason.contains("native holds the only config"));
+
+    let config = json!({
+        "projects": {
+            "/work/app/": { "mcpServers": { "files": {}, "git": {} } },
+            "/work/other": {}
+        }
+    });
+    let lookup = project_lookup(&config, "/work/app");
+    assert_eq!(lookup.key.as_deref(), Some("/work/app/"));
+    assert_eq!(lookup.matched, "normalized");
+    assert_eq!(lookup.servers, 2);
+    assert_eq!(project_lookup(&config, "/work/other").matched, "exact");
+    assert_eq!(project_lookup(&config, "/elsewhere").key, None);
+}
+
+#[tokio::test]
+async fn test_config_path_probes_again_for_a_new_distro() {
+    let cached = vec![
+        location("native", r"C:\Users\me\.claude.json", true),
+        location("wsl:Ubuntu", r"\\wsl$\Ubuntu\home\me\.claude.json", true),
+    ];
+    let mut fresh = cached.clone();
+    fresh.push(location(
+        "wsl:Debian",
+        r"\\wsl$\Debian\home\me\.claude.json",
+        true,
+    ));
+    let probe = |refresh: bool| {
+        let found = if refresh {
+            fresh.clone()
+        } else {
+            cached.clone()
+        };
+        async move { Ok::<_, String>(found) }
+    };
+
+    assert_eq!(
+        resolve_probing(Some("wsl:Debian"), probe).await.unwrap(),
+        PathBuf::from(r"\\wsl$\Debian\home\me\.claude.json")
+    );
+    assert!(resolve_probing(Some("wsl:Arch"), probe).await.is_err());
+    // Unselected with several configs stays an error instead of a guess
+    assert!(resolve_probing(None, probe).await.is_err());
+}
+
+#[test]
+fn test_migration_records_only_unambiguous_locations() {
+    let wsl_only = [
+        location("native", r"C:\Users\me\.claude.json", false),
+        location("wsl:Ubuntu", r"\\wsl$\Ubuntu\home\me\.claude.json", true),
+    ];
+    assert_eq!(
+        location_to_record(None, &wsl_only),
+        Some("wsl:Ubuntu".to_string())
+    );
+    // Never overrides a choice
+    assert_eq!(location_to_record(Some("native"), &wsl_only), None);
+
+    let both = [
+        location("native", r"C:\Users\me\.claude.json", true),
+        location("wsl:Ubuntu", r"\\wsl$\Ubuntu\home\me\.claude.json", true),
+    ];
+    assert_eq!(location_to_record(None, &both), None);
+}
@@ -0,0 +1,222 @@
+//! CLAUDE.md memory files with a generated "tools available" section
+//!
+//! The section listing MCP servers sits between marker comments, so syncing rewrites only that
+//! block and leaves the rest of the file as the user wrote it. Server entries show how each
+//! server is reached and never include env values or headers.
+
+use crate::claude_code_commands::GLOBAL_PROJECT_ID;
+use crate::claude_settings::{self, SettingsScope};
+use crate::client_target::{self, ClientTarget};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+const SECTION_START: &str = "<!-- mcp-linker:servers:start -->";
+const SECTION_END: &str = "<!-- mcp-linker:servers:end -->";
+const SERVERS_PLACEHOLDER: &str = "{{servers}}";
+
+#[derive(Debug, Serialize, Clone)]
+pub struct MemoryTemplate {
+    pub id: &'static str,
+    pub description: &'static str,
+    #[serde(skip)]
+    body: &'static str,
+}
+
+const TEMPLATES: [MemoryTemplate; 2] = [
+    MemoryTemplate {
+        id: "minimal",
+        description: "Only the generated tools section",
+        body: "# CLAUDE.md\n\n{{servers}}\n",
+    },
+    MemoryTemplate {
+        id: "project",
+        description: "Project overview, commands and conventions, plus the tools section",
+        body: "# Project\n\nDescribe what this project does and how it is organized.\n\n## Commands\n\n- Build:\n- Test:\n- Lint:\n\n## Conventions\n\n- \n\n{{servers}}\n",
+    },
+];
+
+#[derive(Debug, Serialize, Clone)]
+pub struct MemoryFile {
+    pub path: String,
+    pub content: String,
+    /// Whether the file changed on disk
+    pub written: bool,
+}
+
+pub(crate) fn memory_path(
+    scope: SettingsScope,
+    working_dir: Option<&str>,
+) -> Result<PathBuf, String> {
+    let claude_dir = claude_settings::claude_dir(scope, working_dir)?;
+    Ok(match scope {
+        SettingsScope::User => claude_dir.join("CLAUDE.md"),
+        // Project memory sits at the repo root, next to `.claude/`
+        SettingsScope::Project => claude_dir.with_file_name("CLAUDE.md"),
+        SettingsScope::Local => claude_dir.with_file_name("CLAUDE.local.md"),
+    })
+}
+
+/// Host (and port) of a server URL; paths, queries and userinfo can carry keys, and CLAUDE.md
+/// is often committed
+fn url_host(url: &str) -> String {
+    url::Url::parse(url)
+        .ok()
+        .and_then(|parsed| {
+            let host = parsed.host_str()?.to_string();
+            Some(match parsed.port() {
+                Some(port) => format!("{}:{}", host, port),
+                None => host,
+            })
+        })
+        .unwrap_or_else(|| "a remote host".to_string())
+}
+
+fn describe_server(name: &str, config: &Value) -> String {
+    let transport = config["type"].as_str().unwrap_or("stdio");
+    let reach = match (config["url"].as_str(), config["command"].as_str()) {
+        (Some(url), _) => format!("{} at {}", transport, url_host(url)),
+        (None, Some(command)) => format!("stdio, runs `{}`", command),
+        _ => transport.to_string(),
+    };
+    format!("- **{}** ({}), tools named `mcp__{}__*`", name, reach, name)
+}
+
+/// The marked section for the given servers
+pub(crate) fn render_section(servers: &Map<String, Value>) -> String {
+    let mut lines = vec![
+        SECTION_START.to_string(),
+        "## MCP tools available".to_string(),
+        String::new(),
+    ];
+    if servers.is_empty() {
+        lines.push("No MCP servers are configured.".to_string());
+    } else {
+        lines.push(
+            "These MCP servers are configured; prefer their tools over shell workarounds:"
+                .to_string(),
+        );
+        lines.push(String::new());
+        lines.extend(
+            servers
+                .iter()
+                .map(|(name, config)| describe_server(name, config)),
+        );
+    }
+    lines.push(SECTION_END.to_string());
+    lines.join("\n")
+}
+
+/// The marked section of `content`, if it has one
+pub(crate) fn section(content: &str) -> Option<&str> {
+    let start = content.find(SECTION_START)?;
+    let end = content.find(SECTION_END)?;
+    (start < end).then(|| &content[start..end + SECTION_END.len()])
+}
+
+/// Replace the marked section, or append it when the file has none
+pub(crate) fn replace_section(content: &str, section: &str) -> String {
+    if let (Some(start), Some(end)) = (content.find(SECTION_START), content.find(SECTION_END)) {
+        if start < end {
+            let end = end + SECTION_END.len();
+            return format!("{}{}{}", &content[..start], section, &content[end..]);
+        }
+    }
+    let trimmed = content.trim_end();
+    if trimmed.is_empty() {
+        format!("{}\n", section)
+    } else {
+        format!("{}\n\n{}\n", trimmed, section)
+    }
+}
+
+/// Servers Claude Code loads for the scope: user servers, plus the project's own for project
+/// and local scope
+async fn scope_servers(
+    scope: SettingsScope,
+    working_dir: Option<&str>,
+) -> Result<Map<String, Value>, String> {
+    let mut servers =
+        client_target::list_servers(&ClientTarget::claude_code(GLOBAL_PROJECT_ID)).await?;
+    if scope != SettingsScope::User {
+        let dir = working_dir.ok_or("Project and local scopes need a working_dir")?;
+        servers.extend(client_target::list_servers(&ClientTarget::claude_code(dir)).await?);
+    }
+    servers.sort_keys();
+    Ok(servers)
+}
+
+pub(crate) fn read_memory(path: &Path) -> Result<Option<String>, String> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+    }
+}
+
+fn write_memory(path: &Path, content: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+#[command]
+pub async fn list_claude_memory_templates() -> Result<Vec<MemoryTemplate>, String> {
+    Ok(TEMPLATES.to_vec())
+}
+
+/// Create CLAUDE.md for the scope from a template. Fails if the file already exists.
+#[command]
+pub async fn create_claude_memory(
+    scope: SettingsScope,
+    working_dir: Option<String>,
+    template: Option<String>,
+) -> Result<MemoryFile, String> {
+    let template_id = template.as_deref().unwrap_or("project");
+    let template = TEMPLATES
+        .iter()
+        .find(|t| t.id == template_id)
+        .ok_or_else(|| format!("Unknown template '{}'", template_id))?;
+    let path = memory_path(scope, working_dir.as_deref())?;
+    if path.exists() {
+        return Err(format!(
+            "{} already exists; sync it instead",
+            path.display()
+        ));
+    }
+    let section = render_section(&scope_servers(scope, working_dir.as_deref()).await?);
+    let content = template.body.replace(SERVERS_PLACEHOLDER, &section);
+    write_memory(&path, &content)?;
+    Ok(MemoryFile {
+        path: path.to_string_lossy().to_string(),
+        content,
+        written: true,
+    })
+}
+
+/// Regenerate the tools section of the scope's CLAUDE.md, creating the file if needed.
+/// With `dry_run` the new content is returned without writing it.
+#[command]
+pub async fn sync_claude_memory(
+    scope: SettingsScope,
+    working_dir: Option<String>,
+    dry_run: Option<bool>,
+) -> Result<MemoryFile, String> {
+    let path = memory_path(scope, working_dir.as_deref())?;
+    let current = read_memory(&path)?.unwrap_or_default();
+    let section = render_section(&scope_servers(scope, working_dir.as_deref()).await?);
+    let content = replace_section(&current, &section);
+    let written = content != current && !dry_run.unwrap_or(false);
+    if written {
+        write_memory(&path, &content)?;
+    }
+    Ok(MemoryFile {
+        path: path.to_string_lossy().to_string(),
+        content,
+        written,
+    })
+}
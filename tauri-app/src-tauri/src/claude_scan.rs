@@ -0,0 +1,246 @@
+//! Config scanning layer shared by the Claude Code commands and client detection
+//!
+//! Config files may live on slow mounts (`\\wsl$` shares on Windows), so reads go through
+//! tokio::fs / spawn_blocking instead of blocking the async task, independent sources are read
+//! concurrently, and parsed documents are cached by (path, mtime, size) so repeated list calls
+//! don't re-parse a multi-megabyte ~/.claude.json. Every read records its timing, which is
+//! surfaced through `get_diagnostics`.
+
+use crate::client::ClientConfig;
+use crate::client_plugins::ConfigFormat;
+use crate::json_manager::utils::get_key_by_client;
+use crate::platform_access;
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Instant, SystemTime};
+use tauri::command;
+
+struct CachedDocument {
+    modified: Option<SystemTime>,
+    len: u64,
+    value: Arc<Value>,
+}
+
+static PARSE_CACHE: Lazy<Mutex<HashMap<PathBuf, CachedDocument>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+static SCAN_TIMINGS: Lazy<Mutex<HashMap<String, SourceTiming>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Timing of the most recent read of one config source
+#[derive(Debug, Serialize, Clone)]
+pub struct SourceTiming {
+    pub source: String,
+    pub path: String,
+    pub duration_ms: f64,
+    pub cache_hit: bool,
+    pub exists: bool,
+    pub error: Option<String>,
+    pub scanned_at: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DetectedClient {
+    pub client: String,
+    pub path: String,
+    pub exists: bool,
+    pub server_count: usize,
+    pub error: Option<String>,
+}
+
+pub(crate) fn record_timing(
+    source: &str,
+    path: &Path,
+    started: Instant,
+    cache_hit: bool,
+    exists: bool,
+    error: Option<String>,
+) {
+    let timing = SourceTiming {
+        source: source.to_string(),
+        path: path.to_string_lossy().to_string(),
+        duration_ms: started.elapsed().as_secs_f64() * 1000.0,
+        cache_hit,
+        exists,
+        error,
+        scanned_at: Utc::now().to_rfc3339(),
+    };
+    if let Ok(mut timings) = SCAN_TIMINGS.lock() {
+        timings.insert(source.to_string(), timing);
+    }
+}
+
+/// Timings of the last read per source, sorted by source name
+pub(crate) fn scan_timings() -> Vec<SourceTiming> {
+    let mut timings: Vec<SourceTiming> = SCAN_TIMINGS
+        .lock()
+        .map(|t| t.values().cloned().collect())
+        .unwrap_or_default();
+    timings.sort_by(|a, b| a.source.cmp(&b.source));
+    timings
+}
+
+/// Drop the cached parse of `path`; called after every write so a same-second rewrite with an
+/// identical size is never served stale.
+pub(crate) fn invalidate(path: &Path) {
+    if let Ok(mut cache) = PARSE_CACHE.lock() {
+        cache.remove(path);
+    }
+}
+
+/// Non-blocking existence check
+pub(crate) async fn path_exists(path: &Path) -> bool {
+    tokio::fs::metadata(path).await.is_ok()
+}
+
+/// Read and parse a JSON config through the shared cache.
+/// Returns `Ok(None)` when the file does not exist.
+pub(crate) async fn load_json_cached(
+    source: &str,
+    path: &Path,
+) -> Result<Option<Arc<Value>>, String> {
+    let started = Instant::now();
+    let result = read_or_reuse(path).await;
+    match &result {
+        Ok(Some((_, hit))) => record_timing(source, path, started, *hit, true, None),
+        Ok(None) => record_timing(source, path, started, false, false, None),
+        Err(e) => record_timing(source, path, started, false, true, Some(e.clone())),
+    }
+    result.map(|r| r.map(|(value, _)| value))
+}
+
+async fn read_or_reuse(path: &Path) -> Result<Option<(Arc<Value>, bool)>, String> {
+    let metadata = match tokio::fs::metadata(path).await {
+        Ok(m) => m,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(platform_access::read_error(path, &e)),
+    };
+    let modified = metadata.modified().ok();
+    let len = metadata.len();
+
+    if let Ok(cache) = PARSE_CACHE.lock() {
+        if let Some(cached) = cache.get(path) {
+            if cached.modified == modified && cached.len == len && modified.is_some() {
+                return Ok(Some((cached.value.clone(), true)));
+            }
+        }
+    }
+
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| platform_access::read_error(path, &e))?;
+    let value: Value = tokio::task::spawn_blocking(move || {
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse config: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Failed to run blocking task for JSON parsing: {}", e))??;
+
+    let value = Arc::new(value);
+    if let Ok(mut cache) = PARSE_CACHE.lock() {
+        cache.insert(
+            path.to_path_buf(),
+            CachedDocument {
+                modified,
+                len,
+                value: value.clone(),
+            },
+        );
+    }
+    Ok(Some((value, false)))
+}
+
+/// Detect which clients have a config on this machine, reading all of them concurrently
+#[command]
+pub async fn detect_clients(clients: Vec<String>) -> Result<Vec<DetectedClient>, String> {
+    futures::future::join_all(clients.into_iter().map(detect_client))
+        .await
+        .into_iter()
+        .collect()
+}
+
+async fn detect_client(client: String) -> Result<DetectedClient, String> {
+    match client.as_str() {
+        "claude_code" => {
+            let path = crate::claude_code_commands::get_claude_config_path(None).await?;
+            let doc = load_json_cached(&client, &path).await;
+            Ok(detected(client, path, doc, "/mcpServers"))
+        }
+        "codex" => {
+            let path = crate::config::get_config_path()?;
+            let started = Instant::now();
+            let servers = crate::codex::read_mcp_servers().await;
+            let exists = path_exists(&path).await;
+            record_timing(
+                &client,
+                &path,
+                started,
+                false,
+                exists,
+                servers.as_ref().err().cloned(),
+            );
+            Ok(DetectedClient {
+                client,
+                path: path.to_string_lossy().to_string(),
+                exists,
+                server_count: servers.as_ref().map(|s| s.len()).unwrap_or(0),
+                error: servers.err(),
+            })
+        }
+        _ => {
+            let path = ClientConfig::new(&client, None).get_path().to_path_buf();
+            if path.as_os_str().is_empty() {
+                // Client has no config location on this OS
+                return Ok(detected(client, path, Ok(None), "/mcpServers"));
+            }
+            let manifest = crate::client_plugins::manifest(&client);
+            let doc = match &manifest {
+                Some(manifest) if manifest.format != ConfigFormat::Json => {
+                    crate::client_plugins::load_config(manifest, &path)
+                        .await
+                        .map(|doc| doc.map(Arc::new))
+                }
+                _ => load_json_cached(&client, &path).await,
+            };
+            let pointer = match manifest {
+                Some(manifest) => manifest.servers_pointer,
+                None => crate::json_pointer::join(&[get_key_by_client(&client)]),
+            };
+            Ok(detected(client, path, doc, &pointer))
+        }
+    }
+}
+
+/// `servers` is the JSON pointer of the servers map
+fn detected(
+    client: String,
+    path: PathBuf,
+    doc: Result<Option<Arc<Value>>, String>,
+    servers: &str,
+) -> DetectedClient {
+    let (exists, server_count, error) = match doc {
+        Ok(Some(value)) => (
+            true,
+            value
+                .pointer(servers)
+                .and_then(|v| v.as_object())
+                .map(|m| m.len())
+                .unwrap_or(0),
+            None,
+        ),
+        Ok(None) => (false, 0, None),
+        Err(e) => (true, 0, Some(e)),
+    };
+    DetectedClient {
+        client,
+        path: path.to_string_lossy().to_string(),
+        exists,
+        server_count,
+        error,
+    }
+}
@@ -0,0 +1,62 @@
+// Config scan cache tests
+use crate::claude_scan::{invalidate, load_json_cached, scan_timings};
+use serde_json::json;
+use std::fs;
+
+fn timing(source: &str) -> crate::claude_scan::SourceTiming {
+    scan_timings()
+        .into_iter()
+        .find(|t| t.source == source)
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_parse_cache_hits_until_the_file_changes() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join("config.json");
+    fs::write(&path, r#"{"mcpServers":{"fs":{}}}"#).unwrap();
+
+    let first = load_json_cached("scan-test-cache", &path)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(first["mcpServers"], json!({ "fs": {} }));
+    assert!(!timing("scan-test-cache").cache_hit);
+
+    load_json_cached("scan-test-cache", &path).await.unwrap();
+    assert!(timing("scan-test-cache").cache_hit);
+
+    // A different size is a different document even within the same mtime tick
+    fs::write(&path, r#"{"mcpServers":{"fs":{},"git":{}}}"#).unwrap();
+    let changed = load_json_cached("scan-test-cache", &path)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(changed["mcpServers"].get("git").is_some());
+    assert!(!timing("scan-test-cache").cache_hit);
+
+    invalidate(&path);
+    load_json_cached("scan-test-cache", &path).await.unwrap();
+    assert!(!timing("scan-test-cache").cache_hit);
+}
+
+#[tokio::test]
+async fn test_missing_and_invalid_files() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let missing = temp_dir.path().join("missing.json");
+    assert!(load_json_cached("scan-test-missing", &missing)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(!timing("scan-test-missing").exists);
+
+    let invalid = temp_dir.path().join("invalid.json");
+    fs::write(&invalid, "{ not json").unwrap();
+    let error = load_json_cached("scan-test-invalid", &invalid)
+        .await
+        .unwrap_err();
+    assert!(error.starts_with("Failed to parse config"));
+    let timing = timing("scan-test-invalid");
+    assert!(timing.exists);
+    assert_eq!(timing.error, Some(error));
+}
@@ -0,0 +1,70 @@
+//! Claude Code settings.json files
+//!
+//! Claude Code reads settings from ~/.claude/settings.json (user), <project>/.claude/
+//! settings.json (project, usually committed) and <project>/.claude/settings.local.json
+//! (local, git-ignored). Writers here only touch the keys they manage and keep the rest of
+//! the file as it was.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SettingsScope {
+    User,
+    Project,
+    Local,
+}
+
+/// The `.claude` directory of a scope
+pub(crate) fn claude_dir(
+    scope: SettingsScope,
+    working_dir: Option<&str>,
+) -> Result<PathBuf, String> {
+    match (scope, working_dir) {
+        (SettingsScope::User, _) => {
+            let home = crate::demo_mode::home_dir().ok_or("Unable to find home directory")?;
+            Ok(home.join(".claude"))
+        }
+        (_, Some(dir)) if !dir.is_empty() => Ok(PathBuf::from(dir).join(".claude")),
+        _ => Err("Project and local scopes need a working_dir".to_string()),
+    }
+}
+
+pub(crate) fn settings_path(
+    scope: SettingsScope,
+    working_dir: Option<&str>,
+) -> Result<PathBuf, String> {
+    let file = match scope {
+        SettingsScope::Local => "settings.local.json",
+        _ => "settings.json",
+    };
+    Ok(claude_dir(scope, working_dir)?.join(file))
+}
+
+/// Settings object of a scope; empty when the file doesn't exist yet
+pub(crate) fn read_settings(path: &Path) -> Result<Map<String, Value>, String> {
+    match fs::read_to_string(path) {
+        Ok(content) if content.trim().is_empty() => Ok(Map::new()),
+        Ok(content) => match serde_json::from_str(&content) {
+            Ok(Value::Object(map)) => Ok(map),
+            Ok(_) => Err(format!("{} is not a JSON object", path.display())),
+            Err(e) => Err(format!("Failed to parse {}: {}", path.display(), e)),
+        },
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Map::new()),
+        Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+    }
+}
+
+pub(crate) fn write_settings(path: &Path, settings: &Map<String, Value>) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let content = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize Claude settings: {}", e))?;
+    fs::write(path, content + "\n")
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
@@ -0,0 +1,175 @@
+//! Size breakdown of ~/.claude.json
+//!
+//! Claude Code keeps history, feature-flag caches and per-project state in the same file as the
+//! MCP server config, so it grows into the megabytes and every read/write slows down. This
+//! reports where the bytes go and removes the sections Claude Code treats as refetchable caches.
+
+use crate::claude_code_commands::{
+    create_backup, get_claude_config_path, serialize_claude_config, write_claude_config_verified,
+    GLOBAL_PROJECT_ID,
+};
+use crate::{claude_scan, json_pointer};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fs;
+use tauri::command;
+
+/// Top-level keys with this prefix hold caches (changelog, feature gates, dynamic configs)
+/// that Claude Code refetches on its next start
+const CACHE_KEY_PREFIX: &str = "cached";
+
+/// Number of largest projects listed in the report
+const MAX_LISTED_PROJECTS: usize = 10;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SectionSize {
+    pub key: String,
+    pub bytes: usize,
+    pub cleanable: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ConfigSizeReport {
+    pub path: String,
+    pub total_bytes: u64,
+    /// Top-level sections, largest first
+    pub sections: Vec<SectionSize>,
+    /// Largest entries under `projects`
+    pub largest_projects: Vec<SectionSize>,
+    pub project_count: usize,
+    /// Breakdown of the requested project's keys (history, mcpServers, ...)
+    pub project_sections: Option<Vec<SectionSize>>,
+    pub cleanable_bytes: usize,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CacheCleanupResult {
+    pub removed: Vec<String>,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    pub backup_path: String,
+}
+
+/// Report how many bytes each section of ~/.claude.json takes.
+/// If working_dir is a project path, that project's keys are broken down as well.
+#[command]
+pub async fn analyze_config_size(working_dir: String) -> Result<ConfigSizeReport, String> {
+    let path = get_claude_config_path(None).await?;
+    let config = claude_scan::load_json_cached("claude_code", &path)
+        .await?
+        .ok_or("Claude config file not found")?;
+    let total_bytes = tokio::fs::metadata(&path)
+        .await
+        .map(|m| m.len())
+        .map_err(|e| format!("Failed to read config metadata: {}", e))?;
+
+    let root = config
+        .as_object()
+        .ok_or("Claude config root is not an object")?;
+    let sections = measure(root, is_cache_key);
+    let cleanable_bytes = sections
+        .iter()
+        .filter(|s| s.cleanable)
+        .map(|s| s.bytes)
+        .sum();
+
+    let projects = root.get("projects").and_then(|p| p.as_object());
+    let mut largest_projects = projects.map(|p| measure(p, |_| false)).unwrap_or_default();
+    largest_projects.truncate(MAX_LISTED_PROJECTS);
+
+    let project_sections = if working_dir == GLOBAL_PROJECT_ID {
+        None
+    } else {
+        projects
+            .and_then(|p| p.get(&working_dir))
+            .and_then(|p| p.as_object())
+            .map(|p| measure(p, |_| false))
+    };
+
+    Ok(ConfigSizeReport {
+        path: path.to_string_lossy().to_string(),
+        total_bytes,
+        sections,
+        largest_projects,
+        project_count: projects.map(|p| p.len()).unwrap_or(0),
+        project_sections,
+        cleanable_bytes,
+    })
+}
+
+/// Remove cache sections from ~/.claude.json. The backup is kept so the cleanup can be undone.
+#[command]
+pub async fn cleanup_config_cache(keys: Vec<String>) -> Result<CacheCleanupResult, String> {
+    if let Some(key) = keys.iter().find(|k| !is_cache_key(k)) {
+        return Err(format!(
+            "'{}' is not a cache section and cannot be cleaned",
+            key
+        ));
+    }
+
+    let path = get_claude_config_path(None).await?;
+    if !path.exists() {
+        return Err("Claude config file not found".to_string());
+    }
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read Claude config: {}", e))?;
+    let mut config: Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse Claude config: {}", e))?;
+    let before = config.clone();
+
+    let root = config
+        .as_object_mut()
+        .ok_or("Claude config root is not an object")?;
+    let removed: Vec<String> = keys
+        .into_iter()
+        .filter(|k| root.shift_remove(k).is_some())
+        .collect();
+    if removed.is_empty() {
+        return Err("None of the requested cache sections exist".to_string());
+    }
+
+    let backup = create_backup(&path)?;
+    let intended: Vec<String> = removed
+        .iter()
+        .map(|k| json_pointer::join(&[k.as_str()]))
+        .collect();
+    write_claude_config_verified(&path, Some(&before), &config, &intended, Some(&backup))?;
+
+    let bytes_after = serialize_claude_config(&config)?.len() as u64;
+    println!(
+        "[ConfigSize] Removed {:?}, {} -> {} bytes (backup: {})",
+        removed,
+        content.len(),
+        bytes_after,
+        backup.path.display()
+    );
+    Ok(CacheCleanupResult {
+        removed,
+        bytes_before: content.len() as u64,
+        bytes_after,
+        backup_path: backup.path.to_string_lossy().to_string(),
+    })
+}
+
+pub(crate) fn is_cache_key(key: &str) -> bool {
+    key.starts_with(CACHE_KEY_PREFIX)
+}
+
+/// Serialized size of every entry in `map`, largest first
+pub(crate) fn measure(
+    map: &Map<String, Value>,
+    cleanable: impl Fn(&str) -> bool,
+) -> Vec<SectionSize> {
+    let mut sizes: Vec<SectionSize> = map
+        .iter()
+        .map(|(key, value)| SectionSize {
+            key: key.clone(),
+            bytes: serde_json::to_string_pretty(value)
+                .map(|s| s.len())
+                .unwrap_or(0),
+            cleanable: cleanable(key),
+        })
+        .collect();
+    sizes.sort_by(|a, b| b.bytes.cmp(&a.bytes));
+    sizes
+}
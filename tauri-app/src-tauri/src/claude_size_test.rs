@@ -0,0 +1,37 @@
+// Claude config size report tests
+use crate::claude_size::{cleanup_config_cache, is_cache_key, measure};
+use serde_json::json;
+
+#[test]
+fn test_measure_sorts_largest_first() {
+    let config = json!({
+        "numStartups": 3,
+        "cachedChangelog": "## 2.0.0\n- a changelog Claude Code fetches again on its next start, so it can go\n",
+        "projects": { "/work/app": { "history": ["one", "two"] } }
+    });
+    let sections = measure(config.as_object().unwrap(), is_cache_key);
+    let keys: Vec<&str> = sections.iter().map(|s| s.key.as_str()).collect();
+    assert_eq!(keys, ["cachedChangelog", "projects", "numStartups"]);
+    assert_eq!(sections[2].bytes, 1);
+    assert!(sections[0].cleanable);
+    assert!(!sections[1].cleanable);
+}
+
+#[test]
+fn test_cache_keys() {
+    assert!(is_cache_key("cachedStatsigGates"));
+    assert!(is_cache_key("cachedDynamicConfigs"));
+    assert!(!is_cache_key("projects"));
+    assert!(!is_cache_key("mcpServers"));
+}
+
+#[tokio::test]
+async fn test_cleanup_refuses_non_cache_sections() {
+    let error = cleanup_config_cache(vec!["cachedChangelog".to_string(), "projects".to_string()])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        "'projects' is not a cache section and cannot be cleaned"
+    );
+}
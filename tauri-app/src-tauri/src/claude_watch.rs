@@ -0,0 +1,168 @@
+//! Incremental Claude Code project list updates
+//!
+//! `subscribe_projects` returns the current project list once and starts watching
+//! ~/.claude.json. Every later change, once the write burst has settled, is diffed against the
+//! last list the frontend saw and published as `projects_added` / `projects_removed`, so the
+//! UI stays current when Claude Code registers a project mid-session without polling the full
+//! list. Choosing another Claude Code config location moves the watch to that config.
+
+use crate::claude_code_commands::{claude_list_projects, get_claude_config_path};
+use crate::event_bus::{self, AppEventKind, EventScope};
+use crate::settings;
+use crate::state::ProjectWatchState;
+use crate::watch_debounce::Debouncer;
+use notify::{recommended_watcher, Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::sync::{Arc, Once};
+use tauri::{AppHandle, State};
+use tokio::sync::Mutex;
+
+#[tauri::command]
+pub async fn subscribe_projects(
+    app: AppHandle,
+    state: State<'_, ProjectWatchState>,
+) -> Result<Vec<String>, String> {
+    let projects = claude_list_projects().await?;
+    *state.known.lock().await = projects.iter().cloned().collect();
+
+    let mut slot = state.watcher.lock().await;
+    if let Some((_, count)) = slot.as_mut() {
+        *count += 1;
+        return Ok(projects);
+    }
+    let watcher = watch_config(app.clone(), state.known.clone()).await?;
+    *slot = Some((watcher, 1));
+    follow_location(app, state.watcher.clone(), state.known.clone());
+    Ok(projects)
+}
+
+type WatcherSlot = Arc<Mutex<Option<(RecommendedWatcher, usize)>>>;
+
+/// Watch the Claude Code config in use, diffing the project list after each change
+async fn watch_config(
+    app: AppHandle,
+    known: Arc<Mutex<BTreeSet<String>>>,
+) -> Result<RecommendedWatcher, String> {
+    // Claude Code replaces the file on write, so watch its directory and filter by name
+    let config_path = get_claude_config_path(None).await?;
+    let watch_dir = config_path
+        .parent()
+        .ok_or_else(|| "Invalid Claude config path".to_string())?
+        .to_path_buf();
+    let file_name = config_path.file_name().map(|n| n.to_os_string());
+
+    // A rewrite burst is diffed once, after the file has settled
+    let debouncer = Debouncer::new(
+        |_, _| (),
+        move |_, ()| {
+            let app = app.clone();
+            let known = known.clone();
+            tauri::async_runtime::spawn(async move {
+                emit_project_changes(&app, &known).await;
+            });
+        },
+    );
+    let mut watcher: RecommendedWatcher =
+        recommended_watcher(move |res: Result<Event, notify::Error>| {
+            if let Ok(event) = res {
+                let touches_config = event
+                    .paths
+                    .iter()
+                    .any(|p| p.file_name().map(|n| n.to_os_string()) == file_name);
+                if touches_config {
+                    debouncer.push(config_path.clone(), ());
+                }
+            }
+        })
+        .map_err(|e| format!("Failed to create watcher: {}", e))?;
+
+    watcher
+        .watch(&watch_dir, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to start watcher: {}", e))?;
+    Ok(watcher)
+}
+
+/// Move a running watch to the config of a newly chosen location and diff the projects there
+fn follow_location(app: AppHandle, slot: WatcherSlot, known: Arc<Mutex<BTreeSet<String>>>) {
+    static FOLLOWING: Once = Once::new();
+    FOLLOWING.call_once(|| {
+        settings::on_change(
+            |settings| settings.claude_config_location.clone(),
+            move |_| {
+                let (app, slot, known) = (app.clone(), slot.clone(), known.clone());
+                async move {
+                    let mut slot = slot.lock().await;
+                    let Some((watcher, _)) = slot.as_mut() else {
+                        return;
+                    };
+                    match watch_config(app.clone(), known.clone()).await {
+                        Ok(moved) => *watcher = moved,
+                        Err(e) => {
+                            println!("[ProjectWatch] Keeping the previous watch: {}", e);
+                            return;
+                        }
+                    }
+                    drop(slot);
+                    emit_project_changes(&app, &known).await;
+                }
+            },
+        );
+    });
+}
+
+#[tauri::command]
+pub async fn unsubscribe_projects(state: State<'_, ProjectWatchState>) -> Result<(), String> {
+    let mut slot = state.watcher.lock().await;
+    if let Some((_, count)) = slot.as_mut() {
+        if *count > 1 {
+            *count -= 1;
+            return Ok(());
+        }
+    }
+    // Dropping the watcher stops it
+    *slot = None;
+    Ok(())
+}
+
+/// Projects added to and removed from `known` to get `current`
+pub(crate) fn project_changes(
+    known: &BTreeSet<String>,
+    current: &BTreeSet<String>,
+) -> (Vec<String>, Vec<String>) {
+    (
+        current.difference(known).cloned().collect(),
+        known.difference(current).cloned().collect(),
+    )
+}
+
+async fn emit_project_changes(app: &AppHandle, known: &Arc<Mutex<BTreeSet<String>>>) {
+    // Held across the re-read so bursts of events are diffed one after another
+    let mut known = known.lock().await;
+    let current: BTreeSet<String> = match claude_list_projects().await {
+        Ok(projects) => projects.into_iter().collect(),
+        Err(e) => {
+            // Usually a partially written file; the next event will catch up
+            log::debug!("[ProjectWatch] skip change: {}", e);
+            return;
+        }
+    };
+
+    let (added, removed) = project_changes(&known, &current);
+    if !added.is_empty() {
+        event_bus::publish(
+            app,
+            AppEventKind::ProjectsAdded,
+            EventScope::client("claude_code"),
+            &added,
+        );
+    }
+    if !removed.is_empty() {
+        event_bus::publish(
+            app,
+            AppEventKind::ProjectsRemoved,
+            EventScope::client("claude_code"),
+            &removed,
+        );
+    }
+    *known = current;
+}
@@ -0,0 +1,25 @@
+// Claude Code project watch tests
+use crate::claude_watch::project_changes;
+use std::collections::BTreeSet;
+
+fn set(projects: &[&str]) -> BTreeSet<String> {
+    projects.iter().map(|p| p.to_string()).collect()
+}
+
+#[test]
+fn test_project_changes() {
+    let known = set(&["/work/app", "/work/lib"]);
+    let current = set(&["/work/app", "/work/new", "/work/other"]);
+    let (added, removed) = project_changes(&known, &current);
+    assert_eq!(added, ["/work/new", "/work/other"]);
+    assert_eq!(removed, ["/work/lib"]);
+}
+
+#[test]
+fn test_unchanged_projects_report_nothing() {
+    let known = set(&["/work/app"]);
+    let (added, removed) = project_changes(&known, &known.clone());
+    assert!(added.is_empty() && removed.is_empty());
+    let (added, _) = project_changes(&BTreeSet::new(), &known);
+    assert_eq!(added, ["/work/app"]);
+}
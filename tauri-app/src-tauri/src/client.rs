@@ -1,4 +1,4 @@
-use dirs::home_dir;
+use crate::demo_mode::home_dir;
 use std::path::{Path, PathBuf};
 
 pub struct ClientConfig {
@@ -9,6 +9,11 @@ impl ClientConfig {
     pub fn new(name: &str, path: Option<&str>) -> Self {
         let home = home_dir().expect("Failed to get home directory");
 
+        if let Some(manifest) = crate::client_plugins::manifest(name) {
+            let path = crate::client_plugins::resolve_path(&manifest, path).unwrap_or_default();
+            return Self { path };
+        }
+
         let path = match (name, path) {
             ("plux", _) => home.join(".config/plux/mcp.json"),
             ("claude", _) => Self::claude_config_path(&home),
@@ -20,13 +25,17 @@ impl ClientConfig {
             ("vscode", Some(base_path)) if !base_path.is_empty() => {
                 PathBuf::from(base_path).join(".vscode/mcp.json")
             }
-            ("vscode", _) => home.join(".vscode/mcp.json"),
+            ("vscode", _) => Self::vscode_user_dir(&home).join("mcp.json"),
             ("cursor", Some(base_path)) if !base_path.is_empty() => {
                 PathBuf::from(base_path).join(".cursor/mcp.json")
             }
             ("cursor", _) => home.join(".cursor/mcp.json"),
+            ("amazonq", Some(base_path)) if !base_path.is_empty() => {
+                PathBuf::from(base_path).join(".amazonq/mcp.json")
+            }
+            ("amazonq", _) => home.join(".aws/amazonq/mcp.json"),
             ("mcphub", _) => home.join(".config/mcphub/servers.json"),
-            ("windsurf", _) => home.join(".codeium/windsurf/mcp_config.json"),
+            ("windsurf", _) => Self::windsurf_config_path(&home),
             ("cherrystudio", _) => home.join(".config/cherrystudio/mcp.json"),
             ("mcplinker", _) => home.join(".config/mcplinker/mcp.json"),
             (_, Some(path_str)) if !path_str.is_empty() => {
@@ -44,28 +53,58 @@ impl ClientConfig {
         Self { path }
     }
 
+    /// Claude Desktop's config, in the platform's config dir (%APPDATA% on Windows). Linux only
+    /// has community builds, which use ~/.config/Claude.
     fn claude_config_path(home: &Path) -> PathBuf {
         if cfg!(target_os = "macos") {
             home.join("Library/Application Support/Claude/claude_desktop_config.json")
-        } else if cfg!(target_os = "windows") {
-            home.join("AppData/Roaming/Claude/claude_desktop_config.json")
         } else {
-            PathBuf::new()
+            let config_dir = crate::demo_mode::config_dir().unwrap_or_else(|| {
+                if cfg!(target_os = "windows") {
+                    home.join("AppData/Roaming")
+                } else {
+                    home.join(".config")
+                }
+            });
+            config_dir.join("Claude/claude_desktop_config.json")
         }
     }
 
-    fn vscode_global_storage_path(home: &Path, extension_id: &str, filename: &str) -> PathBuf {
-        let base_path = if cfg!(target_os = "macos") {
-            home.join("Library/Application Support/Code/User/globalStorage")
+    /// Windsurf's config, under ~/.codeium on every OS (%USERPROFILE% on Windows). Windsurf
+    /// Next keeps its own next to it, which is used when only Windsurf Next is installed.
+    fn windsurf_config_path(home: &Path) -> PathBuf {
+        let stable = home.join(".codeium/windsurf/mcp_config.json");
+        let next = home.join(".codeium/windsurf-next/mcp_config.json");
+        if !stable.exists() && next.exists() {
+            next
+        } else {
+            stable
+        }
+    }
+
+    /// VS Code's user profile directory, which also holds its user-level mcp.json
+    fn vscode_user_dir(home: &Path) -> PathBuf {
+        if cfg!(target_os = "macos") {
+            home.join("Library/Application Support/Code/User")
         } else if cfg!(target_os = "windows") {
-            home.join("AppData/Roaming/Code/User/globalStorage")
+            home.join("AppData/Roaming/Code/User")
         } else if cfg!(target_os = "linux") {
-            home.join(".config/Code/User/globalStorage")
+            home.join(".config/Code/User")
         } else {
-            return PathBuf::new();
-        };
+            PathBuf::new()
+        }
+    }
 
-        base_path.join(extension_id).join("settings").join(filename)
+    fn vscode_global_storage_path(home: &Path, extension_id: &str, filename: &str) -> PathBuf {
+        let user_dir = Self::vscode_user_dir(home);
+        if user_dir.as_os_str().is_empty() {
+            return PathBuf::new();
+        }
+        user_dir
+            .join("globalStorage")
+            .join(extension_id)
+            .join("settings")
+            .join(filename)
     }
 
     fn cline_config_path(home: &Path) -> PathBuf {
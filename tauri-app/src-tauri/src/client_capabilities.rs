@@ -0,0 +1,167 @@
+//! Which MCP features each client supports, and servers that rely on ones their client ignores
+//!
+//! Server features come from the cached handshake (`server_metadata`): the capabilities it
+//! advertises, plus the requests it sent back while connected (sampling, elicitation, roots).
+//! Most servers only ask for sampling or elicitation during a tool call, so a clean report
+//! is not proof. The support matrix reflects the clients' 2025 releases.
+
+use crate::client_target::ClientTarget;
+use crate::server_metadata::ServerMetadata;
+use crate::tool_catalog;
+use serde::Serialize;
+use tauri::command;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum McpFeature {
+    Tools,
+    Prompts,
+    Resources,
+    Completions,
+    Sampling,
+    Elicitation,
+    Roots,
+}
+
+use McpFeature::*;
+
+impl McpFeature {
+    fn label(self) -> &'static str {
+        match self {
+            Tools => "tools",
+            Prompts => "prompts",
+            Resources => "resources",
+            Completions => "argument completions",
+            Sampling => "sampling",
+            Elicitation => "elicitation",
+            Roots => "roots",
+        }
+    }
+
+    /// Features the client provides to the server, rather than displays
+    fn is_client_side(self) -> bool {
+        matches!(self, Sampling | Elicitation | Roots)
+    }
+}
+
+/// Features a client supports, or `None` when it isn't known
+pub(crate) fn client_support(client: &str) -> Option<&'static [McpFeature]> {
+    Some(match client {
+        "claude" => &[Tools, Prompts, Resources],
+        "claude_code" => &[Tools, Prompts, Resources, Roots, Elicitation],
+        "cursor" => &[Tools, Prompts, Resources, Roots, Elicitation],
+        "vscode" => &[
+            Tools,
+            Prompts,
+            Resources,
+            Completions,
+            Sampling,
+            Elicitation,
+            Roots,
+        ],
+        "windsurf" => &[Tools, Resources],
+        "cline" | "roo_code" => &[Tools, Resources],
+        "codex" => &[Tools],
+        "amazonq" => &[Tools, Prompts],
+        "cherrystudio" => &[Tools, Prompts, Resources],
+        _ => return None,
+    })
+}
+
+/// Features `server` offers or asked its client for
+pub(crate) fn server_features(server: &ServerMetadata) -> Vec<McpFeature> {
+    let capabilities = &server.server.capabilities;
+    let mut features: Vec<McpFeature> = [
+        ("tools", Tools),
+        ("prompts", Prompts),
+        ("resources", Resources),
+        ("completions", Completions),
+    ]
+    .iter()
+    .filter(|(key, _)| capabilities.get(*key).is_some())
+    .map(|(_, feature)| *feature)
+    .collect();
+    for (method, feature) in [
+        ("sampling/createMessage", Sampling),
+        ("elicitation/create", Elicitation),
+        ("roots/list", Roots),
+    ] {
+        if server.server.client_requests.iter().any(|m| m == method) {
+            features.push(feature);
+        }
+    }
+    features
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CompatibilityWarning {
+    pub feature: McpFeature,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CompatibilityReport {
+    pub target: ClientTarget,
+    pub server: String,
+    pub features: Vec<McpFeature>,
+    /// False when the client's support isn't known; no warnings are given then
+    pub client_known: bool,
+    pub warnings: Vec<CompatibilityWarning>,
+}
+
+pub(crate) fn check(server: &ServerMetadata) -> CompatibilityReport {
+    let features = server_features(server);
+    let support = client_support(&server.target.client);
+    let client = &server.target.client;
+    let warnings = support
+        .map(|supported| {
+            features
+                .iter()
+                .filter(|f| !supported.contains(f))
+                .map(|&feature| CompatibilityWarning {
+                    feature,
+                    message: if feature.is_client_side() {
+                        format!(
+                            "'{}' asks its client for {}, which {} doesn't support; \
+                             features relying on it will fail there",
+                            server.name,
+                            feature.label(),
+                            client
+                        )
+                    } else {
+                        format!(
+                            "'{}' provides {}, which {} ignores",
+                            server.name,
+                            feature.label(),
+                            client
+                        )
+                    },
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    CompatibilityReport {
+        target: server.target.clone(),
+        server: server.name.clone(),
+        features,
+        client_known: support.is_some(),
+        warnings,
+    }
+}
+
+/// Servers whose features their client doesn't support, optionally for one target or server
+#[command]
+pub async fn check_client_compatibility(
+    target: Option<ClientTarget>,
+    name: Option<String>,
+    refresh: Option<bool>,
+) -> Result<Vec<CompatibilityReport>, String> {
+    let index = tool_catalog::tool_index(refresh.unwrap_or(true)).await;
+    Ok(index
+        .servers
+        .iter()
+        .filter(|s| target.as_ref().is_none_or(|t| s.target == *t))
+        .filter(|s| name.as_ref().is_none_or(|n| s.name == *n))
+        .map(check)
+        .collect())
+}
@@ -0,0 +1,63 @@
+// Client capability compatibility tests
+use crate::client_capabilities::{check, client_support, server_features, McpFeature};
+use crate::client_target::ClientTarget;
+use crate::server_handshake::ServerInfo;
+use crate::server_metadata::ServerMetadata;
+use serde_json::json;
+
+fn server(client: &str, capabilities: serde_json::Value, requests: &[&str]) -> ServerMetadata {
+    ServerMetadata {
+        target: ClientTarget::client(client, None),
+        name: "notes".to_string(),
+        fetched_at: "2026-01-01T00:00:00Z".to_string(),
+        config_hash: String::new(),
+        server: ServerInfo {
+            capabilities,
+            client_requests: requests.iter().map(|r| r.to_string()).collect(),
+            ..ServerInfo::default()
+        },
+        tools: Vec::new(),
+    }
+}
+
+#[test]
+fn test_server_features() {
+    let notes = server(
+        "vscode",
+        json!({ "tools": {}, "resources": { "subscribe": true } }),
+        &["roots/list"],
+    );
+    assert_eq!(
+        server_features(&notes),
+        vec![McpFeature::Tools, McpFeature::Resources, McpFeature::Roots]
+    );
+}
+
+#[test]
+fn test_check_warns_about_unsupported_features() {
+    let notes = server(
+        "codex",
+        json!({ "tools": {}, "prompts": {} }),
+        &["sampling/createMessage"],
+    );
+    let report = check(&notes);
+    assert!(report.client_known);
+    let features: Vec<McpFeature> = report.warnings.iter().map(|w| w.feature).collect();
+    assert_eq!(features, vec![McpFeature::Prompts, McpFeature::Sampling]);
+    assert!(report.warnings[1].message.contains("will fail"));
+
+    let vscode = check(&server(
+        "vscode",
+        json!({ "tools": {}, "prompts": {} }),
+        &[],
+    ));
+    assert!(vscode.warnings.is_empty());
+}
+
+#[test]
+fn test_unknown_client_gives_no_warnings() {
+    assert!(client_support("mcphub").is_none());
+    let report = check(&server("mcphub", json!({ "prompts": {} }), &[]));
+    assert!(!report.client_known);
+    assert!(report.warnings.is_empty());
+}
@@ -0,0 +1,357 @@
+//! MCP logs written by the clients, tailed and matched to configured servers
+//!
+//! Claude Desktop logs its MCP connections to mcp.log and each server's stderr to
+//! mcp-server-<name>.log, in ~/Library/Logs/Claude on macOS, %APPDATA%\Claude\logs on Windows
+//! and ~/.config/Claude/logs on Linux. Lines look like
+//! `2025-06-17T10:21:34.123Z [fetch] [error] Server disconnected`; the server tag is missing in
+//! its own file. `tail_client_logs` returns the last lines and then publishes new ones as
+//! `client_log` events until `stop_client_logs`; `diagnose_client_logs` lists the recent errors
+//! of every configured server and the log files of servers no longer configured.
+
+use crate::client_target::{self, ClientTarget};
+use crate::event_bus::{self, AppEventKind, EventScope};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{command, AppHandle, Runtime};
+
+const GENERAL_LOG: &str = "mcp.log";
+const SERVER_LOG_PREFIX: &str = "mcp-server-";
+
+/// How far back from its end a file is read for the first lines
+const TAIL_BYTES: u64 = 256 * 1024;
+const DEFAULT_LINES: usize = 200;
+const POLL_INTERVAL_MS: u64 = 1000;
+/// Errors reported per server by `diagnose_client_logs`
+const RECENT_ERRORS: usize = 5;
+
+static LINE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(\d{4}-\d{2}-\d{2}T\S+)\s+(?:\[([^\]]+)\]\s+)?\[(debug|info|warn|warning|error)\]\s?(.*)$")
+        .expect("valid log line pattern")
+});
+
+/// Tails running, by id; a tail stops once its id is removed
+static TAILS: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct LogLine {
+    /// Server the line is about: its tag, or the server of the file it is in
+    pub server: Option<String>,
+    pub at: Option<String>,
+    pub level: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ClientLogFile {
+    /// `None` for the client's general MCP log
+    pub server: Option<String>,
+    pub path: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct LogTail {
+    pub id: String,
+    pub files: Vec<ClientLogFile>,
+    pub lines: Vec<LogLine>,
+}
+
+/// Payload of a `client_log` event
+#[derive(Debug, Serialize, Clone)]
+pub struct LogChunk {
+    pub tail: String,
+    pub path: String,
+    pub lines: Vec<LogLine>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ServerLogReport {
+    pub server: String,
+    pub configured: bool,
+    pub has_log: bool,
+    /// Latest last
+    pub errors: Vec<LogLine>,
+}
+
+/// Directory of the MCP logs of `client`
+pub(crate) fn log_dir(client: &str) -> Result<PathBuf, String> {
+    if client != "claude" {
+        return Err(format!("No MCP logs are known for {}", client));
+    }
+    let dir = if cfg!(target_os = "macos") {
+        crate::demo_mode::home_dir().map(|home| home.join("Library/Logs/Claude"))
+    } else {
+        crate::demo_mode::config_dir().map(|config| config.join("Claude").join("logs"))
+    };
+    dir.ok_or_else(|| "Failed to locate the Claude Desktop log directory".to_string())
+}
+
+/// The log's server for a file name: `Some(None)` for the general log, `None` for other files
+pub(crate) fn server_of_file(file_name: &str) -> Option<Option<String>> {
+    if file_name == GENERAL_LOG {
+        return Some(None);
+    }
+    file_name
+        .strip_prefix(SERVER_LOG_PREFIX)?
+        .strip_suffix(".log")
+        .filter(|name| !name.is_empty())
+        .map(|name| Some(name.to_string()))
+}
+
+/// One line of a log; `server` is the server of its file
+pub(crate) fn parse_line(line: &str, server: Option<&str>) -> LogLine {
+    match LINE.captures(line) {
+        Some(captures) => LogLine {
+            server: captures
+                .get(2)
+                .map(|tag| tag.as_str().to_string())
+                .or_else(|| server.map(str::to_string)),
+            at: Some(captures[1].to_string()),
+            level: Some(match &captures[3] {
+                "warning" => "warn".to_string(),
+                level => level.to_string(),
+            }),
+            message: captures[4].to_string(),
+        },
+        None => LogLine {
+            server: server.map(str::to_string),
+            at: None,
+            level: None,
+            message: line.to_string(),
+        },
+    }
+}
+
+/// Errors by server in the lines of every log, and the servers whose own log exists
+pub(crate) fn correlate(
+    configured: &[String],
+    logs: &[(Option<String>, Vec<LogLine>)],
+) -> Vec<ServerLogReport> {
+    let mut reports: BTreeMap<String, ServerLogReport> = configured
+        .iter()
+        .map(|name| {
+            (
+                name.clone(),
+                ServerLogReport {
+                    server: name.clone(),
+                    configured: true,
+                    has_log: false,
+                    errors: Vec::new(),
+                },
+            )
+        })
+        .collect();
+    for (file_server, lines) in logs {
+        if let Some(name) = file_server {
+            reports
+                .entry(name.clone())
+                .or_insert_with(|| ServerLogReport {
+                    server: name.clone(),
+                    configured: false,
+                    has_log: false,
+                    errors: Vec::new(),
+                })
+                .has_log = true;
+        }
+        for line in lines.iter().filter(|l| l.level.as_deref() == Some("error")) {
+            if let Some(report) = line.server.as_ref().and_then(|s| reports.get_mut(s)) {
+                report.errors.push(line.clone());
+            }
+        }
+    }
+    reports
+        .into_values()
+        .map(|mut report| {
+            report.errors.sort_by(|a, b| a.at.cmp(&b.at));
+            let skip = report.errors.len().saturating_sub(RECENT_ERRORS);
+            report.errors.drain(..skip);
+            report
+        })
+        .collect()
+}
+
+/// The MCP logs of `client`, the general one first
+fn log_files(client: &str) -> Result<Vec<ClientLogFile>, String> {
+    let dir = log_dir(client)?;
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read {}: {}", dir.display(), e)),
+    };
+    let mut files: Vec<ClientLogFile> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let server = server_of_file(entry.file_name().to_str()?)?;
+            Some(ClientLogFile {
+                server,
+                path: entry.path().display().to_string(),
+                size: entry.metadata().map(|m| m.len()).unwrap_or(0),
+            })
+        })
+        .collect();
+    files.sort_by(|a, b| a.server.cmp(&b.server));
+    Ok(files)
+}
+
+/// Text of `path` from `offset`
+fn read_from(path: &Path, offset: u64) -> Result<String, String> {
+    let mut file = File::open(path).map_err(|e| format!("Failed to open log: {}", e))?;
+    file.seek(SeekFrom::Start(offset))
+        .map_err(|e| format!("Failed to read log: {}", e))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)
+        .map_err(|e| format!("Failed to read log: {}", e))?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Lines of the last part of a log about `server`, or all of them
+fn recent_lines(file: &ClientLogFile, server: Option<&str>) -> Vec<LogLine> {
+    let path = Path::new(&file.path);
+    let offset = file.size.saturating_sub(TAIL_BYTES);
+    let Ok(text) = read_from(path, offset) else {
+        return Vec::new();
+    };
+    // A partial first line when reading from the middle
+    let skip = usize::from(offset > 0);
+    lines_about(text.lines().skip(skip), file.server.as_deref(), server)
+}
+
+fn lines_about<'a>(
+    lines: impl Iterator<Item = &'a str>,
+    file_server: Option<&str>,
+    server: Option<&str>,
+) -> Vec<LogLine> {
+    lines
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| parse_line(line, file_server))
+        .filter(|line| server.is_none() || line.server.as_deref() == server)
+        .collect()
+}
+
+/// Publish what was appended to `files` each second until the tail is stopped
+fn spawn_tail<R: Runtime>(
+    app: AppHandle<R>,
+    id: String,
+    client: String,
+    server: Option<String>,
+    files: Vec<ClientLogFile>,
+) {
+    tauri::async_runtime::spawn(async move {
+        let mut offsets: Vec<u64> = files.iter().map(|file| file.size).collect();
+        let mut interval = tokio::time::interval(Duration::from_millis(POLL_INTERVAL_MS));
+        loop {
+            interval.tick().await;
+            if !TAILS
+                .lock()
+                .map(|tails| tails.contains(&id))
+                .unwrap_or(false)
+            {
+                return;
+            }
+            for (file, offset) in files.iter().zip(offsets.iter_mut()) {
+                let path = Path::new(&file.path);
+                let size = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+                if size == *offset {
+                    continue;
+                }
+                // Rotated or truncated: start over
+                let from = if size < *offset { 0 } else { *offset };
+                let Ok(text) = read_from(path, from) else {
+                    continue;
+                };
+                // Keep a line still being written for the next round
+                let complete = text.rfind('\n').map_or(0, |newline| newline + 1);
+                *offset = from + complete as u64;
+                let lines = lines_about(
+                    text[..complete].lines(),
+                    file.server.as_deref(),
+                    server.as_deref(),
+                );
+                if lines.is_empty() {
+                    continue;
+                }
+                event_bus::publish(
+                    &app,
+                    AppEventKind::ClientLog,
+                    EventScope::client(&client),
+                    &LogChunk {
+                        tail: id.clone(),
+                        path: file.path.clone(),
+                        lines,
+                    },
+                );
+            }
+        }
+    });
+}
+
+/// The last `lines` lines of `client`'s MCP logs, of `server` only when given, then new ones
+/// as `client_log` events carrying the returned id until `stop_client_logs`
+#[command]
+pub async fn tail_client_logs<R: Runtime>(
+    app: AppHandle<R>,
+    client: String,
+    server: Option<String>,
+    lines: Option<usize>,
+) -> Result<LogTail, String> {
+    let files: Vec<ClientLogFile> = log_files(&client)?
+        .into_iter()
+        .filter(|file| {
+            server.is_none() || file.server.is_none() || file.server.as_deref() == server.as_deref()
+        })
+        .collect();
+    let mut recent: Vec<LogLine> = files
+        .iter()
+        .flat_map(|file| recent_lines(file, server.as_deref()))
+        .collect();
+    recent.sort_by(|a, b| a.at.cmp(&b.at));
+    let skip = recent.len().saturating_sub(lines.unwrap_or(DEFAULT_LINES));
+    recent.drain(..skip);
+
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed).to_string();
+    TAILS
+        .lock()
+        .map_err(|e| format!("Failed to lock log tails: {}", e))?
+        .insert(id.clone());
+    spawn_tail(app, id.clone(), client, server, files.clone());
+    Ok(LogTail {
+        id,
+        files,
+        lines: recent,
+    })
+}
+
+/// Stop a tail; false when it wasn't running
+#[command]
+pub async fn stop_client_logs(id: String) -> Result<bool, String> {
+    Ok(TAILS
+        .lock()
+        .map_err(|e| format!("Failed to lock log tails: {}", e))?
+        .remove(&id))
+}
+
+/// Recent errors of each server configured in `client` found in its logs, and the servers
+/// that still have a log but are no longer configured
+#[command]
+pub async fn diagnose_client_logs(client: String) -> Result<Vec<ServerLogReport>, String> {
+    let files = log_files(&client)?;
+    let configured: Vec<String> = client_target::list_servers(&ClientTarget::client(&client, None))
+        .await?
+        .keys()
+        .cloned()
+        .collect();
+    let logs: Vec<(Option<String>, Vec<LogLine>)> = files
+        .iter()
+        .map(|file| (file.server.clone(), recent_lines(file, None)))
+        .collect();
+    Ok(correlate(&configured, &logs))
+}
@@ -0,0 +1,54 @@
+// Client log tests
+use crate::client_logs::{correlate, parse_line, server_of_file};
+
+#[test]
+fn test_parse_claude_log_lines() {
+    assert_eq!(server_of_file("mcp.log"), Some(None));
+    assert_eq!(
+        server_of_file("mcp-server-github.log"),
+        Some(Some("github".to_string()))
+    );
+    assert_eq!(server_of_file("main.log"), None);
+
+    let tagged = parse_line(
+        "2025-06-17T10:21:34.123Z [fetch] [error] Server disconnected",
+        None,
+    );
+    assert_eq!(tagged.server.as_deref(), Some("fetch"));
+    assert_eq!(tagged.level.as_deref(), Some("error"));
+    assert_eq!(tagged.message, "Server disconnected");
+
+    let own = parse_line("2025-06-17T10:21:35.000Z [info] Initializing", Some("fs"));
+    assert_eq!(own.server.as_deref(), Some("fs"));
+    assert_eq!(own.level.as_deref(), Some("info"));
+
+    let stderr = parse_line("Error: ENOENT: no such file", Some("fs"));
+    assert_eq!(stderr.level, None);
+    assert_eq!(stderr.message, "Error: ENOENT: no such file");
+}
+
+#[test]
+fn test_correlate_errors_with_configured_servers() {
+    let general = vec![
+        parse_line(
+            "2025-06-17T10:00:00Z [fetch] [error] spawn uvx ENOENT",
+            None,
+        ),
+        parse_line("2025-06-17T10:00:01Z [fs] [info] Connected", None),
+    ];
+    let old = vec![parse_line(
+        "2025-06-16T09:00:00Z [error] crashed",
+        Some("old"),
+    )];
+    let reports = correlate(
+        &["fetch".to_string(), "fs".to_string()],
+        &[(None, general), (Some("old".to_string()), old)],
+    );
+    let names: Vec<&str> = reports.iter().map(|r| r.server.as_str()).collect();
+    assert_eq!(names, ["fetch", "fs", "old"]);
+    assert_eq!(reports[0].errors[0].message, "spawn uvx ENOENT");
+    assert!(reports[0].configured && !reports[0].has_log);
+    assert!(reports[1].errors.is_empty());
+    assert!(!reports[2].configured && reports[2].has_log);
+    assert_eq!(reports[2].errors.len(), 1);
+}
@@ -0,0 +1,612 @@
+//! Client adapters declared in manifest files
+//!
+//! Each `*.json` file in the `clients` folder of the app data dir declares one more client:
+//! where its config lives, whether it is JSON, TOML or YAML, the JSON pointer of its servers
+//! map and how its entries differ from the `mcpServers` dialect. Such clients then work
+//! wherever a client name is taken. `register_custom_client` writes a manifest for a client
+//! the user describes, and `remove_custom_client` deletes it.
+//!
+//! A manifest can only reach the files it declares: global paths must lie under the home or
+//! config dir, project paths are relative to the project dir the caller picks, and both must
+//! name a file of the declared format without `..` components. Manifests that fail validation
+//! are skipped and reported by `list_client_plugins`; ids of built-in clients can't be taken
+//! over. TOML and YAML writes replace only the top-level key holding the servers, so the rest
+//! of the file keeps its comments and layout.
+
+use crate::client_target::JSON_CLIENTS;
+use crate::json_manager::JsonManager;
+use crate::json_pointer::{self, escape_segment};
+use crate::settings::app_config_dir;
+use crate::{platform_access, write_journal, yaml_block};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::RwLock;
+use tauri::command;
+use toml_edit::DocumentMut;
+
+/// Client names handled by dedicated code
+const BUILT_IN_CLIENTS: [&str; 5] = [
+    "claude_code",
+    "codex",
+    "plux",
+    crate::continue_config::CLIENT,
+    crate::goose_config::CLIENT,
+];
+
+static REGISTRY: Lazy<RwLock<PluginReport>> = Lazy::new(|| RwLock::new(load_manifests()));
+
+/// How entries of the client differ from the `mcpServers` dialect
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct Dialect {
+    /// Where top-level fields go in the client's entry, e.g. `"/command": "/command/path"`
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+    /// Values set on every written entry, by pointer inside the client's entry
+    #[serde(default)]
+    pub constants: BTreeMap<String, Value>,
+    /// Top-level fields the client doesn't accept, left out on write
+    #[serde(default)]
+    pub drop: Vec<String>,
+}
+
+/// Format of a client's config file
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigFormat {
+    #[default]
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl ConfigFormat {
+    fn extensions(self) -> &'static [&'static str] {
+        match self {
+            ConfigFormat::Json => &["json"],
+            ConfigFormat::Toml => &["toml"],
+            ConfigFormat::Yaml => &["yaml", "yml"],
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ClientManifest {
+    /// Client name used in commands and targets
+    pub id: String,
+    pub name: String,
+    /// Global config file by OS ("macos", "windows", "linux" or "default"). Paths start with
+    /// `~/`, `{home}` or `{config}`.
+    #[serde(default)]
+    pub config_path: BTreeMap<String, String>,
+    /// Project config file, relative to the project dir given as `path`
+    #[serde(default)]
+    pub project_path: Option<String>,
+    #[serde(default)]
+    pub format: ConfigFormat,
+    /// JSON pointer of the servers map inside the config
+    pub servers_pointer: String,
+    #[serde(default)]
+    pub dialect: Dialect,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ManifestError {
+    pub file: String,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct PluginReport {
+    pub dir: String,
+    pub clients: Vec<ClientManifest>,
+    pub errors: Vec<ManifestError>,
+}
+
+fn plugins_dir() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("clients"))
+}
+
+/// Whether `template` stays inside the file it names
+fn check_template(template: &str, relative: bool, format: ConfigFormat, errors: &mut Vec<String>) {
+    let rest = match ["~/", "{home}/", "{config}/"]
+        .iter()
+        .find_map(|prefix| template.strip_prefix(prefix))
+    {
+        Some(rest) if !relative => rest,
+        Some(_) => {
+            errors.push(format!(
+                "'{}' must be relative to the project dir",
+                template
+            ));
+            return;
+        }
+        None if relative => template,
+        None => {
+            errors.push(format!(
+                "'{}' must start with ~/, {{home}} or {{config}}",
+                template
+            ));
+            return;
+        }
+    };
+    let path = Path::new(rest);
+    if !path.components().all(|c| matches!(c, Component::Normal(_))) {
+        errors.push(format!(
+            "'{}' may only contain plain path segments",
+            template
+        ));
+    }
+    let extensions = format.extensions();
+    if !path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| extensions.contains(&ext))
+    {
+        errors.push(format!(
+            "'{}' must name a .{} file",
+            template, extensions[0]
+        ));
+    }
+}
+
+fn check_pointer(pointer: &str, what: &str, errors: &mut Vec<String>) {
+    if !pointer.starts_with('/') || pointer.len() < 2 {
+        errors.push(format!(
+            "{} '{}' must be a JSON pointer like /mcpServers",
+            what, pointer
+        ));
+    }
+}
+
+/// Everything wrong with a manifest; empty when it can be loaded
+pub(crate) fn validate_manifest(manifest: &ClientManifest) -> Vec<String> {
+    let mut errors = Vec::new();
+    let id_ok = !manifest.id.is_empty()
+        && manifest
+            .id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-');
+    if !id_ok {
+        errors.push(format!(
+            "Id '{}' may only contain lowercase letters, digits, '_' and '-'",
+            manifest.id
+        ));
+    }
+    if JSON_CLIENTS.contains(&manifest.id.as_str())
+        || BUILT_IN_CLIENTS.contains(&manifest.id.as_str())
+    {
+        errors.push(format!("Id '{}' is a built-in client", manifest.id));
+    }
+    if manifest.config_path.is_empty() && manifest.project_path.is_none() {
+        errors.push("Declare a config_path, a project_path or both".to_string());
+    }
+    for (os, template) in &manifest.config_path {
+        if !["macos", "windows", "linux", "default"].contains(&os.as_str()) {
+            errors.push(format!("Unknown OS '{}' in config_path", os));
+        }
+        check_template(template, false, manifest.format, &mut errors);
+    }
+    if let Some(template) = &manifest.project_path {
+        check_template(template, true, manifest.format, &mut errors);
+    }
+    check_pointer(&manifest.servers_pointer, "servers_pointer", &mut errors);
+    for (field, pointer) in &manifest.dialect.fields {
+        if !field.starts_with('/') || field[1..].contains('/') {
+            errors.push(format!(
+                "Dialect field '{}' must be a top-level pointer",
+                field
+            ));
+        }
+        check_pointer(pointer, "Dialect target", &mut errors);
+    }
+    for pointer in manifest.dialect.constants.keys() {
+        check_pointer(pointer, "Dialect constant", &mut errors);
+    }
+    errors
+}
+
+fn load_manifests() -> PluginReport {
+    let mut report = PluginReport::default();
+    let Ok(dir) = plugins_dir() else {
+        return report;
+    };
+    report.dir = dir.display().to_string();
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return report;
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+    for file in files {
+        let parsed = fs::read_to_string(&file)
+            .map_err(|e| format!("Failed to read manifest: {}", e))
+            .and_then(|content| {
+                serde_json::from_str::<ClientManifest>(&content)
+                    .map_err(|e| format!("Failed to parse manifest: {}", e))
+            });
+        let mut errors = match &parsed {
+            Ok(manifest) => validate_manifest(manifest),
+            Err(e) => vec![e.clone()],
+        };
+        if let Ok(manifest) = &parsed {
+            if report.clients.iter().any(|c| c.id == manifest.id) {
+                errors.push(format!(
+                    "Id '{}' is declared by another manifest",
+                    manifest.id
+                ));
+            }
+        }
+        match parsed {
+            Ok(manifest) if errors.is_empty() => report.clients.push(manifest),
+            _ => report.errors.push(ManifestError {
+                file: file.display().to_string(),
+                errors,
+            }),
+        }
+    }
+    println!(
+        "[ClientPlugins] Loaded {} client(s), skipped {} manifest(s)",
+        report.clients.len(),
+        report.errors.len()
+    );
+    report
+}
+
+/// The manifest declaring `client`, if any
+pub(crate) fn manifest(client: &str) -> Option<ClientManifest> {
+    REGISTRY
+        .read()
+        .ok()?
+        .clients
+        .iter()
+        .find(|m| m.id == client)
+        .cloned()
+}
+
+pub(crate) fn plugin_clients() -> Vec<String> {
+    REGISTRY
+        .read()
+        .map(|r| r.clients.iter().map(|m| m.id.clone()).collect())
+        .unwrap_or_default()
+}
+
+fn expand(template: &str) -> Result<PathBuf, String> {
+    let home =
+        crate::demo_mode::home_dir().ok_or_else(|| "Cannot find home directory".to_string())?;
+    if let Some(rest) = template.strip_prefix("~/") {
+        return Ok(home.join(rest));
+    }
+    if let Some(rest) = template.strip_prefix("{home}/") {
+        return Ok(home.join(rest));
+    }
+    if let Some(rest) = template.strip_prefix("{config}/") {
+        let config = crate::demo_mode::config_dir()
+            .ok_or_else(|| "Cannot find config directory".to_string())?;
+        return Ok(config.join(rest));
+    }
+    Err(format!("Unsupported path template '{}'", template))
+}
+
+/// Config file of the client: the project file under `project_dir`, or the global one
+pub(crate) fn resolve_path(
+    manifest: &ClientManifest,
+    project_dir: Option<&str>,
+) -> Result<PathBuf, String> {
+    if let Some(dir) = project_dir.filter(|d| !d.is_empty()) {
+        let template = manifest
+            .project_path
+            .as_ref()
+            .ok_or_else(|| format!("Client '{}' has no project config", manifest.id))?;
+        return Ok(Path::new(dir).join(template));
+    }
+    let template = manifest
+        .config_path
+        .get(std::env::consts::OS)
+        .or_else(|| manifest.config_path.get("default"))
+        .ok_or_else(|| format!("Client '{}' has no config on this OS", manifest.id))?;
+    expand(template)
+}
+
+/// A client entry in the `mcpServers` dialect
+pub(crate) fn from_client(dialect: &Dialect, entry: &Value) -> Value {
+    let mut rest = entry.clone();
+    for pointer in dialect.constants.keys() {
+        json_pointer::remove(&mut rest, pointer);
+    }
+    let mut moved = Map::new();
+    for (field, pointer) in &dialect.fields {
+        if let Some(value) = json_pointer::remove(&mut rest, pointer) {
+            moved.insert(json_pointer::unescape_segment(&field[1..]), value);
+        }
+    }
+    let Value::Object(mut canonical) = rest else {
+        return entry.clone();
+    };
+    // Parents emptied by the moves above, like `command` for `/command/path`
+    canonical.retain(|_, value| !value.as_object().is_some_and(|o| o.is_empty()));
+    canonical.extend(moved);
+    Value::Object(canonical)
+}
+
+/// An `mcpServers` entry in the client's dialect
+pub(crate) fn to_client(dialect: &Dialect, canonical: &Value) -> Result<Value, String> {
+    let Some(fields) = canonical.as_object() else {
+        return Err("Server config must be an object".to_string());
+    };
+    let mut entry = Value::Object(Map::new());
+    for (key, value) in fields {
+        let field = format!("/{}", escape_segment(key));
+        if dialect.drop.contains(&field) {
+            continue;
+        }
+        let pointer = dialect.fields.get(&field).unwrap_or(&field);
+        json_pointer::set(&mut entry, pointer, value.clone())?;
+    }
+    for (pointer, value) in &dialect.constants {
+        json_pointer::set(&mut entry, pointer, value.clone())?;
+    }
+    Ok(entry)
+}
+
+/// The config text `content` of a client as JSON; an empty file is an empty object
+pub(crate) fn parse_config(format: ConfigFormat, content: &str) -> Result<Value, String> {
+    if content.trim().is_empty() {
+        return Ok(json!({}));
+    }
+    match format {
+        ConfigFormat::Json => {
+            serde_json::from_str(content).map_err(|e| format!("Failed to parse JSON: {}", e))
+        }
+        ConfigFormat::Toml => {
+            toml::from_str(content).map_err(|e| format!("Failed to parse TOML: {}", e))
+        }
+        ConfigFormat::Yaml => yaml_block::parse(content, "config"),
+    }
+}
+
+/// `content` with its top-level `key` replaced by `value`, the other items untouched
+pub(crate) fn splice_toml(content: &str, key: &str, value: &Value) -> Result<String, String> {
+    let mut doc: DocumentMut = content
+        .parse()
+        .map_err(|e| format!("Failed to parse TOML: {}", e))?;
+    let rendered = toml::to_string(&json!({ key: value }))
+        .map_err(|e| format!("Failed to serialize {}: {}", key, e))?;
+    let fresh: DocumentMut = rendered
+        .parse()
+        .map_err(|e| format!("Failed to parse TOML: {}", e))?;
+    match fresh.get(key) {
+        Some(item) => {
+            doc.insert(key, item.clone());
+        }
+        None => {
+            doc.remove(key);
+        }
+    }
+    Ok(doc.to_string())
+}
+
+/// The top-level key of the servers pointer, the one TOML and YAML writes replace
+fn top_level_key(manifest: &ClientManifest) -> String {
+    let first = manifest.servers_pointer[1..]
+        .split('/')
+        .next()
+        .unwrap_or_default();
+    json_pointer::unescape_segment(first)
+}
+
+/// Text and JSON value of the config; JSON configs have no text
+async fn read_config(manifest: &ClientManifest, path: &Path) -> Result<(String, Value), String> {
+    if manifest.format == ConfigFormat::Json {
+        return Ok((String::new(), JsonManager::read_json_file(path).await?));
+    }
+    let content = match tokio::fs::read_to_string(path).await {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(platform_access::read_error(path, &e)),
+    };
+    let json = parse_config(manifest.format, &content)?;
+    Ok((content, json))
+}
+
+/// The config at `path` as JSON, `None` when it doesn't exist
+pub(crate) async fn load_config(
+    manifest: &ClientManifest,
+    path: &Path,
+) -> Result<Option<Value>, String> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(read_config(manifest, path).await?.1))
+}
+
+/// Write `json` back over the config whose text was `content`
+async fn write_config(
+    manifest: &ClientManifest,
+    path: &Path,
+    content: &str,
+    json: &Value,
+) -> Result<(), String> {
+    let key = top_level_key(manifest);
+    let edited = match manifest.format {
+        ConfigFormat::Json => return JsonManager::write_json_file(path, json).await,
+        ConfigFormat::Toml => splice_toml(content, &key, &json[&key])?,
+        ConfigFormat::Yaml => yaml_block::splice(content, &key, &json[&key], "  ")?,
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    write_journal::write(path, &edited)
+        .map_err(|e| format!("Failed to write config of '{}': {}", manifest.id, e))
+}
+
+/// Servers of the client in the `mcpServers` dialect
+pub(crate) async fn read_servers(
+    manifest: &ClientManifest,
+    project_dir: Option<&str>,
+) -> Result<Map<String, Value>, String> {
+    let path = resolve_path(manifest, project_dir)?;
+    let (_, json) = read_config(manifest, &path).await?;
+    Ok(json
+        .pointer(&manifest.servers_pointer)
+        .and_then(|v| v.as_object())
+        .map(|servers| {
+            servers
+                .iter()
+                .map(|(name, entry)| (name.clone(), from_client(&manifest.dialect, entry)))
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+fn servers_of<'a>(
+    json: &'a mut Value,
+    manifest: &ClientManifest,
+) -> Result<&'a mut Map<String, Value>, String> {
+    if json.pointer(&manifest.servers_pointer).is_none() {
+        json_pointer::set(json, &manifest.servers_pointer, Value::Object(Map::new()))?;
+    }
+    json.pointer_mut(&manifest.servers_pointer)
+        .and_then(|v| v.as_object_mut())
+        .ok_or_else(|| format!("'{}' is not an object", manifest.servers_pointer))
+}
+
+fn response(manifest: &ClientManifest, json: &Value) -> Value {
+    let servers: Map<String, Value> = json
+        .pointer(&manifest.servers_pointer)
+        .and_then(|v| v.as_object())
+        .map(|servers| {
+            servers
+                .iter()
+                .map(|(name, entry)| (name.clone(), from_client(&manifest.dialect, entry)))
+                .collect()
+        })
+        .unwrap_or_default();
+    serde_json::json!({ "mcpServers": servers })
+}
+
+/// Add or replace a server; adding fails when the name is taken
+pub(crate) async fn write_server(
+    manifest: &ClientManifest,
+    project_dir: Option<&str>,
+    name: &str,
+    config: &Value,
+    replace: bool,
+) -> Result<Value, String> {
+    let path = resolve_path(manifest, project_dir)?;
+    let entry = to_client(&manifest.dialect, config)?;
+    let (content, mut json) = read_config(manifest, &path).await?;
+    let servers = servers_of(&mut json, manifest)?;
+    if !replace && servers.contains_key(name) {
+        return Err(format!(
+            "Server '{}' already exists in '{}'",
+            name, manifest.id
+        ));
+    }
+    servers.insert(name.to_string(), entry);
+    write_config(manifest, &path, &content, &json).await?;
+    Ok(response(manifest, &json))
+}
+
+pub(crate) async fn remove_servers(
+    manifest: &ClientManifest,
+    project_dir: Option<&str>,
+    names: &[String],
+) -> Result<Value, String> {
+    let path = resolve_path(manifest, project_dir)?;
+    let (content, mut json) = read_config(manifest, &path).await?;
+    let servers = servers_of(&mut json, manifest)?;
+    for name in names {
+        servers.shift_remove(name);
+    }
+    write_config(manifest, &path, &content, &json).await?;
+    Ok(response(manifest, &json))
+}
+
+/// A config path given as `path` in template form: under `home` it becomes `~/...`
+pub(crate) fn template_for(path: &str, home: &Path) -> String {
+    match Path::new(path).strip_prefix(home) {
+        Ok(rest) => format!("~/{}", rest.to_string_lossy().replace('\\', "/")),
+        _ => path.to_string(),
+    }
+}
+
+/// The manifest file declaring `id`
+fn manifest_file(id: &str) -> Result<Option<PathBuf>, String> {
+    let Ok(entries) = fs::read_dir(plugins_dir()?) else {
+        return Ok(None);
+    };
+    Ok(entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .find(|path| {
+            fs::read_to_string(path)
+                .ok()
+                .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+                .is_some_and(|manifest| manifest["id"] == id)
+        }))
+}
+
+/// Loaded manifest clients and the manifests that were skipped
+#[command]
+pub async fn list_client_plugins() -> Result<PluginReport, String> {
+    Ok(REGISTRY
+        .read()
+        .map_err(|e| format!("Failed to read client plugins: {}", e))?
+        .clone())
+}
+
+/// Read the manifests again, e.g. after adding one
+#[command]
+pub async fn reload_client_plugins() -> Result<PluginReport, String> {
+    let report = load_manifests();
+    *REGISTRY
+        .write()
+        .map_err(|e| format!("Failed to update client plugins: {}", e))? = report.clone();
+    Ok(report)
+}
+
+/// Declare a client by its config path (absolute paths under the home dir are accepted),
+/// format and servers pointer, replacing the one registered with the same id
+#[command]
+pub async fn register_custom_client(mut manifest: ClientManifest) -> Result<PluginReport, String> {
+    let home =
+        crate::demo_mode::home_dir().ok_or_else(|| "Cannot find home directory".to_string())?;
+    for template in manifest.config_path.values_mut() {
+        *template = template_for(template, &home);
+    }
+    let errors = validate_manifest(&manifest);
+    if !errors.is_empty() {
+        return Err(format!("Invalid client: {}", errors.join("; ")));
+    }
+    let dir = plugins_dir()?;
+    let file =
+        manifest_file(&manifest.id)?.unwrap_or_else(|| dir.join(format!("{}.json", manifest.id)));
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create clients directory: {}", e))?;
+    let content = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize client: {}", e))?;
+    fs::write(&file, content).map_err(|e| format!("Failed to write client: {}", e))?;
+    println!(
+        "[ClientPlugins] Registered {} in {}",
+        manifest.id,
+        file.display()
+    );
+    reload_client_plugins().await
+}
+
+/// Delete the manifest of a custom client; its config file is left alone
+#[command]
+pub async fn remove_custom_client(id: String) -> Result<PluginReport, String> {
+    let file =
+        manifest_file(&id)?.ok_or_else(|| format!("Client '{}' is not a custom client", id))?;
+    fs::remove_file(&file).map_err(|e| format!("Failed to remove client: {}", e))?;
+    println!("[ClientPlugins] Removed {}", id);
+    reload_client_plugins().await
+}
@@ -0,0 +1,121 @@
+// Manifest client adapter tests
+use crate::client_plugins::{
+    from_client, parse_config, splice_toml, template_for, to_client, validate_manifest,
+    ClientManifest, ConfigFormat, Dialect,
+};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+fn zed() -> ClientManifest {
+    serde_json::from_value(json!({
+        "id": "zed",
+        "name": "Zed",
+        "config_path": { "default": "{config}/zed/settings.json" },
+        "project_path": ".zed/settings.json",
+        "servers_pointer": "/context_servers",
+        "dialect": {
+            "fields": {
+                "/command": "/command/path",
+                "/args": "/command/args",
+                "/env": "/command/env"
+            },
+            "constants": { "/source": "custom" },
+            "drop": ["/type"]
+        }
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_validate_manifest_accepts_declared_paths() {
+    assert_eq!(validate_manifest(&zed()), Vec::<String>::new());
+}
+
+#[test]
+fn test_validate_manifest_rejects_paths_outside_sandbox() {
+    let mut manifest = zed();
+    manifest.config_path = BTreeMap::from([
+        ("linux".to_string(), "/etc/passwd.json".to_string()),
+        ("macos".to_string(), "~/../other/settings.json".to_string()),
+        ("windows".to_string(), "~/.zed/settings.toml".to_string()),
+    ]);
+    manifest.project_path = Some("~/.zed/settings.json".to_string());
+    assert_eq!(validate_manifest(&manifest).len(), 4);
+}
+
+#[test]
+fn test_validate_manifest_rejects_built_in_ids_and_bad_pointers() {
+    let mut manifest = zed();
+    manifest.id = "cursor".to_string();
+    manifest.servers_pointer = "context_servers".to_string();
+    manifest.dialect = Dialect {
+        fields: BTreeMap::from([("/env/PATH".to_string(), "/env".to_string())]),
+        ..Dialect::default()
+    };
+    let errors = validate_manifest(&manifest);
+    assert_eq!(errors.len(), 3);
+    assert!(errors[0].contains("built-in"));
+}
+
+#[test]
+fn test_dialect_roundtrip() {
+    let dialect = zed().dialect;
+    let canonical = json!({
+        "type": "stdio",
+        "command": "npx",
+        "args": ["-y", "fs-server"],
+        "env": { "ROOT": "/srv" }
+    });
+    let entry = to_client(&dialect, &canonical).unwrap();
+    assert_eq!(
+        entry,
+        json!({
+            "command": { "path": "npx", "args": ["-y", "fs-server"], "env": { "ROOT": "/srv" } },
+            "source": "custom"
+        })
+    );
+    let mut expected = canonical.clone();
+    expected.as_object_mut().unwrap().remove("type");
+    assert_eq!(from_client(&dialect, &entry), expected);
+}
+
+#[test]
+fn test_validate_manifest_checks_extension_of_format() {
+    let mut manifest = zed();
+    manifest.format = ConfigFormat::Yaml;
+    manifest.config_path =
+        BTreeMap::from([("default".to_string(), "~/.tool/config.yml".to_string())]);
+    manifest.project_path = Some(".tool/config.json".to_string());
+    let errors = validate_manifest(&manifest);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains(".yaml"));
+}
+
+#[test]
+fn test_template_for_absolute_paths_under_home() {
+    let home = Path::new("/home/ada");
+    assert_eq!(
+        template_for("/home/ada/.tool/mcp.toml", home),
+        "~/.tool/mcp.toml"
+    );
+    assert_eq!(
+        template_for("{config}/tool/mcp.json", home),
+        "{config}/tool/mcp.json"
+    );
+    assert_eq!(
+        template_for("/etc/tool/mcp.json", home),
+        "/etc/tool/mcp.json"
+    );
+}
+
+#[test]
+fn test_splice_toml_keeps_other_items() {
+    let content = "# Tool settings\nmodel = \"fast\" # default\n\n[servers.fs]\ncommand = \"npx\"\n\n[ui]\ntheme = \"dark\"\n";
+    let mut doc = parse_config(ConfigFormat::Toml, content).unwrap();
+    doc["servers"]["git"] = json!({ "command": "uvx", "args": ["mcp-server-git"] });
+    let edited = splice_toml(content, "servers", &doc["servers"]).unwrap();
+    assert!(edited.contains("model = \"fast\" # default"));
+    assert!(edited.starts_with("# Tool settings"));
+    assert_eq!(parse_config(ConfigFormat::Toml, &edited).unwrap(), doc);
+}
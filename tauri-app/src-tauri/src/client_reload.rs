@@ -0,0 +1,306 @@
+//! Whether a client has to be restarted or reloaded to pick up a config change
+//!
+//! Every recorded mutation (see `op_recorder::record`) leaves its target pending here. Clients
+//! differ in when they read their config: some watch the file and reload on their own, CLIs
+//! read it when a session starts, Claude Desktop only at launch, Windsurf when its MCP panel
+//! is refreshed. A client counts as running when a process with one of its names (or, for
+//! CLIs running under node, its package in the command line) is found; a client that isn't
+//! running reads the change at its next launch. Where it helps, `reload_client` nudges the
+//! client by touching its config file so a watcher fires again; none of these clients document
+//! a reload signal or CLI command, so everything else is left to the user as described in the
+//! hint. The support matrix reflects the clients' 2025 releases.
+
+use crate::client_target::ClientTarget;
+use crate::{mcp_processes, server_history};
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tauri::command;
+
+/// Targets changed since their client last reloaded, with the time of the first change
+static PENDING: Mutex<BTreeMap<String, (ClientTarget, String)>> = Mutex::new(BTreeMap::new());
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReloadBehavior {
+    /// Watches its config and reloads the servers itself
+    Watches,
+    /// Reads the config when a session starts; running sessions keep their servers
+    NewSession,
+    /// Reads the config at launch only
+    Restart,
+    /// Reloads when asked to in its UI
+    InApp,
+}
+
+/// What is known about how a client reads its config
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ClientReload {
+    pub behavior: ReloadBehavior,
+    /// Process names, compared without `.exe`
+    pub processes: &'static [&'static str],
+    /// Command-line fragments of a client that runs under another executable
+    pub command_tokens: &'static [&'static str],
+    /// What the user does to apply the change while the client runs
+    pub manual: &'static str,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ReloadHint {
+    pub target: ClientTarget,
+    pub changed_at: String,
+    pub behavior: Option<ReloadBehavior>,
+    pub running: bool,
+    /// What the app did to get the change picked up
+    pub triggered: Vec<String>,
+    /// What the user still has to do, `None` when nothing
+    pub manual: Option<String>,
+}
+
+use ReloadBehavior::*;
+
+/// How `client` reads its config, `None` when it isn't known
+pub(crate) fn client_reload(client: &str) -> Option<ClientReload> {
+    let (behavior, processes, command_tokens, manual): (_, &[&str], &[&str], _) = match client {
+        "claude" => (
+            Restart,
+            &["Claude"],
+            &[],
+            "Quit Claude Desktop completely (from the tray or menu bar, not just the window) and open it again",
+        ),
+        "claude_code" => (
+            NewSession,
+            &["claude"],
+            &["@anthropic-ai/claude-code"],
+            "Start a new Claude Code session; running sessions keep the servers they started with",
+        ),
+        "cursor" => (
+            Watches,
+            &["Cursor", "cursor"],
+            &[],
+            "If the server doesn't show up, toggle it in Cursor Settings > MCP",
+        ),
+        "vscode" => (
+            Watches,
+            &["Code", "code"],
+            &[],
+            "If VS Code shows the server as stopped, start it from the MCP: List Servers command",
+        ),
+        "windsurf" => (
+            InApp,
+            &["Windsurf", "windsurf"],
+            &[],
+            "Press the refresh button in the MCP panel of Cascade",
+        ),
+        "cline" | "roo_code" => (
+            Watches,
+            &["Code", "code", "Cursor", "cursor", "Windsurf", "windsurf"],
+            &[],
+            "If the server doesn't show up, press Restart Server in the extension's MCP view",
+        ),
+        "codex" => (
+            NewSession,
+            &["codex"],
+            &["@openai/codex"],
+            "Start a new Codex session",
+        ),
+        "amazonq" => (
+            NewSession,
+            &["q", "qchat"],
+            &[],
+            "Start a new q chat session; running ones keep the servers they loaded",
+        ),
+        "zed" => (
+            Watches,
+            &["Zed", "zed", "zed-editor"],
+            &[],
+            "If the server doesn't show up, reopen the Agent panel",
+        ),
+        "continue" => (
+            Watches,
+            &["Code", "code", "idea", "pycharm"],
+            &[],
+            "If the server doesn't show up, reload the Continue config from its settings",
+        ),
+        "goose" => (
+            NewSession,
+            &["Goose", "goose", "goosed"],
+            &[],
+            "Start a new Goose session",
+        ),
+        "cherrystudio" => (
+            Restart,
+            &["Cherry Studio", "CherryStudio"],
+            &[],
+            "Restart Cherry Studio",
+        ),
+        _ => return None,
+    };
+    Some(ClientReload {
+        behavior,
+        processes,
+        command_tokens,
+        manual,
+    })
+}
+
+/// Whether a process looks like the client: by name, or by a fragment of its command line
+pub(crate) fn is_client_process(reload: &ClientReload, name: &str, command_line: &str) -> bool {
+    let name = name.strip_suffix(".exe").unwrap_or(name);
+    reload.processes.contains(&name)
+        || reload
+            .command_tokens
+            .iter()
+            .any(|token| command_line.contains(token))
+}
+
+/// The hint for a change to a client that behaves like `reload`
+pub(crate) fn hint_for(
+    target: ClientTarget,
+    changed_at: String,
+    reload: Option<&ClientReload>,
+    running: bool,
+    triggered: Vec<String>,
+) -> ReloadHint {
+    let manual = match reload {
+        None => Some(format!(
+            "Restart {} if it doesn't pick up the change; how it reloads its config isn't known",
+            target.client
+        )),
+        Some(_) if !running => None,
+        // A nudged watcher reloads; the note only matters if it didn't
+        Some(reload) if reload.behavior == Watches && !triggered.is_empty() => None,
+        Some(reload) => Some(reload.manual.to_string()),
+    };
+    ReloadHint {
+        target,
+        changed_at,
+        behavior: reload.map(|r| r.behavior),
+        running,
+        triggered,
+        manual,
+    }
+}
+
+/// Note a change to `target`; called for every recorded mutation
+pub(crate) fn note_change(target: &ClientTarget) {
+    if let Ok(mut pending) = PENDING.lock() {
+        pending
+            .entry(target.label())
+            .or_insert_with(|| (target.clone(), Utc::now().to_rfc3339()));
+    }
+}
+
+fn running_clients(clients: &[String]) -> BTreeMap<String, bool> {
+    let system = mcp_processes::process_system();
+    clients
+        .iter()
+        .map(|client| {
+            let running = client_reload(client).is_some_and(|reload| {
+                system.processes().values().any(|process| {
+                    let command_line = process
+                        .cmd()
+                        .iter()
+                        .map(|arg| arg.to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    is_client_process(&reload, &process.name().to_string_lossy(), &command_line)
+                })
+            });
+            (client.clone(), running)
+        })
+        .collect()
+}
+
+fn pending() -> Result<Vec<(ClientTarget, String)>, String> {
+    Ok(PENDING
+        .lock()
+        .map_err(|e| format!("Failed to lock reload hints: {}", e))?
+        .values()
+        .cloned()
+        .collect())
+}
+
+fn take_pending(target: &ClientTarget) -> Option<(ClientTarget, String)> {
+    PENDING.lock().ok()?.remove(&target.label())
+}
+
+/// Clients with changes not known to be picked up yet, and what still has to be done. Hints of
+/// clients that aren't running are returned once and then dropped.
+#[command]
+pub async fn get_reload_hints() -> Result<Vec<ReloadHint>, String> {
+    let pending = pending()?;
+    let mut clients: Vec<String> = pending.iter().map(|(t, _)| t.client.clone()).collect();
+    clients.sort();
+    clients.dedup();
+    let running = tokio::task::spawn_blocking(move || running_clients(&clients))
+        .await
+        .map_err(|e| format!("Failed to list processes: {}", e))?;
+    let mut hints = Vec::new();
+    for (target, changed_at) in pending {
+        let reload = client_reload(&target.client);
+        let is_running = running.get(&target.client).copied().unwrap_or(false);
+        let hint = hint_for(target, changed_at, reload.as_ref(), is_running, Vec::new());
+        if hint.manual.is_none() {
+            take_pending(&hint.target);
+        }
+        hints.push(hint);
+    }
+    Ok(hints)
+}
+
+/// Do what the app can to get the change to `target` picked up (touch the config of a client
+/// that watches it) and report what is left to the user. The hint is dropped.
+#[command]
+pub async fn reload_client(target: ClientTarget) -> Result<ReloadHint, String> {
+    let changed_at = take_pending(&target)
+        .map(|(_, changed_at)| changed_at)
+        .unwrap_or_else(|| Utc::now().to_rfc3339());
+    let reload = client_reload(&target.client);
+    let client = target.client.clone();
+    let running = tokio::task::spawn_blocking(move || running_clients(&[client]))
+        .await
+        .map_err(|e| format!("Failed to list processes: {}", e))?
+        .into_values()
+        .next()
+        .unwrap_or(false);
+
+    let mut triggered = Vec::new();
+    if running && reload.is_some_and(|r| r.behavior == Watches) {
+        let path = server_history::config_path(&target).await?;
+        File::options()
+            .append(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(SystemTime::now()))
+            .map_err(|e| format!("Failed to touch {}: {}", path.display(), e))?;
+        println!("[Reload] Touched {} for {}", path.display(), target.label());
+        triggered.push(format!(
+            "Touched {} so {} reloads it",
+            path.display(),
+            target.client
+        ));
+    }
+    Ok(hint_for(
+        target,
+        changed_at,
+        reload.as_ref(),
+        running,
+        triggered,
+    ))
+}
+
+/// Whether a change to one of `client`'s targets is waiting for a reload
+pub(crate) fn is_pending(client: &str) -> bool {
+    PENDING
+        .lock()
+        .is_ok_and(|pending| pending.values().any(|(target, _)| target.client == client))
+}
+
+/// Forget the pending change to `target`
+#[command]
+pub async fn dismiss_reload_hint(target: ClientTarget) -> Result<bool, String> {
+    Ok(take_pending(&target).is_some())
+}
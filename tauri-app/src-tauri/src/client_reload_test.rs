@@ -0,0 +1,59 @@
+// Client reload hint tests
+use crate::client_reload::{client_reload, hint_for, is_client_process, ReloadBehavior};
+use crate::client_target::ClientTarget;
+
+#[test]
+fn test_client_processes() {
+    let desktop = client_reload("claude").unwrap();
+    let code = client_reload("claude_code").unwrap();
+    assert!(is_client_process(&desktop, "Claude.exe", ""));
+    assert!(!is_client_process(&desktop, "claude", ""));
+    assert!(is_client_process(&code, "claude", ""));
+    assert!(is_client_process(
+        &code,
+        "node",
+        "node /usr/lib/node_modules/@anthropic-ai/claude-code/cli.js"
+    ));
+    assert!(!is_client_process(&code, "node", "node server.js"));
+    assert!(client_reload("unknown").is_none());
+}
+
+#[test]
+fn test_hints_by_behavior() {
+    let target = ClientTarget::client("claude", None);
+    let desktop = client_reload("claude").unwrap();
+    assert_eq!(desktop.behavior, ReloadBehavior::Restart);
+    let hint = hint_for(
+        target.clone(),
+        String::new(),
+        Some(&desktop),
+        true,
+        Vec::new(),
+    );
+    assert!(hint.manual.unwrap().contains("Quit Claude Desktop"));
+    let closed = hint_for(target, String::new(), Some(&desktop), false, Vec::new());
+    assert_eq!(closed.manual, None);
+
+    // A nudged watcher needs nothing else
+    let cursor = client_reload("cursor").unwrap();
+    let target = ClientTarget::client("cursor", None);
+    let touched = vec!["Touched mcp.json".to_string()];
+    assert_eq!(
+        hint_for(target.clone(), String::new(), Some(&cursor), true, touched).manual,
+        None
+    );
+    assert!(
+        hint_for(target, String::new(), Some(&cursor), true, Vec::new())
+            .manual
+            .is_some()
+    );
+
+    let unknown = hint_for(
+        ClientTarget::client("acme", None),
+        String::new(),
+        None,
+        false,
+        Vec::new(),
+    );
+    assert!(unknown.manual.unwrap().contains("isn't known"));
+}
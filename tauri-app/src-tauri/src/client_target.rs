@@ -0,0 +1,253 @@
+//! One addressable server collection in any supported client
+//!
+//! Claude Code is scoped by `working_dir` and written through `add_claude_server`; every
+//! other client goes through `ClientAdapter` with an optional config `path`. Features that
+//! write to "some client" (imports, replays, bulk operations) take a `ClientTarget` instead of
+//! re-implementing this dispatch.
+
+use crate::adapter::ClientAdapter;
+use crate::claude_code_commands::{self, GLOBAL_PROJECT_ID};
+use crate::client::ClientConfig;
+use crate::json_manager::utils::{
+    get_key_by_client, is_cherrystudio_client, is_per_server_disabled_client,
+};
+use crate::json_manager::JsonManager;
+use crate::{
+    claude_disabled, claude_scan, client_plugins, codex, continue_config, goose_config,
+    json_pointer, unicode_path,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Clients with a fixed JSON config file, i.e. ones found without a project path
+pub(crate) const JSON_CLIENTS: [&str; 10] = [
+    "claude",
+    "cursor",
+    "windsurf",
+    "vscode",
+    "cline",
+    "roo_code",
+    "cherrystudio",
+    "mcphub",
+    "mcplinker",
+    "amazonq",
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct ClientTarget {
+    pub client: String,
+    /// Config path override for project-level JSON clients
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Claude Code scope; defaults to "Global"
+    #[serde(default)]
+    pub working_dir: Option<String>,
+}
+
+impl ClientTarget {
+    pub fn claude_code(working_dir: &str) -> Self {
+        ClientTarget {
+            client: "claude_code".to_string(),
+            path: None,
+            working_dir: Some(working_dir.to_string()),
+        }
+    }
+
+    /// Target for a JSON or Codex client, with the same `path` the CRUD commands take
+    pub fn client(client: &str, path: Option<&str>) -> Self {
+        ClientTarget {
+            client: client.to_string(),
+            path: path.map(|p| p.to_string()),
+            working_dir: None,
+        }
+    }
+
+    pub fn is_claude_code(&self) -> bool {
+        self.client == "claude_code"
+    }
+
+    pub fn working_dir(&self) -> &str {
+        self.working_dir.as_deref().unwrap_or(GLOBAL_PROJECT_ID)
+    }
+
+    /// Short label for logs and reports
+    pub fn label(&self) -> String {
+        match (&self.working_dir, &self.path) {
+            (Some(dir), _) if self.is_claude_code() => format!("{}:{}", self.client, dir),
+            (_, Some(path)) if !path.is_empty() => format!("{}:{}", self.client, path),
+            _ => self.client.clone(),
+        }
+    }
+}
+
+/// Every target with a config on this machine: JSON and manifest clients whose file exists,
+/// Codex, and the global and project scopes of Claude Code. Project-level configs of other
+/// clients can't be discovered and are not included.
+pub(crate) async fn known_targets() -> Vec<ClientTarget> {
+    let plugins = client_plugins::plugin_clients();
+    let mut targets: Vec<ClientTarget> = JSON_CLIENTS
+        .iter()
+        .copied()
+        .chain(plugins.iter().map(|p| p.as_str()))
+        .filter(|client| ClientConfig::new(client, None).get_path().is_file())
+        .map(|client| ClientTarget::client(client, None))
+        .collect();
+    if crate::config::get_config_path().is_ok_and(|path| path.exists()) {
+        targets.push(ClientTarget::client("codex", None));
+    }
+    if continue_config::continue_config_path(None).is_ok_and(|path| path.is_file()) {
+        targets.push(ClientTarget::client(continue_config::CLIENT, None));
+    }
+    if goose_config::goose_config_path(None).is_ok_and(|path| path.is_file()) {
+        targets.push(ClientTarget::client(goose_config::CLIENT, None));
+    }
+    if let Ok(path) = claude_code_commands::get_claude_config_path(None).await {
+        if let Ok(Some(config)) = claude_scan::load_json_cached("claude_code", &path).await {
+            targets.push(ClientTarget::claude_code(GLOBAL_PROJECT_ID));
+            if let Some(projects) = config.get("projects").and_then(|p| p.as_object()) {
+                targets.extend(
+                    projects
+                        .iter()
+                        .filter(|(_, p)| p["mcpServers"].as_object().is_some_and(|s| !s.is_empty()))
+                        .map(|(dir, _)| ClientTarget::claude_code(dir)),
+                );
+            }
+        }
+    }
+    targets
+}
+
+/// Active servers of the target as raw config objects
+pub(crate) async fn list_servers(target: &ClientTarget) -> Result<Map<String, Value>, String> {
+    if target.is_claude_code() {
+        let path = claude_code_commands::get_claude_config_path(None).await?;
+        let Some(config) = claude_scan::load_json_cached("claude_code", &path).await? else {
+            return Ok(Map::new());
+        };
+        let pointer = if target.working_dir() == GLOBAL_PROJECT_ID {
+            json_pointer::join(&["mcpServers"])
+        } else {
+            let key = unicode_path::resolve_project_key(&config, target.working_dir());
+            json_pointer::join(&["projects", &key, "mcpServers"])
+        };
+        return Ok(config
+            .pointer(&pointer)
+            .and_then(|v| v.as_object())
+            .cloned()
+            .unwrap_or_default());
+    }
+    if target.client == "codex" {
+        let servers = codex::read_mcp_servers().await?;
+        return match serde_json::to_value(servers)
+            .map_err(|e| format!("Failed to serialize codex servers: {}", e))?
+        {
+            Value::Object(map) => Ok(map),
+            _ => Ok(Map::new()),
+        };
+    }
+    if target.client == continue_config::CLIENT {
+        return continue_config::read_servers(target.path.as_deref());
+    }
+    if target.client == goose_config::CLIENT {
+        return goose_config::read_servers(target.path.as_deref(), true);
+    }
+    if let Some(manifest) = client_plugins::manifest(&target.client) {
+        return client_plugins::read_servers(&manifest, target.path.as_deref()).await;
+    }
+    let cfg = ClientConfig::new(&target.client, target.path.as_deref());
+    let json = JsonManager::read_json_file(cfg.get_path()).await?;
+    Ok(json
+        .get(get_key_by_client(&target.client))
+        .and_then(|v| v.as_object())
+        .cloned()
+        .unwrap_or_default())
+}
+
+/// Disabled servers of the target as raw config objects. For clients that flag servers in
+/// place (Cline, Roo Code, Cherry Studio) these are also part of `list_servers`.
+pub(crate) async fn list_disabled_servers(
+    target: &ClientTarget,
+) -> Result<Map<String, Value>, String> {
+    if target.is_claude_code() {
+        let disabled = claude_disabled::read_disabled_file()?;
+        let key = unicode_path::resolve_project_key(&disabled, target.working_dir());
+        return Ok(disabled
+            .pointer(&json_pointer::join(&["projects", &key]))
+            .and_then(|v| v.as_object())
+            .cloned()
+            .unwrap_or_default());
+    }
+    if target.client == "codex" {
+        let servers = codex::list_disabled().await?;
+        return match serde_json::to_value(servers)
+            .map_err(|e| format!("Failed to serialize codex servers: {}", e))?
+        {
+            Value::Object(map) => Ok(map),
+            _ => Ok(Map::new()),
+        };
+    }
+    if target.client == goose_config::CLIENT {
+        return goose_config::read_servers(target.path.as_deref(), false);
+    }
+    if target.client == continue_config::CLIENT
+        || client_plugins::manifest(&target.client).is_some()
+    {
+        return Ok(Map::new());
+    }
+    let cfg = ClientConfig::new(&target.client, target.path.as_deref());
+    let json = JsonManager::read_json_file(cfg.get_path()).await?;
+    let mut disabled = json
+        .get("__disabled")
+        .and_then(|v| v.as_object())
+        .cloned()
+        .unwrap_or_default();
+    if let Some(servers) = json
+        .get(get_key_by_client(&target.client))
+        .and_then(|v| v.as_object())
+    {
+        disabled.extend(
+            servers
+                .iter()
+                .filter(|(_, cfg)| {
+                    (is_per_server_disabled_client(&target.client)
+                        && cfg["disabled"].as_bool() == Some(true))
+                        || (is_cherrystudio_client(&target.client)
+                            && cfg["isActive"].as_bool() == Some(false))
+                })
+                .map(|(name, cfg)| (name.clone(), cfg.clone())),
+        );
+    }
+    Ok(disabled)
+}
+
+/// Create or replace a server in the target with `config` as given, keys the app doesn't
+/// model included. Returns the name written, which is always `name`.
+pub(crate) async fn upsert_server(
+    target: &ClientTarget,
+    name: &str,
+    config: Value,
+) -> Result<String, String> {
+    if target.is_claude_code() {
+        claude_code_commands::upsert_claude_entry(name, config, target.working_dir()).await?;
+        return Ok(name.to_string());
+    }
+    ClientAdapter::new(&target.client, target.path.as_deref())
+        .update(name.to_string(), config)
+        .await?;
+    Ok(name.to_string())
+}
+
+pub(crate) async fn remove_server(target: &ClientTarget, name: &str) -> Result<(), String> {
+    if target.is_claude_code() {
+        claude_code_commands::remove_claude_server(
+            name.to_string(),
+            target.working_dir().to_string(),
+        )
+        .await?;
+        return Ok(());
+    }
+    ClientAdapter::new(&target.client, target.path.as_deref())
+        .remove(name.to_string())
+        .await?;
+    Ok(())
+}
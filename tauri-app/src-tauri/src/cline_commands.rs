@@ -0,0 +1,248 @@
+//! Cline's MCP servers in the `ClaudeCodeServer` model
+//!
+//! Cline keeps its servers in cline_mcp_settings.json in VS Code's globalStorage (see
+//! `ClientConfig`). Besides the transport an entry carries Cline's own fields: `disabled`,
+//! `autoApprove` (tools run without asking), and others such as `timeout` or `cwd`. A
+//! `ClineServer` holds the transport as a `ClaudeCodeServer` and the rest next to it, and
+//! writing one back starts from the entry on disk, so nothing Cline stored is lost and the
+//! fields keep their order. Remote servers are `sse` or `streamableHttp` (`http` here), under
+//! `type` or the older `transportType`.
+
+use crate::claude_code_commands::{self, AddOutcome, ClaudeCodeResponse, ClaudeCodeServer};
+use crate::client_target::{self, ClientTarget};
+use crate::mcp_crud;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use tauri::{command, AppHandle, Runtime};
+
+/// Fields a `ClineServer` models; the others are kept in `extra`
+const MANAGED_FIELDS: [&str; 9] = [
+    "type",
+    "transportType",
+    "command",
+    "args",
+    "env",
+    "url",
+    "headers",
+    "disabled",
+    "autoApprove",
+];
+
+/// Fields the client writer sets on every write
+const BOOKKEEPING_FIELDS: [&str; 2] = ["_creator", "updated_at"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ClineServer {
+    #[serde(flatten)]
+    pub server: ClaudeCodeServer,
+    /// Left as on disk when not given
+    #[serde(default)]
+    pub disabled: Option<bool>,
+    /// Tools Cline runs without asking; left as on disk when not given
+    #[serde(default)]
+    pub auto_approve: Option<Vec<String>>,
+    /// The entry's other fields (`timeout`, `cwd`, ...); left as on disk when not given
+    #[serde(default)]
+    pub extra: Option<Map<String, Value>>,
+}
+
+/// Fields of a Cline entry that `ClineServer` doesn't model
+pub(crate) fn extra_fields(config: &Value) -> Map<String, Value> {
+    config
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(key, _)| {
+            !MANAGED_FIELDS.contains(&key.as_str()) && !BOOKKEEPING_FIELDS.contains(&key.as_str())
+        })
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
+/// A Cline entry as a `ClineServer`
+pub(crate) fn from_cline_entry(name: &str, config: &Value) -> Result<ClineServer, String> {
+    let transport = config["type"]
+        .as_str()
+        .or_else(|| config["transportType"].as_str());
+    let mut entry = config.clone();
+    let r#type = match transport {
+        Some("streamableHttp" | "streamable-http") => "http",
+        Some(other) => other,
+        // Cline treats a bare url as SSE
+        None if config["command"].is_null() && config["url"].is_string() => "sse",
+        None => "stdio",
+    };
+    entry["type"] = json!(r#type);
+    let server = claude_code_commands::parse_server_config(name, &entry)?;
+    Ok(ClineServer {
+        server,
+        disabled: Some(config["disabled"].as_bool().unwrap_or(false)),
+        auto_approve: Some(
+            config["autoApprove"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|tool| tool.as_str().map(str::to_string))
+                .collect(),
+        ),
+        extra: Some(extra_fields(config)),
+    })
+}
+
+/// A `ClineServer` as a Cline entry, merged onto the `existing` one it replaces
+pub(crate) fn to_cline_entry(
+    request: &ClineServer,
+    existing: Option<&Value>,
+) -> Result<Value, String> {
+    let on_disk = existing.and_then(Value::as_object);
+    let server = &request.server;
+    let type_key = match on_disk {
+        Some(fields) if fields.contains_key("transportType") && !fields.contains_key("type") => {
+            "transportType"
+        }
+        _ => "type",
+    };
+    let mut fields = Map::new();
+    match server.r#type.as_str() {
+        "stdio" => {
+            let command = server
+                .command
+                .as_ref()
+                .ok_or("Command is required for stdio servers")?;
+            fields.insert(type_key.into(), json!("stdio"));
+            fields.insert("command".into(), json!(command));
+            if let Some(args) = server.args.as_ref() {
+                fields.insert("args".into(), json!(args));
+            }
+            if let Some(env) = server.env.as_ref().filter(|e| !e.is_empty()) {
+                fields.insert("env".into(), json!(env));
+            }
+        }
+        "http" | "sse" => {
+            let url = server
+                .url
+                .as_ref()
+                .ok_or("URL is required for remote servers")?;
+            let transport = if server.r#type == "http" {
+                "streamableHttp"
+            } else {
+                "sse"
+            };
+            fields.insert(type_key.into(), json!(transport));
+            fields.insert("url".into(), json!(url));
+            if let Some(headers) = server.headers.as_ref().filter(|h| !h.is_empty()) {
+                fields.insert("headers".into(), json!(headers));
+            }
+        }
+        other => return Err(format!("Unsupported server type '{}'", other)),
+    }
+
+    let previous = |key: &str| on_disk.and_then(|e| e.get(key));
+    let disabled = request
+        .disabled
+        .or_else(|| previous("disabled").and_then(Value::as_bool))
+        .unwrap_or(false);
+    fields.insert("disabled".into(), json!(disabled));
+    let auto_approve = match &request.auto_approve {
+        Some(tools) => json!(tools),
+        None => previous("autoApprove")
+            .filter(|tools| tools.is_array())
+            .cloned()
+            .unwrap_or_else(|| json!([])),
+    };
+    fields.insert("autoApprove".into(), auto_approve);
+    let extra = match &request.extra {
+        Some(extra) => extra.clone(),
+        None => existing.map(extra_fields).unwrap_or_default(),
+    };
+    for (key, value) in extra {
+        if !MANAGED_FIELDS.contains(&key.as_str()) {
+            fields.entry(key).or_insert(value);
+        }
+    }
+
+    // Keep the order the fields had on disk, new ones after
+    let mut entry = Map::new();
+    for key in on_disk.into_iter().flat_map(|e| e.keys()) {
+        if let Some(value) = fields.shift_remove(key) {
+            entry.insert(key.clone(), value);
+        }
+    }
+    entry.extend(fields);
+    Ok(Value::Object(entry))
+}
+
+fn cline_target() -> ClientTarget {
+    ClientTarget::client("cline", None)
+}
+
+async fn find_server(name: &str) -> Result<Option<Value>, String> {
+    let target = cline_target();
+    let mut servers = client_target::list_servers(&target).await?;
+    servers.extend(client_target::list_disabled_servers(&target).await?);
+    Ok(servers.get(name).cloned())
+}
+
+/// List Cline's servers, disabled ones included
+#[command]
+pub async fn cline_mcp_list() -> Result<Vec<ClineServer>, String> {
+    client_target::list_servers(&cline_target())
+        .await?
+        .iter()
+        .map(|(name, config)| from_cline_entry(name, config))
+        .collect()
+}
+
+/// Add a server to Cline; fails when the name is taken
+#[command]
+pub async fn cline_mcp_add<R: Runtime>(
+    app: AppHandle<R>,
+    request: ClineServer,
+) -> Result<ClaudeCodeResponse, String> {
+    let name = request.server.name.clone();
+    let entry = to_cline_entry(&request, None)?;
+    if find_server(&name).await?.is_some() {
+        return Err(format!("Server '{}' already exists", name));
+    }
+    mcp_crud::add_mcp_server(app, "cline".to_string(), None, name.clone(), entry).await?;
+    Ok(ClaudeCodeResponse {
+        success: true,
+        message: format!("Server '{}' added to Cline", name),
+        name: Some(name),
+        outcome: Some(AddOutcome::Added),
+        warnings: Vec::new(),
+    })
+}
+
+/// Update an existing Cline server, keeping the fields the request leaves out
+#[command]
+pub async fn cline_mcp_update(request: ClineServer) -> Result<ClaudeCodeResponse, String> {
+    let name = request.server.name.clone();
+    let previous = find_server(&name)
+        .await?
+        .ok_or_else(|| format!("Server '{}' not found", name))?;
+    let entry = to_cline_entry(&request, Some(&previous))?;
+    mcp_crud::update_mcp_server("cline".to_string(), None, name.clone(), entry).await?;
+    Ok(ClaudeCodeResponse {
+        success: true,
+        message: format!("Server '{}' updated in Cline", name),
+        name: Some(name),
+        outcome: Some(AddOutcome::Overwritten),
+        warnings: Vec::new(),
+    })
+}
+
+#[command]
+pub async fn cline_mcp_remove(name: String) -> Result<ClaudeCodeResponse, String> {
+    if find_server(&name).await?.is_none() {
+        return Err(format!("Server '{}' not found", name));
+    }
+    mcp_crud::remove_mcp_server("cline".to_string(), None, name.clone()).await?;
+    Ok(ClaudeCodeResponse {
+        success: true,
+        message: format!("Server '{}' removed from Cline", name),
+        name: Some(name),
+        outcome: None,
+        warnings: Vec::new(),
+    })
+}
@@ -0,0 +1,99 @@
+// Cline command tests
+use crate::cline_commands::{extra_fields, from_cline_entry, to_cline_entry};
+use serde_json::json;
+
+#[test]
+fn test_cline_entry_round_trip_keeps_extra_fields() {
+    let entry = json!({
+        "command": "npx",
+        "args": ["-y", "@x/fs"],
+        "timeout": 120,
+        "disabled": true,
+        "autoApprove": ["read_file"],
+        "type": "stdio",
+        "cwd": "/work",
+    });
+    let server = from_cline_entry("fs", &entry).unwrap();
+    assert_eq!(server.server.r#type, "stdio");
+    assert_eq!(server.disabled, Some(true));
+    assert_eq!(server.auto_approve, Some(vec!["read_file".to_string()]));
+    assert_eq!(
+        server.extra,
+        Some(
+            json!({ "timeout": 120, "cwd": "/work" })
+                .as_object()
+                .cloned()
+                .unwrap()
+        )
+    );
+
+    let written = to_cline_entry(&server, Some(&entry)).unwrap();
+    assert_eq!(written, entry);
+    let keys: Vec<&String> = written.as_object().unwrap().keys().collect();
+    let original: Vec<&String> = entry.as_object().unwrap().keys().collect();
+    assert_eq!(keys, original);
+}
+
+#[test]
+fn test_cline_update_without_extra_keeps_the_fields_on_disk() {
+    let entry = json!({
+        "type": "sse",
+        "url": "https://x/sse",
+        "disabled": false,
+        "autoApprove": ["search"],
+        "timeout": 30,
+        "_creator": "mcp_linker",
+    });
+    let mut server = from_cline_entry("docs", &entry).unwrap();
+    server.server.url = Some("https://y/sse".to_string());
+    server.disabled = None;
+    server.auto_approve = None;
+    server.extra = None;
+    assert_eq!(
+        to_cline_entry(&server, Some(&entry)).unwrap(),
+        json!({
+            "type": "sse",
+            "url": "https://y/sse",
+            "disabled": false,
+            "autoApprove": ["search"],
+            "timeout": 30,
+        })
+    );
+}
+
+#[test]
+fn test_cline_streamable_http_transport() {
+    let entry = json!({ "transportType": "streamableHttp", "url": "https://x/mcp" });
+    let server = from_cline_entry("remote", &entry).unwrap();
+    assert_eq!(server.server.r#type, "http");
+    assert_eq!(
+        to_cline_entry(&server, Some(&entry)).unwrap(),
+        json!({
+            "transportType": "streamableHttp",
+            "url": "https://x/mcp",
+            "disabled": false,
+            "autoApprove": [],
+        })
+    );
+
+    let bare = from_cline_entry("legacy", &json!({ "url": "https://x/sse" })).unwrap();
+    assert_eq!(bare.server.r#type, "sse");
+}
+
+#[test]
+fn test_new_cline_entry_defaults() {
+    let mut server = from_cline_entry("fs", &json!({ "command": "uvx" })).unwrap();
+    server.extra = Some(extra_fields(
+        &json!({ "timeout": 60, "command": "ignored" }),
+    ));
+    assert_eq!(
+        to_cline_entry(&server, None).unwrap(),
+        json!({
+            "type": "stdio",
+            "command": "uvx",
+            "disabled": false,
+            "autoApprove": [],
+            "timeout": 60,
+        })
+    );
+}
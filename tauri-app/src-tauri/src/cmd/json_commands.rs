@@ -1,5 +1,6 @@
 use crate::client::ClientConfig;
 use crate::codex as codex_cmds;
+use crate::config_schema;
 use crate::json_manager::JsonManager;
 use serde_json::{json, Value};
 use std::path::PathBuf;
@@ -49,6 +50,8 @@ pub async fn write_json_file(
     path: Option<String>,
     content: Value,
 ) -> Result<(), String> {
+    config_schema::ensure_valid(config_schema::validate_document(&client_name, &content)?)?;
+
     let app_config = ClientConfig::new(&client_name, path.as_deref());
     let file_path = app_config.get_path();
 
@@ -65,7 +68,7 @@ pub async fn get_app_path(client_name: String, path: Option<String>) -> Result<S
 
 #[tauri::command]
 pub fn check_mcplinker_config_exists() -> bool {
-    let home_dir = dirs::home_dir().unwrap_or_default();
+    let home_dir = crate::demo_mode::home_dir().unwrap_or_default();
     let config_path: PathBuf = home_dir.join(".config/mcplinker/mcp.json");
     config_path.exists()
 }
@@ -5,9 +5,10 @@ use std::path::Path;
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
 use tokio::sync::Mutex;
-use toml_edit::{value, DocumentMut, InlineTable, Item, Table, Value};
+use toml_edit::{value, Array, DocumentMut, InlineTable, Item, Table, Value};
 
 use crate::config::{get_config_path, CodexConfig};
+use crate::write_journal;
 
 fn default_enabled() -> bool {
     true
@@ -18,7 +19,7 @@ fn is_enabled_true(enabled: &bool) -> bool {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(tag = "type")]
+#[serde(tag = "type", try_from = "RawMcpServerConfig")]
 pub enum McpServerConfig {
     #[serde(rename = "stdio")]
     Stdio {
@@ -28,15 +29,70 @@ pub enum McpServerConfig {
         env: Option<HashMap<String, String>>,
         #[serde(default = "default_enabled", skip_serializing_if = "is_enabled_true")]
         enabled: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        startup_timeout_sec: Option<f64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tool_timeout_sec: Option<f64>,
     },
     #[serde(rename = "http")]
     Http {
         url: String,
         #[serde(default = "default_enabled", skip_serializing_if = "is_enabled_true")]
         enabled: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        startup_timeout_sec: Option<f64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tool_timeout_sec: Option<f64>,
     },
 }
 
+/// A server as Codex writes it: without a `type`, the transport follows from `command` or
+/// `url`. Entries written by older versions of this app carry `type`, which is honored.
+#[derive(Deserialize)]
+struct RawMcpServerConfig {
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    command: Option<String>,
+    #[serde(default)]
+    args: Vec<String>,
+    env: Option<HashMap<String, String>>,
+    url: Option<String>,
+    #[serde(default = "default_enabled")]
+    enabled: bool,
+    startup_timeout_sec: Option<f64>,
+    tool_timeout_sec: Option<f64>,
+}
+
+impl TryFrom<RawMcpServerConfig> for McpServerConfig {
+    type Error = String;
+
+    fn try_from(raw: RawMcpServerConfig) -> Result<Self, String> {
+        let stdio = match raw.kind.as_deref() {
+            Some("stdio") => true,
+            Some("http" | "sse" | "streamable_http" | "streamable-http") => false,
+            Some(other) => return Err(format!("unknown server type `{}`", other)),
+            None => raw.command.is_some(),
+        };
+        if stdio {
+            Ok(McpServerConfig::Stdio {
+                command: raw.command.ok_or("missing field `command`")?,
+                args: raw.args,
+                env: raw.env,
+                enabled: raw.enabled,
+                startup_timeout_sec: raw.startup_timeout_sec,
+                tool_timeout_sec: raw.tool_timeout_sec,
+            })
+        } else {
+            Ok(McpServerConfig::Http {
+                url: raw.url.ok_or("missing field `command` or `url`")?,
+                enabled: raw.enabled,
+                startup_timeout_sec: raw.startup_timeout_sec,
+                tool_timeout_sec: raw.tool_timeout_sec,
+            })
+        }
+    }
+}
+
 impl McpServerConfig {
     fn is_enabled(&self) -> bool {
         match self {
@@ -141,9 +197,11 @@ async fn persist_document(config_path: &Path, doc: DocumentMut) -> Result<(), St
         .await
         .map_err(|e| format!("Failed to flush temp file: {}", e))?;
     drop(file);
-    fs::rename(&tmp_path, config_path)
-        .await
-        .map_err(|e| format!("Failed to rename temp file: {}", e))?;
+    // The rename is the only step that touches the config
+    let pending = write_journal::begin_write(config_path, &toml_content);
+    let renamed = fs::rename(&tmp_path, config_path).await;
+    pending.finish();
+    renamed.map_err(|e| format!("Failed to rename temp file: {}", e))?;
     Ok(())
 }
 
@@ -162,17 +220,104 @@ fn remove_entry(doc: &mut DocumentMut, name: &str) -> Option<Item> {
         .and_then(|table| table.remove(name))
 }
 
-fn server_to_item(config: &McpServerConfig) -> Result<Item, String> {
-    let serialized =
-        toml::to_string(config).map_err(|e| format!("Failed to serialize server config: {}", e))?;
-    let mut table = serialized
-        .parse::<DocumentMut>()
-        .map_err(|e| format!("Failed to parse serialized server config: {}", e))?
+/// Keys only a stdio server has, and keys only a streamable HTTP server has
+const STDIO_KEYS: [&str; 5] = ["command", "args", "env", "env_vars", "cwd"];
+const HTTP_KEYS: [&str; 4] = [
+    "url",
+    "bearer_token_env_var",
+    "http_headers",
+    "env_http_headers",
+];
+
+fn string_array(items: &[String]) -> Value {
+    let mut array = Array::new();
+    for item in items {
+        array.push(item.as_str());
+    }
+    Value::Array(array)
+}
+
+fn string_table(entries: &HashMap<String, String>) -> Value {
+    let mut sorted: Vec<_> = entries.iter().collect();
+    sorted.sort();
+    let mut inline = InlineTable::new();
+    for (key, val) in sorted {
+        inline.insert(key.as_str(), Value::from(val.as_str()));
+    }
+    Value::InlineTable(inline)
+}
+
+/// Write `config` into the server table, changing only the keys it models. Keys Codex knows
+/// and this app doesn't (`cwd`, `env_vars`, `enabled_tools`, ...), timeouts the config leaves
+/// unset and comments stay; keys of the other transport and the old `type` key are removed,
+/// since Codex tells the transports apart by `command` and `url`.
+pub(crate) fn apply_server(table: &mut Table, config: &McpServerConfig) {
+    table.remove("type");
+    let (startup, tool) = match config {
+        McpServerConfig::Stdio {
+            command,
+            args,
+            env,
+            startup_timeout_sec,
+            tool_timeout_sec,
+            ..
+        } => {
+            table.insert("command", value(command.as_str()));
+            table.insert("args", Item::Value(string_array(args)));
+            match env.as_ref().filter(|env| !env.is_empty()) {
+                Some(env) => {
+                    table.insert("env", Item::Value(string_table(env)));
+                }
+                None => {
+                    table.remove("env");
+                }
+            }
+            for key in HTTP_KEYS {
+                table.remove(key);
+            }
+            (startup_timeout_sec, tool_timeout_sec)
+        }
+        McpServerConfig::Http {
+            url,
+            startup_timeout_sec,
+            tool_timeout_sec,
+            ..
+        } => {
+            table.insert("url", value(url.as_str()));
+            for key in STDIO_KEYS {
+                table.remove(key);
+            }
+            (startup_timeout_sec, tool_timeout_sec)
+        }
+    };
+    for (key, seconds) in [("startup_timeout_sec", startup), ("tool_timeout_sec", tool)] {
+        if let Some(seconds) = seconds {
+            table.insert(key, value(*seconds));
+        }
+    }
+    set_enabled_field(table, config.is_enabled());
+}
+
+/// Apply `config` to the server `name`, creating its table when it is new
+fn upsert_server(
+    doc: &mut DocumentMut,
+    name: &str,
+    config: &McpServerConfig,
+) -> Result<(), String> {
+    let created = doc.get("mcp_servers").is_none();
+    let servers = ensure_table(doc, "mcp_servers")?;
+    // No bare `[mcp_servers]` header above the server tables
+    if created {
+        servers.set_implicit(true);
+    }
+    if !servers.get(name).is_some_and(Item::is_table) {
+        servers.insert(name, Item::Table(Table::new()));
+    }
+    let table = servers[name]
         .as_table_mut()
-        .clone();
-    inline_child_table(&mut table, "env");
-    inline_child_table(&mut table, "headers");
-    Ok(Item::Table(table))
+        .ok_or_else(|| format!("MCP server '{}' is not a table", name))?;
+    apply_server(table, config);
+    Ok(())
 }
 
 fn doc_counts(doc: &DocumentMut) -> (usize, usize) {
@@ -279,30 +424,11 @@ fn partition_config_states(
     (active, disabled)
 }
 
-fn inline_child_table(table: &mut Table, key: &str) {
-    if let Some(item) = table.get_mut(key) {
-        if let Item::Table(child) = item {
-            let mut inline = InlineTable::default();
-            for (k, v) in child.iter() {
-                if let Some(val) = v.as_value().cloned() {
-                    inline.insert(k, val);
-                } else {
-                    // Fallback: convert item to string for complex structures
-                    inline.insert(k, Value::from(v.to_string()));
-                }
-            }
-            *item = Item::Value(Value::InlineTable(inline));
-        }
-    }
-}
-
 pub async fn add_mcp_server(name: String, config: McpServerConfig) -> Result<(), String> {
     let _guard = CODEX_CFG_LOCK.lock().await;
     let config_path = get_config_path()?;
     let mut doc = load_document(&config_path).await?;
-    let table = ensure_table(&mut doc, "mcp_servers")?;
-    let item = server_to_item(&config)?;
-    table.insert(&name, item);
+    upsert_server(&mut doc, &name, &config)?;
     persist_document(&config_path, doc).await
 }
 
@@ -377,6 +503,33 @@ pub async fn enable(name: &str) -> Result<(), String> {
     }
 }
 
+/// Set or clear the per-server timeouts (seconds) of an active or disabled server
+pub async fn set_timeouts(
+    name: &str,
+    startup_timeout_sec: Option<f64>,
+    tool_timeout_sec: Option<f64>,
+) -> Result<(), String> {
+    let _guard = CODEX_CFG_LOCK.lock().await;
+    let config_path = get_config_path()?;
+    let mut doc = load_document(&config_path).await?;
+    let table = get_server_table_mut(&mut doc, name)
+        .ok_or_else(|| format!("MCP server '{}' not found", name))?;
+    for (key, seconds) in [
+        ("startup_timeout_sec", startup_timeout_sec),
+        ("tool_timeout_sec", tool_timeout_sec),
+    ] {
+        match seconds {
+            Some(seconds) => {
+                table.insert(key, value(seconds));
+            }
+            None => {
+                table.remove(key);
+            }
+        }
+    }
+    persist_document(&config_path, doc).await
+}
+
 pub async fn update_disabled(name: &str, server: McpServerConfig) -> Result<(), String> {
     let _guard = CODEX_CFG_LOCK.lock().await;
     let config_path = get_config_path()?;
@@ -386,10 +539,10 @@ pub async fn update_disabled(name: &str, server: McpServerConfig) -> Result<(),
         name,
         partition_server_keys(&doc).1
     );
-    let table = ensure_table(&mut doc, "mcp_servers")?;
-    let mut item = server_to_item(&server)?;
-    set_enabled_on_item(&mut item, false)?;
-    table.insert(name, item);
+    upsert_server(&mut doc, name, &server)?;
+    if let Some(table) = get_server_table_mut(&mut doc, name) {
+        set_enabled_field(table, false);
+    }
     println!(
         "[Codex] update_disabled saved | disabled_keys(after)={:?}",
         partition_server_keys(&doc).1
@@ -0,0 +1,96 @@
+// Codex config tests
+use crate::codex::{apply_server, McpServerConfig};
+use crate::config::CodexConfig;
+use std::collections::HashMap;
+use toml_edit::DocumentMut;
+
+const CONFIG: &str = r#"model = "o3"
+
+[mcp_servers.files]
+# Launched from the repo
+command = "npx"
+args = ["-y", "server-files"]
+cwd = "/work"
+enabled_tools = ["read"]
+startup_timeout_sec = 20
+"#;
+
+#[test]
+fn test_apply_server_keeps_unmodeled_keys() {
+    let mut doc: DocumentMut = CONFIG.parse().unwrap();
+    let config = McpServerConfig::Stdio {
+        command: "node".to_string(),
+        args: vec!["server.js".to_string()],
+        env: Some(HashMap::from([("TOKEN".to_string(), "x".to_string())])),
+        enabled: true,
+        startup_timeout_sec: None,
+        tool_timeout_sec: None,
+    };
+    apply_server(doc["mcp_servers"]["files"].as_table_mut().unwrap(), &config);
+    let edited = doc.to_string();
+
+    assert!(edited.starts_with("model = \"o3\""));
+    assert!(edited.contains("# Launched from the repo"));
+    assert!(edited.contains("command = \"node\""));
+    assert!(edited.contains("cwd = \"/work\""));
+    assert!(edited.contains("enabled_tools = [\"read\"]"));
+    assert!(edited.contains("startup_timeout_sec = 20"));
+    assert!(edited.contains("TOKEN = \"x\""));
+    assert!(!edited.contains("type"));
+}
+
+#[test]
+fn test_apply_server_switches_transport() {
+    let mut doc: DocumentMut =
+        "[mcp_servers.files]\ntype = \"stdio\"\ncommand = \"npx\"\nargs = []\ncwd = \"/work\"\n"
+            .parse()
+            .unwrap();
+    let config = McpServerConfig::Http {
+        url: "https://example.com/mcp".to_string(),
+        enabled: false,
+        startup_timeout_sec: None,
+        tool_timeout_sec: Some(30.0),
+    };
+    apply_server(doc["mcp_servers"]["files"].as_table_mut().unwrap(), &config);
+    let table = doc["mcp_servers"]["files"].as_table().unwrap();
+
+    for key in ["type", "command", "args", "cwd"] {
+        assert!(!table.contains_key(key), "{} was kept", key);
+    }
+    assert_eq!(table["url"].as_str(), Some("https://example.com/mcp"));
+    assert_eq!(table["tool_timeout_sec"].as_float(), Some(30.0));
+    assert_eq!(table["enabled"].as_bool(), Some(false));
+}
+
+#[test]
+fn test_parse_servers_without_type() {
+    let config: CodexConfig = toml::from_str(
+        r#"
+[mcp_servers.files]
+command = "npx"
+
+[mcp_servers.remote]
+url = "https://example.com/mcp"
+bearer_token_env_var = "TOKEN"
+
+[mcp_servers.legacy]
+type = "http"
+url = "https://example.com/old"
+"#,
+    )
+    .unwrap();
+
+    assert!(matches!(
+        &config.mcp_servers["files"],
+        McpServerConfig::Stdio { command, args, .. } if command == "npx" && args.is_empty()
+    ));
+    assert!(matches!(
+        &config.mcp_servers["remote"],
+        McpServerConfig::Http { url, enabled: true, .. } if url == "https://example.com/mcp"
+    ));
+    assert!(matches!(
+        &config.mcp_servers["legacy"],
+        McpServerConfig::Http { .. }
+    ));
+    assert!(toml::from_str::<CodexConfig>("[mcp_servers.broken]\nargs = []\n").is_err());
+}
@@ -1,4 +1,4 @@
-use dirs::home_dir;
+use crate::demo_mode::home_dir;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -20,7 +20,14 @@ pub struct CodexConfig {
     pub profiles: HashMap<String, serde_json::Value>,
 }
 
+/// config.toml in `CODEX_HOME` when it is set, as Codex itself resolves it, else in ~/.codex.
+/// Demo mode ignores `CODEX_HOME`, which names a real directory.
 pub fn get_config_path() -> Result<PathBuf, String> {
+    if let Some(codex_home) = std::env::var_os("CODEX_HOME")
+        .filter(|dir| !dir.is_empty() && !crate::demo_mode::is_active())
+    {
+        return Ok(PathBuf::from(codex_home).join("config.toml"));
+    }
     let home = home_dir().ok_or_else(|| "Failed to get home directory".to_string())?;
     Ok(home.join(".codex").join("config.toml"))
 }
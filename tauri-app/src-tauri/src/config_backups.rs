@@ -0,0 +1,195 @@
+//! Backups of ~/.claude.json taken before each write, and their manifest
+//!
+//! `create` copies the config to `<name>.backup.<id>` next to it and records the backup under
+//! that id in config_backups.json in the app data dir. Backups used to be named after the
+//! second they were taken, so two writes within one second shared a file: the second copy
+//! replaced the first, and whichever write finished first deleted the other's backup. Ids now
+//! combine the millisecond with a sequence number of this run, and the file is created only if
+//! it doesn't exist, so a clash with another process moves on to the next id. A failed write
+//! rolls back from the backup with its id; `restore_config_backup` does the same on request.
+//! The manifest also notes when each config was last backed up, which outlives the discarded
+//! backup files.
+
+use crate::settings::app_config_dir;
+use crate::{claude_scan, disk_space, metrics, unicode_path};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tauri::command;
+
+/// Tried ids per backup before giving up on a run of clashes
+const MAX_ATTEMPTS: u32 = 16;
+
+static NEXT_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// Serializes read-modify-write cycles of config_backups.json
+static LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ConfigBackup {
+    pub id: String,
+    /// The config the backup was taken of
+    pub config: PathBuf,
+    pub path: PathBuf,
+    pub created_at: String,
+}
+
+impl ConfigBackup {
+    /// Delete the backup once the write it guarded has gone through
+    pub(crate) fn discard(self) {
+        let _ = fs::remove_file(&self.path);
+        if let Err(e) = update_manifest(|manifest| manifest.backups.retain(|b| b.id != self.id)) {
+            println!(
+                "[Backup] Failed to drop {} from the manifest: {}",
+                self.id, e
+            );
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct Manifest {
+    #[serde(default)]
+    backups: Vec<ConfigBackup>,
+    /// When the newest backup of each config was taken, by config path
+    #[serde(default)]
+    last_taken: HashMap<String, String>,
+}
+
+fn manifest_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("config_backups.json"))
+}
+
+fn read_manifest() -> Result<Manifest, String> {
+    match fs::read_to_string(manifest_path()?) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse backup manifest: {}", e)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Manifest::default()),
+        Err(e) => Err(format!("Failed to read backup manifest: {}", e)),
+    }
+}
+
+fn update_manifest<T>(update: impl FnOnce(&mut Manifest) -> T) -> Result<T, String> {
+    let _guard = LOCK
+        .lock()
+        .map_err(|e| format!("Failed to lock backup manifest: {}", e))?;
+    let mut manifest = read_manifest()?;
+    let result = update(&mut manifest);
+    let path = manifest_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize backup manifest: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write backup manifest: {}", e))?;
+    Ok(result)
+}
+
+/// Copy `config` to a backup file no other backup uses; returns its id and path
+pub(crate) fn copy_unique(config: &Path) -> Result<(String, PathBuf), String> {
+    let millis = Utc::now().timestamp_millis();
+    for _ in 0..MAX_ATTEMPTS {
+        let id = format!("{}-{}", millis, NEXT_SEQ.fetch_add(1, Ordering::Relaxed));
+        let path = unicode_path::backup_path(config, &id);
+        let mut target = match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(target) => target,
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(format!("Failed to create backup: {}", e)),
+        };
+        let copied = File::open(config).and_then(|mut source| io::copy(&mut source, &mut target));
+        if let Err(e) = copied {
+            let _ = fs::remove_file(&path);
+            return Err(format!("Failed to create backup: {}", e));
+        }
+        return Ok((id, path));
+    }
+    Err("Failed to create backup: no free backup name".to_string())
+}
+
+/// Back up `config` before a write and record the backup in the manifest
+pub(crate) fn create(config: &Path) -> Result<ConfigBackup, String> {
+    if !config.exists() {
+        return Err("Config file does not exist".to_string());
+    }
+    let size = fs::metadata(config)
+        .map_err(|e| format!("Failed to read config file: {}", e))?
+        .len();
+    disk_space::ensure_space(config, size)?;
+
+    let copied = copy_unique(config);
+    metrics::backup_operation("create", copied.is_ok());
+    let (id, path) = copied?;
+    let backup = ConfigBackup {
+        id,
+        config: config.to_path_buf(),
+        path,
+        created_at: Utc::now().to_rfc3339(),
+    };
+    let recorded = update_manifest(|manifest| {
+        manifest
+            .last_taken
+            .insert(config.display().to_string(), backup.created_at.clone());
+        manifest.backups.push(backup.clone());
+    });
+    if let Err(e) = recorded {
+        // The copy is still there for the rollback of this write
+        println!("[Backup] Failed to record {}: {}", backup.id, e);
+    }
+    Ok(backup)
+}
+
+/// When `config` was last backed up, whether or not the backup is still kept
+pub(crate) fn last_backup_at(config: &Path) -> Option<String> {
+    read_manifest()
+        .ok()?
+        .last_taken
+        .remove(&config.display().to_string())
+}
+
+/// Copy the backup back over the config it was taken of
+pub(crate) fn restore(backup: &ConfigBackup) -> Result<(), String> {
+    if !backup.path.exists() {
+        return Err("Backup file does not exist".to_string());
+    }
+    let copied = fs::copy(&backup.path, &backup.config);
+    metrics::backup_operation("restore", copied.is_ok());
+    copied.map_err(|e| format!("Failed to restore backup: {}", e))?;
+    Ok(())
+}
+
+/// Recorded backups whose files still exist, of `config` or of every config, newest first
+#[command]
+pub async fn list_config_backups(config: Option<String>) -> Result<Vec<ConfigBackup>, String> {
+    let manifest = read_manifest()?;
+    Ok(manifest
+        .backups
+        .into_iter()
+        .rev()
+        .filter(|b| config.as_ref().is_none_or(|c| b.config == Path::new(c)))
+        .filter(|b| b.path.exists())
+        .collect())
+}
+
+/// Restore the config a backup was taken of from the backup `id`
+#[command]
+pub async fn restore_config_backup(id: String) -> Result<ConfigBackup, String> {
+    let backup = read_manifest()?
+        .backups
+        .into_iter()
+        .find(|b| b.id == id)
+        .ok_or_else(|| format!("Backup '{}' not found", id))?;
+    restore(&backup)?;
+    claude_scan::invalidate(&backup.config);
+    println!(
+        "[Backup] Restored {} from backup {}",
+        backup.config.display(),
+        backup.id
+    );
+    Ok(backup)
+}
@@ -0,0 +1,33 @@
+// Config backup tests
+use crate::config_backups::{copy_unique, create, last_backup_at};
+use crate::unicode_path::is_backup_of;
+use std::fs;
+
+#[test]
+fn test_backups_in_the_same_second_get_their_own_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join(".claude.json");
+    fs::write(&config, "{\"first\": true}").unwrap();
+    let (first_id, first) = copy_unique(&config).unwrap();
+    fs::write(&config, "{\"second\": true}").unwrap();
+    let (second_id, second) = copy_unique(&config).unwrap();
+
+    assert_ne!(first_id, second_id);
+    assert_ne!(first, second);
+    assert!(is_backup_of(&config, &first) && is_backup_of(&config, &second));
+    assert_eq!(fs::read_to_string(&first).unwrap(), "{\"first\": true}");
+    assert_eq!(fs::read_to_string(&second).unwrap(), "{\"second\": true}");
+    assert!(copy_unique(&dir.path().join("missing.json")).is_err());
+}
+
+#[test]
+fn test_last_backup_is_kept_after_discard() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join(".claude.json");
+    fs::write(&config, "{}").unwrap();
+    assert_eq!(last_backup_at(&config), None);
+    let backup = create(&config).unwrap();
+    let created_at = backup.created_at.clone();
+    backup.discard();
+    assert_eq!(last_backup_at(&config), Some(created_at));
+}
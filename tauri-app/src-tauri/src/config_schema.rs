@@ -0,0 +1,281 @@
+//! JSON Schemas for each client config dialect
+//!
+//! The schemas under `schemas/mcp` are embedded at build time, served to the frontend through
+//! `get_config_schema`, and used to validate documents before they are written. Validation
+//! implements the draft-07 subset the embedded schemas use (`type`, `properties`, `required`,
+//! `additionalProperties`, `items`, `enum`, `anyOf`, `minLength` and local `$ref`).
+
+use crate::client::ClientConfig;
+use crate::json_manager::JsonManager;
+use crate::settings::app_config_dir;
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use tauri::command;
+
+const MCP_SERVERS_SCHEMA: &str = include_str!("../schemas/mcp/mcp_servers.schema.json");
+const VSCODE_SCHEMA: &str = include_str!("../schemas/mcp/vscode.schema.json");
+const CLINE_SCHEMA: &str = include_str!("../schemas/mcp/cline.schema.json");
+const CHERRYSTUDIO_SCHEMA: &str = include_str!("../schemas/mcp/cherrystudio.schema.json");
+const CLAUDE_CODE_SCHEMA: &str = include_str!("../schemas/mcp/claude_code.schema.json");
+const CODEX_SCHEMA: &str = include_str!("../schemas/mcp/codex.schema.json");
+const WINDSURF_SCHEMA: &str = include_str!("../schemas/mcp/windsurf.schema.json");
+
+/// Validation stops after this many errors
+const MAX_ERRORS: usize = 50;
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct SchemaError {
+    /// JSON pointer of the offending value
+    pub pointer: String,
+    pub message: String,
+}
+
+/// Schema dialect name and source for a client
+fn dialect(client: &str) -> (&'static str, &'static str) {
+    match client {
+        "vscode" => ("vscode", VSCODE_SCHEMA),
+        "cline" | "roo_code" => ("cline", CLINE_SCHEMA),
+        "cherrystudio" => ("cherrystudio", CHERRYSTUDIO_SCHEMA),
+        "claude_code" => ("claude_code", CLAUDE_CODE_SCHEMA),
+        "codex" => ("codex", CODEX_SCHEMA),
+        "windsurf" => ("windsurf", WINDSURF_SCHEMA),
+        _ => ("mcp_servers", MCP_SERVERS_SCHEMA),
+    }
+}
+
+pub(crate) fn schema_for(client: &str) -> Result<Value, String> {
+    serde_json::from_str(dialect(client).1)
+        .map_err(|e| format!("Embedded schema for '{}' is invalid: {}", client, e))
+}
+
+/// Validate a whole config document against the client's schema
+pub(crate) fn validate_document(
+    client: &str,
+    document: &Value,
+) -> Result<Vec<SchemaError>, String> {
+    let schema = schema_for(client)?;
+    let mut errors = Vec::new();
+    validate(&schema, &schema, document, "", &mut errors);
+    Ok(errors)
+}
+
+/// Validate a single server entry against the client's server definition
+pub(crate) fn validate_server_entry(
+    client: &str,
+    name: &str,
+    entry: &Value,
+) -> Result<Vec<SchemaError>, String> {
+    let schema = schema_for(client)?;
+    let server_schema = schema
+        .pointer("/definitions/server")
+        .or_else(|| schema.pointer("/properties/mcp_servers/additionalProperties"))
+        .cloned()
+        .unwrap_or(Value::Bool(true));
+    let mut errors = Vec::new();
+    let pointer = format!("/{}", crate::json_pointer::escape_segment(name));
+    validate(&schema, &server_schema, entry, &pointer, &mut errors);
+    Ok(errors)
+}
+
+/// Turn validation errors into the repo's `Result<_, String>` error style
+pub(crate) fn ensure_valid(errors: Vec<SchemaError>) -> Result<(), String> {
+    if errors.is_empty() {
+        return Ok(());
+    }
+    let summary: Vec<String> = errors
+        .iter()
+        .take(3)
+        .map(|e| {
+            let at = if e.pointer.is_empty() {
+                "/"
+            } else {
+                &e.pointer
+            };
+            format!("{}: {}", at, e.message)
+        })
+        .collect();
+    Err(format!(
+        "Config does not match schema: {}",
+        summary.join("; ")
+    ))
+}
+
+fn type_matches(expected: &str, value: &Value) -> bool {
+    match expected {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+fn validate(
+    root: &Value,
+    schema: &Value,
+    value: &Value,
+    pointer: &str,
+    errors: &mut Vec<SchemaError>,
+) {
+    if errors.len() >= MAX_ERRORS {
+        return;
+    }
+    let schema = match schema {
+        Value::Bool(true) => return,
+        Value::Bool(false) => {
+            errors.push(error(pointer, "value is not allowed"));
+            return;
+        }
+        Value::Object(map) => map,
+        _ => return,
+    };
+
+    if let Some(reference) = schema.get("$ref").and_then(|r| r.as_str()) {
+        match reference.strip_prefix('#').and_then(|p| root.pointer(p)) {
+            Some(target) => validate(root, target, value, pointer, errors),
+            None => errors.push(error(pointer, &format!("unresolved $ref '{}'", reference))),
+        }
+        return;
+    }
+
+    if let Some(expected) = schema.get("type") {
+        let ok = match expected {
+            Value::String(t) => type_matches(t, value),
+            Value::Array(types) => types
+                .iter()
+                .filter_map(|t| t.as_str())
+                .any(|t| type_matches(t, value)),
+            _ => true,
+        };
+        if !ok {
+            errors.push(error(pointer, &format!("expected type {}", expected)));
+            return;
+        }
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(|e| e.as_array()) {
+        if !allowed.contains(value) {
+            errors.push(error(
+                pointer,
+                &format!("must be one of {}", Value::Array(allowed.clone())),
+            ));
+        }
+    }
+
+    if let (Some(min), Some(s)) = (
+        schema.get("minLength").and_then(|m| m.as_u64()),
+        value.as_str(),
+    ) {
+        if (s.chars().count() as u64) < min {
+            errors.push(error(
+                pointer,
+                &format!("must be at least {} characters", min),
+            ));
+        }
+    }
+
+    if let Some(options) = schema.get("anyOf").and_then(|a| a.as_array()) {
+        let matched = options.iter().any(|option| {
+            let mut option_errors = Vec::new();
+            validate(root, option, value, pointer, &mut option_errors);
+            option_errors.is_empty()
+        });
+        if !matched {
+            errors.push(error(pointer, "does not match any allowed shape"));
+        }
+    }
+
+    if let Some(object) = value.as_object() {
+        for key in schema
+            .get("required")
+            .and_then(|r| r.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|k| k.as_str())
+        {
+            if !object.contains_key(key) {
+                errors.push(error(
+                    pointer,
+                    &format!("missing required property '{}'", key),
+                ));
+            }
+        }
+        let properties = schema.get("properties").and_then(|p| p.as_object());
+        for (key, child) in object {
+            let child_pointer = format!("{}/{}", pointer, crate::json_pointer::escape_segment(key));
+            match properties.and_then(|p| p.get(key)) {
+                Some(child_schema) => validate(root, child_schema, child, &child_pointer, errors),
+                None => {
+                    if let Some(additional) = schema.get("additionalProperties") {
+                        validate(root, additional, child, &child_pointer, errors);
+                    }
+                }
+            }
+        }
+    }
+
+    if let (Some(items), Some(array)) = (schema.get("items"), value.as_array()) {
+        for (index, item) in array.iter().enumerate() {
+            validate(root, items, item, &format!("{}/{}", pointer, index), errors);
+        }
+    }
+}
+
+fn error(pointer: &str, message: &str) -> SchemaError {
+    SchemaError {
+        pointer: pointer.to_string(),
+        message: message.to_string(),
+    }
+}
+
+/// JSON Schema of the client's config dialect
+#[command]
+pub async fn get_config_schema(client: String) -> Result<Value, String> {
+    schema_for(&client)
+}
+
+#[command]
+pub async fn validate_config(client: String, content: Value) -> Result<Vec<SchemaError>, String> {
+    validate_document(&client, &content)
+}
+
+/// Write the client's schema to ~/.config/mcplinker/schemas and reference it from the config's
+/// `$schema` key so editors offer completion. Returns the schema URI.
+#[command]
+pub async fn insert_config_schema_ref(
+    client: String,
+    path: Option<String>,
+) -> Result<String, String> {
+    if matches!(client.as_str(), "claude_code" | "codex") {
+        return Err(format!(
+            "'{}' config is not edited by hand through a schema",
+            client
+        ));
+    }
+    let (name, source) = dialect(&client);
+    let schema_dir = app_config_dir()?.join("schemas");
+    fs::create_dir_all(&schema_dir)
+        .map_err(|e| format!("Failed to create schema directory: {}", e))?;
+    let schema_path = schema_dir.join(format!("{}.schema.json", name));
+    fs::write(&schema_path, source).map_err(|e| format!("Failed to write schema: {}", e))?;
+    let uri = url::Url::from_file_path(&schema_path)
+        .map_err(|_| format!("Invalid schema path: {}", schema_path.display()))?
+        .to_string();
+
+    let cfg = ClientConfig::new(&client, path.as_deref());
+    let config_path = cfg.get_path();
+    let mut json = JsonManager::read_json_file(config_path).await?;
+    let object = json.as_object().ok_or("Config root is not an object")?;
+    // Keep $schema first, where editors and readers expect it
+    let mut with_schema = serde_json::Map::new();
+    with_schema.insert("$schema".to_string(), Value::String(uri.clone()));
+    for (key, value) in object.iter().filter(|(k, _)| *k != "$schema") {
+        with_schema.insert(key.clone(), value.clone());
+    }
+    JsonManager::write_json_file(config_path, &Value::Object(with_schema)).await?;
+    Ok(uri)
+}
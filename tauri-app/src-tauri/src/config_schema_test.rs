@@ -0,0 +1,55 @@
+// Config schema validation tests
+use crate::adapter::ClientAdapter;
+use crate::cmd::write_json_file;
+use crate::config_schema::{validate_document, validate_server_entry};
+use serde_json::json;
+
+#[test]
+fn test_valid_mcp_servers_document() {
+    let doc = json!({
+        "mcpServers": {
+            "fs": { "command": "npx", "args": ["-y", "@modelcontextprotocol/server-filesystem"] },
+            "remote": { "type": "http", "url": "https://example.com/mcp" }
+        }
+    });
+    assert!(validate_document("claude", &doc).unwrap().is_empty());
+}
+
+#[test]
+fn test_server_without_command_or_url_is_rejected() {
+    let errors =
+        validate_server_entry("cursor", "broken", &json!({ "args": ["--port", 3000] })).unwrap();
+    assert!(errors.iter().any(|e| e.pointer == "/broken/args/1"));
+    assert!(errors
+        .iter()
+        .any(|e| e.message.contains("any allowed shape")));
+}
+
+#[tokio::test]
+async fn test_adapter_rejects_invalid_entry() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let base = temp_dir.path().to_str().unwrap();
+    let adapter = ClientAdapter::new("cursor", Some(base));
+    let invalid = json!({ "args": ["--port", 3000] });
+
+    let added = adapter.add("broken".to_string(), invalid.clone()).await;
+    assert!(added
+        .unwrap_err()
+        .starts_with("Config does not match schema"));
+    let updated = adapter.update("broken".to_string(), invalid).await;
+    assert!(updated
+        .unwrap_err()
+        .starts_with("Config does not match schema"));
+    assert!(!temp_dir.path().join(".cursor/mcp.json").exists());
+}
+
+#[tokio::test]
+async fn test_write_json_file_rejects_invalid_document() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let base = temp_dir.path().to_string_lossy().to_string();
+    let invalid = json!({ "mcpServers": { "broken": { "args": ["--port", 3000] } } });
+
+    let result = write_json_file("cursor".to_string(), Some(base), invalid).await;
+    assert!(result.unwrap_err().contains("/mcpServers/broken"));
+    assert!(!temp_dir.path().join(".cursor/mcp.json").exists());
+}
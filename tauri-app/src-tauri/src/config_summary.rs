@@ -0,0 +1,191 @@
+//! Plain-language summaries of the servers Claude Code loads
+//!
+//! `describe_config` describes each scope in full sentences ("3 user-scope servers: github
+//! (stdio via npx), sentry (http at mcp.sentry.io), ..."), so it reads well through a screen
+//! reader and can be pasted into a chat. Every server is also returned as structured fields for
+//! the UI to lay out. Summaries say how a server is reached but never include env values,
+//! headers, arguments or URL paths, which may carry secrets.
+
+use crate::claude_code_commands::{self, is_global_config, ClaudeCodeServer, GLOBAL_PROJECT_ID};
+use crate::client_target::{self, ClientTarget};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ServerSummary {
+    pub name: String,
+    pub transport: String,
+    /// "stdio via npx", "http at mcp.sentry.io"
+    pub reached: String,
+    pub disabled: bool,
+    /// Scope whose server of the same name this one overrides
+    pub overrides: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ScopeSummary {
+    /// "user", "project" or "local"
+    pub scope: String,
+    pub servers: Vec<ServerSummary>,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ConfigSummary {
+    pub working_dir: String,
+    pub scopes: Vec<ScopeSummary>,
+    /// The scope texts as one paragraph
+    pub text: String,
+}
+
+/// How a server is reached, without anything that may be secret
+pub(crate) fn reached(server: &ClaudeCodeServer) -> String {
+    match (&server.command, &server.url) {
+        (Some(command), _) => {
+            let program = Path::new(command)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| command.clone());
+            format!("{} via {}", server.r#type, program)
+        }
+        (None, Some(url)) => match url::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_string))
+        {
+            Some(host) => format!("{} at {}", server.r#type, host),
+            None => server.r#type.clone(),
+        },
+        (None, None) => server.r#type.clone(),
+    }
+}
+
+fn summarize(name: &str, config: &Value, disabled: bool) -> Option<ServerSummary> {
+    let server = claude_code_commands::parse_server_config(name, config).ok()?;
+    Some(ServerSummary {
+        name: name.to_string(),
+        transport: server.r#type.clone(),
+        reached: reached(&server),
+        disabled,
+        overrides: None,
+    })
+}
+
+/// The sentence(s) for one scope
+pub(crate) fn scope_text(scope: &str, servers: &[ServerSummary]) -> String {
+    let (active, disabled): (Vec<&ServerSummary>, Vec<&ServerSummary>) =
+        servers.iter().partition(|s| !s.disabled);
+    let mut text = match active.len() {
+        0 => format!("No {}-scope servers.", scope),
+        count => format!(
+            "{} {}-scope server{}: {}.",
+            count,
+            scope,
+            if count == 1 { "" } else { "s" },
+            active
+                .iter()
+                .map(|s| format!("{} ({})", s.name, s.reached))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    if !disabled.is_empty() {
+        text.push_str(&format!(
+            " Disabled: {}.",
+            disabled
+                .iter()
+                .map(|s| s.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    for server in active.iter().filter(|s| s.overrides.is_some()) {
+        text.push_str(&format!(
+            " {} overrides the {}-scope server of the same name.",
+            server.name,
+            server.overrides.as_deref().unwrap_or_default()
+        ));
+    }
+    text
+}
+
+/// Servers of a project's shared .mcp.json
+fn project_servers(working_dir: &str) -> Map<String, Value> {
+    let path = PathBuf::from(working_dir).join(".mcp.json");
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+        .and_then(|config| config.get("mcpServers").and_then(Value::as_object).cloned())
+        .unwrap_or_default()
+}
+
+async fn target_servers(target: &ClientTarget) -> Result<Vec<ServerSummary>, String> {
+    let mut servers: Vec<ServerSummary> = client_target::list_servers(target)
+        .await?
+        .iter()
+        .filter_map(|(name, config)| summarize(name, config, false))
+        .collect();
+    servers.extend(
+        client_target::list_disabled_servers(target)
+            .await?
+            .iter()
+            .filter_map(|(name, config)| summarize(name, config, true)),
+    );
+    Ok(servers)
+}
+
+/// Plain-language summary of the user-scope servers and, for a project, its project and
+/// local ones. Scopes later in the list take precedence for servers of the same name.
+#[command]
+pub async fn describe_config(working_dir: String) -> Result<ConfigSummary, String> {
+    let mut scopes = vec![(
+        "user",
+        target_servers(&ClientTarget::claude_code(GLOBAL_PROJECT_ID)).await?,
+    )];
+    if !is_global_config(&working_dir) {
+        let project = project_servers(&working_dir)
+            .iter()
+            .filter_map(|(name, config)| summarize(name, config, false))
+            .collect();
+        scopes.push(("project", project));
+        scopes.push((
+            "local",
+            target_servers(&ClientTarget::claude_code(&working_dir)).await?,
+        ));
+    }
+
+    for index in 1..scopes.len() {
+        let (earlier, later) = scopes.split_at_mut(index);
+        for server in later[0].1.iter_mut().filter(|s| !s.disabled) {
+            server.overrides = earlier
+                .iter()
+                .rev()
+                .find(|(_, servers)| {
+                    servers
+                        .iter()
+                        .any(|other| other.name == server.name && !other.disabled)
+                })
+                .map(|(scope, _)| scope.to_string());
+        }
+    }
+
+    let scopes: Vec<ScopeSummary> = scopes
+        .into_iter()
+        .map(|(scope, servers)| ScopeSummary {
+            scope: scope.to_string(),
+            text: scope_text(scope, &servers),
+            servers,
+        })
+        .collect();
+    Ok(ConfigSummary {
+        text: scopes
+            .iter()
+            .map(|s| s.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" "),
+        working_dir,
+        scopes,
+    })
+}
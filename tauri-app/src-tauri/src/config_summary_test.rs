@@ -0,0 +1,53 @@
+// Config summary tests
+use crate::claude_code_commands::parse_server_config;
+use crate::config_summary::{reached, scope_text, ServerSummary};
+use serde_json::json;
+
+fn summary(name: &str, reached: &str, disabled: bool) -> ServerSummary {
+    ServerSummary {
+        name: name.to_string(),
+        transport: "stdio".to_string(),
+        reached: reached.to_string(),
+        disabled,
+        overrides: None,
+    }
+}
+
+#[test]
+fn test_reached_leaves_out_secrets() {
+    let npx = parse_server_config(
+        "github",
+        &json!({ "command": "/usr/local/bin/npx", "args": ["-y", "x"], "env": { "T": "secret" } }),
+    )
+    .unwrap();
+    assert_eq!(reached(&npx), "stdio via npx");
+    let remote = parse_server_config(
+        "sentry",
+        &json!({ "type": "http", "url": "https://mcp.sentry.io/mcp?token=abc" }),
+    )
+    .unwrap();
+    assert_eq!(reached(&remote), "http at mcp.sentry.io");
+}
+
+#[test]
+fn test_scope_text_reads_as_sentences() {
+    let servers = vec![
+        summary("github", "stdio via npx", false),
+        summary("sentry", "http at mcp.sentry.io", false),
+        summary("old", "stdio via uvx", true),
+    ];
+    assert_eq!(
+        scope_text("user", &servers),
+        "2 user-scope servers: github (stdio via npx), sentry (http at mcp.sentry.io). \
+         Disabled: old."
+    );
+    assert_eq!(scope_text("project", &[]), "No project-scope servers.");
+
+    let mut local = summary("github", "stdio via docker", false);
+    local.overrides = Some("user".to_string());
+    assert_eq!(
+        scope_text("local", &[local]),
+        "1 local-scope server: github (stdio via docker). github overrides the user-scope \
+         server of the same name."
+    );
+}
@@ -0,0 +1,177 @@
+//! Raw JSON pointer access to client configs
+//!
+//! Lets the frontend and advanced users read and write fields the typed server model doesn't
+//! know about yet. Pointers are relative to the scope's root (the Claude Code project entry or
+//! the JSON client's config file) and must stay inside an MCP subtree, so this can't be used
+//! to rewrite unrelated settings.
+
+use crate::claude_code_commands::{
+    create_backup, get_claude_config_path, write_claude_config_verified, GLOBAL_PROJECT_ID,
+};
+use crate::client::ClientConfig;
+use crate::config_schema;
+use crate::json_manager::utils::get_key_by_client;
+use crate::json_manager::JsonManager;
+use crate::json_pointer;
+use crate::unicode_path;
+use serde_json::Value;
+use std::fs;
+use tauri::command;
+
+const CLAUDE_CODE_SUBTREES: [&str; 3] = [
+    "mcpServers",
+    "enabledMcpjsonServers",
+    "disabledMcpjsonServers",
+];
+
+fn allowed_subtrees(client: &str) -> Result<Vec<&str>, String> {
+    match client {
+        "claude_code" => Ok(CLAUDE_CODE_SUBTREES.to_vec()),
+        "codex" => Err("Codex config is TOML and not addressable by JSON pointer".to_string()),
+        other => Ok(vec![get_key_by_client(other), "__disabled"]),
+    }
+}
+
+/// Check `pointer` is inside a whitelisted subtree. Writes must target something below the
+/// subtree root, so a single call can't replace every server at once. Segments must be valid
+/// RFC 6901 escapes and can't be `.` or `..`, which other tools might resolve as paths.
+pub(crate) fn check_pointer(client: &str, pointer: &str, for_write: bool) -> Result<(), String> {
+    let subtrees = allowed_subtrees(client)?;
+    let raw: Vec<&str> = pointer
+        .strip_prefix('/')
+        .ok_or_else(|| format!("'{}' is not a JSON pointer", pointer))?
+        .split('/')
+        .collect();
+    for segment in &raw {
+        if segment
+            .split('~')
+            .skip(1)
+            .any(|after| !after.starts_with(['0', '1']))
+        {
+            return Err(format!("'{}' has an invalid '~' escape", pointer));
+        }
+    }
+    let segments: Vec<String> = raw
+        .into_iter()
+        .map(json_pointer::unescape_segment)
+        .collect();
+    if segments.iter().any(|s| s == "." || s == "..") {
+        return Err(format!("'{}' contains a relative segment", pointer));
+    }
+    if !subtrees.contains(&segments[0].as_str()) {
+        return Err(format!(
+            "'{}' is outside the editable subtrees ({})",
+            pointer,
+            subtrees.join(", ")
+        ));
+    }
+    if for_write && segments.len() < 2 {
+        return Err(format!(
+            "'{}' replaces a whole subtree; address a server",
+            pointer
+        ));
+    }
+    Ok(())
+}
+
+/// Pointer from the file root for a scope-relative pointer, using the project's key as
+/// spelled in `config`
+fn claude_pointer(config: &Value, scope: Option<&str>, pointer: &str) -> String {
+    match scope {
+        Some(dir) if dir != GLOBAL_PROJECT_ID => {
+            let key = unicode_path::resolve_project_key(config, dir);
+            format!("{}{}", json_pointer::join(&["projects", &key]), pointer)
+        }
+        _ => pointer.to_string(),
+    }
+}
+
+/// Read the value at `json_pointer`. `scope` is the Claude Code working dir or the config path
+/// of a JSON client. Returns `null` when nothing is there.
+#[command]
+pub async fn get_config_value(
+    client: String,
+    scope: Option<String>,
+    json_pointer: String,
+) -> Result<Value, String> {
+    check_pointer(&client, &json_pointer, false)?;
+    if client == "claude_code" {
+        let config_path = get_claude_config_path(None).await?;
+        if !config_path.exists() {
+            return Ok(Value::Null);
+        }
+        let content = fs::read_to_string(&config_path)
+            .map_err(|e| format!("Failed to read Claude config: {}", e))?;
+        let config: Value = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse Claude config: {}", e))?;
+        let pointer = claude_pointer(&config, scope.as_deref(), &json_pointer);
+        return Ok(config.pointer(&pointer).cloned().unwrap_or(Value::Null));
+    }
+    let cfg = ClientConfig::new(&client, scope.as_deref());
+    let json = JsonManager::read_json_file(cfg.get_path()).await?;
+    Ok(json.pointer(&json_pointer).cloned().unwrap_or(Value::Null))
+}
+
+/// Set the value at `json_pointer`, or remove it when `value` is omitted. The result is
+/// checked against the client's schema before it is written.
+#[command]
+pub async fn set_config_value(
+    client: String,
+    scope: Option<String>,
+    json_pointer: String,
+    value: Option<Value>,
+) -> Result<Value, String> {
+    check_pointer(&client, &json_pointer, true)?;
+    if client == "claude_code" {
+        let config_path = get_claude_config_path(None).await?;
+        if !config_path.exists() {
+            return Err("Claude config file not found".to_string());
+        }
+        let content = fs::read_to_string(&config_path)
+            .map_err(|e| format!("Failed to read Claude config: {}", e))?;
+        let mut config: Value = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse Claude config: {}", e))?;
+        let before = config.clone();
+        let pointer = claude_pointer(&config, scope.as_deref(), &json_pointer);
+        if scope.as_deref().is_some_and(|dir| dir != GLOBAL_PROJECT_ID)
+            && config
+                .pointer(&claude_pointer(&config, scope.as_deref(), ""))
+                .is_none()
+        {
+            return Err(format!("Project '{}' not found", scope.unwrap_or_default()));
+        }
+        apply(&mut config, &pointer, value.clone())?;
+        config_schema::ensure_valid(config_schema::validate_document(&client, &config)?)?;
+
+        let backup = create_backup(&config_path)?;
+        write_claude_config_verified(
+            &config_path,
+            Some(&before),
+            &config,
+            &[pointer],
+            Some(&backup),
+        )?;
+        backup.discard();
+        println!("[ConfigValue] Set {} in claude_code", json_pointer);
+        return Ok(value.unwrap_or(Value::Null));
+    }
+
+    let cfg = ClientConfig::new(&client, scope.as_deref());
+    let path = cfg.get_path();
+    let mut json = JsonManager::read_json_file(path).await?;
+    apply(&mut json, &json_pointer, value.clone())?;
+    config_schema::ensure_valid(config_schema::validate_document(&client, &json)?)?;
+    JsonManager::write_json_file(path, &json).await?;
+    println!("[ConfigValue] Set {} in {}", json_pointer, client);
+    Ok(value.unwrap_or(Value::Null))
+}
+
+fn apply(document: &mut Value, pointer: &str, value: Option<Value>) -> Result<(), String> {
+    match value {
+        Some(value) => json_pointer::set(document, pointer, value),
+        None => {
+            json_pointer::remove(document, pointer);
+            Ok(())
+        }
+    }
+}
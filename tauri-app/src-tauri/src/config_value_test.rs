@@ -0,0 +1,33 @@
+// Config value pointer guard tests
+use crate::config_value::check_pointer;
+
+#[test]
+fn test_allowed_subtrees() {
+    assert!(check_pointer("cursor", "/mcpServers/fs/command", true).is_ok());
+    assert!(check_pointer("vscode", "/servers/fs", true).is_ok());
+    assert!(check_pointer("cursor", "/__disabled/fs/args/0", true).is_ok());
+    assert!(check_pointer("claude_code", "/enabledMcpjsonServers/0", true).is_ok());
+    // Whole subtrees can be read but not replaced
+    assert!(check_pointer("cursor", "/mcpServers", false).is_ok());
+    assert!(check_pointer("cursor", "/mcpServers", true).is_err());
+}
+
+#[test]
+fn test_rejects_pointers_outside_subtrees() {
+    assert!(check_pointer("cursor", "/theme", false).is_err());
+    assert!(check_pointer("vscode", "/mcpServers/fs", false).is_err());
+    assert!(check_pointer("claude_code", "/projects/~1tmp/mcpServers", false).is_err());
+    assert!(check_pointer("cursor", "mcpServers/fs", false).is_err());
+    assert!(check_pointer("codex", "/mcp_servers/fs", false).is_err());
+}
+
+#[test]
+fn test_rejects_relative_segments_and_bad_escapes() {
+    assert!(check_pointer("cursor", "/mcpServers/../theme", true).is_err());
+    assert!(check_pointer("cursor", "/mcpServers/./fs", true).is_err());
+    assert!(check_pointer("cursor", "/mcp~1Servers/fs", true).is_err());
+    assert!(check_pointer("cursor", "/mcpServers/f~2s", true).is_err());
+    assert!(check_pointer("cursor", "/mcpServers/fs~", true).is_err());
+    // Valid escapes stay inside the server's key
+    assert!(check_pointer("cursor", "/mcpServers/a~1b~0c", true).is_ok());
+}
@@ -0,0 +1,264 @@
+//! Continue's MCP servers, the `mcpServers` list of its YAML config
+//!
+//! Continue reads servers from ~/.continue/config.yaml and from the block files in a
+//! workspace's .continue/mcpServers/ directory. Both hold a `mcpServers` list whose entries carry
+//! their own `name`, next to models, rules and prompts this app knows nothing about. The list is
+//! read as JSON in the shape of other clients (`streamable-http` as "http", `requestOptions`
+//! headers as `headers`), and writes replace only the text of the top-level `mcpServers` block
+//! (see `yaml_block`): every other line of the file, comments included, stays as it was.
+//! Entries keep the fields they had that this app doesn't model (`cwd`, `connectionTimeout`,
+//! other request options).
+
+use crate::demo_mode::home_dir;
+use crate::{write_journal, yaml_block};
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::command;
+
+pub(crate) const CLIENT: &str = "continue";
+
+const SERVERS_KEY: &str = "mcpServers";
+
+/// Block file written for a workspace, under .continue/mcpServers/
+const BLOCK_FILE: &str = "mcp-linker.yaml";
+
+/// Entry fields translated to and from the JSON shape
+const MODELED_FIELDS: &[&str] = &["name", "type", "command", "args", "env", "url"];
+
+/// Header of a config written from scratch
+const NEW_CONFIG: &str = "name: Local Config\nversion: 1.0.0\nschema: v1\n";
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// ~/.continue/config.yaml, or the config named by `path`: a YAML file, or a workspace whose
+/// servers go to a block file of their own
+pub(crate) fn continue_config_path(path: Option<&str>) -> Result<PathBuf, String> {
+    match path.filter(|p| !p.is_empty()) {
+        Some(path) if path.ends_with(".yaml") || path.ends_with(".yml") => Ok(PathBuf::from(path)),
+        Some(dir) => Ok(PathBuf::from(dir)
+            .join(".continue")
+            .join("mcpServers")
+            .join(BLOCK_FILE)),
+        None => Ok(home_dir()
+            .ok_or("Failed to get home directory")?
+            .join(".continue")
+            .join("config.yaml")),
+    }
+}
+
+/// The YAML document as JSON; an empty file is an empty object
+pub(crate) fn parse(content: &str) -> Result<Value, String> {
+    yaml_block::parse(content, "Continue config")
+}
+
+/// A Continue entry in the JSON shape of other clients, without its `name`
+pub(crate) fn from_continue_entry(entry: &Value) -> Value {
+    let mut server = entry.as_object().cloned().unwrap_or_default();
+    server.remove("name");
+    if server.get("type").and_then(Value::as_str) == Some("streamable-http") {
+        server.insert("type".into(), json!("http"));
+    }
+    if let Some(Value::Object(mut options)) = server.remove("requestOptions") {
+        if let Some(headers) = options.remove("headers") {
+            server.insert("headers".into(), headers);
+        }
+        if !options.is_empty() {
+            server.insert("requestOptions".into(), Value::Object(options));
+        }
+    }
+    Value::Object(server)
+}
+
+/// `config` as the Continue entry `name`, keeping the unmodeled fields of `existing`
+pub(crate) fn to_continue_entry(name: &str, config: &Value, existing: Option<&Value>) -> Value {
+    let mut entry = Map::new();
+    entry.insert("name".into(), json!(name));
+    let mut options = Map::new();
+    let mut kept = Map::new();
+    for (key, value) in existing.and_then(Value::as_object).into_iter().flatten() {
+        match (key.as_str(), value) {
+            ("requestOptions", Value::Object(existing_options)) => options.extend(
+                existing_options
+                    .iter()
+                    .filter(|(option, _)| *option != "headers")
+                    .map(|(option, value)| (option.clone(), value.clone())),
+            ),
+            (key, _) if MODELED_FIELDS.contains(&key) => {}
+            _ => {
+                kept.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    for (key, value) in config.as_object().into_iter().flatten() {
+        match key.as_str() {
+            "name" => {}
+            "type" if value == "http" => {
+                entry.insert("type".into(), json!("streamable-http"));
+            }
+            "headers" => {
+                if value.as_object().is_some_and(|h| !h.is_empty()) {
+                    options.insert("headers".into(), value.clone());
+                }
+            }
+            "requestOptions" => {
+                if let Some(given) = value.as_object() {
+                    options.extend(given.clone());
+                }
+            }
+            _ => {
+                entry.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    for (key, value) in kept {
+        entry.entry(key).or_insert(value);
+    }
+    if !options.is_empty() {
+        entry.insert("requestOptions".into(), Value::Object(options));
+    }
+    Value::Object(entry)
+}
+
+/// Named servers of a parsed config, in the JSON shape
+pub(crate) fn servers_of(doc: &Value) -> Map<String, Value> {
+    doc[SERVERS_KEY]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            Some((
+                entry["name"].as_str()?.to_string(),
+                from_continue_entry(entry),
+            ))
+        })
+        .collect()
+}
+
+/// `content` with its `mcpServers` block replaced by `entries`; a missing block is appended
+/// and an empty file gets the header Continue requires
+pub(crate) fn splice_servers(content: &str, entries: &[Value]) -> Result<String, String> {
+    if content.trim().is_empty() {
+        return splice_servers(NEW_CONFIG, entries);
+    }
+    yaml_block::splice(content, SERVERS_KEY, &Value::Array(entries.to_vec()), "  ")
+}
+
+fn read_content(path: &Path) -> Result<String, String> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(format!("Failed to read Continue config: {}", e)),
+    }
+}
+
+/// Apply `edit` to the raw entries of the config at `path` and write the file back
+fn edit_entries(
+    path: Option<&str>,
+    edit: impl FnOnce(&mut Vec<Value>) -> Result<(), String>,
+) -> Result<Map<String, Value>, String> {
+    let path = continue_config_path(path)?;
+    let _guard = LOCK
+        .lock()
+        .map_err(|e| format!("Failed to lock Continue config: {}", e))?;
+    let content = read_content(&path)?;
+    let mut entries = parse(&content)?[SERVERS_KEY]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    edit(&mut entries)?;
+    let edited = splice_servers(&content, &entries)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create Continue config directory: {}", e))?;
+    }
+    write_journal::write(&path, &edited)
+        .map_err(|e| format!("Failed to write Continue config: {}", e))?;
+    Ok(servers_of(&json!({ SERVERS_KEY: entries })))
+}
+
+/// Servers of the Continue config at `path`
+pub(crate) fn read_servers(path: Option<&str>) -> Result<Map<String, Value>, String> {
+    let content = read_content(&continue_config_path(path)?)?;
+    Ok(servers_of(&parse(&content)?))
+}
+
+/// Add or, with `replace`, overwrite the server `name`; it keeps its place in the list
+pub(crate) fn write_server(
+    path: Option<&str>,
+    name: &str,
+    config: &Value,
+    replace: bool,
+) -> Result<Map<String, Value>, String> {
+    edit_entries(path, |entries| {
+        match entries.iter().position(|entry| entry["name"] == name) {
+            Some(_) if !replace => Err(format!("Server '{}' already exists in Continue", name)),
+            Some(index) => {
+                let entry = to_continue_entry(name, config, Some(&entries[index]));
+                entries[index] = entry;
+                Ok(())
+            }
+            None => {
+                entries.push(to_continue_entry(name, config, None));
+                Ok(())
+            }
+        }
+    })
+}
+
+pub(crate) fn remove_servers(
+    path: Option<&str>,
+    names: &[String],
+) -> Result<Map<String, Value>, String> {
+    edit_entries(path, |entries| {
+        let before = entries.len();
+        entries.retain(|entry| !names.iter().any(|name| entry["name"] == name.as_str()));
+        if entries.len() == before {
+            return Err(format!(
+                "Server '{}' not found in Continue",
+                names.join("', '")
+            ));
+        }
+        Ok(())
+    })
+}
+
+/// Write `servers` in one edit: all of them, dropping the others with `override_all`, or only
+/// the names not configured yet
+pub(crate) fn sync_servers(
+    path: Option<&str>,
+    servers: &Map<String, Value>,
+    override_all: bool,
+) -> Result<Map<String, Value>, String> {
+    edit_entries(path, |entries| {
+        if override_all {
+            entries.retain(|entry| {
+                entry["name"]
+                    .as_str()
+                    .is_none_or(|name| servers.contains_key(name))
+            });
+        }
+        for (name, config) in servers {
+            match entries
+                .iter()
+                .position(|entry| entry["name"] == name.as_str())
+            {
+                Some(index) if override_all => {
+                    let entry = to_continue_entry(name, config, Some(&entries[index]));
+                    entries[index] = entry;
+                }
+                Some(_) => {}
+                None => entries.push(to_continue_entry(name, config, None)),
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Servers of Continue's config.yaml, or of the config or workspace at `path`, keyed by name as
+/// other clients' configs are
+#[command]
+pub async fn read_continue_servers(path: Option<String>) -> Result<Value, String> {
+    Ok(json!({ SERVERS_KEY: read_servers(path.as_deref())? }))
+}
@@ -0,0 +1,113 @@
+// Continue config tests
+use crate::continue_config::{
+    from_continue_entry, parse, servers_of, splice_servers, to_continue_entry,
+};
+use serde_json::json;
+
+const CONFIG: &str = "\
+name: Local Config # shown in the picker
+version: 1.0.0
+schema: v1
+models:
+  - name: Llama
+    provider: ollama
+mcpServers:
+  # database first
+  - name: db
+    command: uvx
+    args:
+      - db-server
+    cwd: /srv
+  - name: docs
+    type: streamable-http
+    url: https://docs.example.com/mcp
+    requestOptions:
+      headers:
+        Authorization: Bearer ${{ secrets.DOCS }}
+      timeout: 30
+
+# rules apply to every model
+rules:
+  - Be brief
+";
+
+#[test]
+fn test_continue_entries_in_client_shape() {
+    let servers = servers_of(&parse(CONFIG).unwrap());
+    assert_eq!(
+        servers["db"],
+        json!({ "command": "uvx", "args": ["db-server"], "cwd": "/srv" })
+    );
+    assert_eq!(
+        servers["docs"],
+        json!({
+            "type": "http",
+            "url": "https://docs.example.com/mcp",
+            "headers": { "Authorization": "Bearer ${{ secrets.DOCS }}" },
+            "requestOptions": { "timeout": 30 }
+        })
+    );
+}
+
+#[test]
+fn test_continue_entry_keeps_unmodeled_fields() {
+    let existing = json!({
+        "name": "docs",
+        "type": "streamable-http",
+        "url": "https://docs.example.com/mcp",
+        "connectionTimeout": 5000,
+        "requestOptions": { "headers": { "X-Old": "1" }, "timeout": 30 }
+    });
+    let config = json!({
+        "type": "http",
+        "url": "https://docs.example.com/v2/mcp",
+        "headers": { "X-New": "2" }
+    });
+    assert_eq!(
+        to_continue_entry("docs", &config, Some(&existing)),
+        json!({
+            "name": "docs",
+            "type": "streamable-http",
+            "url": "https://docs.example.com/v2/mcp",
+            "connectionTimeout": 5000,
+            "requestOptions": { "timeout": 30, "headers": { "X-New": "2" } }
+        })
+    );
+    assert_eq!(
+        from_continue_entry(&to_continue_entry("db", &json!({ "command": "uvx" }), None)),
+        json!({ "command": "uvx" })
+    );
+}
+
+#[test]
+fn test_splice_keeps_the_rest_of_the_file() {
+    let doc = parse(CONFIG).unwrap();
+    let mut entries = doc["mcpServers"].as_array().unwrap().clone();
+    entries.remove(0);
+    entries.push(to_continue_entry("fs", &json!({ "command": "npx" }), None));
+    let edited = splice_servers(CONFIG, &entries).unwrap();
+
+    assert!(edited.starts_with(
+        "name: Local Config # shown in the picker\nversion: 1.0.0\nschema: v1\nmodels:\n  - name: \
+         Llama\n    provider: ollama\nmcpServers:\n  - name: docs\n"
+    ));
+    assert!(edited.ends_with(
+        "  - name: fs\n    command: npx\n\n# rules apply to every model\nrules:\n  - Be brief\n"
+    ));
+    let servers = servers_of(&parse(&edited).unwrap());
+    assert_eq!(servers.keys().collect::<Vec<_>>(), vec!["docs", "fs"]);
+    assert_eq!(parse(&edited).unwrap()["rules"], json!(["Be brief"]));
+}
+
+#[test]
+fn test_splice_adds_a_missing_block() {
+    let entries = vec![to_continue_entry("fs", &json!({ "command": "npx" }), None)];
+    assert_eq!(
+        splice_servers("name: Mine\nversion: 0.1.0\nschema: v1", &entries).unwrap(),
+        "name: Mine\nversion: 0.1.0\nschema: v1\nmcpServers:\n  - name: fs\n    command: npx\n"
+    );
+    assert_eq!(
+        splice_servers("", &[]).unwrap(),
+        "name: Local Config\nversion: 1.0.0\nschema: v1\nmcpServers: []\n"
+    );
+}
@@ -0,0 +1,159 @@
+//! Expiry tracking for stored secrets and auth profiles
+//!
+//! A background task checks recorded expiry dates every hour and emits
+//! a `credentials_expiring` event when the set of expiring or expired credentials changes. List
+//! responses use `statuses_for_client` to flag servers whose attached profile is affected.
+
+use crate::auth_profiles::{self, ProfileTarget};
+use crate::event_bus::{self, AppEventKind, EventScope};
+use crate::secrets;
+use crate::webhooks::{self, WebhookEvent};
+use chrono::{DateTime, Duration, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Mutex;
+use tauri::{command, AppHandle, Runtime};
+
+/// Credentials expiring within this many days are reported
+const REMINDER_WINDOW_DAYS: i64 = 7;
+
+const CHECK_INTERVAL_SECS: u64 = 60 * 60;
+
+/// Credentials included in the last emitted reminder
+static LAST_REMINDED: Lazy<Mutex<BTreeSet<String>>> = Lazy::new(|| Mutex::new(BTreeSet::new()));
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CredentialStatus {
+    CredentialsExpiring,
+    CredentialsExpired,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ExpiringCredential {
+    pub secret_id: String,
+    /// Auth profile backed by this secret, if any
+    pub profile: Option<String>,
+    pub expires_at: String,
+    pub status: CredentialStatus,
+    /// Servers the profile is attached to
+    pub servers: Vec<ProfileTarget>,
+}
+
+pub(crate) fn status_at(expires_at: &str, now: DateTime<Utc>) -> Option<CredentialStatus> {
+    let expires_at = DateTime::parse_from_rfc3339(expires_at).ok()?;
+    if expires_at <= now {
+        Some(CredentialStatus::CredentialsExpired)
+    } else if expires_at <= now + Duration::days(REMINDER_WINDOW_DAYS) {
+        Some(CredentialStatus::CredentialsExpiring)
+    } else {
+        None
+    }
+}
+
+/// Secrets that are expired or expire within the reminder window
+pub(crate) fn expiring_credentials() -> Result<Vec<ExpiringCredential>, String> {
+    let now = Utc::now();
+    let profiles = auth_profiles::read_profiles()?.profiles;
+    let mut expiring = Vec::new();
+    for info in secrets::secret_infos()? {
+        let Some(expires_at) = info.expires_at else {
+            continue;
+        };
+        let Some(status) = status_at(&expires_at, now) else {
+            continue;
+        };
+        let profile = profiles
+            .values()
+            .find(|p| auth_profiles::secret_id(&p.name) == info.id);
+        expiring.push(ExpiringCredential {
+            secret_id: info.id,
+            profile: profile.map(|p| p.name.clone()),
+            expires_at,
+            status,
+            servers: profile.map(|p| p.attachments.clone()).unwrap_or_default(),
+        });
+    }
+    Ok(expiring)
+}
+
+/// Credential status of every affected server of `client`, keyed by (working_dir, server)
+pub(crate) fn statuses_for_client(
+    client: &str,
+) -> HashMap<(Option<String>, String), CredentialStatus> {
+    let mut statuses = HashMap::new();
+    let Ok(expiring) = expiring_credentials() else {
+        return statuses;
+    };
+    for credential in expiring {
+        for target in credential
+            .servers
+            .into_iter()
+            .filter(|t| t.client == client)
+        {
+            let entry = statuses
+                .entry((target.working_dir, target.server))
+                .or_insert(credential.status);
+            // Expired wins over expiring when several profiles apply
+            if credential.status == CredentialStatus::CredentialsExpired {
+                *entry = CredentialStatus::CredentialsExpired;
+            }
+        }
+    }
+    statuses
+}
+
+#[command]
+pub async fn list_expiring_credentials() -> Result<Vec<ExpiringCredential>, String> {
+    expiring_credentials()
+}
+
+fn check_and_notify<R: Runtime>(app: &AppHandle<R>) {
+    let expiring = match expiring_credentials() {
+        Ok(expiring) => expiring,
+        Err(e) => {
+            println!("[CredentialExpiry] Check failed: {}", e);
+            return;
+        }
+    };
+    let current: BTreeSet<String> = expiring
+        .iter()
+        .map(|c| format!("{}:{:?}", c.secret_id, c.status))
+        .collect();
+    let Ok(mut last) = LAST_REMINDED.lock() else {
+        return;
+    };
+    if *last == current {
+        return;
+    }
+    *last = current;
+    webhooks::emit(
+        WebhookEvent::HealthChanged,
+        serde_json::json!({ "expiring_credentials": &expiring }),
+    );
+    if !expiring.is_empty() {
+        println!(
+            "[CredentialExpiry] {} credential(s) need rotation",
+            expiring.len()
+        );
+        event_bus::publish(
+            app,
+            AppEventKind::CredentialsExpiring,
+            EventScope::none(),
+            &expiring,
+        );
+    }
+}
+
+/// Start the hourly expiry check
+pub fn spawn_expiry_checker<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval =
+            tokio::time::interval(std::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            check_and_notify(&app);
+        }
+    });
+}
@@ -0,0 +1,39 @@
+// Credential expiry status tests
+use crate::credential_expiry::{status_at, CredentialStatus};
+use chrono::{Duration, TimeZone, Utc};
+
+#[test]
+fn test_status_at() {
+    let now = Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap();
+    let at = |offset: Duration| (now + offset).to_rfc3339();
+
+    assert_eq!(
+        status_at(&at(-Duration::hours(1)), now),
+        Some(CredentialStatus::CredentialsExpired)
+    );
+    assert_eq!(
+        status_at(&now.to_rfc3339(), now),
+        Some(CredentialStatus::CredentialsExpired)
+    );
+    assert_eq!(
+        status_at(&at(Duration::days(3)), now),
+        Some(CredentialStatus::CredentialsExpiring)
+    );
+    assert_eq!(
+        status_at(&at(Duration::days(7)), now),
+        Some(CredentialStatus::CredentialsExpiring)
+    );
+    assert_eq!(status_at(&at(Duration::days(8)), now), None);
+}
+
+#[test]
+fn test_status_at_other_offsets_and_invalid_dates() {
+    let now = Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap();
+    // 13:30 at +02:00 is 11:30 UTC, already past
+    assert_eq!(
+        status_at("2026-03-10T13:30:00+02:00", now),
+        Some(CredentialStatus::CredentialsExpired)
+    );
+    assert_eq!(status_at("next tuesday", now), None);
+    assert_eq!(status_at("2026-03-11", now), None);
+}
@@ -0,0 +1,194 @@
+//! Cursor's MCP servers in the `ClaudeCodeServer` model
+//!
+//! Cursor reads ~/.cursor/mcp.json and a project's .cursor/mcp.json. Its entries have no
+//! `type`: stdio servers have a `command`, remote ones a `url` (Cursor picks the transport).
+//! These commands take the same `project_dir` as the Claude Code ones, "Global" meaning the
+//! user file, and write through the generic client CRUD so warm-up and recording apply.
+//! `cursor_list_projects` finds projects with a .cursor/mcp.json among the folders Cursor has
+//! opened (its workspace storage) and the projects Claude Code knows.
+
+use crate::claude_code_commands::{
+    self, is_global_config, AddOutcome, ClaudeCodeResponse, ClaudeCodeServer, GLOBAL_PROJECT_ID,
+};
+use crate::client::ClientConfig;
+use crate::client_target::{self, ClientTarget};
+use crate::mcp_crud;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{command, AppHandle, Runtime};
+
+fn cursor_path(project_dir: &str) -> Option<String> {
+    (!is_global_config(project_dir)).then(|| project_dir.to_string())
+}
+
+/// A Cursor entry as a `ClaudeCodeServer`
+pub(crate) fn from_cursor_entry(name: &str, config: &Value) -> Result<ClaudeCodeServer, String> {
+    let mut server = claude_code_commands::parse_server_config(name, config)?;
+    server.r#type = match (config["type"].as_str(), &server.command) {
+        (Some("sse"), _) => "sse".to_string(),
+        (Some("stdio"), _) | (None, Some(_)) => "stdio".to_string(),
+        _ => "http".to_string(),
+    };
+    Ok(server)
+}
+
+/// A `ClaudeCodeServer` as a Cursor entry
+pub(crate) fn to_cursor_entry(server: &ClaudeCodeServer) -> Result<Value, String> {
+    let mut entry = Map::new();
+    match server.r#type.as_str() {
+        "stdio" => {
+            let command = server
+                .command
+                .as_ref()
+                .ok_or("Command is required for stdio servers")?;
+            entry.insert("command".into(), json!(command));
+            if let Some(args) = server.args.as_ref().filter(|a| !a.is_empty()) {
+                entry.insert("args".into(), json!(args));
+            }
+            if let Some(env) = server.env.as_ref().filter(|e| !e.is_empty()) {
+                entry.insert("env".into(), json!(env));
+            }
+        }
+        "http" | "sse" => {
+            let url = server
+                .url
+                .as_ref()
+                .ok_or("URL is required for remote servers")?;
+            entry.insert("url".into(), json!(url));
+            if let Some(headers) = server.headers.as_ref().filter(|h| !h.is_empty()) {
+                entry.insert("headers".into(), json!(headers));
+            }
+        }
+        other => return Err(format!("Unsupported server type '{}'", other)),
+    }
+    Ok(Value::Object(entry))
+}
+
+/// Folder of a Cursor `workspace.json`; multi-root workspaces have none
+pub(crate) fn workspace_folder(workspace: &Value) -> Option<PathBuf> {
+    let uri = url::Url::parse(workspace["folder"].as_str()?).ok()?;
+    uri.to_file_path().ok()
+}
+
+/// Folders in Cursor's workspace storage
+fn opened_folders() -> Vec<PathBuf> {
+    let Some(storage) =
+        crate::demo_mode::config_dir().map(|d| d.join("Cursor/User/workspaceStorage"))
+    else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(storage) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| fs::read_to_string(entry.path().join("workspace.json")).ok())
+        .filter_map(|content| serde_json::from_str::<Value>(&content).ok())
+        .filter_map(|workspace| workspace_folder(&workspace))
+        .collect()
+}
+
+fn has_cursor_config(dir: &Path) -> bool {
+    dir.join(".cursor/mcp.json").is_file()
+}
+
+/// List the MCP servers of Cursor's user config ("Global") or of a project
+#[command]
+pub async fn cursor_mcp_list(project_dir: String) -> Result<Vec<ClaudeCodeServer>, String> {
+    let target = ClientTarget::client("cursor", cursor_path(&project_dir).as_deref());
+    client_target::list_servers(&target)
+        .await?
+        .iter()
+        .map(|(name, config)| from_cursor_entry(name, config))
+        .collect()
+}
+
+/// Add a server to Cursor; an existing one with the same name is only replaced with
+/// `overwrite`
+#[command]
+pub async fn cursor_mcp_add<R: Runtime>(
+    app: AppHandle<R>,
+    request: ClaudeCodeServer,
+    project_dir: String,
+    overwrite: Option<bool>,
+) -> Result<ClaudeCodeResponse, String> {
+    let entry = to_cursor_entry(&request)?;
+    let path = cursor_path(&project_dir);
+    let exists = cursor_mcp_list(project_dir.clone())
+        .await?
+        .iter()
+        .any(|server| server.name == request.name);
+    let outcome = if exists && overwrite.unwrap_or(false) {
+        mcp_crud::update_mcp_server("cursor".to_string(), path, request.name.clone(), entry)
+            .await?;
+        AddOutcome::Overwritten
+    } else {
+        mcp_crud::add_mcp_server(app, "cursor".to_string(), path, request.name.clone(), entry)
+            .await?;
+        AddOutcome::Added
+    };
+    Ok(ClaudeCodeResponse {
+        success: true,
+        message: format!(
+            "Server '{}' saved to Cursor ({})",
+            request.name, project_dir
+        ),
+        name: Some(request.name),
+        outcome: Some(outcome),
+        warnings: Vec::new(),
+    })
+}
+
+#[command]
+pub async fn cursor_mcp_remove(
+    name: String,
+    project_dir: String,
+) -> Result<ClaudeCodeResponse, String> {
+    if !cursor_mcp_list(project_dir.clone())
+        .await?
+        .iter()
+        .any(|server| server.name == name)
+    {
+        return Err(format!("Server '{}' not found", name));
+    }
+    mcp_crud::remove_mcp_server(
+        "cursor".to_string(),
+        cursor_path(&project_dir),
+        name.clone(),
+    )
+    .await?;
+    Ok(ClaudeCodeResponse {
+        success: true,
+        message: format!("Server '{}' removed from Cursor ({})", name, project_dir),
+        name: Some(name),
+        outcome: None,
+        warnings: Vec::new(),
+    })
+}
+
+/// "Global" when ~/.cursor/mcp.json exists, then the projects with a .cursor/mcp.json
+#[command]
+pub async fn cursor_list_projects() -> Result<Vec<String>, String> {
+    let mut projects = Vec::new();
+    if ClientConfig::new("cursor", None).get_path().is_file() {
+        projects.push(GLOBAL_PROJECT_ID.to_string());
+    }
+    let mut candidates: BTreeSet<PathBuf> = opened_folders().into_iter().collect();
+    candidates.extend(
+        claude_code_commands::claude_list_projects()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|project| !is_global_config(project))
+            .map(PathBuf::from),
+    );
+    projects.extend(
+        candidates
+            .iter()
+            .filter(|dir| has_cursor_config(dir))
+            .map(|dir| dir.display().to_string()),
+    );
+    Ok(projects)
+}
@@ -0,0 +1,49 @@
+// Cursor command tests
+use crate::cursor_commands::{from_cursor_entry, to_cursor_entry, workspace_folder};
+use serde_json::json;
+
+#[test]
+fn test_from_cursor_entry_infers_type() {
+    let stdio =
+        from_cursor_entry("fs", &json!({ "command": "npx", "args": ["-y", "fs"] })).unwrap();
+    assert_eq!(stdio.r#type, "stdio");
+    assert_eq!(stdio.args, Some(vec!["-y".to_string(), "fs".to_string()]));
+    let remote =
+        from_cursor_entry("sentry", &json!({ "url": "https://mcp.sentry.dev/mcp" })).unwrap();
+    assert_eq!(remote.r#type, "http");
+    let sse = from_cursor_entry("old", &json!({ "type": "sse", "url": "https://x/sse" })).unwrap();
+    assert_eq!(sse.r#type, "sse");
+}
+
+#[test]
+fn test_to_cursor_entry_omits_type_and_empty_fields() {
+    let server = from_cursor_entry(
+        "sentry",
+        &json!({ "type": "http", "url": "https://mcp.sentry.dev/mcp", "headers": {} }),
+    )
+    .unwrap();
+    assert_eq!(
+        to_cursor_entry(&server).unwrap(),
+        json!({ "url": "https://mcp.sentry.dev/mcp" })
+    );
+}
+
+#[test]
+fn test_to_cursor_entry_requires_command() {
+    let mut server = from_cursor_entry("fs", &json!({ "command": "npx" })).unwrap();
+    server.command = None;
+    assert!(to_cursor_entry(&server).is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_workspace_folder() {
+    assert_eq!(
+        workspace_folder(&json!({ "folder": "file:///home/me/my%20app" })),
+        Some(std::path::PathBuf::from("/home/me/my app"))
+    );
+    assert_eq!(
+        workspace_folder(&json!({ "workspace": "file:///w.code-workspace" })),
+        None
+    );
+}
@@ -0,0 +1,152 @@
+//! Demo mode: the app works on sample configs in a throwaway sandbox
+//!
+//! Demo mode is chosen once per process with `--demo` or `MCPLINKER_DEMO=1`, like a profile.
+//! A fresh directory under the system temp dir then stands in for the home directory:
+//! `home_dir` and `config_dir` resolve inside it, and every client adapter finds its config
+//! through them, so nothing outside the sandbox is read or written. The app data dir moves
+//! along with it, leaving the settings and secrets of the real profiles alone. The sandbox is
+//! seeded with bundled sample configs (Claude Code with a project, Claude Desktop, Cursor,
+//! Codex) and recreated on every start. WSL configs aren't probed and writes aren't checked
+//! with the Claude CLI, since both would look at the real files.
+
+use once_cell::sync::OnceCell;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+pub const DEMO_ENV: &str = "MCPLINKER_DEMO";
+pub(crate) const DEMO_ARG: &str = "--demo";
+
+/// Directory of the sample project in the sandbox
+const DEMO_PROJECT: &str = "projects/demo-app";
+
+/// Sample configs, by path relative to the sandbox home and, for `true`, to its config dir
+const SAMPLES: &[(bool, &str, &str)] = &[
+    (
+        false,
+        ".claude.json",
+        include_str!("../demo/claude_code.json"),
+    ),
+    (
+        true,
+        "Claude/claude_desktop_config.json",
+        include_str!("../demo/claude_desktop.json"),
+    ),
+    (
+        false,
+        ".cursor/mcp.json",
+        include_str!("../demo/cursor.json"),
+    ),
+    (
+        false,
+        ".codex/config.toml",
+        include_str!("../demo/codex.toml"),
+    ),
+];
+
+static SANDBOX: OnceCell<Option<PathBuf>> = OnceCell::new();
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DemoInfo {
+    pub active: bool,
+    /// The sandbox standing in for the home directory
+    pub sandbox: Option<String>,
+}
+
+pub(crate) fn requested(args: &[String], env: Option<&str>) -> bool {
+    args.iter().any(|arg| arg == DEMO_ARG)
+        || env.is_some_and(|value| matches!(value, "1" | "true" | "yes"))
+}
+
+/// Config dir of the platform under `home`, as `dirs::config_dir` lays it out
+fn config_dir_in(home: &Path) -> PathBuf {
+    if cfg!(target_os = "macos") {
+        home.join("Library/Application Support")
+    } else if cfg!(target_os = "windows") {
+        home.join("AppData/Roaming")
+    } else {
+        home.join(".config")
+    }
+}
+
+/// `content` with the sandbox placeholders replaced by JSON strings of their paths
+pub(crate) fn fill_placeholders(content: &str, sandbox: &Path) -> String {
+    let quoted =
+        |path: &Path| serde_json::to_string(&path.display().to_string()).unwrap_or_default();
+    content
+        .replace("\"{{PROJECT}}\"", &quoted(&sandbox.join(DEMO_PROJECT)))
+        .replace("\"{{SANDBOX}}\"", &quoted(sandbox))
+}
+
+/// Write the sample configs and the sample project into an empty `sandbox`
+pub(crate) fn seed(sandbox: &Path) -> Result<(), String> {
+    fs::create_dir_all(sandbox.join(DEMO_PROJECT))
+        .map_err(|e| format!("Failed to create demo sandbox: {}", e))?;
+    for (in_config_dir, relative, content) in SAMPLES {
+        let base = if *in_config_dir {
+            config_dir_in(sandbox)
+        } else {
+            sandbox.to_path_buf()
+        };
+        let path = base.join(relative);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create demo sandbox: {}", e))?;
+        }
+        fs::write(&path, fill_placeholders(content, sandbox))
+            .map_err(|e| format!("Failed to write demo config {}: {}", relative, e))?;
+    }
+    Ok(())
+}
+
+/// Turn demo mode on for this process when asked for on the command line or in the
+/// environment. Must run before anything resolves a home or config path; later calls are
+/// ignored.
+pub fn init() {
+    let args: Vec<String> = std::env::args().collect();
+    let env = std::env::var(DEMO_ENV).ok();
+    let sandbox = requested(&args, env.as_deref()).then(|| {
+        let sandbox = std::env::temp_dir().join("mcp-linker-demo");
+        let _ = fs::remove_dir_all(&sandbox);
+        // The sandbox stays the home even when seeding fails, so real files are never touched
+        match seed(&sandbox) {
+            Ok(()) => println!("[Demo] Using sandbox {}", sandbox.display()),
+            Err(e) => println!("[Demo] {}", e),
+        }
+        sandbox
+    });
+    let _ = SANDBOX.set(sandbox);
+}
+
+pub(crate) fn sandbox() -> Option<&'static Path> {
+    SANDBOX.get().and_then(|sandbox| sandbox.as_deref())
+}
+
+pub fn is_active() -> bool {
+    sandbox().is_some()
+}
+
+/// The home directory, the sandbox in demo mode
+pub(crate) fn home_dir() -> Option<PathBuf> {
+    match sandbox() {
+        Some(sandbox) => Some(sandbox.to_path_buf()),
+        None => dirs::home_dir(),
+    }
+}
+
+/// The platform's config dir, inside the sandbox in demo mode
+pub(crate) fn config_dir() -> Option<PathBuf> {
+    match sandbox() {
+        Some(sandbox) => Some(config_dir_in(sandbox)),
+        None => dirs::config_dir(),
+    }
+}
+
+#[command]
+pub async fn get_demo_mode() -> Result<DemoInfo, String> {
+    Ok(DemoInfo {
+        active: is_active(),
+        sandbox: sandbox().map(|sandbox| sandbox.display().to_string()),
+    })
+}
@@ -0,0 +1,33 @@
+// Demo mode tests
+use crate::demo_mode::{requested, seed};
+use serde_json::Value;
+use std::fs;
+
+#[test]
+fn test_requested_by_arg_or_env() {
+    let args = |list: &[&str]| list.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+    assert!(requested(&args(&["mcp-linker", "--demo"]), None));
+    assert!(requested(&args(&["mcp-linker"]), Some("1")));
+    assert!(!requested(&args(&["mcp-linker"]), Some("0")));
+    assert!(!requested(
+        &args(&["mcp-linker", "--profile", "demo"]),
+        None
+    ));
+}
+
+#[test]
+fn test_seed_points_samples_at_the_sandbox() {
+    let dir = tempfile::tempdir().unwrap();
+    seed(dir.path()).unwrap();
+
+    let content = fs::read_to_string(dir.path().join(".claude.json")).unwrap();
+    let config: Value = serde_json::from_str(&content).unwrap();
+    let project = dir.path().join("projects/demo-app").display().to_string();
+    assert!(config["projects"][&project]["mcpServers"]["sqlite"].is_object());
+    assert_eq!(
+        config["mcpServers"]["filesystem"]["args"][2],
+        dir.path().display().to_string()
+    );
+    assert!(dir.path().join("projects/demo-app").is_dir());
+    assert!(dir.path().join(".cursor/mcp.json").is_file());
+}
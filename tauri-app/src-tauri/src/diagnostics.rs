@@ -0,0 +1,163 @@
+//! Backend diagnostics surfaced to the UI and support requests
+
+use crate::claude_code_commands::get_claude_config_path;
+use crate::claude_scan::{scan_timings, SourceTiming};
+use crate::client::ClientConfig;
+use crate::client_target::JSON_CLIENTS;
+use crate::settings::app_config_dir;
+use crate::{disk_space, log_rotation, startup_check};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::command;
+use walkdir::WalkDir;
+
+/// Subsystems owning files and directories directly in the app data dir; anything else counts
+/// as "other"
+const SUBSYSTEMS: &[(&str, &[&str])] = &[
+    ("audit_log", &["audit.jsonl"]),
+    ("write_journal", &["write_journal.json", "batch_undo.json"]),
+    ("config_backups", &["config_backups.json"]),
+    ("registry", &["registries.json", "registry_cache.json"]),
+    (
+        "metadata",
+        &[
+            "server_metadata.json",
+            "package_metadata.json",
+            "advisories.json",
+        ],
+    ),
+    ("icons", &["icons", "server_icons.json"]),
+    ("schemas", &["schemas"]),
+    ("client_plugins", &["clients"]),
+    (
+        "secrets",
+        &["secrets.json", "secrets.key", "auth_profiles.json"],
+    ),
+    ("settings", &["settings.json"]),
+    ("profiles", &["profiles"]),
+];
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct BackupStore {
+    /// Backups `create_backup` left next to the client configs
+    pub files: usize,
+    pub bytes: u64,
+    /// Free space on the volume of the app data dir, when it could be determined
+    pub app_data_free: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct SubsystemUsage {
+    pub subsystem: String,
+    pub files: usize,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct StorageUsage {
+    pub dir: String,
+    pub total_bytes: u64,
+    /// Largest first
+    pub subsystems: Vec<SubsystemUsage>,
+    /// Free space on the volume of the app data dir, when it could be determined
+    pub available: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct Diagnostics {
+    /// Most recent read of each config source
+    pub scan_timings: Vec<SourceTiming>,
+    pub backup_store: BackupStore,
+}
+
+async fn backup_store() -> BackupStore {
+    let mut configs: Vec<PathBuf> = JSON_CLIENTS
+        .iter()
+        .map(|client| ClientConfig::new(client, None).get_path().to_path_buf())
+        .collect();
+    configs.extend(get_claude_config_path(None).await.ok());
+    configs.extend(crate::config::get_config_path().ok());
+
+    let mut store = BackupStore::default();
+    for config in &configs {
+        for backup in startup_check::backups_of(config) {
+            if let Ok(metadata) = fs::metadata(&backup) {
+                store.files += 1;
+                store.bytes += metadata.len();
+            }
+        }
+    }
+    store.app_data_free = app_config_dir()
+        .ok()
+        .and_then(|dir| disk_space::volume_for(&dir))
+        .map(|volume| volume.available);
+    store
+}
+
+#[command]
+pub async fn get_diagnostics() -> Result<Diagnostics, String> {
+    Ok(Diagnostics {
+        scan_timings: scan_timings(),
+        backup_store: backup_store().await,
+    })
+}
+
+/// The subsystem owning `name`, a file or directory directly in the app data dir. Rotations of
+/// a log belong to the log's subsystem.
+pub(crate) fn subsystem_of(name: &str) -> &'static str {
+    SUBSYSTEMS
+        .iter()
+        .find(|(_, names)| {
+            names.iter().any(|owned| {
+                *owned == name
+                    || (log_rotation::LOGS.contains(owned)
+                        && log_rotation::rotation_index(owned, name).is_some())
+            })
+        })
+        .map_or("other", |(subsystem, _)| subsystem)
+}
+
+/// Files and bytes under `dir` by subsystem, largest first
+pub(crate) fn usage_by_subsystem(dir: &Path) -> Vec<SubsystemUsage> {
+    let mut usage: BTreeMap<&str, SubsystemUsage> = BTreeMap::new();
+    for entry in WalkDir::new(dir).min_depth(1).into_iter().flatten() {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let Some(top) = entry
+            .path()
+            .strip_prefix(dir)
+            .ok()
+            .and_then(|relative| relative.components().next())
+        else {
+            continue;
+        };
+        let subsystem = subsystem_of(&top.as_os_str().to_string_lossy());
+        let bytes = entry.metadata().map_or(0, |metadata| metadata.len());
+        let slot = usage.entry(subsystem).or_insert_with(|| SubsystemUsage {
+            subsystem: subsystem.to_string(),
+            files: 0,
+            bytes: 0,
+        });
+        slot.files += 1;
+        slot.bytes += bytes;
+    }
+    let mut usage: Vec<SubsystemUsage> = usage.into_values().collect();
+    usage.sort_by(|a, b| b.bytes.cmp(&a.bytes));
+    usage
+}
+
+/// What the app data dir of the active profile holds, by subsystem
+#[command]
+pub async fn get_storage_usage() -> Result<StorageUsage, String> {
+    let dir = app_config_dir()?;
+    let subsystems = usage_by_subsystem(&dir);
+    Ok(StorageUsage {
+        dir: dir.display().to_string(),
+        total_bytes: subsystems.iter().map(|usage| usage.bytes).sum(),
+        available: disk_space::volume_for(&dir).map(|volume| volume.available),
+        subsystems,
+    })
+}
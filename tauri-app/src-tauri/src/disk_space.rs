@@ -0,0 +1,88 @@
+//! Free space checks before the app writes copies of user data
+//!
+//! Config backups, write journal snapshots, exported and imported app state bundles and
+//! extracted DXT manifests are checked against the free space of the volume they go to before
+//! anything is written, so a full disk or an exhausted quota fails with a message naming the
+//! volume instead of leaving a truncated file behind. The space counted is what is available
+//! to the user, without blocks reserved for root. When the volume can't be determined the
+//! write goes ahead.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use sysinfo::Disks;
+
+/// Left free on top of what a write needs, for the filesystem's own bookkeeping
+pub(crate) const HEADROOM: u64 = 8 * 1024 * 1024;
+
+/// Mount point and available bytes of a volume
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Volume {
+    pub mount_point: PathBuf,
+    pub available: u64,
+}
+
+/// The volume holding `path`: the one with the longest mount point that `path` is under
+pub(crate) fn volume_of<'a>(path: &Path, volumes: &'a [Volume]) -> Option<&'a Volume> {
+    volumes
+        .iter()
+        .filter(|volume| path.starts_with(&volume.mount_point))
+        .max_by_key(|volume| volume.mount_point.components().count())
+}
+
+/// `path` or its closest existing parent, resolved, as a file about to be created has no
+/// volume yet
+fn existing_ancestor(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|ancestor| ancestor.exists())
+        .and_then(|ancestor| fs::canonicalize(ancestor).ok())
+}
+
+/// The volume `path` would be written to
+pub(crate) fn volume_for(path: &Path) -> Option<Volume> {
+    let target = existing_ancestor(path)?;
+    let disks = Disks::new_with_refreshed_list();
+    let volumes: Vec<Volume> = disks
+        .list()
+        .iter()
+        .map(|disk| Volume {
+            mount_point: disk.mount_point().to_path_buf(),
+            available: disk.available_space(),
+        })
+        .collect();
+    volume_of(&target, &volumes).cloned()
+}
+
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// Whether `needed` bytes fit on `volume` with `HEADROOM` to spare
+pub(crate) fn check_free(volume: &Volume, needed: u64) -> Result<(), String> {
+    if volume.available >= needed.saturating_add(HEADROOM) {
+        return Ok(());
+    }
+    Err(format!(
+        "Not enough disk space on {}: {} needed, {} free",
+        volume.mount_point.display(),
+        format_bytes(needed),
+        format_bytes(volume.available)
+    ))
+}
+
+/// Fail before writing `needed` bytes to `path` when its volume can't take them
+pub(crate) fn ensure_space(path: &Path, needed: u64) -> Result<(), String> {
+    match volume_for(path) {
+        Some(volume) => check_free(&volume, needed),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,54 @@
+// Disk space check tests
+use crate::disk_space::{check_free, ensure_space, format_bytes, volume_of, Volume, HEADROOM};
+use std::path::{Path, PathBuf};
+
+fn volume(mount_point: &str, available: u64) -> Volume {
+    Volume {
+        mount_point: PathBuf::from(mount_point),
+        available,
+    }
+}
+
+#[test]
+fn test_volume_of_picks_longest_mount_point() {
+    let volumes = vec![
+        volume("/", 1),
+        volume("/home", 2),
+        volume("/home/user/data", 3),
+    ];
+    let found = volume_of(Path::new("/home/user/.claude.json"), &volumes).unwrap();
+    assert_eq!(found.mount_point, PathBuf::from("/home"));
+    let found = volume_of(Path::new("/home/user/data/backups"), &volumes).unwrap();
+    assert_eq!(found.available, 3);
+}
+
+#[test]
+fn test_volume_of_matches_whole_components() {
+    let volumes = vec![volume("/", 1), volume("/home", 2)];
+    let found = volume_of(Path::new("/homes/other"), &volumes).unwrap();
+    assert_eq!(found.mount_point, PathBuf::from("/"));
+    assert!(volume_of(Path::new("/tmp"), &[volume("/home", 2)]).is_none());
+}
+
+#[test]
+fn test_check_free_keeps_headroom() {
+    assert!(check_free(&volume("/", HEADROOM + 100), 100).is_ok());
+    let err = check_free(&volume("/home", HEADROOM + 99), 100).unwrap_err();
+    assert!(err.starts_with("Not enough disk space on /home"), "{}", err);
+    assert!(check_free(&volume("/", u64::MAX), u64::MAX).is_err());
+}
+
+#[test]
+fn test_format_bytes() {
+    assert_eq!(format_bytes(512), "512 B");
+    assert_eq!(format_bytes(1536), "1.5 KB");
+    assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GB");
+}
+
+#[test]
+fn test_ensure_space_for_missing_file_uses_parent_volume() {
+    let path = std::env::temp_dir()
+        .join("mcp-linker-disk-space")
+        .join("nope.json");
+    assert!(ensure_space(&path, 0).is_ok());
+}
@@ -0,0 +1,377 @@
+//! Scheduled config drift reports
+//!
+//! Current servers of each watched target are compared against an approved baseline (captured
+//! with `approve_drift_baseline`) or, when configured, a desired-state file. A background task
+//! runs the comparison on the configured interval and emits a `config_drift` event plus a
+//! `drift_detected` webhook when the drift changes. Reports name the fields that differ but
+//! never include their values. The baseline stores a SHA-256 of every value in place of the
+//! value (see `hash_leaves`), so drift.json holds no secrets; current configs are hashed the
+//! same way before they are compared.
+
+use crate::client_target::{self, ClientTarget};
+use crate::event_bus::{self, AppEventKind, EventScope};
+use crate::server_metadata;
+use crate::settings::app_config_dir;
+use crate::webhooks::{self, WebhookEvent};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::PathBuf;
+use tauri::{command, AppHandle, Runtime};
+
+const CHECK_INTERVAL_SECS: u64 = 5 * 60;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TargetState {
+    #[serde(flatten)]
+    pub target: ClientTarget,
+    pub servers: Map<String, Value>,
+}
+
+/// Desired-state file: the servers each target should have
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DesiredState {
+    #[serde(default)]
+    pub targets: Vec<TargetState>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ChangedServer {
+    pub name: String,
+    /// JSON pointers of the differing fields inside the entry
+    pub fields: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TargetDrift {
+    pub target: ClientTarget,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<ChangedServer>,
+    /// Set when the target could not be read
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl TargetDrift {
+    fn has_drift(&self) -> bool {
+        !self.added.is_empty()
+            || !self.removed.is_empty()
+            || !self.changed.is_empty()
+            || self.error.is_some()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DriftReport {
+    pub generated_at: String,
+    /// "baseline" or the desired-state file path
+    pub compared_to: String,
+    pub has_drift: bool,
+    /// Only targets that drifted
+    pub targets: Vec<TargetDrift>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DriftSchedule {
+    /// Minutes between scheduled checks; `None` disables the schedule
+    #[serde(default)]
+    pub interval_minutes: Option<u64>,
+    /// Compare against this file instead of the approved baseline
+    #[serde(default)]
+    pub desired_state_path: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct DriftFile {
+    #[serde(default)]
+    schedule: DriftSchedule,
+    #[serde(default)]
+    baseline: Vec<TargetState>,
+    /// False for baselines approved before values were hashed
+    #[serde(default)]
+    baseline_hashed: bool,
+    #[serde(default)]
+    approved_at: Option<String>,
+    #[serde(default)]
+    last_checked_at: Option<String>,
+    #[serde(default)]
+    last_report: Option<DriftReport>,
+}
+
+fn drift_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("drift.json"))
+}
+
+fn read_drift_file() -> Result<DriftFile, String> {
+    let path = drift_path()?;
+    let mut file: DriftFile = match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse drift state: {}", e))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(DriftFile::default()),
+        Err(e) => return Err(format!("Failed to read drift state: {}", e)),
+    };
+    if !file.baseline_hashed {
+        // Baselines approved before hashing hold the configs in plain text
+        for state in &mut file.baseline {
+            state.servers = hash_servers(&state.servers);
+        }
+        file.baseline_hashed = true;
+        write_drift_file(&file)?;
+        println!("[Drift] Replaced the stored baseline values with hashes");
+    }
+    Ok(file)
+}
+
+fn write_drift_file(file: &DriftFile) -> Result<(), String> {
+    let path = drift_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(file)
+        .map_err(|e| format!("Failed to serialize drift state: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write drift state: {}", e))
+}
+
+/// `value` with every string, number, bool and null replaced by "sha256:<hex>" of it. Objects
+/// and arrays keep their shape, so `changed_fields` on two hashed entries names the same
+/// fields as on the entries themselves.
+pub(crate) fn hash_leaves(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| (key.clone(), hash_leaves(value)))
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(hash_leaves).collect()),
+        leaf => Value::String(format!(
+            "sha256:{}",
+            server_metadata::config_fingerprint(leaf)
+        )),
+    }
+}
+
+pub(crate) fn hash_servers(servers: &Map<String, Value>) -> Map<String, Value> {
+    servers
+        .iter()
+        .map(|(name, config)| (name.clone(), hash_leaves(config)))
+        .collect()
+}
+
+/// Pointers of fields that differ between two entries, descending into objects
+pub(crate) fn changed_fields(
+    expected: &Value,
+    actual: &Value,
+    pointer: &str,
+    out: &mut Vec<String>,
+) {
+    match (expected, actual) {
+        (Value::Object(e), Value::Object(a)) => {
+            let keys = e.keys().chain(a.keys().filter(|k| !e.contains_key(*k)));
+            for key in keys {
+                let child = format!("{}/{}", pointer, crate::json_pointer::escape_segment(key));
+                match (e.get(key), a.get(key)) {
+                    (Some(ev), Some(av)) => changed_fields(ev, av, &child, out),
+                    _ => out.push(child),
+                }
+            }
+        }
+        _ if expected != actual => out.push(pointer.to_string()),
+        _ => {}
+    }
+}
+
+pub(crate) fn compare(
+    target: &ClientTarget,
+    expected: &Map<String, Value>,
+    actual: &Map<String, Value>,
+) -> TargetDrift {
+    let added = actual
+        .keys()
+        .filter(|name| !expected.contains_key(*name))
+        .cloned()
+        .collect();
+    let removed = expected
+        .keys()
+        .filter(|name| !actual.contains_key(*name))
+        .cloned()
+        .collect();
+    let changed = expected
+        .iter()
+        .filter_map(|(name, expected)| {
+            let actual = actual.get(name)?;
+            let mut fields = Vec::new();
+            changed_fields(expected, actual, "", &mut fields);
+            (!fields.is_empty()).then(|| ChangedServer {
+                name: name.clone(),
+                fields,
+            })
+        })
+        .collect();
+    TargetDrift {
+        target: target.clone(),
+        added,
+        removed,
+        changed,
+        error: None,
+    }
+}
+
+fn load_desired_state(path: &str) -> Result<Vec<TargetState>, String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read desired state: {}", e))?;
+    let state: DesiredState = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse desired state: {}", e))?;
+    Ok(state.targets)
+}
+
+async fn build_report(file: &DriftFile) -> Result<DriftReport, String> {
+    let (expected, compared_to) = match &file.schedule.desired_state_path {
+        Some(path) => (load_desired_state(path)?, path.clone()),
+        None if file.approved_at.is_none() => {
+            return Err("No approved baseline; approve one or set a desired-state file".to_string())
+        }
+        None => (file.baseline.clone(), "baseline".to_string()),
+    };
+    let hashed = file.schedule.desired_state_path.is_none();
+    let mut targets = Vec::new();
+    for state in &expected {
+        let drift = match client_target::list_servers(&state.target).await {
+            Ok(actual) if hashed => compare(&state.target, &state.servers, &hash_servers(&actual)),
+            Ok(actual) => compare(&state.target, &state.servers, &actual),
+            Err(e) => TargetDrift {
+                target: state.target.clone(),
+                added: Vec::new(),
+                removed: Vec::new(),
+                changed: Vec::new(),
+                error: Some(e),
+            },
+        };
+        if drift.has_drift() {
+            targets.push(drift);
+        }
+    }
+    Ok(DriftReport {
+        generated_at: Utc::now().to_rfc3339(),
+        compared_to,
+        has_drift: !targets.is_empty(),
+        targets,
+    })
+}
+
+/// Run a check and store it. Returns the report and whether it differs from the previous one.
+async fn run_check() -> Result<(DriftReport, bool), String> {
+    let mut file = read_drift_file()?;
+    let report = build_report(&file).await?;
+    let changed = file
+        .last_report
+        .as_ref()
+        .map_or(report.has_drift, |last| last.targets != report.targets);
+    file.last_checked_at = Some(report.generated_at.clone());
+    file.last_report = Some(report.clone());
+    write_drift_file(&file)?;
+    Ok((report, changed))
+}
+
+fn notify<R: Runtime>(app: &AppHandle<R>, report: &DriftReport) {
+    println!(
+        "[Drift] {} target(s) drifted from {}",
+        report.targets.len(),
+        report.compared_to
+    );
+    event_bus::publish(app, AppEventKind::ConfigDrift, EventScope::none(), report);
+    webhooks::emit(
+        WebhookEvent::DriftDetected,
+        serde_json::to_value(report).unwrap_or_default(),
+    );
+}
+
+fn check_due(file: &DriftFile, now: DateTime<Utc>) -> bool {
+    let Some(minutes) = file.schedule.interval_minutes else {
+        return false;
+    };
+    match file
+        .last_checked_at
+        .as_deref()
+        .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+    {
+        Some(last) => now - last.with_timezone(&Utc) >= Duration::minutes(minutes as i64),
+        None => true,
+    }
+}
+
+/// Start the background task that runs scheduled drift checks
+pub fn spawn_drift_checker<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval =
+            tokio::time::interval(std::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            let due = read_drift_file().is_ok_and(|file| check_due(&file, Utc::now()));
+            if !due {
+                continue;
+            }
+            match run_check().await {
+                Ok((report, true)) if report.has_drift => notify(&app, &report),
+                Ok(_) => {}
+                Err(e) => println!("[Drift] Scheduled check failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Record the current servers of `targets` as the approved baseline, returned with its values
+/// hashed as it is stored
+#[command]
+pub async fn approve_drift_baseline(
+    targets: Vec<ClientTarget>,
+) -> Result<Vec<TargetState>, String> {
+    let mut baseline = Vec::new();
+    for target in targets {
+        let servers = hash_servers(&client_target::list_servers(&target).await?);
+        baseline.push(TargetState { target, servers });
+    }
+    let mut file = read_drift_file()?;
+    file.baseline = baseline.clone();
+    file.baseline_hashed = true;
+    file.approved_at = Some(Utc::now().to_rfc3339());
+    file.last_report = None;
+    write_drift_file(&file)?;
+    Ok(baseline)
+}
+
+#[command]
+pub async fn get_drift_schedule() -> Result<DriftSchedule, String> {
+    Ok(read_drift_file()?.schedule)
+}
+
+#[command]
+pub async fn set_drift_schedule(schedule: DriftSchedule) -> Result<DriftSchedule, String> {
+    if schedule.interval_minutes == Some(0) {
+        return Err("Interval must be at least one minute".to_string());
+    }
+    if let Some(path) = &schedule.desired_state_path {
+        load_desired_state(path)?;
+    }
+    let mut file = read_drift_file()?;
+    file.schedule = schedule.clone();
+    write_drift_file(&file)?;
+    Ok(schedule)
+}
+
+/// Run a drift check now
+#[command]
+pub async fn check_drift<R: Runtime>(app: AppHandle<R>) -> Result<DriftReport, String> {
+    let (report, changed) = run_check().await?;
+    if changed && report.has_drift {
+        notify(&app, &report);
+    }
+    Ok(report)
+}
+
+#[command]
+pub async fn get_last_drift_report() -> Result<Option<DriftReport>, String> {
+    Ok(read_drift_file()?.last_report)
+}
@@ -0,0 +1,99 @@
+// Config drift comparison tests
+use crate::client_target::ClientTarget;
+use crate::drift::{compare, hash_servers};
+use serde_json::json;
+
+#[test]
+fn test_compare_reports_added_removed_and_changed_fields() {
+    let expected = json!({
+        "fs": { "command": "npx", "args": ["-y", "fs-server"], "env": { "ROOT": "/srv" } },
+        "old": { "url": "https://old.example/mcp" }
+    });
+    let actual = json!({
+        "fs": { "command": "npx", "args": ["-y", "fs-server"], "env": { "ROOT": "/tmp" } },
+        "new": { "url": "https://new.example/mcp" }
+    });
+    let drift = compare(
+        &ClientTarget::client("cursor", None),
+        expected.as_object().unwrap(),
+        actual.as_object().unwrap(),
+    );
+    assert_eq!(drift.added, vec!["new".to_string()]);
+    assert_eq!(drift.removed, vec!["old".to_string()]);
+    assert_eq!(drift.changed.len(), 1);
+    assert_eq!(drift.changed[0].fields, vec!["/env/ROOT".to_string()]);
+}
+
+fn servers(value: serde_json::Value) -> serde_json::Map<String, serde_json::Value> {
+    value.as_object().unwrap().clone()
+}
+
+#[test]
+fn test_compare_classifies_each_kind_of_drift() {
+    let target = ClientTarget::client("cursor", None);
+    let expected = servers(json!({
+        "same": { "command": "npx", "args": ["a"] },
+        "args": { "command": "npx", "args": ["a", "b"] },
+        "keys": { "url": "https://a", "headers": { "X-Old": "1" } },
+        "gone": { "url": "https://gone" }
+    }));
+    let actual = servers(json!({
+        "same": { "command": "npx", "args": ["a"] },
+        "args": { "command": "npx", "args": ["a", "c"] },
+        "keys": { "url": "https://a", "headers": { "X-New": "1" }, "type": "http" },
+        "fresh": { "url": "https://fresh" },
+        "fresher": { "url": "https://fresher" }
+    }));
+    let drift = compare(&target, &expected, &actual);
+    assert_eq!(drift.added, ["fresh", "fresher"]);
+    assert_eq!(drift.removed, ["gone"]);
+    let changed: Vec<(&str, Vec<&str>)> = drift
+        .changed
+        .iter()
+        .map(|c| {
+            (
+                c.name.as_str(),
+                c.fields.iter().map(String::as_str).collect(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        changed,
+        [
+            // Arrays compare as a whole
+            ("args", vec!["/args"]),
+            // Keys on either side only are reported as fields too
+            ("keys", vec!["/headers/X-Old", "/headers/X-New", "/type"]),
+        ]
+    );
+
+    let unchanged = compare(&target, &expected, &expected);
+    assert!(unchanged.added.is_empty() && unchanged.removed.is_empty());
+    assert!(unchanged.changed.is_empty());
+}
+
+#[test]
+fn test_hashed_baseline_finds_the_same_drift_without_values() {
+    let target = ClientTarget::client("cursor", None);
+    let approved = servers(json!({
+        "fs": { "command": "npx", "env": { "TOKEN": "secret-1", "ROOT": "/srv" } },
+        "old": { "url": "https://old.example/mcp" }
+    }));
+    let current = servers(json!({
+        "fs": { "command": "npx", "env": { "TOKEN": "secret-2", "ROOT": "/srv" } },
+        "new": { "url": "https://new.example/mcp" }
+    }));
+    let baseline = hash_servers(&approved);
+    let stored = serde_json::to_string(&baseline).unwrap();
+    assert!(!stored.contains("secret-1") && !stored.contains("old.example"));
+    assert!(stored.contains("\"TOKEN\":\"sha256:"));
+
+    let drift = compare(&target, &baseline, &hash_servers(&current));
+    assert_eq!(drift.added, ["new"]);
+    assert_eq!(drift.removed, ["old"]);
+    assert_eq!(drift.changed.len(), 1);
+    assert_eq!(drift.changed[0].fields, ["/env/TOKEN"]);
+    assert!(compare(&target, &baseline, &hash_servers(&approved))
+        .changed
+        .is_empty());
+}
@@ -1,19 +1,29 @@
+use crate::disk_space;
 use glob::glob;
 use std::fs;
 use std::io::{Cursor, Read};
+use std::path::Path;
 use zip::ZipArchive;
 
+/// Glob for the installed manifests under `base_path`. The base is escaped so folder names
+/// with brackets or other glob characters match literally.
+fn manifest_pattern(base_path: &Path) -> Option<String> {
+    let base = glob::Pattern::escape(base_path.to_str()?);
+    Some(format!("{}/*/*/manifest.json", base))
+}
+
 #[tauri::command]
 pub async fn load_manifests() -> Result<serde_json::Value, String> {
     async {
-        let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Cannot find home directory"))?;
+        let home = crate::demo_mode::home_dir().ok_or_else(|| anyhow::anyhow!("Cannot find home directory"))?;
         let base_path = home.join(".config/finder/dxt");
-        let pattern = base_path.join("*/*/manifest.json");
+        let pattern = manifest_pattern(&base_path)
+            .ok_or_else(|| anyhow::anyhow!("Home directory is not valid UTF-8"))?;
 
         let mut manifests = serde_json::Map::new();
 
         if base_path.exists() {
-            for entry in glob(pattern.to_str().unwrap())? {
+            for entry in glob(&pattern)? {
                 let path = entry?;
                 let parent_dir = path
                     .parent()
@@ -48,7 +58,7 @@ pub async fn load_manifests() -> Result<serde_json::Value, String> {
 #[tauri::command]
 pub async fn load_manifest(user: String, repo: String) -> Result<serde_json::Value, String> {
     async {
-        let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Cannot find home directory"))?;
+        let home = crate::demo_mode::home_dir().ok_or_else(|| anyhow::anyhow!("Cannot find home directory"))?;
         let manifest_path = home
             .join(".config/finder/dxt")
             .join(&user)
@@ -70,7 +80,7 @@ pub async fn load_manifest(user: String, repo: String) -> Result<serde_json::Val
 #[tauri::command]
 pub async fn fetch_and_save_manifest(user: &str, repo: &str) -> Result<(), String> {
     async {
-        let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Cannot find home directory"))?;
+        let home = crate::demo_mode::home_dir().ok_or_else(|| anyhow::anyhow!("Cannot find home directory"))?;
         let dxt_path = home.join(".config/finder/dxt").join(user).join(repo);
 
         // Create the directory if it doesn't exist
@@ -101,7 +111,7 @@ pub async fn fetch_and_save_manifest(user: &str, repo: &str) -> Result<(), Strin
 #[tauri::command]
 pub async fn read_dxt_setting(user: String, repo: String) -> Result<serde_json::Value, String> {
     async {
-        let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Cannot find home directory"))?;
+        let home = crate::demo_mode::home_dir().ok_or_else(|| anyhow::anyhow!("Cannot find home directory"))?;
         let settings_dir = home.join(".config/finder/dxt-settings");
         tokio::fs::create_dir_all(&settings_dir).await?;
         let settings_path = settings_dir.join(format!("{}.{}.json", &user, &repo));
@@ -125,7 +135,7 @@ pub async fn save_dxt_setting(
     content: serde_json::Value,
 ) -> Result<(), String> {
     async {
-        let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Cannot find home directory"))?;
+        let home = crate::demo_mode::home_dir().ok_or_else(|| anyhow::anyhow!("Cannot find home directory"))?;
         let settings_dir = home.join(".config/finder/dxt-settings");
         let settings_path = settings_dir.join(format!("{}.{}.json", &user, &repo));
         let content_string = serde_json::to_string_pretty(&content)?;
@@ -139,7 +149,7 @@ pub async fn save_dxt_setting(
 #[tauri::command]
 pub async fn download_and_extract_manifests() -> Result<(), String> {
     async {
-        let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Cannot find home directory"))?;
+        let home = crate::demo_mode::home_dir().ok_or_else(|| anyhow::anyhow!("Cannot find home directory"))?;
         let dxt_base_path = home.join(".config/finder/dxt");
         
         // Create base directory if it doesn't exist
@@ -160,6 +170,10 @@ pub async fn download_and_extract_manifests() -> Result<(), String> {
         // Extract the zip file
         let reader = Cursor::new(zip_data);
         let mut archive = ZipArchive::new(reader)?;
+        let extracted_size: u64 = (0..archive.len())
+            .filter_map(|i| archive.by_index(i).ok().map(|file| file.size()))
+            .sum();
+        disk_space::ensure_space(&dxt_base_path, extracted_size).map_err(anyhow::Error::msg)?;
 
         for i in 0..archive.len() {
             let mut file = archive.by_index(i)?;
@@ -200,7 +214,7 @@ pub async fn download_and_extract_manifests() -> Result<(), String> {
 
 #[tauri::command]
 pub async fn check_manifests_exist() -> Result<bool, String> {
-    let home = dirs::home_dir().ok_or("Cannot find home directory")?;
+    let home = crate::demo_mode::home_dir().ok_or("Cannot find home directory")?;
     let dxt_base_path = home.join(".config/finder/dxt");
 
     if !dxt_base_path.exists() {
@@ -208,8 +222,10 @@ pub async fn check_manifests_exist() -> Result<bool, String> {
     }
 
     // Check if there are any manifest.json files
-    let pattern = dxt_base_path.join("*/*/manifest.json");
-    match glob(pattern.to_str().unwrap()) {
+    let Some(pattern) = manifest_pattern(&dxt_base_path) else {
+        return Ok(false);
+    };
+    match glob(&pattern) {
         Ok(paths) => {
             for _path in paths {
                 return Ok(true); // Found at least one manifest
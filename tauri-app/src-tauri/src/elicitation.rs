@@ -0,0 +1,251 @@
+//! User answers to server elicitation requests during test launches and inspection
+//!
+//! Servers using MCP elicitation send `elicitation/create` mid-session to ask the user for
+//! input, and can't be health-checked if nobody answers. A session opened with an `Elicitor`
+//! advertises the capability, publishes each request as an `elicitation_requested` event and
+//! waits for the frontend's `respond_elicitation`; requests left unanswered for
+//! `RESPONSE_TIMEOUT` are cancelled. Time spent waiting on the user doesn't count against the
+//! launch timeout (see `timeout_excluding_answers`).
+
+use crate::event_bus::{self, AppEventKind, EventScope};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{command, AppHandle, Runtime};
+use tokio::sync::oneshot;
+
+/// How long a request waits for the user before it is cancelled
+pub(crate) const RESPONSE_TIMEOUT: Duration = Duration::from_secs(300);
+
+static PENDING: Lazy<Mutex<HashMap<String, Pending>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+struct Pending {
+    schema: Value,
+    answer: oneshot::Sender<ElicitationAnswer>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ElicitationRequest {
+    pub id: String,
+    /// Server that asked, as named in the client config or by its command
+    pub server: String,
+    pub message: String,
+    /// Flat JSON schema of the requested fields
+    pub requested_schema: Value,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ElicitationAction {
+    Accept,
+    Decline,
+    Cancel,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ElicitationAnswer {
+    pub action: ElicitationAction,
+    /// Field values; only sent on accept
+    #[serde(default)]
+    pub content: Option<Value>,
+}
+
+/// Time a session spent waiting on the user
+#[derive(Debug, Default)]
+pub(crate) struct AnswerClock {
+    waited: Duration,
+    since: Option<Instant>,
+}
+
+impl AnswerClock {
+    fn total(&self) -> Duration {
+        self.waited + self.since.map(|since| since.elapsed()).unwrap_or_default()
+    }
+}
+
+/// Forwards a session's elicitation requests to the frontend
+pub(crate) struct Elicitor {
+    server: String,
+    publish: Box<dyn Fn(&ElicitationRequest) + Send + Sync>,
+    clock: Arc<Mutex<AnswerClock>>,
+}
+
+impl Elicitor {
+    pub(crate) fn new<R: Runtime>(app: &AppHandle<R>, server: &str) -> Self {
+        let app = app.clone();
+        Elicitor {
+            server: server.to_string(),
+            publish: Box::new(move |request| {
+                event_bus::publish(
+                    &app,
+                    AppEventKind::ElicitationRequested,
+                    EventScope::none(),
+                    request,
+                )
+            }),
+            clock: Arc::new(Mutex::new(AnswerClock::default())),
+        }
+    }
+
+    pub(crate) fn clock(&self) -> Arc<Mutex<AnswerClock>> {
+        self.clock.clone()
+    }
+
+    /// Ask the user and return the `elicitation/create` result
+    pub(crate) async fn ask(&self, params: &Value) -> Value {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed).to_string();
+        let request = ElicitationRequest {
+            id: id.clone(),
+            server: self.server.clone(),
+            message: params["message"].as_str().unwrap_or_default().to_string(),
+            requested_schema: params
+                .get("requestedSchema")
+                .cloned()
+                .unwrap_or_else(|| json!({})),
+        };
+        let (sender, receiver) = oneshot::channel();
+        if let Ok(mut pending) = PENDING.lock() {
+            pending.insert(
+                id.clone(),
+                Pending {
+                    schema: request.requested_schema.clone(),
+                    answer: sender,
+                },
+            );
+        }
+        println!("[Elicitation] {} asks: {}", request.server, request.message);
+        (self.publish)(&request);
+        self.set_waiting(true);
+        let answer = tokio::time::timeout(RESPONSE_TIMEOUT, receiver).await;
+        self.set_waiting(false);
+        if let Ok(mut pending) = PENDING.lock() {
+            pending.remove(&id);
+        }
+        let answer = answer
+            .ok()
+            .and_then(|a| a.ok())
+            .unwrap_or(ElicitationAnswer {
+                action: ElicitationAction::Cancel,
+                content: None,
+            });
+        result_of(&answer)
+    }
+
+    fn set_waiting(&self, waiting: bool) {
+        if let Ok(mut clock) = self.clock.lock() {
+            if waiting {
+                clock.since = Some(Instant::now());
+            } else if let Some(since) = clock.since.take() {
+                clock.waited += since.elapsed();
+            }
+        }
+    }
+}
+
+/// `elicitation/create` result for an answer; content is dropped unless accepted
+pub(crate) fn result_of(answer: &ElicitationAnswer) -> Value {
+    match (&answer.action, &answer.content) {
+        (ElicitationAction::Accept, Some(content)) => {
+            json!({ "action": "accept", "content": content })
+        }
+        (action, _) => json!({ "action": action }),
+    }
+}
+
+/// Check accepted content against a requested schema: required fields are present and values
+/// have the primitive type (and enum value) the schema asks for
+pub(crate) fn check_content(schema: &Value, content: &Value) -> Result<(), String> {
+    let fields = content.as_object().ok_or("Content must be an object")?;
+    for required in schema["required"].as_array().into_iter().flatten() {
+        let Some(name) = required.as_str() else {
+            continue;
+        };
+        if !fields.contains_key(name) {
+            return Err(format!("'{}' is required", name));
+        }
+    }
+    let empty = serde_json::Map::new();
+    let properties = schema["properties"].as_object().unwrap_or(&empty);
+    for (name, value) in fields {
+        let Some(property) = properties.get(name) else {
+            return Err(format!("'{}' wasn't asked for", name));
+        };
+        let type_ok = match property["type"].as_str() {
+            Some("string") => value.is_string(),
+            Some("number") => value.is_number(),
+            Some("integer") => value.is_i64() || value.is_u64(),
+            Some("boolean") => value.is_boolean(),
+            _ => true,
+        };
+        if !type_ok {
+            return Err(format!(
+                "'{}' must be of type {}",
+                name,
+                property["type"].as_str().unwrap_or_default()
+            ));
+        }
+        if let Some(allowed) = property["enum"].as_array() {
+            if !allowed.contains(value) {
+                return Err(format!("'{}' isn't one of the offered values", name));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Run `future` within `timeout`, not counting time `clock` records as spent waiting on the
+/// user; `None` when it timed out
+pub(crate) async fn timeout_excluding_answers<F: Future>(
+    timeout: Duration,
+    clock: Option<Arc<Mutex<AnswerClock>>>,
+    future: F,
+) -> Option<F::Output> {
+    let Some(clock) = clock else {
+        return tokio::time::timeout(timeout, future).await.ok();
+    };
+    tokio::pin!(future);
+    let mut deadline = tokio::time::Instant::now() + timeout;
+    let mut credited = Duration::ZERO;
+    loop {
+        tokio::select! {
+            output = &mut future => return Some(output),
+            _ = tokio::time::sleep_until(deadline) => {
+                let waited = clock.lock().map(|clock| clock.total()).unwrap_or_default();
+                if waited <= credited {
+                    return None;
+                }
+                deadline += waited - credited;
+                credited = waited;
+            }
+        }
+    }
+}
+
+/// Answer a pending elicitation request. Accepted content that doesn't match the requested
+/// schema is refused and the request stays pending.
+#[command]
+pub async fn respond_elicitation(id: String, answer: ElicitationAnswer) -> Result<(), String> {
+    let mut pending = PENDING
+        .lock()
+        .map_err(|e| format!("Failed to lock elicitation requests: {}", e))?;
+    let request = pending
+        .get(&id)
+        .ok_or_else(|| format!("Elicitation request {} is no longer pending", id))?;
+    if answer.action == ElicitationAction::Accept {
+        check_content(
+            &request.schema,
+            answer.content.as_ref().unwrap_or(&json!({})),
+        )?;
+    }
+    let request = pending.remove(&id).expect("checked above");
+    request
+        .answer
+        .send(answer)
+        .map_err(|_| "The server session has ended".to_string())
+}
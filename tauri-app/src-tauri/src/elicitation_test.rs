@@ -0,0 +1,63 @@
+// Elicitation tests
+use crate::elicitation::{
+    check_content, result_of, timeout_excluding_answers, ElicitationAction, ElicitationAnswer,
+};
+use serde_json::json;
+use std::time::Duration;
+
+fn schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "token": { "type": "string" },
+            "port": { "type": "integer" },
+            "region": { "type": "string", "enum": ["eu", "us"] }
+        },
+        "required": ["token"]
+    })
+}
+
+#[test]
+fn test_check_content() {
+    assert!(check_content(&schema(), &json!({ "token": "abc", "port": 8080 })).is_ok());
+    assert_eq!(
+        check_content(&schema(), &json!({ "port": 8080 })),
+        Err("'token' is required".to_string())
+    );
+    assert_eq!(
+        check_content(&schema(), &json!({ "token": "abc", "port": "8080" })),
+        Err("'port' must be of type integer".to_string())
+    );
+    assert!(check_content(&schema(), &json!({ "token": "abc", "region": "asia" })).is_err());
+    assert!(check_content(&schema(), &json!({ "token": "abc", "extra": true })).is_err());
+}
+
+#[test]
+fn test_result_drops_content_unless_accepted() {
+    let answer = |action| ElicitationAnswer {
+        action,
+        content: Some(json!({ "token": "abc" })),
+    };
+    assert_eq!(
+        result_of(&answer(ElicitationAction::Accept)),
+        json!({ "action": "accept", "content": { "token": "abc" } })
+    );
+    assert_eq!(
+        result_of(&answer(ElicitationAction::Decline)),
+        json!({ "action": "decline" })
+    );
+}
+
+#[tokio::test]
+async fn test_timeout_without_clock() {
+    let slow = tokio::time::sleep(Duration::from_secs(5));
+    assert_eq!(
+        timeout_excluding_answers(Duration::from_millis(10), None, slow).await,
+        None
+    );
+    let quick = async { 1 };
+    assert_eq!(
+        timeout_excluding_answers(Duration::from_secs(1), None, quick).await,
+        Some(1)
+    );
+}
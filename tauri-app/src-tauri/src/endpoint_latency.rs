@@ -0,0 +1,159 @@
+//! Picking the fastest of a remote server's regional endpoints
+//!
+//! Registry entries can list several remotes for one server (see `registry_import`). Each is
+//! probed a few times and timed to the first response; any HTTP status counts as an answer,
+//! since MCP endpoints reject plain GETs or unauthenticated requests. The median is compared
+//! so one slow probe doesn't decide. Running the benchmark again re-checks the choice.
+
+use crate::client_target;
+use crate::metrics;
+use crate::network;
+use crate::op_recorder::{self, RecordedOp};
+use crate::registry_import::{self, RemoteEndpoint};
+use crate::settings;
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::Value;
+use std::time::Instant;
+use tauri::command;
+
+const PROBES: usize = 3;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct EndpointLatency {
+    pub endpoint: RemoteEndpoint,
+    /// Median of the probes that got a response
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct EndpointBenchmark {
+    pub server: String,
+    pub checked_at: String,
+    pub endpoints: Vec<EndpointLatency>,
+    pub fastest: Option<String>,
+    /// Targets whose config now uses the fastest endpoint
+    pub configured: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+pub(crate) fn median(mut samples: Vec<u64>) -> Option<u64> {
+    samples.sort_unstable();
+    samples.get(samples.len() / 2).copied()
+}
+
+/// The endpoint with the lowest latency among the ones that answered
+pub(crate) fn fastest(results: &[EndpointLatency]) -> Option<&EndpointLatency> {
+    results
+        .iter()
+        .filter(|r| r.latency_ms.is_some())
+        .min_by_key(|r| r.latency_ms)
+}
+
+async fn probe(client: &reqwest::Client, endpoint: RemoteEndpoint) -> EndpointLatency {
+    let mut samples = Vec::new();
+    let mut error = None;
+    for _ in 0..PROBES {
+        let started = Instant::now();
+        match client.get(&endpoint.url).send().await {
+            Ok(_) => samples.push(started.elapsed().as_millis() as u64),
+            Err(e) => error = Some(format!("Failed to reach endpoint: {}", e)),
+        }
+    }
+    let latency_ms = median(samples);
+    if latency_ms.is_none() {
+        metrics::inc(metrics::HEALTH_FAILURES, &[("kind", "endpoint")]);
+    }
+    EndpointLatency {
+        // Only reported when no probe got through
+        error: error.filter(|_| latency_ms.is_none()),
+        latency_ms,
+        endpoint,
+    }
+}
+
+/// Point the server's configs in the source's targets at `url`, keeping everything else
+async fn configure(
+    source: &registry_import::RegistrySource,
+    name: &str,
+    endpoint: &RemoteEndpoint,
+    report: &mut EndpointBenchmark,
+) {
+    for target in &source.targets {
+        let configs = match client_target::list_servers(target).await {
+            Ok(configs) => configs,
+            Err(e) => {
+                report.errors.push(format!("{}: {}", target.label(), e));
+                continue;
+            }
+        };
+        let Some(mut config) = configs.get(name).cloned() else {
+            continue;
+        };
+        if config["url"].as_str() == Some(endpoint.url.as_str()) {
+            report.configured.push(target.label());
+            continue;
+        }
+        config["url"] = Value::String(endpoint.url.clone());
+        config["type"] = Value::String(endpoint.transport.clone());
+        match client_target::upsert_server(target, name, config.clone()).await {
+            Ok(written) => {
+                op_recorder::record(
+                    target.clone(),
+                    RecordedOp::UpsertServer {
+                        name: written,
+                        config,
+                    },
+                );
+                report.configured.push(target.label());
+            }
+            Err(e) => report.errors.push(format!("{}: {}", target.label(), e)),
+        }
+    }
+}
+
+/// Measure latency to every endpoint the registry at `registry_url` lists for `server_id`
+/// (registry name or local name). With `apply`, the server's configs in the source's targets
+/// are switched to the fastest one.
+#[command]
+pub async fn benchmark_endpoints(
+    server_id: String,
+    registry_url: String,
+    apply: Option<bool>,
+) -> Result<EndpointBenchmark, String> {
+    let source = registry_import::find_source(&registry_url)?;
+    let server = registry_import::fetch_servers(&source.url, source.auth_profile.as_deref())
+        .await?
+        .into_iter()
+        .find(|s| s.registry_name == server_id || s.name == server_id)
+        .ok_or_else(|| format!("Server '{}' not found in {}", server_id, registry_url))?;
+    if server.endpoints.is_empty() {
+        return Err(format!("'{}' has no remote endpoints", server_id));
+    }
+
+    let client = network::http_client(&settings::load_settings().network)?;
+    let mut endpoints = Vec::new();
+    // One at a time, so probes don't compete for bandwidth
+    for endpoint in server.endpoints.clone() {
+        endpoints.push(probe(&client, endpoint).await);
+    }
+    let best = fastest(&endpoints).map(|r| r.endpoint.clone());
+    let mut report = EndpointBenchmark {
+        server: server.name.clone(),
+        checked_at: Utc::now().to_rfc3339(),
+        fastest: best.as_ref().map(|e| e.url.clone()),
+        endpoints,
+        configured: Vec::new(),
+        errors: Vec::new(),
+    };
+    if let (Some(true), Some(best)) = (apply, &best) {
+        configure(&source, &server.name, best, &mut report).await;
+    }
+    println!(
+        "[Endpoints] {}: fastest {}",
+        report.server,
+        report.fastest.as_deref().unwrap_or("none")
+    );
+    Ok(report)
+}
@@ -0,0 +1,36 @@
+// Endpoint latency tests
+use crate::endpoint_latency::{fastest, median, EndpointLatency};
+use crate::registry_import::RemoteEndpoint;
+
+fn result(url: &str, latency_ms: Option<u64>) -> EndpointLatency {
+    EndpointLatency {
+        endpoint: RemoteEndpoint {
+            url: url.to_string(),
+            transport: "http".to_string(),
+            region: None,
+        },
+        latency_ms,
+        error: None,
+    }
+}
+
+#[test]
+fn test_median() {
+    assert_eq!(median(vec![300, 20, 25]), Some(25));
+    assert_eq!(median(vec![40]), Some(40));
+    assert_eq!(median(Vec::new()), None);
+}
+
+#[test]
+fn test_fastest_skips_unreachable() {
+    let results = vec![
+        result("https://us.example.com/mcp", Some(180)),
+        result("https://eu.example.com/mcp", Some(35)),
+        result("https://ap.example.com/mcp", None),
+    ];
+    assert_eq!(
+        fastest(&results).map(|r| r.endpoint.url.as_str()),
+        Some("https://eu.example.com/mcp")
+    );
+    assert!(fastest(&[result("https://ap.example.com/mcp", None)]).is_none());
+}
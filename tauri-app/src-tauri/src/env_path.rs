@@ -1,31 +1,24 @@
 use dirs::home_dir;
 use std::env;
+use std::path::PathBuf;
 
 /// Update the PATH environment variable to include common local bin directories.
 pub fn update_env_path() {
-    let home = home_dir().unwrap().to_str().unwrap().to_string();
-    let local_bin = format!("{}/.local/bin", home);
-    let current_path = env::var("PATH").unwrap_or_default();
+    // Joined as OS strings, so a home directory that isn't valid UTF-8 still works
+    let local_bin = home_dir().map(|home| home.join(".local").join("bin"));
+    let current_path = env::var_os("PATH").unwrap_or_default();
 
-    let new_paths: Vec<String> = if cfg!(target_os = "windows") {
-        vec![local_bin]
+    let mut new_paths: Vec<PathBuf> = if cfg!(target_os = "windows") {
+        Vec::new()
     } else {
-        vec![
-            "/opt/homebrew/bin".into(),
-            "/usr/local/bin".into(),
-            local_bin,
-        ]
+        vec!["/opt/homebrew/bin".into(), "/usr/local/bin".into()]
     };
+    new_paths.extend(local_bin);
+    new_paths.extend(env::split_paths(&current_path));
 
-    let separator = if cfg!(target_os = "windows") {
-        ";"
-    } else {
-        ":"
-    };
-
-    let updated_path = format!("{}{}{}", new_paths.join(separator), separator, current_path);
-
-    env::set_var("PATH", &updated_path);
+    if let Ok(updated_path) = env::join_paths(new_paths) {
+        env::set_var("PATH", &updated_path);
+    }
 }
 
 #[tauri::command]
@@ -0,0 +1,187 @@
+//! Typed app events, delivered only to subscribers whose filter matches
+//!
+//! The frontend calls `subscribe_events` with a filter (event kinds, clients, scopes) and
+//! listens on `bus:<subscription id>`; each matching event arrives there as a `BusEvent`.
+//! Nothing is emitted for an event no subscription wants. A scope is a Claude Code working
+//! dir, a file path or a registry URL, and a scope filter also matches everything below it.
+//! Codex session events and deep links keep their own window-scoped events.
+
+use crate::client_target::ClientTarget;
+use crate::unicode_path;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tauri::{command, AppHandle, Emitter, Runtime};
+
+static SUBSCRIPTIONS: Lazy<Mutex<HashMap<String, EventFilter>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AppEventKind {
+    CredentialsExpiring,
+    ConfigDrift,
+    ProjectsAdded,
+    ProjectsRemoved,
+    RegistryRefreshProgress,
+    RegistrySynced,
+    FsChange,
+    StartupReport,
+    ServerWarmedUp,
+    ForeignModification,
+    BootstrapProgress,
+    CliVerification,
+    ElicitationRequested,
+    LockedServerModified,
+    ClientLog,
+}
+
+/// Empty lists match everything
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct EventFilter {
+    #[serde(default)]
+    pub kinds: Vec<AppEventKind>,
+    #[serde(default)]
+    pub clients: Vec<String>,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
+/// What an event is about, for filtering
+#[derive(Debug, Clone, Default)]
+pub struct EventScope {
+    pub client: Option<String>,
+    pub scope: Option<String>,
+}
+
+impl EventScope {
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    pub fn client(client: &str) -> Self {
+        EventScope {
+            client: Some(client.to_string()),
+            scope: None,
+        }
+    }
+
+    pub fn path(path: &str) -> Self {
+        EventScope {
+            client: None,
+            scope: Some(path.to_string()),
+        }
+    }
+
+    /// Client of the target, scoped to its working dir or config path
+    pub fn target(target: &ClientTarget) -> Self {
+        let scope = if target.is_claude_code() {
+            target.working_dir.clone()
+        } else {
+            target.path.clone()
+        };
+        EventScope {
+            client: Some(target.client.clone()),
+            scope,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct BusEvent {
+    pub subscription: String,
+    pub kind: AppEventKind,
+    pub client: Option<String>,
+    pub scope: Option<String>,
+    pub payload: Value,
+}
+
+/// `actual` is `wanted` or lies below it
+pub(crate) fn scope_matches(wanted: &str, actual: &str) -> bool {
+    let wanted = unicode_path::normalize_project_path(wanted);
+    let actual = unicode_path::normalize_project_path(actual);
+    actual == wanted
+        || actual
+            .strip_prefix(&wanted)
+            .is_some_and(|rest| wanted.ends_with('/') || rest.starts_with(['/', '\\']))
+}
+
+pub(crate) fn matches(filter: &EventFilter, kind: AppEventKind, scope: &EventScope) -> bool {
+    let kind_ok = filter.kinds.is_empty() || filter.kinds.contains(&kind);
+    let client_ok = filter.clients.is_empty()
+        || scope
+            .client
+            .as_ref()
+            .is_some_and(|c| filter.clients.contains(c));
+    let scope_ok = filter.scopes.is_empty()
+        || scope
+            .scope
+            .as_deref()
+            .is_some_and(|actual| filter.scopes.iter().any(|w| scope_matches(w, actual)));
+    kind_ok && client_ok && scope_ok
+}
+
+/// Emit `payload` to every subscription that wants it
+pub(crate) fn publish<R: Runtime>(
+    app: &AppHandle<R>,
+    kind: AppEventKind,
+    scope: EventScope,
+    payload: &impl Serialize,
+) {
+    let subscribers: Vec<String> = match SUBSCRIPTIONS.lock() {
+        Ok(subscriptions) => subscriptions
+            .iter()
+            .filter(|(_, filter)| matches(filter, kind, &scope))
+            .map(|(id, _)| id.clone())
+            .collect(),
+        Err(_) => return,
+    };
+    if subscribers.is_empty() {
+        return;
+    }
+    let payload = match serde_json::to_value(payload) {
+        Ok(payload) => payload,
+        Err(e) => {
+            println!("[EventBus] Failed to serialize {:?} event: {}", kind, e);
+            return;
+        }
+    };
+    for subscription in subscribers {
+        let channel = format!("bus:{}", subscription);
+        let event = BusEvent {
+            subscription,
+            kind,
+            client: scope.client.clone(),
+            scope: scope.scope.clone(),
+            payload: payload.clone(),
+        };
+        if let Err(e) = app.emit(&channel, event) {
+            println!("[EventBus] Failed to emit {:?} event: {}", kind, e);
+        }
+    }
+}
+
+/// Register a filter; matching events are emitted on `bus:<returned id>`
+#[command]
+pub async fn subscribe_events(filter: EventFilter) -> Result<String, String> {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed).to_string();
+    SUBSCRIPTIONS
+        .lock()
+        .map_err(|e| format!("Failed to lock subscriptions: {}", e))?
+        .insert(id.clone(), filter);
+    Ok(id)
+}
+
+/// Drop a subscription; false when it didn't exist
+#[command]
+pub async fn unsubscribe_events(subscription_id: String) -> Result<bool, String> {
+    Ok(SUBSCRIPTIONS
+        .lock()
+        .map_err(|e| format!("Failed to lock subscriptions: {}", e))?
+        .remove(&subscription_id)
+        .is_some())
+}
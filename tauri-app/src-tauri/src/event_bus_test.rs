@@ -0,0 +1,81 @@
+// Event bus tests
+use crate::event_bus::{matches, scope_matches, AppEventKind, EventFilter, EventScope};
+
+#[test]
+fn test_empty_filter_matches_everything() {
+    let filter = EventFilter::default();
+    assert!(matches(
+        &filter,
+        AppEventKind::FsChange,
+        &EventScope::none()
+    ));
+    assert!(matches(
+        &filter,
+        AppEventKind::ConfigDrift,
+        &EventScope::client("cursor")
+    ));
+}
+
+#[test]
+fn test_filter_by_kind_and_client() {
+    let filter = EventFilter {
+        kinds: vec![AppEventKind::ConfigDrift],
+        clients: vec!["cursor".to_string()],
+        scopes: Vec::new(),
+    };
+    assert!(matches(
+        &filter,
+        AppEventKind::ConfigDrift,
+        &EventScope::client("cursor")
+    ));
+    assert!(!matches(
+        &filter,
+        AppEventKind::ConfigDrift,
+        &EventScope::client("vscode")
+    ));
+    assert!(!matches(
+        &filter,
+        AppEventKind::FsChange,
+        &EventScope::client("cursor")
+    ));
+    // An event without a client doesn't match a client filter
+    assert!(!matches(
+        &filter,
+        AppEventKind::ConfigDrift,
+        &EventScope::none()
+    ));
+}
+
+#[test]
+fn test_filter_by_scope() {
+    let filter = EventFilter {
+        kinds: Vec::new(),
+        clients: Vec::new(),
+        scopes: vec!["/home/me/app".to_string()],
+    };
+    assert!(matches(
+        &filter,
+        AppEventKind::FsChange,
+        &EventScope::path("/home/me/app/src/main.rs")
+    ));
+    assert!(!matches(
+        &filter,
+        AppEventKind::FsChange,
+        &EventScope::path("/home/me/other")
+    ));
+    assert!(!matches(
+        &filter,
+        AppEventKind::FsChange,
+        &EventScope::client("cursor")
+    ));
+}
+
+#[test]
+fn test_scope_matches() {
+    assert!(scope_matches("/home/me/app", "/home/me/app"));
+    assert!(scope_matches("/home/me/app/", "/home/me/app/src"));
+    assert!(scope_matches("/home/me/app", "/home/me/app/src"));
+    assert!(!scope_matches("/home/me/app", "/home/me/application"));
+    assert!(scope_matches(r"C:\Users\Me\App", "c:/users/me/app/src"));
+    assert!(scope_matches("/", "/home"));
+}
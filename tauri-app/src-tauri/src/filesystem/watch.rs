@@ -1,8 +1,10 @@
+use crate::event_bus::{self, AppEventKind, EventScope};
 use crate::state::WatchState;
+use crate::watch_debounce::{merge_kind, Debouncer};
 use notify::{recommended_watcher, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use serde::Serialize;
 use std::path::{Path, PathBuf};
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, State};
 
 #[derive(Serialize, Debug, Clone)]
 pub struct FsChangePayload {
@@ -60,17 +62,21 @@ pub async fn start_watch_directory(
     }
 
     let app_for_cb = app.clone();
-    // Create a new watcher with a callback that emits tauri event
+    // Bursts of events for a path are coalesced into one fs_change event
+    let debouncer = Debouncer::new(merge_kind, move |path, kind| {
+        let payload = FsChangePayload {
+            path: path.to_string_lossy().to_string(),
+            kind,
+        };
+        let scope = EventScope::path(&payload.path);
+        event_bus::publish(&app_for_cb, AppEventKind::FsChange, scope, &payload);
+    });
     let mut watcher: RecommendedWatcher =
         recommended_watcher(move |res: Result<Event, notify::Error>| {
             if let Ok(event) = res {
-                // Send one event per affected path
+                // One event per affected path
                 for p in event.paths.iter() {
-                    let payload = FsChangePayload {
-                        path: p.to_string_lossy().to_string(),
-                        kind: kind_to_string(&event.kind),
-                    };
-                    let _ = app_for_cb.emit("fs_change", &payload);
+                    debouncer.push(p.clone(), kind_to_string(&event.kind));
                 }
             }
         })
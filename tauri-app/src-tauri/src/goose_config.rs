@@ -0,0 +1,326 @@
+//! Goose's MCP servers, the `extensions` of its config.yaml
+//!
+//! Goose keeps extensions in ~/.config/goose/config.yaml (%APPDATA%\Block\goose\config on
+//! Windows), keyed by name, next to its provider and model settings. They have their own
+//! shape: `type: stdio` with `cmd`, `args` and `envs`, `sse` and `streamable_http` with `uri`
+//! (and `headers`), each with an `enabled` flag. The servers are read as JSON in the shape of
+//! other clients: enabled ones as active, `enabled: false` ones as disabled. Builtin and
+//! platform extensions run inside Goose, aren't MCP servers of their own and are left out.
+//! Writes replace only the `extensions` block (see `yaml_block`) and keep the fields of an
+//! entry this app doesn't model (`timeout`, `description`, `env_keys`, ...).
+
+use crate::{write_journal, yaml_block};
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::command;
+
+pub(crate) const CLIENT: &str = "goose";
+
+const EXTENSIONS_KEY: &str = "extensions";
+
+/// Entry fields translated to and from the JSON shape
+const MODELED_FIELDS: &[&str] = &["name", "type", "cmd", "args", "envs", "uri", "headers"];
+
+/// Seconds Goose waits on a tool call, written to new entries as Goose does
+const DEFAULT_TIMEOUT: u64 = 300;
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// Goose's config.yaml, or the YAML file named by `path`
+pub(crate) fn goose_config_path(path: Option<&str>) -> Result<PathBuf, String> {
+    if let Some(path) = path.filter(|p| !p.is_empty()) {
+        return Ok(PathBuf::from(path));
+    }
+    let dir = if cfg!(target_os = "windows") {
+        crate::demo_mode::config_dir()
+            .map(|config| config.join("Block").join("goose").join("config"))
+    } else {
+        crate::demo_mode::home_dir().map(|home| home.join(".config").join("goose"))
+    };
+    Ok(dir
+        .ok_or("Failed to locate the Goose config directory")?
+        .join("config.yaml"))
+}
+
+pub(crate) fn parse(content: &str) -> Result<Value, String> {
+    yaml_block::parse(content, "Goose config")
+}
+
+/// A Goose extension in the JSON shape of other clients; `None` for extensions that aren't
+/// MCP servers
+pub(crate) fn from_goose_entry(entry: &Value) -> Option<Value> {
+    let mut server = Map::new();
+    match entry["type"].as_str()? {
+        "stdio" => {
+            server.insert("command".into(), entry["cmd"].as_str()?.into());
+            server.insert(
+                "args".into(),
+                entry.get("args").cloned().unwrap_or_else(|| json!([])),
+            );
+            if let Some(envs) = entry["envs"].as_object().filter(|envs| !envs.is_empty()) {
+                server.insert("env".into(), Value::Object(envs.clone()));
+            }
+        }
+        kind @ ("sse" | "streamable_http") => {
+            server.insert(
+                "type".into(),
+                json!(if kind == "sse" { "sse" } else { "http" }),
+            );
+            server.insert("url".into(), entry["uri"].as_str()?.into());
+            if let Some(headers) = entry["headers"].as_object().filter(|h| !h.is_empty()) {
+                server.insert("headers".into(), Value::Object(headers.clone()));
+            }
+        }
+        _ => return None,
+    }
+    Some(Value::Object(server))
+}
+
+/// `config` as the Goose extension `name`, keeping the unmodeled fields of `existing`
+pub(crate) fn to_goose_entry(
+    name: &str,
+    config: &Value,
+    existing: Option<&Value>,
+    enabled: bool,
+) -> Result<Value, String> {
+    let mut entry = Map::new();
+    entry.insert("name".into(), json!(name));
+    match (config["command"].as_str(), config["url"].as_str()) {
+        (Some(command), _) => {
+            entry.insert("type".into(), json!("stdio"));
+            entry.insert("cmd".into(), json!(command));
+            entry.insert(
+                "args".into(),
+                config.get("args").cloned().unwrap_or_else(|| json!([])),
+            );
+            entry.insert(
+                "envs".into(),
+                config.get("env").cloned().unwrap_or_else(|| json!({})),
+            );
+        }
+        (None, Some(url)) => {
+            let sse = config["type"] == "sse";
+            entry.insert(
+                "type".into(),
+                json!(if sse { "sse" } else { "streamable_http" }),
+            );
+            entry.insert("uri".into(), json!(url));
+            if let Some(headers) = config["headers"].as_object().filter(|h| !h.is_empty()) {
+                entry.insert("headers".into(), Value::Object(headers.clone()));
+            }
+        }
+        (None, None) => {
+            return Err(format!(
+                "Server '{}' needs a command or a URL for Goose",
+                name
+            ))
+        }
+    }
+    entry.insert("enabled".into(), json!(enabled));
+    match existing.and_then(Value::as_object) {
+        Some(existing) => {
+            for (key, value) in existing {
+                if !MODELED_FIELDS.contains(&key.as_str()) {
+                    entry.entry(key.clone()).or_insert(value.clone());
+                }
+            }
+        }
+        None => {
+            entry.insert("timeout".into(), json!(DEFAULT_TIMEOUT));
+        }
+    }
+    Ok(Value::Object(entry))
+}
+
+/// MCP servers of a parsed config in the JSON shape, enabled ones or disabled ones
+pub(crate) fn servers_of(doc: &Value, enabled: bool) -> Map<String, Value> {
+    doc[EXTENSIONS_KEY]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(_, entry)| entry["enabled"].as_bool().unwrap_or(true) == enabled)
+        .filter_map(|(name, entry)| Some((name.clone(), from_goose_entry(entry)?)))
+        .collect()
+}
+
+/// `content` with its `extensions` block replaced by `extensions`
+pub(crate) fn splice_extensions(
+    content: &str,
+    extensions: &Map<String, Value>,
+) -> Result<String, String> {
+    yaml_block::splice(
+        content,
+        EXTENSIONS_KEY,
+        &Value::Object(extensions.clone()),
+        "  ",
+    )
+}
+
+fn read_content(path: &Path) -> Result<String, String> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(format!("Failed to read Goose config: {}", e)),
+    }
+}
+
+/// Apply `edit` to the raw extensions of the config at `path` and write the file back;
+/// returns the servers that are `enabled` afterwards, or the disabled ones
+fn edit_extensions(
+    path: Option<&str>,
+    enabled: bool,
+    edit: impl FnOnce(&mut Map<String, Value>) -> Result<(), String>,
+) -> Result<Map<String, Value>, String> {
+    let path = goose_config_path(path)?;
+    let _guard = LOCK
+        .lock()
+        .map_err(|e| format!("Failed to lock Goose config: {}", e))?;
+    let content = read_content(&path)?;
+    let mut extensions = parse(&content)?[EXTENSIONS_KEY]
+        .as_object()
+        .cloned()
+        .unwrap_or_default();
+    edit(&mut extensions)?;
+    let edited = splice_extensions(&content, &extensions)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create Goose config directory: {}", e))?;
+    }
+    write_journal::write(&path, &edited)
+        .map_err(|e| format!("Failed to write Goose config: {}", e))?;
+    Ok(servers_of(&json!({ EXTENSIONS_KEY: extensions }), enabled))
+}
+
+/// Servers of the Goose config at `path`, enabled ones or disabled ones
+pub(crate) fn read_servers(
+    path: Option<&str>,
+    enabled: bool,
+) -> Result<Map<String, Value>, String> {
+    let content = read_content(&goose_config_path(path)?)?;
+    Ok(servers_of(&parse(&content)?, enabled))
+}
+
+fn mcp_entry<'a>(extensions: &'a Map<String, Value>, name: &str) -> Option<&'a Value> {
+    extensions
+        .get(name)
+        .filter(|entry| from_goose_entry(entry).is_some())
+}
+
+/// Add or, with `replace`, overwrite the server `name`; an overwritten server stays enabled
+/// or disabled as it was
+pub(crate) fn write_server(
+    path: Option<&str>,
+    name: &str,
+    config: &Value,
+    replace: bool,
+) -> Result<Map<String, Value>, String> {
+    edit_extensions(path, true, |extensions| {
+        let existing = extensions.get(name);
+        if existing.is_some_and(|entry| from_goose_entry(entry).is_none()) {
+            return Err(format!("'{}' is a builtin Goose extension", name));
+        }
+        if existing.is_some() && !replace {
+            return Err(format!("Server '{}' already exists in Goose", name));
+        }
+        let enabled = existing.is_none_or(|entry| entry["enabled"].as_bool().unwrap_or(true));
+        let entry = to_goose_entry(name, config, existing, enabled)?;
+        extensions.insert(name.to_string(), entry);
+        Ok(())
+    })
+}
+
+/// Overwrite the disabled server `name`, which stays disabled
+pub(crate) fn write_disabled_server(
+    path: Option<&str>,
+    name: &str,
+    config: &Value,
+) -> Result<Map<String, Value>, String> {
+    edit_extensions(path, false, |extensions| {
+        let existing = mcp_entry(extensions, name)
+            .filter(|entry| entry["enabled"] == false)
+            .ok_or_else(|| format!("Disabled server '{}' not found in Goose", name))?;
+        let entry = to_goose_entry(name, config, Some(existing), false)?;
+        extensions.insert(name.to_string(), entry);
+        Ok(())
+    })
+}
+
+/// Flip the `enabled` flag of the server `name`; returns the disabled servers
+pub(crate) fn set_enabled(
+    path: Option<&str>,
+    name: &str,
+    enabled: bool,
+) -> Result<Map<String, Value>, String> {
+    edit_extensions(path, false, |extensions| {
+        if mcp_entry(extensions, name).is_none() {
+            return Err(format!("Server '{}' not found in Goose", name));
+        }
+        extensions[name]["enabled"] = json!(enabled);
+        Ok(())
+    })
+}
+
+pub(crate) fn remove_servers(
+    path: Option<&str>,
+    names: &[String],
+) -> Result<Map<String, Value>, String> {
+    edit_extensions(path, true, |extensions| {
+        let missing: Vec<&str> = names
+            .iter()
+            .filter(|name| mcp_entry(extensions, name).is_none())
+            .map(String::as_str)
+            .collect();
+        if !missing.is_empty() {
+            return Err(format!(
+                "Server '{}' not found in Goose",
+                missing.join("', '")
+            ));
+        }
+        for name in names {
+            extensions.shift_remove(name);
+        }
+        Ok(())
+    })
+}
+
+/// Write `servers` in one edit: all of them, dropping the other MCP servers with
+/// `override_all`, or only the names not configured yet. Builtin extensions are never touched.
+pub(crate) fn sync_servers(
+    path: Option<&str>,
+    servers: &Map<String, Value>,
+    override_all: bool,
+) -> Result<Map<String, Value>, String> {
+    edit_extensions(path, true, |extensions| {
+        if override_all {
+            extensions.retain(|name, entry| {
+                from_goose_entry(entry).is_none() || servers.contains_key(name)
+            });
+        }
+        for (name, config) in servers {
+            let existing = extensions.get(name);
+            if existing.is_some_and(|entry| from_goose_entry(entry).is_none()) {
+                println!("[Goose] Not replacing builtin extension {}", name);
+                continue;
+            }
+            if existing.is_some() && !override_all {
+                continue;
+            }
+            let enabled = existing.is_none_or(|entry| entry["enabled"].as_bool().unwrap_or(true));
+            let entry = to_goose_entry(name, config, existing, enabled)?;
+            extensions.insert(name.clone(), entry);
+        }
+        Ok(())
+    })
+}
+
+/// MCP servers of Goose's config.yaml, or of the config at `path`, keyed by name as other
+/// clients' configs are; disabled extensions under `__disabled`
+#[command]
+pub async fn read_goose_servers(path: Option<String>) -> Result<Value, String> {
+    Ok(json!({
+        "mcpServers": read_servers(path.as_deref(), true)?,
+        "__disabled": read_servers(path.as_deref(), false)?,
+    }))
+}
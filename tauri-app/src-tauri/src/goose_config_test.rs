@@ -0,0 +1,117 @@
+// Goose config tests
+use crate::goose_config::{from_goose_entry, parse, servers_of, splice_extensions, to_goose_entry};
+use serde_json::json;
+
+const CONFIG: &str = "\
+GOOSE_PROVIDER: anthropic # default provider
+GOOSE_MODEL: claude-sonnet
+extensions:
+  developer:
+    bundled: true
+    enabled: true
+    name: developer
+    timeout: 300
+    type: builtin
+  github:
+    args:
+    - -y
+    - '@modelcontextprotocol/server-github'
+    cmd: npx
+    enabled: true
+    envs:
+      GITHUB_TOKEN: ghp_x
+    name: github
+    timeout: 120
+    type: stdio
+  docs:
+    enabled: false
+    name: docs
+    type: streamable_http
+    uri: https://docs.example.com/mcp
+    headers:
+      Authorization: Bearer x
+
+# chosen in the session picker
+GOOSE_MODE: smart_approve
+";
+
+#[test]
+fn test_goose_extensions_in_client_shape() {
+    let doc = parse(CONFIG).unwrap();
+    let active = servers_of(&doc, true);
+    assert_eq!(active.keys().collect::<Vec<_>>(), vec!["github"]);
+    assert_eq!(
+        active["github"],
+        json!({
+            "command": "npx",
+            "args": ["-y", "@modelcontextprotocol/server-github"],
+            "env": { "GITHUB_TOKEN": "ghp_x" }
+        })
+    );
+    assert_eq!(
+        servers_of(&doc, false)["docs"],
+        json!({
+            "type": "http",
+            "url": "https://docs.example.com/mcp",
+            "headers": { "Authorization": "Bearer x" }
+        })
+    );
+    assert_eq!(from_goose_entry(&doc["extensions"]["developer"]), None);
+}
+
+#[test]
+fn test_goose_entry_keeps_unmodeled_fields() {
+    let doc = parse(CONFIG).unwrap();
+    let entry = to_goose_entry(
+        "github",
+        &json!({ "command": "uvx", "args": ["gh-mcp"] }),
+        Some(&doc["extensions"]["github"]),
+        true,
+    )
+    .unwrap();
+    assert_eq!(
+        entry,
+        json!({
+            "name": "github",
+            "type": "stdio",
+            "cmd": "uvx",
+            "args": ["gh-mcp"],
+            "envs": {},
+            "enabled": true,
+            "timeout": 120
+        })
+    );
+    let new = to_goose_entry(
+        "sse",
+        &json!({ "type": "sse", "url": "http://x" }),
+        None,
+        true,
+    );
+    assert_eq!(
+        new.unwrap(),
+        json!({ "name": "sse", "type": "sse", "uri": "http://x", "enabled": true, "timeout": 300 })
+    );
+    assert!(to_goose_entry("empty", &json!({}), None, true).is_err());
+}
+
+#[test]
+fn test_splice_extensions_keeps_the_rest_of_the_file() {
+    let doc = parse(CONFIG).unwrap();
+    let mut extensions = doc["extensions"].as_object().unwrap().clone();
+    extensions.shift_remove("docs");
+    let edited = splice_extensions(CONFIG, &extensions).unwrap();
+
+    assert!(edited.starts_with(
+        "GOOSE_PROVIDER: anthropic # default provider\nGOOSE_MODEL: claude-sonnet\nextensions:\n  \
+         developer:\n"
+    ));
+    assert!(edited.ends_with("\n# chosen in the session picker\nGOOSE_MODE: smart_approve\n"));
+    let reparsed = parse(&edited).unwrap();
+    assert_eq!(reparsed["extensions"], json!(extensions));
+    assert_eq!(reparsed["GOOSE_MODE"], "smart_approve");
+
+    assert_eq!(
+        splice_extensions("GOOSE_MODEL: x\n", &serde_json::Map::new()).unwrap(),
+        "GOOSE_MODEL: x\nextensions: {}\n"
+    );
+}
@@ -21,7 +21,7 @@ pub async fn install_command(
 }
 
 // Added: Function to determine default package manager
-fn get_default_package_manager() -> String {
+pub(crate) fn get_default_package_manager() -> String {
     match std::env::consts::OS {
         "macos" => "brew".to_string(),
         "windows" => "winget".to_string(),
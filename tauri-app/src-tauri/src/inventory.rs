@@ -0,0 +1,369 @@
+//! Machine-readable inventory of every configured server
+//!
+//! One row per server across all known targets (see `client_target::known_targets`), active
+//! and disabled, as JSON, CSV, a Markdown table or a CycloneDX SBOM (see `sbom`). Env and
+//! header values are never included, only their key names; commands and URLs go through the
+//! redaction rules (see `redaction`), and URL userinfo and secret query parameters are dropped.
+//! `credential_health` only reflects credential expiry (see `credential_expiry`), not whether
+//! the server answers. License and repository of npm and PyPI packages are looked up on
+//! request (see `package_metadata`). The protocol version a server negotiated comes from the
+//! handshake cache (see `server_metadata`) and is empty for servers that were never launched.
+
+use crate::client_target::{self, ClientTarget};
+use crate::credential_expiry::{self, CredentialStatus};
+use crate::package_metadata;
+use crate::redaction::{self, Rules};
+use crate::sbom;
+use crate::server_handshake::{self, ProtocolStatus};
+use crate::server_metadata::{self, ServerMetadata};
+use crate::server_package::{self, PackageRef};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fs;
+use tauri::command;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InventoryFormat {
+    Json,
+    Csv,
+    Markdown,
+    Cyclonedx,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct InventoryEntry {
+    pub client: String,
+    /// Claude Code project, config path of a project-level client, or "Global"
+    pub scope: String,
+    pub name: String,
+    /// stdio, http or sse
+    pub transport: String,
+    pub protocol_version: Option<String>,
+    pub protocol_status: Option<ProtocolStatus>,
+    pub command: Option<String>,
+    pub url: Option<String>,
+    pub package: Option<PackageRef>,
+    pub version_pinned: bool,
+    pub license: Option<String>,
+    pub repository: Option<String>,
+    pub env_keys: Vec<String>,
+    pub header_keys: Vec<String>,
+    pub enabled: bool,
+    /// Credential expiry only: "ok", "credentials_expiring" or "credentials_expired"
+    pub credential_health: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct Inventory {
+    pub generated_at: String,
+    pub servers: Vec<InventoryEntry>,
+}
+
+pub(crate) fn scope_label(target: &ClientTarget) -> String {
+    match (&target.working_dir, &target.path) {
+        (Some(dir), _) if target.is_claude_code() => dir.clone(),
+        (_, Some(path)) if !path.is_empty() => path.clone(),
+        _ => crate::claude_code_commands::GLOBAL_PROJECT_ID.to_string(),
+    }
+}
+
+fn keys_of(value: &Value) -> Vec<String> {
+    value
+        .as_object()
+        .map(|m| m.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+pub(crate) fn credential_health_label(status: Option<&CredentialStatus>) -> String {
+    match status {
+        Some(CredentialStatus::CredentialsExpiring) => "credentials_expiring",
+        Some(CredentialStatus::CredentialsExpired) => "credentials_expired",
+        None => "ok",
+    }
+    .to_string()
+}
+
+/// `url` without userinfo and secret query parameters, then redacted
+pub(crate) fn redact_url(rules: &Rules, url: &str) -> String {
+    let Ok(mut parsed) = url::Url::parse(url) else {
+        return rules.redact_text(url);
+    };
+    let _ = parsed.set_password(None);
+    let _ = parsed.set_username("");
+    if parsed
+        .query_pairs()
+        .any(|(key, _)| rules.is_secret_key(&key))
+    {
+        let pairs: Vec<(String, String)> = parsed
+            .query_pairs()
+            .filter(|(key, _)| !rules.is_secret_key(key))
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect();
+        if pairs.is_empty() {
+            parsed.set_query(None);
+        } else {
+            parsed.query_pairs_mut().clear().extend_pairs(pairs);
+        }
+    }
+    rules.redact_text(parsed.as_str())
+}
+
+pub(crate) fn entry_row(
+    target: &ClientTarget,
+    name: &str,
+    config: &Value,
+    enabled: bool,
+    health: Option<&CredentialStatus>,
+) -> InventoryEntry {
+    let rules = redaction::rules();
+    let url = config["url"].as_str().map(|s| redact_url(&rules, s));
+    let transport = config["type"]
+        .as_str()
+        .map(|t| t.to_string())
+        .unwrap_or_else(|| if url.is_some() { "http" } else { "stdio" }.to_string());
+    let package = server_package::package_ref(config);
+    InventoryEntry {
+        client: target.client.clone(),
+        scope: scope_label(target),
+        name: name.to_string(),
+        transport,
+        protocol_version: None,
+        protocol_status: None,
+        command: config["command"].as_str().map(|s| rules.redact_text(s)),
+        url,
+        version_pinned: package.as_ref().is_some_and(|p| p.is_pinned()),
+        package,
+        license: None,
+        repository: None,
+        env_keys: keys_of(&config["env"]),
+        header_keys: keys_of(&config["headers"]),
+        enabled,
+        credential_health: credential_health_label(health),
+    }
+}
+
+async fn target_rows(
+    target: &ClientTarget,
+    statuses: &HashMap<(Option<String>, String), CredentialStatus>,
+) -> Result<Vec<InventoryEntry>, String> {
+    let active = client_target::list_servers(target).await?;
+    let disabled: Map<String, Value> = client_target::list_disabled_servers(target).await?;
+    let working_dir = target
+        .is_claude_code()
+        .then(|| target.working_dir().to_string());
+    let health = |name: &str| statuses.get(&(working_dir.clone(), name.to_string()));
+    let mut rows: Vec<InventoryEntry> = active
+        .iter()
+        .filter(|(name, _)| !disabled.contains_key(*name))
+        .map(|(name, config)| entry_row(target, name, config, true, health(name)))
+        .collect();
+    rows.extend(
+        disabled
+            .iter()
+            .map(|(name, config)| entry_row(target, name, config, false, health(name))),
+    );
+    Ok(rows)
+}
+
+/// Protocol version from the last handshake with each row's server
+pub(crate) fn attach_protocol(
+    target: &ClientTarget,
+    rows: &mut [InventoryEntry],
+    cached: &[ServerMetadata],
+) {
+    for row in rows {
+        if let Some(found) = cached
+            .iter()
+            .find(|m| m.target == *target && m.name == row.name)
+        {
+            let version = found.server.protocol_version.clone();
+            row.protocol_status = Some(server_handshake::protocol_status(version.as_deref()));
+            row.protocol_version = version;
+        }
+    }
+}
+
+/// Rows for every known target; targets that can't be read are logged and skipped
+pub(crate) async fn collect_inventory() -> Inventory {
+    Inventory {
+        generated_at: Utc::now().to_rfc3339(),
+        servers: collect_rows(|_| true).await,
+    }
+}
+
+/// Rows for the known targets `include` accepts, which are the only ones read
+pub(crate) async fn collect_rows(include: impl Fn(&ClientTarget) -> bool) -> Vec<InventoryEntry> {
+    let mut servers = Vec::new();
+    let mut statuses = HashMap::new();
+    let (cached, _) = server_metadata::cached();
+    for target in client_target::known_targets().await {
+        if !include(&target) {
+            continue;
+        }
+        let client_statuses = statuses
+            .entry(target.client.clone())
+            .or_insert_with(|| credential_expiry::statuses_for_client(&target.client));
+        match target_rows(&target, client_statuses).await {
+            Ok(mut rows) => {
+                attach_protocol(&target, &mut rows, &cached);
+                servers.extend(rows)
+            }
+            Err(e) => println!("[Inventory] Skipping {}: {}", target.label(), e),
+        }
+    }
+    servers
+}
+
+/// Fill in license and repository of every server that runs a registry package
+pub(crate) async fn attach_metadata(inventory: &mut Inventory) {
+    let packages: Vec<PackageRef> = inventory
+        .servers
+        .iter()
+        .filter_map(|entry| entry.package.clone())
+        .collect();
+    let metadata = package_metadata::resolve(&packages).await;
+    for entry in &mut inventory.servers {
+        if let Some(found) = entry.package.as_ref().and_then(|p| metadata.get(p)) {
+            entry.license = found.license.clone();
+            entry.repository = found.repository.clone().or_else(|| found.homepage.clone());
+        }
+    }
+}
+
+fn package_cells(entry: &InventoryEntry) -> (String, String) {
+    match &entry.package {
+        Some(p) => (p.name.clone(), p.version.clone().unwrap_or_default()),
+        None => (String::new(), String::new()),
+    }
+}
+
+fn protocol_status_label(status: ProtocolStatus) -> &'static str {
+    match status {
+        ProtocolStatus::Current => "current",
+        ProtocolStatus::Previous => "previous",
+        ProtocolStatus::Outdated => "outdated",
+        ProtocolStatus::Unknown => "unknown",
+    }
+}
+
+const COLUMNS: [&str; 16] = [
+    "client",
+    "scope",
+    "name",
+    "transport",
+    "protocol_version",
+    "protocol_status",
+    "package",
+    "version",
+    "pinned",
+    "license",
+    "repository",
+    "url",
+    "env_keys",
+    "header_keys",
+    "enabled",
+    "credential_health",
+];
+
+fn row_cells(entry: &InventoryEntry) -> Vec<String> {
+    let (package, version) = package_cells(entry);
+    vec![
+        entry.client.clone(),
+        entry.scope.clone(),
+        entry.name.clone(),
+        entry.transport.clone(),
+        entry.protocol_version.clone().unwrap_or_default(),
+        entry
+            .protocol_status
+            .map(protocol_status_label)
+            .unwrap_or_default()
+            .to_string(),
+        // Servers without a known launcher are identified by their command
+        if package.is_empty() {
+            entry.command.clone().unwrap_or_default()
+        } else {
+            package
+        },
+        version,
+        entry.version_pinned.to_string(),
+        entry.license.clone().unwrap_or_default(),
+        entry.repository.clone().unwrap_or_default(),
+        entry.url.clone().unwrap_or_default(),
+        entry.env_keys.join(";"),
+        entry.header_keys.join(";"),
+        entry.enabled.to_string(),
+        entry.credential_health.clone(),
+    ]
+}
+
+fn csv_cell(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub(crate) fn render_csv(inventory: &Inventory) -> String {
+    let mut lines = vec![COLUMNS.join(",")];
+    for entry in &inventory.servers {
+        let cells: Vec<String> = row_cells(entry).iter().map(|c| csv_cell(c)).collect();
+        lines.push(cells.join(","));
+    }
+    lines.join("\n") + "\n"
+}
+
+fn markdown_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+pub(crate) fn render_markdown(inventory: &Inventory) -> String {
+    let mut lines = vec![
+        "# MCP server inventory".to_string(),
+        String::new(),
+        format!("Generated {}", inventory.generated_at),
+        String::new(),
+        format!("| {} |", COLUMNS.join(" | ")),
+        format!("|{}", " --- |".repeat(COLUMNS.len())),
+    ];
+    for entry in &inventory.servers {
+        let cells: Vec<String> = row_cells(entry).iter().map(|c| markdown_cell(c)).collect();
+        lines.push(format!("| {} |", cells.join(" | ")));
+    }
+    lines.join("\n") + "\n"
+}
+
+pub(crate) fn render(inventory: &Inventory, format: InventoryFormat) -> Result<String, String> {
+    match format {
+        InventoryFormat::Json => serde_json::to_string_pretty(inventory)
+            .map_err(|e| format!("Failed to serialize inventory: {}", e)),
+        InventoryFormat::Csv => Ok(render_csv(inventory)),
+        InventoryFormat::Markdown => Ok(render_markdown(inventory)),
+        InventoryFormat::Cyclonedx => {
+            serde_json::to_string_pretty(&sbom::render_cyclonedx(inventory))
+                .map_err(|e| format!("Failed to serialize SBOM: {}", e))
+        }
+    }
+}
+
+/// Inventory of all servers in `format`, also written to `path` when given. With
+/// `resolve_metadata` package licenses and repositories are looked up in the registries.
+#[command]
+pub async fn generate_inventory(
+    format: InventoryFormat,
+    path: Option<String>,
+    resolve_metadata: Option<bool>,
+) -> Result<String, String> {
+    let mut inventory = collect_inventory().await;
+    if resolve_metadata == Some(true) {
+        attach_metadata(&mut inventory).await;
+    }
+    let content = render(&inventory, format)?;
+    if let Some(path) = path {
+        fs::write(&path, &content).map_err(|e| format!("Failed to write inventory: {}", e))?;
+    }
+    Ok(content)
+}
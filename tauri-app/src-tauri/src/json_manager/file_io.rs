@@ -19,7 +19,7 @@ pub async fn read_json_file(path: &Path) -> Result<Value, String> {
             if e.kind() == ErrorKind::NotFound {
                 Ok(json!({})) // Return empty JSON for Not Found
             } else {
-                Err(format!("Failed to read file: {}", e))
+                Err(crate::platform_access::read_error(&path_buf, &e))
             }
         }
     }
@@ -49,8 +49,11 @@ pub async fn write_json_file(path: &Path, content: &Value) -> Result<(), String>
 
     let json_string = json_string_result?; // Handle the inner Result from the blocking task
 
-    // Write the JSON file asynchronously
-    fs::write(&path_buf, json_string)
-        .await
-        .map_err(|e| format!("Failed to write file: {}", e))
+    // Write the JSON file under the journal in a blocking task
+    task::spawn_blocking(crate::write_journal::carry_batch(move || {
+        crate::write_journal::write(&path_buf, &json_string)
+    }))
+    .await
+    .map_err(|e| format!("Failed to run blocking task for file write: {}", e))?
+    .map_err(|e| format!("Failed to write file: {}", e))
 }
@@ -0,0 +1,91 @@
+//! RFC 6901 JSON pointer helpers
+//!
+//! serde_json can resolve pointers but not build or delete them; project keys in
+//! ~/.claude.json are filesystem paths, so every segment has to be escaped.
+
+use serde_json::Value;
+
+/// Escape a key for use as a pointer segment
+pub fn escape_segment(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+pub fn unescape_segment(segment: &str) -> String {
+    segment.replace("~1", "/").replace("~0", "~")
+}
+
+/// Build a pointer from raw (unescaped) keys
+pub fn join(keys: &[&str]) -> String {
+    keys.iter()
+        .map(|k| format!("/{}", escape_segment(k)))
+        .collect()
+}
+
+/// Pointer to a Claude Code server entry for the given scope
+pub fn claude_server_pointer(working_dir: &str, name: &str) -> String {
+    if working_dir == crate::claude_code_commands::GLOBAL_PROJECT_ID {
+        join(&["mcpServers", name])
+    } else {
+        join(&["projects", working_dir, "mcpServers", name])
+    }
+}
+
+/// Split a pointer into its parent pointer and last (unescaped) key
+pub fn split_last(pointer: &str) -> Option<(&str, String)> {
+    let idx = pointer.rfind('/')?;
+    Some((&pointer[..idx], unescape_segment(&pointer[idx + 1..])))
+}
+
+/// Ancestor pointers of `pointer` from deepest to shallowest, excluding the root
+pub fn ancestors(pointer: &str) -> Vec<&str> {
+    let mut result = Vec::new();
+    let mut current = pointer;
+    while let Some(idx) = current.rfind('/') {
+        current = &current[..idx];
+        if current.is_empty() {
+            break;
+        }
+        result.push(current);
+    }
+    result
+}
+
+/// Remove the value at `pointer` from its parent object or array
+pub fn remove(value: &mut Value, pointer: &str) -> Option<Value> {
+    let (parent, key) = split_last(pointer)?;
+    match value.pointer_mut(parent)? {
+        Value::Object(map) => map.shift_remove(&key),
+        Value::Array(items) => {
+            let index: usize = key.parse().ok()?;
+            (index < items.len()).then(|| items.remove(index))
+        }
+        _ => None,
+    }
+}
+
+/// Set the value at `pointer`, creating missing parent objects. Array indexes must exist.
+pub fn set(value: &mut Value, pointer: &str, new_value: Value) -> Result<(), String> {
+    let Some((parent, key)) = split_last(pointer) else {
+        *value = new_value;
+        return Ok(());
+    };
+    if value.pointer(parent).is_none() {
+        set(value, parent, Value::Object(serde_json::Map::new()))?;
+    }
+    match value.pointer_mut(parent) {
+        Some(Value::Object(map)) => {
+            map.insert(key, new_value);
+            Ok(())
+        }
+        Some(Value::Array(items)) => {
+            let slot = key
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| items.get_mut(index))
+                .ok_or_else(|| format!("Array index '{}' out of range at '{}'", key, parent))?;
+            *slot = new_value;
+            Ok(())
+        }
+        _ => Err(format!("'{}' is not an object or array", parent)),
+    }
+}
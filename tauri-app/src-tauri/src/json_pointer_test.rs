@@ -0,0 +1,59 @@
+// JSON pointer helper tests
+use crate::json_pointer::{
+    ancestors, escape_segment, join, remove, set, split_last, unescape_segment,
+};
+use serde_json::json;
+
+#[test]
+fn test_escape_roundtrip() {
+    assert_eq!(escape_segment("/home/a~b"), "~1home~1a~0b");
+    assert_eq!(unescape_segment("~1home~1a~0b"), "/home/a~b");
+    // `~01` is an escaped `~` followed by `1`, not a slash
+    assert_eq!(unescape_segment("~01"), "~1");
+    assert_eq!(
+        join(&["projects", "/work/app", "mcpServers"]),
+        "/projects/~1work~1app/mcpServers"
+    );
+}
+
+#[test]
+fn test_split_last_and_ancestors() {
+    assert_eq!(
+        split_last("/projects/~1work~1app"),
+        Some(("/projects", "/work/app".to_string()))
+    );
+    assert_eq!(split_last(""), None);
+    assert_eq!(ancestors("/a/b/c"), vec!["/a/b", "/a"]);
+    assert!(ancestors("/a").is_empty());
+}
+
+#[test]
+fn test_set_creates_parents() {
+    let mut value = json!({});
+    set(
+        &mut value,
+        "/projects/~1work/mcpServers/fs",
+        json!({ "command": "npx" }),
+    )
+    .unwrap();
+    assert_eq!(
+        value["projects"]["/work"]["mcpServers"]["fs"]["command"],
+        "npx"
+    );
+
+    let mut list = json!({ "args": ["a", "b"] });
+    set(&mut list, "/args/1", json!("c")).unwrap();
+    assert_eq!(list["args"], json!(["a", "c"]));
+    assert!(set(&mut list, "/args/5", json!("d")).is_err());
+    assert!(set(&mut list, "/args/1/x", json!("d")).is_err());
+}
+
+#[test]
+fn test_remove() {
+    let mut value = json!({ "mcpServers": { "fs": {}, "git": {} }, "args": ["a", "b"] });
+    assert_eq!(remove(&mut value, "/mcpServers/fs"), Some(json!({})));
+    assert_eq!(remove(&mut value, "/args/0"), Some(json!("a")));
+    assert_eq!(remove(&mut value, "/args/9"), None);
+    assert_eq!(remove(&mut value, "/missing/key"), None);
+    assert_eq!(value, json!({ "mcpServers": { "git": {} }, "args": ["b"] }));
+}
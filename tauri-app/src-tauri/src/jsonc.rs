@@ -0,0 +1,342 @@
+//! In-place edits of JSON files with comments
+//!
+//! Some clients keep their servers inside a larger settings file the user edits by hand (Zed's
+//! settings.json), which may hold `//` and `/* */` comments and trailing commas. Parsing and
+//! re-serializing such a file would drop the comments and reformat it, so these edits only
+//! replace, insert or cut the text of one member and leave every other byte as it was.
+//! Offsets are found in a copy of the text with comments and trailing commas blanked out by
+//! spaces, which has the same length and parses as plain JSON.
+
+use serde::Serialize;
+use serde_json::ser::PrettyFormatter;
+use serde_json::{json, Map, Value};
+
+/// `text` with comments and trailing commas replaced by spaces; newlines are kept
+pub(crate) fn blank(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = bytes.to_vec();
+    let mut i = 0;
+    while i < bytes.len() {
+        match (bytes[i], bytes.get(i + 1)) {
+            (b'"', _) => i = string_end(bytes, i),
+            (b'/', Some(b'/')) => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    out[i] = b' ';
+                    i += 1;
+                }
+            }
+            (b'/', Some(b'*')) => {
+                let end = text[i + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |offset| i + 2 + offset + 2);
+                for byte in &mut out[i..end] {
+                    if *byte != b'\n' {
+                        *byte = b' ';
+                    }
+                }
+                i = end;
+            }
+            _ => i += 1,
+        }
+    }
+    let mut i = 0;
+    while i < out.len() {
+        match out[i] {
+            b'"' => i = string_end(&out, i),
+            b',' => {
+                if matches!(out.get(skip_ws(&out, i + 1)), Some(b'}' | b']')) {
+                    out[i] = b' ';
+                }
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+    // Only ASCII and whole characters inside comments were replaced
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// The value of a JSON-with-comments text
+pub(crate) fn parse(text: &str) -> Result<Value, String> {
+    serde_json::from_str(&blank(text)).map_err(|e| format!("Failed to parse settings: {}", e))
+}
+
+/// Offset just past the string opening at `start`
+fn string_end(bytes: &[u8], start: usize) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+fn skip_ws(bytes: &[u8], mut i: usize) -> usize {
+    while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+        i += 1;
+    }
+    i
+}
+
+/// Offset just past the value starting at `start`
+fn value_end(bytes: &[u8], start: usize) -> usize {
+    match bytes.get(start) {
+        Some(b'"') => string_end(bytes, start),
+        Some(b'{' | b'[') => {
+            let mut depth = 0;
+            let mut i = start;
+            while i < bytes.len() {
+                match bytes[i] {
+                    b'"' => {
+                        i = string_end(bytes, i);
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return i + 1;
+                        }
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+            bytes.len()
+        }
+        _ => {
+            let mut i = start;
+            while i < bytes.len()
+                && !matches!(bytes[i], b',' | b'}' | b']')
+                && !bytes[i].is_ascii_whitespace()
+            {
+                i += 1;
+            }
+            i
+        }
+    }
+}
+
+struct Member {
+    key: String,
+    key_start: usize,
+    value_start: usize,
+    value_end: usize,
+}
+
+/// Members of the object opening at `open`, and the offset of its closing brace
+fn members(bytes: &[u8], open: usize) -> (Vec<Member>, usize) {
+    let mut members = Vec::new();
+    let mut i = skip_ws(bytes, open + 1);
+    while bytes.get(i) == Some(&b'"') {
+        let key_end = string_end(bytes, i);
+        let key = serde_json::from_slice::<String>(&bytes[i..key_end]).unwrap_or_default();
+        let value_start = skip_ws(bytes, skip_ws(bytes, key_end) + 1);
+        let end = value_end(bytes, value_start);
+        members.push(Member {
+            key,
+            key_start: i,
+            value_start,
+            value_end: end,
+        });
+        i = skip_ws(bytes, end);
+        if bytes.get(i) == Some(&b',') {
+            i = skip_ws(bytes, i + 1);
+        }
+    }
+    (members, i)
+}
+
+/// The object at `path`, or the deepest one on the way: its `{` and how many segments exist
+fn locate(bytes: &[u8], path: &[&str]) -> Result<(usize, usize), String> {
+    let mut open = skip_ws(bytes, 0);
+    for (depth, segment) in path.iter().enumerate() {
+        let (members, _) = members(bytes, open);
+        match members.iter().find(|m| m.key == *segment) {
+            Some(m) if bytes[m.value_start] == b'{' => open = m.value_start,
+            Some(_) => return Err(format!("'{}' in settings is not an object", segment)),
+            None => return Ok((open, depth)),
+        }
+    }
+    Ok((open, path.len()))
+}
+
+fn line_start(bytes: &[u8], i: usize) -> usize {
+    bytes[..i]
+        .iter()
+        .rposition(|b| *b == b'\n')
+        .map_or(0, |newline| newline + 1)
+}
+
+/// Leading whitespace of the line containing `i`
+fn indent_at(text: &str, i: usize) -> &str {
+    let start = line_start(text.as_bytes(), i);
+    let line = &text[start..];
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+/// Indentation step of the file: the leading whitespace of its first indented line
+fn indent_unit(text: &str) -> &str {
+    text.lines()
+        .map(|line| &line[..line.len() - line.trim_start_matches([' ', '\t']).len()])
+        .find(|indent| !indent.is_empty())
+        .unwrap_or("  ")
+}
+
+fn render(value: &Value, indent: &str, unit: &str) -> String {
+    let mut out = Vec::new();
+    let mut serializer = serde_json::Serializer::with_formatter(
+        &mut out,
+        PrettyFormatter::with_indent(unit.as_bytes()),
+    );
+    if value.serialize(&mut serializer).is_err() {
+        return value.to_string();
+    }
+    String::from_utf8_lossy(&out).replace('\n', &format!("\n{}", indent))
+}
+
+fn object(key: &str, value: Value) -> Value {
+    Value::Object(Map::from_iter([(key.to_string(), value)]))
+}
+
+fn checked_object(text: &str) -> Result<String, String> {
+    let blanked = blank(text);
+    match serde_json::from_str::<Value>(&blanked) {
+        Ok(Value::Object(_)) => Ok(blanked),
+        Ok(_) => Err("Settings are not a JSON object".to_string()),
+        Err(e) => Err(format!("Failed to parse settings: {}", e)),
+    }
+}
+
+fn verified(text: String) -> Result<String, String> {
+    checked_object(&text)
+        .map(|_| text)
+        .map_err(|e| format!("Edit would leave invalid settings: {}", e))
+}
+
+/// Set member `key` of the object at `path` to `value`, creating the missing objects on the
+/// way. An existing value is replaced in place; a new member goes after the last one.
+pub(crate) fn set_member(
+    text: &str,
+    path: &[&str],
+    key: &str,
+    value: &Value,
+) -> Result<String, String> {
+    let blanked = checked_object(text)?;
+    let bytes = blanked.as_bytes();
+    let original = text.as_bytes();
+    let unit = indent_unit(text);
+    let (open, found) = locate(bytes, path)?;
+    let (members, close) = members(bytes, open);
+
+    let (key, value) = if found < path.len() {
+        let nested = path[found + 1..]
+            .iter()
+            .rev()
+            .fold(object(key, value.clone()), |inner, segment| {
+                object(segment, inner)
+            });
+        (path[found], nested)
+    } else {
+        if let Some(m) = members.iter().find(|m| m.key == key) {
+            let rendered = render(value, indent_at(text, m.key_start), unit);
+            let mut edited = text.to_string();
+            edited.replace_range(m.value_start..m.value_end, &rendered);
+            return verified(edited);
+        }
+        (key, value.clone())
+    };
+
+    let mut edited = text.to_string();
+    let Some(last) = members.last() else {
+        let outer = indent_at(text, open);
+        let indent = format!("{}{}", outer, unit);
+        let member = format!("{}: {}", json!(key), render(&value, &indent, unit));
+        // Replace whitespace-only insides, but don't drop comments
+        if original[open + 1..close] == bytes[open + 1..close] {
+            edited.replace_range(
+                open + 1..close,
+                &format!("\n{}{}\n{}", indent, member, outer),
+            );
+        } else {
+            edited.insert_str(open + 1, &format!("\n{}{}", indent, member));
+        }
+        return verified(edited);
+    };
+
+    let indent = indent_at(text, last.key_start).to_string();
+    let member = format!("{}: {}", json!(key), render(&value, &indent, unit));
+    let trailing = (last.value_end..close).find(|&j| original[j] == b',' && bytes[j] == b' ');
+    let from = trailing.map_or(last.value_end, |comma| comma + 1);
+    // After a comment ending the last member's line, unless a block comment starts there
+    let newline = (from..close)
+        .find(|&j| original[j] == b'\n')
+        .filter(|&j| !text[from..j].contains("/*"));
+    let suffix = if trailing.is_some() { "," } else { "" };
+    match newline {
+        Some(at) => edited.insert_str(at, &format!("\n{}{}{}", indent, member, suffix)),
+        None => edited.insert_str(from, &format!(" {}{}", member, suffix)),
+    }
+    if trailing.is_none() {
+        edited.insert(last.value_end, ',');
+    }
+    verified(edited)
+}
+
+/// Cut member `key` from the object at `path`, with its separating comma and, when it has
+/// lines of its own, those lines. `None` when there is no such member.
+pub(crate) fn remove_member(
+    text: &str,
+    path: &[&str],
+    key: &str,
+) -> Result<Option<String>, String> {
+    let blanked = checked_object(text)?;
+    let bytes = blanked.as_bytes();
+    let original = text.as_bytes();
+    let (open, found) = locate(bytes, path)?;
+    if found < path.len() {
+        return Ok(None);
+    }
+    let (members, close) = members(bytes, open);
+    let Some(index) = members.iter().position(|m| m.key == key) else {
+        return Ok(None);
+    };
+    let member = &members[index];
+
+    let first = line_start(bytes, member.key_start);
+    let own_line = bytes[first..member.key_start]
+        .iter()
+        .all(u8::is_ascii_whitespace);
+    let start = if own_line { first } else { member.key_start };
+    let mut end = member.value_end;
+    let mut preceding_comma = None;
+    let next = skip_ws(bytes, end);
+    if bytes.get(next) == Some(&b',') {
+        end = next + 1;
+    } else if let Some(comma) = (end..close).find(|&j| original[j] == b',' && bytes[j] == b' ') {
+        end = comma + 1;
+    } else if index > 0 {
+        preceding_comma =
+            (members[index - 1].value_end..member.key_start).find(|&j| bytes[j] == b',');
+    }
+    if own_line {
+        // The rest of the line, a comment on the member included
+        if let Some(newline) = (end..bytes.len()).find(|&j| bytes[j] == b'\n') {
+            if bytes[end..newline].iter().all(u8::is_ascii_whitespace)
+                && !text[end..newline].contains("/*")
+            {
+                end = newline + 1;
+            }
+        }
+    }
+
+    let mut edited = text.to_string();
+    edited.replace_range(start..end, "");
+    if let Some(comma) = preceding_comma {
+        edited.remove(comma);
+    }
+    verified(edited).map(Some)
+}
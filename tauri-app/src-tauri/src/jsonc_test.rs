@@ -0,0 +1,109 @@
+// JSON-with-comments edit tests
+use crate::jsonc::{blank, parse, remove_member, set_member};
+use serde_json::json;
+
+const SETTINGS: &str = r#"// Zed settings
+{
+  "theme": "One Dark", // keep
+  "context_servers": {
+    "a": { "command": "x" }, // first
+    "b": { "command": "y" }
+  },
+  /* trailing */ "vim_mode": true,
+}
+"#;
+
+#[test]
+fn test_blank_keeps_offsets_and_strings() {
+    let text = "{\"url\": \"http://x\", // comment\n}";
+    let blanked = blank(text);
+    assert_eq!(blanked.len(), text.len());
+    assert_eq!(parse(text).unwrap(), json!({ "url": "http://x" }));
+    assert_eq!(
+        parse(SETTINGS).unwrap()["context_servers"]["b"],
+        json!({ "command": "y" })
+    );
+}
+
+#[test]
+fn test_set_member_adds_after_last_member_and_its_comment() {
+    let edited = set_member(
+        SETTINGS,
+        &["context_servers"],
+        "c",
+        &json!({ "command": "z" }),
+    )
+    .unwrap();
+    assert!(edited.contains(
+        "    \"b\": { \"command\": \"y\" },\n    \"c\": {\n      \"command\": \"z\"\n    }\n  },"
+    ));
+    assert!(edited.starts_with("// Zed settings\n{\n  \"theme\": \"One Dark\", // keep\n"));
+    assert!(edited.contains("/* trailing */ \"vim_mode\": true,\n}"));
+}
+
+#[test]
+fn test_set_member_replaces_existing_value_in_place() {
+    let edited = set_member(
+        SETTINGS,
+        &["context_servers"],
+        "a",
+        &json!({ "command": "npx" }),
+    )
+    .unwrap();
+    assert_eq!(
+        parse(&edited).unwrap()["context_servers"]["a"],
+        json!({ "command": "npx" })
+    );
+    assert!(edited.contains("    }, // first\n"));
+    assert_eq!(edited.matches("// keep").count(), 1);
+}
+
+#[test]
+fn test_set_member_creates_missing_section() {
+    let text = "{\n  // UI\n  \"theme\": \"One Dark\",\n}\n";
+    let edited = set_member(
+        text,
+        &["context_servers"],
+        "fs",
+        &json!({ "command": "npx" }),
+    )
+    .unwrap();
+    assert_eq!(
+        edited,
+        "{\n  // UI\n  \"theme\": \"One Dark\",\n  \"context_servers\": {\n    \"fs\": {\n      \
+         \"command\": \"npx\"\n    }\n  },\n}\n"
+    );
+
+    let empty = set_member(
+        "{}\n",
+        &["context_servers"],
+        "fs",
+        &json!({ "command": "npx" }),
+    )
+    .unwrap();
+    assert_eq!(
+        empty,
+        "{\n  \"context_servers\": {\n    \"fs\": {\n      \"command\": \"npx\"\n    }\n  }\n}\n"
+    );
+}
+
+#[test]
+fn test_remove_member_takes_its_lines_and_comma() {
+    let without_b = remove_member(SETTINGS, &["context_servers"], "b")
+        .unwrap()
+        .unwrap();
+    assert!(without_b.contains("    \"a\": { \"command\": \"x\" } // first\n  },"));
+    assert!(!without_b.contains("\"b\""));
+
+    let without_a = remove_member(SETTINGS, &["context_servers"], "a")
+        .unwrap()
+        .unwrap();
+    assert!(without_a.contains("  \"context_servers\": {\n    \"b\": { \"command\": \"y\" }\n  },"));
+    assert!(!without_a.contains("// first"));
+
+    assert_eq!(
+        remove_member(SETTINGS, &["context_servers"], "missing").unwrap(),
+        None
+    );
+    assert!(set_member("[1]", &["context_servers"], "a", &json!({})).is_err());
+}
@@ -9,28 +9,316 @@ use tauri::{AppHandle, Emitter, Manager};
 use sleep::{allow_sleep, prevent_sleep, SleepState};
 
 mod adapter;
+mod advisories;
+mod audit_log;
+mod auth_profiles;
 mod codex_commands;
 mod claude_code_commands;
 mod claude_disabled;
+mod claude_edit;
+mod claude_files;
+mod claude_hooks;
+mod claude_integrity;
+mod claude_memory;
+mod claude_scan;
+mod claude_settings;
+mod claude_size;
+mod claude_watch;
 mod client;
+mod client_target;
 mod cmd;
 mod codex;
 mod config;
+mod config_schema;
+mod config_value;
+mod credential_expiry;
+mod diagnostics;
+mod drift;
 mod dxt;
 mod encryption;
 mod env_path;
 mod filesystem;
 mod git;
 mod installer;
+mod inventory;
 mod json_manager;
+mod json_pointer;
 mod mcp_commands;
 mod mcp_crud;
+mod mcp_processes;
 mod mcp_sync;
+mod metrics;
+mod mutation_hooks;
+mod op_recorder;
+mod package_metadata;
+mod profile;
+mod quarantine;
+mod registry_export;
+mod registry_import;
+mod roundtrip;
+mod sbom;
+mod secrets;
 mod sleep;
+mod server_merge;
+mod server_autostart;
+mod server_metadata;
+mod tool_catalog;
+mod server_inspect;
+mod client_capabilities;
+mod endpoint_latency;
+mod network;
+mod unicode_path;
+mod write_journal;
+mod startup_check;
+mod app_state;
+mod event_bus;
+mod watch_debounce;
+mod ownership_marks;
+mod client_plugins;
+mod transform_plugins;
+mod batch_ops;
+mod server_history;
+mod role_presets;
+mod bootstrap;
+mod platform_access;
+mod claude_cli_verify;
+mod elicitation;
+mod sampling;
+mod test_presets;
+mod server_icons;
+mod server_defaults;
+mod disk_space;
+mod redaction;
+mod server_copy;
+mod temporary_servers;
+mod cursor_commands;
+mod removal_impact;
+mod server_aliases;
+mod vscode_commands;
+mod server_qr;
+mod windsurf_commands;
+mod config_summary;
+mod cline_commands;
+mod server_benchmark;
+mod jsonc;
+mod zed_commands;
+mod server_locks;
+mod continue_config;
+mod claude_location;
+mod yaml_block;
+mod goose_config;
+mod config_backups;
+mod client_reload;
+mod roo_commands;
+mod amazonq_commands;
+mod client_logs;
+mod troubleshoot;
+mod catalog_locale;
+mod log_rotation;
+mod demo_mode;
+mod server_tags;
+mod server_query;
+mod server_list_page;
+mod claude_batch;
+mod server_handshake;
+mod server_name;
+mod server_package;
+mod server_provenance;
+mod server_sandbox;
+mod server_timeouts;
+mod server_trust;
+mod settings;
 mod state;
+mod status_page;
+mod webhooks;
+
+#[cfg(test)]
+mod roundtrip_test;
+#[cfg(test)]
+mod server_name_test;
+#[cfg(test)]
+mod registry_test;
+#[cfg(test)]
+mod config_schema_test;
+#[cfg(test)]
+mod config_value_test;
+#[cfg(test)]
+mod server_merge_test;
+#[cfg(test)]
+mod webhooks_test;
+#[cfg(test)]
+mod drift_test;
+#[cfg(test)]
+mod claude_hooks_test;
+#[cfg(test)]
+mod claude_integrity_test;
+#[cfg(test)]
+mod server_autostart_test;
+#[cfg(test)]
+mod quarantine_test;
+#[cfg(test)]
+mod claude_files_test;
+#[cfg(test)]
+mod status_page_test;
+#[cfg(test)]
+mod profile_test;
+#[cfg(test)]
+mod credential_expiry_test;
+#[cfg(test)]
+mod auth_profiles_test;
+#[cfg(test)]
+mod claude_edit_test;
+#[cfg(test)]
+mod claude_size_test;
+#[cfg(test)]
+mod claude_watch_test;
+#[cfg(test)]
+mod claude_scan_test;
+#[cfg(test)]
+mod claude_memory_test;
+#[cfg(test)]
+mod server_timeouts_test;
+#[cfg(test)]
+mod server_provenance_test;
+#[cfg(test)]
+mod server_package_test;
+#[cfg(test)]
+mod package_metadata_test;
+#[cfg(test)]
+mod inventory_test;
+#[cfg(test)]
+mod op_recorder_test;
+#[cfg(test)]
+mod metrics_test;
+#[cfg(test)]
+mod sbom_test;
+#[cfg(test)]
+mod advisories_test;
+#[cfg(test)]
+mod server_sandbox_test;
+#[cfg(test)]
+mod mcp_processes_test;
+#[cfg(test)]
+mod server_metadata_test;
+#[cfg(test)]
+mod tool_catalog_test;
+#[cfg(test)]
+mod server_inspect_test;
+#[cfg(test)]
+mod client_capabilities_test;
+#[cfg(test)]
+mod endpoint_latency_test;
+#[cfg(test)]
+mod network_test;
+#[cfg(test)]
+mod unicode_path_test;
+#[cfg(test)]
+mod write_journal_test;
+#[cfg(test)]
+mod app_state_test;
+#[cfg(test)]
+mod event_bus_test;
+#[cfg(test)]
+mod watch_debounce_test;
+#[cfg(test)]
+mod ownership_marks_test;
+#[cfg(test)]
+mod client_plugins_test;
+#[cfg(test)]
+mod transform_plugins_test;
+#[cfg(test)]
+mod batch_ops_test;
+#[cfg(test)]
+mod server_history_test;
+#[cfg(test)]
+mod role_presets_test;
+#[cfg(test)]
+mod bootstrap_test;
+#[cfg(test)]
+mod platform_access_test;
+#[cfg(test)]
+mod claude_cli_verify_test;
+#[cfg(test)]
+mod elicitation_test;
+#[cfg(test)]
+mod sampling_test;
+#[cfg(test)]
+mod test_presets_test;
+#[cfg(test)]
+mod server_icons_test;
+#[cfg(test)]
+mod server_defaults_test;
+#[cfg(test)]
+mod disk_space_test;
+#[cfg(test)]
+mod redaction_test;
+#[cfg(test)]
+mod server_copy_test;
+#[cfg(test)]
+mod temporary_servers_test;
+#[cfg(test)]
+mod cursor_commands_test;
+#[cfg(test)]
+mod removal_impact_test;
+#[cfg(test)]
+mod server_aliases_test;
+#[cfg(test)]
+mod vscode_commands_test;
+#[cfg(test)]
+mod server_qr_test;
+#[cfg(test)]
+mod windsurf_commands_test;
+#[cfg(test)]
+mod config_summary_test;
+#[cfg(test)]
+mod cline_commands_test;
+#[cfg(test)]
+mod server_benchmark_test;
+#[cfg(test)]
+mod jsonc_test;
+#[cfg(test)]
+mod json_pointer_test;
+#[cfg(test)]
+mod zed_commands_test;
+#[cfg(test)]
+mod server_locks_test;
+#[cfg(test)]
+mod continue_config_test;
+#[cfg(test)]
+mod claude_location_test;
+#[cfg(test)]
+mod codex_test;
+#[cfg(test)]
+mod goose_config_test;
+#[cfg(test)]
+mod config_backups_test;
+#[cfg(test)]
+mod client_reload_test;
+#[cfg(test)]
+mod roo_commands_test;
+#[cfg(test)]
+mod amazonq_commands_test;
+#[cfg(test)]
+mod client_logs_test;
+#[cfg(test)]
+mod troubleshoot_test;
+#[cfg(test)]
+mod catalog_locale_test;
+#[cfg(test)]
+mod log_rotation_test;
+#[cfg(test)]
+mod claude_code_commands_test;
+#[cfg(test)]
+mod demo_mode_test;
+#[cfg(test)]
+mod server_query_test;
+#[cfg(test)]
+mod server_list_page_test;
+#[cfg(test)]
+mod claude_batch_test;
 
 use codex_commands::CodexState;
-use state::WatchState;
+use state::{ProjectWatchState, WatchState};
 use filesystem::{
     directory_ops::{canonicalize_path, get_default_directories, read_directory, search_files},
     file_analysis::calculate_file_tokens,
@@ -51,6 +339,9 @@ use filesystem::{
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     env_path::update_env_path();
+    demo_mode::init();
+    profile::init();
+    platform_access::restore_grants();
 
     let mut builder = tauri::Builder::default()
         .plugin(tauri_plugin_process::init())
@@ -58,9 +349,12 @@ pub fn run() {
 
     #[cfg(desktop)]
     {
-        builder = builder.plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
-            show_window(app, argv);
-        }));
+        // Profile and demo processes run alongside the default instance
+        if profile::active_profile().is_none() && !demo_mode::is_active() {
+            builder = builder.plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
+                show_window(app, argv);
+            }));
+        }
     }
 
     builder = builder.plugin(tauri_plugin_deep_link::init());
@@ -104,9 +398,219 @@ pub fn run() {
             claude_code_commands::claude_mcp_get,
             claude_code_commands::claude_mcp_add,
             claude_code_commands::claude_mcp_remove,
+            claude_code_commands::claude_mcp_update,
+            server_list_page::claude_mcp_list_page,
+            claude_batch::claude_mcp_batch_apply,
             claude_code_commands::claude_list_projects,
             claude_code_commands::check_claude_cli_available,
             claude_code_commands::check_claude_config_exists,
+            roundtrip::roundtrip_check,
+            claude_scan::detect_clients,
+            diagnostics::get_diagnostics,
+            diagnostics::get_storage_usage,
+            claude_watch::subscribe_projects,
+            claude_watch::unsubscribe_projects,
+            claude_size::analyze_config_size,
+            claude_size::cleanup_config_cache,
+            settings::get_settings,
+            settings::update_settings,
+            server_name::validate_server_name,
+            server_name::claude_name_issues,
+            server_name::claude_mcp_rename,
+            claude_edit::add_server_arg,
+            claude_edit::remove_server_arg,
+            claude_edit::set_env_var,
+            claude_edit::unset_env_var,
+            secrets::list_secrets,
+            secrets::set_secret,
+            secrets::set_secret_expiry,
+            secrets::delete_secret,
+            auth_profiles::list_auth_profiles,
+            auth_profiles::save_auth_profile,
+            auth_profiles::delete_auth_profile,
+            auth_profiles::attach_auth_profile,
+            auth_profiles::detach_auth_profile,
+            auth_profiles::set_auth_profile_expiry,
+            credential_expiry::list_expiring_credentials,
+            registry_import::fetch_registry_servers,
+            registry_import::list_registry_sources,
+            registry_import::save_registry_source,
+            registry_import::remove_registry_source,
+            registry_import::import_registry_servers,
+            registry_export::export_registry_manifest,
+            config_schema::get_config_schema,
+            config_schema::validate_config,
+            config_schema::insert_config_schema_ref,
+            server_merge::three_way_merge,
+            server_merge::apply_merge,
+            config_value::get_config_value,
+            config_value::set_config_value,
+            op_recorder::start_recording,
+            op_recorder::stop_recording,
+            op_recorder::is_recording,
+            op_recorder::replay_script,
+            profile::get_active_profile,
+            demo_mode::get_demo_mode,
+            profile::list_profiles,
+            profile::open_profile_window,
+            status_page::get_status_report,
+            status_page::get_status_server,
+            webhooks::list_webhooks,
+            webhooks::save_webhook,
+            webhooks::delete_webhook,
+            webhooks::test_webhook,
+            drift::approve_drift_baseline,
+            drift::get_drift_schedule,
+            drift::set_drift_schedule,
+            drift::check_drift,
+            drift::get_last_drift_report,
+            claude_hooks::list_claude_hooks,
+            claude_hooks::validate_hook_matcher,
+            claude_hooks::add_claude_hook,
+            claude_hooks::remove_claude_hook,
+            claude_files::list_claude_files,
+            claude_files::list_claude_file_templates,
+            claude_files::create_claude_file,
+            claude_files::copy_claude_file_to_project,
+            claude_memory::list_claude_memory_templates,
+            claude_memory::create_claude_memory,
+            claude_memory::sync_claude_memory,
+            server_timeouts::get_timeout_support,
+            server_timeouts::get_server_timeouts,
+            server_timeouts::set_server_timeouts,
+            server_trust::get_server_trust,
+            server_trust::set_server_trust,
+            audit_log::get_audit_log,
+            server_provenance::scan_server_provenance,
+            server_provenance::set_server_ownership,
+            server_provenance::clear_server_ownership,
+            inventory::generate_inventory,
+            server_query::query,
+            server_tags::list_server_tags,
+            server_tags::set_server_tags,
+            advisories::security_report,
+            advisories::check_server_advisories,
+            quarantine::quarantine_server,
+            quarantine::clear_quarantine,
+            quarantine::list_quarantined,
+            server_handshake::test_server_launch,
+            mcp_processes::list_running_mcp_processes,
+            mcp_processes::kill_process,
+            mcp_processes::get_server_resource_usage,
+            server_autostart::set_server_autostart,
+            server_autostart::get_autostart_status,
+            server_autostart::run_autostart_now,
+            server_metadata::get_server_metadata,
+            server_metadata::refresh_server_metadata,
+            server_metadata::get_protocol_versions,
+            tool_catalog::search_tools,
+            tool_catalog::get_tool_index,
+            tool_catalog::find_duplicate_tools,
+            server_inspect::inspect_server_prompts,
+            server_inspect::inspect_server_resources,
+            server_inspect::call_server_tool,
+            test_presets::get_test_roots,
+            test_presets::set_test_roots,
+            server_icons::get_server_icon,
+            server_icons::list_server_icons,
+            server_icons::refresh_server_icons,
+            server_defaults::suggest_server_defaults,
+            server_defaults::list_server_defaults,
+            server_defaults::clear_server_defaults,
+            server_copy::copy_servers,
+            temporary_servers::add_temporary_server,
+            temporary_servers::list_temporary_servers,
+            temporary_servers::keep_temporary_server,
+            cursor_commands::cursor_mcp_list,
+            cursor_commands::cursor_mcp_add,
+            cursor_commands::cursor_mcp_remove,
+            cursor_commands::cursor_list_projects,
+            removal_impact::analyze_server_removal,
+            removal_impact::clean_up_server_references,
+            server_aliases::list_server_aliases,
+            server_aliases::set_server_alias,
+            vscode_commands::vscode_mcp_list,
+            vscode_commands::vscode_mcp_add,
+            vscode_commands::vscode_mcp_update,
+            vscode_commands::vscode_mcp_remove,
+            server_qr::export_servers_qr,
+            server_qr::import_servers_qr,
+            windsurf_commands::windsurf_mcp_list,
+            windsurf_commands::windsurf_mcp_add,
+            windsurf_commands::windsurf_mcp_update,
+            windsurf_commands::windsurf_mcp_remove,
+            config_summary::describe_config,
+            cline_commands::cline_mcp_list,
+            cline_commands::cline_mcp_add,
+            cline_commands::cline_mcp_update,
+            cline_commands::cline_mcp_remove,
+            server_benchmark::benchmark_server_variants,
+            zed_commands::zed_mcp_list,
+            zed_commands::zed_mcp_add,
+            zed_commands::zed_mcp_remove,
+            server_locks::lock_server,
+            server_locks::unlock_server,
+            server_locks::list_locked_servers,
+            continue_config::read_continue_servers,
+            claude_location::list_claude_config_locations,
+            claude_location::set_claude_config_location,
+            claude_location::explain_config_resolution,
+            goose_config::read_goose_servers,
+            config_backups::list_config_backups,
+            config_backups::restore_config_backup,
+            client_reload::get_reload_hints,
+            client_reload::reload_client,
+            client_reload::dismiss_reload_hint,
+            roo_commands::roo_mcp_list,
+            roo_commands::roo_mcp_add,
+            roo_commands::roo_mcp_update,
+            roo_commands::roo_mcp_remove,
+            amazonq_commands::amazonq_mcp_list,
+            amazonq_commands::amazonq_mcp_add,
+            amazonq_commands::amazonq_mcp_remove,
+            amazonq_commands::amazonq_list_projects,
+            client_logs::tail_client_logs,
+            client_logs::stop_client_logs,
+            client_logs::diagnose_client_logs,
+            troubleshoot::troubleshoot_server,
+            client_capabilities::check_client_compatibility,
+            endpoint_latency::benchmark_endpoints,
+            write_journal::get_interrupted_operations,
+            write_journal::resolve_interrupted_operation,
+            startup_check::get_startup_report,
+            startup_check::run_startup_check,
+            app_state::export_app_state,
+            app_state::import_app_state,
+            event_bus::subscribe_events,
+            event_bus::unsubscribe_events,
+            ownership_marks::list_foreign_modifications,
+            ownership_marks::accept_foreign_modification,
+            client_plugins::list_client_plugins,
+            client_plugins::reload_client_plugins,
+            client_plugins::register_custom_client,
+            client_plugins::remove_custom_client,
+            transform_plugins::list_transform_plugins,
+            transform_plugins::run_transform_plugin,
+            batch_ops::execute_batch,
+            batch_ops::list_batch_undo,
+            batch_ops::undo_batch,
+            server_history::server_history,
+            server_history::restore_server_version,
+            role_presets::list_role_presets,
+            role_presets::save_server_profile,
+            role_presets::save_role_preset,
+            role_presets::delete_role_preset,
+            role_presets::get_role_signing_key,
+            role_presets::export_role,
+            role_presets::import_role_bundle,
+            role_presets::apply_role,
+            bootstrap::bootstrap_check,
+            bootstrap::bootstrap_install,
+            platform_access::get_platform_access,
+            platform_access::grant_folder_access,
+            platform_access::revoke_folder_access,
+            claude_cli_verify::verify_claude_server,
+            elicitation::respond_elicitation,
             claude_disabled::claude_list_disabled,
             claude_disabled::claude_disable_server,
             claude_disabled::claude_enable_server,
@@ -172,6 +676,7 @@ pub fn run() {
         .manage(CodexState::new())
         .manage(SleepState::default())
         .manage(WatchState::new())
+        .manage(ProjectWatchState::new())
         .setup(|app| {
             #[cfg(any(windows, target_os = "linux"))]
             {
@@ -184,6 +689,33 @@ pub fn run() {
                 app.handle().clone(),
                 codex_state.client_state.clone(),
             );
+            claude_location::spawn_location_migration();
+            startup_check::spawn_startup_check(app.handle().clone());
+            webhooks::spawn_webhook_worker();
+            credential_expiry::spawn_expiry_checker(app.handle().clone());
+            registry_import::spawn_registry_refresh(app.handle().clone());
+            drift::spawn_drift_checker(app.handle().clone());
+            ownership_marks::spawn_foreign_check(app.handle().clone());
+            server_locks::spawn_lock_watch(app.handle().clone());
+            status_page::spawn_status_page();
+            audit_log::spawn_retention_watch();
+            server_autostart::spawn_autostart();
+            temporary_servers::spawn_temporary_cleanup(app.handle().clone());
+
+            if demo_mode::is_active() {
+                for window in app.webview_windows().values() {
+                    if let Ok(title) = window.title() {
+                        let _ = window.set_title(&format!("{} [Demo]", title));
+                    }
+                }
+            }
+            if let Some(name) = profile::active_profile() {
+                for window in app.webview_windows().values() {
+                    if let Ok(title) = window.title() {
+                        let _ = window.set_title(&format!("{} [{}]", title, name));
+                    }
+                }
+            }
 
             Ok(())
         })
@@ -0,0 +1,163 @@
+//! Size-based rotation of the app's own logs
+//!
+//! Append-only logs in the app data dir are rotated before the write that would take them past
+//! `max_file_kb`: audit.jsonl becomes audit.1.jsonl, the previous audit.1.jsonl becomes
+//! audit.2.jsonl and so on, dropping files past `max_rotated_files`. After a rotation the oldest
+//! rotated files of every log are deleted until all logs together fit in `max_total_kb`; live
+//! files are never deleted. Readers go through `files_oldest_first`, so history stays readable
+//! up to the caps.
+
+use crate::settings::LogRetentionSettings;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// File names of the rotated logs, in the app data dir
+pub(crate) const LOGS: &[&str] = &["audit.jsonl"];
+
+/// Stem and extension of a log file name, "audit.jsonl" as ("audit", ".jsonl")
+fn split_name(name: &str) -> (&str, &str) {
+    match name.rfind('.') {
+        Some(dot) if dot > 0 => (&name[..dot], &name[dot..]),
+        _ => (name, ""),
+    }
+}
+
+/// The `index`-th rotation of `path`, 1 being the most recent
+pub(crate) fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let (stem, extension) = split_name(&name);
+    path.with_file_name(format!("{}.{}{}", stem, index, extension))
+}
+
+/// The rotation index of `file_name` when it is a rotation of the log `log`
+pub(crate) fn rotation_index(log: &str, file_name: &str) -> Option<usize> {
+    let (stem, extension) = split_name(log);
+    file_name
+        .strip_prefix(stem)?
+        .strip_prefix('.')?
+        .strip_suffix(extension)?
+        .parse()
+        .ok()
+        .filter(|index| *index > 0)
+}
+
+/// Rotations of `path` on disk and their sizes, by index
+fn rotations(path: &Path) -> Vec<(usize, PathBuf, u64)> {
+    let (Some(dir), Some(log)) = (path.parent(), path.file_name()) else {
+        return Vec::new();
+    };
+    let log = log.to_string_lossy();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut found: Vec<(usize, PathBuf, u64)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let index = rotation_index(&log, &entry.file_name().to_string_lossy())?;
+            let size = entry.metadata().ok()?.len();
+            Some((index, entry.path(), size))
+        })
+        .collect();
+    found.sort_by_key(|(index, _, _)| *index);
+    found
+}
+
+/// The rotations of `path`, oldest first, then `path` itself when it exists
+pub(crate) fn files_oldest_first(path: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = rotations(path)
+        .into_iter()
+        .rev()
+        .map(|(_, file, _)| file)
+        .collect();
+    if path.exists() {
+        files.push(path.to_path_buf());
+    }
+    files
+}
+
+/// Rotated files to delete so that `live` bytes plus the rest of `rotated` fit in `cap`.
+/// `rotated` holds (index, path, size); the highest indexes are the oldest and go first.
+pub(crate) fn over_cap(live: u64, rotated: &[(usize, PathBuf, u64)], cap: u64) -> Vec<PathBuf> {
+    let mut total = live + rotated.iter().map(|(_, _, size)| size).sum::<u64>();
+    let mut oldest_first: Vec<&(usize, PathBuf, u64)> = rotated.iter().collect();
+    oldest_first.sort_by(|a, b| b.0.cmp(&a.0));
+    let mut delete = Vec::new();
+    for (_, path, size) in oldest_first {
+        if total <= cap {
+            break;
+        }
+        total -= size;
+        delete.push(path.clone());
+    }
+    delete
+}
+
+/// Rotate `path` when appending `incoming` bytes would take it past the cap. True when it was
+/// rotated.
+pub(crate) fn rotate_if_needed(
+    path: &Path,
+    incoming: u64,
+    retention: &LogRetentionSettings,
+) -> io::Result<bool> {
+    let size = match fs::metadata(path) {
+        Ok(metadata) => metadata.len(),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e),
+    };
+    if retention.max_file_kb == 0 || size == 0 || size + incoming <= retention.max_file_kb * 1024 {
+        return Ok(false);
+    }
+    for (index, file, _) in rotations(path).into_iter().rev() {
+        if index >= retention.max_rotated_files {
+            fs::remove_file(&file)?;
+        } else {
+            fs::rename(&file, rotated_path(path, index + 1))?;
+        }
+    }
+    if retention.max_rotated_files == 0 {
+        fs::remove_file(path)?;
+    } else {
+        fs::rename(path, rotated_path(path, 1))?;
+    }
+    if let Some(dir) = path.parent() {
+        enforce_total(dir, retention);
+    }
+    Ok(true)
+}
+
+/// Delete the rotations of `path` past `max_rotated_files`, then enforce `max_total_kb` as after
+/// a rotation; for caps lowered after the files were written
+pub(crate) fn apply_caps(path: &Path, retention: &LogRetentionSettings) -> io::Result<()> {
+    for (index, file, _) in rotations(path) {
+        if index > retention.max_rotated_files {
+            fs::remove_file(&file)?;
+        }
+    }
+    if let Some(dir) = path.parent() {
+        enforce_total(dir, retention);
+    }
+    Ok(())
+}
+
+/// Delete the oldest rotated files of the logs in `dir` until they fit in `max_total_kb`
+fn enforce_total(dir: &Path, retention: &LogRetentionSettings) {
+    if retention.max_total_kb == 0 {
+        return;
+    }
+    let mut live = 0;
+    let mut rotated = Vec::new();
+    for log in LOGS {
+        let path = dir.join(log);
+        live += fs::metadata(&path).map_or(0, |metadata| metadata.len());
+        rotated.extend(rotations(&path));
+    }
+    for file in over_cap(live, &rotated, retention.max_total_kb * 1024) {
+        if let Err(e) = fs::remove_file(&file) {
+            println!("[Logs] Failed to delete {}: {}", file.display(), e);
+        }
+    }
+}
@@ -0,0 +1,86 @@
+// Log rotation tests
+use crate::diagnostics::subsystem_of;
+use crate::log_rotation::{
+    apply_caps, files_oldest_first, over_cap, rotate_if_needed, rotation_index,
+};
+use crate::settings::LogRetentionSettings;
+use std::fs;
+use std::path::PathBuf;
+
+#[test]
+fn test_rotation_names() {
+    assert_eq!(rotation_index("audit.jsonl", "audit.3.jsonl"), Some(3));
+    assert_eq!(rotation_index("audit.jsonl", "audit.jsonl"), None);
+    assert_eq!(rotation_index("audit.jsonl", "audit.0.jsonl"), None);
+    assert_eq!(rotation_index("audit.jsonl", "audit.x.jsonl"), None);
+    assert_eq!(subsystem_of("audit.2.jsonl"), "audit_log");
+    assert_eq!(subsystem_of("icons"), "icons");
+    assert_eq!(subsystem_of("unknown.json"), "other");
+}
+
+#[test]
+fn test_over_cap_drops_oldest_rotations() {
+    let rotated = vec![
+        (1, PathBuf::from("a.1"), 100),
+        (3, PathBuf::from("a.3"), 100),
+        (2, PathBuf::from("a.2"), 100),
+    ];
+    assert_eq!(
+        over_cap(100, &rotated, 250),
+        vec![PathBuf::from("a.3"), PathBuf::from("a.2")]
+    );
+    assert!(over_cap(100, &rotated, 400).is_empty());
+}
+
+#[test]
+fn test_rotate_shifts_and_keeps_the_newest() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = dir.path().join("audit.jsonl");
+    let retention = LogRetentionSettings {
+        max_file_kb: 1,
+        max_rotated_files: 2,
+        max_total_kb: 0,
+    };
+    for round in 0..4 {
+        fs::write(&log, format!("{}\n", round).repeat(600)).unwrap();
+        assert!(rotate_if_needed(&log, 10, &retention).unwrap());
+    }
+    fs::write(&log, "live\n").unwrap();
+    assert!(!rotate_if_needed(&log, 10, &retention).unwrap());
+
+    let files = files_oldest_first(&log);
+    assert_eq!(
+        files,
+        vec![
+            dir.path().join("audit.2.jsonl"),
+            dir.path().join("audit.1.jsonl"),
+            log.clone(),
+        ]
+    );
+    assert!(fs::read_to_string(&files[0]).unwrap().starts_with("2\n"));
+    assert!(fs::read_to_string(&files[1]).unwrap().starts_with("3\n"));
+}
+
+#[test]
+fn test_lowered_caps_trim_existing_rotations() {
+    let dir = tempfile::tempdir().unwrap();
+    let log = dir.path().join("audit.jsonl");
+    fs::write(&log, "live\n").unwrap();
+    for index in 1..=4 {
+        fs::write(dir.path().join(format!("audit.{}.jsonl", index)), "old\n").unwrap();
+    }
+    let retention = LogRetentionSettings {
+        max_file_kb: 1024,
+        max_rotated_files: 2,
+        max_total_kb: 0,
+    };
+    apply_caps(&log, &retention).unwrap();
+    assert_eq!(
+        files_oldest_first(&log),
+        vec![
+            dir.path().join("audit.2.jsonl"),
+            dir.path().join("audit.1.jsonl"),
+            log.clone(),
+        ]
+    );
+}
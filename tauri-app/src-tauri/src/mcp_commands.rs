@@ -1,4 +1,7 @@
 use crate::adapter::ClientAdapter;
+use crate::client_target::ClientTarget;
+use crate::op_recorder::{self, RecordedOp};
+use crate::quarantine;
 use serde_json::Value;
 
 #[tauri::command]
@@ -8,7 +11,12 @@ pub async fn disable_mcp_server(
     server_name: String,
 ) -> Result<Value, String> {
     let adapter = ClientAdapter::new(&client_name, path.as_deref());
-    adapter.disable(server_name).await
+    let result = adapter.disable(server_name.clone()).await?;
+    op_recorder::record(
+        ClientTarget::client(&client_name, path.as_deref()),
+        RecordedOp::DisableServer { name: server_name },
+    );
+    Ok(result)
 }
 
 #[tauri::command]
@@ -17,8 +25,18 @@ pub async fn enable_mcp_server(
     path: Option<String>,
     server_name: String,
 ) -> Result<Value, String> {
+    quarantine::ensure_not_quarantined(
+        &ClientTarget::client(&client_name, path.as_deref()),
+        &server_name,
+        "enabling",
+    )?;
     let adapter = ClientAdapter::new(&client_name, path.as_deref());
-    adapter.enable(server_name).await
+    let result = adapter.enable(server_name.clone()).await?;
+    op_recorder::record(
+        ClientTarget::client(&client_name, path.as_deref()),
+        RecordedOp::EnableServer { name: server_name },
+    );
+    Ok(result)
 }
 
 #[tauri::command]
@@ -1,8 +1,15 @@
 use crate::adapter::ClientAdapter;
+use crate::client_target::ClientTarget;
+use crate::op_recorder::{self, RecordedOp};
+use crate::{redaction, server_metadata};
 use serde_json::Value;
+use tauri::{AppHandle, Runtime};
 
 pub(crate) fn normalize_codex_config(mut server_config: Value) -> Result<Value, String> {
-    println!("[Codex] normalize input: {}", server_config);
+    println!(
+        "[Codex] normalize input: {}",
+        redaction::redact_config(&server_config)
+    );
     // Ensure a "type" discriminator exists for serde(tag="type") enum
     if !server_config.get("type").and_then(|v| v.as_str()).is_some() {
         if server_config.get("command").is_some() {
@@ -72,19 +79,40 @@ pub(crate) fn normalize_codex_config(mut server_config: Value) -> Result<Value,
         }
     }
 
-    println!("[Codex] normalize output: {}", server_config);
+    println!(
+        "[Codex] normalize output: {}",
+        redaction::redact_config(&server_config)
+    );
     Ok(server_config)
 }
 
 #[tauri::command]
-pub async fn add_mcp_server(
+pub async fn add_mcp_server<R: Runtime>(
+    app: AppHandle<R>,
     client_name: String,
     path: Option<String>,
     server_name: String,
     server_config: Value,
 ) -> Result<Value, String> {
     let adapter = ClientAdapter::new(&client_name, path.as_deref());
-    adapter.add(server_name, server_config).await
+    let result = adapter
+        .add(server_name.clone(), server_config.clone())
+        .await?;
+    let target = ClientTarget::client(&client_name, path.as_deref());
+    server_metadata::spawn_warm_up(
+        &app,
+        target.clone(),
+        server_name.clone(),
+        server_config.clone(),
+    );
+    op_recorder::record(
+        target,
+        RecordedOp::UpsertServer {
+            name: server_name,
+            config: server_config,
+        },
+    );
+    Ok(result)
 }
 
 #[tauri::command]
@@ -94,7 +122,12 @@ pub async fn remove_mcp_server(
     server_name: String,
 ) -> Result<Value, String> {
     let adapter = ClientAdapter::new(&client_name, path.as_deref());
-    adapter.remove(server_name).await
+    let result = adapter.remove(server_name.clone()).await?;
+    op_recorder::record(
+        ClientTarget::client(&client_name, path.as_deref()),
+        RecordedOp::RemoveServer { name: server_name },
+    );
+    Ok(result)
 }
 
 #[tauri::command]
@@ -105,7 +138,17 @@ pub async fn update_mcp_server(
     server_config: Value,
 ) -> Result<Value, String> {
     let adapter = ClientAdapter::new(&client_name, path.as_deref());
-    adapter.update(server_name, server_config).await
+    let result = adapter
+        .update(server_name.clone(), server_config.clone())
+        .await?;
+    op_recorder::record(
+        ClientTarget::client(&client_name, path.as_deref()),
+        RecordedOp::UpsertServer {
+            name: server_name,
+            config: server_config,
+        },
+    );
+    Ok(result)
 }
 
 #[tauri::command]
@@ -115,5 +158,12 @@ pub async fn batch_delete_mcp_servers(
     server_names: Vec<String>,
 ) -> Result<Value, String> {
     let adapter = ClientAdapter::new(&client_name, path.as_deref());
-    adapter.batch_delete(server_names).await
+    let result = adapter.batch_delete(server_names.clone()).await?;
+    for name in server_names {
+        op_recorder::record(
+            ClientTarget::client(&client_name, path.as_deref()),
+            RecordedOp::RemoveServer { name },
+        );
+    }
+    Ok(result)
 }
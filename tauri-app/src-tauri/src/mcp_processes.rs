@@ -0,0 +1,393 @@
+//! Running MCP server processes, including ones left behind by crashed clients
+//!
+//! A process counts as an MCP server when its command line mentions the package or script of
+//! a configured stdio server, or when its program, or the script or package an interpreter or
+//! launcher runs, is named like one (`mcp-server-*`, `*-mcp`). It is orphaned when
+//! its parent is gone or it was re-parented to init. Only listed processes can be killed, and
+//! the kill takes the pid together with the start time from the listing, so a pid reused since
+//! the user confirmed is never hit.
+//!
+//! Resource usage is reported per process tree, so a server started through `npx` counts the
+//! wrapper and the node process it runs together.
+
+use crate::client_target;
+use crate::server_package;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+use tauri::command;
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct MatchedServer {
+    pub client: String,
+    pub scope: String,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct McpProcess {
+    pub pid: u32,
+    pub parent_pid: Option<u32>,
+    pub name: String,
+    pub command_line: String,
+    /// Seconds since the unix epoch; pass back to `kill_process`
+    pub started_at: u64,
+    /// Configured server the process runs, when it could be told
+    pub server: Option<MatchedServer>,
+    pub orphaned: bool,
+}
+
+/// Programs whose script argument, not their name, identifies the server
+const INTERPRETERS: [&str; 11] = [
+    "node",
+    "python",
+    "python3",
+    "deno",
+    "bun",
+    "uv",
+    "sh",
+    "bash",
+    "cmd",
+    "powershell",
+    "pwsh",
+];
+
+/// Programs that run a package; without one they say nothing about the server
+const LAUNCHERS: [&str; 9] = [
+    "npx", "bunx", "pnpm", "yarn", "npm", "uvx", "pipx", "docker", "podman",
+];
+
+/// Command-line fragments that identify a configured server's process. Empty when nothing
+/// specific to the server is known, e.g. for a bare `npx` or `docker`.
+pub(crate) fn server_tokens(config: &Value) -> Vec<String> {
+    if let Some(package) = server_package::package_ref(config) {
+        // npx runs the package's bin from node_modules, so also match the unscoped name
+        let mut tokens = vec![package.name.clone()];
+        if let Some((_, bare)) = package.name.rsplit_once('/') {
+            tokens.push(bare.to_string());
+        }
+        return tokens;
+    }
+    let Some(command) = config["command"].as_str() else {
+        return Vec::new();
+    };
+    let program = server_package::program_name(command);
+    // Interpreters are too common on their own; their script identifies the server
+    let script = config["args"].as_array().and_then(|args| {
+        args.iter()
+            .filter_map(|a| a.as_str())
+            .find(|a| !a.starts_with('-'))
+    });
+    if INTERPRETERS.contains(&program) {
+        script
+            .map(|script| vec![script.to_string()])
+            .unwrap_or_default()
+    } else if LAUNCHERS.contains(&program) {
+        Vec::new()
+    } else {
+        vec![command.to_string()]
+    }
+}
+
+/// What names the process: its program, or the script or package an interpreter or launcher
+/// runs. The other arguments don't, so an editor opening `mcp-server.ts` isn't a server.
+fn identifying_name(argv: &[String]) -> Option<String> {
+    let (first, rest) = argv.split_first()?;
+    let lower = first.to_lowercase();
+    let program = server_package::program_name(&lower);
+    if LAUNCHERS.contains(&program) {
+        let entry = serde_json::json!({ "command": program, "args": rest });
+        return server_package::package_ref(&entry).map(|package| package.name);
+    }
+    if !INTERPRETERS.contains(&program) {
+        return Some(program.to_string());
+    }
+    let mut args = rest.iter();
+    while let Some(arg) = args.next() {
+        match arg.to_lowercase().as_str() {
+            // A shell running a command line names what that runs
+            "-c" | "/c" | "-command" => {
+                let inner: Vec<String> = args
+                    .flat_map(|a| a.split_whitespace())
+                    .map(|a| a.to_string())
+                    .collect();
+                return identifying_name(&inner);
+            }
+            "-m" => return args.next().cloned(),
+            "run" if program == "uv" => {
+                return identifying_name(&args.cloned().collect::<Vec<_>>())
+            }
+            "tool" if program == "uv" => {}
+            flag if flag.starts_with('-') => {}
+            _ => return Some(arg.clone()),
+        }
+    }
+    None
+}
+
+/// Whether a process looks like an MCP server without matching a configured one
+pub(crate) fn looks_like_mcp_server(argv: &[String]) -> bool {
+    identifying_name(argv).is_some_and(|name| {
+        let name = name.to_lowercase();
+        [
+            "mcp-server",
+            "mcp_server",
+            "server-mcp",
+            "mcp-remote",
+            "-mcp/",
+        ]
+        .iter()
+        .any(|pattern| name.contains(pattern))
+            || server_package::program_name(&name).ends_with("-mcp")
+    })
+}
+
+pub(crate) fn match_server(
+    command_line: &str,
+    servers: &[(MatchedServer, Vec<String>)],
+) -> Option<MatchedServer> {
+    servers
+        .iter()
+        .find(|(_, tokens)| tokens.iter().any(|t| command_line.contains(t.as_str())))
+        .map(|(server, _)| server.clone())
+}
+
+async fn configured_servers() -> Vec<(MatchedServer, Vec<String>)> {
+    let mut servers = Vec::new();
+    for target in client_target::known_targets().await {
+        let Ok(configs) = client_target::list_servers(&target).await else {
+            continue;
+        };
+        let scope = match &target.working_dir {
+            Some(dir) => dir.clone(),
+            None => target.path.clone().unwrap_or_default(),
+        };
+        for (name, config) in configs {
+            let tokens = server_tokens(&config);
+            if tokens.is_empty() {
+                continue;
+            }
+            servers.push((
+                MatchedServer {
+                    client: target.client.clone(),
+                    scope: scope.clone(),
+                    name,
+                },
+                tokens,
+            ));
+        }
+    }
+    servers
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ProcessUsage {
+    pub pid: u32,
+    pub name: String,
+    pub server: Option<MatchedServer>,
+    pub orphaned: bool,
+    /// CPU of the process tree in percent of one core
+    pub cpu_percent: f32,
+    /// Resident memory of the process tree
+    pub memory_bytes: u64,
+    pub uptime_secs: u64,
+    /// Processes in the tree
+    pub processes: usize,
+}
+
+fn refresh(system: &mut System) {
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::nothing()
+            .with_cmd(UpdateKind::OnlyIfNotSet)
+            .with_memory()
+            .with_cpu(),
+    );
+}
+
+pub(crate) fn process_system() -> System {
+    let mut system = System::new();
+    refresh(&mut system);
+    system
+}
+
+fn children_of(system: &System) -> HashMap<u32, Vec<u32>> {
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    for (child, p) in system.processes() {
+        if let Some(parent) = p.parent() {
+            children
+                .entry(parent.as_u32())
+                .or_default()
+                .push(child.as_u32());
+        }
+    }
+    children
+}
+
+/// `pid` followed by all of its descendants, breadth first. Each pid appears once, even when
+/// a reused pid makes the parent links loop.
+pub(crate) fn process_tree(pid: u32, children: &HashMap<u32, Vec<u32>>) -> Vec<u32> {
+    let mut tree = vec![pid];
+    let mut seen = HashSet::from([pid]);
+    let mut i = 0;
+    while i < tree.len() {
+        if let Some(kids) = children.get(&tree[i]) {
+            for kid in kids {
+                if seen.insert(*kid) {
+                    tree.push(*kid);
+                }
+            }
+        }
+        i += 1;
+    }
+    tree
+}
+
+/// Listed processes whose parent isn't listed; the others are counted in their parent's tree.
+/// A listed "parent" that started after the process is a reused pid, not its parent.
+pub(crate) fn tree_roots(listed: Vec<McpProcess>) -> Vec<McpProcess> {
+    let started: HashMap<u32, u64> = listed.iter().map(|p| (p.pid, p.started_at)).collect();
+    listed
+        .into_iter()
+        .filter(|p| {
+            !p.parent_pid
+                .and_then(|parent| started.get(&parent))
+                .is_some_and(|parent_started| *parent_started <= p.started_at)
+        })
+        .collect()
+}
+
+/// CPU, memory and uptime of each MCP server process tree. CPU needs two samples, so this
+/// takes a moment.
+pub(crate) async fn resource_usage() -> Vec<ProcessUsage> {
+    let mut system = process_system();
+    tokio::time::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL).await;
+    refresh(&mut system);
+    let listed = running_mcp_processes(&system).await;
+    let children = children_of(&system);
+    tree_roots(listed)
+        .into_iter()
+        .map(|p| {
+            let tree = process_tree(p.pid, &children);
+            let members: Vec<&sysinfo::Process> = tree
+                .iter()
+                .filter_map(|pid| system.process(Pid::from_u32(*pid)))
+                .collect();
+            ProcessUsage {
+                cpu_percent: members.iter().map(|m| m.cpu_usage()).sum(),
+                memory_bytes: members.iter().map(|m| m.memory()).sum(),
+                uptime_secs: members.first().map(|m| m.run_time()).unwrap_or_default(),
+                processes: members.len(),
+                pid: p.pid,
+                name: p.name,
+                server: p.server,
+                orphaned: p.orphaned,
+            }
+        })
+        .collect()
+}
+
+fn argv(process: &sysinfo::Process) -> Vec<String> {
+    process
+        .cmd()
+        .iter()
+        .map(|arg| arg.to_string_lossy().to_string())
+        .collect()
+}
+
+fn command_line(process: &sysinfo::Process) -> String {
+    argv(process).join(" ")
+}
+
+/// MCP server processes among everything running on the machine
+pub(crate) async fn running_mcp_processes(system: &System) -> Vec<McpProcess> {
+    let servers = configured_servers().await;
+    let own_pid = std::process::id();
+    let mut found: Vec<McpProcess> = system
+        .processes()
+        .iter()
+        .filter(|(pid, _)| pid.as_u32() != own_pid)
+        .filter_map(|(pid, process)| {
+            let argv = argv(process);
+            let command_line = argv.join(" ");
+            if command_line.is_empty() {
+                return None;
+            }
+            let server = match_server(&command_line, &servers);
+            if server.is_none() && !looks_like_mcp_server(&argv) {
+                return None;
+            }
+            let parent_pid = process.parent().map(|p| p.as_u32());
+            let orphaned = match process.parent() {
+                None => true,
+                Some(parent) => parent.as_u32() == 1 || system.process(parent).is_none(),
+            };
+            Some(McpProcess {
+                pid: pid.as_u32(),
+                parent_pid,
+                name: process.name().to_string_lossy().to_string(),
+                command_line,
+                started_at: process.start_time(),
+                server,
+                orphaned,
+            })
+        })
+        .collect();
+    found.sort_by_key(|p| p.pid);
+    found
+}
+
+/// Resource usage of running MCP servers, heaviest first
+#[command]
+pub async fn get_server_resource_usage() -> Result<Vec<ProcessUsage>, String> {
+    let mut usage = resource_usage().await;
+    usage.sort_by(|a, b| b.cpu_percent.total_cmp(&a.cpu_percent));
+    Ok(usage)
+}
+
+#[command]
+pub async fn list_running_mcp_processes() -> Result<Vec<McpProcess>, String> {
+    let system = process_system();
+    Ok(running_mcp_processes(&system).await)
+}
+
+/// Kill a listed process and its children. `started_at` is the value from the listing; the
+/// kill is refused when the pid now belongs to a different process or isn't an MCP server.
+#[command]
+pub async fn kill_process(pid: u32, started_at: u64) -> Result<bool, String> {
+    let system = process_system();
+    if !running_mcp_processes(&system)
+        .await
+        .iter()
+        .any(|listed| listed.pid == pid)
+    {
+        return Err(format!("Process {} is not a running MCP server", pid));
+    }
+    let process = system
+        .process(Pid::from_u32(pid))
+        .ok_or_else(|| format!("Process {} is no longer running", pid))?;
+    if process.start_time() != started_at {
+        return Err(format!(
+            "Process {} has been replaced by another process since it was listed",
+            pid
+        ));
+    }
+
+    // Children first, so npx/uvx wrappers don't respawn or orphan them
+    let tree = process_tree(pid, &children_of(&system));
+    for descendant in tree.iter().skip(1).rev() {
+        if let Some(p) = system.process(Pid::from_u32(*descendant)) {
+            p.kill();
+        }
+    }
+    let killed = process.kill();
+    println!(
+        "[Processes] Killed {} ({}): {}",
+        pid,
+        command_line(process),
+        killed
+    );
+    Ok(killed)
+}
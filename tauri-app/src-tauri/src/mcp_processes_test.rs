@@ -0,0 +1,84 @@
+// MCP process matching tests
+use crate::mcp_processes::{
+    looks_like_mcp_server, match_server, process_tree, server_tokens, tree_roots, MatchedServer,
+    McpProcess,
+};
+use serde_json::json;
+use std::collections::HashMap;
+
+fn process(pid: u32, parent_pid: Option<u32>, started_at: u64) -> McpProcess {
+    McpProcess {
+        pid,
+        parent_pid,
+        name: "node".to_string(),
+        command_line: "node server.js".to_string(),
+        started_at,
+        server: None,
+        orphaned: false,
+    }
+}
+
+#[test]
+fn test_server_tokens() {
+    let npx = json!({ "command": "npx", "args": ["-y", "@modelcontextprotocol/server-github"] });
+    assert_eq!(
+        server_tokens(&npx),
+        vec!["@modelcontextprotocol/server-github", "server-github"]
+    );
+    let script = json!({ "command": "node", "args": ["--inspect", "/srv/mcp/index.js"] });
+    assert_eq!(server_tokens(&script), vec!["/srv/mcp/index.js"]);
+    assert!(server_tokens(&json!({ "command": "python" })).is_empty());
+    assert!(server_tokens(&json!({ "url": "https://example.com/mcp" })).is_empty());
+    // Launchers without a package would match every npx or docker process
+    assert!(server_tokens(&json!({ "command": "npx" })).is_empty());
+    assert!(server_tokens(&json!({ "command": "docker", "args": ["compose", "up"] })).is_empty());
+    assert!(server_tokens(&json!({ "command": "uvx", "args": ["--help"] })).is_empty());
+    assert_eq!(
+        server_tokens(&json!({ "command": "/opt/bin/github-mcp-server" })),
+        vec!["/opt/bin/github-mcp-server"]
+    );
+}
+
+#[test]
+fn test_match_server() {
+    let github = MatchedServer {
+        client: "cursor".to_string(),
+        scope: String::new(),
+        name: "github".to_string(),
+    };
+    let servers = vec![(
+        github.clone(),
+        server_tokens(
+            &json!({ "command": "npx", "args": ["-y", "@modelcontextprotocol/server-github"] }),
+        ),
+    )];
+    // The node process npx starts runs the package's bin
+    let child = "node /home/me/.npm/_npx/3f2a/node_modules/.bin/server-github";
+    assert_eq!(match_server(child, &servers), Some(github));
+    assert_eq!(match_server("node server.js", &servers), None);
+}
+
+/// `looks_like_mcp_server` for a command line without quoted args
+fn looks(command_line: &str) -> bool {
+    let argv: Vec<String> = command_line.split_whitespace().map(String::from).collect();
+    looks_like_mcp_server(&argv)
+}
+
+#[test]
+fn test_looks_like_mcp_server() {
+    assert!(looks("uvx mcp-server-fetch"));
+    assert!(looks("/usr/local/bin/tavily-mcp"));
+    assert!(looks("npx -y tavily-mcp@latest"));
+    assert!(looks("node --inspect /srv/mcp-server-notes/index.js"));
+    assert!(looks("python3 -m mcp_server_time"));
+    assert!(looks("uv run mcp-server-git"));
+    assert!(looks("sh -c uvx mcp-server-fetch"));
+    assert!(looks("C:\\Tools\\Weather-MCP.exe"));
+    assert!(!looks("node server.js"));
+    // Only the program or what it runs counts, not any argument
+    assert!(!looks("vim mcp-server.ts"));
+    assert!(!looks("grep -r mcp-server ."));
+    assert!(!looks("node build.js --out dist-mcp"));
+    assert!(!looks("npx tsc -p mcp-server"));
+    assert!(!looks(""));
+}
@@ -1,11 +1,30 @@
 use crate::claude_code_commands;
 use crate::client::ClientConfig;
 use crate::codex as codex_cmds;
+use crate::continue_config;
+use crate::goose_config;
 use crate::json_manager::utils::{is_cherrystudio_client, is_per_server_disabled_client};
 use crate::json_manager::JsonManager;
+use crate::server_aliases;
+use crate::settings::ConflictPolicy;
+use crate::windsurf_commands;
+use crate::write_journal;
+use serde::Serialize;
 use serde_json::json;
 use serde_json::Value as JsonValue;
 
+/// Servers a sync couldn't write under their source name. Only Claude Code targets, which
+/// validate names, fill it.
+#[derive(Debug, Serialize, Default)]
+pub struct SyncReport {
+    /// Source name -> name written, for names normalized because `normalize_names` was set
+    pub renamed: std::collections::BTreeMap<String, String>,
+    /// Source name -> error, for servers that were not written
+    pub failed: std::collections::BTreeMap<String, String>,
+}
+
+/// Copy servers between clients. Names Claude Code rejects are reported as failed unless
+/// `normalize_names` is set, in which case they are written normalized and reported as renamed.
 #[tauri::command]
 pub async fn sync_mcp_config(
     from_client: String,
@@ -13,18 +32,47 @@ pub async fn sync_mcp_config(
     from_path: Option<String>,
     to_path: Option<String>,
     override_all: bool,
-) -> Result<(), String> {
+    normalize_names: Option<bool>,
+) -> Result<SyncReport, String> {
+    // Journaled as one operation, so an interrupted sync can be rolled back as a whole
+    let batch = write_journal::begin_batch(
+        &format!("Sync {} to {}", from_client, to_client),
+        Vec::new(),
+    );
     // Load source and target
     let from_json = read_from_client(&from_client, from_path.as_deref()).await?;
     let mut to_json = read_from_client(&to_client, to_path.as_deref())
         .await
         .unwrap_or_else(|_| json!({}));
 
-    let mut from_servers = from_json.get("mcpServers").cloned().unwrap_or(json!({}));
-    let from_disabled = from_json.get("__disabled").cloned().unwrap_or(json!({}));
+    // Keyed by the names the target client has the servers under (see `server_aliases`), in
+    // the `url` shape or Windsurf's `serverUrl` one
+    let aliases = server_aliases::load();
+    let translate = |servers: Option<&JsonValue>| {
+        let servers = servers
+            .and_then(|v| v.as_object())
+            .cloned()
+            .unwrap_or_default();
+        let servers = aliases.translate_servers(&from_client, &to_client, servers);
+        JsonValue::Object(
+            servers
+                .into_iter()
+                .map(|(name, config)| {
+                    let config = windsurf_commands::entry_from_windsurf(&config);
+                    if to_client == "windsurf" {
+                        (name, windsurf_commands::entry_to_windsurf(&config))
+                    } else {
+                        (name, config)
+                    }
+                })
+                .collect(),
+        )
+    };
+    let mut from_servers = translate(from_json.get("mcpServers"));
+    let from_disabled = translate(from_json.get("__disabled"));
 
-    // If syncing to codex (no disabled concept), filter out disabled servers from source
-    if to_client == "codex" {
+    // Codex and Goose entries have no `disabled`/`isActive` flag; leave disabled servers out
+    if to_client == "codex" || to_client == goose_config::CLIENT {
         if is_per_server_disabled_client(&from_client) {
             if let Some(obj) = from_servers.as_object_mut() {
                 obj.retain(|_, v| !v.get("disabled").and_then(|d| d.as_bool()).unwrap_or(false));
@@ -199,7 +247,15 @@ pub async fn sync_mcp_config(
     }
 
     // If writing to codex, perform codex-aware write
-    write_to_client(&to_client, to_path.as_deref(), to_json, override_all).await
+    batch
+        .run(write_to_client(
+            &to_client,
+            to_path.as_deref(),
+            to_json,
+            override_all,
+            normalize_names.unwrap_or(false),
+        ))
+        .await
 }
 
 async fn read_from_client(client: &str, path: Option<&str>) -> Result<JsonValue, String> {
@@ -208,6 +264,13 @@ async fn read_from_client(client: &str, path: Option<&str>) -> Result<JsonValue,
         let disabled = codex_cmds::list_disabled().await?;
         let j = json!({ "mcpServers": servers, "__disabled": disabled });
         Ok(j)
+    } else if client == continue_config::CLIENT {
+        Ok(json!({ "mcpServers": continue_config::read_servers(path)? }))
+    } else if client == goose_config::CLIENT {
+        Ok(json!({
+            "mcpServers": goose_config::read_servers(path, true)?,
+            "__disabled": goose_config::read_servers(path, false)?,
+        }))
     } else if client == "claude_code" {
         let workdir = path.ok_or_else(|| "Claude Code workingDir is required".to_string())?;
         let list = claude_code_commands::claude_mcp_list(workdir.to_string()).await?;
@@ -242,7 +305,8 @@ async fn write_to_client(
     path: Option<&str>,
     content: JsonValue,
     override_all: bool,
-) -> Result<(), String> {
+    normalize_names: bool,
+) -> Result<SyncReport, String> {
     if client == "codex" {
         let from_servers = content.get("mcpServers").cloned().unwrap_or(json!({}));
         let from_map = from_servers.as_object().cloned().unwrap_or_default();
@@ -277,9 +341,18 @@ async fn write_to_client(
                 }
             }
         }
-        Ok(())
+        Ok(SyncReport::default())
+    } else if client == continue_config::CLIENT {
+        let from_servers = content.get("mcpServers").cloned().unwrap_or(json!({}));
+        let from_map = from_servers.as_object().cloned().unwrap_or_default();
+        continue_config::sync_servers(path, &from_map, override_all).map(|_| SyncReport::default())
+    } else if client == goose_config::CLIENT {
+        let from_servers = content.get("mcpServers").cloned().unwrap_or(json!({}));
+        let from_map = from_servers.as_object().cloned().unwrap_or_default();
+        goose_config::sync_servers(path, &from_map, override_all).map(|_| SyncReport::default())
     } else if client == "claude_code" {
         let workdir = path.ok_or_else(|| "Claude Code workingDir is required".to_string())?;
+        let mut report = SyncReport::default();
         // from_map is mapping name->config
         let from_servers = content.get("mcpServers").cloned().unwrap_or(json!({}));
         let from_map = from_servers.as_object().cloned().unwrap_or_default();
@@ -290,9 +363,11 @@ async fn write_to_client(
         if override_all {
             for name in current_names.iter() {
                 if !from_map.contains_key(name) {
-                    let _ =
-                        claude_code_commands::claude_mcp_remove(name.clone(), workdir.to_string())
-                            .await;
+                    let _ = claude_code_commands::remove_claude_server(
+                        name.clone(),
+                        workdir.to_string(),
+                    )
+                    .await;
                 }
             }
         }
@@ -335,13 +410,37 @@ async fn write_to_client(
                         .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
                         .collect()
                 }),
+                headers: cfg_val.get("headers").and_then(|v| v.as_object()).map(|m| {
+                    m.iter()
+                        .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
+                        .collect()
+                }),
+                credential_status: None,
             };
-            let _ = claude_code_commands::claude_mcp_add(server, workdir.to_string()).await;
+            match claude_code_commands::add_claude_server(
+                server,
+                workdir.to_string(),
+                Some(ConflictPolicy::Overwrite),
+                Some(normalize_names),
+            )
+            .await
+            {
+                Ok(response) => {
+                    if let Some(written) = response.name.filter(|written| *written != name) {
+                        report.renamed.insert(name, written);
+                    }
+                }
+                Err(e) => {
+                    report.failed.insert(name, e);
+                }
+            }
         }
-        Ok(())
+        Ok(report)
     } else {
         let cfg = ClientConfig::new(client, path);
         let p = cfg.get_path();
-        JsonManager::write_json_file(p, &content).await
+        JsonManager::write_json_file(p, &content)
+            .await
+            .map(|_| SyncReport::default())
     }
 }
@@ -0,0 +1,182 @@
+//! Process-wide counters rendered in Prometheus text format at `/metrics`
+//!
+//! Counters live for the lifetime of the process and start at zero on launch. Gauges (clients,
+//! servers, credentials) are computed from a fresh `StatusReport` on every scrape.
+
+use crate::status_page::{ClientState, StatusReport};
+use once_cell::sync::Lazy;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+
+pub const CONFIG_MUTATIONS: &str = "mcplinker_config_mutations_total";
+pub const HEALTH_FAILURES: &str = "mcplinker_health_failures_total";
+pub const WRITE_FAILURES: &str = "mcplinker_write_failures_total";
+pub const WEBHOOK_FAILURES: &str = "mcplinker_webhook_failures_total";
+pub const REFRESH_FAILURES: &str = "mcplinker_refresh_failures_total";
+pub const BACKUP_OPERATIONS: &str = "mcplinker_backup_operations_total";
+
+/// (name, help) of every counter, so each one is exported even before it is incremented
+const COUNTERS: [(&str, &str); 6] = [
+    (
+        CONFIG_MUTATIONS,
+        "Server add, update, remove, enable and disable operations",
+    ),
+    (
+        HEALTH_FAILURES,
+        "Failed server health checks: test launches and endpoint probes",
+    ),
+    (
+        WRITE_FAILURES,
+        "Failed config writes, including integrity check rollbacks",
+    ),
+    (
+        WEBHOOK_FAILURES,
+        "Webhook deliveries that failed after all retries",
+    ),
+    (REFRESH_FAILURES, "Failed background refreshes"),
+    (BACKUP_OPERATIONS, "Config backups created and restored"),
+];
+
+type Labels = Vec<(&'static str, String)>;
+
+static VALUES: Lazy<Mutex<BTreeMap<(&'static str, Labels), u64>>> =
+    Lazy::new(|| Mutex::new(BTreeMap::new()));
+
+/// Increment counter `name` for the given label set
+pub(crate) fn inc(name: &'static str, labels: &[(&'static str, &str)]) {
+    let labels: Labels = labels.iter().map(|(k, v)| (*k, v.to_string())).collect();
+    if let Ok(mut values) = VALUES.lock() {
+        *values.entry((name, labels)).or_insert(0) += 1;
+    }
+}
+
+pub(crate) fn backup_operation(op: &str, ok: bool) {
+    let result = if ok { "ok" } else { "error" };
+    inc(BACKUP_OPERATIONS, &[("op", op), ("result", result)]);
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn format_labels(labels: &[(&str, String)]) -> String {
+    if labels.is_empty() {
+        return String::new();
+    }
+    let inner: Vec<String> = labels
+        .iter()
+        .map(|(k, v)| format!("{}=\"{}\"", k, escape_label(v)))
+        .collect();
+    format!("{{{}}}", inner.join(","))
+}
+
+fn write_gauge(out: &mut String, name: &str, help: &str, samples: &[(Labels, usize)]) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} gauge", name);
+    for (labels, value) in samples {
+        let _ = writeln!(out, "{}{} {}", name, format_labels(labels), value);
+    }
+}
+
+pub(crate) fn render(report: &StatusReport) -> String {
+    let mut out = String::new();
+    let values = VALUES.lock().map(|v| v.clone()).unwrap_or_default();
+    for (name, help) in COUNTERS {
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        let _ = writeln!(out, "# TYPE {} counter", name);
+        let mut any = false;
+        for ((_, labels), value) in values.iter().filter(|((n, _), _)| *n == name) {
+            let _ = writeln!(out, "{}{} {}", name, format_labels(labels), value);
+            any = true;
+        }
+        if !any {
+            let _ = writeln!(out, "{} 0", name);
+        }
+    }
+
+    let clients: Vec<(Labels, usize)> = report
+        .clients
+        .iter()
+        .map(|c| {
+            let state = match c.state {
+                ClientState::Ok => "ok",
+                ClientState::Missing => "missing",
+                ClientState::Invalid => "invalid",
+            };
+            let labels = vec![("client", c.client.clone()), ("state", state.to_string())];
+            (labels, 1)
+        })
+        .collect();
+    write_gauge(
+        &mut out,
+        "mcplinker_client_info",
+        "Detected clients and whether their config is readable",
+        &clients,
+    );
+
+    let mut servers = Vec::new();
+    let mut credentials = Vec::new();
+    for client in &report.clients {
+        for scope in &client.scopes {
+            let labels = |extra: (&'static str, &str)| {
+                vec![
+                    ("client", client.client.clone()),
+                    ("scope", scope.scope.clone()),
+                    (extra.0, extra.1.to_string()),
+                ]
+            };
+            servers.push((labels(("state", "active")), scope.active));
+            servers.push((labels(("state", "disabled")), scope.disabled));
+            credentials.push((labels(("status", "expiring")), scope.credentials_expiring));
+            credentials.push((labels(("status", "expired")), scope.credentials_expired));
+        }
+    }
+    write_gauge(
+        &mut out,
+        "mcplinker_servers",
+        "Configured servers per client scope",
+        &servers,
+    );
+    write_gauge(
+        &mut out,
+        "mcplinker_server_credentials",
+        "Servers whose attached credentials are expiring or expired",
+        &credentials,
+    );
+    write_gauge(
+        &mut out,
+        "mcplinker_expiring_credentials",
+        "Stored credentials expired or expiring within the reminder window",
+        &[(Vec::new(), report.expiring_credentials)],
+    );
+    let memory: Vec<(Labels, usize)> = report
+        .running_servers
+        .iter()
+        .map(|usage| {
+            let server = usage.server.as_ref();
+            let labels = vec![
+                ("pid", usage.pid.to_string()),
+                (
+                    "client",
+                    server.map(|s| s.client.clone()).unwrap_or_default(),
+                ),
+                (
+                    "server",
+                    server.map_or_else(|| usage.name.clone(), |s| s.name.clone()),
+                ),
+            ];
+            (labels, usage.memory_bytes as usize)
+        })
+        .collect();
+    write_gauge(
+        &mut out,
+        "mcplinker_server_memory_bytes",
+        "Resident memory of running MCP server process trees",
+        &memory,
+    );
+    out
+}
@@ -0,0 +1,50 @@
+// Prometheus metrics tests
+use crate::metrics::{self, render};
+use crate::status_page::StatusReport;
+
+fn report() -> StatusReport {
+    StatusReport {
+        generated_at: "2025-01-01T00:00:00Z".to_string(),
+        profile: None,
+        clients: Vec::new(),
+        expiring_credentials: 0,
+        running_servers: Vec::new(),
+    }
+}
+
+fn samples<'a>(out: &'a str, name: &str) -> Vec<&'a str> {
+    out.lines()
+        .filter(|line| line.starts_with(name) && !line.starts_with('#'))
+        .collect()
+}
+
+#[test]
+fn test_every_counter_is_exported() {
+    let out = render(&report());
+    for name in [
+        metrics::CONFIG_MUTATIONS,
+        metrics::HEALTH_FAILURES,
+        metrics::WRITE_FAILURES,
+        metrics::WEBHOOK_FAILURES,
+        metrics::REFRESH_FAILURES,
+        metrics::BACKUP_OPERATIONS,
+    ] {
+        assert!(out.contains(&format!("# TYPE {} counter", name)));
+        assert!(!samples(&out, name).is_empty());
+    }
+}
+
+#[test]
+fn test_failures_are_counted_separately() {
+    metrics::inc(metrics::WRITE_FAILURES, &[("client", "metrics-test")]);
+    metrics::inc(metrics::WRITE_FAILURES, &[("client", "metrics-test")]);
+    metrics::inc(metrics::REFRESH_FAILURES, &[("kind", "metrics-test")]);
+    let out = render(&report());
+    assert!(samples(&out, metrics::WRITE_FAILURES)
+        .contains(&"mcplinker_write_failures_total{client=\"metrics-test\"} 2"));
+    assert!(samples(&out, metrics::REFRESH_FAILURES)
+        .contains(&"mcplinker_refresh_failures_total{kind=\"metrics-test\"} 1"));
+    assert!(!samples(&out, metrics::HEALTH_FAILURES)
+        .iter()
+        .any(|line| line.contains("metrics-test")));
+}
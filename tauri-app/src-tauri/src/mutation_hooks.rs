@@ -0,0 +1,31 @@
+//! Side effects of a command-level server mutation
+//!
+//! Every add/update/remove/enable/disable command notes its change through
+//! `op_recorder::record`, which runs `run` before appending the recording step. Features that
+//! react to mutations hook in here rather than in the recorder: metrics, the audit log,
+//! webhooks, client reload hints, install defaults and ownership marks.
+
+use crate::client_target::ClientTarget;
+use crate::op_recorder::RecordedOp;
+use crate::{audit_log, client_reload, metrics, ownership_marks, server_defaults, webhooks};
+
+pub(crate) fn run(target: &ClientTarget, op: &RecordedOp) {
+    metrics::inc(
+        metrics::CONFIG_MUTATIONS,
+        &[("client", &target.client), ("op", op.kind())],
+    );
+    audit_log::log(op.kind(), target, Some(op.name()), None, Vec::new());
+    webhooks::emit_mutation(target, op);
+    client_reload::note_change(target);
+    if let RecordedOp::UpsertServer { config, .. } = op {
+        server_defaults::remember(config);
+    }
+    match op {
+        RecordedOp::UpsertServer { name, .. } | RecordedOp::EnableServer { name } => {
+            ownership_marks::spawn_mark(target.clone(), name.clone())
+        }
+        RecordedOp::RemoveServer { name } | RecordedOp::DisableServer { name } => {
+            ownership_marks::unmark(target, name)
+        }
+    }
+}
@@ -0,0 +1,54 @@
+//! HTTP clients and retries for the app's own requests, configured by `NetworkSettings`
+//!
+//! Registry fetches, package and advisory lookups, endpoint probes and webhook deliveries get
+//! their timeouts from here instead of their own constants. Retries back off exponentially
+//! and only repeat requests that failed to get through or got a 429 or 5xx answer.
+
+use crate::settings::{NetworkSettings, RetryPolicy};
+use std::time::Duration;
+
+pub(crate) fn http_client(network: &NetworkSettings) -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(network.request_timeout_secs.max(1)))
+        .connect_timeout(Duration::from_secs(network.connect_timeout_secs.max(1)))
+        .user_agent("mcp-linker")
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+/// Delay before retry number `retry` (1 for the first retry)
+pub(crate) fn backoff_delay(policy: &RetryPolicy, retry: u32) -> Duration {
+    let factor = 2u64.saturating_pow(retry.saturating_sub(1));
+    Duration::from_millis(
+        policy
+            .initial_backoff_ms
+            .saturating_mul(factor)
+            .min(policy.max_backoff_ms),
+    )
+}
+
+pub(crate) fn is_retryable(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Send the request `build` makes, retrying per `policy`. The last response is returned
+/// whatever its status; the error only when no attempt got an answer.
+pub(crate) async fn send_with_retry(
+    policy: &RetryPolicy,
+    build: impl Fn() -> reqwest::RequestBuilder,
+) -> Result<reqwest::Response, reqwest::Error> {
+    let attempts = policy.max_attempts.max(1);
+    let mut retry = 0;
+    loop {
+        let result = build().send().await;
+        let retryable = match &result {
+            Ok(response) => is_retryable(response.status()),
+            Err(_) => true,
+        };
+        retry += 1;
+        if !retryable || retry >= attempts {
+            return result;
+        }
+        tokio::time::sleep(backoff_delay(policy, retry)).await;
+    }
+}
@@ -0,0 +1,36 @@
+// Network retry policy tests
+use crate::network::{backoff_delay, is_retryable};
+use crate::settings::{AppSettings, RetryPolicy};
+use std::time::Duration;
+
+#[test]
+fn test_backoff_doubles_up_to_the_cap() {
+    let policy = RetryPolicy {
+        max_attempts: 6,
+        initial_backoff_ms: 500,
+        max_backoff_ms: 3000,
+    };
+    let delays: Vec<Duration> = (1..=5).map(|retry| backoff_delay(&policy, retry)).collect();
+    assert_eq!(
+        delays,
+        [500, 1000, 2000, 3000, 3000].map(Duration::from_millis)
+    );
+    assert_eq!(backoff_delay(&policy, 80), Duration::from_millis(3000));
+}
+
+#[test]
+fn test_is_retryable() {
+    assert!(is_retryable(reqwest::StatusCode::TOO_MANY_REQUESTS));
+    assert!(is_retryable(reqwest::StatusCode::BAD_GATEWAY));
+    assert!(!is_retryable(reqwest::StatusCode::NOT_FOUND));
+    assert!(!is_retryable(reqwest::StatusCode::UNAUTHORIZED));
+}
+
+#[test]
+fn test_network_settings_default_when_missing() {
+    let settings: AppSettings =
+        serde_json::from_str(r#"{ "network": { "request_timeout_secs": 60 } }"#).unwrap();
+    assert_eq!(settings.network.request_timeout_secs, 60);
+    assert_eq!(settings.network.connect_timeout_secs, 10);
+    assert_eq!(settings.network.retry, RetryPolicy::default());
+}
@@ -0,0 +1,297 @@
+//! Record server operations and replay them elsewhere
+//!
+//! While a recording is active, the add/update/remove/enable/disable commands append a step
+//! naming the target and the config they wrote. `stop_recording` returns the steps as a script
+//! (optionally saved as script.json) and `replay_script` applies them in order, to their
+//! original targets or all to one target, e.g. to set up a new machine. Secret env and header
+//! values, secret args and token-shaped URL parts are redacted in scripts (see `redaction`);
+//! a replay takes them from the existing server of the same name on the target, and a step
+//! whose values can't be found there fails.
+//!
+//! Only the command entry points record. Internal calls and replays go through the non-command
+//! functions, so a disable (which removes the server) is recorded once and not also as a remove.
+
+use crate::adapter::ClientAdapter;
+use crate::batch_ops::{self, Operation};
+use crate::claude_disabled;
+use crate::client_target::{self, ClientTarget};
+use crate::json_pointer;
+use crate::redaction::{self, REDACTED};
+use crate::{mutation_hooks, quarantine};
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::sync::Mutex;
+use tauri::command;
+
+const SCRIPT_VERSION: u32 = 1;
+
+static RECORDING: Lazy<Mutex<Option<OpScript>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum RecordedOp {
+    /// Add or update; replays overwrite an existing server of the same name
+    UpsertServer {
+        name: String,
+        config: Value,
+    },
+    RemoveServer {
+        name: String,
+    },
+    DisableServer {
+        name: String,
+    },
+    EnableServer {
+        name: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecordedStep {
+    pub target: ClientTarget,
+    #[serde(flatten)]
+    pub op: RecordedOp,
+    pub recorded_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpScript {
+    pub version: u32,
+    pub started_at: String,
+    #[serde(default)]
+    pub finished_at: Option<String>,
+    pub steps: Vec<RecordedStep>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct StepError {
+    pub index: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ReplayReport {
+    pub applied: usize,
+    pub errors: Vec<StepError>,
+    /// Id for `undo_batch`, undoing the whole replay
+    pub undo_id: Option<String>,
+}
+
+impl RecordedOp {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RecordedOp::UpsertServer { .. } => "upsert",
+            RecordedOp::RemoveServer { .. } => "remove",
+            RecordedOp::DisableServer { .. } => "disable",
+            RecordedOp::EnableServer { .. } => "enable",
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            RecordedOp::UpsertServer { name, .. }
+            | RecordedOp::RemoveServer { name }
+            | RecordedOp::DisableServer { name }
+            | RecordedOp::EnableServer { name } => name,
+        }
+    }
+}
+
+/// Note a command-level mutation: run the mutation hooks (see `mutation_hooks`) and append a
+/// step, with secrets redacted, if a recording is active
+pub(crate) fn record(target: ClientTarget, op: RecordedOp) {
+    mutation_hooks::run(&target, &op);
+    if let Ok(mut recording) = RECORDING.lock() {
+        if let Some(script) = recording.as_mut() {
+            script.steps.push(RecordedStep {
+                target,
+                op: redacted(op),
+                recorded_at: Utc::now().to_rfc3339(),
+            });
+        }
+    }
+}
+
+/// `op` as it is stored in scripts
+pub(crate) fn redacted(op: RecordedOp) -> RecordedOp {
+    match op {
+        RecordedOp::UpsertServer { name, config } => RecordedOp::UpsertServer {
+            name,
+            config: redaction::redact_config(&config),
+        },
+        op => op,
+    }
+}
+
+fn is_redacted(value: &Value) -> bool {
+    value.as_str().is_some_and(|text| text.contains(REDACTED))
+}
+
+/// `config` with the values a script redacted taken from `existing`, the target's current
+/// entry of the same name. Fails naming the values that `existing` doesn't have.
+pub(crate) fn fill_redacted(config: &Value, existing: Option<&Value>) -> Result<Value, String> {
+    let mut filled = config.clone();
+    let mut missing = Vec::new();
+    let from_existing = |pointer: &str| {
+        existing
+            .and_then(|entry| entry.pointer(pointer))
+            .filter(|value| !is_redacted(value))
+            .cloned()
+    };
+    for field in ["env", "headers"] {
+        if let Some(values) = filled.get_mut(field).and_then(Value::as_object_mut) {
+            for (key, value) in values.iter_mut() {
+                if is_redacted(value) {
+                    match from_existing(&json_pointer::join(&[field, key.as_str()])) {
+                        Some(found) => *value = found,
+                        None => missing.push(format!("{}.{}", field, key)),
+                    }
+                }
+            }
+        }
+    }
+    // Args and URLs are only redacted in parts, so the whole existing value is taken
+    for field in ["args", "url"] {
+        let has_redacted = match &filled[field] {
+            Value::Array(args) => args.iter().any(is_redacted),
+            value => is_redacted(value),
+        };
+        if has_redacted {
+            match from_existing(&json_pointer::join(&[field])) {
+                Some(found) if !found.as_array().is_some_and(|a| a.iter().any(is_redacted)) => {
+                    filled[field] = found
+                }
+                _ => missing.push(field.to_string()),
+            }
+        }
+    }
+    if missing.is_empty() {
+        Ok(filled)
+    } else {
+        Err(format!(
+            "Recorded config has redacted values the target doesn't have: {}",
+            missing.join(", ")
+        ))
+    }
+}
+
+/// Start recording; fails if a recording is already in progress
+#[command]
+pub async fn start_recording() -> Result<(), String> {
+    let mut recording = RECORDING
+        .lock()
+        .map_err(|e| format!("Failed to lock recorder: {}", e))?;
+    if recording.is_some() {
+        return Err("A recording is already in progress".to_string());
+    }
+    *recording = Some(OpScript {
+        version: SCRIPT_VERSION,
+        started_at: Utc::now().to_rfc3339(),
+        finished_at: None,
+        steps: Vec::new(),
+    });
+    println!("[Recorder] Recording started");
+    Ok(())
+}
+
+/// Stop recording and return the script, also writing it to `path` when given
+#[command]
+pub async fn stop_recording(path: Option<String>) -> Result<OpScript, String> {
+    let mut script = RECORDING
+        .lock()
+        .map_err(|e| format!("Failed to lock recorder: {}", e))?
+        .take()
+        .ok_or("No recording in progress")?;
+    script.finished_at = Some(Utc::now().to_rfc3339());
+    if let Some(path) = path {
+        let content = serde_json::to_string_pretty(&script)
+            .map_err(|e| format!("Failed to serialize script: {}", e))?;
+        fs::write(&path, content).map_err(|e| format!("Failed to write script: {}", e))?;
+    }
+    println!(
+        "[Recorder] Recording stopped ({} steps)",
+        script.steps.len()
+    );
+    Ok(script)
+}
+
+#[command]
+pub async fn is_recording() -> Result<bool, String> {
+    Ok(RECORDING
+        .lock()
+        .map_err(|e| format!("Failed to lock recorder: {}", e))?
+        .is_some())
+}
+
+pub(crate) async fn replay_step(target: &ClientTarget, op: &RecordedOp) -> Result<(), String> {
+    match op {
+        RecordedOp::UpsertServer { name, config } => {
+            // Recorded scripts have their secrets redacted, journal steps don't
+            let config = if config.to_string().contains(REDACTED) {
+                let existing = client_target::list_servers(target).await?;
+                fill_redacted(config, existing.get(name))?
+            } else {
+                config.clone()
+            };
+            client_target::upsert_server(target, name, config).await?;
+        }
+        RecordedOp::RemoveServer { name } => client_target::remove_server(target, name).await?,
+        RecordedOp::DisableServer { name } if target.is_claude_code() => {
+            claude_disabled::disable_claude_server(target.working_dir(), name).await?;
+        }
+        RecordedOp::EnableServer { name } if target.is_claude_code() => {
+            quarantine::ensure_not_quarantined(target, name, "enabling")?;
+            claude_disabled::enable_claude_server(target.working_dir(), name, false).await?;
+        }
+        RecordedOp::DisableServer { name } => {
+            ClientAdapter::new(&target.client, target.path.as_deref())
+                .disable(name.clone())
+                .await?;
+        }
+        RecordedOp::EnableServer { name } => {
+            quarantine::ensure_not_quarantined(target, name, "enabling")?;
+            ClientAdapter::new(&target.client, target.path.as_deref())
+                .enable(name.clone())
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Apply a script's steps in order. With `target`, every step is applied to it instead of the
+/// recorded target. Failed steps are reported and the rest still run.
+#[command]
+pub async fn replay_script(
+    script: OpScript,
+    target: Option<ClientTarget>,
+) -> Result<ReplayReport, String> {
+    if script.version > SCRIPT_VERSION {
+        return Err(format!(
+            "Script version {} is newer than supported version {}",
+            script.version, SCRIPT_VERSION
+        ));
+    }
+    let ops: Vec<Operation> = script
+        .steps
+        .into_iter()
+        .map(|step| Operation::from_step(target.clone().unwrap_or(step.target), step.op))
+        .collect();
+    let batch = batch_ops::run_batch("Replay script", &ops, false, false).await;
+    Ok(ReplayReport {
+        applied: batch.applied,
+        errors: batch
+            .results
+            .into_iter()
+            .filter_map(|result| {
+                result.error.map(|message| StepError {
+                    index: result.index,
+                    message,
+                })
+            })
+            .collect(),
+        undo_id: batch.undo_id,
+    })
+}
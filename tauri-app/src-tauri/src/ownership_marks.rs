@@ -0,0 +1,353 @@
+//! Detection of other tools rewriting servers this app wrote
+//!
+//! Every server written through a command is fingerprinted after the write: one SHA-256 per
+//! field, kept in ownership_marks.json in the app data dir, so no config values are stored.
+//! A background task compares the fingerprints against the configs and publishes a
+//! `foreign_modification` event naming the fields another tool (or a hand edit) added,
+//! removed or changed, and the tool when `server_provenance` recognizes it. With
+//! `write_ownership_markers` set, entries of clients that keep unknown keys also get a
+//! `"_managedBy": "mcp-linker"` annotation; a rewrite that drops it is reported as well.
+//!
+//! Accepting a modification takes the current entry as the new fingerprint.
+
+use crate::client_target::{self, ClientTarget};
+use crate::event_bus::{self, AppEventKind, EventScope};
+use crate::json_pointer::escape_segment;
+use crate::server_metadata::config_fingerprint;
+use crate::server_provenance::{self, ProvenanceSource};
+use crate::settings::{app_config_dir, load_settings};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{command, AppHandle, Runtime};
+
+pub(crate) const MARKER_KEY: &str = "_managedBy";
+pub(crate) const MARKER_VALUE: &str = "mcp-linker";
+const CHECK_INTERVAL_SECS: u64 = 60;
+
+/// Clients known to keep unknown keys in server entries
+const MARKER_CLIENTS: [&str; 7] = [
+    "cursor",
+    "windsurf",
+    "cline",
+    "roo_code",
+    "cherrystudio",
+    "mcphub",
+    "mcplinker",
+];
+
+/// Serializes read-modify-write cycles of ownership_marks.json
+static LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OwnershipMark {
+    pub target: ClientTarget,
+    pub name: String,
+    /// SHA-256 of each field, by JSON pointer inside the entry
+    pub fields: BTreeMap<String, String>,
+    /// Whether the entry carried the `_managedBy` marker when it was fingerprinted
+    #[serde(default)]
+    pub marked: bool,
+    pub written_at: String,
+    /// Fingerprint of the state last reported as foreign, so it is reported once
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reported: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct MarksFile {
+    #[serde(default)]
+    marks: Vec<OwnershipMark>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ForeignModification {
+    pub target: ClientTarget,
+    pub name: String,
+    /// The whole entry is gone
+    pub removed_entry: bool,
+    /// JSON pointers of fields another tool added, removed or changed
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<String>,
+    /// We wrote the `_managedBy` marker and it is gone
+    pub marker_lost: bool,
+    /// Manager the entry now points to, when recognized
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suspected_tool: Option<ProvenanceSource>,
+    pub written_at: String,
+}
+
+fn marks_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("ownership_marks.json"))
+}
+
+fn read_marks() -> Result<MarksFile, String> {
+    let path = marks_path()?;
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse ownership marks: {}", e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(MarksFile::default()),
+        Err(e) => Err(format!("Failed to read ownership marks: {}", e)),
+    }
+}
+
+fn write_marks(file: &MarksFile) -> Result<(), String> {
+    let path = marks_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(file)
+        .map_err(|e| format!("Failed to serialize ownership marks: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write ownership marks: {}", e))
+}
+
+fn update_marks(update: impl FnOnce(&mut MarksFile)) -> Result<(), String> {
+    let _guard = LOCK
+        .lock()
+        .map_err(|e| format!("Failed to lock ownership marks: {}", e))?;
+    let mut file = read_marks()?;
+    update(&mut file);
+    write_marks(&file)
+}
+
+pub(crate) fn accepts_marker(client: &str) -> bool {
+    MARKER_CLIENTS.contains(&client)
+}
+
+pub(crate) fn has_marker(entry: &Value) -> bool {
+    entry[MARKER_KEY].as_str() == Some(MARKER_VALUE)
+}
+
+fn hash_fields(value: &Value, pointer: &str, out: &mut BTreeMap<String, String>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, child) in map {
+                if pointer.is_empty() && key == MARKER_KEY {
+                    continue;
+                }
+                let child_pointer = format!("{}/{}", pointer, escape_segment(key));
+                hash_fields(child, &child_pointer, out);
+            }
+        }
+        _ => {
+            out.insert(pointer.to_string(), config_fingerprint(value));
+        }
+    }
+}
+
+/// Per-field hashes of a server entry, leaving out the marker
+pub(crate) fn field_hashes(entry: &Value) -> BTreeMap<String, String> {
+    let mut fields = BTreeMap::new();
+    hash_fields(entry, "", &mut fields);
+    fields
+}
+
+fn state_fingerprint(entry: Option<&Value>) -> String {
+    match entry {
+        Some(entry) => {
+            config_fingerprint(&serde_json::to_value(field_hashes(entry)).unwrap_or_default())
+        }
+        None => "removed".to_string(),
+    }
+}
+
+/// How `current` differs from what was fingerprinted; `None` when it doesn't
+pub(crate) fn compare(
+    mark: &OwnershipMark,
+    current: Option<&Value>,
+) -> Option<ForeignModification> {
+    let mut modification = ForeignModification {
+        target: mark.target.clone(),
+        name: mark.name.clone(),
+        removed_entry: current.is_none(),
+        added: Vec::new(),
+        removed: Vec::new(),
+        changed: Vec::new(),
+        marker_lost: false,
+        suspected_tool: None,
+        written_at: mark.written_at.clone(),
+    };
+    let Some(current) = current else {
+        return Some(modification);
+    };
+    let fields = field_hashes(current);
+    for (pointer, hash) in &fields {
+        match mark.fields.get(pointer) {
+            None => modification.added.push(pointer.clone()),
+            Some(expected) if expected != hash => modification.changed.push(pointer.clone()),
+            Some(_) => {}
+        }
+    }
+    modification.removed = mark
+        .fields
+        .keys()
+        .filter(|pointer| !fields.contains_key(*pointer))
+        .cloned()
+        .collect();
+    modification.marker_lost = mark.marked && !has_marker(current);
+    let source = server_provenance::detect(current).source;
+    modification.suspected_tool = (source != ProvenanceSource::Unknown).then_some(source);
+
+    let unchanged = modification.added.is_empty()
+        && modification.removed.is_empty()
+        && modification.changed.is_empty()
+        && !modification.marker_lost;
+    (!unchanged).then_some(modification)
+}
+
+/// Fingerprint the server as it is now in the config, writing the marker first if enabled
+async fn fingerprint(target: &ClientTarget, name: &str) -> Result<(), String> {
+    let Some(mut entry) = client_target::list_servers(target).await?.remove(name) else {
+        return forget(target, name);
+    };
+    if load_settings().write_ownership_markers
+        && accepts_marker(&target.client)
+        && !has_marker(&entry)
+    {
+        if let Some(object) = entry.as_object_mut() {
+            object.insert(MARKER_KEY.to_string(), Value::from(MARKER_VALUE));
+            client_target::upsert_server(target, name, entry.clone()).await?;
+        }
+    }
+    let mark = OwnershipMark {
+        target: target.clone(),
+        name: name.to_string(),
+        fields: field_hashes(&entry),
+        marked: has_marker(&entry),
+        written_at: Utc::now().to_rfc3339(),
+        reported: None,
+    };
+    update_marks(|file| {
+        file.marks
+            .retain(|m| !(m.target == mark.target && m.name == mark.name));
+        file.marks.push(mark);
+    })
+}
+
+fn forget(target: &ClientTarget, name: &str) -> Result<(), String> {
+    update_marks(|file| {
+        file.marks
+            .retain(|m| !(m.target == *target && m.name == name));
+    })
+}
+
+/// Fingerprint a server the app just wrote, in the background
+pub(crate) fn spawn_mark(target: ClientTarget, name: String) {
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = fingerprint(&target, &name).await {
+            println!(
+                "[Ownership] Failed to mark {} on {}: {}",
+                name,
+                target.label(),
+                e
+            );
+        }
+    });
+}
+
+/// Stop tracking a server the app removed or disabled
+pub(crate) fn unmark(target: &ClientTarget, name: &str) {
+    if let Err(e) = forget(target, name) {
+        println!(
+            "[Ownership] Failed to unmark {} on {}: {}",
+            name,
+            target.label(),
+            e
+        );
+    }
+}
+
+/// Current modifications, with the state each was found in and whether that was reported
+async fn scan() -> Result<Vec<(ForeignModification, String, bool)>, String> {
+    let marks = read_marks()?.marks;
+    let mut servers: HashMap<ClientTarget, Map<String, Value>> = HashMap::new();
+    let mut found = Vec::new();
+    for mark in &marks {
+        if !servers.contains_key(&mark.target) {
+            match client_target::list_servers(&mark.target).await {
+                Ok(listed) => servers.insert(mark.target.clone(), listed),
+                // Unreadable configs are the startup check's business
+                Err(_) => continue,
+            };
+        }
+        let current = servers.get(&mark.target).and_then(|s| s.get(&mark.name));
+        if let Some(modification) = compare(mark, current) {
+            let state = state_fingerprint(current);
+            let reported = mark.reported.as_ref() == Some(&state);
+            found.push((modification, state, reported));
+        }
+    }
+    Ok(found)
+}
+
+fn set_reported(reported: &[(ForeignModification, String)]) -> Result<(), String> {
+    update_marks(|file| {
+        for (modification, state) in reported {
+            if let Some(mark) = file
+                .marks
+                .iter_mut()
+                .find(|m| m.target == modification.target && m.name == modification.name)
+            {
+                mark.reported = Some(state.clone());
+            }
+        }
+    })
+}
+
+async fn check_and_publish<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+    let fresh: Vec<(ForeignModification, String)> = scan()
+        .await?
+        .into_iter()
+        .filter(|(_, _, reported)| !reported)
+        .map(|(modification, state, _)| (modification, state))
+        .collect();
+    if fresh.is_empty() {
+        return Ok(());
+    }
+    for (modification, _) in &fresh {
+        println!(
+            "[Ownership] {} on {} was modified outside the app",
+            modification.name,
+            modification.target.label()
+        );
+        event_bus::publish(
+            app,
+            AppEventKind::ForeignModification,
+            EventScope::target(&modification.target),
+            modification,
+        );
+    }
+    set_reported(&fresh)
+}
+
+/// Start the background task that looks for foreign modifications
+pub fn spawn_foreign_check<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval =
+            tokio::time::interval(std::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            if let Err(e) = check_and_publish(&app).await {
+                println!("[Ownership] Check failed: {}", e);
+            }
+        }
+    });
+}
+
+/// Servers written by this app that another tool has modified since, reported or not
+#[command]
+pub async fn list_foreign_modifications() -> Result<Vec<ForeignModification>, String> {
+    Ok(scan().await?.into_iter().map(|(m, _, _)| m).collect())
+}
+
+/// Take the server as it is now as ours, or stop tracking it when it is gone
+#[command]
+pub async fn accept_foreign_modification(target: ClientTarget, name: String) -> Result<(), String> {
+    fingerprint(&target, &name).await
+}
@@ -0,0 +1,68 @@
+// Ownership mark tests
+use crate::client_target::ClientTarget;
+use crate::ownership_marks::{compare, field_hashes, OwnershipMark, MARKER_KEY};
+use crate::server_provenance::ProvenanceSource;
+use serde_json::json;
+
+fn mark(entry: &serde_json::Value, marked: bool) -> OwnershipMark {
+    OwnershipMark {
+        target: ClientTarget::client("cursor", None),
+        name: "fs".to_string(),
+        fields: field_hashes(entry),
+        marked,
+        written_at: "2026-01-01T00:00:00Z".to_string(),
+        reported: None,
+    }
+}
+
+#[test]
+fn test_field_hashes_skip_marker() {
+    let entry = json!({ "command": "npx", "env": { "ROOT": "/srv" } });
+    let mut marked = entry.clone();
+    marked[MARKER_KEY] = json!("mcp-linker");
+    let fields = field_hashes(&entry);
+    assert_eq!(fields, field_hashes(&marked));
+    assert_eq!(
+        fields.keys().collect::<Vec<_>>(),
+        vec!["/command", "/env/ROOT"]
+    );
+    // No values are kept
+    assert!(fields.values().all(|hash| !hash.contains("srv")));
+}
+
+#[test]
+fn test_compare_unchanged() {
+    let entry =
+        json!({ "command": "npx", "args": ["-y", "fs-server"], "_managedBy": "mcp-linker" });
+    assert_eq!(compare(&mark(&entry, true), Some(&entry)), None);
+}
+
+#[test]
+fn test_compare_reports_fields_and_tool() {
+    let written = json!({
+        "command": "npx",
+        "args": ["-y", "fs-server"],
+        "env": { "ROOT": "/srv" },
+        "_managedBy": "mcp-linker"
+    });
+    let rewritten =
+        json!({ "command": "npx", "args": ["-y", "@smithery/cli", "run", "fs"], "timeout": 30 });
+    let modification = compare(&mark(&written, true), Some(&rewritten)).unwrap();
+    assert!(!modification.removed_entry);
+    assert_eq!(modification.added, vec!["/timeout".to_string()]);
+    assert_eq!(modification.removed, vec!["/env/ROOT".to_string()]);
+    assert_eq!(modification.changed, vec!["/args".to_string()]);
+    assert!(modification.marker_lost);
+    assert_eq!(
+        modification.suspected_tool,
+        Some(ProvenanceSource::Smithery)
+    );
+}
+
+#[test]
+fn test_compare_removed_entry() {
+    let written = json!({ "url": "https://example.com/mcp" });
+    let modification = compare(&mark(&written, false), None).unwrap();
+    assert!(modification.removed_entry);
+    assert!(!modification.marker_lost);
+}
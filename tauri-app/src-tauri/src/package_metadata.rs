@@ -0,0 +1,254 @@
+//! License, repository and description of npm and PyPI server packages
+//!
+//! Looked up in the public registries and cached in package_metadata.json in the app data dir
+//! for a week. Unpinned packages resolve to the latest release, which is what `npx`/`uvx`
+//! would run today.
+
+use crate::network;
+use crate::server_package::{Ecosystem, PackageRef};
+use crate::settings::{self, app_config_dir, RetryPolicy};
+use chrono::{DateTime, Duration, Utc};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+const CACHE_DAYS: i64 = 7;
+const CONCURRENT_LOOKUPS: usize = 8;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct PackageMetadata {
+    /// SPDX expression or the license name the package declares
+    pub license: Option<String>,
+    pub repository: Option<String>,
+    pub homepage: Option<String>,
+    /// Version the lookup resolved to
+    pub version: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct CachedMetadata {
+    fetched_at: String,
+    metadata: PackageMetadata,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct MetadataCache {
+    #[serde(default)]
+    packages: HashMap<String, CachedMetadata>,
+}
+
+fn cache_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("package_metadata.json"))
+}
+
+fn read_cache() -> MetadataCache {
+    cache_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_cache(cache: &MetadataCache) -> Result<(), String> {
+    let path = cache_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(cache)
+        .map_err(|e| format!("Failed to serialize package metadata: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write package metadata: {}", e))
+}
+
+fn cache_key(package: &PackageRef) -> String {
+    let ecosystem = match package.ecosystem {
+        Ecosystem::Npm => "npm",
+        Ecosystem::Pypi => "pypi",
+        Ecosystem::Docker => "docker",
+    };
+    let version = if package.is_pinned() {
+        package.version.as_deref().unwrap_or("latest")
+    } else {
+        "latest"
+    };
+    format!("{}:{}@{}", ecosystem, package.name, version)
+}
+
+/// `git+https://github.com/o/r.git` -> `https://github.com/o/r`
+pub(crate) fn normalize_repository(url: &str) -> String {
+    let url = url.trim();
+    let url = url.strip_prefix("git+").unwrap_or(url);
+    let url = url.strip_suffix(".git").unwrap_or(url);
+    if let Some(rest) = url.strip_prefix("git@github.com:") {
+        return format!("https://github.com/{}", rest);
+    }
+    if let Some(rest) = url.strip_prefix("github:") {
+        return format!("https://github.com/{}", rest);
+    }
+    url.replacen("git://", "https://", 1)
+}
+
+fn string_or_field(value: &Value, field: &str) -> Option<String> {
+    match value {
+        Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        Value::Object(o) => o.get(field)?.as_str().map(|s| s.to_string()),
+        _ => None,
+    }
+}
+
+pub(crate) fn parse_npm(document: &Value) -> PackageMetadata {
+    PackageMetadata {
+        license: string_or_field(&document["license"], "type"),
+        repository: string_or_field(&document["repository"], "url")
+            .map(|url| normalize_repository(&url)),
+        homepage: document["homepage"].as_str().map(|s| s.to_string()),
+        version: document["version"].as_str().map(|s| s.to_string()),
+        description: document["description"].as_str().map(|s| s.to_string()),
+    }
+}
+
+pub(crate) fn parse_pypi(document: &Value) -> PackageMetadata {
+    let info = &document["info"];
+    let classifier_license = info["classifiers"].as_array().and_then(|classifiers| {
+        classifiers
+            .iter()
+            .filter_map(|c| c.as_str())
+            .find_map(|c| c.strip_prefix("License :: "))
+            .map(|c| c.rsplit(" :: ").next().unwrap_or(c).to_string())
+    });
+    // `license` is sometimes the whole license text; only a short value is a name
+    let declared = info["license"]
+        .as_str()
+        .filter(|l| !l.trim().is_empty() && l.len() <= 64 && !l.contains('\n'))
+        .map(|l| l.trim().to_string());
+    let urls = info["project_urls"].as_object();
+    let project_url = |keys: &[&str]| {
+        urls.and_then(|urls| {
+            urls.iter()
+                .find(|(k, _)| keys.iter().any(|key| k.eq_ignore_ascii_case(key)))
+                .and_then(|(_, v)| v.as_str())
+                .map(|s| s.to_string())
+        })
+    };
+    PackageMetadata {
+        license: info["license_expression"]
+            .as_str()
+            .map(|s| s.to_string())
+            .or(declared)
+            .or(classifier_license),
+        repository: project_url(&["Source", "Repository", "Source Code", "Code"])
+            .map(|url| normalize_repository(&url)),
+        homepage: project_url(&["Homepage", "Home"])
+            .or_else(|| info["home_page"].as_str().map(|s| s.to_string())),
+        version: info["version"].as_str().map(|s| s.to_string()),
+        description: info["summary"].as_str().map(|s| s.to_string()),
+    }
+}
+
+async fn fetch(
+    client: &reqwest::Client,
+    retry: &RetryPolicy,
+    package: &PackageRef,
+) -> Result<PackageMetadata, String> {
+    let version = package.version.as_deref().filter(|_| package.is_pinned());
+    let url = match package.ecosystem {
+        Ecosystem::Npm => format!(
+            "https://registry.npmjs.org/{}/{}",
+            package.name.replace('/', "%2F"),
+            version.unwrap_or("latest")
+        ),
+        Ecosystem::Pypi => match version {
+            Some(version) => format!("https://pypi.org/pypi/{}/{}/json", package.name, version),
+            None => format!("https://pypi.org/pypi/{}/json", package.name),
+        },
+        Ecosystem::Docker => return Ok(PackageMetadata::default()),
+    };
+    let response = network::send_with_retry(retry, || client.get(&url))
+        .await
+        .map_err(|e| format!("Failed to reach registry: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("{} returned {}", url, response.status()));
+    }
+    let document: Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse registry response: {}", e))?;
+    Ok(match package.ecosystem {
+        Ecosystem::Npm => parse_npm(&document),
+        _ => parse_pypi(&document),
+    })
+}
+
+/// Metadata for each package, from the cache when fresh. Failed lookups are logged and left
+/// out of the result.
+pub(crate) async fn resolve(packages: &[PackageRef]) -> HashMap<PackageRef, PackageMetadata> {
+    let mut cache = read_cache();
+    let now = Utc::now();
+    let is_fresh = |entry: &CachedMetadata| {
+        DateTime::parse_from_rfc3339(&entry.fetched_at)
+            .is_ok_and(|t| now - t.with_timezone(&Utc) < Duration::days(CACHE_DAYS))
+    };
+
+    let mut resolved = HashMap::new();
+    let mut missing = Vec::new();
+    for package in packages {
+        match cache.packages.get(&cache_key(package)) {
+            Some(entry) if is_fresh(entry) => {
+                resolved.insert(package.clone(), entry.metadata.clone());
+            }
+            _ if package.ecosystem != Ecosystem::Docker && !missing.contains(package) => {
+                missing.push(package.clone())
+            }
+            _ => {}
+        }
+    }
+    if missing.is_empty() {
+        return resolved;
+    }
+
+    let network = settings::load_settings().network;
+    let client = match network::http_client(&network) {
+        Ok(client) => client,
+        Err(e) => {
+            println!("[Packages] {}", e);
+            return resolved;
+        }
+    };
+    let results: Vec<(PackageRef, Result<PackageMetadata, String>)> =
+        futures::stream::iter(missing)
+            .map(|package| {
+                let client = client.clone();
+                let retry = &network.retry;
+                async move {
+                    let result = fetch(&client, retry, &package).await;
+                    (package, result)
+                }
+            })
+            .buffer_unordered(CONCURRENT_LOOKUPS)
+            .collect()
+            .await;
+
+    for (package, result) in results {
+        match result {
+            Ok(metadata) => {
+                cache.packages.insert(
+                    cache_key(&package),
+                    CachedMetadata {
+                        fetched_at: now.to_rfc3339(),
+                        metadata: metadata.clone(),
+                    },
+                );
+                resolved.insert(package, metadata);
+            }
+            Err(e) => println!("[Packages] Lookup of {} failed: {}", package.name, e),
+        }
+    }
+    if let Err(e) = write_cache(&cache) {
+        println!("[Packages] {}", e);
+    }
+    resolved
+}
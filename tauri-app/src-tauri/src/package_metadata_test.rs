@@ -0,0 +1,72 @@
+// Registry metadata parsing tests
+use crate::package_metadata::{normalize_repository, parse_npm, parse_pypi};
+use serde_json::json;
+
+#[test]
+fn test_parse_npm_metadata() {
+    let metadata = parse_npm(&json!({
+        "name": "@modelcontextprotocol/server-github",
+        "version": "2025.4.8",
+        "license": "MIT",
+        "repository": {
+            "type": "git",
+            "url": "git+https://github.com/modelcontextprotocol/servers.git"
+        }
+    }));
+    assert_eq!(metadata.license.as_deref(), Some("MIT"));
+    assert_eq!(
+        metadata.repository.as_deref(),
+        Some("https://github.com/modelcontextprotocol/servers")
+    );
+    assert_eq!(metadata.version.as_deref(), Some("2025.4.8"));
+
+    // Older packages use an object license and a shorthand repository
+    let legacy = parse_npm(&json!({
+        "license": { "type": "Apache-2.0" },
+        "repository": "github:owner/repo"
+    }));
+    assert_eq!(legacy.license.as_deref(), Some("Apache-2.0"));
+    assert_eq!(
+        legacy.repository.as_deref(),
+        Some("https://github.com/owner/repo")
+    );
+}
+
+#[test]
+fn test_parse_pypi_metadata() {
+    let metadata = parse_pypi(&json!({
+        "info": {
+            "version": "0.6.2",
+            "license": "MIT License\n\nPermission is hereby granted...",
+            "classifiers": ["License :: OSI Approved :: MIT License"],
+            "project_urls": {
+                "Homepage": "https://example.com",
+                "Source": "https://github.com/modelcontextprotocol/servers.git"
+            }
+        }
+    }));
+    // Full license text is skipped in favor of the classifier
+    assert_eq!(metadata.license.as_deref(), Some("MIT License"));
+    assert_eq!(
+        metadata.repository.as_deref(),
+        Some("https://github.com/modelcontextprotocol/servers")
+    );
+    assert_eq!(metadata.homepage.as_deref(), Some("https://example.com"));
+
+    let expression = parse_pypi(&json!({
+        "info": { "license_expression": "Apache-2.0", "license": "Apache" }
+    }));
+    assert_eq!(expression.license.as_deref(), Some("Apache-2.0"));
+}
+
+#[test]
+fn test_normalize_repository() {
+    assert_eq!(
+        normalize_repository("git@github.com:owner/repo.git"),
+        "https://github.com/owner/repo"
+    );
+    assert_eq!(
+        normalize_repository("git://gitlab.com/owner/repo"),
+        "https://gitlab.com/owner/repo"
+    );
+}
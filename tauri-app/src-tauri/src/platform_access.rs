@@ -0,0 +1,283 @@
+//! Platform quirks that otherwise surface as generic read failures
+//!
+//! macOS: an app started from a quarantined download runs translocated (from a random
+//! read-only path under /private/var/folders/.../AppTranslocation), and a sandboxed build can
+//! only read folders the user granted. Permission errors on client configs are reported with
+//! that cause. Folders granted with `grant_folder_access` are kept as security-scoped
+//! bookmarks in folder_grants.json and re-opened at startup.
+//!
+//! Windows ARM64: Node may be installed x64-emulated, under Program Files (x86), nvm's
+//! symlink or the npm prefix, and its launchers are `.cmd` shims that `Command::new("npx")`
+//! doesn't find. `resolve_launcher` looks them up through PATH and PATHEXT, then those
+//! install dirs, so test launches start the shim that exists.
+
+use crate::settings::app_config_dir;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+/// Node launchers resolved through `resolve_launcher`
+const NODE_LAUNCHERS: [&str; 6] = ["node", "npx", "npm", "pnpm", "yarn", "corepack"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FolderGrant {
+    pub path: String,
+    /// Security-scoped bookmark, base64
+    pub bookmark: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct GrantsFile {
+    #[serde(default)]
+    grants: Vec<FolderGrant>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PlatformAccess {
+    pub os: String,
+    pub arch: String,
+    /// macOS runs the app from a translocated copy; moving it to /Applications fixes this
+    pub translocated: bool,
+    /// The app runs in the macOS App Sandbox and needs folder grants
+    pub sandboxed: bool,
+    pub granted_folders: Vec<String>,
+    /// Where each Node launcher resolves to, when found
+    pub node_launchers: Vec<(String, Option<String>)>,
+}
+
+/// Whether `exe` is a translocated copy of an app bundle
+pub(crate) fn is_translocated(exe: &Path) -> bool {
+    exe.components()
+        .any(|c| c.as_os_str() == "AppTranslocation")
+}
+
+fn is_sandboxed() -> bool {
+    std::env::var_os("APP_SANDBOX_CONTAINER_ID").is_some()
+}
+
+fn translocated() -> bool {
+    cfg!(target_os = "macos")
+        && std::env::current_exe()
+            .map(|exe| is_translocated(&exe))
+            .unwrap_or(false)
+}
+
+/// Error message for a failed read of `path`, naming the macOS restriction behind a
+/// permission error when there is one
+pub(crate) fn read_error(path: &Path, e: &std::io::Error) -> String {
+    if e.kind() != std::io::ErrorKind::PermissionDenied || !cfg!(target_os = "macos") {
+        return format!("Failed to read {}: {}", path.display(), e);
+    }
+    let cause = if is_sandboxed() {
+        "the app is sandboxed and this folder hasn't been granted; grant access to it in Settings"
+    } else if translocated() {
+        "macOS runs the app from a translocated copy; move it to /Applications and reopen it"
+    } else {
+        "macOS denied access; allow the app under Privacy & Security > Files and Folders"
+    };
+    format!("Failed to read {}: {} ({})", path.display(), cause, e)
+}
+
+/// `command` as found in `dirs`, trying each of `extensions` (e.g. ".exe", ".cmd") in order
+pub(crate) fn find_launcher(
+    command: &str,
+    dirs: &[PathBuf],
+    extensions: &[String],
+    exists: impl Fn(&Path) -> bool,
+) -> Option<PathBuf> {
+    dirs.iter().find_map(|dir| {
+        extensions
+            .iter()
+            .map(|ext| dir.join(format!("{}{}", command, ext)))
+            .find(|candidate| exists(candidate))
+    })
+}
+
+/// Node install dirs outside PATH: x64 (emulated) installs, nvm's symlink and the npm prefix
+fn node_install_dirs() -> Vec<PathBuf> {
+    ["ProgramW6432", "ProgramFiles", "ProgramFiles(x86)"]
+        .iter()
+        .filter_map(|var| std::env::var_os(var))
+        .map(|dir| PathBuf::from(dir).join("nodejs"))
+        .chain(std::env::var_os("NVM_SYMLINK").map(PathBuf::from))
+        .chain(std::env::var_os("APPDATA").map(|dir| PathBuf::from(dir).join("npm")))
+        .collect()
+}
+
+/// Full path of a launcher on Windows, so `.cmd` shims start; other commands and platforms
+/// are returned unchanged
+pub(crate) fn resolve_launcher(command: &str) -> PathBuf {
+    let as_given = PathBuf::from(command);
+    if !cfg!(windows) || as_given.components().count() > 1 || as_given.extension().is_some() {
+        return as_given;
+    }
+    let mut dirs: Vec<PathBuf> = std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).collect())
+        .unwrap_or_default();
+    if NODE_LAUNCHERS.contains(&command.to_ascii_lowercase().as_str()) {
+        dirs.extend(node_install_dirs());
+    }
+    let extensions: Vec<String> = std::env::var("PATHEXT")
+        .unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_string())
+        .split(';')
+        .filter(|ext| !ext.is_empty())
+        .map(|ext| ext.to_ascii_lowercase())
+        .collect();
+    find_launcher(command, &dirs, &extensions, Path::is_file).unwrap_or(as_given)
+}
+
+fn grants_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("folder_grants.json"))
+}
+
+fn read_grants() -> Result<GrantsFile, String> {
+    let path = grants_path()?;
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse folder grants: {}", e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(GrantsFile::default()),
+        Err(e) => Err(format!("Failed to read folder grants: {}", e)),
+    }
+}
+
+fn write_grants(file: &GrantsFile) -> Result<(), String> {
+    let path = grants_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(file)
+        .map_err(|e| format!("Failed to serialize folder grants: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write folder grants: {}", e))
+}
+
+#[cfg(target_os = "macos")]
+mod bookmarks {
+    use objc2::rc::Id;
+    use objc2::runtime::Bool;
+    use objc2_foundation::{
+        NSData, NSError, NSString, NSURLBookmarkCreationWithSecurityScope,
+        NSURLBookmarkResolutionWithSecurityScope, NSURL,
+    };
+
+    fn describe(error: Id<NSError>) -> String {
+        unsafe { error.localizedDescription() }.to_string()
+    }
+
+    /// Security-scoped bookmark of a folder the user just picked
+    pub fn create(path: &str) -> Result<Vec<u8>, String> {
+        unsafe {
+            let url = NSURL::fileURLWithPath(&NSString::from_str(path));
+            url.bookmarkDataWithOptions_includingResourceValuesForKeys_relativeToURL_error(
+                NSURLBookmarkCreationWithSecurityScope,
+                None,
+                None,
+            )
+            .map(|data| data.bytes().to_vec())
+            .map_err(describe)
+        }
+    }
+
+    /// Resolve a bookmark and start accessing the folder for the rest of the process
+    pub fn open(bookmark: &[u8]) -> Result<(), String> {
+        unsafe {
+            let data = NSData::with_bytes(bookmark);
+            let mut stale = Bool::NO;
+            let url =
+                NSURL::URLByResolvingBookmarkData_options_relativeToURL_bookmarkDataIsStale_error(
+                    &data,
+                    NSURLBookmarkResolutionWithSecurityScope,
+                    None,
+                    &mut stale,
+                )
+                .map_err(describe)?;
+            if url.startAccessingSecurityScopedResource() {
+                Ok(())
+            } else {
+                Err("macOS refused access to the folder".to_string())
+            }
+        }
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod bookmarks {
+    /// Folders need no grant outside macOS
+    pub fn create(_path: &str) -> Result<Vec<u8>, String> {
+        Ok(Vec::new())
+    }
+
+    pub fn open(_bookmark: &[u8]) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Re-open the folders granted in earlier runs. Must run before client configs are read.
+pub fn restore_grants() {
+    if !cfg!(target_os = "macos") {
+        return;
+    }
+    let grants = match read_grants() {
+        Ok(file) => file.grants,
+        Err(e) => {
+            println!("[Platform] {}", e);
+            return;
+        }
+    };
+    for grant in grants {
+        let opened = BASE64
+            .decode(&grant.bookmark)
+            .map_err(|e| e.to_string())
+            .and_then(|bookmark| bookmarks::open(&bookmark));
+        if let Err(e) = opened {
+            println!("[Platform] Lost access to {}: {}", grant.path, e);
+        }
+    }
+}
+
+#[command]
+pub async fn get_platform_access() -> Result<PlatformAccess, String> {
+    Ok(PlatformAccess {
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        translocated: translocated(),
+        sandboxed: cfg!(target_os = "macos") && is_sandboxed(),
+        granted_folders: read_grants()?.grants.into_iter().map(|g| g.path).collect(),
+        node_launchers: NODE_LAUNCHERS
+            .iter()
+            .map(|launcher| {
+                let resolved = resolve_launcher(launcher);
+                let found = resolved
+                    .is_absolute()
+                    .then(|| resolved.display().to_string());
+                (launcher.to_string(), found)
+            })
+            .collect(),
+    })
+}
+
+/// Keep access to `path`, a folder the user picked in the open dialog, across restarts
+#[command]
+pub async fn grant_folder_access(path: String) -> Result<Vec<String>, String> {
+    let bookmark = bookmarks::create(&path)?;
+    let mut file = read_grants()?;
+    file.grants.retain(|g| g.path != path);
+    if !bookmark.is_empty() {
+        file.grants.push(FolderGrant {
+            path,
+            bookmark: BASE64.encode(bookmark),
+        });
+    }
+    write_grants(&file)?;
+    Ok(file.grants.into_iter().map(|g| g.path).collect())
+}
+
+#[command]
+pub async fn revoke_folder_access(path: String) -> Result<Vec<String>, String> {
+    let mut file = read_grants()?;
+    file.grants.retain(|g| g.path != path);
+    write_grants(&file)?;
+    Ok(file.grants.into_iter().map(|g| g.path).collect())
+}
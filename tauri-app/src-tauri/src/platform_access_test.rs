@@ -0,0 +1,41 @@
+// Platform quirk tests
+use crate::platform_access::{find_launcher, is_translocated, read_error};
+use std::path::{Path, PathBuf};
+
+#[test]
+fn test_is_translocated() {
+    let translocated =
+        Path::new("/private/var/folders/xy/T/AppTranslocation/1A2B/d/MCP Linker.app/Contents");
+    assert!(is_translocated(translocated));
+    assert!(!is_translocated(Path::new(
+        "/Applications/MCP Linker.app/Contents"
+    )));
+}
+
+#[test]
+fn test_find_launcher_prefers_path_order_then_extension_order() {
+    let dirs = vec![
+        PathBuf::from("/bin-a"),
+        PathBuf::from("/x64/nodejs"),
+        PathBuf::from("/arm64/nodejs"),
+    ];
+    let extensions = vec![".exe".to_string(), ".cmd".to_string()];
+    let existing = [
+        PathBuf::from("/x64/nodejs/npx.cmd"),
+        PathBuf::from("/arm64/nodejs/npx.exe"),
+    ];
+    let found = find_launcher("npx", &dirs, &extensions, |p| {
+        existing.iter().any(|e| e == p)
+    });
+    assert_eq!(found, Some(PathBuf::from("/x64/nodejs/npx.cmd")));
+    assert_eq!(find_launcher("uvx", &dirs, &extensions, |_| false), None);
+}
+
+#[test]
+fn test_read_error_keeps_other_errors_plain() {
+    let e = std::io::Error::new(std::io::ErrorKind::InvalidData, "bad");
+    assert_eq!(
+        read_error(Path::new("/tmp/mcp.json"), &e),
+        "Failed to read /tmp/mcp.json: bad"
+    );
+}
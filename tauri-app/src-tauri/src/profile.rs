@@ -0,0 +1,163 @@
+//! Alternate app profiles with their own data directory
+//!
+//! A profile is chosen once per process with `--profile <name>` or `MCPLINKER_PROFILE`. It
+//! moves everything under `settings::app_config_dir` (settings, secrets, auth profiles,
+//! registry sources, ...) to ~/.config/mcplinker/profiles/<name>. Client config files are not
+//! affected since they belong to the clients.
+//!
+//! Profile windows run as separate processes so no state is shared; only the default profile
+//! registers the single-instance plugin.
+
+use crate::settings::base_config_dir;
+use once_cell::sync::OnceCell;
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+use tauri::command;
+
+pub const PROFILE_ENV: &str = "MCPLINKER_PROFILE";
+pub(crate) const PROFILE_ARG: &str = "--profile";
+
+static ACTIVE_PROFILE: OnceCell<Option<String>> = OnceCell::new();
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ProfileInfo {
+    /// `None` for the default profile
+    pub name: Option<String>,
+    pub data_dir: String,
+}
+
+pub(crate) fn validate_profile_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid profile name '{}': use letters, digits, '-' and '_'",
+            name
+        ))
+    }
+}
+
+pub(crate) fn profile_from_args(args: &[String]) -> Option<String> {
+    args.iter().enumerate().find_map(|(i, arg)| {
+        if let Some(value) = arg.strip_prefix("--profile=") {
+            return Some(value.to_string());
+        }
+        if arg == PROFILE_ARG {
+            return args.get(i + 1).cloned();
+        }
+        None
+    })
+}
+
+/// Select the profile for this process from the command line or environment. Must run before
+/// anything reads the app data dir; later calls are ignored.
+pub fn init() {
+    let args: Vec<String> = std::env::args().collect();
+    let requested = profile_from_args(&args)
+        .or_else(|| std::env::var(PROFILE_ENV).ok())
+        .filter(|name| !name.is_empty() && name != "default");
+    let profile = match requested {
+        Some(name) => match validate_profile_name(&name) {
+            Ok(()) => Some(name),
+            Err(e) => {
+                println!("[Profile] {}; using the default profile", e);
+                None
+            }
+        },
+        None => None,
+    };
+    if let Some(name) = &profile {
+        println!("[Profile] Using profile '{}'", name);
+    }
+    let _ = ACTIVE_PROFILE.set(profile);
+}
+
+pub fn active_profile() -> Option<&'static str> {
+    ACTIVE_PROFILE.get().and_then(|p| p.as_deref())
+}
+
+fn profiles_dir() -> Result<PathBuf, String> {
+    Ok(base_config_dir()?.join("profiles"))
+}
+
+/// App data dir of the active profile
+pub(crate) fn profile_dir() -> Result<PathBuf, String> {
+    match active_profile() {
+        Some(name) => Ok(profiles_dir()?.join(name)),
+        None => base_config_dir(),
+    }
+}
+
+#[command]
+pub async fn get_active_profile() -> Result<ProfileInfo, String> {
+    Ok(ProfileInfo {
+        name: active_profile().map(|p| p.to_string()),
+        data_dir: profile_dir()?.display().to_string(),
+    })
+}
+
+/// App data dir of the named profile, or of the default profile for `None`
+pub(crate) fn data_dir_of(name: Option<&str>) -> Result<PathBuf, String> {
+    match name {
+        Some(name) => {
+            validate_profile_name(name)?;
+            Ok(profiles_dir()?.join(name))
+        }
+        None => base_config_dir(),
+    }
+}
+
+/// Profiles that have a data dir, not counting the default profile
+#[command]
+pub async fn list_profiles() -> Result<Vec<String>, String> {
+    named_profiles()
+}
+
+pub(crate) fn named_profiles() -> Result<Vec<String>, String> {
+    let dir = profiles_dir()?;
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read profiles directory: {}", e)),
+    };
+    let mut profiles: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().to_str().map(|s| s.to_string()))
+        .filter(|name| validate_profile_name(name).is_ok())
+        .collect();
+    profiles.sort();
+    Ok(profiles)
+}
+
+/// Launch another app process running `profile`. Pass no profile for the default one.
+#[command]
+pub async fn open_profile_window(profile: Option<String>) -> Result<(), String> {
+    let profile = profile.filter(|p| !p.is_empty() && p != "default");
+    if profile.as_deref() == active_profile() {
+        return Err("This window already uses that profile".to_string());
+    }
+    let exe = std::env::current_exe().map_err(|e| format!("Failed to locate executable: {}", e))?;
+    let mut command = std::process::Command::new(exe);
+    command.env_remove(PROFILE_ENV);
+    if let Some(name) = &profile {
+        validate_profile_name(name)?;
+        fs::create_dir_all(profiles_dir()?.join(name))
+            .map_err(|e| format!("Failed to create profile directory: {}", e))?;
+        command.arg(PROFILE_ARG).arg(name);
+    }
+    command
+        .spawn()
+        .map_err(|e| format!("Failed to start profile window: {}", e))?;
+    println!(
+        "[Profile] Opened window for profile '{}'",
+        profile.as_deref().unwrap_or("default")
+    );
+    Ok(())
+}
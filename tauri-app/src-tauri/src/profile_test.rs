@@ -0,0 +1,41 @@
+// App profile selection tests
+use crate::profile::{profile_from_args, validate_profile_name};
+
+fn args(values: &[&str]) -> Vec<String> {
+    values.iter().map(|v| v.to_string()).collect()
+}
+
+#[test]
+fn test_profile_from_args() {
+    assert_eq!(
+        profile_from_args(&args(&["mcp-linker", "--profile", "work"])),
+        Some("work".to_string())
+    );
+    assert_eq!(
+        profile_from_args(&args(&["mcp-linker", "--profile=staging", "--verbose"])),
+        Some("staging".to_string())
+    );
+    assert_eq!(profile_from_args(&args(&["mcp-linker", "--profile"])), None);
+    assert_eq!(profile_from_args(&args(&["mcp-linker", "--verbose"])), None);
+    // The first occurrence wins
+    assert_eq!(
+        profile_from_args(&args(&["app", "--profile", "a", "--profile=b"])),
+        Some("a".to_string())
+    );
+}
+
+#[test]
+fn test_validate_profile_name() {
+    assert!(validate_profile_name("work").is_ok());
+    assert!(validate_profile_name("client_a-2").is_ok());
+    assert!(validate_profile_name(&"a".repeat(64)).is_ok());
+
+    assert!(validate_profile_name("").is_err());
+    assert!(validate_profile_name(&"a".repeat(65)).is_err());
+    assert!(validate_profile_name("../default").is_err());
+    assert!(validate_profile_name("my profile").is_err());
+    assert_eq!(
+        validate_profile_name("a/b").unwrap_err(),
+        "Invalid profile name 'a/b': use letters, digits, '-' and '_'"
+    );
+}
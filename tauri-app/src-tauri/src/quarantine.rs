@@ -0,0 +1,168 @@
+//! Quarantine: disable a suspicious server and keep it disabled
+//!
+//! Quarantining moves the server to the disabled section of its client (like a normal
+//! disable), writes the reason to the audit log and records it in quarantine.json in the app
+//! data dir. Enabling a quarantined server, or adding or updating a server of that name, fails
+//! until the quarantine is cleared, whether it comes from the UI, a batch, a replayed script or
+//! a cloud download. Clearing does not re-enable the server.
+
+use crate::adapter::ClientAdapter;
+use crate::audit_log;
+use crate::claude_code_commands::GLOBAL_PROJECT_ID;
+use crate::claude_disabled;
+use crate::client_target::{self, ClientTarget};
+use crate::op_recorder::{self, RecordedOp};
+use crate::settings::app_config_dir;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use tauri::command;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QuarantineRecord {
+    pub target: ClientTarget,
+    pub name: String,
+    pub reason: String,
+    pub quarantined_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct QuarantineFile {
+    #[serde(default)]
+    servers: Vec<QuarantineRecord>,
+}
+
+fn quarantine_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("quarantine.json"))
+}
+
+fn read_quarantine() -> Result<QuarantineFile, String> {
+    let path = quarantine_path()?;
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse quarantine list: {}", e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(QuarantineFile::default()),
+        Err(e) => Err(format!("Failed to read quarantine list: {}", e)),
+    }
+}
+
+fn write_quarantine(file: &QuarantineFile) -> Result<(), String> {
+    let path = quarantine_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(file)
+        .map_err(|e| format!("Failed to serialize quarantine list: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write quarantine list: {}", e))
+}
+
+/// Claude Code project, or the config path of a project-level client
+pub(crate) fn target_for(client: Option<&str>, working_dir: &str) -> ClientTarget {
+    match client {
+        None | Some("claude_code") => ClientTarget::claude_code(working_dir),
+        Some(client) => {
+            let path = (working_dir != GLOBAL_PROJECT_ID && !working_dir.is_empty())
+                .then_some(working_dir);
+            ClientTarget::client(client, path)
+        }
+    }
+}
+
+/// Fails if `name` is quarantined in `target`; called before every enable, add and update.
+/// `action` is what gets refused, e.g. "enabling".
+pub(crate) fn ensure_not_quarantined(
+    target: &ClientTarget,
+    name: &str,
+    action: &str,
+) -> Result<(), String> {
+    let file = read_quarantine()?;
+    match file
+        .servers
+        .iter()
+        .find(|r| r.target == *target && r.name == name)
+    {
+        Some(record) => Err(format!(
+            "Server '{}' is quarantined ({}); clear the quarantine before {} it",
+            name, record.reason, action
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Disable a server and block re-enabling it until the quarantine is cleared. `client`
+/// defaults to Claude Code; for other clients `working_dir` is the project config path.
+#[command]
+pub async fn quarantine_server(
+    name: String,
+    working_dir: String,
+    reason: String,
+    client: Option<String>,
+) -> Result<QuarantineRecord, String> {
+    let reason = reason.trim().to_string();
+    if reason.is_empty() {
+        return Err("A reason is required to quarantine a server".to_string());
+    }
+    let target = target_for(client.as_deref(), &working_dir);
+    let disabled = client_target::list_disabled_servers(&target).await?;
+    if !disabled.contains_key(&name) {
+        if !client_target::list_servers(&target)
+            .await?
+            .contains_key(&name)
+        {
+            return Err(format!("Server '{}' not found", name));
+        }
+        if target.is_claude_code() {
+            claude_disabled::disable_claude_server(target.working_dir(), &name).await?;
+        } else {
+            ClientAdapter::new(&target.client, target.path.as_deref())
+                .disable(name.clone())
+                .await?;
+        }
+        op_recorder::record(
+            target.clone(),
+            RecordedOp::DisableServer { name: name.clone() },
+        );
+    }
+
+    let record = QuarantineRecord {
+        target: target.clone(),
+        name: name.clone(),
+        reason: reason.clone(),
+        quarantined_at: Utc::now().to_rfc3339(),
+    };
+    let mut file = read_quarantine()?;
+    file.servers
+        .retain(|r| !(r.target == target && r.name == name));
+    file.servers.push(record.clone());
+    write_quarantine(&file)?;
+    audit_log::log("quarantine", &target, Some(&name), Some(reason), Vec::new());
+    println!("[Quarantine] {} quarantined in {}", name, target.label());
+    Ok(record)
+}
+
+/// Lift a quarantine so the server can be enabled again; the server itself stays disabled
+#[command]
+pub async fn clear_quarantine(
+    name: String,
+    working_dir: String,
+    client: Option<String>,
+) -> Result<bool, String> {
+    let target = target_for(client.as_deref(), &working_dir);
+    let mut file = read_quarantine()?;
+    let before = file.servers.len();
+    file.servers
+        .retain(|r| !(r.target == target && r.name == name));
+    if file.servers.len() == before {
+        return Ok(false);
+    }
+    write_quarantine(&file)?;
+    audit_log::log("clear_quarantine", &target, Some(&name), None, Vec::new());
+    Ok(true)
+}
+
+#[command]
+pub async fn list_quarantined() -> Result<Vec<QuarantineRecord>, String> {
+    Ok(read_quarantine()?.servers)
+}
@@ -0,0 +1,72 @@
+// Quarantine tests
+use crate::client_target::{self, ClientTarget};
+use crate::mcp_commands::enable_mcp_server;
+use crate::quarantine::{clear_quarantine, list_quarantined, quarantine_server};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_quarantine_requires_a_reason() {
+    let result = quarantine_server(
+        "fs".to_string(),
+        "Global".to_string(),
+        "  ".to_string(),
+        None,
+    )
+    .await;
+    assert!(result.unwrap_err().contains("reason is required"));
+}
+
+#[tokio::test]
+async fn test_quarantine_blocks_enable_and_upsert_until_cleared() {
+    let dir = tempfile::tempdir().unwrap();
+    let project = dir.path().to_str().unwrap().to_string();
+    let config_path = dir.path().join(".cursor/mcp.json");
+    std::fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+    let config = json!({ "command": "npx", "args": ["suspicious-server"] });
+    std::fs::write(
+        &config_path,
+        json!({ "mcpServers": { "quarantine-test": config } }).to_string(),
+    )
+    .unwrap();
+    let name = "quarantine-test".to_string();
+    let target = ClientTarget::client("cursor", Some(project.as_str()));
+
+    let record = quarantine_server(
+        name.clone(),
+        project.clone(),
+        "Unexpected network access".to_string(),
+        Some("cursor".to_string()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(record.target, target);
+    assert!(client_target::list_disabled_servers(&target)
+        .await
+        .unwrap()
+        .contains_key(&name));
+
+    let enabled = enable_mcp_server("cursor".to_string(), Some(project.clone()), name.clone());
+    assert!(enabled.await.unwrap_err().contains("is quarantined"));
+    let upserted = client_target::upsert_server(&target, &name, config.clone()).await;
+    assert!(upserted.unwrap_err().contains("is quarantined"));
+
+    let cleared = clear_quarantine(name.clone(), project.clone(), Some("cursor".to_string()));
+    assert!(cleared.await.unwrap());
+    assert!(!list_quarantined()
+        .await
+        .unwrap()
+        .iter()
+        .any(|r| r.target == target && r.name == name));
+    // Clearing lifts the block without enabling the server
+    assert!(!client_target::list_servers(&target)
+        .await
+        .unwrap()
+        .contains_key(&name));
+    assert!(client_target::list_disabled_servers(&target)
+        .await
+        .unwrap()
+        .contains_key(&name));
+    client_target::upsert_server(&target, &name, config)
+        .await
+        .unwrap();
+}
@@ -0,0 +1,171 @@
+//! Secret detection and redaction shared by export, install defaults and logging
+//!
+//! A name (env var, header or `--flag`) is secret when it contains one of `SECRET_MARKERS` or
+//! matches one of the `redaction.key_patterns` settings, unless it is listed in
+//! `redaction.allow_keys`. Values are redacted where they have the shape of a well-known token
+//! (`TOKEN_PATTERNS`) or match `redaction.value_patterns`. Everything asks `rules()`, so rules
+//! changed in the settings apply to every feature at once; invalid patterns are refused by
+//! `update_settings`.
+
+use crate::settings::{self, RedactionSettings};
+use regex::{Regex, RegexBuilder};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+pub(crate) const REDACTED: &str = "<redacted>";
+
+const SECRET_MARKERS: [&str; 5] = ["KEY", "TOKEN", "SECRET", "PASSWORD", "AUTH"];
+
+/// GitHub, OpenAI and Anthropic, Slack and AWS keys, and bearer credentials
+const TOKEN_PATTERNS: [&str; 6] = [
+    r"\bgh[pousr]_[A-Za-z0-9]{20,}",
+    r"\bgithub_pat_[A-Za-z0-9_]{20,}",
+    r"\bsk-[A-Za-z0-9_-]{20,}",
+    r"\bxox[abprs]-[A-Za-z0-9-]{10,}",
+    r"\bAKIA[0-9A-Z]{16}\b",
+    r"(?i)\bbearer\s+[A-Za-z0-9._~+/=-]{16,}",
+];
+
+/// Rules compiled for the settings they were built from
+static CACHE: Mutex<Option<(RedactionSettings, Arc<Rules>)>> = Mutex::new(None);
+
+#[derive(Debug)]
+pub(crate) struct Rules {
+    key_patterns: Vec<Regex>,
+    value_patterns: Vec<Regex>,
+    /// Uppercased
+    allow_keys: HashSet<String>,
+}
+
+fn compile_all<'a>(
+    patterns: impl IntoIterator<Item = &'a str>,
+    case_insensitive: bool,
+) -> Result<Vec<Regex>, String> {
+    patterns
+        .into_iter()
+        .map(|pattern| {
+            RegexBuilder::new(pattern)
+                .case_insensitive(case_insensitive)
+                .build()
+                .map_err(|e| format!("Invalid redaction pattern '{}': {}", pattern, e))
+        })
+        .collect()
+}
+
+impl Rules {
+    pub(crate) fn compile(settings: &RedactionSettings) -> Result<Rules, String> {
+        let value_patterns = TOKEN_PATTERNS
+            .iter()
+            .copied()
+            .chain(settings.value_patterns.iter().map(String::as_str));
+        Ok(Rules {
+            key_patterns: compile_all(settings.key_patterns.iter().map(String::as_str), true)?,
+            value_patterns: compile_all(value_patterns, false)?,
+            allow_keys: settings
+                .allow_keys
+                .iter()
+                .map(|key| key.trim().to_ascii_uppercase())
+                .collect(),
+        })
+    }
+
+    /// Whether values of the env var, header or flag `name` are secret
+    pub(crate) fn is_secret_key(&self, name: &str) -> bool {
+        let name = name.trim_start_matches('-');
+        let upper = name.to_ascii_uppercase();
+        if self.allow_keys.contains(&upper) {
+            return false;
+        }
+        SECRET_MARKERS.iter().any(|m| upper.contains(m))
+            || self.key_patterns.iter().any(|p| p.is_match(name))
+    }
+
+    /// `text` with every token-shaped value replaced by `REDACTED`
+    pub(crate) fn redact_text(&self, text: &str) -> String {
+        self.value_patterns
+            .iter()
+            .fold(text.to_string(), |text, pattern| {
+                pattern.replace_all(&text, REDACTED).into_owned()
+            })
+    }
+
+    /// Args with the values of secret flags (`--api-key=x`, `--api-key x`) and token-shaped
+    /// values redacted
+    fn redact_args(&self, args: &[Value]) -> Vec<Value> {
+        let mut redacted = Vec::with_capacity(args.len());
+        let mut after_secret_flag = false;
+        for arg in args {
+            let Some(text) = arg.as_str() else {
+                redacted.push(arg.clone());
+                continue;
+            };
+            let value = match text.split_once('=') {
+                _ if after_secret_flag => REDACTED.to_string(),
+                Some((flag, _)) if flag.starts_with('-') && self.is_secret_key(flag) => {
+                    format!("{}={}", flag, REDACTED)
+                }
+                _ => self.redact_text(text),
+            };
+            after_secret_flag =
+                text.starts_with('-') && !text.contains('=') && self.is_secret_key(text);
+            redacted.push(Value::String(value));
+        }
+        redacted
+    }
+
+    /// A client config entry with secret env and header values, secret args and token-shaped
+    /// values in the URL replaced by `REDACTED`
+    pub(crate) fn redact_config(&self, config: &Value) -> Value {
+        let mut config = config.clone();
+        for field in ["env", "headers"] {
+            if let Some(values) = config.get_mut(field).and_then(Value::as_object_mut) {
+                for (name, value) in values.iter_mut() {
+                    if let Some(text) = value.as_str() {
+                        *value = Value::String(if self.is_secret_key(name) {
+                            REDACTED.to_string()
+                        } else {
+                            self.redact_text(text)
+                        });
+                    }
+                }
+            }
+        }
+        if let Some(args) = config.get("args").and_then(Value::as_array) {
+            config["args"] = Value::Array(self.redact_args(args));
+        }
+        if let Some(url) = config.get("url").and_then(Value::as_str) {
+            config["url"] = Value::String(self.redact_text(url));
+        }
+        config
+    }
+}
+
+/// Rules of the current settings. Settings saved before a pattern became invalid fall back
+/// to the built-in rules.
+pub(crate) fn rules() -> Arc<Rules> {
+    let redaction = settings::load_settings().redaction;
+    if let Ok(cache) = CACHE.lock() {
+        if let Some((cached_for, rules)) = cache.as_ref() {
+            if *cached_for == redaction {
+                return rules.clone();
+            }
+        }
+    }
+    let rules = Arc::new(Rules::compile(&redaction).unwrap_or_else(|e| {
+        println!("[Redaction] {}; using the built-in rules", e);
+        Rules::compile(&RedactionSettings::default()).expect("built-in patterns compile")
+    }));
+    if let Ok(mut cache) = CACHE.lock() {
+        *cache = Some((redaction, rules.clone()));
+    }
+    rules
+}
+
+pub(crate) fn is_secret_key(name: &str) -> bool {
+    rules().is_secret_key(name)
+}
+
+pub(crate) fn redact_config(config: &Value) -> Value {
+    rules().redact_config(config)
+}
@@ -0,0 +1,226 @@
+//! Export client config entries as MCP registry `server.json` manifests
+//!
+//! The inverse of `registry_import`: a launch command is mapped back to the package it runs
+//! (`npx` -> npm, `uvx` -> pypi, `docker run` -> oci) and a URL to a remote. Env var and
+//! header values are never exported, only their names, since they usually hold secrets.
+
+use crate::redaction;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use tauri::command;
+
+pub const SERVER_SCHEMA_URL: &str =
+    "https://static.modelcontextprotocol.io/schemas/2025-09-29/server.schema.json";
+
+/// Registry limit on the description length
+const MAX_DESCRIPTION_CHARS: usize = 100;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ManifestRequest {
+    /// Local server name
+    pub name: String,
+    /// Client config entry (command/args/env or url/headers)
+    pub config: Value,
+    /// Reverse-DNS registry name, e.g. io.github.user/weather
+    #[serde(default)]
+    pub registry_name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub repository_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ManifestResult {
+    pub name: String,
+    pub manifest: Value,
+    /// Fields the registry will reject or that had to be guessed
+    pub warnings: Vec<String>,
+}
+
+/// Split `pkg@1.2.3` (npm, scoped names keep their leading @), `pkg==1.2.3` or `image:tag`
+fn split_version<'a>(spec: &'a str, separator: &str) -> (&'a str, Option<&'a str>) {
+    let search_from = if separator == "@" && spec.starts_with('@') {
+        1
+    } else {
+        0
+    };
+    match spec[search_from..].rfind(separator) {
+        Some(idx) => {
+            let idx = idx + search_from;
+            (&spec[..idx], Some(&spec[idx + separator.len()..]))
+        }
+        None => (spec, None),
+    }
+}
+
+fn env_variables(config: &Value) -> Vec<Value> {
+    let rules = redaction::rules();
+    config
+        .get("env")
+        .and_then(|e| e.as_object())
+        .into_iter()
+        .flatten()
+        .map(|(name, _)| {
+            json!({
+                "name": name,
+                "isRequired": true,
+                "isSecret": rules.is_secret_key(name),
+            })
+        })
+        .collect()
+}
+
+fn package_entry(config: &Value, warnings: &mut Vec<String>) -> Option<(Value, Option<String>)> {
+    let command = config.get("command")?.as_str()?;
+    let args: Vec<&str> = config
+        .get("args")
+        .and_then(|a| a.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|a| a.as_str())
+        .collect();
+
+    let (registry_type, spec_index, separator) = match command {
+        "npx" => ("npm", args.iter().position(|a| !a.starts_with('-'))?, "@"),
+        "uvx" => ("pypi", args.iter().position(|a| !a.starts_with('-'))?, "=="),
+        "docker" => {
+            // Image is the first non-flag after `run`
+            let run = args.iter().position(|a| *a == "run")?;
+            let image = args[run + 1..].iter().position(|a| !a.starts_with('-'))?;
+            ("oci", run + 1 + image, ":")
+        }
+        other => {
+            warnings.push(format!(
+                "command '{}' is not an npm, pypi or oci launcher and cannot be published",
+                other
+            ));
+            return None;
+        }
+    };
+    let (identifier, version) = split_version(args[spec_index], separator);
+
+    let mut package = json!({
+        "registryType": registry_type,
+        "identifier": identifier,
+        "transport": { "type": "stdio" },
+    });
+    if let Some(version) = version {
+        package["version"] = json!(version);
+    }
+    let package_arguments: Vec<Value> = args[spec_index + 1..]
+        .iter()
+        .map(|a| json!({ "type": "positional", "value": a }))
+        .collect();
+    if !package_arguments.is_empty() {
+        package["packageArguments"] = Value::Array(package_arguments);
+    }
+    let env = env_variables(config);
+    if !env.is_empty() {
+        package["environmentVariables"] = Value::Array(env);
+    }
+    Some((package, version.map(|v| v.to_string())))
+}
+
+fn remote_entry(config: &Value) -> Option<Value> {
+    let url = config.get("url")?.as_str()?;
+    let transport = match config.get("type").and_then(|t| t.as_str()) {
+        Some("sse") => "sse",
+        _ => "streamable-http",
+    };
+    let rules = redaction::rules();
+    let mut remote = json!({ "type": transport, "url": rules.redact_text(url) });
+    let headers: Vec<Value> = config
+        .get("headers")
+        .and_then(|h| h.as_object())
+        .into_iter()
+        .flatten()
+        .map(|(name, _)| {
+            json!({ "name": name, "isRequired": true, "isSecret": rules.is_secret_key(name) })
+        })
+        .collect();
+    if !headers.is_empty() {
+        remote["headers"] = Value::Array(headers);
+    }
+    Some(remote)
+}
+
+pub fn build_manifest(request: &ManifestRequest) -> ManifestResult {
+    let mut warnings = Vec::new();
+    let mut manifest = Map::new();
+    manifest.insert("$schema".to_string(), json!(SERVER_SCHEMA_URL));
+
+    let registry_name = request.registry_name.clone().unwrap_or_else(|| {
+        warnings.push("registry_name not set; use a reverse-DNS name you own".to_string());
+        format!("io.github.<user>/{}", request.name)
+    });
+    if registry_name.matches('/').count() != 1 {
+        warnings.push("registry name must be '<namespace>/<name>'".to_string());
+    }
+    manifest.insert("name".to_string(), json!(registry_name));
+
+    let description = request.description.clone().unwrap_or_default();
+    if description.is_empty() {
+        warnings.push("description is required".to_string());
+    } else if description.chars().count() > MAX_DESCRIPTION_CHARS {
+        warnings.push(format!(
+            "description exceeds {} characters",
+            MAX_DESCRIPTION_CHARS
+        ));
+    }
+    manifest.insert("description".to_string(), json!(description));
+
+    let (package, package_version) = match package_entry(&request.config, &mut warnings) {
+        Some((package, version)) => (Some(package), version),
+        None => (None, None),
+    };
+    let version = request.version.clone().or(package_version);
+    match version.as_deref() {
+        None => warnings.push("version is required".to_string()),
+        Some("latest") => warnings.push("'latest' is not a valid registry version".to_string()),
+        Some(_) => {}
+    }
+    manifest.insert(
+        "version".to_string(),
+        json!(version.unwrap_or_else(|| "0.0.0".to_string())),
+    );
+
+    if let Some(url) = &request.repository_url {
+        let source = if url.contains("github.com") {
+            "github"
+        } else if url.contains("gitlab.com") {
+            "gitlab"
+        } else {
+            "git"
+        };
+        manifest.insert(
+            "repository".to_string(),
+            json!({ "url": url, "source": source }),
+        );
+    }
+    if let Some(package) = package {
+        manifest.insert("packages".to_string(), json!([package]));
+    }
+    if let Some(remote) = remote_entry(&request.config) {
+        manifest.insert("remotes".to_string(), json!([remote]));
+    }
+    if !manifest.contains_key("packages") && !manifest.contains_key("remotes") {
+        warnings.push("server has neither a publishable package nor a remote URL".to_string());
+    }
+
+    ManifestResult {
+        name: request.name.clone(),
+        manifest: Value::Object(manifest),
+        warnings,
+    }
+}
+
+/// Build a server.json manifest for each server
+#[command]
+pub async fn export_registry_manifest(
+    servers: Vec<ManifestRequest>,
+) -> Result<Vec<ManifestResult>, String> {
+    Ok(servers.iter().map(build_manifest).collect())
+}
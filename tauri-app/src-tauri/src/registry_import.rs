@@ -0,0 +1,754 @@
+//! Import servers advertised by an MCP registry / gateway
+//!
+//! Sources speak the MCP registry API (`GET /v0/servers`, cursor paginated). Each advertised
+//! server is converted to an `mcpServers` entry (remote URL first, otherwise an npx / uvx /
+//! docker launch of its package) and upserted into the source's targets. Sources with a refresh
+//! interval are re-imported in the background; servers that disappear upstream are reported
+//! as stale but never deleted automatically.
+//!
+//! Refreshes are incremental: the raw entries of the last fetch are cached per registry, and
+//! the next one only asks for servers updated since (`updated_since`, with `If-Modified-Since`
+//! so unchanged registries can answer 304), over a gzip-compressed connection. Each fetched
+//! page is reported as a `registry_refresh_progress` event.
+
+use crate::auth_profiles;
+use crate::catalog_locale;
+use crate::client_target::{self, ClientTarget};
+use crate::event_bus::{self, AppEventKind, EventScope};
+use crate::metrics;
+use crate::network;
+use crate::settings::{self, app_config_dir};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::path::PathBuf;
+use tauri::{command, AppHandle, Runtime};
+
+/// Upper bound on pages fetched from one registry
+const MAX_PAGES: usize = 50;
+
+const REFRESH_CHECK_SECS: u64 = 5 * 60;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RegistrySource {
+    /// Base URL, e.g. https://registry.example.com
+    pub url: String,
+    /// Auth profile whose headers are sent to the registry
+    #[serde(default)]
+    pub auth_profile: Option<String>,
+    pub targets: Vec<ClientTarget>,
+    /// Re-import interval; manual only when unset
+    #[serde(default)]
+    pub refresh_minutes: Option<u64>,
+    #[serde(default)]
+    pub last_synced_at: Option<String>,
+    /// Server names written by the last import
+    #[serde(default)]
+    pub imported: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(default)]
+struct RegistrySourcesFile {
+    sources: Vec<RegistrySource>,
+}
+
+/// A registry entry converted to client config
+#[derive(Debug, Serialize, Clone)]
+pub struct RegistryServer {
+    /// Local server name (last segment of the registry name)
+    pub name: String,
+    pub registry_name: String,
+    /// Display name, when the registry gives one
+    pub title: Option<String>,
+    pub description: Option<String>,
+    /// Translation `title` and `description` are in (see `catalog_locale`); `None` for the
+    /// registry's own text
+    pub locale: Option<String>,
+    pub version: Option<String>,
+    pub config: Value,
+    /// Every remote the registry lists; the first one is used in `config`
+    pub endpoints: Vec<RemoteEndpoint>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct RemoteEndpoint {
+    pub url: String,
+    /// sse or http
+    pub transport: String,
+    /// Region label, when the registry gives one
+    pub region: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RegistryImportReport {
+    pub url: String,
+    pub imported: Vec<String>,
+    /// Servers from the previous import that the registry no longer advertises
+    pub stale: Vec<String>,
+    pub skipped: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+fn sources_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("registries.json"))
+}
+
+fn read_sources() -> Result<RegistrySourcesFile, String> {
+    let path = sources_path()?;
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse registry sources: {}", e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(RegistrySourcesFile::default()),
+        Err(e) => Err(format!("Failed to read registry sources: {}", e)),
+    }
+}
+
+fn write_sources(file: &RegistrySourcesFile) -> Result<(), String> {
+    let path = sources_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(file)
+        .map_err(|e| format!("Failed to serialize registry sources: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write registry sources: {}", e))
+}
+
+/// Progress of a registry fetch, emitted per page as a `registry_refresh_progress` event
+#[derive(Debug, Serialize, Clone)]
+pub struct RefreshProgress {
+    pub url: String,
+    pub page: usize,
+    /// Entries received so far
+    pub fetched: usize,
+    /// Whether only changes since the last refresh are fetched
+    pub delta: bool,
+}
+
+/// Raw entries of the last fetch per registry, so refreshes only fetch what changed
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct RegistryCache {
+    url: String,
+    /// When the last fetch started; the next one asks for updates since then
+    fetched_at: String,
+    entries: Vec<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(default)]
+struct RegistryCacheFile {
+    registries: Vec<RegistryCache>,
+}
+
+fn cache_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("registry_cache.json"))
+}
+
+fn read_cache() -> Result<RegistryCacheFile, String> {
+    let path = cache_path()?;
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse registry cache: {}", e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(RegistryCacheFile::default()),
+        Err(e) => Err(format!("Failed to read registry cache: {}", e)),
+    }
+}
+
+fn write_cache(file: &RegistryCacheFile) -> Result<(), String> {
+    let path = cache_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let content = serde_json::to_string(file)
+        .map_err(|e| format!("Failed to serialize registry cache: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write registry cache: {}", e))
+}
+
+/// Entries the registry returned, or `None` when it answered 304 Not Modified
+async fn fetch_entries(
+    url: &str,
+    auth_profile: Option<&str>,
+    updated_since: Option<&str>,
+    progress: &(dyn Fn(RefreshProgress) + Send + Sync),
+) -> Result<Option<Vec<Value>>, String> {
+    let headers = match auth_profile {
+        Some(profile) => auth_profiles::profile_headers(profile)?,
+        None => Default::default(),
+    };
+    let network = settings::load_settings().network;
+    let client = network::http_client(&network)?;
+    let endpoint = format!("{}/v0/servers", url.trim_end_matches('/'));
+    let mut entries = Vec::new();
+    let mut cursor: Option<String> = None;
+
+    let modified_since = updated_since
+        .and_then(|since| DateTime::parse_from_rfc3339(since).ok())
+        .map(|since| {
+            since
+                .with_timezone(&Utc)
+                .format("%a, %d %b %Y %H:%M:%S GMT")
+                .to_string()
+        });
+
+    for page_number in 1..=MAX_PAGES {
+        let build = || {
+            let mut request = client.get(&endpoint).query(&[("limit", "100")]);
+            if let Some(since) = updated_since {
+                request = request.query(&[("updated_since", since)]);
+            }
+            if let (1, Some(date)) = (page_number, &modified_since) {
+                request = request.header(reqwest::header::IF_MODIFIED_SINCE, date);
+            }
+            if let Some(cursor) = &cursor {
+                request = request.query(&[("cursor", cursor)]);
+            }
+            for (name, value) in &headers {
+                request = request.header(name, value);
+            }
+            request
+        };
+        let response = network::send_with_retry(&network.retry, build)
+            .await
+            .map_err(|e| format!("Failed to reach registry: {}", e))?;
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(format!("Registry returned {}", response.status()));
+        }
+        let page: Value = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse registry response: {}", e))?;
+
+        entries.extend(
+            page.get("servers")
+                .and_then(|s| s.as_array())
+                .into_iter()
+                .flatten()
+                .cloned(),
+        );
+        progress(RefreshProgress {
+            url: url.to_string(),
+            page: page_number,
+            fetched: entries.len(),
+            delta: updated_since.is_some(),
+        });
+
+        cursor = page
+            .pointer("/metadata/nextCursor")
+            .or_else(|| page.pointer("/metadata/next_cursor"))
+            .and_then(|c| c.as_str())
+            .filter(|c| !c.is_empty())
+            .map(|c| c.to_string());
+        if cursor.is_none() {
+            break;
+        }
+    }
+    Ok(Some(entries))
+}
+
+/// Raw entries of every registry fetched so far, across sources
+pub(crate) fn cached_entries() -> Vec<Value> {
+    read_cache()
+        .map(|file| {
+            file.registries
+                .into_iter()
+                .flat_map(|r| r.entries)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn entry_name(entry: &Value) -> Option<&str> {
+    entry.get("server").unwrap_or(entry).get("name")?.as_str()
+}
+
+fn is_deleted(entry: &Value) -> bool {
+    let server = entry.get("server").unwrap_or(entry);
+    [
+        server.get("status"),
+        entry.pointer("/_meta/io.modelcontextprotocol.registry~1official/status"),
+    ]
+    .iter()
+    .any(|status| status.and_then(|s| s.as_str()) == Some("deleted"))
+}
+
+/// Apply changed entries to the cached ones: updates replace the entry of the same name,
+/// deleted entries are dropped
+pub(crate) fn merge_entries(mut cached: Vec<Value>, updates: Vec<Value>) -> Vec<Value> {
+    for update in updates {
+        let Some(name) = entry_name(&update).map(|n| n.to_string()) else {
+            continue;
+        };
+        cached.retain(|entry| entry_name(entry) != Some(name.as_str()));
+        if !is_deleted(&update) {
+            cached.push(update);
+        }
+    }
+    cached
+}
+
+fn convert_entries(entries: &[Value]) -> Vec<RegistryServer> {
+    let locale = catalog_locale::app_locale();
+    entries
+        .iter()
+        .filter(|entry| !is_deleted(entry))
+        .filter_map(|entry| {
+            convert_entry(entry).map(|server| catalog_locale::localize(server, entry, &locale))
+        })
+        .collect()
+}
+
+/// Fetch every server the registry advertises
+pub(crate) async fn fetch_servers(
+    url: &str,
+    auth_profile: Option<&str>,
+) -> Result<Vec<RegistryServer>, String> {
+    let entries = fetch_entries(url, auth_profile, None, &|_| {})
+        .await?
+        .unwrap_or_default();
+    Ok(convert_entries(&entries))
+}
+
+/// Like `fetch_servers`, but only asks for entries changed since the last refresh and merges
+/// them into the cached ones. Registries that ignore `updated_since` return everything, which
+/// merges just the same.
+pub(crate) async fn refresh_servers(
+    source: &RegistrySource,
+    progress: &(dyn Fn(RefreshProgress) + Send + Sync),
+) -> Result<Vec<RegistryServer>, String> {
+    let mut file = read_cache().unwrap_or_default();
+    let cached = file.registries.iter().position(|c| c.url == source.url);
+    let started = Utc::now().to_rfc3339();
+    let since = cached.map(|i| file.registries[i].fetched_at.clone());
+    let fetched = fetch_entries(
+        &source.url,
+        source.auth_profile.as_deref(),
+        since.as_deref(),
+        progress,
+    )
+    .await?;
+    let entries = match (cached, fetched) {
+        (Some(i), Some(updates)) => merge_entries(file.registries[i].entries.clone(), updates),
+        (Some(i), None) => file.registries[i].entries.clone(),
+        (None, fetched) => fetched.unwrap_or_default(),
+    };
+    let servers = convert_entries(&entries);
+    let cache = RegistryCache {
+        url: source.url.clone(),
+        fetched_at: started,
+        entries,
+    };
+    match cached {
+        Some(i) => file.registries[i] = cache,
+        None => file.registries.push(cache),
+    }
+    if let Err(e) = write_cache(&file) {
+        println!("[Registry] {}", e);
+    }
+    Ok(servers)
+}
+
+/// Convert one registry entry. Newer registries wrap the server.json under `server`.
+pub(crate) fn convert_entry(entry: &Value) -> Option<RegistryServer> {
+    let server = entry.get("server").unwrap_or(entry);
+    let registry_name = server.get("name")?.as_str()?.to_string();
+    let name = registry_name
+        .rsplit('/')
+        .next()
+        .unwrap_or(&registry_name)
+        .to_string();
+    let config = remote_config(server).or_else(|| package_config(server))?;
+    Some(RegistryServer {
+        name,
+        registry_name,
+        title: server
+            .get("title")
+            .and_then(|t| t.as_str())
+            .map(|t| t.to_string()),
+        description: server
+            .get("description")
+            .and_then(|d| d.as_str())
+            .map(|d| d.to_string()),
+        version: server
+            .get("version")
+            .or_else(|| server.pointer("/version_detail/version"))
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string()),
+        locale: None,
+        config,
+        endpoints: remote_endpoints(server),
+    })
+}
+
+fn remote_transport(remote: &Value) -> &'static str {
+    match remote.get("type").and_then(|t| t.as_str()) {
+        Some("sse") => "sse",
+        _ => "http",
+    }
+}
+
+pub(crate) fn remote_endpoints(server: &Value) -> Vec<RemoteEndpoint> {
+    server
+        .get("remotes")
+        .and_then(|r| r.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|remote| {
+            Some(RemoteEndpoint {
+                url: remote.get("url")?.as_str()?.to_string(),
+                transport: remote_transport(remote).to_string(),
+                region: remote
+                    .get("region")
+                    .or_else(|| remote.pointer("/_meta/region"))
+                    .and_then(|r| r.as_str())
+                    .map(|r| r.to_string()),
+            })
+        })
+        .collect()
+}
+
+fn remote_config(server: &Value) -> Option<Value> {
+    remote_entry(server.get("remotes")?.as_array()?.first()?)
+}
+
+/// Client config for one of a server's `remotes`
+pub(crate) fn remote_entry(remote: &Value) -> Option<Value> {
+    let url = remote.get("url")?.as_str()?;
+    let transport = remote_transport(remote);
+    let mut config = json!({ "type": transport, "url": url });
+    let headers: Map<String, Value> = remote
+        .get("headers")
+        .and_then(|h| h.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|h| {
+            let name = h.get("name")?.as_str()?;
+            let value = h.get("value").and_then(|v| v.as_str()).unwrap_or("");
+            Some((name.to_string(), Value::String(value.to_string())))
+        })
+        .collect();
+    if !headers.is_empty() {
+        config["headers"] = Value::Object(headers);
+    }
+    Some(config)
+}
+
+/// Client config launching the server's first package locally
+pub(crate) fn package_config(server: &Value) -> Option<Value> {
+    let package = server.get("packages")?.as_array()?.first()?;
+    let identifier = package
+        .get("identifier")
+        .or_else(|| package.get("name"))?
+        .as_str()?;
+    let version = package.get("version").and_then(|v| v.as_str());
+    let registry_type = package
+        .get("registryType")
+        .or_else(|| package.get("registry_name"))
+        .and_then(|r| r.as_str())
+        .unwrap_or("npm");
+
+    let (command, mut args) = match registry_type {
+        "pypi" => (
+            "uvx",
+            vec![version.map_or(identifier.to_string(), |v| format!("{}=={}", identifier, v))],
+        ),
+        "oci" | "docker" => (
+            "docker",
+            vec![
+                "run".to_string(),
+                "-i".to_string(),
+                "--rm".to_string(),
+                version.map_or(identifier.to_string(), |v| format!("{}:{}", identifier, v)),
+            ],
+        ),
+        _ => (
+            "npx",
+            vec![
+                "-y".to_string(),
+                version.map_or(identifier.to_string(), |v| format!("{}@{}", identifier, v)),
+            ],
+        ),
+    };
+    for arg in package
+        .get("packageArguments")
+        .or_else(|| package.get("package_arguments"))
+        .and_then(|a| a.as_array())
+        .into_iter()
+        .flatten()
+    {
+        if let Some(flag) = arg.get("name").and_then(|n| n.as_str()) {
+            if arg.get("type").and_then(|t| t.as_str()) == Some("named") {
+                args.push(flag.to_string());
+            }
+        }
+        if let Some(value) = arg
+            .get("value")
+            .or_else(|| arg.get("default"))
+            .and_then(|v| v.as_str())
+        {
+            args.push(value.to_string());
+        }
+    }
+
+    let env: Map<String, Value> = package
+        .get("environmentVariables")
+        .or_else(|| package.get("environment_variables"))
+        .and_then(|e| e.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|var| {
+            let name = var.get("name")?.as_str()?;
+            let value = var
+                .get("value")
+                .or_else(|| var.get("default"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            Some((name.to_string(), Value::String(value.to_string())))
+        })
+        .collect();
+
+    let mut config = json!({ "type": "stdio", "command": command, "args": args });
+    if !env.is_empty() {
+        config["env"] = Value::Object(env);
+    }
+    Some(config)
+}
+
+/// `advertised` for a server a target may already have. Env vars and headers keep the values
+/// set locally, so refreshes don't wipe keys and tokens with the registry's blank defaults;
+/// only keys the registry adds are taken. Fields the registry doesn't advertise (timeouts,
+/// trust flags) are kept as well.
+pub(crate) fn merge_with_local(local: Option<&Value>, advertised: &Value) -> Value {
+    let Some(local) = local.and_then(Value::as_object) else {
+        return advertised.clone();
+    };
+    let mut merged = local.clone();
+    for (key, value) in advertised.as_object().into_iter().flatten() {
+        if matches!(key.as_str(), "env" | "headers") {
+            if let Some(current) = merged.get_mut(key).and_then(Value::as_object_mut) {
+                for (name, default) in value.as_object().into_iter().flatten() {
+                    current
+                        .entry(name.clone())
+                        .or_insert_with(|| default.clone());
+                }
+                continue;
+            }
+        }
+        merged.insert(key.clone(), value.clone());
+    }
+    Value::Object(merged)
+}
+
+async fn import_into(
+    source: &RegistrySource,
+    names: Option<&[String]>,
+    progress: &(dyn Fn(RefreshProgress) + Send + Sync),
+) -> Result<RegistryImportReport, String> {
+    let servers = refresh_servers(source, progress).await?;
+    let mut report = RegistryImportReport {
+        url: source.url.clone(),
+        imported: Vec::new(),
+        stale: Vec::new(),
+        skipped: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    let mut targets = Vec::new();
+    for target in &source.targets {
+        match client_target::list_servers(target).await {
+            Ok(local) => targets.push((target, local)),
+            // Writing blind could overwrite values set locally
+            Err(e) => report.errors.push(format!("{}: {}", target.label(), e)),
+        }
+    }
+
+    for server in &servers {
+        if names.is_some_and(|names| !names.contains(&server.name)) {
+            report.skipped.push(server.name.clone());
+            continue;
+        }
+        let mut written = None;
+        for (target, local) in &targets {
+            let config = merge_with_local(local.get(&server.name), &server.config);
+            match client_target::upsert_server(target, &server.name, config).await {
+                Ok(name) => written = Some(name),
+                Err(e) => {
+                    report
+                        .errors
+                        .push(format!("{} -> {}: {}", server.name, target.label(), e))
+                }
+            }
+        }
+        if let Some(name) = written {
+            report.imported.push(name);
+        }
+    }
+
+    let advertised: Vec<&String> = servers.iter().map(|s| &s.name).collect();
+    report.stale = source
+        .imported
+        .iter()
+        .filter(|name| !advertised.contains(name))
+        .cloned()
+        .collect();
+    Ok(report)
+}
+
+pub(crate) fn find_source(url: &str) -> Result<RegistrySource, String> {
+    read_sources()?
+        .sources
+        .into_iter()
+        .find(|s| s.url == url)
+        .ok_or_else(|| format!("Registry source '{}' not found", url))
+}
+
+/// Run an import for the stored source at `url` and record the result
+async fn sync_source(
+    url: &str,
+    names: Option<&[String]>,
+    progress: &(dyn Fn(RefreshProgress) + Send + Sync),
+) -> Result<RegistryImportReport, String> {
+    let source = find_source(url)?;
+    let report = import_into(&source, names, progress).await?;
+
+    let mut file = read_sources()?;
+    if let Some(stored) = file.sources.iter_mut().find(|s| s.url == url) {
+        stored.last_synced_at = Some(Utc::now().to_rfc3339());
+        for name in &report.imported {
+            if !stored.imported.contains(name) {
+                stored.imported.push(name.clone());
+            }
+        }
+        write_sources(&file)?;
+    }
+    Ok(report)
+}
+
+/// Preview what a registry advertises without writing anything
+#[command]
+pub async fn fetch_registry_servers(
+    url: String,
+    auth_profile: Option<String>,
+) -> Result<Vec<RegistryServer>, String> {
+    fetch_servers(&url, auth_profile.as_deref()).await
+}
+
+#[command]
+pub async fn list_registry_sources() -> Result<Vec<RegistrySource>, String> {
+    Ok(read_sources()?.sources)
+}
+
+/// Add or update a registry source
+#[command]
+pub async fn save_registry_source(
+    url: String,
+    auth_profile: Option<String>,
+    targets: Vec<ClientTarget>,
+    refresh_minutes: Option<u64>,
+) -> Result<RegistrySource, String> {
+    reqwest::Url::parse(&url).map_err(|e| format!("Invalid registry URL: {}", e))?;
+    if targets.is_empty() {
+        return Err("At least one target is required".to_string());
+    }
+    let mut file = read_sources()?;
+    let previous = file.sources.iter().position(|s| s.url == url);
+    let source = RegistrySource {
+        url,
+        auth_profile,
+        targets,
+        refresh_minutes,
+        last_synced_at: previous.and_then(|i| file.sources[i].last_synced_at.clone()),
+        imported: previous
+            .map(|i| file.sources[i].imported.clone())
+            .unwrap_or_default(),
+    };
+    match previous {
+        Some(i) => file.sources[i] = source.clone(),
+        None => file.sources.push(source.clone()),
+    }
+    write_sources(&file)?;
+    Ok(source)
+}
+
+/// Forget a registry source; servers it imported are left in place
+#[command]
+pub async fn remove_registry_source(url: String) -> Result<(), String> {
+    let mut file = read_sources()?;
+    let before = file.sources.len();
+    file.sources.retain(|s| s.url != url);
+    if file.sources.len() == before {
+        return Err(format!("Registry source '{}' not found", url));
+    }
+    write_sources(&file)
+}
+
+/// Import servers from a stored source now; `names` limits the import to those servers
+#[command]
+pub async fn import_registry_servers<R: Runtime>(
+    app: AppHandle<R>,
+    url: String,
+    names: Option<Vec<String>>,
+) -> Result<RegistryImportReport, String> {
+    sync_source(&url, names.as_deref(), &emit_progress(&app)).await
+}
+
+fn emit_progress<R: Runtime>(app: &AppHandle<R>) -> impl Fn(RefreshProgress) + Send + Sync {
+    let app = app.clone();
+    move |progress| {
+        let scope = EventScope::path(&progress.url);
+        event_bus::publish(
+            &app,
+            AppEventKind::RegistryRefreshProgress,
+            scope,
+            &progress,
+        );
+    }
+}
+
+fn is_due(source: &RegistrySource, now: DateTime<Utc>) -> bool {
+    let Some(minutes) = source.refresh_minutes else {
+        return false;
+    };
+    match source
+        .last_synced_at
+        .as_deref()
+        .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+    {
+        Some(last) => now.signed_duration_since(last) >= chrono::Duration::minutes(minutes as i64),
+        None => true,
+    }
+}
+
+/// Start background refresh of sources that have a refresh interval
+pub fn spawn_registry_refresh<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval =
+            tokio::time::interval(std::time::Duration::from_secs(REFRESH_CHECK_SECS));
+        loop {
+            interval.tick().await;
+            let Ok(file) = read_sources() else {
+                continue;
+            };
+            let now = Utc::now();
+            for source in file.sources.iter().filter(|s| is_due(s, now)) {
+                match sync_source(&source.url, None, &emit_progress(&app)).await {
+                    Ok(report) => event_bus::publish(
+                        &app,
+                        AppEventKind::RegistrySynced,
+                        EventScope::path(&source.url),
+                        &report,
+                    ),
+                    Err(e) => {
+                        metrics::inc(metrics::REFRESH_FAILURES, &[("kind", "registry")]);
+                        println!("[Registry] Refresh of {} failed: {}", source.url, e);
+                    }
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,137 @@
+// Registry manifest export/import tests
+use crate::registry_export::{build_manifest, ManifestRequest};
+use crate::registry_import::{convert_entry, merge_entries, merge_with_local};
+use serde_json::json;
+
+fn request(config: serde_json::Value) -> ManifestRequest {
+    ManifestRequest {
+        name: "weather".to_string(),
+        config,
+        registry_name: Some("io.github.example/weather".to_string()),
+        description: Some("Weather forecasts".to_string()),
+        version: None,
+        repository_url: Some("https://github.com/example/weather".to_string()),
+    }
+}
+
+#[test]
+fn test_npx_server_roundtrips_through_manifest() {
+    let config = json!({
+        "type": "stdio",
+        "command": "npx",
+        "args": ["-y", "@example/weather@1.2.0", "--units", "metric"]
+    });
+    let result = build_manifest(&request(config.clone()));
+    assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+    assert_eq!(result.manifest["version"], "1.2.0");
+    assert_eq!(
+        result.manifest["packages"][0]["identifier"],
+        "@example/weather"
+    );
+
+    let imported = convert_entry(&result.manifest).unwrap();
+    assert_eq!(imported.name, "weather");
+    assert_eq!(imported.config, config);
+}
+
+#[test]
+fn test_env_values_are_not_exported() {
+    let config = json!({
+        "command": "uvx",
+        "args": ["weather-mcp==0.3.1"],
+        "env": { "WEATHER_API_KEY": "sk-live-123" }
+    });
+    let result = build_manifest(&request(config));
+    let text = result.manifest.to_string();
+    assert!(!text.contains("sk-live-123"));
+    let env = &result.manifest["packages"][0]["environmentVariables"][0];
+    assert_eq!(env["name"], "WEATHER_API_KEY");
+    assert_eq!(env["isSecret"], true);
+}
+
+#[test]
+fn test_remote_server_without_version_warns() {
+    let config = json!({ "type": "http", "url": "https://weather.example.com/mcp" });
+    let result = build_manifest(&request(config));
+    assert_eq!(result.manifest["remotes"][0]["type"], "streamable-http");
+    assert!(result.warnings.iter().any(|w| w.contains("version")));
+}
+
+#[test]
+fn test_regional_remotes_are_listed() {
+    let entry = json!({
+        "name": "io.example/search",
+        "remotes": [
+            {
+                "type": "streamable-http",
+                "url": "https://us.search.example.com/mcp",
+                "region": "us"
+            },
+            {
+                "type": "sse",
+                "url": "https://eu.search.example.com/sse",
+                "_meta": { "region": "eu" }
+            }
+        ]
+    });
+    let server = convert_entry(&entry).unwrap();
+    assert_eq!(server.config["url"], "https://us.search.example.com/mcp");
+    assert_eq!(server.endpoints.len(), 2);
+    assert_eq!(server.endpoints[0].region.as_deref(), Some("us"));
+    assert_eq!(server.endpoints[1].transport, "sse");
+    assert_eq!(server.endpoints[1].region.as_deref(), Some("eu"));
+}
+
+#[test]
+fn test_merge_entries_applies_updates_and_deletions() {
+    let cached = vec![
+        json!({ "server": { "name": "io.example/a", "version": "1.0.0" } }),
+        json!({ "server": { "name": "io.example/b", "version": "1.0.0" } }),
+    ];
+    let updates = vec![
+        json!({ "server": { "name": "io.example/a", "version": "1.1.0" } }),
+        json!({
+            "server": { "name": "io.example/b" },
+            "_meta": { "io.modelcontextprotocol.registry/official": { "status": "deleted" } }
+        }),
+        json!({ "server": { "name": "io.example/c", "version": "0.1.0" } }),
+    ];
+    let merged = merge_entries(cached, updates);
+    let versions: Vec<(&str, &str)> = merged
+        .iter()
+        .map(|e| {
+            (
+                e["server"]["name"].as_str().unwrap(),
+                e["server"]["version"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        versions,
+        vec![("io.example/a", "1.1.0"), ("io.example/c", "0.1.0")]
+    );
+}
+
+#[test]
+fn test_refresh_keeps_local_env_and_fields() {
+    let local = json!({
+        "command": "npx",
+        "args": ["-y", "@example/weather@1.0.0"],
+        "env": { "API_KEY": "secret" },
+        "timeout": 60
+    });
+    let advertised = json!({
+        "type": "stdio",
+        "command": "npx",
+        "args": ["-y", "@example/weather@1.1.0"],
+        "env": { "API_KEY": "", "REGION": "eu" }
+    });
+    let merged = merge_with_local(Some(&local), &advertised);
+    assert_eq!(
+        merged["env"],
+        json!({ "API_KEY": "secret", "REGION": "eu" })
+    );
+    assert_eq!(merged["args"][1], "@example/weather@1.1.0");
+    assert_eq!(merged["timeout"], 60);
+    assert_eq!(merge_with_local(None, &advertised), advertised);
+}
@@ -0,0 +1,393 @@
+//! What else refers to a server, so removing it doesn't leave orphans behind
+//!
+//! `analyze_server_removal` lists, for a server about to be removed from a target, the Claude
+//! Code permission rules (`mcp__<server>`, `mcp__<server>__<tool>`) and hooks naming it in the
+//! settings of the scopes that load it, the CLAUDE.md files mentioning it, other clients with
+//! the same definition and the role profiles listing it. Claude Code settings are only looked
+//! at for Claude Code targets; rules in the user settings are left out when the user scope
+//! still has a server of that name.
+//!
+//! `clean_up_server_references` removes what the user picked: the permission rules, the hooks
+//! whose matcher only names this server, the server from the other clients (as one batch, see
+//! `batch_ops`) and from the profiles, and regenerates the tools section of CLAUDE.md files.
+//! Run it after the server is removed, as the section is rendered from the servers still
+//! configured. Mentions outside the generated section are only reported.
+
+use crate::audit_log;
+use crate::batch_ops::{self, BatchReport, Operation};
+use crate::claude_code_commands::GLOBAL_PROJECT_ID;
+use crate::claude_hooks::{self, HookEntry};
+use crate::claude_memory;
+use crate::claude_settings::{self, SettingsScope};
+use crate::client_target::{self, ClientTarget};
+use crate::role_presets;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tauri::command;
+
+/// Permission lists of Claude Code settings
+const PERMISSION_LISTS: [&str; 3] = ["allow", "ask", "deny"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PermissionReference {
+    pub scope: SettingsScope,
+    /// "allow", "ask" or "deny"
+    pub list: String,
+    pub rule: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct HookReference {
+    pub scope: SettingsScope,
+    pub entry: HookEntry,
+    /// The matcher names no other tool, so the hook can go with the server
+    pub removable: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MemoryReference {
+    pub scope: SettingsScope,
+    pub path: String,
+    /// 1-based lines mentioning the server
+    pub lines: Vec<usize>,
+    /// The generated tools section mentions it
+    pub generated: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ClientReference {
+    pub target: ClientTarget,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RemovalImpact {
+    pub name: String,
+    pub permissions: Vec<PermissionReference>,
+    pub hooks: Vec<HookReference>,
+    pub memory: Vec<MemoryReference>,
+    /// Entries of other targets with the same definition, under any name
+    pub clients: Vec<ClientReference>,
+    /// Ids of the role profiles listing the server
+    pub profiles: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct RemovalCleanup {
+    #[serde(default)]
+    pub permissions: bool,
+    #[serde(default)]
+    pub hooks: bool,
+    #[serde(default)]
+    pub memory: bool,
+    #[serde(default)]
+    pub clients: Vec<ClientReference>,
+    #[serde(default)]
+    pub profiles: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct CleanupReport {
+    pub permissions: usize,
+    pub hooks: usize,
+    /// CLAUDE.md files whose tools section was regenerated
+    pub memory: Vec<String>,
+    pub clients: Option<BatchReport>,
+    pub profiles: Vec<String>,
+}
+
+/// Whether a permission rule (or one alternative of a hook matcher) names server `name`
+pub(crate) fn names_server(rule: &str, name: &str) -> bool {
+    let prefix = format!("mcp__{}", name);
+    rule == prefix || rule.starts_with(&format!("{}__", prefix))
+}
+
+/// Whether a hook matcher names `name`, and whether it names nothing else
+pub(crate) fn matcher_reference(matcher: &str, name: &str) -> (bool, bool) {
+    let alternatives: Vec<&str> = matcher
+        .split('|')
+        .map(|alt| {
+            alt.trim()
+                .trim_matches(|c| matches!(c, '^' | '$' | '(' | ')'))
+        })
+        .collect();
+    let named = alternatives
+        .iter()
+        .filter(|alt| names_server(alt, name))
+        .count();
+    (named > 0, named == alternatives.len())
+}
+
+/// 1-based lines of `content` mentioning `name` as a word of its own
+pub(crate) fn mention_lines(content: &str, name: &str) -> Vec<usize> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '-';
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            line.match_indices(name).any(|(at, _)| {
+                let before = line[..at].chars().next_back();
+                let after = line[at + name.len()..].chars().next();
+                !before.is_some_and(is_word) && !after.is_some_and(is_word)
+            })
+        })
+        .map(|(index, _)| index + 1)
+        .collect()
+}
+
+/// Whether two entries run the same server, ignoring enable flags and an explicit stdio type
+pub(crate) fn same_definition(a: &Value, b: &Value) -> bool {
+    fn comparable(config: &Value) -> Option<Map<String, Value>> {
+        let mut fields = config.as_object()?.clone();
+        fields.shift_remove("disabled");
+        fields.shift_remove("isActive");
+        if fields.get("type").and_then(Value::as_str) == Some("stdio") {
+            fields.shift_remove("type");
+        }
+        fields.sort_keys();
+        Some(fields)
+    }
+    matches!((comparable(a), comparable(b)), (Some(a), Some(b)) if a == b)
+}
+
+/// Claude Code settings scopes that may refer to a server of `target`; the user scope counts
+/// for a project server unless the user scope has one of the same name
+pub(crate) fn settings_scopes(target: &ClientTarget, user_has_name: bool) -> Vec<SettingsScope> {
+    if !target.is_claude_code() {
+        Vec::new()
+    } else if target.working_dir() == GLOBAL_PROJECT_ID {
+        vec![SettingsScope::User]
+    } else if user_has_name {
+        vec![SettingsScope::Project, SettingsScope::Local]
+    } else {
+        vec![
+            SettingsScope::User,
+            SettingsScope::Project,
+            SettingsScope::Local,
+        ]
+    }
+}
+
+fn scope_dir(target: &ClientTarget, scope: SettingsScope) -> Option<&str> {
+    (scope != SettingsScope::User).then(|| target.working_dir())
+}
+
+fn rules_of<'a>(settings: &'a Map<String, Value>, list: &str) -> impl Iterator<Item = &'a str> {
+    settings
+        .get("permissions")
+        .and_then(|p| p.get(list))
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+}
+
+/// Drop the rules naming `name`; returns how many were dropped
+fn remove_rules(settings: &mut Map<String, Value>, name: &str) -> usize {
+    let Some(permissions) = settings
+        .get_mut("permissions")
+        .and_then(Value::as_object_mut)
+    else {
+        return 0;
+    };
+    let mut removed = 0;
+    for list in PERMISSION_LISTS {
+        if let Some(rules) = permissions.get_mut(list).and_then(Value::as_array_mut) {
+            let before = rules.len();
+            rules.retain(|rule| !rule.as_str().is_some_and(|r| names_server(r, name)));
+            removed += before - rules.len();
+        }
+    }
+    removed
+}
+
+async fn user_has_server(name: &str) -> bool {
+    let global = ClientTarget::claude_code(GLOBAL_PROJECT_ID);
+    client_target::list_servers(&global)
+        .await
+        .is_ok_and(|servers| servers.contains_key(name))
+}
+
+async fn find_entry(target: &ClientTarget, name: &str) -> Result<Value, String> {
+    if let Some(config) = client_target::list_servers(target).await?.get(name) {
+        return Ok(config.clone());
+    }
+    client_target::list_disabled_servers(target)
+        .await?
+        .get(name)
+        .cloned()
+        .ok_or_else(|| format!("Server '{}' not found in {}", name, target.label()))
+}
+
+/// Everything that refers to server `name` of `target`, to review before removing it
+#[command]
+pub async fn analyze_server_removal(
+    target: ClientTarget,
+    name: String,
+) -> Result<RemovalImpact, String> {
+    let config = find_entry(&target, &name).await?;
+    let mut impact = RemovalImpact {
+        name: name.clone(),
+        permissions: Vec::new(),
+        hooks: Vec::new(),
+        memory: Vec::new(),
+        clients: Vec::new(),
+        profiles: role_presets::profiles_with(&name)?,
+    };
+
+    let user_has_name = target.working_dir() != GLOBAL_PROJECT_ID && user_has_server(&name).await;
+    for scope in settings_scopes(&target, user_has_name) {
+        let dir = scope_dir(&target, scope);
+        let settings =
+            claude_settings::read_settings(&claude_settings::settings_path(scope, dir)?)?;
+        for list in PERMISSION_LISTS {
+            impact.permissions.extend(
+                rules_of(&settings, list)
+                    .filter(|rule| names_server(rule, &name))
+                    .map(|rule| PermissionReference {
+                        scope,
+                        list: list.to_string(),
+                        rule: rule.to_string(),
+                    }),
+            );
+        }
+        for entry in claude_hooks::list_entries(&settings) {
+            let (named, only) = matcher_reference(&entry.matcher, &name);
+            if named {
+                impact.hooks.push(HookReference {
+                    scope,
+                    entry,
+                    removable: only,
+                });
+            }
+        }
+
+        let path = claude_memory::memory_path(scope, dir)?;
+        let Some(content) = claude_memory::read_memory(&path)? else {
+            continue;
+        };
+        let lines = mention_lines(&content, &name);
+        if !lines.is_empty() {
+            impact.memory.push(MemoryReference {
+                scope,
+                path: path.to_string_lossy().to_string(),
+                lines,
+                generated: claude_memory::section(&content)
+                    .is_some_and(|section| !mention_lines(section, &name).is_empty()),
+            });
+        }
+    }
+
+    for other in client_target::known_targets().await {
+        if other == target {
+            continue;
+        }
+        let mut servers = client_target::list_servers(&other)
+            .await
+            .unwrap_or_default();
+        servers.extend(
+            client_target::list_disabled_servers(&other)
+                .await
+                .unwrap_or_default(),
+        );
+        impact.clients.extend(
+            servers
+                .iter()
+                .filter(|(_, entry)| same_definition(entry, &config))
+                .map(|(other_name, _)| ClientReference {
+                    target: other.clone(),
+                    name: other_name.clone(),
+                }),
+        );
+    }
+    Ok(impact)
+}
+
+/// Remove the references to server `name` of `target` picked in `cleanup`
+#[command]
+pub async fn clean_up_server_references(
+    target: ClientTarget,
+    name: String,
+    cleanup: RemovalCleanup,
+) -> Result<CleanupReport, String> {
+    let mut report = CleanupReport::default();
+    let user_has_name = target.working_dir() != GLOBAL_PROJECT_ID && user_has_server(&name).await;
+    for scope in settings_scopes(&target, user_has_name) {
+        let dir = scope_dir(&target, scope);
+        if cleanup.permissions || cleanup.hooks {
+            let path = claude_settings::settings_path(scope, dir)?;
+            let mut settings = claude_settings::read_settings(&path)?;
+            let mut changed = 0;
+            if cleanup.permissions {
+                let removed = remove_rules(&mut settings, &name);
+                report.permissions += removed;
+                changed += removed;
+            }
+            if cleanup.hooks {
+                for entry in claude_hooks::list_entries(&settings) {
+                    if matcher_reference(&entry.matcher, &name) != (true, true) {
+                        continue;
+                    }
+                    if claude_hooks::remove_entry(
+                        &mut settings,
+                        &entry.event,
+                        &entry.matcher,
+                        &entry.command,
+                    ) {
+                        report.hooks += 1;
+                        changed += 1;
+                    }
+                }
+            }
+            if changed > 0 {
+                claude_settings::write_settings(&path, &settings)?;
+            }
+        }
+
+        if cleanup.memory {
+            let path = claude_memory::memory_path(scope, dir)?;
+            let mentioned = claude_memory::read_memory(&path)?
+                .as_deref()
+                .and_then(claude_memory::section)
+                .is_some_and(|section| !mention_lines(section, &name).is_empty());
+            if mentioned {
+                let file =
+                    claude_memory::sync_claude_memory(scope, dir.map(str::to_string), None).await?;
+                if file.written {
+                    report.memory.push(file.path);
+                }
+            }
+        }
+    }
+
+    if !cleanup.clients.is_empty() {
+        let ops: Vec<Operation> = cleanup
+            .clients
+            .iter()
+            .map(|reference| Operation::RemoveServer {
+                target: reference.target.clone(),
+                name: reference.name.clone(),
+            })
+            .collect();
+        let label = format!("Remove copies of '{}' from {} client(s)", name, ops.len());
+        report.clients = Some(batch_ops::run_batch(&label, &ops, false, true).await);
+    }
+    if !cleanup.profiles.is_empty() {
+        report.profiles = role_presets::remove_from_profiles(&name, &cleanup.profiles)?;
+    }
+
+    audit_log::log(
+        "clean_up_references",
+        &target,
+        Some(&name),
+        Some(format!(
+            "{} permission rule(s), {} hook(s), {} memory file(s), {} profile(s)",
+            report.permissions,
+            report.hooks,
+            report.memory.len(),
+            report.profiles.len()
+        )),
+        Vec::new(),
+    );
+    Ok(report)
+}
@@ -0,0 +1,77 @@
+// Removal impact tests
+use crate::claude_settings::SettingsScope;
+use crate::client_target::ClientTarget;
+use crate::removal_impact::{
+    matcher_reference, mention_lines, names_server, same_definition, settings_scopes,
+};
+use serde_json::json;
+
+#[test]
+fn test_names_server_matches_server_and_tool_rules_only() {
+    assert!(names_server("mcp__github", "github"));
+    assert!(names_server("mcp__github__*", "github"));
+    assert!(names_server("mcp__github__create_issue", "github"));
+    assert!(!names_server("mcp__github_enterprise", "github"));
+    assert!(!names_server("mcp__github_enterprise__search", "github"));
+    assert!(!names_server("Bash(git push:*)", "github"));
+}
+
+#[test]
+fn test_matcher_reference_tells_shared_matchers_apart() {
+    assert_eq!(matcher_reference("mcp__github__.*", "github"), (true, true));
+    assert_eq!(
+        matcher_reference(
+            "^(mcp__github__create_issue|mcp__github__merge_pr)$",
+            "github"
+        ),
+        (true, true)
+    );
+    assert_eq!(
+        matcher_reference("mcp__github__.*|Write", "github"),
+        (true, false)
+    );
+    assert_eq!(matcher_reference("", "github"), (false, false));
+    assert_eq!(
+        matcher_reference("mcp__gitlab__.*", "github"),
+        (false, false)
+    );
+}
+
+#[test]
+fn test_mention_lines_finds_whole_names() {
+    let content = "# Notes\nUse github for issues.\nnot github-enterprise or mygithub\n\
+                   - **github** (stdio), tools named `mcp__github__*`\n";
+    assert_eq!(mention_lines(content, "github"), vec![2, 4]);
+    assert!(mention_lines(content, "gitlab").is_empty());
+}
+
+#[test]
+fn test_same_definition_ignores_flags_and_stdio_type() {
+    let claude = json!({ "type": "stdio", "command": "npx", "args": ["-y", "@x/fs"] });
+    let cline = json!({ "args": ["-y", "@x/fs"], "command": "npx", "disabled": false });
+    assert!(same_definition(&claude, &cline));
+    let other = json!({ "command": "npx", "args": ["-y", "@x/git"] });
+    assert!(!same_definition(&claude, &other));
+    let remote = json!({ "type": "http", "url": "https://x/mcp" });
+    assert!(!same_definition(
+        &remote,
+        &json!({ "url": "https://x/mcp" })
+    ));
+}
+
+#[test]
+fn test_settings_scopes_per_target() {
+    assert_eq!(
+        settings_scopes(&ClientTarget::claude_code("Global"), false),
+        vec![SettingsScope::User]
+    );
+    assert_eq!(
+        settings_scopes(&ClientTarget::claude_code("/work/app"), true),
+        vec![SettingsScope::Project, SettingsScope::Local]
+    );
+    assert_eq!(
+        settings_scopes(&ClientTarget::claude_code("/work/app"), false).len(),
+        3
+    );
+    assert!(settings_scopes(&ClientTarget::client("cursor", None), false).is_empty());
+}
@@ -0,0 +1,395 @@
+//! Role presets for structured team rollout
+//!
+//! A role (frontend-dev, data-eng, sre, ...) is composed of server profiles: named sets of
+//! servers, each marked required or optional. Roles and profiles are kept in
+//! role_presets.json in the app data dir. `export_role` packs a role with its profiles into a
+//! bundle signed with this installation's Ed25519 key, which lives in the secrets store.
+//! `import_role_bundle` only accepts bundles signed by that key or one of the
+//! `trusted_role_keys` in the settings, so a bundle can't be altered on its way from the
+//! team lead.
+//!
+//! `apply_role` installs the required servers into the chosen targets as one batch (see
+//! `batch_ops`) and returns the optional ones for the user to choose from.
+
+use crate::batch_ops::{self, BatchReport, Operation};
+use crate::client_target::ClientTarget;
+use crate::secrets;
+use crate::settings::{app_config_dir, load_settings};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chrono::Utc;
+use ring::rand::SystemRandom;
+use ring::signature::{Ed25519KeyPair, KeyPair, UnparsedPublicKey, ED25519};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::command;
+
+const BUNDLE_VERSION: u32 = 1;
+/// Secrets store id of the signing key (PKCS#8, base64)
+const SIGNING_KEY_ID: &str = "role-signing-key";
+
+/// Serializes read-modify-write cycles of role_presets.json
+static LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PresetServer {
+    pub config: Value,
+    /// Installed by `apply_role`; optional servers are offered instead
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ServerProfile {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub servers: BTreeMap<String, PresetServer>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RolePreset {
+    pub id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Ids of the profiles it is composed of, in order
+    pub profiles: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RolePresets {
+    #[serde(default)]
+    pub roles: Vec<RolePreset>,
+    #[serde(default)]
+    pub profiles: Vec<ServerProfile>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RoleBundle {
+    pub version: u32,
+    pub exported_at: String,
+    pub role: RolePreset,
+    pub profiles: Vec<ServerProfile>,
+    /// Ed25519 public key of the signer, base64
+    pub signed_by: String,
+    /// Signature over the other fields, base64
+    pub signature: String,
+}
+
+/// The signed part of a bundle
+#[derive(Serialize)]
+struct SignedPayload<'a> {
+    version: u32,
+    exported_at: &'a str,
+    role: &'a RolePreset,
+    profiles: &'a [ServerProfile],
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct RoleServer {
+    pub name: String,
+    /// Profile the server comes from
+    pub profile: String,
+    pub config: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RoleApplication {
+    pub role: String,
+    /// Outcome of installing the required servers
+    pub installed: BatchReport,
+    /// Servers for the user to choose from
+    pub optional: Vec<RoleServer>,
+}
+
+fn presets_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("role_presets.json"))
+}
+
+fn read_presets() -> Result<RolePresets, String> {
+    let path = presets_path()?;
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse role presets: {}", e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(RolePresets::default()),
+        Err(e) => Err(format!("Failed to read role presets: {}", e)),
+    }
+}
+
+fn write_presets(presets: &RolePresets) -> Result<(), String> {
+    let path = presets_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(presets)
+        .map_err(|e| format!("Failed to serialize role presets: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write role presets: {}", e))
+}
+
+fn update_presets<T>(
+    update: impl FnOnce(&mut RolePresets) -> Result<T, String>,
+) -> Result<T, String> {
+    let _guard = LOCK
+        .lock()
+        .map_err(|e| format!("Failed to lock role presets: {}", e))?;
+    let mut presets = read_presets()?;
+    let result = update(&mut presets)?;
+    write_presets(&presets)?;
+    Ok(result)
+}
+
+fn upsert_profile(presets: &mut RolePresets, profile: ServerProfile) {
+    presets.profiles.retain(|p| p.id != profile.id);
+    presets.profiles.push(profile);
+}
+
+fn upsert_role(presets: &mut RolePresets, role: RolePreset) {
+    presets.roles.retain(|r| r.id != role.id);
+    presets.roles.push(role);
+}
+
+/// Required and optional servers of `role`, in profile order. A server listed by several
+/// profiles takes its config from the first and is required if any of them requires it.
+pub(crate) fn role_servers(
+    role: &RolePreset,
+    profiles: &[ServerProfile],
+) -> Result<(Vec<RoleServer>, Vec<RoleServer>), String> {
+    let mut servers: Vec<(RoleServer, bool)> = Vec::new();
+    for id in &role.profiles {
+        let profile = profiles
+            .iter()
+            .find(|p| p.id == *id)
+            .ok_or_else(|| format!("Role '{}' uses unknown profile '{}'", role.id, id))?;
+        for (name, server) in &profile.servers {
+            match servers.iter_mut().find(|(s, _)| s.name == *name) {
+                Some((_, required)) => *required |= server.required,
+                None => servers.push((
+                    RoleServer {
+                        name: name.clone(),
+                        profile: profile.id.clone(),
+                        config: server.config.clone(),
+                        description: server.description.clone(),
+                    },
+                    server.required,
+                )),
+            }
+        }
+    }
+    let (required, optional): (Vec<_>, Vec<_>) = servers.into_iter().partition(|(_, r)| *r);
+    Ok((
+        required.into_iter().map(|(s, _)| s).collect(),
+        optional.into_iter().map(|(s, _)| s).collect(),
+    ))
+}
+
+/// Ids of the profiles listing a server named `name`
+pub(crate) fn profiles_with(name: &str) -> Result<Vec<String>, String> {
+    Ok(read_presets()?
+        .profiles
+        .into_iter()
+        .filter(|p| p.servers.contains_key(name))
+        .map(|p| p.id)
+        .collect())
+}
+
+/// Take `name` out of the profiles `ids`; returns the ids it was removed from
+pub(crate) fn remove_from_profiles(name: &str, ids: &[String]) -> Result<Vec<String>, String> {
+    update_presets(|presets| {
+        let mut removed = Vec::new();
+        for profile in presets.profiles.iter_mut().filter(|p| ids.contains(&p.id)) {
+            if profile.servers.remove(name).is_some() {
+                removed.push(profile.id.clone());
+            }
+        }
+        Ok(removed)
+    })
+}
+
+fn payload_bytes(bundle: &RoleBundle) -> Result<Vec<u8>, String> {
+    serde_json::to_vec(&SignedPayload {
+        version: bundle.version,
+        exported_at: &bundle.exported_at,
+        role: &bundle.role,
+        profiles: &bundle.profiles,
+    })
+    .map_err(|e| format!("Failed to serialize role bundle: {}", e))
+}
+
+/// Fill in `signed_by` and `signature` of `bundle` with `key`
+pub(crate) fn sign_bundle(bundle: &mut RoleBundle, key: &Ed25519KeyPair) -> Result<(), String> {
+    bundle.signed_by = BASE64.encode(key.public_key().as_ref());
+    bundle.signature = BASE64.encode(key.sign(&payload_bytes(bundle)?).as_ref());
+    Ok(())
+}
+
+/// Check that `bundle` is signed by one of `trusted` and unchanged since
+pub(crate) fn verify_bundle(bundle: &RoleBundle, trusted: &[String]) -> Result<(), String> {
+    if bundle.version > BUNDLE_VERSION {
+        return Err(format!(
+            "Role bundle version {} is newer than supported version {}",
+            bundle.version, BUNDLE_VERSION
+        ));
+    }
+    if !trusted.iter().any(|key| key.trim() == bundle.signed_by) {
+        return Err("Role bundle is signed by a key that isn't trusted".to_string());
+    }
+    let public_key = BASE64
+        .decode(&bundle.signed_by)
+        .map_err(|_| "Role bundle has an invalid signer key".to_string())?;
+    let signature = BASE64
+        .decode(&bundle.signature)
+        .map_err(|_| "Role bundle has an invalid signature".to_string())?;
+    UnparsedPublicKey::new(&ED25519, public_key)
+        .verify(&payload_bytes(bundle)?, &signature)
+        .map_err(|_| "Role bundle signature doesn't match its contents".to_string())
+}
+
+/// This installation's signing key, created on first use
+fn signing_key() -> Result<Ed25519KeyPair, String> {
+    let pkcs8 = match secrets::get_secret(SIGNING_KEY_ID)? {
+        Some(encoded) => BASE64
+            .decode(encoded)
+            .map_err(|e| format!("Failed to decode signing key: {}", e))?,
+        None => {
+            let document = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new())
+                .map_err(|_| "Failed to generate signing key".to_string())?;
+            secrets::put_secret(SIGNING_KEY_ID, &BASE64.encode(document.as_ref()))?;
+            document.as_ref().to_vec()
+        }
+    };
+    Ed25519KeyPair::from_pkcs8(&pkcs8).map_err(|e| format!("Invalid signing key: {}", e))
+}
+
+fn find_role(presets: &RolePresets, id: &str) -> Result<RolePreset, String> {
+    presets
+        .roles
+        .iter()
+        .find(|r| r.id == id)
+        .cloned()
+        .ok_or_else(|| format!("No role '{}'", id))
+}
+
+#[command]
+pub async fn list_role_presets() -> Result<RolePresets, String> {
+    read_presets()
+}
+
+#[command]
+pub async fn save_server_profile(profile: ServerProfile) -> Result<(), String> {
+    if profile.id.trim().is_empty() {
+        return Err("Profile id is required".to_string());
+    }
+    update_presets(|presets| {
+        upsert_profile(presets, profile);
+        Ok(())
+    })
+}
+
+#[command]
+pub async fn save_role_preset(role: RolePreset) -> Result<(), String> {
+    if role.id.trim().is_empty() {
+        return Err("Role id is required".to_string());
+    }
+    update_presets(|presets| {
+        role_servers(&role, &presets.profiles)?;
+        upsert_role(presets, role);
+        Ok(())
+    })
+}
+
+/// Delete a role; its profiles are kept since other roles may use them
+#[command]
+pub async fn delete_role_preset(id: String) -> Result<bool, String> {
+    update_presets(|presets| {
+        let before = presets.roles.len();
+        presets.roles.retain(|r| r.id != id);
+        Ok(presets.roles.len() != before)
+    })
+}
+
+/// Public key to add to teammates' `trusted_role_keys`
+#[command]
+pub async fn get_role_signing_key() -> Result<String, String> {
+    Ok(BASE64.encode(signing_key()?.public_key().as_ref()))
+}
+
+/// A signed bundle of the role and the profiles it is composed of
+#[command]
+pub async fn export_role(role: String) -> Result<RoleBundle, String> {
+    let presets = read_presets()?;
+    let role = find_role(&presets, &role)?;
+    let profiles = presets
+        .profiles
+        .into_iter()
+        .filter(|p| role.profiles.contains(&p.id))
+        .collect();
+    let mut bundle = RoleBundle {
+        version: BUNDLE_VERSION,
+        exported_at: Utc::now().to_rfc3339(),
+        role,
+        profiles,
+        signed_by: String::new(),
+        signature: String::new(),
+    };
+    sign_bundle(&mut bundle, &signing_key()?)?;
+    Ok(bundle)
+}
+
+/// Verify a bundle and keep its role and profiles, replacing ones with the same ids
+#[command]
+pub async fn import_role_bundle(bundle: RoleBundle) -> Result<RolePreset, String> {
+    let mut trusted = load_settings().trusted_role_keys;
+    trusted.push(BASE64.encode(signing_key()?.public_key().as_ref()));
+    verify_bundle(&bundle, &trusted)?;
+    role_servers(&bundle.role, &bundle.profiles)?;
+    let role = bundle.role.clone();
+    update_presets(|presets| {
+        for profile in bundle.profiles {
+            upsert_profile(presets, profile);
+        }
+        upsert_role(presets, bundle.role);
+        Ok(())
+    })?;
+    println!("[Roles] Imported role '{}'", role.id);
+    Ok(role)
+}
+
+/// Install the role's required servers into every target and list its optional ones
+#[command]
+pub async fn apply_role(
+    role: String,
+    targets: Vec<ClientTarget>,
+) -> Result<RoleApplication, String> {
+    if targets.is_empty() {
+        return Err("Choose at least one client to apply the role to".to_string());
+    }
+    let presets = read_presets()?;
+    let preset = find_role(&presets, &role)?;
+    let (required, optional) = role_servers(&preset, &presets.profiles)?;
+    let ops: Vec<Operation> = targets
+        .iter()
+        .flat_map(|target| {
+            required.iter().map(|server| Operation::UpsertServer {
+                target: target.clone(),
+                name: server.name.clone(),
+                config: server.config.clone(),
+            })
+        })
+        .collect();
+    let label = format!("Apply role {}", preset.name);
+    let installed = batch_ops::run_batch(&label, &ops, false, true).await;
+    Ok(RoleApplication {
+        role,
+        installed,
+        optional,
+    })
+}
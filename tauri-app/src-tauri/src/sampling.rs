@@ -0,0 +1,124 @@
+//! Answers to server sampling requests in test sessions
+//!
+//! Servers that call `sampling/createMessage` during a tool call wait for the client's LLM,
+//! so a test session that ignores the request hangs until the timeout. Sessions advertise
+//! sampling unless `sampling.mode` is `off` and reply with `canned_response`. Real model
+//! calls only happen in `api` mode with an API key stored in the secrets store under
+//! `api_key_secret`; text content is forwarded to the Messages API and other content is
+//! dropped.
+
+use crate::settings::{self, SamplingMode, SamplingSettings};
+use crate::{network, secrets};
+use serde_json::{json, Value};
+
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Text of an MCP content block; `None` for images and audio
+fn text_of(content: &Value) -> Option<&str> {
+    (content["type"] == "text")
+        .then(|| content["text"].as_str())
+        .flatten()
+}
+
+/// `sampling/createMessage` result in `canned` mode
+pub(crate) fn canned_result(sampling: &SamplingSettings) -> Value {
+    json!({
+        "role": "assistant",
+        "content": { "type": "text", "text": sampling.canned_response },
+        "model": "mcp-linker-stub",
+        "stopReason": "endTurn"
+    })
+}
+
+/// Messages API request body for sampling `params`
+pub(crate) fn messages_request(sampling: &SamplingSettings, params: &Value) -> Value {
+    let messages: Vec<Value> = params["messages"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|message| {
+            let text = text_of(&message["content"])?;
+            Some(json!({
+                "role": message["role"].as_str().unwrap_or("user"),
+                "content": text
+            }))
+        })
+        .collect();
+    let max_tokens = params["maxTokens"]
+        .as_u64()
+        .unwrap_or(sampling.max_tokens as u64)
+        .min(sampling.max_tokens as u64);
+    let mut request = json!({
+        "model": sampling.model,
+        "max_tokens": max_tokens,
+        "messages": messages
+    });
+    if let Some(system) = params["systemPrompt"].as_str() {
+        request["system"] = json!(system);
+    }
+    if let Some(temperature) = params["temperature"].as_f64() {
+        request["temperature"] = json!(temperature);
+    }
+    request
+}
+
+/// `sampling/createMessage` result for a Messages API response
+pub(crate) fn result_from_response(response: &Value) -> Result<Value, String> {
+    let text: String = response["content"]
+        .as_array()
+        .ok_or("Sampling response has no content")?
+        .iter()
+        .filter_map(text_of)
+        .collect();
+    let stop_reason = match response["stop_reason"].as_str() {
+        Some("max_tokens") => "maxTokens",
+        Some("stop_sequence") => "stopSequence",
+        _ => "endTurn",
+    };
+    Ok(json!({
+        "role": "assistant",
+        "content": { "type": "text", "text": text },
+        "model": response["model"],
+        "stopReason": stop_reason
+    }))
+}
+
+async fn call_api(sampling: &SamplingSettings, params: &Value) -> Result<Value, String> {
+    let secret = sampling
+        .api_key_secret
+        .as_deref()
+        .ok_or("Sampling is set to use the API but no API key secret is configured")?;
+    let key = secrets::get_secret(secret)?
+        .ok_or_else(|| format!("Secret '{}' for sampling not found", secret))?;
+    let client = network::http_client(&settings::load_settings().network)?;
+    let response = client
+        .post(&sampling.api_url)
+        .header("x-api-key", key)
+        .header("anthropic-version", ANTHROPIC_VERSION)
+        .json(&messages_request(sampling, params))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to call the sampling API: {}", e))?;
+    let status = response.status();
+    let body: Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse sampling response: {}", e))?;
+    if !status.is_success() {
+        return Err(format!(
+            "Sampling API returned {}: {}",
+            status,
+            body["error"]["message"].as_str().unwrap_or("unknown error")
+        ));
+    }
+    result_from_response(&body)
+}
+
+/// Answer a server's `sampling/createMessage` request per `sampling`
+pub(crate) async fn respond(sampling: &SamplingSettings, params: &Value) -> Result<Value, String> {
+    match sampling.mode {
+        SamplingMode::Off => Err("Sampling is turned off".to_string()),
+        SamplingMode::Canned => Ok(canned_result(sampling)),
+        SamplingMode::Api => call_api(sampling, params).await,
+    }
+}
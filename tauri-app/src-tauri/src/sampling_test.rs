@@ -0,0 +1,55 @@
+// Sampling stub tests
+use crate::sampling::{canned_result, messages_request, result_from_response};
+use crate::settings::SamplingSettings;
+use serde_json::json;
+
+#[test]
+fn test_canned_result() {
+    let sampling = SamplingSettings {
+        canned_response: "ok".to_string(),
+        ..Default::default()
+    };
+    let result = canned_result(&sampling);
+    assert_eq!(result["role"], "assistant");
+    assert_eq!(result["content"], json!({ "type": "text", "text": "ok" }));
+}
+
+#[test]
+fn test_messages_request() {
+    let sampling = SamplingSettings {
+        model: "claude-test".to_string(),
+        max_tokens: 100,
+        ..Default::default()
+    };
+    let params = json!({
+        "messages": [
+            { "role": "user", "content": { "type": "text", "text": "Summarize" } },
+            { "role": "user", "content": { "type": "image", "data": "", "mimeType": "image/png" } }
+        ],
+        "systemPrompt": "Be brief",
+        "maxTokens": 500
+    });
+    assert_eq!(
+        messages_request(&sampling, &params),
+        json!({
+            "model": "claude-test",
+            "max_tokens": 100,
+            "messages": [{ "role": "user", "content": "Summarize" }],
+            "system": "Be brief"
+        })
+    );
+}
+
+#[test]
+fn test_result_from_response() {
+    let response = json!({
+        "model": "claude-test",
+        "content": [{ "type": "text", "text": "Short." }],
+        "stop_reason": "max_tokens"
+    });
+    let result = result_from_response(&response).unwrap();
+    assert_eq!(result["content"]["text"], "Short.");
+    assert_eq!(result["stopReason"], "maxTokens");
+    assert_eq!(result["model"], "claude-test");
+    assert!(result_from_response(&json!({})).is_err());
+}
@@ -0,0 +1,182 @@
+//! CycloneDX SBOM of the server inventory
+//!
+//! Every npm, PyPI and container package a server runs becomes one component, identified by
+//! its package URL and listing the servers that use it. Versions are only recorded when
+//! pinned, since an unpinned launcher may run a different release tomorrow. Remote servers
+//! have no package and are listed as services with their endpoint.
+
+use crate::inventory::{Inventory, InventoryEntry};
+use crate::server_package::{Ecosystem, PackageRef};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde_json::{json, Map, Value};
+
+const SPEC_VERSION: &str = "1.5";
+
+fn purl_segment(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            '@' => "%40".to_string(),
+            ':' => "%3A".to_string(),
+            ' ' => "%20".to_string(),
+            _ => c.to_string(),
+        })
+        .collect()
+}
+
+/// Package URL (https://github.com/package-url/purl-spec) of `package`
+pub(crate) fn purl(package: &PackageRef) -> String {
+    let version = package
+        .version
+        .as_deref()
+        .filter(|_| package.is_pinned())
+        .map(|v| format!("@{}", purl_segment(v)))
+        .unwrap_or_default();
+    match package.ecosystem {
+        Ecosystem::Npm => format!("pkg:npm/{}{}", purl_segment(&package.name), version),
+        Ecosystem::Pypi => format!(
+            "pkg:pypi/{}{}",
+            package.name.replace('_', "-").to_lowercase(),
+            version
+        ),
+        Ecosystem::Docker => {
+            // Images outside Docker Hub name their registry in the first segment
+            let (registry, name) = match package.name.split_once('/') {
+                Some((host, rest)) if host.contains(['.', ':']) || host == "localhost" => {
+                    (Some(host), rest)
+                }
+                _ => (None, package.name.as_str()),
+            };
+            match registry {
+                Some(host) => format!("pkg:docker/{}{}?repository_url={}", name, version, host),
+                None => format!("pkg:docker/{}{}", name, version),
+            }
+        }
+    }
+}
+
+fn server_label(entry: &InventoryEntry) -> String {
+    format!("{}/{}/{}", entry.client, entry.scope, entry.name)
+}
+
+/// Random (version 4) UUID URN; the serial number is optional, so a failing RNG just omits it
+fn serial_number() -> Option<String> {
+    let mut bytes = [0u8; 16];
+    SystemRandom::new().fill(&mut bytes).ok()?;
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    Some(format!(
+        "urn:uuid:{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    ))
+}
+
+fn component(package: &PackageRef, entry: &InventoryEntry) -> Value {
+    let purl = purl(package);
+    let mut component = Map::new();
+    let kind = match package.ecosystem {
+        Ecosystem::Docker => "container",
+        _ => "library",
+    };
+    component.insert("type".to_string(), json!(kind));
+    component.insert("bom-ref".to_string(), json!(purl));
+    component.insert("name".to_string(), json!(package.name));
+    if package.is_pinned() {
+        component.insert("version".to_string(), json!(package.version));
+    }
+    component.insert("purl".to_string(), json!(purl));
+    if let Some(license) = &entry.license {
+        // SPDX ids go in `id`; anything else is free-form
+        let is_spdx_id = license
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || ".-+".contains(c));
+        let license = if is_spdx_id {
+            json!({ "license": { "id": license } })
+        } else {
+            json!({ "license": { "name": license } })
+        };
+        component.insert("licenses".to_string(), json!([license]));
+    }
+    if let Some(repository) = &entry.repository {
+        component.insert(
+            "externalReferences".to_string(),
+            json!([{ "type": "vcs", "url": repository }]),
+        );
+    }
+    let mut properties = Vec::new();
+    if !package.is_pinned() {
+        if let Some(spec) = &package.version {
+            properties.push(json!({ "name": "mcp-linker:version_spec", "value": spec }));
+        }
+    }
+    component.insert("properties".to_string(), json!(properties));
+    Value::Object(component)
+}
+
+fn add_server_property(component: &mut Value, entry: &InventoryEntry) {
+    if let Some(properties) = component["properties"].as_array_mut() {
+        properties.push(json!({ "name": "mcp-linker:server", "value": server_label(entry) }));
+    }
+}
+
+fn service(entry: &InventoryEntry, url: &str) -> Value {
+    json!({
+        "bom-ref": format!("service:{}", server_label(entry)),
+        "name": entry.name,
+        "endpoints": [url],
+        "authenticated": !entry.header_keys.is_empty(),
+        "properties": [
+            { "name": "mcp-linker:server", "value": server_label(entry) },
+            { "name": "mcp-linker:transport", "value": entry.transport }
+        ]
+    })
+}
+
+/// CycloneDX JSON document for `inventory`
+pub(crate) fn render_cyclonedx(inventory: &Inventory) -> Value {
+    let mut components: Vec<Value> = Vec::new();
+    let mut services = Vec::new();
+    for entry in &inventory.servers {
+        match (&entry.package, &entry.url) {
+            (Some(package), _) => {
+                let purl = purl(package);
+                let index = match components.iter().position(|c| c["purl"] == json!(purl)) {
+                    Some(index) => index,
+                    None => {
+                        components.push(component(package, entry));
+                        components.len() - 1
+                    }
+                };
+                add_server_property(&mut components[index], entry);
+            }
+            (None, Some(url)) => services.push(service(entry, url)),
+            (None, None) => {}
+        }
+    }
+    let mut bom = json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": SPEC_VERSION,
+        "version": 1,
+        "metadata": {
+            "timestamp": inventory.generated_at,
+            "tools": {
+                "components": [{
+                    "type": "application",
+                    "name": "mcp-linker",
+                    "version": env!("CARGO_PKG_VERSION")
+                }]
+            }
+        },
+        "components": components,
+        "services": services
+    });
+    if let Some(serial) = serial_number() {
+        bom["serialNumber"] = json!(serial);
+    }
+    bom
+}
@@ -0,0 +1,77 @@
+// CycloneDX SBOM tests
+use crate::client_target::ClientTarget;
+use crate::inventory::{entry_row, Inventory};
+use crate::sbom::{purl, render_cyclonedx};
+use crate::server_package::{parse_image, parse_npm_spec, parse_pypi_spec};
+use serde_json::json;
+
+#[test]
+fn test_purl() {
+    assert_eq!(
+        purl(&parse_npm_spec(
+            "@modelcontextprotocol/server-github@2025.4.8"
+        )),
+        "pkg:npm/%40modelcontextprotocol/server-github@2025.4.8"
+    );
+    // Unpinned versions are left out
+    assert_eq!(
+        purl(&parse_npm_spec("tavily-mcp@latest")),
+        "pkg:npm/tavily-mcp"
+    );
+    assert_eq!(
+        purl(&parse_pypi_spec("mcp_server_git==0.6.2")),
+        "pkg:pypi/mcp-server-git@0.6.2"
+    );
+    assert_eq!(
+        purl(&parse_image("ghcr.io/github/github-mcp-server:1.0.0")),
+        "pkg:docker/github/github-mcp-server@1.0.0?repository_url=ghcr.io"
+    );
+}
+
+#[test]
+fn test_render_cyclonedx() {
+    let cursor = ClientTarget::client("cursor", None);
+    let claude = ClientTarget::claude_code("Global");
+    let github =
+        json!({ "command": "npx", "args": ["-y", "@modelcontextprotocol/server-github@2025.4.8"] });
+    let inventory = Inventory {
+        generated_at: "2025-01-01T00:00:00Z".to_string(),
+        servers: vec![
+            entry_row(&cursor, "github", &github, true, None),
+            entry_row(&claude, "github", &github, true, None),
+            entry_row(
+                &cursor,
+                "docs",
+                &json!({ "url": "https://example.com/mcp" }),
+                true,
+                None,
+            ),
+            entry_row(
+                &cursor,
+                "local",
+                &json!({ "command": "./server" }),
+                true,
+                None,
+            ),
+        ],
+    };
+    let bom = render_cyclonedx(&inventory);
+    assert_eq!(bom["bomFormat"], "CycloneDX");
+
+    // One component per package, listing every server that runs it
+    let components = bom["components"].as_array().unwrap();
+    assert_eq!(components.len(), 1);
+    assert_eq!(components[0]["version"], "2025.4.8");
+    let servers: Vec<&str> = components[0]["properties"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|p| p["name"] == "mcp-linker:server")
+        .filter_map(|p| p["value"].as_str())
+        .collect();
+    assert_eq!(servers.len(), 2);
+
+    let services = bom["services"].as_array().unwrap();
+    assert_eq!(services.len(), 1);
+    assert_eq!(services[0]["endpoints"][0], "https://example.com/mcp");
+}
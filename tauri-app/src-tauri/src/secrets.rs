@@ -0,0 +1,203 @@
+//! Encrypted secrets store at ~/.config/mcplinker/secrets.json
+//!
+//! Values are sealed with AES-256-GCM (see `encryption`) under a per-machine key kept in
+//! `secrets.key` next to the store with owner-only permissions. This keeps tokens out of
+//! plain-text client configs and backups; it is not a substitute for an OS keychain.
+
+use crate::encryption::{decrypt_data, encrypt_data, generate_encryption_key};
+use crate::settings::app_config_dir;
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::command;
+
+/// Serializes read-modify-write cycles on the store file
+static STORE_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct SecretEntry {
+    ciphertext: String,
+    created_at: String,
+    updated_at: String,
+    /// RFC 3339 expiry recorded by the user, e.g. a PAT's expiration date
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expires_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(default)]
+struct SecretsFile {
+    secrets: BTreeMap<String, SecretEntry>,
+}
+
+/// Secret metadata returned to the frontend; never includes the value
+#[derive(Debug, Serialize, Clone)]
+pub struct SecretInfo {
+    pub id: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub expires_at: Option<String>,
+}
+
+fn store_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("secrets.json"))
+}
+
+fn key_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("secrets.key"))
+}
+
+fn write_private(path: &Path, content: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create secrets directory: {}", e))?;
+    }
+    fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))
+            .map_err(|e| format!("Failed to restrict permissions: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Load the store key, creating it on first use
+fn load_key() -> Result<String, String> {
+    let path = key_path()?;
+    match fs::read_to_string(&path) {
+        Ok(key) => Ok(key.trim().to_string()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            let key = generate_encryption_key();
+            write_private(&path, &key)?;
+            Ok(key)
+        }
+        Err(e) => Err(format!("Failed to read secrets key: {}", e)),
+    }
+}
+
+fn read_store() -> Result<SecretsFile, String> {
+    let path = store_path()?;
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse secrets store: {}", e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(SecretsFile::default()),
+        Err(e) => Err(format!("Failed to read secrets store: {}", e)),
+    }
+}
+
+fn write_store(store: &SecretsFile) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(store)
+        .map_err(|e| format!("Failed to serialize secrets store: {}", e))?;
+    write_private(&store_path()?, &content)
+}
+
+pub(crate) fn put_secret(id: &str, value: &str) -> Result<(), String> {
+    let _guard = STORE_LOCK
+        .lock()
+        .map_err(|_| "Secrets store lock poisoned")?;
+    let key = load_key()?;
+    let mut store = read_store()?;
+    let now = Utc::now().to_rfc3339();
+    let ciphertext = encrypt_data(value, &key)?;
+    let existing = store.secrets.get(id);
+    let created_at = existing
+        .map(|e| e.created_at.clone())
+        .unwrap_or_else(|| now.clone());
+    // Rotating a value keeps the recorded expiry until the user updates it
+    let expires_at = existing.and_then(|e| e.expires_at.clone());
+    store.secrets.insert(
+        id.to_string(),
+        SecretEntry {
+            ciphertext,
+            created_at,
+            updated_at: now,
+            expires_at,
+        },
+    );
+    write_store(&store)
+}
+
+/// Record or clear the expiry of a stored secret
+pub(crate) fn set_expiry(id: &str, expires_at: Option<String>) -> Result<(), String> {
+    if let Some(expires_at) = &expires_at {
+        chrono::DateTime::parse_from_rfc3339(expires_at)
+            .map_err(|e| format!("Invalid expiry '{}': {}", expires_at, e))?;
+    }
+    let _guard = STORE_LOCK
+        .lock()
+        .map_err(|_| "Secrets store lock poisoned")?;
+    let mut store = read_store()?;
+    let entry = store
+        .secrets
+        .get_mut(id)
+        .ok_or_else(|| format!("Secret '{}' not found", id))?;
+    entry.expires_at = expires_at;
+    write_store(&store)
+}
+
+/// Metadata of all stored secrets, sorted by id
+pub(crate) fn secret_infos() -> Result<Vec<SecretInfo>, String> {
+    let _guard = STORE_LOCK
+        .lock()
+        .map_err(|_| "Secrets store lock poisoned")?;
+    Ok(read_store()?
+        .secrets
+        .into_iter()
+        .map(|(id, entry)| SecretInfo {
+            id,
+            created_at: entry.created_at,
+            updated_at: entry.updated_at,
+            expires_at: entry.expires_at,
+        })
+        .collect())
+}
+
+pub(crate) fn get_secret(id: &str) -> Result<Option<String>, String> {
+    let _guard = STORE_LOCK
+        .lock()
+        .map_err(|_| "Secrets store lock poisoned")?;
+    let store = read_store()?;
+    match store.secrets.get(id) {
+        Some(entry) => decrypt_data(&entry.ciphertext, &load_key()?)
+            .map(Some)
+            .map_err(|e| format!("Failed to decrypt secret '{}': {}", id, e)),
+        None => Ok(None),
+    }
+}
+
+pub(crate) fn remove_secret(id: &str) -> Result<bool, String> {
+    let _guard = STORE_LOCK
+        .lock()
+        .map_err(|_| "Secrets store lock poisoned")?;
+    let mut store = read_store()?;
+    let removed = store.secrets.remove(id).is_some();
+    if removed {
+        write_store(&store)?;
+    }
+    Ok(removed)
+}
+
+#[command]
+pub async fn list_secrets() -> Result<Vec<SecretInfo>, String> {
+    secret_infos()
+}
+
+#[command]
+pub async fn set_secret(id: String, value: String) -> Result<(), String> {
+    put_secret(&id, &value)
+}
+
+#[command]
+pub async fn set_secret_expiry(id: String, expires_at: Option<String>) -> Result<(), String> {
+    set_expiry(&id, expires_at)
+}
+
+#[command]
+pub async fn delete_secret(id: String) -> Result<bool, String> {
+    remove_secret(&id)
+}
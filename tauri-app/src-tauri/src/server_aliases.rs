@@ -0,0 +1,162 @@
+//! Per-client names of one logical server
+//!
+//! Some clients restrict server names (Claude Code only matches permission rules on ASCII
+//! letters, digits, `-` and `_`), so the same server may have to be written as `github` in one
+//! client and `GitHub Tools` in another. The alias map in server_aliases.json in the app data
+//! dir records, per logical name, the name each client has it under; clients without an entry
+//! use the logical name. Sync and copy translate names through it, so a server already present
+//! under its alias is matched instead of being written a second time.
+
+use crate::server_name;
+use crate::settings::app_config_dir;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::command;
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ServerAliases {
+    /// Logical name -> client -> name in that client
+    #[serde(default)]
+    pub servers: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl ServerAliases {
+    /// Logical name of the server `client` has as `name`
+    pub(crate) fn logical_name(&self, client: &str, name: &str) -> String {
+        self.servers
+            .iter()
+            .find(|(_, names)| names.get(client).is_some_and(|n| n == name))
+            .map_or_else(|| name.to_string(), |(logical, _)| logical.clone())
+    }
+
+    /// Name `client` has the logical server under
+    pub(crate) fn name_for(&self, logical: &str, client: &str) -> String {
+        self.servers
+            .get(logical)
+            .and_then(|names| names.get(client))
+            .cloned()
+            .unwrap_or_else(|| logical.to_string())
+    }
+
+    /// Name in `to` of the server `from` has as `name`
+    pub(crate) fn translate(&self, from: &str, name: &str, to: &str) -> String {
+        self.name_for(&self.logical_name(from, name), to)
+    }
+
+    /// `servers` of `from` keyed by their names in `to`
+    pub(crate) fn translate_servers(
+        &self,
+        from: &str,
+        to: &str,
+        servers: Map<String, Value>,
+    ) -> Map<String, Value> {
+        servers
+            .into_iter()
+            .map(|(name, config)| (self.translate(from, &name, to), config))
+            .collect()
+    }
+
+    /// Set or (with `None`) clear the name `client` has `logical` under
+    pub(crate) fn set(
+        &mut self,
+        logical: &str,
+        client: &str,
+        name: Option<&str>,
+    ) -> Result<(), String> {
+        if logical.trim().is_empty() || client.trim().is_empty() {
+            return Err("Logical name and client are required".to_string());
+        }
+        let name = name
+            .map(str::trim)
+            .filter(|n| !n.is_empty() && *n != logical);
+        if let Some(name) = name {
+            if client == "claude_code" {
+                let validation = server_name::check_server_name(name, &[]);
+                if !validation.valid {
+                    return Err(format!(
+                        "Invalid Claude Code server name '{}': {}",
+                        name,
+                        validation.errors.join("; ")
+                    ));
+                }
+            }
+            let taken = self
+                .servers
+                .keys()
+                .find(|other| *other != logical && self.name_for(other, client) == name);
+            if let Some(other) = taken {
+                return Err(format!(
+                    "'{}' already stands for '{}' in {}",
+                    name, other, client
+                ));
+            }
+        }
+        let names = self.servers.entry(logical.to_string()).or_default();
+        match name {
+            Some(name) => names.insert(client.to_string(), name.to_string()),
+            None => names.remove(client),
+        };
+        self.servers.retain(|_, names| !names.is_empty());
+        Ok(())
+    }
+}
+
+fn aliases_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("server_aliases.json"))
+}
+
+fn read_aliases() -> Result<ServerAliases, String> {
+    let path = aliases_path()?;
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse server aliases: {}", e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(ServerAliases::default()),
+        Err(e) => Err(format!("Failed to read server aliases: {}", e)),
+    }
+}
+
+fn write_aliases(aliases: &ServerAliases) -> Result<(), String> {
+    let path = aliases_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(aliases)
+        .map_err(|e| format!("Failed to serialize server aliases: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write server aliases: {}", e))
+}
+
+/// The alias map, empty when it can't be read
+pub(crate) fn load() -> ServerAliases {
+    read_aliases().unwrap_or_else(|e| {
+        println!("[Aliases] {}", e);
+        ServerAliases::default()
+    })
+}
+
+#[command]
+pub async fn list_server_aliases() -> Result<ServerAliases, String> {
+    read_aliases()
+}
+
+/// Record that `client` has the logical server `logical` as `name`; no name clears the alias
+#[command]
+pub async fn set_server_alias(
+    logical: String,
+    client: String,
+    name: Option<String>,
+) -> Result<ServerAliases, String> {
+    let _guard = LOCK
+        .lock()
+        .map_err(|e| format!("Failed to lock server aliases: {}", e))?;
+    let mut aliases = read_aliases()?;
+    aliases.set(&logical, &client, name.as_deref())?;
+    write_aliases(&aliases)?;
+    Ok(aliases)
+}
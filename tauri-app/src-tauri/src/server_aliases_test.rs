@@ -0,0 +1,52 @@
+// Server alias tests
+use crate::server_aliases::ServerAliases;
+use serde_json::{json, Map};
+
+fn aliases() -> ServerAliases {
+    let mut aliases = ServerAliases::default();
+    aliases
+        .set("GitHub Tools", "claude_code", Some("github"))
+        .unwrap();
+    aliases.set("GitHub Tools", "cursor", Some("gh")).unwrap();
+    aliases
+}
+
+#[test]
+fn test_translate_between_aliases() {
+    let aliases = aliases();
+    assert_eq!(aliases.translate("claude_code", "github", "cursor"), "gh");
+    assert_eq!(aliases.translate("cursor", "gh", "claude"), "GitHub Tools");
+    assert_eq!(
+        aliases.translate("claude", "GitHub Tools", "claude_code"),
+        "github"
+    );
+    assert_eq!(aliases.translate("cursor", "fetch", "claude_code"), "fetch");
+}
+
+#[test]
+fn test_translate_servers_renames_keys() {
+    let mut servers = Map::new();
+    servers.insert("gh".to_string(), json!({ "command": "gh-mcp" }));
+    servers.insert("fetch".to_string(), json!({ "command": "uvx" }));
+    let translated = aliases().translate_servers("cursor", "claude_code", servers);
+    assert_eq!(
+        translated.keys().cloned().collect::<Vec<_>>(),
+        vec!["github".to_string(), "fetch".to_string()]
+    );
+}
+
+#[test]
+fn test_set_validates_and_clears() {
+    let mut aliases = aliases();
+    assert!(aliases
+        .set("Slack", "claude_code", Some("slack bot"))
+        .is_err());
+    assert!(aliases.set("Other", "cursor", Some("gh")).is_err());
+    assert_eq!(aliases.translate("cursor", "gh", "claude_code"), "github");
+
+    aliases.set("GitHub Tools", "cursor", None).unwrap();
+    aliases
+        .set("GitHub Tools", "claude_code", Some("GitHub Tools"))
+        .unwrap();
+    assert!(aliases.servers.is_empty());
+}
@@ -0,0 +1,454 @@
+//! Warm selected servers at login
+//!
+//! Flagged servers are listed in autostart.json in the app data dir. While any are flagged the
+//! app registers itself as a login item (a LaunchAgent on macOS, an XDG autostart entry on
+//! Linux, the `Run` registry key on Windows) started with `--autostart`. On such a launch each
+//! flagged stdio server gets a sandboxed test launch, one after another. Clients still start
+//! their own server processes, but the first start (package download, image pull, dependency
+//! resolution) has already happened, so they come up quickly.
+
+use crate::client_target::{self, ClientTarget};
+use crate::profile::{self, PROFILE_ARG};
+use crate::server_handshake;
+use crate::settings::{self, app_config_dir};
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::command;
+
+pub const AUTOSTART_ARG: &str = "--autostart";
+
+static STATUS: Lazy<Mutex<Vec<AutostartStatus>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AutostartEntry {
+    pub target: ClientTarget,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct AutostartFile {
+    #[serde(default)]
+    servers: Vec<AutostartEntry>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WarmState {
+    Pending,
+    Starting,
+    Ready,
+    Failed,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct AutostartStatus {
+    pub target: ClientTarget,
+    pub name: String,
+    pub state: WarmState,
+    pub error: Option<String>,
+    pub elapsed_ms: Option<u64>,
+    pub finished_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct AutostartReport {
+    pub login_item_installed: bool,
+    /// Whether this process was started at login
+    pub launched_at_login: bool,
+    pub servers: Vec<AutostartStatus>,
+}
+
+fn autostart_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("autostart.json"))
+}
+
+fn read_autostart() -> Result<AutostartFile, String> {
+    let path = autostart_path()?;
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse autostart servers: {}", e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(AutostartFile::default()),
+        Err(e) => Err(format!("Failed to read autostart servers: {}", e)),
+    }
+}
+
+fn write_autostart(file: &AutostartFile) -> Result<(), String> {
+    let path = autostart_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(file)
+        .map_err(|e| format!("Failed to serialize autostart servers: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write autostart servers: {}", e))
+}
+
+/// Login item name; profiles get their own so each warms its own servers
+fn login_item_name() -> String {
+    match profile::active_profile() {
+        Some(name) => format!("mcp-linker-{}", name),
+        None => "mcp-linker".to_string(),
+    }
+}
+
+/// Command line of a login launch of `exe` into `profile`
+pub(crate) fn login_args_for(exe: &str, profile: Option<&str>) -> Vec<String> {
+    let mut args = vec![exe.to_string(), AUTOSTART_ARG.to_string()];
+    if let Some(name) = profile {
+        args.push(PROFILE_ARG.to_string());
+        args.push(name.to_string());
+    }
+    args
+}
+
+fn login_args() -> Result<Vec<String>, String> {
+    let exe = std::env::current_exe().map_err(|e| format!("Failed to locate executable: {}", e))?;
+    Ok(login_args_for(
+        &exe.to_string_lossy(),
+        profile::active_profile(),
+    ))
+}
+
+#[cfg(any(test, target_os = "macos"))]
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// LaunchAgent plist running `args`, labelled after the login item `name`
+#[cfg(any(test, target_os = "macos"))]
+pub(crate) fn plist_contents(name: &str, args: &[String]) -> String {
+    let args: String = args
+        .iter()
+        .map(|a| format!("        <string>{}</string>\n", xml_escape(a)))
+        .collect();
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \
+         \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n\
+         <plist version=\"1.0\">\n<dict>\n    <key>Label</key>\n    \
+         <string>com.{}.autostart</string>\n    <key>ProgramArguments</key>\n    <array>\n{}\
+         </array>\n    <key>RunAtLoad</key>\n    <true/>\n</dict>\n</plist>\n",
+        xml_escape(name),
+        args
+    )
+}
+
+/// XDG autostart entry running `args`. Each arg is quoted per the Desktop Entry spec: `"`,
+/// `` ` ``, `$` and `\` are backslash-escaped inside the quotes, `%` is doubled, and the
+/// backslashes are escaped once more as the value is a string.
+#[cfg(any(test, all(unix, not(target_os = "macos"))))]
+pub(crate) fn desktop_entry(args: &[String]) -> String {
+    let exec: Vec<String> = args
+        .iter()
+        .map(|a| {
+            let mut quoted = String::from("\"");
+            for c in a.chars() {
+                match c {
+                    '"' | '`' | '$' => {
+                        quoted.push_str("\\\\");
+                        quoted.push(c);
+                    }
+                    '\\' => quoted.push_str("\\\\\\\\"),
+                    '%' => quoted.push_str("%%"),
+                    c => quoted.push(c),
+                }
+            }
+            quoted.push('"');
+            quoted
+        })
+        .collect();
+    format!(
+        "[Desktop Entry]\nType=Application\nName=MCP Linker\nExec={}\n\
+         X-GNOME-Autostart-enabled=true\nNoDisplay=true\n",
+        exec.join(" ")
+    )
+}
+
+mod login_item {
+    use super::{login_args, login_item_name};
+    use std::path::PathBuf;
+
+    #[cfg(target_os = "macos")]
+    fn path() -> Result<PathBuf, String> {
+        let home = crate::demo_mode::home_dir().ok_or("Failed to get home directory")?;
+        Ok(home
+            .join("Library/LaunchAgents")
+            .join(format!("com.{}.autostart.plist", login_item_name())))
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn path() -> Result<PathBuf, String> {
+        let config = crate::demo_mode::config_dir().ok_or("Failed to get config directory")?;
+        Ok(config
+            .join("autostart")
+            .join(format!("{}.desktop", login_item_name())))
+    }
+
+    #[cfg(target_os = "macos")]
+    fn contents() -> Result<String, String> {
+        Ok(super::plist_contents(&login_item_name(), &login_args()?))
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn contents() -> Result<String, String> {
+        Ok(super::desktop_entry(&login_args()?))
+    }
+
+    #[cfg(unix)]
+    pub fn install() -> Result<(), String> {
+        let path = path()?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create autostart directory: {}", e))?;
+        }
+        std::fs::write(&path, contents()?).map_err(|e| format!("Failed to write login item: {}", e))
+    }
+
+    #[cfg(unix)]
+    pub fn uninstall() -> Result<(), String> {
+        match std::fs::remove_file(path()?) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(format!("Failed to remove login item: {}", e))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    #[cfg(unix)]
+    pub fn is_installed() -> bool {
+        path().is_ok_and(|p| p.is_file())
+    }
+
+    #[cfg(windows)]
+    const RUN_KEY: &str = r"HKCU\Software\Microsoft\Windows\CurrentVersion\Run";
+
+    #[cfg(windows)]
+    fn reg(args: &[&str]) -> Result<bool, String> {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        std::process::Command::new("reg")
+            .args(args)
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+            .map(|o| o.status.success())
+            .map_err(|e| format!("Failed to run reg: {}", e))
+    }
+
+    #[cfg(windows)]
+    pub fn install() -> Result<(), String> {
+        let command: Vec<String> = login_args()?.iter().map(|a| format!("\"{}\"", a)).collect();
+        let name = login_item_name();
+        let value = command.join(" ");
+        let args = [
+            "add", RUN_KEY, "/v", &name, "/t", "REG_SZ", "/d", &value, "/f",
+        ];
+        if reg(&args)? {
+            Ok(())
+        } else {
+            Err("Failed to add login item to the registry".to_string())
+        }
+    }
+
+    #[cfg(windows)]
+    pub fn uninstall() -> Result<(), String> {
+        if is_installed() {
+            reg(&["delete", RUN_KEY, "/v", &login_item_name(), "/f"])?;
+        }
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    pub fn is_installed() -> bool {
+        reg(&["query", RUN_KEY, "/v", &login_item_name()]).unwrap_or(false)
+    }
+}
+
+pub(crate) fn launched_at_login() -> bool {
+    std::env::args().any(|arg| arg == AUTOSTART_ARG)
+}
+
+fn set_status(entry: &AutostartEntry, state: WarmState, update: impl FnOnce(&mut AutostartStatus)) {
+    if let Ok(mut statuses) = STATUS.lock() {
+        let index = match statuses
+            .iter()
+            .position(|s| s.target == entry.target && s.name == entry.name)
+        {
+            Some(index) => index,
+            None => {
+                statuses.push(AutostartStatus {
+                    target: entry.target.clone(),
+                    name: entry.name.clone(),
+                    state,
+                    error: None,
+                    elapsed_ms: None,
+                    finished_at: None,
+                });
+                statuses.len() - 1
+            }
+        };
+        statuses[index].state = state;
+        update(&mut statuses[index]);
+    }
+}
+
+/// Test-launch every flagged server in turn
+pub(crate) async fn warm_servers() {
+    let entries = match read_autostart() {
+        Ok(file) => file.servers,
+        Err(e) => {
+            println!("[Autostart] {}", e);
+            return;
+        }
+    };
+    if let Ok(mut statuses) = STATUS.lock() {
+        statuses.retain(|s| {
+            entries
+                .iter()
+                .any(|e| e.target == s.target && e.name == s.name)
+        });
+    }
+    for entry in &entries {
+        set_status(entry, WarmState::Pending, |s| s.error = None);
+    }
+    let sandbox = settings::load_settings().sandbox;
+    for entry in &entries {
+        set_status(entry, WarmState::Starting, |_| {});
+        let started = Instant::now();
+        let config = client_target::list_servers(&entry.target)
+            .await
+            .and_then(|servers| {
+                servers
+                    .get(&entry.name)
+                    .cloned()
+                    .ok_or_else(|| format!("Server '{}' not found", entry.name))
+            });
+        let result = match config {
+            Ok(config) => server_handshake::connect(&config, &sandbox, Default::default())
+                .await
+                .map(|_| ())
+                .map_err(|(e, _)| e),
+            Err(e) => Err(e),
+        };
+        let elapsed_ms = started.elapsed().as_millis() as u64;
+        let state = if result.is_ok() {
+            WarmState::Ready
+        } else {
+            WarmState::Failed
+        };
+        println!(
+            "[Autostart] {} in {}: {:?} after {}ms",
+            entry.name,
+            entry.target.label(),
+            state,
+            elapsed_ms
+        );
+        set_status(entry, state, |s| {
+            s.error = result.err();
+            s.elapsed_ms = Some(elapsed_ms);
+            s.finished_at = Some(Utc::now().to_rfc3339());
+        });
+    }
+}
+
+/// On a login launch, warm the flagged servers in the background
+pub fn spawn_autostart() {
+    if !launched_at_login() {
+        return;
+    }
+    tauri::async_runtime::spawn(warm_servers());
+}
+
+/// Flag or unflag `entry` in `servers`. Flagging needs the server's `config`, which must be a
+/// stdio server; on failure `servers` is left as it was.
+pub(crate) fn apply_flag(
+    servers: &mut Vec<AutostartEntry>,
+    entry: AutostartEntry,
+    enabled: bool,
+    config: Option<&Value>,
+) -> Result<(), String> {
+    if enabled {
+        let config = config.ok_or_else(|| format!("Server '{}' not found", entry.name))?;
+        if config["command"].as_str().is_none() {
+            return Err("Only stdio servers can be started at login".to_string());
+        }
+    }
+    servers.retain(|e| *e != entry);
+    if enabled {
+        servers.push(entry);
+    }
+    Ok(())
+}
+
+/// Flag or unflag a server for warming at login; installs or removes the login item as needed
+#[command]
+pub async fn set_server_autostart(
+    target: ClientTarget,
+    name: String,
+    enabled: bool,
+) -> Result<Vec<AutostartEntry>, String> {
+    let mut file = read_autostart()?;
+    let config = if enabled {
+        client_target::list_servers(&target)
+            .await?
+            .get(&name)
+            .cloned()
+    } else {
+        None
+    };
+    apply_flag(
+        &mut file.servers,
+        AutostartEntry { target, name },
+        enabled,
+        config.as_ref(),
+    )?;
+    write_autostart(&file)?;
+    if file.servers.is_empty() {
+        login_item::uninstall()?;
+    } else {
+        login_item::install()?;
+    }
+    Ok(file.servers)
+}
+
+#[command]
+pub async fn get_autostart_status() -> Result<AutostartReport, String> {
+    let entries = read_autostart()?.servers;
+    let statuses = STATUS.lock().map(|s| s.clone()).unwrap_or_default();
+    let servers = entries
+        .iter()
+        .map(|entry| {
+            statuses
+                .iter()
+                .find(|s| s.target == entry.target && s.name == entry.name)
+                .cloned()
+                .unwrap_or_else(|| AutostartStatus {
+                    target: entry.target.clone(),
+                    name: entry.name.clone(),
+                    state: WarmState::Pending,
+                    error: None,
+                    elapsed_ms: None,
+                    finished_at: None,
+                })
+        })
+        .collect();
+    Ok(AutostartReport {
+        login_item_installed: login_item::is_installed(),
+        launched_at_login: launched_at_login(),
+        servers,
+    })
+}
+
+/// Warm the flagged servers now instead of waiting for the next login
+#[command]
+pub async fn run_autostart_now() -> Result<AutostartReport, String> {
+    warm_servers().await;
+    get_autostart_status().await
+}
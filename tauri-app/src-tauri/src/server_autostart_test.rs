@@ -0,0 +1,72 @@
+// Server autostart tests
+use crate::client_target::ClientTarget;
+use crate::server_autostart::{
+    apply_flag, desktop_entry, login_args_for, plist_contents, AutostartEntry,
+};
+use serde_json::json;
+
+fn entry(name: &str) -> AutostartEntry {
+    AutostartEntry {
+        target: ClientTarget::client("cursor", None),
+        name: name.to_string(),
+    }
+}
+
+#[test]
+fn test_login_args() {
+    assert_eq!(
+        login_args_for("/opt/mcp-linker", None),
+        vec!["/opt/mcp-linker", "--autostart"]
+    );
+    assert_eq!(
+        login_args_for("/opt/mcp-linker", Some("work")),
+        vec!["/opt/mcp-linker", "--autostart", "--profile", "work"]
+    );
+}
+
+#[test]
+fn test_desktop_entry_quotes_args() {
+    let args = login_args_for("/opt/My Apps/mcp \"linker\"", Some("100%$HOME`x`\\"));
+    let entry = desktop_entry(&args);
+    assert!(entry.starts_with("[Desktop Entry]\nType=Application\n"));
+    let exec = entry.lines().find(|l| l.starts_with("Exec=")).unwrap();
+    assert_eq!(
+        exec,
+        r#"Exec="/opt/My Apps/mcp \\"linker\\"" "--autostart" "--profile" "100%%\\$HOME\\`x\\`\\\\""#
+    );
+}
+
+#[test]
+fn test_plist_escapes_args() {
+    let args = login_args_for("/Applications/A&B <Beta>.app/mcp-linker", Some("work"));
+    let plist = plist_contents("mcp-linker-work", &args);
+    assert!(plist.contains("<string>com.mcp-linker-work.autostart</string>"));
+    assert!(plist.contains(
+        "        <string>/Applications/A&amp;B &lt;Beta&gt;.app/mcp-linker</string>\n\
+         \x20       <string>--autostart</string>\n\
+         \x20       <string>--profile</string>\n\
+         \x20       <string>work</string>\n"
+    ));
+    assert!(plist.contains("<key>RunAtLoad</key>\n    <true/>"));
+}
+
+#[test]
+fn test_apply_flag() {
+    let stdio = json!({ "command": "npx", "args": ["server"] });
+    let remote = json!({ "type": "http", "url": "https://example.com/mcp" });
+    let mut servers = vec![entry("a")];
+
+    apply_flag(&mut servers, entry("b"), true, Some(&stdio)).unwrap();
+    // Flagging again doesn't duplicate the entry
+    apply_flag(&mut servers, entry("b"), true, Some(&stdio)).unwrap();
+    assert_eq!(servers, vec![entry("a"), entry("b")]);
+
+    let err = apply_flag(&mut servers, entry("c"), true, Some(&remote)).unwrap_err();
+    assert!(err.contains("Only stdio servers"));
+    let err = apply_flag(&mut servers, entry("a"), true, None).unwrap_err();
+    assert!(err.contains("not found"));
+    assert_eq!(servers, vec![entry("a"), entry("b")]);
+
+    apply_flag(&mut servers, entry("a"), false, None).unwrap();
+    assert_eq!(servers, vec![entry("b")]);
+}
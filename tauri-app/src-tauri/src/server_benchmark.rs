@@ -0,0 +1,381 @@
+//! Comparing the local and hosted variants of a registry server
+//!
+//! Many registry servers can be launched from their package (stdio) or reached at one of their
+//! remotes. `benchmark_server_variants` connects to each variant in turn, runs the same
+//! workload against it and reports startup time, per-call latency and throughput, so the user
+//! can pick the variant to configure. The workload is `WORKLOAD_CALLS` sequential `tools/list`
+//! requests: every server answers them and, unlike tool calls, they have no side effects.
+//! Remote variants are spoken to over Streamable HTTP; legacy SSE remotes are listed but not
+//! measured. Variants come from the registries fetched so far (see `registry_import`).
+
+use crate::claude_code_commands;
+use crate::config_summary;
+use crate::endpoint_latency::median;
+use crate::network;
+use crate::registry_import;
+use crate::server_handshake::{self, ClientOptions, McpSession, PROTOCOL_VERSION};
+use crate::settings::{self, SandboxSettings};
+use chrono::Utc;
+use reqwest::header::CONTENT_TYPE;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+use tauri::command;
+
+const WORKLOAD_CALLS: usize = 10;
+
+/// Longest wait for one workload response
+const CALL_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Serialize, Clone)]
+pub struct VariantBenchmark {
+    /// "stdio via npx", "http at mcp.example.com"
+    pub label: String,
+    pub transport: String,
+    /// Launch or connect plus the initialize handshake
+    pub startup_ms: Option<u64>,
+    /// Median workload call
+    pub latency_ms: Option<u64>,
+    pub p95_ms: Option<u64>,
+    pub calls_per_sec: Option<f64>,
+    /// Workload calls that got an answer
+    pub completed: usize,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct VariantComparison {
+    pub server: String,
+    pub registry_name: String,
+    pub checked_at: String,
+    pub calls: usize,
+    pub variants: Vec<VariantBenchmark>,
+    /// Label of the variant with the lowest latency among those that completed the workload
+    pub fastest: Option<String>,
+}
+
+/// Client configs of a registry entry's variants: its package first, then every remote
+pub(crate) fn variants(entry: &Value) -> Vec<Value> {
+    let server = entry.get("server").unwrap_or(entry);
+    registry_import::package_config(server)
+        .into_iter()
+        .chain(
+            server["remotes"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(registry_import::remote_entry),
+        )
+        .collect()
+}
+
+/// Value at percentile `pct` (0-100) of the samples
+pub(crate) fn percentile(samples: &[u64], pct: usize) -> Option<u64> {
+    let mut sorted = samples.to_vec();
+    sorted.sort_unstable();
+    let rank = (sorted.len() * pct).div_ceil(100);
+    sorted.get(rank.saturating_sub(1)).copied()
+}
+
+pub(crate) fn fastest(variants: &[VariantBenchmark], calls: usize) -> Option<&VariantBenchmark> {
+    variants
+        .iter()
+        .filter(|v| v.completed == calls && v.latency_ms.is_some())
+        .min_by_key(|v| v.latency_ms)
+}
+
+/// The JSON-RPC message with `id` among the complete events of an SSE body
+pub(crate) fn sse_response(body: &str, id: &Value) -> Option<Value> {
+    let body = body.replace("\r\n", "\n");
+    let mut events: Vec<&str> = body.split("\n\n").collect();
+    // The last piece is an event still being received
+    events.pop();
+    events.into_iter().find_map(|event| {
+        let data = event
+            .lines()
+            .filter_map(|line| line.strip_prefix("data:"))
+            .map(str::trim_start)
+            .collect::<Vec<_>>()
+            .join("\n");
+        serde_json::from_str::<Value>(&data)
+            .ok()
+            .filter(|message| message["id"] == *id)
+    })
+}
+
+fn rpc_result(method: &str, message: Value) -> Result<Value, String> {
+    match message.get("error") {
+        Some(error) => Err(format!(
+            "{} failed: {}",
+            method,
+            error["message"].as_str().unwrap_or("unknown error")
+        )),
+        None => Ok(message["result"].clone()),
+    }
+}
+
+/// Just enough of a Streamable HTTP client for the workload: requests are POSTed and
+/// answered with JSON or an SSE stream; server requests are not handled
+struct HttpSession {
+    client: reqwest::Client,
+    url: String,
+    headers: Vec<(String, String)>,
+    session_id: Option<String>,
+    next_id: u64,
+}
+
+impl HttpSession {
+    async fn post(&self, message: &Value) -> Result<reqwest::Response, String> {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header(CONTENT_TYPE, "application/json")
+            .header("Accept", "application/json, text/event-stream")
+            .body(message.to_string());
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        if let Some(session_id) = &self.session_id {
+            request = request
+                .header("Mcp-Session-Id", session_id)
+                .header("MCP-Protocol-Version", PROTOCOL_VERSION);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach server: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("Server answered {}", response.status()));
+        }
+        Ok(response)
+    }
+
+    async fn request(&mut self, method: &str, params: Value) -> Result<Value, String> {
+        self.next_id += 1;
+        let id = json!(self.next_id);
+        let mut response = self
+            .post(&json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))
+            .await?;
+        if let Some(session_id) = response
+            .headers()
+            .get("Mcp-Session-Id")
+            .and_then(|v| v.to_str().ok())
+        {
+            self.session_id = Some(session_id.to_string());
+        }
+        let streamed = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|t| t.starts_with("text/event-stream"));
+        if !streamed {
+            let message = response
+                .json::<Value>()
+                .await
+                .map_err(|e| format!("Failed to read {} response: {}", method, e))?;
+            return rpc_result(method, message);
+        }
+        // Servers may keep the stream open after answering, so read only up to the response
+        let mut body = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| format!("Failed to read {} response: {}", method, e))?
+        {
+            body.extend_from_slice(&chunk);
+            if let Some(message) = sse_response(&String::from_utf8_lossy(&body), &id) {
+                return rpc_result(method, message);
+            }
+        }
+        Err(format!(
+            "Server closed the stream without answering {}",
+            method
+        ))
+    }
+
+    async fn notify(&self, method: &str) -> Result<(), String> {
+        self.post(&json!({ "jsonrpc": "2.0", "method": method }))
+            .await
+            .map(|_| ())
+    }
+
+    /// End the server-side session; failures don't matter to the benchmark
+    async fn close(&self) {
+        if let Some(session_id) = &self.session_id {
+            let _ = self
+                .client
+                .delete(&self.url)
+                .header("Mcp-Session-Id", session_id)
+                .send()
+                .await;
+        }
+    }
+}
+
+enum Session {
+    Stdio(McpSession),
+    Http(HttpSession),
+}
+
+impl Session {
+    async fn connect(
+        config: &Value,
+        client: &reqwest::Client,
+        sandbox: &SandboxSettings,
+    ) -> Result<Session, String> {
+        if config["command"].is_string() {
+            return server_handshake::connect(config, sandbox, ClientOptions::default())
+                .await
+                .map(Session::Stdio)
+                .map_err(|(e, _)| e);
+        }
+        let mut session = HttpSession {
+            client: client.clone(),
+            url: config["url"].as_str().unwrap_or_default().to_string(),
+            headers: config["headers"]
+                .as_object()
+                .into_iter()
+                .flatten()
+                .filter_map(|(name, value)| Some((name.clone(), value.as_str()?.to_string())))
+                .collect(),
+            session_id: None,
+            next_id: 0,
+        };
+        let params = json!({
+            "protocolVersion": PROTOCOL_VERSION,
+            "capabilities": {},
+            "clientInfo": { "name": "mcp-linker", "version": env!("CARGO_PKG_VERSION") }
+        });
+        let handshake = async {
+            session.request("initialize", params).await?;
+            session.notify("notifications/initialized").await
+        };
+        match tokio::time::timeout(sandbox.launch_timeout(), handshake).await {
+            Ok(Ok(())) => Ok(Session::Http(session)),
+            Ok(Err(e)) => Err(e),
+            Err(_) => Err(format!(
+                "No handshake response within {}s",
+                sandbox.launch_timeout().as_secs()
+            )),
+        }
+    }
+
+    async fn request(&mut self, method: &str, params: Value) -> Result<Value, String> {
+        match self {
+            Session::Stdio(session) => session.request(method, params).await,
+            Session::Http(session) => session.request(method, params).await,
+        }
+    }
+
+    async fn close(self) {
+        if let Session::Http(session) = self {
+            session.close().await;
+        }
+    }
+}
+
+async fn run_variant(
+    config: &Value,
+    client: &reqwest::Client,
+    sandbox: &SandboxSettings,
+) -> VariantBenchmark {
+    let server = claude_code_commands::parse_server_config("variant", config).ok();
+    let mut report = VariantBenchmark {
+        label: server
+            .as_ref()
+            .map(config_summary::reached)
+            .unwrap_or_else(|| "unknown".to_string()),
+        transport: config["type"].as_str().unwrap_or("stdio").to_string(),
+        startup_ms: None,
+        latency_ms: None,
+        p95_ms: None,
+        calls_per_sec: None,
+        completed: 0,
+        error: None,
+    };
+    if report.transport == "sse" {
+        report.error = Some("Legacy SSE endpoints aren't benchmarked".to_string());
+        return report;
+    }
+
+    let started = Instant::now();
+    let mut session = match Session::connect(config, client, sandbox).await {
+        Ok(session) => session,
+        Err(e) => {
+            report.error = Some(e);
+            return report;
+        }
+    };
+    report.startup_ms = Some(started.elapsed().as_millis() as u64);
+
+    let mut samples = Vec::new();
+    let workload = Instant::now();
+    for _ in 0..WORKLOAD_CALLS {
+        let call = Instant::now();
+        match tokio::time::timeout(CALL_TIMEOUT, session.request("tools/list", json!({}))).await {
+            Ok(Ok(_)) => samples.push(call.elapsed().as_millis() as u64),
+            Ok(Err(e)) => {
+                report.error = Some(e);
+                break;
+            }
+            Err(_) => {
+                report.error = Some(format!("No response within {}s", CALL_TIMEOUT.as_secs()));
+                break;
+            }
+        }
+    }
+    let elapsed = workload.elapsed().as_secs_f64();
+    session.close().await;
+
+    report.completed = samples.len();
+    report.p95_ms = percentile(&samples, 95);
+    if !samples.is_empty() && elapsed > 0.0 {
+        report.calls_per_sec = Some(samples.len() as f64 / elapsed);
+    }
+    report.latency_ms = median(samples);
+    report
+}
+
+/// Run the workload against every variant of `server_id` (registry name or local name) in
+/// the fetched registries
+#[command]
+pub async fn benchmark_server_variants(server_id: String) -> Result<VariantComparison, String> {
+    let (server, entry) = registry_import::cached_entries()
+        .into_iter()
+        .find_map(|entry| {
+            registry_import::convert_entry(&entry)
+                .filter(|s| s.registry_name == server_id || s.name == server_id)
+                .map(|server| (server, entry))
+        })
+        .ok_or_else(|| format!("Server '{}' not found in the fetched registries", server_id))?;
+    let configs = variants(&entry);
+    if configs.len() < 2 {
+        return Err(format!(
+            "'{}' has only one variant; there is nothing to compare",
+            server_id
+        ));
+    }
+
+    let settings = settings::load_settings();
+    let client = network::http_client(&settings.network)?;
+    let mut results = Vec::new();
+    // One at a time, so variants don't compete for CPU and bandwidth
+    for config in &configs {
+        results.push(run_variant(config, &client, &settings.sandbox).await);
+    }
+    let fastest = fastest(&results, WORKLOAD_CALLS).map(|v| v.label.clone());
+    println!(
+        "[Benchmark] {}: {} variants, fastest {}",
+        server.name,
+        results.len(),
+        fastest.as_deref().unwrap_or("none")
+    );
+    Ok(VariantComparison {
+        server: server.name,
+        registry_name: server.registry_name,
+        checked_at: Utc::now().to_rfc3339(),
+        calls: WORKLOAD_CALLS,
+        variants: results,
+        fastest,
+    })
+}
@@ -0,0 +1,73 @@
+// Server variant benchmark tests
+use crate::server_benchmark::{fastest, percentile, sse_response, variants, VariantBenchmark};
+use serde_json::json;
+
+fn variant(label: &str, latency_ms: Option<u64>, completed: usize) -> VariantBenchmark {
+    VariantBenchmark {
+        label: label.to_string(),
+        transport: "stdio".to_string(),
+        startup_ms: Some(400),
+        latency_ms,
+        p95_ms: latency_ms,
+        calls_per_sec: None,
+        completed,
+        error: None,
+    }
+}
+
+#[test]
+fn test_variants_lists_package_then_remotes() {
+    let entry = json!({
+        "server": {
+            "name": "io.example/search",
+            "packages": [{ "registryType": "npm", "identifier": "@example/search" }],
+            "remotes": [
+                { "type": "streamable-http", "url": "https://mcp.example.com/mcp" },
+                { "type": "sse", "url": "https://mcp.example.com/sse" }
+            ]
+        }
+    });
+    let configs = variants(&entry);
+    assert_eq!(configs.len(), 3);
+    assert_eq!(configs[0]["command"], "npx");
+    assert_eq!(
+        configs[1],
+        json!({ "type": "http", "url": "https://mcp.example.com/mcp" })
+    );
+    assert_eq!(configs[2]["type"], "sse");
+}
+
+#[test]
+fn test_percentile() {
+    let samples: Vec<u64> = (1..=20).collect();
+    assert_eq!(percentile(&samples, 95), Some(19));
+    assert_eq!(percentile(&[7], 95), Some(7));
+    assert_eq!(percentile(&[], 95), None);
+}
+
+#[test]
+fn test_fastest_ignores_incomplete_variants() {
+    let results = vec![
+        variant("stdio via npx", Some(12), 10),
+        variant("http at mcp.example.com", Some(5), 4),
+        variant("http at eu.example.com", Some(40), 10),
+    ];
+    assert_eq!(
+        fastest(&results, 10).map(|v| v.label.as_str()),
+        Some("stdio via npx")
+    );
+    assert!(fastest(&results[1..2], 10).is_none());
+}
+
+#[test]
+fn test_sse_response_waits_for_complete_event() {
+    let id = json!(2);
+    let body = "event: message\r\ndata: {\"jsonrpc\":\"2.0\",\"method\":\"notifications/progress\"}\r\n\r\n\
+                data: {\"jsonrpc\":\"2.0\",\"id\":2,\"result\":{\"tools\":[]}}\n";
+    assert_eq!(sse_response(body, &id), None);
+    let complete = format!("{}\n", body);
+    assert_eq!(
+        sse_response(&complete, &id),
+        Some(json!({ "jsonrpc": "2.0", "id": 2, "result": { "tools": [] } }))
+    );
+}
@@ -0,0 +1,150 @@
+//! Copy chosen servers from one client to another, e.g. between Claude Desktop and Claude Code
+//!
+//! `copy_servers` reads the named active servers of the source target and writes them to the
+//! destination as one batch (see `batch_ops`), so a copy can be undone as a whole. Entries are
+//! put in the destination's shape first: enable flags of the source client are dropped,
+//! Claude Desktop gets no `type` and Windsurf gets `serverUrl` for `url`. Claude Desktop only
+//! runs stdio servers from its config file (remote ones are added as connectors in the app),
+//! so remote servers aren't copied there.
+//! Names are translated through the alias map (see `server_aliases`), and names the
+//! destination already has are left alone unless `overwrite` is set.
+
+use crate::batch_ops::{self, Operation};
+use crate::client_target::{self, ClientTarget};
+use crate::server_aliases;
+use crate::windsurf_commands;
+use serde::Serialize;
+use serde_json::Value;
+use tauri::command;
+
+const DESKTOP_REMOTE: &str =
+    "Claude Desktop only runs stdio servers from its config; add it as a connector instead";
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct CopyFailure {
+    pub name: String,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CopyReport {
+    /// Names written to the destination
+    pub copied: Vec<String>,
+    /// Names the destination already had, left as they were
+    pub existing: Vec<String>,
+    pub failed: Vec<CopyFailure>,
+    /// Id for `undo_batch`
+    pub undo_id: Option<String>,
+}
+
+/// `config` as an entry of `client`'s config
+pub(crate) fn entry_for(client: &str, config: &Value) -> Result<Value, String> {
+    let mut entry = windsurf_commands::entry_from_windsurf(config);
+    let Some(fields) = entry.as_object_mut() else {
+        return Err("Server config must be an object".to_string());
+    };
+    fields.shift_remove("disabled");
+    fields.shift_remove("isActive");
+    if client == "claude" {
+        if !fields.contains_key("command") {
+            return Err(DESKTOP_REMOTE.to_string());
+        }
+        fields.shift_remove("type");
+    }
+    if client == "windsurf" {
+        return Ok(windsurf_commands::entry_to_windsurf(&entry));
+    }
+    Ok(entry)
+}
+
+/// Write source `entries` to `to` as one batch, in `to`'s shape. Names `to` already has are
+/// left alone unless `overwrite` is set.
+pub(crate) async fn write_entries(
+    to: &ClientTarget,
+    entries: Vec<(String, Result<Value, String>)>,
+    overwrite: bool,
+) -> Result<CopyReport, String> {
+    let mut present = client_target::list_servers(to).await?;
+    present.extend(client_target::list_disabled_servers(to).await?);
+
+    let mut report = CopyReport {
+        copied: Vec::new(),
+        existing: Vec::new(),
+        failed: Vec::new(),
+        undo_id: None,
+    };
+    let mut ops = Vec::new();
+    for (name, entry) in entries {
+        if present.contains_key(&name) && !overwrite {
+            report.existing.push(name);
+            continue;
+        }
+        match entry.and_then(|config| entry_for(&to.client, &config)) {
+            Ok(config) => ops.push(Operation::UpsertServer {
+                target: to.clone(),
+                name,
+                config,
+            }),
+            Err(error) => report.failed.push(CopyFailure { name, error }),
+        }
+    }
+    if ops.is_empty() {
+        return Ok(report);
+    }
+
+    let label = format!("Copy {} server(s) to {}", ops.len(), to.label());
+    let batch = batch_ops::run_batch(&label, &ops, false, true).await;
+    for result in batch.results {
+        let Some(Operation::UpsertServer { name, .. }) = ops.get(result.index) else {
+            continue;
+        };
+        match result.error {
+            None => report.copied.push(name.clone()),
+            Some(error) => report.failed.push(CopyFailure {
+                name: name.clone(),
+                error,
+            }),
+        }
+    }
+    report.undo_id = batch.undo_id;
+    Ok(report)
+}
+
+/// Copy the servers `names` of `from` to `to`
+#[command]
+pub async fn copy_servers(
+    from: ClientTarget,
+    to: ClientTarget,
+    names: Vec<String>,
+    overwrite: Option<bool>,
+) -> Result<CopyReport, String> {
+    if from == to {
+        return Err("Source and destination are the same".to_string());
+    }
+    let source = client_target::list_servers(&from).await?;
+    let aliases = server_aliases::load();
+    let entries = names
+        .into_iter()
+        .map(|source_name| {
+            let entry = source.get(&source_name).cloned().ok_or_else(|| {
+                format!(
+                    "'{}' is not an active server of {}",
+                    source_name,
+                    from.label()
+                )
+            });
+            let name = aliases.translate(&from.client, &source_name, &to.client);
+            (name, entry)
+        })
+        .collect();
+    let report = write_entries(&to, entries, overwrite.unwrap_or(false)).await?;
+    println!(
+        "[Copy] {} to {}: {} copied, {} existing, {} failed",
+        from.label(),
+        to.label(),
+        report.copied.len(),
+        report.existing.len(),
+        report.failed.len()
+    );
+    Ok(report)
+}
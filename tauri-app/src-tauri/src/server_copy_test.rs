@@ -0,0 +1,29 @@
+// Server copy tests
+use crate::server_copy::entry_for;
+use serde_json::json;
+
+#[test]
+fn test_entry_for_desktop_drops_type_and_flags() {
+    let config =
+        json!({ "type": "stdio", "command": "npx", "args": ["-y", "x"], "disabled": false });
+    assert_eq!(
+        entry_for("claude", &config).unwrap(),
+        json!({ "command": "npx", "args": ["-y", "x"] })
+    );
+}
+
+#[test]
+fn test_entry_for_desktop_refuses_remote_servers() {
+    let config = json!({ "type": "http", "url": "https://example.com/mcp" });
+    let err = entry_for("claude", &config).unwrap_err();
+    assert!(err.contains("connector"), "{}", err);
+}
+
+#[test]
+fn test_entry_for_claude_code_keeps_remote_servers() {
+    let config = json!({ "type": "http", "url": "https://example.com/mcp", "isActive": true });
+    assert_eq!(
+        entry_for("claude_code", &config).unwrap(),
+        json!({ "type": "http", "url": "https://example.com/mcp" })
+    );
+}
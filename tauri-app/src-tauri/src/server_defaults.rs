@@ -0,0 +1,255 @@
+//! Env values remembered from past installs, to pre-fill the next install of a package
+//!
+//! Every recorded upsert of a server that runs a registry package (see `server_package`) notes
+//! the env keys and values it was written with, per package, in install_defaults.json in the
+//! app data dir of the active profile. Values of keys that look like credentials are kept in
+//! the profile's secrets store under `install-default:<package>:<key>` instead. The file is
+//! part of the exported app state. Nothing leaves the machine. `suggest_server_defaults`
+//! returns the latest value of each key.
+
+use crate::server_package::{self, Ecosystem, PackageRef};
+use crate::settings::app_config_dir;
+use crate::{redaction, secrets};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::command;
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RememberedEnv {
+    pub key: String,
+    /// Plain value; `None` for secret keys, whose value is in the secrets store
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(default)]
+    pub secret: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PackageDefaults {
+    pub package: PackageRef,
+    pub env: Vec<RememberedEnv>,
+    pub updated_at: String,
+    /// Upserts recorded for the package
+    pub installs: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct DefaultsFile {
+    #[serde(default)]
+    packages: BTreeMap<String, PackageDefaults>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ServerDefaults {
+    pub package: PackageRef,
+    /// Values to pre-fill
+    pub env: BTreeMap<String, String>,
+    /// Secret keys whose value isn't in this profile's secrets store
+    pub missing_secrets: Vec<String>,
+    pub updated_at: String,
+}
+
+fn defaults_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("install_defaults.json"))
+}
+
+fn read_defaults() -> Result<DefaultsFile, String> {
+    let path = defaults_path()?;
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse install defaults: {}", e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(DefaultsFile::default()),
+        Err(e) => Err(format!("Failed to read install defaults: {}", e)),
+    }
+}
+
+fn write_defaults(file: &DefaultsFile) -> Result<(), String> {
+    let path = defaults_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(file)
+        .map_err(|e| format!("Failed to serialize install defaults: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write install defaults: {}", e))
+}
+
+/// Key of a package in the defaults file; versions don't matter
+pub(crate) fn package_key(package: &PackageRef) -> String {
+    let ecosystem = match package.ecosystem {
+        Ecosystem::Npm => "npm",
+        Ecosystem::Pypi => "pypi",
+        Ecosystem::Docker => "docker",
+    };
+    format!("{}:{}", ecosystem, package.name)
+}
+
+fn secret_id(key: &str, env_key: &str) -> String {
+    format!("install-default:{}:{}", key, env_key)
+}
+
+/// Env vars worth remembering: non-empty strings that aren't references to the environment
+pub(crate) fn rememberable(env: &Map<String, Value>) -> Vec<(String, String)> {
+    env.iter()
+        .filter_map(|(key, value)| {
+            let value = value.as_str()?.trim();
+            let is_reference = value.starts_with("${") && value.ends_with('}');
+            (!value.is_empty() && !is_reference).then(|| (key.clone(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Remembered env after an install with `written`: its keys take the new values, keys it
+/// didn't set are kept
+pub(crate) fn merge_env(
+    previous: &[RememberedEnv],
+    written: &[(String, String)],
+) -> Vec<RememberedEnv> {
+    let mut merged: Vec<RememberedEnv> = previous
+        .iter()
+        .filter(|env| !written.iter().any(|(key, _)| *key == env.key))
+        .cloned()
+        .collect();
+    let rules = redaction::rules();
+    merged.extend(written.iter().map(|(key, value)| {
+        let secret = rules.is_secret_key(key);
+        RememberedEnv {
+            key: key.clone(),
+            value: (!secret).then(|| value.clone()),
+            secret,
+        }
+    }));
+    merged.sort_by(|a, b| a.key.cmp(&b.key));
+    merged
+}
+
+fn store(key: &str, package: PackageRef, written: &[(String, String)]) -> Result<(), String> {
+    let _guard = LOCK
+        .lock()
+        .map_err(|e| format!("Failed to lock install defaults: {}", e))?;
+    let rules = redaction::rules();
+    for (env_key, value) in written.iter().filter(|(k, _)| rules.is_secret_key(k)) {
+        secrets::put_secret(&secret_id(key, env_key), value)?;
+    }
+    let mut file = read_defaults()?;
+    let previous = file.packages.remove(key);
+    let entry = PackageDefaults {
+        env: merge_env(
+            previous
+                .as_ref()
+                .map(|p| p.env.as_slice())
+                .unwrap_or_default(),
+            written,
+        ),
+        installs: previous.map_or(0, |p| p.installs) + 1,
+        updated_at: Utc::now().to_rfc3339(),
+        package: PackageRef {
+            version: None,
+            ..package
+        },
+    };
+    file.packages.insert(key.to_string(), entry);
+    write_defaults(&file)
+}
+
+/// Note the env a server config was written with. Called for recorded upserts.
+pub(crate) fn remember(config: &Value) {
+    let Some(package) = server_package::package_ref(config) else {
+        return;
+    };
+    let written = config["env"]
+        .as_object()
+        .map(rememberable)
+        .unwrap_or_default();
+    if written.is_empty() {
+        return;
+    }
+    let key = package_key(&package);
+    if let Err(e) = store(&key, package, &written) {
+        println!("[Defaults] Failed to remember env of {}: {}", key, e);
+    }
+}
+
+/// Keys `package` may be stored under: it may be given with a version, in npm, PyPI or image
+/// syntax
+fn candidate_keys(package: &str) -> Vec<String> {
+    [
+        server_package::parse_npm_spec(package),
+        server_package::parse_pypi_spec(package),
+        server_package::parse_image(package),
+    ]
+    .iter()
+    .map(package_key)
+    .collect()
+}
+
+/// Env values the user configured the last times `package` was installed, for pre-filling
+#[command]
+pub async fn suggest_server_defaults(package: String) -> Result<Option<ServerDefaults>, String> {
+    let file = read_defaults()?;
+    let Some((key, found)) = candidate_keys(&package)
+        .into_iter()
+        .find_map(|key| file.packages.get(&key).map(|found| (key, found)))
+    else {
+        return Ok(None);
+    };
+    let mut env = BTreeMap::new();
+    let mut missing_secrets = Vec::new();
+    for remembered in &found.env {
+        let value = match (&remembered.value, remembered.secret) {
+            (Some(value), _) => Some(value.clone()),
+            (None, true) => secrets::get_secret(&secret_id(&key, &remembered.key))?,
+            (None, false) => None,
+        };
+        match value {
+            Some(value) => {
+                env.insert(remembered.key.clone(), value);
+            }
+            None => missing_secrets.push(remembered.key.clone()),
+        }
+    }
+    Ok(Some(ServerDefaults {
+        package: found.package.clone(),
+        env,
+        missing_secrets,
+        updated_at: found.updated_at.clone(),
+    }))
+}
+
+/// Every package with remembered env; secret values are not included
+#[command]
+pub async fn list_server_defaults() -> Result<Vec<PackageDefaults>, String> {
+    Ok(read_defaults()?.packages.into_values().collect())
+}
+
+/// Forget what was remembered for `package`, or for every package when unset
+#[command]
+pub async fn clear_server_defaults(package: Option<String>) -> Result<usize, String> {
+    let _guard = LOCK
+        .lock()
+        .map_err(|e| format!("Failed to lock install defaults: {}", e))?;
+    let mut file = read_defaults()?;
+    let keys: Vec<String> = match &package {
+        Some(package) => candidate_keys(package)
+            .into_iter()
+            .filter(|key| file.packages.contains_key(key))
+            .collect(),
+        None => file.packages.keys().cloned().collect(),
+    };
+    for key in &keys {
+        if let Some(removed) = file.packages.remove(key) {
+            for env in removed.env.iter().filter(|env| env.secret) {
+                secrets::remove_secret(&secret_id(key, &env.key))?;
+            }
+        }
+    }
+    write_defaults(&file)?;
+    Ok(keys.len())
+}
@@ -0,0 +1,70 @@
+// Install default tests
+use crate::server_defaults::{merge_env, package_key, rememberable, RememberedEnv};
+use crate::server_package::parse_npm_spec;
+use serde_json::json;
+
+#[test]
+fn test_package_key_ignores_version() {
+    assert_eq!(
+        package_key(&parse_npm_spec("@modelcontextprotocol/server-github@1.2.0")),
+        "npm:@modelcontextprotocol/server-github"
+    );
+}
+
+#[test]
+fn test_rememberable_skips_empty_and_references() {
+    let env = json!({
+        "GITHUB_TOKEN": "ghp_abc",
+        "EMPTY": "  ",
+        "FROM_SHELL": "${HOME}",
+        "PORT": 8080,
+        "LOG_LEVEL": "debug"
+    });
+    assert_eq!(
+        rememberable(env.as_object().unwrap()),
+        vec![
+            ("GITHUB_TOKEN".to_string(), "ghp_abc".to_string()),
+            ("LOG_LEVEL".to_string(), "debug".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_merge_env_keeps_old_keys_and_hides_secrets() {
+    let previous = vec![
+        RememberedEnv {
+            key: "LOG_LEVEL".to_string(),
+            value: Some("info".to_string()),
+            secret: false,
+        },
+        RememberedEnv {
+            key: "REGION".to_string(),
+            value: Some("eu".to_string()),
+            secret: false,
+        },
+    ];
+    let written = vec![
+        ("LOG_LEVEL".to_string(), "debug".to_string()),
+        ("API_KEY".to_string(), "sk-123".to_string()),
+    ];
+    assert_eq!(
+        merge_env(&previous, &written),
+        vec![
+            RememberedEnv {
+                key: "API_KEY".to_string(),
+                value: None,
+                secret: true,
+            },
+            RememberedEnv {
+                key: "LOG_LEVEL".to_string(),
+                value: Some("debug".to_string()),
+                secret: false,
+            },
+            RememberedEnv {
+                key: "REGION".to_string(),
+                value: Some("eu".to_string()),
+                secret: false,
+            },
+        ]
+    );
+}
@@ -0,0 +1,356 @@
+//! Test launches: start a stdio server in the sandbox and run the MCP handshake
+//!
+//! `McpSession` speaks newline-delimited JSON-RPC over the process's stdin/stdout. Server
+//! notifications and requests arriving while a response is awaited are skipped, except those
+//! for the client features the session offers: elicitation (with an `Elicitor`, put to the
+//! user), roots (the directories in `ClientOptions`) and sampling (per the `sampling`
+//! settings). The session owns the sandboxed process, so dropping it kills the server and
+//! its children.
+
+use crate::elicitation::{self, Elicitor};
+use crate::server_sandbox::{self, SandboxedProcess};
+use crate::settings::{self, SamplingMode, SamplingSettings, SandboxSettings};
+use crate::{metrics, redaction, sampling, test_presets};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tauri::{command, AppHandle, Runtime};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::process::{ChildStdin, ChildStdout};
+
+pub(crate) const PROTOCOL_VERSION: &str = "2025-06-18";
+
+/// Released protocol revisions, oldest first; the last one is `PROTOCOL_VERSION`
+pub(crate) const PROTOCOL_REVISIONS: [&str; 3] = ["2024-11-05", "2025-03-26", PROTOCOL_VERSION];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtocolStatus {
+    Current,
+    /// The previous revision, still accepted by current clients
+    Previous,
+    /// Older revisions, which newer clients may refuse
+    Outdated,
+    /// Missing or not a released revision
+    Unknown,
+}
+
+pub(crate) fn protocol_status(version: Option<&str>) -> ProtocolStatus {
+    let Some(index) = version.and_then(|v| PROTOCOL_REVISIONS.iter().position(|r| *r == v)) else {
+        return ProtocolStatus::Unknown;
+    };
+    match PROTOCOL_REVISIONS.len() - 1 - index {
+        0 => ProtocolStatus::Current,
+        1 => ProtocolStatus::Previous,
+        _ => ProtocolStatus::Outdated,
+    }
+}
+
+/// Stderr lines kept for error reports
+const STDERR_LINES: usize = 20;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ServerInfo {
+    pub name: Option<String>,
+    pub version: Option<String>,
+    /// Protocol version the server answered with
+    pub protocol_version: Option<String>,
+    pub capabilities: Value,
+    pub instructions: Option<String>,
+    /// Requests the server sent to the client while connected, e.g. `roots/list`
+    #[serde(default)]
+    pub client_requests: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct LaunchTest {
+    pub ok: bool,
+    pub server: Option<ServerInfo>,
+    pub error: Option<String>,
+    /// Last lines the server wrote to stderr
+    pub stderr: Vec<String>,
+    pub elapsed_ms: u64,
+}
+
+/// Client features a session offers
+#[derive(Default)]
+pub(crate) struct ClientOptions {
+    /// Puts elicitation requests to the user
+    pub elicitor: Option<Elicitor>,
+    /// Directories listed for `roots/list`; roots aren't offered when empty
+    pub roots: Vec<PathBuf>,
+}
+
+pub(crate) struct McpSession {
+    // Declared first so the pipes close before the process tree is killed
+    stdin: ChildStdin,
+    stdout: Lines<BufReader<ChildStdout>>,
+    stderr: Arc<Mutex<VecDeque<String>>>,
+    next_id: u64,
+    pub info: ServerInfo,
+    elicitor: Option<Elicitor>,
+    roots: Vec<Value>,
+    sampling: SamplingSettings,
+    _process: SandboxedProcess,
+}
+
+impl McpSession {
+    async fn send(&mut self, message: &Value) -> Result<(), String> {
+        let mut line = message.to_string();
+        line.push('\n');
+        self.stdin
+            .write_all(line.as_bytes())
+            .await
+            .map_err(|e| format!("Failed to write to server: {}", e))?;
+        self.stdin
+            .flush()
+            .await
+            .map_err(|e| format!("Failed to write to server: {}", e))
+    }
+
+    /// Send a request and wait for its response's `result`
+    pub async fn request(&mut self, method: &str, params: Value) -> Result<Value, String> {
+        self.next_id += 1;
+        let id = self.next_id;
+        self.send(&json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))
+            .await?;
+        loop {
+            let line = self
+                .stdout
+                .next_line()
+                .await
+                .map_err(|e| format!("Failed to read from server: {}", e))?
+                .ok_or_else(|| self.exited_error())?;
+            // Servers sometimes log to stdout; only JSON-RPC messages count
+            let Ok(message) = serde_json::from_str::<Value>(&line) else {
+                continue;
+            };
+            if let Some(method) = message["method"].as_str() {
+                if message.get("id").is_some()
+                    && !self.info.client_requests.iter().any(|m| m == method)
+                {
+                    self.info.client_requests.push(method.to_string());
+                }
+                self.answer(method, &message).await?;
+                continue;
+            }
+            if message["id"] != json!(id) {
+                continue;
+            }
+            if let Some(error) = message.get("error") {
+                return Err(format!(
+                    "{} failed: {}",
+                    method,
+                    error["message"].as_str().unwrap_or("unknown error")
+                ));
+            }
+            return Ok(message["result"].clone());
+        }
+    }
+
+    /// Reply to a server request the session advertised support for; others are left
+    /// unanswered
+    async fn answer(&mut self, method: &str, message: &Value) -> Result<(), String> {
+        let outcome = match (method, &self.elicitor) {
+            ("elicitation/create", Some(elicitor)) => Ok(elicitor.ask(&message["params"]).await),
+            ("roots/list", _) if !self.roots.is_empty() => Ok(json!({ "roots": self.roots })),
+            ("sampling/createMessage", _) if self.sampling.mode != SamplingMode::Off => {
+                sampling::respond(&self.sampling, &message["params"]).await
+            }
+            _ => return Ok(()),
+        };
+        let reply = match outcome {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": message["id"], "result": result }),
+            Err(e) => json!({
+                "jsonrpc": "2.0",
+                "id": message["id"],
+                "error": { "code": -32603, "message": e }
+            }),
+        };
+        self.send(&reply).await
+    }
+
+    pub async fn notify(&mut self, method: &str) -> Result<(), String> {
+        self.send(&json!({ "jsonrpc": "2.0", "method": method }))
+            .await
+    }
+
+    fn exited_error(&self) -> String {
+        match self.stderr_tail().last() {
+            Some(line) => format!("Server exited: {}", line),
+            None => "Server exited before responding".to_string(),
+        }
+    }
+
+    /// Time spent waiting on the user, for `elicitation::timeout_excluding_answers`
+    pub(crate) fn answer_clock(&self) -> Option<Arc<Mutex<elicitation::AnswerClock>>> {
+        self.elicitor.as_ref().map(Elicitor::clock)
+    }
+
+    pub fn stderr_tail(&self) -> Vec<String> {
+        self.stderr
+            .lock()
+            .map(|lines| lines.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+pub(crate) fn server_info(result: &Value) -> ServerInfo {
+    ServerInfo {
+        name: result["serverInfo"]["name"].as_str().map(|s| s.to_string()),
+        version: result["serverInfo"]["version"]
+            .as_str()
+            .map(|s| s.to_string()),
+        protocol_version: result["protocolVersion"].as_str().map(|s| s.to_string()),
+        capabilities: result
+            .get("capabilities")
+            .cloned()
+            .unwrap_or_else(|| json!({})),
+        instructions: result["instructions"].as_str().map(|s| s.to_string()),
+        client_requests: Vec::new(),
+    }
+}
+
+fn collect_stderr(process: &mut SandboxedProcess) -> Arc<Mutex<VecDeque<String>>> {
+    let lines = Arc::new(Mutex::new(VecDeque::new()));
+    if let Some(stderr) = process.child.stderr.take() {
+        let lines = lines.clone();
+        // Servers sometimes echo their credentials; the tail ends up in reports and the UI
+        let rules = redaction::rules();
+        tokio::spawn(async move {
+            let mut reader = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = reader.next_line().await {
+                if let Ok(mut lines) = lines.lock() {
+                    if lines.len() == STDERR_LINES {
+                        lines.pop_front();
+                    }
+                    lines.push_back(rules.redact_text(&line));
+                }
+            }
+        });
+    }
+    lines
+}
+
+/// Start `config` in the sandbox and complete the initialize handshake, offering the client
+/// features in `options`. Failures count as health check failures.
+pub(crate) async fn connect(
+    config: &Value,
+    sandbox: &SandboxSettings,
+    options: ClientOptions,
+) -> Result<McpSession, (String, Vec<String>)> {
+    let session = start_session(config, sandbox, options).await;
+    if session.is_err() {
+        metrics::inc(metrics::HEALTH_FAILURES, &[("kind", "launch")]);
+    }
+    session
+}
+
+async fn start_session(
+    config: &Value,
+    sandbox: &SandboxSettings,
+    options: ClientOptions,
+) -> Result<McpSession, (String, Vec<String>)> {
+    let roots = options
+        .roots
+        .iter()
+        .map(|root| test_presets::root_entry(root))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| (e, Vec::new()))?;
+    let mut process = server_sandbox::spawn(config, sandbox).map_err(|e| (e, Vec::new()))?;
+    let stderr = collect_stderr(&mut process);
+    let (Some(stdin), Some(stdout)) = (process.child.stdin.take(), process.child.stdout.take())
+    else {
+        return Err(("Failed to open server pipes".to_string(), Vec::new()));
+    };
+    let mut session = McpSession {
+        stdin,
+        stdout: BufReader::new(stdout).lines(),
+        stderr,
+        next_id: 0,
+        info: ServerInfo::default(),
+        elicitor: options.elicitor,
+        roots,
+        sampling: settings::load_settings().sampling,
+        _process: process,
+    };
+    let mut capabilities = json!({});
+    if session.elicitor.is_some() {
+        capabilities["elicitation"] = json!({});
+    }
+    if !session.roots.is_empty() {
+        capabilities["roots"] = json!({ "listChanged": false });
+    }
+    if session.sampling.mode != SamplingMode::Off {
+        capabilities["sampling"] = json!({});
+    }
+    let clock = session.answer_clock();
+    let params = json!({
+        "protocolVersion": PROTOCOL_VERSION,
+        "capabilities": capabilities,
+        "clientInfo": { "name": "mcp-linker", "version": env!("CARGO_PKG_VERSION") }
+    });
+    let timeout = sandbox.launch_timeout();
+    let handshake = async {
+        let result = session.request("initialize", params).await?;
+        session.notify("notifications/initialized").await?;
+        Ok::<Value, String>(result)
+    };
+    match elicitation::timeout_excluding_answers(timeout, clock, handshake).await {
+        Some(Ok(result)) => {
+            let client_requests = std::mem::take(&mut session.info.client_requests);
+            session.info = ServerInfo {
+                client_requests,
+                ..server_info(&result)
+            };
+            Ok(session)
+        }
+        Some(Err(e)) => Err((e, session.stderr_tail())),
+        None => Err((
+            format!("No handshake response within {}s", timeout.as_secs()),
+            session.stderr_tail(),
+        )),
+    }
+}
+
+/// Launch a stdio server config in the sandbox, run the handshake and shut it down again.
+/// Elicitation requests during the handshake are put to the user, and `roots` are offered.
+#[command]
+pub async fn test_server_launch<R: Runtime>(
+    app: AppHandle<R>,
+    config: Value,
+    roots: Option<Vec<String>>,
+) -> Result<LaunchTest, String> {
+    let roots = test_presets::checked_roots(&roots.unwrap_or_default())?;
+    let sandbox = settings::load_settings().sandbox;
+    let started = Instant::now();
+    let server = config["command"]
+        .as_str()
+        .or(config["url"].as_str())
+        .unwrap_or("server");
+    let options = ClientOptions {
+        elicitor: Some(Elicitor::new(&app, server)),
+        roots,
+    };
+    let result = connect(&config, &sandbox, options).await;
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+    Ok(match result {
+        Ok(session) => LaunchTest {
+            ok: true,
+            stderr: session.stderr_tail(),
+            server: Some(session.info.clone()),
+            error: None,
+            elapsed_ms,
+        },
+        Err((error, stderr)) => LaunchTest {
+            ok: false,
+            server: None,
+            error: Some(error),
+            stderr,
+            elapsed_ms,
+        },
+    })
+}
@@ -0,0 +1,466 @@
+//! Timeline of every change to one server
+//!
+//! `server_history` merges the audit log (what the app did, without config values) with the
+//! versions of the server found in the config file's backups, the batch undo entries and the
+//! file's git history when it is tracked. Versions carry the server's config as it was and the
+//! fields that changed since the previous one; identical consecutive versions are folded.
+//!
+//! `restore_server_version` writes one historical version of the server back, or removes the
+//! server when it didn't exist then. The rest of the file is left as it is.
+
+use crate::audit_log::{self, AuditEntry};
+use crate::batch_ops;
+use crate::claude_code_commands::{self, GLOBAL_PROJECT_ID};
+use crate::client::ClientConfig;
+use crate::client_target::{self, ClientTarget};
+use crate::config::{get_config_path, CodexConfig};
+use crate::json_manager::utils::get_key_by_client;
+use crate::op_recorder::{self, RecordedOp};
+use crate::ownership_marks::field_hashes;
+use crate::{
+    client_plugins, continue_config, goose_config, json_pointer, server_trust, startup_check,
+    unicode_path,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tauri::command;
+
+/// Git commits of the config file looked at
+const MAX_GIT_COMMITS: usize = 50;
+/// Author of changes made through the app
+const APP_ACTOR: &str = "mcp-linker";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HistorySource {
+    Audit,
+    Backup,
+    Batch,
+    Git,
+    Current,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct HistoryEvent {
+    pub at: String,
+    pub source: HistorySource,
+    /// Git author, or the app for its own changes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub who: Option<String>,
+    /// Audit action, batch label or commit subject
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Id for `restore_server_version`; set on events that carry a version of the server
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// The server in this version; `None` when it didn't exist or the event has no version
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config: Option<Value>,
+    /// JSON pointers of the fields that differ from the previous version
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub changed: Vec<String>,
+}
+
+/// A version of the server found in one of the sources
+#[derive(Debug, Clone)]
+pub(crate) struct Snapshot {
+    pub at: DateTime<Utc>,
+    pub source: HistorySource,
+    pub who: Option<String>,
+    pub action: Option<String>,
+    pub version: String,
+    pub entry: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RestoreResult {
+    pub version: String,
+    /// The server was absent in that version and has been removed
+    pub removed: bool,
+}
+
+/// Fields that differ between two versions of a server, as JSON pointers
+pub(crate) fn changed_fields(before: Option<&Value>, after: Option<&Value>) -> Vec<String> {
+    let before = before.map(field_hashes).unwrap_or_default();
+    let after = after.map(field_hashes).unwrap_or_default();
+    let mut changed: Vec<String> = after
+        .iter()
+        .filter(|(pointer, hash)| before.get(*pointer) != Some(hash))
+        .map(|(pointer, _)| pointer.clone())
+        .chain(before.keys().filter(|p| !after.contains_key(*p)).cloned())
+        .collect();
+    changed.sort();
+    changed
+}
+
+/// The server `name` in the config file `content` of `target`; `None` when it isn't there
+pub(crate) fn entry_in(
+    target: &ClientTarget,
+    content: &str,
+    name: &str,
+) -> Result<Option<Value>, String> {
+    if target.client == "codex" {
+        let config: CodexConfig =
+            toml::from_str(content).map_err(|e| format!("Failed to parse config: {}", e))?;
+        let server = config
+            .mcp_servers
+            .get(name)
+            .or_else(|| config.disabled_mcp_servers.get(name));
+        return server
+            .map(|s| serde_json::to_value(s).map_err(|e| format!("Failed to serialize: {}", e)))
+            .transpose();
+    }
+    if target.client == continue_config::CLIENT {
+        return Ok(continue_config::servers_of(&continue_config::parse(content)?).remove(name));
+    }
+    if target.client == goose_config::CLIENT {
+        let doc = goose_config::parse(content)?;
+        return Ok(goose_config::servers_of(&doc, true)
+            .remove(name)
+            .or_else(|| goose_config::servers_of(&doc, false).remove(name)));
+    }
+    let config: Value = match client_plugins::manifest(&target.client) {
+        Some(manifest) => client_plugins::parse_config(manifest.format, content)?,
+        None => {
+            serde_json::from_str(content).map_err(|e| format!("Failed to parse config: {}", e))?
+        }
+    };
+    let entry = if target.is_claude_code() {
+        let pointer = if target.working_dir() == GLOBAL_PROJECT_ID {
+            json_pointer::join(&["mcpServers", name])
+        } else {
+            let key = unicode_path::resolve_project_key(&config, target.working_dir());
+            json_pointer::join(&["projects", &key, "mcpServers", name])
+        };
+        config.pointer(&pointer).cloned()
+    } else if let Some(manifest) = client_plugins::manifest(&target.client) {
+        config
+            .pointer(&manifest.servers_pointer)
+            .and_then(|servers| servers.get(name))
+            .map(|entry| client_plugins::from_client(&manifest.dialect, entry))
+    } else {
+        let key = get_key_by_client(&target.client);
+        config
+            .pointer(&json_pointer::join(&[key, name]))
+            .or_else(|| config.pointer(&json_pointer::join(&["__disabled", name])))
+            .cloned()
+    };
+    Ok(entry)
+}
+
+/// Order snapshots and audit entries into a timeline, newest first. A snapshot identical to
+/// the one before it is dropped.
+pub(crate) fn build_timeline(
+    mut snapshots: Vec<Snapshot>,
+    audit: Vec<AuditEntry>,
+) -> Vec<HistoryEvent> {
+    snapshots.sort_by_key(|s| s.at);
+    let mut timed: Vec<(DateTime<Utc>, HistoryEvent)> = Vec::new();
+    let mut previous: Option<Option<Value>> = None;
+    for snapshot in snapshots {
+        // Versions before the server first appeared say nothing about it
+        let before_first = previous.is_none() && snapshot.entry.is_none();
+        if before_first || previous.as_ref() == Some(&snapshot.entry) {
+            continue;
+        }
+        let changed = changed_fields(
+            previous.as_ref().and_then(|p| p.as_ref()),
+            snapshot.entry.as_ref(),
+        );
+        timed.push((
+            snapshot.at,
+            HistoryEvent {
+                at: snapshot.at.to_rfc3339(),
+                source: snapshot.source,
+                who: snapshot.who,
+                action: snapshot.action,
+                detail: None,
+                version: Some(snapshot.version),
+                config: snapshot.entry.clone(),
+                changed,
+            },
+        ));
+        previous = Some(snapshot.entry);
+    }
+    for entry in audit {
+        let Ok(at) = DateTime::parse_from_rfc3339(&entry.at) else {
+            continue;
+        };
+        timed.push((
+            at.with_timezone(&Utc),
+            HistoryEvent {
+                at: entry.at,
+                source: HistorySource::Audit,
+                who: Some(APP_ACTOR.to_string()),
+                action: Some(entry.action),
+                detail: entry.detail,
+                version: None,
+                config: None,
+                changed: Vec::new(),
+            },
+        ));
+    }
+    timed.sort_by(|a, b| b.0.cmp(&a.0));
+    timed.into_iter().map(|(_, event)| event).collect()
+}
+
+pub(crate) async fn config_path(target: &ClientTarget) -> Result<PathBuf, String> {
+    if target.is_claude_code() {
+        return claude_code_commands::get_claude_config_path(None).await;
+    }
+    if target.client == "codex" {
+        return get_config_path();
+    }
+    if target.client == continue_config::CLIENT {
+        return continue_config::continue_config_path(target.path.as_deref());
+    }
+    if target.client == goose_config::CLIENT {
+        return goose_config::goose_config_path(target.path.as_deref());
+    }
+    if let Some(manifest) = client_plugins::manifest(&target.client) {
+        return client_plugins::resolve_path(&manifest, target.path.as_deref());
+    }
+    Ok(ClientConfig::new(&target.client, target.path.as_deref())
+        .get_path()
+        .to_path_buf())
+}
+
+fn git(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Commits touching `path` as (hash, author, date, subject), newest first
+fn git_commits(path: &Path) -> Vec<(String, String, String, String)> {
+    let (Some(dir), Some(file)) = (path.parent(), path.file_name()) else {
+        return Vec::new();
+    };
+    let limit = format!("-n{}", MAX_GIT_COMMITS);
+    let file = file.to_string_lossy();
+    let Some(log) = git(
+        dir,
+        &[
+            "log",
+            &limit,
+            "--format=%H%x1f%an%x1f%aI%x1f%s",
+            "--",
+            &file,
+        ],
+    ) else {
+        return Vec::new();
+    };
+    log.lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(4, '\u{1f}').map(|f| f.to_string());
+            Some((
+                fields.next()?,
+                fields.next()?,
+                fields.next()?,
+                fields.next()?,
+            ))
+        })
+        .collect()
+}
+
+fn git_show(path: &Path, hash: &str) -> Option<String> {
+    let dir = path.parent()?;
+    let file = path.file_name()?.to_string_lossy();
+    git(dir, &["show", &format!("{}:./{}", hash, file)])
+}
+
+fn modified_at(path: &Path) -> Option<DateTime<Utc>> {
+    Some(DateTime::<Utc>::from(
+        fs::metadata(path).ok()?.modified().ok()?,
+    ))
+}
+
+/// Every version of the server the sources hold; unreadable ones are skipped
+fn snapshots(target: &ClientTarget, path: &Path, name: &str) -> Vec<Snapshot> {
+    let mut found = Vec::new();
+    let mut push = |at: DateTime<Utc>,
+                    source: HistorySource,
+                    who: Option<String>,
+                    action: Option<String>,
+                    version: String,
+                    content: &str| {
+        if let Ok(entry) = entry_in(target, content, name) {
+            found.push(Snapshot {
+                at,
+                source,
+                who,
+                action,
+                version,
+                entry,
+            });
+        }
+    };
+
+    if let (Ok(content), Some(at)) = (fs::read_to_string(path), modified_at(path)) {
+        push(
+            at,
+            HistorySource::Current,
+            None,
+            None,
+            "current".to_string(),
+            &content,
+        );
+    }
+    for backup in startup_check::backups_of(path) {
+        let (Ok(content), Some(at)) = (fs::read_to_string(&backup), modified_at(&backup)) else {
+            continue;
+        };
+        let file = backup.file_name().unwrap_or_default().to_string_lossy();
+        push(
+            at,
+            HistorySource::Backup,
+            Some(APP_ACTOR.to_string()),
+            None,
+            format!("backup:{}", file),
+            &content,
+        );
+    }
+    for undo in batch_ops::undo_entries().unwrap_or_default() {
+        let Ok(at) = DateTime::parse_from_rfc3339(&undo.created_at) else {
+            continue;
+        };
+        if let Some(file) = undo.files.iter().find(|f| f.path == path) {
+            push(
+                at.with_timezone(&Utc),
+                HistorySource::Batch,
+                Some(APP_ACTOR.to_string()),
+                Some(undo.label.clone()),
+                format!("batch:{}", undo.id),
+                &file.after,
+            );
+        }
+    }
+    for (hash, author, date, subject) in git_commits(path) {
+        let (Ok(at), Some(content)) = (DateTime::parse_from_rfc3339(&date), git_show(path, &hash))
+        else {
+            continue;
+        };
+        push(
+            at.with_timezone(&Utc),
+            HistorySource::Git,
+            Some(author),
+            Some(subject),
+            format!("git:{}", hash),
+            &content,
+        );
+    }
+    found
+}
+
+/// Content of the config file in `version`
+fn version_content(path: &Path, version: &str) -> Result<String, String> {
+    let read = |p: &Path| {
+        fs::read_to_string(p).map_err(|e| format!("Failed to read {}: {}", p.display(), e))
+    };
+    if version == "current" {
+        return read(path);
+    }
+    if let Some(file) = version.strip_prefix("backup:") {
+        let backup = path.with_file_name(file);
+        if file.contains(['/', '\\']) || !unicode_path::is_backup_of(path, &backup) {
+            return Err(format!("'{}' is not a backup of {}", file, path.display()));
+        }
+        return read(&backup);
+    }
+    if let Some(id) = version.strip_prefix("batch:") {
+        return batch_ops::undo_entries()?
+            .into_iter()
+            .find(|e| e.id == id)
+            .and_then(|e| e.files.into_iter().find(|f| f.path == path))
+            .map(|f| f.after)
+            .ok_or_else(|| format!("No batch version '{}'", id));
+    }
+    if let Some(hash) = version.strip_prefix("git:") {
+        if !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("Invalid commit '{}'", hash));
+        }
+        return git_show(path, hash).ok_or_else(|| format!("Commit {} has no such file", hash));
+    }
+    Err(format!("Unknown version '{}'", version))
+}
+
+/// Every change to the server `name`, newest first. `scope` is the Claude Code project
+/// (default global) or the config path of a project-level client.
+#[command]
+pub async fn server_history(
+    name: String,
+    client: String,
+    scope: Option<String>,
+) -> Result<Vec<HistoryEvent>, String> {
+    let target = server_trust::target_for(&client, scope.as_deref());
+    let path = config_path(&target).await?;
+    let audit = audit_log::read_entries()?
+        .into_iter()
+        .filter(|e| e.target.label() == target.label() && e.server.as_deref() == Some(&name))
+        .collect();
+    let found = tokio::task::spawn_blocking({
+        let target = target.clone();
+        move || snapshots(&target, &path, &name)
+    })
+    .await
+    .map_err(|e| format!("Failed to read server history: {}", e))?;
+    Ok(build_timeline(found, audit))
+}
+
+/// Write the server as it was in `version`, leaving the other servers alone. A version in
+/// which the server didn't exist removes it.
+#[command]
+pub async fn restore_server_version(
+    name: String,
+    client: String,
+    scope: Option<String>,
+    version: String,
+) -> Result<RestoreResult, String> {
+    let target = server_trust::target_for(&client, scope.as_deref());
+    let path = config_path(&target).await?;
+    let content = version_content(&path, &version)?;
+    let entry = entry_in(&target, &content, &name)?;
+    let current = client_target::list_servers(&target).await?;
+    let op = match entry {
+        Some(config) => {
+            client_target::upsert_server(&target, &name, config.clone()).await?;
+            RecordedOp::UpsertServer {
+                name: name.clone(),
+                config,
+            }
+        }
+        None if current.contains_key(&name) => {
+            client_target::remove_server(&target, &name).await?;
+            RecordedOp::RemoveServer { name: name.clone() }
+        }
+        None => return Err(format!("'{}' exists neither in that version nor now", name)),
+    };
+    let removed = matches!(op, RecordedOp::RemoveServer { .. });
+    op_recorder::record(target.clone(), op);
+    audit_log::log(
+        "restore_version",
+        &target,
+        Some(&name),
+        Some(version.clone()),
+        Vec::new(),
+    );
+    println!(
+        "[History] Restored {} on {} to {}",
+        name,
+        target.label(),
+        version
+    );
+    Ok(RestoreResult { version, removed })
+}
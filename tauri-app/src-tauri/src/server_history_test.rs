@@ -0,0 +1,88 @@
+// Server history tests
+use crate::audit_log::AuditEntry;
+use crate::client_target::ClientTarget;
+use crate::server_history::{build_timeline, changed_fields, entry_in, HistorySource, Snapshot};
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+
+fn snapshot(at: &str, source: HistorySource, entry: Option<Value>) -> Snapshot {
+    Snapshot {
+        at: DateTime::parse_from_rfc3339(at)
+            .unwrap()
+            .with_timezone(&Utc),
+        source,
+        who: None,
+        action: None,
+        version: format!("{:?}:{}", source, at),
+        entry,
+    }
+}
+
+#[test]
+fn test_changed_fields() {
+    let before = json!({ "command": "npx", "args": ["a"], "env": { "A": "1" } });
+    let after = json!({ "command": "npx", "args": ["b"], "timeout": 30 });
+    assert_eq!(
+        changed_fields(Some(&before), Some(&after)),
+        vec!["/args", "/env/A", "/timeout"]
+    );
+    assert_eq!(changed_fields(None, Some(&after)).len(), 3);
+}
+
+#[test]
+fn test_entry_in_json_and_claude_code() {
+    let cursor = ClientTarget::client("cursor", None);
+    let content = r#"{ "mcpServers": {}, "__disabled": { "fs": { "command": "npx" } } }"#;
+    assert_eq!(
+        entry_in(&cursor, content, "fs").unwrap(),
+        Some(json!({ "command": "npx" }))
+    );
+    assert_eq!(entry_in(&cursor, content, "git").unwrap(), None);
+
+    let project = ClientTarget::claude_code("/work/app");
+    let content = r#"{
+        "mcpServers": { "fs": { "command": "global" } },
+        "projects": { "/work/app": { "mcpServers": { "fs": { "command": "local" } } } }
+    }"#;
+    assert_eq!(
+        entry_in(&project, content, "fs").unwrap(),
+        Some(json!({ "command": "local" }))
+    );
+    assert!(entry_in(&project, "not json", "fs").is_err());
+}
+
+#[test]
+fn test_build_timeline_folds_unchanged_versions() {
+    let v1 = json!({ "command": "npx", "args": ["a"] });
+    let v2 = json!({ "command": "npx", "args": ["b"] });
+    let snapshots = vec![
+        snapshot(
+            "2026-01-04T00:00:00Z",
+            HistorySource::Current,
+            Some(v2.clone()),
+        ),
+        snapshot("2026-01-01T00:00:00Z", HistorySource::Git, None),
+        snapshot("2026-01-02T00:00:00Z", HistorySource::Git, Some(v1)),
+        snapshot("2026-01-03T00:00:00Z", HistorySource::Backup, Some(v2)),
+    ];
+    let audit = vec![AuditEntry {
+        at: "2026-01-02T12:00:00+00:00".to_string(),
+        action: "upsert".to_string(),
+        target: ClientTarget::client("cursor", None),
+        server: Some("fs".to_string()),
+        detail: None,
+        warnings: Vec::new(),
+    }];
+    let timeline = build_timeline(snapshots, audit);
+    let sources: Vec<HistorySource> = timeline.iter().map(|e| e.source).collect();
+    assert_eq!(
+        sources,
+        vec![
+            HistorySource::Backup,
+            HistorySource::Audit,
+            HistorySource::Git
+        ]
+    );
+    assert_eq!(timeline[0].changed, vec!["/args"]);
+    assert!(timeline[1].version.is_none());
+}
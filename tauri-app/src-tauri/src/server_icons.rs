@@ -0,0 +1,411 @@
+//! Icons and descriptions of catalog and configured servers, cached for the UI
+//!
+//! `refresh_server_icons` collects candidate image URLs per server name: the logo and
+//! description of catalog entries the frontend passes in, `icons` and `websiteUrl` of cached
+//! registry entries, and the repository or homepage of configured servers' packages (see
+//! `package_metadata`), as the GitHub owner's avatar or the site's favicon. The first image
+//! that downloads is stored under icons/ in the app data dir and indexed in server_icons.json,
+//! so `get_server_icon` answers with a local path and works offline. Entries are refreshed
+//! after `CACHE_DAYS`; failed ones are retried after a day.
+
+use crate::inventory;
+use crate::network;
+use crate::package_metadata;
+use crate::registry_import;
+use crate::server_package::PackageRef;
+use crate::settings::{self, app_config_dir, RetryPolicy};
+use chrono::{DateTime, Duration, Utc};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::command;
+
+const CACHE_DAYS: i64 = 30;
+const RETRY_FAILED_HOURS: i64 = 24;
+const CONCURRENT_FETCHES: usize = 8;
+/// Larger downloads are not icons
+const MAX_ICON_BYTES: usize = 512 * 1024;
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// A catalog entry as the frontend shows it
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogIcon {
+    pub name: String,
+    #[serde(default)]
+    pub logo_url: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// What is known about one server before fetching
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct IconSource {
+    pub name: String,
+    /// Candidate image URLs, best first
+    pub urls: Vec<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CachedIcon {
+    pub name: String,
+    /// Local file; `None` when no candidate could be fetched
+    pub path: Option<String>,
+    pub source_url: Option<String>,
+    pub description: Option<String>,
+    pub fetched_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct IconsFile {
+    #[serde(default)]
+    icons: BTreeMap<String, CachedIcon>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct IconRefreshReport {
+    pub fetched: Vec<String>,
+    /// Up-to-date entries that weren't fetched again
+    pub cached: usize,
+    pub errors: Vec<String>,
+}
+
+fn icons_dir() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("icons"))
+}
+
+fn index_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("server_icons.json"))
+}
+
+fn read_index() -> Result<IconsFile, String> {
+    let path = index_path()?;
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse server icons: {}", e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(IconsFile::default()),
+        Err(e) => Err(format!("Failed to read server icons: {}", e)),
+    }
+}
+
+fn write_index(file: &IconsFile) -> Result<(), String> {
+    let path = index_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(file)
+        .map_err(|e| format!("Failed to serialize server icons: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write server icons: {}", e))
+}
+
+/// Avatar of the owner of a GitHub repository URL
+pub(crate) fn github_avatar(repository: &str) -> Option<String> {
+    let url = url::Url::parse(repository).ok()?;
+    if url.host_str() != Some("github.com") {
+        return None;
+    }
+    let owner = url.path_segments()?.find(|s| !s.is_empty())?;
+    Some(format!("https://github.com/{}.png?size=128", owner))
+}
+
+pub(crate) fn favicon(site: &str) -> Option<String> {
+    let url = url::Url::parse(site).ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    Some(format!(
+        "{}/favicon.ico",
+        url.origin().ascii_serialization()
+    ))
+}
+
+/// Icon source of a raw registry entry
+pub(crate) fn registry_source(entry: &Value) -> Option<IconSource> {
+    let server = entry.get("server").unwrap_or(entry);
+    let registry_name = server["name"].as_str()?;
+    let name = registry_name.rsplit('/').next().unwrap_or(registry_name);
+    let mut urls: Vec<String> = server["icons"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|icon| icon["src"].as_str().map(|s| s.to_string()))
+        .collect();
+    urls.extend(server["repository"]["url"].as_str().and_then(github_avatar));
+    urls.extend(server["websiteUrl"].as_str().and_then(favicon));
+    Some(IconSource {
+        name: name.to_string(),
+        urls,
+        description: server["description"].as_str().map(|s| s.to_string()),
+    })
+}
+
+/// Combine sources of the same server, keeping the order they were given in
+pub(crate) fn merge_sources(sources: Vec<IconSource>) -> Vec<IconSource> {
+    let mut merged: Vec<IconSource> = Vec::new();
+    for source in sources {
+        match merged.iter_mut().find(|m| m.name == source.name) {
+            Some(existing) => {
+                for url in source.urls {
+                    if !existing.urls.contains(&url) {
+                        existing.urls.push(url);
+                    }
+                }
+                existing.description = existing.description.take().or(source.description);
+            }
+            None => merged.push(source),
+        }
+    }
+    merged
+}
+
+/// File extension of an image content type; `None` for anything else
+pub(crate) fn image_extension(content_type: &str) -> Option<&'static str> {
+    let mime = content_type.split(';').next()?.trim().to_ascii_lowercase();
+    Some(match mime.as_str() {
+        "image/png" => "png",
+        "image/jpeg" => "jpg",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "image/svg+xml" => "svg",
+        "image/x-icon" | "image/vnd.microsoft.icon" => "ico",
+        _ => return None,
+    })
+}
+
+/// Cache file name for a server name, safe on every platform
+pub(crate) fn file_stem(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Whether a cached entry should be fetched again at `now`
+pub(crate) fn is_due(entry: &CachedIcon, now: DateTime<Utc>) -> bool {
+    let Ok(at) = DateTime::parse_from_rfc3339(&entry.fetched_at) else {
+        return true;
+    };
+    let age = now - at.with_timezone(&Utc);
+    match entry.path {
+        Some(_) => age > Duration::days(CACHE_DAYS),
+        None => age > Duration::hours(RETRY_FAILED_HOURS),
+    }
+}
+
+async fn download(
+    client: &reqwest::Client,
+    retry: &RetryPolicy,
+    url: &str,
+) -> Result<(Vec<u8>, &'static str), String> {
+    let response = network::send_with_retry(retry, || client.get(url))
+        .await
+        .map_err(|e| format!("Failed to fetch {}: {}", url, e))?;
+    if !response.status().is_success() {
+        return Err(format!("{} returned {}", url, response.status()));
+    }
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let extension = image_extension(&content_type)
+        .ok_or_else(|| format!("{} is not an image ({})", url, content_type))?;
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to fetch {}: {}", url, e))?;
+    if bytes.is_empty() || bytes.len() > MAX_ICON_BYTES {
+        return Err(format!(
+            "{} has an unusable size ({} bytes)",
+            url,
+            bytes.len()
+        ));
+    }
+    Ok((bytes.to_vec(), extension))
+}
+
+/// Fetch the first candidate that downloads and store it; without candidates only the
+/// description is kept
+async fn fetch_icon(
+    client: &reqwest::Client,
+    retry: &RetryPolicy,
+    source: &IconSource,
+) -> CachedIcon {
+    let mut entry = CachedIcon {
+        name: source.name.clone(),
+        path: None,
+        source_url: None,
+        description: source.description.clone(),
+        fetched_at: Utc::now().to_rfc3339(),
+        error: None,
+    };
+    let mut errors = Vec::new();
+    for url in &source.urls {
+        let stored = download(client, retry, url).await.and_then(|(bytes, ext)| {
+            let dir = icons_dir()?;
+            fs::create_dir_all(&dir)
+                .map_err(|e| format!("Failed to create icons directory: {}", e))?;
+            let path = dir.join(format!("{}.{}", file_stem(&source.name), ext));
+            fs::write(&path, bytes).map_err(|e| format!("Failed to write icon: {}", e))?;
+            Ok(path)
+        });
+        match stored {
+            Ok(path) => {
+                entry.path = Some(path.display().to_string());
+                entry.source_url = Some(url.clone());
+                return entry;
+            }
+            Err(e) => errors.push(e),
+        }
+    }
+    if !errors.is_empty() {
+        entry.error = Some(errors.join("; "));
+    }
+    entry
+}
+
+/// Sources of configured servers, from their packages' repository and homepage
+async fn configured_sources() -> Vec<IconSource> {
+    let inventory = inventory::collect_inventory().await;
+    let packages: Vec<PackageRef> = inventory
+        .servers
+        .iter()
+        .filter_map(|entry| entry.package.clone())
+        .collect();
+    let metadata = package_metadata::resolve(&packages).await;
+    inventory
+        .servers
+        .iter()
+        .map(|entry| {
+            let found = entry.package.as_ref().and_then(|p| metadata.get(p));
+            let repository = found.and_then(|m| m.repository.as_deref());
+            let homepage = found.and_then(|m| m.homepage.as_deref());
+            IconSource {
+                name: entry.name.clone(),
+                urls: repository
+                    .and_then(github_avatar)
+                    .into_iter()
+                    .chain(homepage.and_then(favicon))
+                    .chain(entry.url.as_deref().and_then(favicon))
+                    .collect(),
+                description: found.and_then(|m| m.description.clone()),
+            }
+        })
+        .collect()
+}
+
+/// Local path of a server's cached icon; never fetches
+#[command]
+pub async fn get_server_icon(name: String) -> Result<Option<String>, String> {
+    let index = read_index()?;
+    Ok(index
+        .icons
+        .get(&name)
+        .and_then(|icon| icon.path.clone())
+        .filter(|path| PathBuf::from(path).is_file()))
+}
+
+/// Every cached entry, with descriptions for offline display
+#[command]
+pub async fn list_server_icons() -> Result<Vec<CachedIcon>, String> {
+    Ok(read_index()?.icons.into_values().collect())
+}
+
+/// Fetch icons of `catalog` entries and of configured and registry servers that have none
+/// cached or are due. `force` fetches every one again.
+#[command]
+pub async fn refresh_server_icons(
+    catalog: Option<Vec<CatalogIcon>>,
+    force: Option<bool>,
+) -> Result<IconRefreshReport, String> {
+    let mut sources: Vec<IconSource> = catalog
+        .unwrap_or_default()
+        .into_iter()
+        .map(|entry| IconSource {
+            name: entry.name,
+            urls: entry.logo_url.into_iter().collect(),
+            description: entry.description,
+        })
+        .collect();
+    sources.extend(
+        registry_import::cached_entries()
+            .iter()
+            .filter_map(registry_source),
+    );
+    sources.extend(configured_sources().await);
+    let sources: Vec<IconSource> = merge_sources(sources)
+        .into_iter()
+        .filter(|source| !source.urls.is_empty() || source.description.is_some())
+        .collect();
+
+    let now = Utc::now();
+    let index = read_index()?;
+    let force = force.unwrap_or(false);
+    let (due, fresh): (Vec<IconSource>, Vec<IconSource>) =
+        sources.into_iter().partition(|source| {
+            force || !matches!(index.icons.get(&source.name), Some(e) if !is_due(e, now))
+        });
+
+    let network = settings::load_settings().network;
+    let client = network::http_client(&network)?;
+    let fetched: Vec<CachedIcon> = futures::stream::iter(due)
+        .map(|source| {
+            let client = client.clone();
+            let retry = &network.retry;
+            async move { fetch_icon(&client, retry, &source).await }
+        })
+        .buffer_unordered(CONCURRENT_FETCHES)
+        .collect()
+        .await;
+
+    let mut report = IconRefreshReport {
+        cached: fresh.len(),
+        ..Default::default()
+    };
+    let _guard = LOCK
+        .lock()
+        .map_err(|e| format!("Failed to lock server icons: {}", e))?;
+    let mut index = read_index()?;
+    for entry in fetched {
+        if entry.path.is_some() {
+            report.fetched.push(entry.name.clone());
+        } else if let Some(e) = &entry.error {
+            report.errors.push(format!("{}: {}", entry.name, e));
+        }
+        // A failed refresh keeps the icon fetched before
+        let entry = match (entry.path.is_none(), index.icons.remove(&entry.name)) {
+            (true, Some(old)) if old.path.is_some() => CachedIcon {
+                fetched_at: entry.fetched_at,
+                error: entry.error,
+                description: entry.description.or(old.description),
+                ..old
+            },
+            _ => entry,
+        };
+        index.icons.insert(entry.name.clone(), entry);
+    }
+    write_index(&index)?;
+    report.fetched.sort();
+    println!(
+        "[Icons] Fetched {}, {} cached, {} failed",
+        report.fetched.len(),
+        report.cached,
+        report.errors.len()
+    );
+    Ok(report)
+}
@@ -0,0 +1,94 @@
+// Server icon cache tests
+use crate::server_icons::{
+    favicon, file_stem, github_avatar, image_extension, is_due, merge_sources, registry_source,
+    CachedIcon, IconSource,
+};
+use chrono::{Duration, Utc};
+use serde_json::json;
+
+#[test]
+fn test_icon_urls() {
+    assert_eq!(
+        github_avatar("https://github.com/modelcontextprotocol/servers").as_deref(),
+        Some("https://github.com/modelcontextprotocol.png?size=128")
+    );
+    assert_eq!(github_avatar("https://gitlab.com/acme/tool"), None);
+    assert_eq!(
+        favicon("https://docs.example.com/guide?x=1").as_deref(),
+        Some("https://docs.example.com/favicon.ico")
+    );
+    assert_eq!(favicon("file:///tmp"), None);
+}
+
+#[test]
+fn test_registry_source() {
+    let entry = json!({
+        "server": {
+            "name": "io.github.acme/weather",
+            "description": "Forecasts",
+            "icons": [{ "src": "https://acme.dev/icon.png", "mimeType": "image/png" }],
+            "repository": { "url": "https://github.com/acme/weather", "source": "github" },
+            "websiteUrl": "https://acme.dev"
+        }
+    });
+    assert_eq!(
+        registry_source(&entry),
+        Some(IconSource {
+            name: "weather".to_string(),
+            urls: vec![
+                "https://acme.dev/icon.png".to_string(),
+                "https://github.com/acme.png?size=128".to_string(),
+                "https://acme.dev/favicon.ico".to_string(),
+            ],
+            description: Some("Forecasts".to_string()),
+        })
+    );
+}
+
+#[test]
+fn test_merge_sources_keeps_first_description() {
+    let source = |urls: &[&str], description: Option<&str>| IconSource {
+        name: "fs".to_string(),
+        urls: urls.iter().map(|u| u.to_string()).collect(),
+        description: description.map(|d| d.to_string()),
+    };
+    let merged = merge_sources(vec![
+        source(&["https://a/logo.png"], None),
+        source(
+            &["https://a/logo.png", "https://b/favicon.ico"],
+            Some("Files"),
+        ),
+    ]);
+    assert_eq!(
+        merged,
+        vec![source(
+            &["https://a/logo.png", "https://b/favicon.ico"],
+            Some("Files")
+        )]
+    );
+}
+
+#[test]
+fn test_image_extension_and_file_stem() {
+    assert_eq!(image_extension("image/svg+xml; charset=utf-8"), Some("svg"));
+    assert_eq!(image_extension("image/vnd.microsoft.icon"), Some("ico"));
+    assert_eq!(image_extension("text/html"), None);
+    assert_eq!(file_stem("@acme/weather server"), "_acme_weather_server");
+}
+
+#[test]
+fn test_is_due() {
+    let now = Utc::now();
+    let entry = |days: i64, path: Option<&str>| CachedIcon {
+        name: "fs".to_string(),
+        path: path.map(|p| p.to_string()),
+        source_url: None,
+        description: None,
+        fetched_at: (now - Duration::days(days)).to_rfc3339(),
+        error: None,
+    };
+    assert!(!is_due(&entry(10, Some("/icons/fs.png")), now));
+    assert!(is_due(&entry(31, Some("/icons/fs.png")), now));
+    assert!(is_due(&entry(2, None), now));
+    assert!(!is_due(&entry(0, None), now));
+}
@@ -0,0 +1,287 @@
+//! Prompt, resource and tool-call inspection of configured servers
+//!
+//! Each command test-launches the server in the sandbox, lists what it offers and shuts it
+//! down again. Servers that don't advertise the capability get an empty list rather than an
+//! error. A sample resource can be read to check that reads actually work; its text is cut
+//! to `SAMPLE_TEXT_LIMIT` and binary contents are reported by size only. A tool can be
+//! called to check it end to end. Servers get the roots saved in their test preset (see
+//! `test_presets`), elicitation requests are put to the user (see `elicitation`) and sampling
+//! requests answered per settings (see `sampling`).
+
+use crate::client_target::ClientTarget;
+use crate::elicitation::{self, Elicitor};
+use crate::server_handshake::{self, ClientOptions, McpSession};
+use crate::server_metadata::{self, list_all};
+use crate::{settings, test_presets};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::future::Future;
+use tauri::{command, AppHandle, Runtime};
+
+/// Characters of a sample resource's text returned to the UI
+const SAMPLE_TEXT_LIMIT: usize = 16 * 1024;
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct PromptArgument {
+    pub name: String,
+    pub description: Option<String>,
+    pub required: bool,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct PromptInfo {
+    pub name: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub arguments: Vec<PromptArgument>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ResourceInfo {
+    pub uri: String,
+    pub name: Option<String>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub mime_type: Option<String>,
+    pub size: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ResourceTemplateInfo {
+    pub uri_template: String,
+    pub name: Option<String>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub mime_type: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ResourceContent {
+    pub uri: String,
+    pub mime_type: Option<String>,
+    pub text: Option<String>,
+    /// Whether `text` was cut to `SAMPLE_TEXT_LIMIT`
+    pub truncated: bool,
+    /// Decoded size of binary contents
+    pub blob_bytes: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ResourceSample {
+    pub uri: String,
+    pub contents: Vec<ResourceContent>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ResourceInspection {
+    pub resources: Vec<ResourceInfo>,
+    pub templates: Vec<ResourceTemplateInfo>,
+    pub sample: Option<ResourceSample>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ToolCallResult {
+    pub is_error: bool,
+    /// Content blocks as the server returned them
+    pub content: Vec<Value>,
+    pub structured_content: Option<Value>,
+    /// Requests the server sent to the client, e.g. `sampling/createMessage`
+    pub client_requests: Vec<String>,
+}
+
+fn string(value: &Value, key: &str) -> Option<String> {
+    value[key].as_str().map(|s| s.to_string())
+}
+
+pub(crate) fn parse_prompt(prompt: &Value) -> Option<PromptInfo> {
+    Some(PromptInfo {
+        name: string(prompt, "name")?,
+        title: string(prompt, "title"),
+        description: string(prompt, "description"),
+        arguments: prompt["arguments"]
+            .as_array()
+            .map(|args| {
+                args.iter()
+                    .filter_map(|arg| {
+                        Some(PromptArgument {
+                            name: string(arg, "name")?,
+                            description: string(arg, "description"),
+                            required: arg["required"].as_bool().unwrap_or(false),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+    })
+}
+
+pub(crate) fn parse_resource(resource: &Value) -> Option<ResourceInfo> {
+    Some(ResourceInfo {
+        uri: string(resource, "uri")?,
+        name: string(resource, "name"),
+        title: string(resource, "title"),
+        description: string(resource, "description"),
+        mime_type: string(resource, "mimeType"),
+        size: resource["size"].as_u64(),
+    })
+}
+
+pub(crate) fn parse_template(template: &Value) -> Option<ResourceTemplateInfo> {
+    Some(ResourceTemplateInfo {
+        uri_template: string(template, "uriTemplate")?,
+        name: string(template, "name"),
+        title: string(template, "title"),
+        description: string(template, "description"),
+        mime_type: string(template, "mimeType"),
+    })
+}
+
+pub(crate) fn parse_content(content: &Value) -> Option<ResourceContent> {
+    let text = content["text"].as_str();
+    let truncated = text.is_some_and(|t| t.chars().count() > SAMPLE_TEXT_LIMIT);
+    Some(ResourceContent {
+        uri: string(content, "uri")?,
+        mime_type: string(content, "mimeType"),
+        text: text.map(|t| t.chars().take(SAMPLE_TEXT_LIMIT).collect()),
+        truncated,
+        blob_bytes: content["blob"].as_str().map(|blob| {
+            BASE64
+                .decode(blob)
+                .map(|bytes| bytes.len())
+                .unwrap_or(blob.len() * 3 / 4)
+        }),
+    })
+}
+
+/// Launch a configured server with its test roots and run `inspect` against it within the
+/// launch timeout
+async fn with_session<R, T, F, Fut>(
+    app: &AppHandle<R>,
+    target: &ClientTarget,
+    name: &str,
+    inspect: F,
+) -> Result<T, String>
+where
+    R: Runtime,
+    F: FnOnce(McpSession) -> Fut,
+    Fut: Future<Output = Result<T, String>>,
+{
+    let config = server_metadata::configured_server(target, name).await?;
+    let sandbox = settings::load_settings().sandbox;
+    let options = ClientOptions {
+        elicitor: Some(Elicitor::new(app, name)),
+        roots: test_presets::preset_roots(target, name),
+    };
+    let session = server_handshake::connect(&config, &sandbox, options)
+        .await
+        .map_err(|(e, _)| e)?;
+    let timeout = sandbox.launch_timeout();
+    let clock = session.answer_clock();
+    elicitation::timeout_excluding_answers(timeout, clock, inspect(session))
+        .await
+        .ok_or_else(|| format!("No response from '{}' within {}s", name, timeout.as_secs()))?
+}
+
+async fn read_sample(session: &mut McpSession, uri: String) -> ResourceSample {
+    match session
+        .request("resources/read", json!({ "uri": uri }))
+        .await
+    {
+        Ok(result) => ResourceSample {
+            contents: result["contents"]
+                .as_array()
+                .map(|c| c.iter().filter_map(parse_content).collect())
+                .unwrap_or_default(),
+            error: None,
+            uri,
+        },
+        Err(error) => ResourceSample {
+            uri,
+            contents: Vec::new(),
+            error: Some(error),
+        },
+    }
+}
+
+/// Prompts a configured server offers, with their arguments
+#[command]
+pub async fn inspect_server_prompts<R: Runtime>(
+    app: AppHandle<R>,
+    target: ClientTarget,
+    name: String,
+) -> Result<Vec<PromptInfo>, String> {
+    with_session(&app, &target, &name, |mut session| async move {
+        if session.info.capabilities.get("prompts").is_none() {
+            return Ok(Vec::new());
+        }
+        let prompts = list_all(&mut session, "prompts/list", "prompts").await?;
+        Ok(prompts.iter().filter_map(parse_prompt).collect())
+    })
+    .await
+}
+
+/// Resources and resource templates of a configured server. With `fetch_sample`, also reads
+/// `sample_uri`, or the first listed resource when none is given.
+#[command]
+pub async fn inspect_server_resources<R: Runtime>(
+    app: AppHandle<R>,
+    target: ClientTarget,
+    name: String,
+    fetch_sample: Option<bool>,
+    sample_uri: Option<String>,
+) -> Result<ResourceInspection, String> {
+    with_session(&app, &target, &name, |mut session| async move {
+        if session.info.capabilities.get("resources").is_none() {
+            return Ok(ResourceInspection::default());
+        }
+        let resources: Vec<ResourceInfo> = list_all(&mut session, "resources/list", "resources")
+            .await?
+            .iter()
+            .filter_map(parse_resource)
+            .collect();
+        // Templates are optional; older servers answer with "method not found"
+        let templates = list_all(
+            &mut session,
+            "resources/templates/list",
+            "resourceTemplates",
+        )
+        .await
+        .map(|t| t.iter().filter_map(parse_template).collect())
+        .unwrap_or_default();
+        let sample_uri = sample_uri.or_else(|| resources.first().map(|r| r.uri.clone()));
+        let sample = match (fetch_sample.unwrap_or(false), sample_uri) {
+            (true, Some(uri)) => Some(read_sample(&mut session, uri).await),
+            _ => None,
+        };
+        Ok(ResourceInspection {
+            resources,
+            templates,
+            sample,
+        })
+    })
+    .await
+}
+
+/// Call `tool` on a configured server with `arguments`
+#[command]
+pub async fn call_server_tool<R: Runtime>(
+    app: AppHandle<R>,
+    target: ClientTarget,
+    name: String,
+    tool: String,
+    arguments: Option<Value>,
+) -> Result<ToolCallResult, String> {
+    with_session(&app, &target, &name, |mut session| async move {
+        let params = json!({ "name": tool, "arguments": arguments.unwrap_or_else(|| json!({})) });
+        let result = session.request("tools/call", params).await?;
+        Ok(ToolCallResult {
+            is_error: result["isError"].as_bool().unwrap_or(false),
+            content: result["content"].as_array().cloned().unwrap_or_default(),
+            structured_content: result.get("structuredContent").cloned(),
+            client_requests: session.info.client_requests.clone(),
+        })
+    })
+    .await
+}
@@ -0,0 +1,65 @@
+// Prompt and resource inspection tests
+use crate::server_inspect::{parse_content, parse_prompt, parse_resource, parse_template};
+use serde_json::json;
+
+#[test]
+fn test_parse_prompt() {
+    let prompt = parse_prompt(&json!({
+        "name": "review",
+        "description": "Review a change",
+        "arguments": [
+            { "name": "diff", "required": true },
+            { "name": "style", "description": "Review style" },
+            { "description": "no name" }
+        ]
+    }))
+    .unwrap();
+    assert_eq!(prompt.name, "review");
+    assert_eq!(prompt.arguments.len(), 2);
+    assert!(prompt.arguments[0].required);
+    assert!(!prompt.arguments[1].required);
+    assert_eq!(
+        prompt.arguments[1].description.as_deref(),
+        Some("Review style")
+    );
+    assert!(parse_prompt(&json!({ "description": "no name" })).is_none());
+}
+
+#[test]
+fn test_parse_resources_and_templates() {
+    let resource = parse_resource(&json!({
+        "uri": "file:///notes.md",
+        "name": "notes.md",
+        "mimeType": "text/markdown",
+        "size": 120
+    }))
+    .unwrap();
+    assert_eq!(resource.mime_type.as_deref(), Some("text/markdown"));
+    assert_eq!(resource.size, Some(120));
+
+    let template = parse_template(&json!({
+        "uriTemplate": "repo://{owner}/{name}",
+        "name": "repository"
+    }))
+    .unwrap();
+    assert_eq!(template.uri_template, "repo://{owner}/{name}");
+    assert!(parse_template(&json!({ "uri": "repo://a/b" })).is_none());
+}
+
+#[test]
+fn test_parse_content() {
+    let long = "x".repeat(20 * 1024);
+    let text = parse_content(&json!({ "uri": "file:///big.txt", "text": long })).unwrap();
+    assert!(text.truncated);
+    assert_eq!(text.text.unwrap().len(), 16 * 1024);
+
+    let blob = parse_content(&json!({
+        "uri": "file:///logo.png",
+        "mimeType": "image/png",
+        "blob": "aGVsbG8="
+    }))
+    .unwrap();
+    assert_eq!(blob.blob_bytes, Some(5));
+    assert_eq!(blob.text, None);
+    assert!(!blob.truncated);
+}
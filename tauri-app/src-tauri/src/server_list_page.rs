@@ -0,0 +1,124 @@
+//! Filtered, sorted pages of one Claude Code scope
+//!
+//! `claude_mcp_list` returns every server of a scope, which for scopes with hundreds of servers
+//! means large IPC payloads and slow renders. `claude_mcp_list_page` filters the same list on
+//! the backend (name substring, transport, tag, credential health), sorts it and returns one
+//! page. Sorting is stable and ties are broken by name, so pages don't shift between requests
+//! while the config stays the same.
+
+use crate::claude_code_commands::{self, ClaudeCodeServer};
+use crate::inventory::credential_health_label;
+use crate::server_aliases::{self, ServerAliases};
+use crate::server_query::{matches_any, matches_tags, paginate};
+use crate::server_tags::{self, ServerTags};
+use serde::{Deserialize, Serialize};
+use tauri::command;
+
+const CLIENT: &str = "claude_code";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ServerSort {
+    /// As listed in ~/.claude.json
+    #[default]
+    Config,
+    Name,
+    Type,
+    /// Credential expiry, see `inventory::credential_health_label`
+    CredentialHealth,
+}
+
+/// Lists are "any of"; empty lists match everything
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ServerListFilter {
+    /// Case-insensitive substring of the name
+    pub name: Option<String>,
+    /// stdio, http or sse
+    pub types: Vec<String>,
+    pub tags: Vec<String>,
+    /// Credential expiry: "ok", "credentials_expiring" or "credentials_expired"
+    pub credential_health: Vec<String>,
+    pub sort: ServerSort,
+    pub descending: bool,
+    pub offset: usize,
+    /// `DEFAULT_LIMIT` when unset, at most `MAX_LIMIT`
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ServerListPage {
+    /// Servers matching the filter, on all pages
+    pub total: usize,
+    pub offset: usize,
+    pub servers: Vec<ClaudeCodeServer>,
+    /// Offset of the next page, `None` on the last one
+    pub next_offset: Option<usize>,
+}
+
+fn matches(
+    filter: &ServerListFilter,
+    server: &ClaudeCodeServer,
+    tags: &ServerTags,
+    aliases: &ServerAliases,
+) -> bool {
+    let name = filter.name.as_deref().map(str::trim).unwrap_or_default();
+    (name.is_empty() || server.name.to_lowercase().contains(&name.to_lowercase()))
+        && matches_any(&filter.types, &server.r#type)
+        && matches_any(
+            &filter.credential_health,
+            &credential_health_label(server.credential_status.as_ref()),
+        )
+        && matches_tags(&filter.tags, |tag| {
+            tags.tags_of(aliases, CLIENT, &server.name).contains(tag)
+        })
+}
+
+/// The page of `servers` that `filter` asks for
+pub(crate) fn page(
+    servers: Vec<ClaudeCodeServer>,
+    filter: &ServerListFilter,
+    tags: &ServerTags,
+    aliases: &ServerAliases,
+) -> ServerListPage {
+    let mut matching: Vec<ClaudeCodeServer> = servers
+        .into_iter()
+        .filter(|server| matches(filter, server, tags, aliases))
+        .collect();
+    let key = |server: &ClaudeCodeServer| match filter.sort {
+        ServerSort::Config => String::new(),
+        ServerSort::Name => server.name.to_lowercase(),
+        ServerSort::Type => server.r#type.clone(),
+        ServerSort::CredentialHealth => credential_health_label(server.credential_status.as_ref()),
+    };
+    if filter.sort != ServerSort::Config {
+        matching.sort_by(|a, b| key(a).cmp(&key(b)).then_with(|| a.name.cmp(&b.name)));
+    }
+    if filter.descending {
+        matching.reverse();
+    }
+
+    let (total, servers, next_offset) = paginate(matching, filter.offset, filter.limit);
+    ServerListPage {
+        total,
+        offset: filter.offset,
+        next_offset,
+        servers,
+    }
+}
+
+/// One page of the servers of a Claude Code scope ("Global" or a project), filtered and sorted
+/// on the backend
+#[command]
+pub async fn claude_mcp_list_page(
+    working_dir: String,
+    filter: Option<ServerListFilter>,
+) -> Result<ServerListPage, String> {
+    let servers = claude_code_commands::claude_mcp_list(working_dir).await?;
+    Ok(page(
+        servers,
+        &filter.unwrap_or_default(),
+        &server_tags::load(),
+        &server_aliases::load(),
+    ))
+}
@@ -0,0 +1,90 @@
+// Server list page tests
+use crate::claude_code_commands::ClaudeCodeServer;
+use crate::credential_expiry::CredentialStatus;
+use crate::server_aliases::ServerAliases;
+use crate::server_list_page::{page, ServerListFilter, ServerSort};
+use crate::server_tags::ServerTags;
+
+fn server(name: &str, r#type: &str) -> ClaudeCodeServer {
+    ClaudeCodeServer {
+        name: name.to_string(),
+        r#type: r#type.to_string(),
+        url: None,
+        command: None,
+        args: None,
+        env: None,
+        headers: None,
+        credential_status: None,
+    }
+}
+
+fn names(servers: &[ClaudeCodeServer]) -> Vec<&str> {
+    servers.iter().map(|s| s.name.as_str()).collect()
+}
+
+#[test]
+fn test_page_sorts_stably_and_paginates() {
+    let servers = vec![
+        server("zeta", "http"),
+        server("Alpha", "stdio"),
+        server("beta", "http"),
+        server("gamma", "stdio"),
+    ];
+    let tags = ServerTags::default();
+    let aliases = ServerAliases::default();
+
+    let by_type = ServerListFilter {
+        sort: ServerSort::Type,
+        limit: Some(3),
+        ..Default::default()
+    };
+    let first = page(servers.clone(), &by_type, &tags, &aliases);
+    assert_eq!(names(&first.servers), ["beta", "zeta", "Alpha"]);
+    assert_eq!((first.total, first.next_offset), (4, Some(3)));
+    let second = page(
+        servers.clone(),
+        &ServerListFilter {
+            offset: 3,
+            ..by_type
+        },
+        &tags,
+        &aliases,
+    );
+    assert_eq!(names(&second.servers), ["gamma"]);
+    assert_eq!(second.next_offset, None);
+
+    let unsorted = page(servers, &ServerListFilter::default(), &tags, &aliases);
+    assert_eq!(names(&unsorted.servers), ["zeta", "Alpha", "beta", "gamma"]);
+}
+
+#[test]
+fn test_page_filters_by_name_tag_and_credential_health() {
+    let mut expired = server("github-work", "http");
+    expired.credential_status = Some(CredentialStatus::CredentialsExpired);
+    let servers = vec![expired, server("GitHub", "http"), server("fetch", "stdio")];
+    let mut tags = ServerTags::default();
+    tags.set("fetch", &["web".to_string()]).unwrap();
+    let aliases = ServerAliases::default();
+
+    let filter = |filter: ServerListFilter| {
+        names(&page(servers.clone(), &filter, &tags, &aliases).servers)
+            .into_iter()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        filter(ServerListFilter {
+            name: Some("github".to_string()),
+            credential_health: vec!["ok".to_string()],
+            ..Default::default()
+        }),
+        ["GitHub"]
+    );
+    assert_eq!(
+        filter(ServerListFilter {
+            tags: vec!["Web".to_string()],
+            ..Default::default()
+        }),
+        ["fetch"]
+    );
+}
@@ -0,0 +1,355 @@
+//! Locked servers: no edits or removal until explicitly unlocked
+//!
+//! `lock_server` fingerprints a server entry field by field (as `ownership_marks` does) and
+//! records it in server_locks.json in the app data dir. While the lock holds, every write path
+//! that would change, disable, enable or remove the server (client adapters, Claude Code
+//! scopes, replays and bulk operations) fails with an error naming the lock, so a misclick
+//! can't delete it. The config files of locked servers are watched: the app can't write a
+//! locked server, so any difference from the fingerprint was made outside it and is published
+//! as a `locked_server_modified` event, once per state. Locking again takes the current entry
+//! as the new fingerprint.
+
+use crate::audit_log;
+use crate::claude_code_commands::GLOBAL_PROJECT_ID;
+use crate::client_target::{self, ClientTarget};
+use crate::event_bus::{self, AppEventKind, EventScope};
+use crate::ownership_marks::{self, ForeignModification, OwnershipMark};
+use crate::server_metadata::config_fingerprint;
+use crate::settings::{self, app_config_dir};
+use crate::watch_debounce::Debouncer;
+use crate::{quarantine, server_history};
+use chrono::Utc;
+use notify::{recommended_watcher, Event, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use tauri::{command, AppHandle};
+
+/// Serializes read-modify-write cycles of server_locks.json
+static FILE_LOCK: Mutex<()> = Mutex::new(());
+
+static WATCHER: Mutex<Option<RecommendedWatcher>> = Mutex::new(None);
+
+/// Set by `spawn_lock_watch`; changes aren't watched before it runs
+static APP: OnceLock<AppHandle> = OnceLock::new();
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ServerLock {
+    pub target: ClientTarget,
+    pub name: String,
+    #[serde(default)]
+    pub reason: Option<String>,
+    pub locked_at: String,
+    /// SHA-256 of each field when locked, by JSON pointer inside the entry
+    pub fields: BTreeMap<String, String>,
+    /// Fingerprint of the state last reported as modified, so it is reported once
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reported: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct LockedServer {
+    #[serde(flatten)]
+    pub lock: ServerLock,
+    /// Changes made outside the app since the server was locked
+    pub modification: Option<ForeignModification>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct LocksFile {
+    #[serde(default)]
+    locks: Vec<ServerLock>,
+}
+
+fn locks_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("server_locks.json"))
+}
+
+fn read_locks() -> Result<LocksFile, String> {
+    let path = locks_path()?;
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse server locks: {}", e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(LocksFile::default()),
+        Err(e) => Err(format!("Failed to read server locks: {}", e)),
+    }
+}
+
+fn write_locks(file: &LocksFile) -> Result<(), String> {
+    let path = locks_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(file)
+        .map_err(|e| format!("Failed to serialize server locks: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write server locks: {}", e))
+}
+
+fn update_locks(update: impl FnOnce(&mut LocksFile)) -> Result<(), String> {
+    let _guard = FILE_LOCK
+        .lock()
+        .map_err(|e| format!("Failed to lock server locks: {}", e))?;
+    let mut file = read_locks()?;
+    update(&mut file);
+    write_locks(&file)
+}
+
+/// The error for a write to a locked server
+pub(crate) fn locked_error(lock: &ServerLock) -> String {
+    let reason = lock
+        .reason
+        .as_deref()
+        .map(|r| format!(" ({})", r))
+        .unwrap_or_default();
+    format!(
+        "Server '{}' is locked in {}{}; unlock it before editing or removing it",
+        lock.name,
+        lock.target.label(),
+        reason
+    )
+}
+
+/// Fails if `name` is locked in `target`; called before every write that changes, disables,
+/// enables or removes a server
+pub(crate) fn ensure_unlocked(target: &ClientTarget, name: &str) -> Result<(), String> {
+    match read_locks()?
+        .locks
+        .iter()
+        .find(|l| l.target == *target && l.name == name)
+    {
+        Some(lock) => Err(locked_error(lock)),
+        None => Ok(()),
+    }
+}
+
+/// How `current` differs from the entry as it was locked
+pub(crate) fn modification(
+    lock: &ServerLock,
+    current: Option<&Value>,
+) -> Option<ForeignModification> {
+    let mark = OwnershipMark {
+        target: lock.target.clone(),
+        name: lock.name.clone(),
+        fields: lock.fields.clone(),
+        marked: false,
+        written_at: lock.locked_at.clone(),
+        reported: None,
+    };
+    ownership_marks::compare(&mark, current)
+}
+
+/// The server's entry, active or disabled
+async fn current_entry(target: &ClientTarget, name: &str) -> Result<Option<Value>, String> {
+    if let Some(entry) = client_target::list_servers(target).await?.remove(name) {
+        return Ok(Some(entry));
+    }
+    Ok(client_target::list_disabled_servers(target)
+        .await?
+        .remove(name))
+}
+
+/// Publish the locked servers modified outside the app since last reported
+async fn check_locks(app: &AppHandle) -> Result<(), String> {
+    let mut states = Vec::new();
+    for lock in read_locks()?.locks {
+        let current = current_entry(&lock.target, &lock.name).await?;
+        let changed = modification(&lock, current.as_ref());
+        let state = changed.as_ref().map(|_| match &current {
+            Some(entry) => config_fingerprint(entry),
+            None => "removed".to_string(),
+        });
+        if state == lock.reported {
+            continue;
+        }
+        if let Some(modification) = &changed {
+            println!(
+                "[Locks] Locked server {} on {} was modified outside the app",
+                lock.name,
+                lock.target.label()
+            );
+            event_bus::publish(
+                app,
+                AppEventKind::LockedServerModified,
+                EventScope::target(&lock.target),
+                modification,
+            );
+        }
+        states.push((lock.target, lock.name, state));
+    }
+    if states.is_empty() {
+        return Ok(());
+    }
+    update_locks(|file| {
+        for lock in &mut file.locks {
+            if let Some((_, _, state)) = states
+                .iter()
+                .find(|(target, name, _)| *target == lock.target && *name == lock.name)
+            {
+                lock.reported = state.clone();
+            }
+        }
+    })
+}
+
+/// Watch the config files of the locked servers, replacing the previous watcher
+async fn refresh_watch() -> Result<(), String> {
+    let Some(app) = APP.get().cloned() else {
+        return Ok(());
+    };
+    let mut files = BTreeSet::new();
+    for lock in read_locks()?.locks {
+        match server_history::config_path(&lock.target).await {
+            Ok(path) => {
+                files.insert(path);
+            }
+            Err(e) => println!("[Locks] Not watching {}: {}", lock.target.label(), e),
+        }
+    }
+    let mut slot = WATCHER
+        .lock()
+        .map_err(|e| format!("Failed to lock watcher: {}", e))?;
+    if files.is_empty() {
+        *slot = None;
+        return Ok(());
+    }
+
+    let debouncer = Debouncer::new(
+        |_, _| (),
+        move |_, ()| {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = check_locks(&app).await {
+                    println!("[Locks] Check failed: {}", e);
+                }
+            });
+        },
+    );
+    let watched = files.clone();
+    let mut watcher: RecommendedWatcher =
+        recommended_watcher(move |res: Result<Event, notify::Error>| {
+            if let Ok(event) = res {
+                for path in event.paths.iter().filter(|p| watched.contains(*p)) {
+                    debouncer.push(path.clone(), ());
+                }
+            }
+        })
+        .map_err(|e| format!("Failed to create watcher: {}", e))?;
+    // Clients replace their files on write, so the directories are watched
+    let dirs: BTreeSet<&Path> = files.iter().filter_map(|file| file.parent()).collect();
+    for dir in dirs {
+        if let Err(e) = watcher.watch(dir, RecursiveMode::NonRecursive) {
+            println!("[Locks] Failed to watch {}: {}", dir.display(), e);
+        }
+    }
+    *slot = Some(watcher);
+    Ok(())
+}
+
+async fn check_and_watch(app: &AppHandle) {
+    if let Err(e) = check_locks(app).await {
+        println!("[Locks] Check failed: {}", e);
+    }
+    if let Err(e) = refresh_watch().await {
+        println!("[Locks] {}", e);
+    }
+}
+
+/// Start watching the locked servers, after looking for changes made while the app was closed.
+/// A newly chosen Claude Code config location is checked and watched in the same way.
+pub fn spawn_lock_watch(app: AppHandle) {
+    let _ = APP.set(app.clone());
+    let moved = app.clone();
+    settings::on_change(
+        |settings| settings.claude_config_location.clone(),
+        move |_| {
+            let app = moved.clone();
+            async move { check_and_watch(&app).await }
+        },
+    );
+    tauri::async_runtime::spawn(async move { check_and_watch(&app).await });
+}
+
+/// Lock a server against edits and removal. `client` is a client name; `scope` is the Claude
+/// Code project or the project config path of other clients, "Global" by default.
+#[command]
+pub async fn lock_server(
+    name: String,
+    client: String,
+    scope: Option<String>,
+    reason: Option<String>,
+) -> Result<ServerLock, String> {
+    let target =
+        quarantine::target_for(Some(&client), scope.as_deref().unwrap_or(GLOBAL_PROJECT_ID));
+    let entry = current_entry(&target, &name)
+        .await?
+        .ok_or_else(|| format!("Server '{}' not found in {}", name, target.label()))?;
+    let lock = ServerLock {
+        target: target.clone(),
+        name: name.clone(),
+        reason: reason
+            .map(|r| r.trim().to_string())
+            .filter(|r| !r.is_empty()),
+        locked_at: Utc::now().to_rfc3339(),
+        fields: ownership_marks::field_hashes(&entry),
+        reported: None,
+    };
+    update_locks(|file| {
+        file.locks
+            .retain(|l| !(l.target == target && l.name == name));
+        file.locks.push(lock.clone());
+    })?;
+    audit_log::log(
+        "lock",
+        &target,
+        Some(&name),
+        lock.reason.clone(),
+        Vec::new(),
+    );
+    if let Err(e) = refresh_watch().await {
+        println!("[Locks] {}", e);
+    }
+    Ok(lock)
+}
+
+/// Lift a lock; false when the server wasn't locked
+#[command]
+pub async fn unlock_server(
+    name: String,
+    client: String,
+    scope: Option<String>,
+) -> Result<bool, String> {
+    let target =
+        quarantine::target_for(Some(&client), scope.as_deref().unwrap_or(GLOBAL_PROJECT_ID));
+    let mut removed = false;
+    update_locks(|file| {
+        let before = file.locks.len();
+        file.locks
+            .retain(|l| !(l.target == target && l.name == name));
+        removed = file.locks.len() != before;
+    })?;
+    if removed {
+        audit_log::log("unlock", &target, Some(&name), None, Vec::new());
+        if let Err(e) = refresh_watch().await {
+            println!("[Locks] {}", e);
+        }
+    }
+    Ok(removed)
+}
+
+/// Every lock, with the changes made outside the app since
+#[command]
+pub async fn list_locked_servers() -> Result<Vec<LockedServer>, String> {
+    let mut locked = Vec::new();
+    for lock in read_locks()?.locks {
+        let current = current_entry(&lock.target, &lock.name).await?;
+        locked.push(LockedServer {
+            modification: modification(&lock, current.as_ref()),
+            lock,
+        });
+    }
+    Ok(locked)
+}
@@ -0,0 +1,45 @@
+// Server lock tests
+use crate::client_target::ClientTarget;
+use crate::ownership_marks::field_hashes;
+use crate::server_locks::{locked_error, modification, ServerLock};
+use serde_json::json;
+
+fn lock(entry: &serde_json::Value, reason: Option<&str>) -> ServerLock {
+    ServerLock {
+        target: ClientTarget::claude_code("/work/app"),
+        name: "db".to_string(),
+        reason: reason.map(str::to_string),
+        locked_at: "2026-01-01T00:00:00Z".to_string(),
+        fields: field_hashes(entry),
+        reported: None,
+    }
+}
+
+#[test]
+fn test_locked_error_names_scope_and_reason() {
+    let entry = json!({ "command": "uvx", "args": ["db-server"] });
+    assert_eq!(
+        locked_error(&lock(&entry, Some("production credentials"))),
+        "Server 'db' is locked in claude_code:/work/app (production credentials); unlock it \
+         before editing or removing it"
+    );
+    assert_eq!(
+        locked_error(&lock(&entry, None)),
+        "Server 'db' is locked in claude_code:/work/app; unlock it before editing or removing it"
+    );
+}
+
+#[test]
+fn test_modification_of_locked_entry() {
+    let entry = json!({ "command": "uvx", "args": ["db-server"], "env": { "DB": "prod" } });
+    let locked = lock(&entry, None);
+    assert_eq!(modification(&locked, Some(&entry)), None);
+
+    let edited = json!({ "command": "uvx", "args": ["db-server"], "env": { "DB": "dev" } });
+    let changed = modification(&locked, Some(&edited)).unwrap();
+    assert_eq!(changed.changed, vec!["/env/DB".to_string()]);
+    assert!(!changed.removed_entry);
+    assert!(!changed.marker_lost);
+
+    assert!(modification(&locked, None).unwrap().removed_entry);
+}
@@ -0,0 +1,201 @@
+//! Field-level three-way merge of a server entry
+//!
+//! Used when sync, reconcile or import finds that both sides changed the same server since
+//! their common base. Non-overlapping edits merge automatically (objects such as `env` and
+//! `headers` are merged key by key); overlapping edits come back as conflicts for the UI to
+//! resolve, and `apply_merge` writes the result only once every conflict has a choice.
+
+use crate::client_target::{self, ClientTarget};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tauri::command;
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct FieldConflict {
+    /// JSON pointer of the field inside the server entry
+    pub pointer: String,
+    /// `None` means the field is absent (or was deleted) on that side
+    pub base: Option<Value>,
+    pub ours: Option<Value>,
+    pub theirs: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct MergeResult {
+    /// Merged entry with conflicting fields still holding our value
+    pub merged: Value,
+    pub conflicts: Vec<FieldConflict>,
+    /// Fields taken from theirs without conflict
+    pub from_theirs: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "pick", rename_all = "snake_case")]
+pub enum FieldChoice {
+    Base,
+    Ours,
+    Theirs,
+    /// Hand-edited value; `null` removes the field
+    Custom {
+        value: Value,
+    },
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct FieldResolution {
+    pub pointer: String,
+    #[serde(flatten)]
+    pub choice: FieldChoice,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct MergeResolution {
+    pub target: ClientTarget,
+    pub name: String,
+    #[serde(default)]
+    pub base: Option<Value>,
+    pub ours: Value,
+    pub theirs: Value,
+    #[serde(default)]
+    pub resolutions: Vec<FieldResolution>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct AppliedMerge {
+    /// Name written, which can differ when the client normalizes it
+    pub name: String,
+    pub merged: Value,
+}
+
+fn pointer_child(pointer: &str, key: &str) -> String {
+    format!("{}/{}", pointer, crate::json_pointer::escape_segment(key))
+}
+
+fn merge_value(
+    base: Option<&Value>,
+    ours: Option<&Value>,
+    theirs: Option<&Value>,
+    pointer: &str,
+    result: &mut MergeResult,
+) -> Option<Value> {
+    if ours == theirs || theirs == base {
+        return ours.cloned();
+    }
+    if ours == base {
+        result.from_theirs.push(pointer.to_string());
+        return theirs.cloned();
+    }
+    // Both changed: recurse when both sides are still objects
+    if let (Some(Value::Object(o)), Some(Value::Object(t))) = (ours, theirs) {
+        let empty = Map::new();
+        let b = match base {
+            Some(Value::Object(b)) => b,
+            _ => &empty,
+        };
+        let mut merged = Map::new();
+        let keys = o.keys().chain(t.keys().filter(|k| !o.contains_key(*k)));
+        for key in keys {
+            let child = pointer_child(pointer, key);
+            if let Some(v) = merge_value(b.get(key), o.get(key), t.get(key), &child, result) {
+                merged.insert(key.clone(), v);
+            }
+        }
+        return Some(Value::Object(merged));
+    }
+    result.conflicts.push(FieldConflict {
+        pointer: pointer.to_string(),
+        base: base.cloned(),
+        ours: ours.cloned(),
+        theirs: theirs.cloned(),
+    });
+    ours.cloned()
+}
+
+pub fn merge_servers(base: Option<&Value>, ours: &Value, theirs: &Value) -> MergeResult {
+    let mut result = MergeResult {
+        merged: Value::Null,
+        conflicts: Vec::new(),
+        from_theirs: Vec::new(),
+    };
+    let merged = merge_value(base, Some(ours), Some(theirs), "", &mut result);
+    result.merged = merged.unwrap_or_else(|| Value::Object(Map::new()));
+    result
+}
+
+/// Merge with every conflict replaced by its chosen value
+pub fn resolve_merge(
+    base: Option<&Value>,
+    ours: &Value,
+    theirs: &Value,
+    resolutions: &[FieldResolution],
+) -> Result<Value, String> {
+    let result = merge_servers(base, ours, theirs);
+    let mut merged = result.merged;
+    for conflict in &result.conflicts {
+        let resolution = resolutions
+            .iter()
+            .find(|r| r.pointer == conflict.pointer)
+            .ok_or_else(|| format!("Conflict at '{}' is not resolved", conflict.pointer))?;
+        let value = match &resolution.choice {
+            FieldChoice::Base => conflict.base.clone(),
+            FieldChoice::Ours => conflict.ours.clone(),
+            FieldChoice::Theirs => conflict.theirs.clone(),
+            FieldChoice::Custom { value } if value.is_null() => None,
+            FieldChoice::Custom { value } => Some(value.clone()),
+        };
+        if conflict.pointer.is_empty() {
+            merged = value.unwrap_or_else(|| Value::Object(Map::new()));
+            continue;
+        }
+        match value {
+            Some(value) => crate::json_pointer::set(&mut merged, &conflict.pointer, value)?,
+            None => {
+                crate::json_pointer::remove(&mut merged, &conflict.pointer);
+            }
+        }
+    }
+    if !merged.is_object() {
+        return Err("Merged server entry is not an object".to_string());
+    }
+    Ok(merged)
+}
+
+#[command]
+pub async fn three_way_merge(
+    base: Option<Value>,
+    ours: Value,
+    theirs: Value,
+) -> Result<MergeResult, String> {
+    Ok(merge_servers(base.as_ref(), &ours, &theirs))
+}
+
+/// Write a resolved merge to the target. Refuses if the target's entry no longer matches
+/// `ours`, so a concurrent edit is never silently overwritten.
+#[command]
+pub async fn apply_merge(resolution: MergeResolution) -> Result<AppliedMerge, String> {
+    let merged = resolve_merge(
+        resolution.base.as_ref(),
+        &resolution.ours,
+        &resolution.theirs,
+        &resolution.resolutions,
+    )?;
+
+    let current = client_target::list_servers(&resolution.target).await?;
+    if current.get(&resolution.name) != Some(&resolution.ours) {
+        return Err(format!(
+            "Server '{}' in {} changed since the merge was computed; merge again",
+            resolution.name,
+            resolution.target.label()
+        ));
+    }
+
+    let name =
+        client_target::upsert_server(&resolution.target, &resolution.name, merged.clone()).await?;
+    println!(
+        "[Merge] Applied merge for '{}' in {} ({} resolutions)",
+        name,
+        resolution.target.label(),
+        resolution.resolutions.len()
+    );
+    Ok(AppliedMerge { name, merged })
+}
@@ -0,0 +1,49 @@
+// Three-way server merge tests
+use crate::server_merge::{merge_servers, resolve_merge, FieldChoice, FieldResolution};
+use serde_json::json;
+
+#[test]
+fn test_disjoint_edits_merge_without_conflicts() {
+    let base = json!({ "command": "npx", "args": ["srv"], "env": { "A": "1" } });
+    let ours = json!({ "command": "npx", "args": ["srv", "--verbose"], "env": { "A": "1" } });
+    let theirs = json!({ "command": "npx", "args": ["srv"], "env": { "A": "1", "B": "2" } });
+
+    let result = merge_servers(Some(&base), &ours, &theirs);
+    assert!(result.conflicts.is_empty());
+    assert_eq!(
+        result.merged,
+        json!({ "command": "npx", "args": ["srv", "--verbose"], "env": { "A": "1", "B": "2" } })
+    );
+    assert_eq!(result.from_theirs, vec!["/env".to_string()]);
+}
+
+#[test]
+fn test_overlapping_edit_is_a_conflict_until_resolved() {
+    let base = json!({ "url": "https://a.example/mcp", "headers": { "X-Team": "core" } });
+    let ours = json!({ "url": "https://a.example/mcp", "headers": { "X-Team": "infra" } });
+    let theirs = json!({ "url": "https://a.example/mcp", "headers": { "X-Team": "web" } });
+
+    let result = merge_servers(Some(&base), &ours, &theirs);
+    assert_eq!(result.conflicts.len(), 1);
+    assert_eq!(result.conflicts[0].pointer, "/headers/X-Team");
+    assert!(resolve_merge(Some(&base), &ours, &theirs, &[]).is_err());
+
+    let resolutions = vec![FieldResolution {
+        pointer: "/headers/X-Team".to_string(),
+        choice: FieldChoice::Theirs,
+    }];
+    let merged = resolve_merge(Some(&base), &ours, &theirs, &resolutions).unwrap();
+    assert_eq!(merged["headers"]["X-Team"], "web");
+}
+
+#[test]
+fn test_delete_versus_edit_conflicts() {
+    let base = json!({ "command": "uvx", "env": { "TOKEN": "old" } });
+    let ours = json!({ "command": "uvx" });
+    let theirs = json!({ "command": "uvx", "env": { "TOKEN": "new" } });
+
+    let result = merge_servers(Some(&base), &ours, &theirs);
+    assert_eq!(result.conflicts.len(), 1);
+    assert_eq!(result.conflicts[0].ours, None);
+    assert!(result.merged.get("env").is_none());
+}
@@ -0,0 +1,363 @@
+//! Cached handshake results and tool lists of configured servers
+//!
+//! Entries are stored per target and server name in server_metadata.json in the app data dir.
+//! They are filled by `refresh_server_metadata` and, when `warm_up_after_add` is set, right
+//! after a server is added: the server gets a sandboxed test launch, its tools are listed and
+//! a `server_warmed_up` event carries the result to the UI.
+//!
+//! Entries record a fingerprint of the config they were fetched with, and count as stale once
+//! the config changed or `METADATA_TTL_HOURS` passed. Failed fetches are remembered for the
+//! same time, so lazy refreshes don't relaunch a broken server on every lookup.
+
+use crate::client_target::{self, ClientTarget};
+use crate::event_bus::{self, AppEventKind, EventScope};
+use crate::server_handshake::{self, McpSession, ProtocolStatus, ServerInfo};
+use crate::settings::{self, app_config_dir, SandboxSettings};
+use crate::tool_catalog;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use tauri::{command, AppHandle, Runtime};
+
+pub(crate) const METADATA_TTL_HOURS: i64 = 24;
+
+/// Pages of `tools/list` fetched at most, against servers that never stop paginating
+const MAX_PAGES: usize = 20;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ToolInfo {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub input_schema: Value,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ServerMetadata {
+    pub target: ClientTarget,
+    pub name: String,
+    pub fetched_at: String,
+    /// `config_fingerprint` of the config the entry was fetched with
+    #[serde(default)]
+    pub config_hash: String,
+    pub server: ServerInfo,
+    pub tools: Vec<ToolInfo>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FailedFetch {
+    pub target: ClientTarget,
+    pub name: String,
+    pub failed_at: String,
+    #[serde(default)]
+    pub config_hash: String,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct MetadataFile {
+    #[serde(default)]
+    servers: Vec<ServerMetadata>,
+    #[serde(default)]
+    failures: Vec<FailedFetch>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct WarmUpResult {
+    pub target: ClientTarget,
+    pub name: String,
+    pub ok: bool,
+    pub error: Option<String>,
+    pub tool_count: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ProtocolReport {
+    pub target: ClientTarget,
+    pub name: String,
+    pub protocol_version: Option<String>,
+    pub status: ProtocolStatus,
+}
+
+fn metadata_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("server_metadata.json"))
+}
+
+fn read_metadata() -> Result<MetadataFile, String> {
+    let path = metadata_path()?;
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse server metadata: {}", e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(MetadataFile::default()),
+        Err(e) => Err(format!("Failed to read server metadata: {}", e)),
+    }
+}
+
+fn write_metadata(file: &MetadataFile) -> Result<(), String> {
+    let path = metadata_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(file)
+        .map_err(|e| format!("Failed to serialize server metadata: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write server metadata: {}", e))
+}
+
+/// SHA-256 of a server config, so cached entries can be matched to it without storing secrets
+pub(crate) fn config_fingerprint(config: &Value) -> String {
+    let digest = ring::digest::digest(&ring::digest::SHA256, config.to_string().as_bytes());
+    digest
+        .as_ref()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Whether something fetched at `at` with `config_hash` needs fetching again for `config`
+pub(crate) fn is_stale(at: &str, config_hash: &str, config: &Value, now: DateTime<Utc>) -> bool {
+    let Ok(at) = DateTime::parse_from_rfc3339(at) else {
+        return true;
+    };
+    config_hash != config_fingerprint(config)
+        || now - at.with_timezone(&Utc) > Duration::hours(METADATA_TTL_HOURS)
+}
+
+pub(crate) fn parse_tool(tool: &Value) -> Option<ToolInfo> {
+    Some(ToolInfo {
+        name: tool["name"].as_str()?.to_string(),
+        title: tool["title"].as_str().map(|s| s.to_string()),
+        description: tool["description"].as_str().map(|s| s.to_string()),
+        input_schema: tool.get("inputSchema").cloned().unwrap_or(Value::Null),
+    })
+}
+
+/// Every page of a paginated list method, e.g. `tools/list` with key `tools`
+pub(crate) async fn list_all(
+    session: &mut McpSession,
+    method: &str,
+    key: &str,
+) -> Result<Vec<Value>, String> {
+    let mut items = Vec::new();
+    let mut cursor: Option<String> = None;
+    for _ in 0..MAX_PAGES {
+        let params = match &cursor {
+            Some(cursor) => json!({ "cursor": cursor }),
+            None => json!({}),
+        };
+        let result = session.request(method, params).await?;
+        if let Some(page) = result[key].as_array() {
+            items.extend(page.iter().cloned());
+        }
+        cursor = result["nextCursor"].as_str().map(|s| s.to_string());
+        if cursor.is_none() {
+            break;
+        }
+    }
+    Ok(items)
+}
+
+/// Launch `config`, run the handshake and list its tools
+pub(crate) async fn fetch(
+    config: &Value,
+    sandbox: &SandboxSettings,
+) -> Result<(ServerInfo, Vec<ToolInfo>), String> {
+    let mut session = server_handshake::connect(config, sandbox, Default::default())
+        .await
+        .map_err(|(e, _)| e)?;
+    let timeout = sandbox.launch_timeout();
+    let tools = if session.info.capabilities.get("tools").is_some() {
+        tokio::time::timeout(timeout, list_all(&mut session, "tools/list", "tools"))
+            .await
+            .map_err(|_| format!("No tool list within {}s", timeout.as_secs()))??
+            .iter()
+            .filter_map(parse_tool)
+            .collect()
+    } else {
+        Vec::new()
+    };
+    Ok((session.info.clone(), tools))
+}
+
+fn store(result: Result<ServerMetadata, FailedFetch>) -> Result<(), String> {
+    let mut file = read_metadata()?;
+    let (target, name) = match &result {
+        Ok(entry) => (&entry.target, &entry.name),
+        Err(failure) => (&failure.target, &failure.name),
+    };
+    file.servers
+        .retain(|m| !(&m.target == target && &m.name == name));
+    file.failures
+        .retain(|f| !(&f.target == target && &f.name == name));
+    match result {
+        Ok(entry) => file.servers.push(entry),
+        Err(failure) => file.failures.push(failure),
+    }
+    write_metadata(&file)
+}
+
+/// Keep one entry per server (the newest, and metadata over a failure, as `store` leaves
+/// only one) and drop entries for servers missing from their target. Targets absent from
+/// `configured` couldn't be listed and keep their entries. Returns how many were removed.
+pub(crate) fn prune_entries(
+    servers: &mut Vec<ServerMetadata>,
+    failures: &mut Vec<FailedFetch>,
+    configured: &HashMap<ClientTarget, HashSet<String>>,
+) -> usize {
+    let before = servers.len() + failures.len();
+    let keep =
+        |target: &ClientTarget, name: &String, seen: &mut HashSet<(ClientTarget, String)>| {
+            configured
+                .get(target)
+                .is_none_or(|names| names.contains(name))
+                && seen.insert((target.clone(), name.clone()))
+        };
+    // Newer entries are appended, so dedupe from the back
+    let mut seen = HashSet::new();
+    servers.reverse();
+    servers.retain(|m| keep(&m.target, &m.name, &mut seen));
+    servers.reverse();
+    failures.reverse();
+    failures.retain(|f| keep(&f.target, &f.name, &mut seen));
+    failures.reverse();
+    before - servers.len() - failures.len()
+}
+
+/// Apply `prune_entries` to the cache file
+pub(crate) fn prune(configured: &HashMap<ClientTarget, HashSet<String>>) -> Result<usize, String> {
+    let mut file = read_metadata()?;
+    let removed = prune_entries(&mut file.servers, &mut file.failures, configured);
+    if removed > 0 {
+        write_metadata(&file)?;
+    }
+    Ok(removed)
+}
+
+/// Cached metadata and remembered failures of every server
+pub(crate) fn cached() -> (Vec<ServerMetadata>, Vec<FailedFetch>) {
+    read_metadata()
+        .map(|f| (f.servers, f.failures))
+        .unwrap_or_default()
+}
+
+/// Fetch and cache the metadata of one server
+pub(crate) async fn refresh(
+    target: &ClientTarget,
+    name: &str,
+    config: &Value,
+) -> Result<ServerMetadata, String> {
+    let config_hash = config_fingerprint(config);
+    match fetch(config, &settings::load_settings().sandbox).await {
+        Ok((server, tools)) => {
+            let entry = ServerMetadata {
+                target: target.clone(),
+                name: name.to_string(),
+                fetched_at: Utc::now().to_rfc3339(),
+                config_hash,
+                server,
+                tools,
+            };
+            store(Ok(entry.clone()))?;
+            Ok(entry)
+        }
+        Err(error) => {
+            store(Err(FailedFetch {
+                target: target.clone(),
+                name: name.to_string(),
+                failed_at: Utc::now().to_rfc3339(),
+                config_hash,
+                error: error.clone(),
+            }))?;
+            Err(error)
+        }
+    }
+}
+
+/// Config of the server `name` in `target`
+pub(crate) async fn configured_server(target: &ClientTarget, name: &str) -> Result<Value, String> {
+    client_target::list_servers(target)
+        .await?
+        .get(name)
+        .cloned()
+        .ok_or_else(|| format!("Server '{}' not found", name))
+}
+
+/// After an add: warm up the server in the background when `warm_up_after_add` is set
+pub(crate) fn spawn_warm_up<R: Runtime>(
+    app: &AppHandle<R>,
+    target: ClientTarget,
+    name: String,
+    config: Value,
+) {
+    if !settings::load_settings().warm_up_after_add || config["command"].as_str().is_none() {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let result = refresh(&target, &name, &config).await;
+        let report = WarmUpResult {
+            ok: result.is_ok(),
+            tool_count: result.as_ref().ok().map(|m| m.tools.len()),
+            error: result.err(),
+            target,
+            name,
+        };
+        println!(
+            "[Metadata] Warm-up of {}: {}",
+            report.name,
+            report.error.as_deref().unwrap_or("ok")
+        );
+        let scope = EventScope::target(&report.target);
+        event_bus::publish(&app, AppEventKind::ServerWarmedUp, scope, &report);
+    });
+}
+
+#[command]
+pub async fn get_server_metadata(
+    target: ClientTarget,
+    name: String,
+) -> Result<Option<ServerMetadata>, String> {
+    Ok(read_metadata()?
+        .servers
+        .into_iter()
+        .find(|m| m.target == target && m.name == name))
+}
+
+/// Launch a configured server and cache its handshake result and tools
+#[command]
+pub async fn refresh_server_metadata(
+    target: ClientTarget,
+    name: String,
+) -> Result<ServerMetadata, String> {
+    let config = configured_server(&target, &name).await?;
+    refresh(&target, &name, &config).await
+}
+
+/// Protocol revision each launchable server negotiated, outdated and unknown ones first
+#[command]
+pub async fn get_protocol_versions(refresh: Option<bool>) -> Result<Vec<ProtocolReport>, String> {
+    let index = tool_catalog::tool_index(refresh.unwrap_or(true)).await;
+    let mut reports: Vec<ProtocolReport> = index
+        .servers
+        .into_iter()
+        .map(|m| ProtocolReport {
+            status: server_handshake::protocol_status(m.server.protocol_version.as_deref()),
+            protocol_version: m.server.protocol_version,
+            target: m.target,
+            name: m.name,
+        })
+        .collect();
+    reports.sort_by_key(|r| match r.status {
+        ProtocolStatus::Outdated => 0,
+        ProtocolStatus::Unknown => 1,
+        ProtocolStatus::Previous => 2,
+        ProtocolStatus::Current => 3,
+    });
+    Ok(reports)
+}
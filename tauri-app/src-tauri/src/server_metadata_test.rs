@@ -0,0 +1,105 @@
+// Server metadata tests
+use crate::client_target::ClientTarget;
+use crate::server_handshake::{protocol_status, ProtocolStatus, ServerInfo, PROTOCOL_VERSION};
+use crate::server_metadata::{
+    config_fingerprint, is_stale, parse_tool, prune_entries, FailedFetch, ServerMetadata, ToolInfo,
+};
+use chrono::{TimeZone, Utc};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+
+#[test]
+fn test_parse_tool() {
+    let tool = json!({
+        "name": "search_issues",
+        "description": "Search issues",
+        "inputSchema": { "type": "object", "properties": { "query": { "type": "string" } } }
+    });
+    assert_eq!(
+        parse_tool(&tool),
+        Some(ToolInfo {
+            name: "search_issues".to_string(),
+            title: None,
+            description: Some("Search issues".to_string()),
+            input_schema: tool["inputSchema"].clone(),
+        })
+    );
+    assert_eq!(parse_tool(&json!({ "description": "no name" })), None);
+}
+
+#[test]
+fn test_protocol_status() {
+    assert_eq!(
+        protocol_status(Some(PROTOCOL_VERSION)),
+        ProtocolStatus::Current
+    );
+    assert_eq!(
+        protocol_status(Some("2025-03-26")),
+        ProtocolStatus::Previous
+    );
+    assert_eq!(
+        protocol_status(Some("2024-11-05")),
+        ProtocolStatus::Outdated
+    );
+    assert_eq!(protocol_status(Some("1.0")), ProtocolStatus::Unknown);
+    assert_eq!(protocol_status(None), ProtocolStatus::Unknown);
+}
+
+#[test]
+fn test_is_stale() {
+    let config = json!({ "command": "npx", "args": ["-y", "server-github"] });
+    let hash = config_fingerprint(&config);
+    let now = Utc.with_ymd_and_hms(2026, 3, 2, 12, 0, 0).unwrap();
+    assert!(!is_stale("2026-03-02T00:00:00Z", &hash, &config, now));
+    assert!(is_stale("2026-03-01T00:00:00Z", &hash, &config, now));
+    let changed = json!({ "command": "npx", "args": ["-y", "server-gitlab"] });
+    assert!(is_stale("2026-03-02T00:00:00Z", &hash, &changed, now));
+    assert!(is_stale("not a date", &hash, &config, now));
+}
+
+fn metadata(client: &str, name: &str, fetched_at: &str) -> ServerMetadata {
+    ServerMetadata {
+        target: ClientTarget::client(client, None),
+        name: name.to_string(),
+        fetched_at: fetched_at.to_string(),
+        config_hash: String::new(),
+        server: ServerInfo::default(),
+        tools: Vec::new(),
+    }
+}
+
+#[test]
+fn test_prune_entries() {
+    let mut servers = vec![
+        metadata("cursor", "github", "2026-01-01T00:00:00Z"),
+        metadata("cursor", "removed", "2026-01-01T00:00:00Z"),
+        metadata("cursor", "github", "2026-01-02T00:00:00Z"),
+        // Target that couldn't be listed keeps its entries
+        metadata("vscode", "anything", "2026-01-01T00:00:00Z"),
+    ];
+    let mut failures = vec![FailedFetch {
+        target: ClientTarget::client("cursor", None),
+        name: "github".to_string(),
+        failed_at: "2026-01-03T00:00:00Z".to_string(),
+        config_hash: String::new(),
+        error: "timed out".to_string(),
+    }];
+    let configured = HashMap::from([(
+        ClientTarget::client("cursor", None),
+        HashSet::from(["github".to_string()]),
+    )]);
+
+    assert_eq!(prune_entries(&mut servers, &mut failures, &configured), 3);
+    let kept: Vec<(&str, &str)> = servers
+        .iter()
+        .map(|m| (m.name.as_str(), m.fetched_at.as_str()))
+        .collect();
+    assert_eq!(
+        kept,
+        vec![
+            ("github", "2026-01-02T00:00:00Z"),
+            ("anything", "2026-01-01T00:00:00Z")
+        ]
+    );
+    assert!(failures.is_empty());
+}
@@ -0,0 +1,216 @@
+//! Server name validation for Claude Code
+//!
+//! Claude Code exposes tools as `mcp__<server>__<tool>` and permission rules match on that
+//! string, so a name with a trailing space or a dot can be added but never allowed or denied.
+//! Names are restricted to ASCII letters, digits, `-` and `_`.
+
+use crate::claude_code_commands::{
+    self, create_backup, get_claude_config_path, write_claude_config_verified, ClaudeCodeResponse,
+};
+use crate::json_pointer;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fs;
+use tauri::command;
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct NameValidation {
+    pub name: String,
+    pub valid: bool,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+    /// Valid name to rename to, when `name` is not already valid
+    pub suggestion: Option<String>,
+}
+
+fn is_allowed_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_'
+}
+
+/// Closest valid name: trimmed, with runs of other characters replaced by a single `-`
+pub fn normalize_server_name(name: &str) -> String {
+    let mut normalized = String::new();
+    for c in name.trim().chars() {
+        if is_allowed_char(c) {
+            normalized.push(c);
+        } else if !normalized.ends_with('-') {
+            normalized.push('-');
+        }
+    }
+    normalized.trim_matches('-').to_string()
+}
+
+/// Validate `name` against the names already present in the same scope
+pub fn check_server_name(name: &str, existing: &[String]) -> NameValidation {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    if name.trim().is_empty() {
+        errors.push("name is empty".to_string());
+    } else if name.trim() != name {
+        errors.push("name has leading or trailing whitespace".to_string());
+    }
+    let invalid: Vec<char> = name
+        .trim()
+        .chars()
+        .filter(|c| !is_allowed_char(*c))
+        .collect();
+    if !invalid.is_empty() {
+        errors.push(format!(
+            "name contains characters permission rules can't match: {:?}",
+            invalid
+        ));
+    }
+    if name.contains("__") {
+        warnings.push("'__' is the separator in mcp__server__tool permission names".to_string());
+    }
+    for other in existing {
+        if other != name && other.eq_ignore_ascii_case(name) {
+            warnings.push(format!("name differs only by case from '{}'", other));
+        }
+    }
+
+    let suggestion = if errors.is_empty() {
+        None
+    } else {
+        Some(normalize_server_name(name)).filter(|s| !s.is_empty())
+    };
+    NameValidation {
+        name: name.to_string(),
+        valid: errors.is_empty(),
+        errors,
+        warnings,
+        suggestion,
+    }
+}
+
+/// Name to write for an add request, plus warnings to pass back.
+/// Invalid names fail with the suggestion, or are replaced by it when `normalize` is set.
+pub(crate) fn accept_server_name(
+    name: &str,
+    existing: &[String],
+    normalize: bool,
+) -> Result<(String, Vec<String>), String> {
+    let validation = check_server_name(name, existing);
+    if validation.valid {
+        return Ok((validation.name, validation.warnings));
+    }
+    match validation.suggestion {
+        Some(suggestion) if normalize => {
+            let mut warnings = check_server_name(&suggestion, existing).warnings;
+            warnings.insert(
+                0,
+                format!("name '{}' was normalized to '{}'", name, suggestion),
+            );
+            Ok((suggestion, warnings))
+        }
+        suggestion => Err(format!(
+            "Invalid server name '{}': {}{}",
+            name,
+            validation.errors.join("; "),
+            suggestion
+                .map(|s| format!(" (suggested: '{}')", s))
+                .unwrap_or_default()
+        )),
+    }
+}
+
+/// Validate a prospective server name for the given scope
+#[command]
+pub async fn validate_server_name(
+    name: String,
+    working_dir: String,
+) -> Result<NameValidation, String> {
+    let existing = existing_names(&working_dir).await?;
+    Ok(check_server_name(&name, &existing))
+}
+
+/// Existing servers in the scope whose names have errors or warnings
+#[command]
+pub async fn claude_name_issues(working_dir: String) -> Result<Vec<NameValidation>, String> {
+    let existing = existing_names(&working_dir).await?;
+    Ok(existing
+        .iter()
+        .map(|name| check_server_name(name, &existing))
+        .filter(|v| !v.valid || !v.warnings.is_empty())
+        .collect())
+}
+
+/// Rename a server in place, keeping its position among the other servers
+#[command]
+pub async fn claude_mcp_rename(
+    name: String,
+    new_name: String,
+    working_dir: String,
+) -> Result<ClaudeCodeResponse, String> {
+    let validation = check_server_name(&new_name, &[]);
+    if !validation.valid {
+        return Err(format!(
+            "Invalid server name '{}': {}",
+            new_name,
+            validation.errors.join("; ")
+        ));
+    }
+
+    let config_path = get_claude_config_path(None).await?;
+    if !config_path.exists() {
+        return Err("Claude config file not found".to_string());
+    }
+    let content = fs::read_to_string(&config_path)
+        .map_err(|e| format!("Failed to read Claude config: {}", e))?;
+    let mut config: Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse Claude config: {}", e))?;
+    let before = config.clone();
+
+    let old_pointer = json_pointer::claude_server_pointer(&working_dir, &name);
+    let new_pointer = json_pointer::claude_server_pointer(&working_dir, &new_name);
+    if config.pointer(&new_pointer).is_some() {
+        return Err(format!("Server '{}' already exists", new_name));
+    }
+    let (servers_pointer, _) =
+        json_pointer::split_last(&old_pointer).ok_or("Invalid server pointer")?;
+    let servers = config
+        .pointer_mut(servers_pointer)
+        .and_then(|v| v.as_object_mut())
+        .filter(|m| m.contains_key(&name))
+        .ok_or_else(|| format!("Server '{}' not found", name))?;
+    let renamed: Map<String, Value> = std::mem::take(servers)
+        .into_iter()
+        .map(|(k, v)| {
+            if k == name {
+                (new_name.clone(), v)
+            } else {
+                (k, v)
+            }
+        })
+        .collect();
+    *servers = renamed;
+
+    let backup = create_backup(&config_path)?;
+    write_claude_config_verified(
+        &config_path,
+        Some(&before),
+        &config,
+        &[old_pointer, new_pointer],
+        Some(&backup),
+    )?;
+    backup.discard();
+
+    Ok(ClaudeCodeResponse {
+        success: true,
+        message: format!("Server '{}' renamed to '{}'", name, new_name),
+        name: Some(new_name),
+        outcome: None,
+        warnings: validation.warnings,
+    })
+}
+
+async fn existing_names(working_dir: &str) -> Result<Vec<String>, String> {
+    Ok(
+        claude_code_commands::claude_mcp_list(working_dir.to_string())
+            .await?
+            .into_iter()
+            .map(|s| s.name)
+            .collect(),
+    )
+}
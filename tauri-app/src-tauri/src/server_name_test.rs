@@ -0,0 +1,33 @@
+// Server name validation tests
+use crate::server_name::{accept_server_name, check_server_name, normalize_server_name};
+
+#[test]
+fn test_trailing_space_is_rejected_with_suggestion() {
+    let result = check_server_name("github ", &[]);
+    assert!(!result.valid);
+    assert_eq!(result.suggestion.as_deref(), Some("github"));
+}
+
+#[test]
+fn test_normalize_collapses_invalid_characters() {
+    assert_eq!(normalize_server_name("  my server.v2 "), "my-server-v2");
+    assert_eq!(normalize_server_name("a / b"), "a-b");
+    assert_eq!(normalize_server_name("🚀"), "");
+}
+
+#[test]
+fn test_case_only_difference_warns() {
+    let existing = vec!["GitHub".to_string()];
+    let result = check_server_name("github", &existing);
+    assert!(result.valid);
+    assert_eq!(result.warnings.len(), 1);
+}
+
+#[test]
+fn test_accept_normalizes_only_when_asked() {
+    assert!(accept_server_name("my server", &[], false).is_err());
+    let (name, warnings) = accept_server_name("my server", &[], true).unwrap();
+    assert_eq!(name, "my-server");
+    assert_eq!(warnings.len(), 1);
+    assert!(accept_server_name("   ", &[], true).is_err());
+}
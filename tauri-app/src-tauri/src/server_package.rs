@@ -0,0 +1,156 @@
+//! The package a stdio server runs, from its launcher command
+//!
+//! `npx`/`bunx`/`pnpm dlx` run npm packages, `uvx`/`pipx run` PyPI packages and `docker run`
+//! container images. Servers started from a local binary or script have no package.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Ecosystem {
+    Npm,
+    Pypi,
+    Docker,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct PackageRef {
+    pub ecosystem: Ecosystem,
+    pub name: String,
+    /// Version or tag as written, e.g. `1.2.3`, `latest` or `^1.0`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+impl PackageRef {
+    /// Whether `version` names one exact release
+    pub fn is_pinned(&self) -> bool {
+        self.version.as_deref().is_some_and(|v| {
+            v.starts_with(|c: char| c.is_ascii_digit())
+                && v.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || ".-+".contains(c))
+        })
+    }
+}
+
+/// Docker flags that take a separate value
+const DOCKER_VALUE_FLAGS: [&str; 14] = [
+    "-e",
+    "--env",
+    "--env-file",
+    "-v",
+    "--volume",
+    "--name",
+    "--network",
+    "-p",
+    "--publish",
+    "-w",
+    "--workdir",
+    "--mount",
+    "-u",
+    "--user",
+];
+
+/// Launcher flags that take a separate value
+const LAUNCHER_VALUE_FLAGS: [&str; 5] = ["-p", "--package", "--python", "--with", "--index-url"];
+
+pub(crate) fn program_name(command: &str) -> &str {
+    let file = command.rsplit(['/', '\\']).next().unwrap_or(command);
+    file.strip_suffix(".cmd")
+        .or_else(|| file.strip_suffix(".exe"))
+        .unwrap_or(file)
+}
+
+/// First positional argument, skipping flags and the values of `value_flags`
+fn first_positional<'a>(args: &[&'a str], value_flags: &[&str]) -> Option<&'a str> {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if value_flags.contains(arg) {
+            iter.next();
+        } else if !arg.starts_with('-') {
+            return Some(arg);
+        }
+    }
+    None
+}
+
+pub(crate) fn parse_npm_spec(spec: &str) -> PackageRef {
+    // The version separator is the last '@' that isn't the scope prefix
+    let (name, version) = match spec.rfind('@') {
+        Some(at) if at > 0 => (&spec[..at], Some(spec[at + 1..].to_string())),
+        _ => (spec, None),
+    };
+    PackageRef {
+        ecosystem: Ecosystem::Npm,
+        name: name.to_string(),
+        version: version.filter(|v| !v.is_empty()),
+    }
+}
+
+pub(crate) fn parse_pypi_spec(spec: &str) -> PackageRef {
+    let split = spec.find(['=', '@', '<', '>', '~', '!']);
+    let (name, version) = match split {
+        Some(i) => {
+            let version = spec[i..].trim_start_matches(['=', '@']);
+            (&spec[..i], Some(version.to_string()))
+        }
+        None => (spec, None),
+    };
+    // Extras don't change which package is installed
+    let name = name.split('[').next().unwrap_or(name);
+    PackageRef {
+        ecosystem: Ecosystem::Pypi,
+        name: name.trim().to_lowercase(),
+        version: version.filter(|v| !v.is_empty()),
+    }
+}
+
+pub(crate) fn parse_image(image: &str) -> PackageRef {
+    let image = image.split('@').next().unwrap_or(image);
+    let last_slash = image.rfind('/').map_or(0, |i| i + 1);
+    let (name, version) = match image[last_slash..].rfind(':') {
+        Some(colon) => {
+            let colon = last_slash + colon;
+            (&image[..colon], Some(image[colon + 1..].to_string()))
+        }
+        None => (image, None),
+    };
+    PackageRef {
+        ecosystem: Ecosystem::Docker,
+        name: name.to_string(),
+        version,
+    }
+}
+
+/// Package a server entry runs, if its command is a known launcher
+pub(crate) fn package_ref(entry: &Value) -> Option<PackageRef> {
+    let command = entry["command"].as_str()?;
+    let args: Vec<&str> = entry["args"]
+        .as_array()
+        .map(|args| args.iter().filter_map(|a| a.as_str()).collect())
+        .unwrap_or_default();
+    match program_name(command) {
+        "npx" | "bunx" => first_positional(&args, &LAUNCHER_VALUE_FLAGS[..2]).map(parse_npm_spec),
+        "pnpm" | "yarn" if args.first() == Some(&"dlx") => {
+            first_positional(&args[1..], &LAUNCHER_VALUE_FLAGS[..2]).map(parse_npm_spec)
+        }
+        "uvx" => {
+            // `uvx --from <spec> <cmd>` installs <spec>
+            if let Some(pos) = args.iter().position(|a| *a == "--from") {
+                return args.get(pos + 1).copied().map(parse_pypi_spec);
+            }
+            first_positional(&args, &LAUNCHER_VALUE_FLAGS).map(parse_pypi_spec)
+        }
+        "pipx" if args.first() == Some(&"run") => {
+            if let Some(pos) = args.iter().position(|a| *a == "--spec") {
+                return args.get(pos + 1).copied().map(parse_pypi_spec);
+            }
+            first_positional(&args[1..], &LAUNCHER_VALUE_FLAGS).map(parse_pypi_spec)
+        }
+        "docker" | "podman" if args.first() == Some(&"run") => {
+            first_positional(&args[1..], &DOCKER_VALUE_FLAGS).map(parse_image)
+        }
+        _ => None,
+    }
+}
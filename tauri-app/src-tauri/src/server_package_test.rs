@@ -0,0 +1,45 @@
+// Launcher package detection tests
+use crate::server_package::{package_ref, Ecosystem};
+use serde_json::json;
+
+#[test]
+fn test_package_ref_from_launchers() {
+    let npm = package_ref(&json!({
+        "command": "npx",
+        "args": ["-y", "@modelcontextprotocol/server-github@2025.4.8"]
+    }))
+    .unwrap();
+    assert_eq!(npm.ecosystem, Ecosystem::Npm);
+    assert_eq!(npm.name, "@modelcontextprotocol/server-github");
+    assert_eq!(npm.version.as_deref(), Some("2025.4.8"));
+    assert!(npm.is_pinned());
+
+    let latest = package_ref(&json!({ "command": "npx", "args": ["-y", "tavily-mcp@latest"] }));
+    assert!(!latest.unwrap().is_pinned());
+
+    let pypi = package_ref(
+        &json!({ "command": "uvx", "args": ["mcp-server-git==0.6.2", "--repository", "."] }),
+    )
+    .unwrap();
+    assert_eq!(
+        (pypi.ecosystem, pypi.name.as_str()),
+        (Ecosystem::Pypi, "mcp-server-git")
+    );
+    assert_eq!(pypi.version.as_deref(), Some("0.6.2"));
+
+    let from = package_ref(
+        &json!({ "command": "uvx", "args": ["--from", "Awesome-MCP[cli]", "awesome"] }),
+    )
+    .unwrap();
+    assert_eq!((from.name.as_str(), from.version), ("awesome-mcp", None));
+
+    let image = package_ref(&json!({
+        "command": "docker",
+        "args": ["run", "-i", "--rm", "-e", "GITHUB_TOKEN", "ghcr.io/github/github-mcp-server:v0.5.0"]
+    }))
+    .unwrap();
+    assert_eq!(image.name, "ghcr.io/github/github-mcp-server");
+    assert_eq!(image.version.as_deref(), Some("v0.5.0"));
+
+    assert!(package_ref(&json!({ "command": "/usr/local/bin/my-server" })).is_none());
+}
@@ -0,0 +1,293 @@
+//! Which tool installed a server
+//!
+//! Other MCP managers write into the same client configs but leave no state of their own
+//! behind, so provenance is inferred from the entry: Smithery runs servers through
+//! `@smithery/cli run <id>` or hosts them on `server.smithery.ai`, mcpm through `mcpm run`,
+//! mcp-get through its `@michaellatman/mcp-get` launcher, Docker's MCP Toolkit through
+//! `docker mcp gateway run`, and `mcp-remote` proxies remote servers over stdio. Users then
+//! either adopt a server (re-write it through this app and track it as managed) or mark it
+//! external, which leaves it untouched.
+//!
+//! Ownership decisions are stored in managed.json in the app data dir.
+
+use crate::client_target::{self, ClientTarget};
+use crate::op_recorder::{self, RecordedOp};
+use crate::settings::app_config_dir;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+use tauri::command;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProvenanceSource {
+    Smithery,
+    Mcpm,
+    McpGet,
+    DockerMcp,
+    McpRemote,
+    /// No other manager's pattern matched
+    Unknown,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Provenance {
+    pub source: ProvenanceSource,
+    /// Package or server id in the other manager, when the entry names one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package: Option<String>,
+    /// What in the entry gave it away
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evidence: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Ownership {
+    /// Re-written and managed by this app
+    Adopted,
+    /// Left to the tool that installed it
+    External,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OwnershipRecord {
+    pub target: ClientTarget,
+    pub name: String,
+    pub ownership: Ownership,
+    pub source: ProvenanceSource,
+    pub decided_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct ManagedFile {
+    #[serde(default)]
+    servers: Vec<OwnershipRecord>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ServerProvenance {
+    pub target: ClientTarget,
+    pub name: String,
+    pub provenance: Provenance,
+    pub ownership: Option<Ownership>,
+}
+
+fn managed_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("managed.json"))
+}
+
+fn read_managed() -> Result<ManagedFile, String> {
+    let path = managed_path()?;
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse managed servers: {}", e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(ManagedFile::default()),
+        Err(e) => Err(format!("Failed to read managed servers: {}", e)),
+    }
+}
+
+fn write_managed(file: &ManagedFile) -> Result<(), String> {
+    let path = managed_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(file)
+        .map_err(|e| format!("Failed to serialize managed servers: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write managed servers: {}", e))
+}
+
+fn string_args(entry: &Value) -> Vec<&str> {
+    entry["args"]
+        .as_array()
+        .map(|args| args.iter().filter_map(|a| a.as_str()).collect())
+        .unwrap_or_default()
+}
+
+fn found(source: ProvenanceSource, package: Option<&str>, evidence: String) -> Provenance {
+    Provenance {
+        source,
+        package: package.map(|p| p.to_string()),
+        evidence: Some(evidence),
+    }
+}
+
+/// Infer which manager wrote `entry`
+pub(crate) fn detect(entry: &Value) -> Provenance {
+    let command = entry["command"].as_str().unwrap_or_default();
+    let program = command.rsplit(['/', '\\']).next().unwrap_or_default();
+    let args = string_args(entry);
+    let after = |pos: usize| args.get(pos + 1).copied();
+
+    if let Some(url) = entry["url"].as_str() {
+        if let Ok(parsed) = url::Url::parse(url) {
+            if parsed
+                .host_str()
+                .is_some_and(|h| h == "server.smithery.ai" || h.ends_with(".smithery.ai"))
+            {
+                let package = parsed.path().trim_matches('/').trim_end_matches("/mcp");
+                let package = (!package.is_empty()).then_some(package);
+                return found(
+                    ProvenanceSource::Smithery,
+                    package,
+                    "smithery.ai URL".to_string(),
+                );
+            }
+        }
+    }
+    if let Some(pos) = args.iter().position(|a| a.starts_with("@smithery/cli")) {
+        let package = args[pos..]
+            .iter()
+            .position(|a| *a == "run")
+            .and_then(|run| after(pos + run));
+        return found(
+            ProvenanceSource::Smithery,
+            package,
+            format!("runs {}", args[pos]),
+        );
+    }
+    if program.starts_with("mcpm") || args.contains(&"mcpm") {
+        let package = args.iter().position(|a| *a == "run").and_then(after);
+        return found(ProvenanceSource::Mcpm, package, "runs mcpm".to_string());
+    }
+    if let Some(pos) = args
+        .iter()
+        .position(|a| a.starts_with("@michaellatman/mcp-get"))
+    {
+        let package = args[pos..]
+            .iter()
+            .position(|a| *a == "run" || *a == "install")
+            .and_then(|run| after(pos + run));
+        return found(
+            ProvenanceSource::McpGet,
+            package,
+            format!("runs {}", args[pos]),
+        );
+    }
+    if program.starts_with("docker") && args.starts_with(&["mcp", "gateway"]) {
+        return found(
+            ProvenanceSource::DockerMcp,
+            None,
+            "runs docker mcp gateway".to_string(),
+        );
+    }
+    if let Some(pos) = args.iter().position(|a| a.starts_with("mcp-remote")) {
+        return found(
+            ProvenanceSource::McpRemote,
+            after(pos),
+            "runs mcp-remote".to_string(),
+        );
+    }
+    Provenance {
+        source: ProvenanceSource::Unknown,
+        package: None,
+        evidence: None,
+    }
+}
+
+fn ownership_of(file: &ManagedFile, target: &ClientTarget, name: &str) -> Option<Ownership> {
+    file.servers
+        .iter()
+        .find(|r| r.target == *target && r.name == name)
+        .map(|r| r.ownership)
+}
+
+/// Provenance of every server in `targets` (all known targets by default). Servers of unknown
+/// origin are left out unless `include_unknown` is set.
+#[command]
+pub async fn scan_server_provenance(
+    targets: Option<Vec<ClientTarget>>,
+    include_unknown: Option<bool>,
+) -> Result<Vec<ServerProvenance>, String> {
+    let targets = match targets {
+        Some(targets) => targets,
+        None => client_target::known_targets().await,
+    };
+    let managed = read_managed()?;
+    let mut results = Vec::new();
+    for target in targets {
+        let servers = match client_target::list_servers(&target).await {
+            Ok(servers) => servers,
+            Err(e) => {
+                println!("[Provenance] Skipping {}: {}", target.label(), e);
+                continue;
+            }
+        };
+        for (name, entry) in servers {
+            let provenance = detect(&entry);
+            if provenance.source == ProvenanceSource::Unknown && include_unknown != Some(true) {
+                continue;
+            }
+            results.push(ServerProvenance {
+                ownership: ownership_of(&managed, &target, &name),
+                target: target.clone(),
+                name,
+                provenance,
+            });
+        }
+    }
+    Ok(results)
+}
+
+/// Adopt a server (re-write it through this app) or mark it as external
+#[command]
+pub async fn set_server_ownership(
+    target: ClientTarget,
+    name: String,
+    ownership: Ownership,
+) -> Result<OwnershipRecord, String> {
+    let entry = client_target::list_servers(&target)
+        .await?
+        .get(&name)
+        .cloned()
+        .ok_or_else(|| format!("Server '{}' not found", name))?;
+    let source = detect(&entry).source;
+
+    let mut name = name;
+    if ownership == Ownership::Adopted {
+        // Writing through the app normalizes the entry (and the name, for Claude Code)
+        let written = client_target::upsert_server(&target, &name, entry.clone()).await?;
+        if written != name {
+            client_target::remove_server(&target, &name).await?;
+        }
+        op_recorder::record(
+            target.clone(),
+            RecordedOp::UpsertServer {
+                name: written.clone(),
+                config: entry,
+            },
+        );
+        name = written;
+    }
+
+    let record = OwnershipRecord {
+        target: target.clone(),
+        name: name.clone(),
+        ownership,
+        source,
+        decided_at: Utc::now().to_rfc3339(),
+    };
+    let mut file = read_managed()?;
+    file.servers
+        .retain(|r| !(r.target == target && r.name == name));
+    file.servers.push(record.clone());
+    write_managed(&file)?;
+    Ok(record)
+}
+
+/// Forget an ownership decision so the server shows up as undecided again
+#[command]
+pub async fn clear_server_ownership(target: ClientTarget, name: String) -> Result<bool, String> {
+    let mut file = read_managed()?;
+    let before = file.servers.len();
+    file.servers
+        .retain(|r| !(r.target == target && r.name == name));
+    if file.servers.len() == before {
+        return Ok(false);
+    }
+    write_managed(&file)?;
+    Ok(true)
+}
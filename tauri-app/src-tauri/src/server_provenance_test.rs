@@ -0,0 +1,40 @@
+// Server provenance detection tests
+use crate::server_provenance::{detect, ProvenanceSource};
+use serde_json::json;
+
+#[test]
+fn test_detect_other_managers() {
+    let smithery_cli = json!({
+        "command": "npx",
+        "args": ["-y", "@smithery/cli@latest", "run", "@acme/weather", "--key", "k"]
+    });
+    let provenance = detect(&smithery_cli);
+    assert_eq!(provenance.source, ProvenanceSource::Smithery);
+    assert_eq!(provenance.package.as_deref(), Some("@acme/weather"));
+
+    let hosted = json!({ "type": "http", "url": "https://server.smithery.ai/@acme/weather/mcp" });
+    assert_eq!(detect(&hosted).package.as_deref(), Some("@acme/weather"));
+
+    let mcp_get = json!({
+        "command": "npx",
+        "args": ["-y", "@michaellatman/mcp-get@latest", "run", "@acme/notes"]
+    });
+    let provenance = detect(&mcp_get);
+    assert_eq!(provenance.source, ProvenanceSource::McpGet);
+    assert_eq!(provenance.package.as_deref(), Some("@acme/notes"));
+
+    let docker = json!({ "command": "docker", "args": ["mcp", "gateway", "run"] });
+    assert_eq!(detect(&docker).source, ProvenanceSource::DockerMcp);
+
+    let remote = json!({ "command": "npx", "args": ["mcp-remote", "https://mcp.example/sse"] });
+    let provenance = detect(&remote);
+    assert_eq!(provenance.source, ProvenanceSource::McpRemote);
+    assert_eq!(
+        provenance.package.as_deref(),
+        Some("https://mcp.example/sse")
+    );
+
+    let plain =
+        json!({ "command": "npx", "args": ["-y", "@modelcontextprotocol/server-filesystem"] });
+    assert_eq!(detect(&plain).source, ProvenanceSource::Unknown);
+}
@@ -0,0 +1,167 @@
+//! Share servers as a QR code
+//!
+//! `export_servers_qr` packs the named servers of a target into one short text payload: the
+//! entries as compact JSON, deflated and base64url-encoded behind a `MCPL1:` prefix. The UI
+//! renders it as a QR code (byte mode), so it must fit one code; larger selections are
+//! refused. Secret env, header and arg values are redacted first (see `redaction`), since a
+//! code on a screen can be photographed by anyone nearby. `import_servers_qr` decodes the text
+//! a scanner read and writes the servers like `copy_servers`, naming the ones whose secrets
+//! still need filling in.
+
+use crate::client_target::{self, ClientTarget};
+use crate::redaction::{self, REDACTED};
+use crate::server_copy::{self, CopyReport};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD as BASE64_URL, Engine};
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::io::{Read, Write};
+use tauri::command;
+
+const PREFIX: &str = "MCPL1:";
+
+/// Bytes a QR code holds at most (version 40, low error correction, byte mode)
+pub(crate) const QR_CAPACITY: usize = 2953;
+
+/// Largest decoded payload accepted, against crafted inputs that inflate without end
+const MAX_DECODED: u64 = 1024 * 1024;
+
+/// Fields client writers add that the receiving side doesn't need
+const BOOKKEEPING_FIELDS: [&str; 4] = ["_creator", "updated_at", "disabled", "isActive"];
+
+#[derive(Debug, Serialize, Clone)]
+pub struct QrExport {
+    /// Text to render as the QR code
+    pub payload: String,
+    pub servers: Vec<String>,
+    /// Servers with redacted values the recipient has to fill in
+    pub redacted: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct QrImport {
+    pub report: CopyReport,
+    /// Imported servers with `REDACTED` placeholders to replace
+    pub needs_secrets: Vec<String>,
+}
+
+fn has_redacted(config: &Value) -> bool {
+    config.to_string().contains(REDACTED)
+}
+
+/// The payload for `servers`
+pub(crate) fn encode(servers: &Map<String, Value>) -> Result<String, String> {
+    let json =
+        serde_json::to_vec(servers).map_err(|e| format!("Failed to serialize servers: {}", e))?;
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+    encoder
+        .write_all(&json)
+        .map_err(|e| format!("Failed to compress servers: {}", e))?;
+    let compressed = encoder
+        .finish()
+        .map_err(|e| format!("Failed to compress servers: {}", e))?;
+    Ok(format!("{}{}", PREFIX, BASE64_URL.encode(compressed)))
+}
+
+/// Servers of a payload
+pub(crate) fn decode(payload: &str) -> Result<Map<String, Value>, String> {
+    let data = payload
+        .trim()
+        .strip_prefix(PREFIX)
+        .ok_or("Not an MCP Linker QR code")?;
+    let compressed = BASE64_URL
+        .decode(data)
+        .map_err(|e| format!("Damaged QR payload: {}", e))?;
+    let mut json = Vec::new();
+    DeflateDecoder::new(compressed.as_slice())
+        .take(MAX_DECODED + 1)
+        .read_to_end(&mut json)
+        .map_err(|e| format!("Damaged QR payload: {}", e))?;
+    if json.len() as u64 > MAX_DECODED {
+        return Err("QR payload is too large".to_string());
+    }
+    match serde_json::from_slice(&json) {
+        Ok(Value::Object(servers)) => Ok(servers),
+        Ok(_) => Err("QR payload holds no servers".to_string()),
+        Err(e) => Err(format!("Damaged QR payload: {}", e)),
+    }
+}
+
+/// A server entry as shared: bookkeeping fields dropped and secrets redacted
+pub(crate) fn shareable(config: &Value) -> Value {
+    let mut config = redaction::redact_config(config);
+    if let Some(fields) = config.as_object_mut() {
+        for field in BOOKKEEPING_FIELDS {
+            fields.shift_remove(field);
+        }
+    }
+    config
+}
+
+/// Payload with the servers `names` of `from`, to show as a QR code
+#[command]
+pub async fn export_servers_qr(from: ClientTarget, names: Vec<String>) -> Result<QrExport, String> {
+    let mut available = client_target::list_servers(&from).await?;
+    available.extend(client_target::list_disabled_servers(&from).await?);
+    let mut servers = Map::new();
+    for name in &names {
+        let config = available
+            .get(name)
+            .ok_or_else(|| format!("Server '{}' not found in {}", name, from.label()))?;
+        servers.insert(name.clone(), shareable(config));
+    }
+    let payload = encode(&servers)?;
+    if payload.len() > QR_CAPACITY {
+        return Err(format!(
+            "{} server(s) need {} bytes, more than one QR code holds ({}); share fewer at once",
+            servers.len(),
+            payload.len(),
+            QR_CAPACITY
+        ));
+    }
+    Ok(QrExport {
+        payload,
+        redacted: servers
+            .iter()
+            .filter(|(_, config)| has_redacted(config))
+            .map(|(name, _)| name.clone())
+            .collect(),
+        servers: servers.keys().cloned().collect(),
+    })
+}
+
+/// Write the servers of a scanned payload to `to`
+#[command]
+pub async fn import_servers_qr(
+    payload: String,
+    to: ClientTarget,
+    overwrite: Option<bool>,
+) -> Result<QrImport, String> {
+    let servers = decode(&payload)?;
+    let needs_secrets = servers
+        .iter()
+        .filter(|(_, config)| has_redacted(config))
+        .map(|(name, _)| name.clone())
+        .collect::<Vec<_>>();
+    let entries = servers
+        .into_iter()
+        .map(|(name, config)| (name, Ok(config)))
+        .collect();
+    let report = server_copy::write_entries(&to, entries, overwrite.unwrap_or(false)).await?;
+    println!(
+        "[QR] Imported to {}: {} copied, {} existing, {} failed",
+        to.label(),
+        report.copied.len(),
+        report.existing.len(),
+        report.failed.len()
+    );
+    Ok(QrImport {
+        needs_secrets: needs_secrets
+            .into_iter()
+            .filter(|name| report.copied.contains(name))
+            .collect(),
+        report,
+    })
+}
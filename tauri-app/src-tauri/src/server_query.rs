@@ -0,0 +1,167 @@
+//! One typed query over every configured server
+//!
+//! `query` answers what used to take a round-trip per client and scope: it filters the
+//! inventory rows (see `inventory`) by client, scope, transport, credential health, enabled
+//! state and tag (see `server_tags`), keeps only the selected fields and returns one page.
+//! Client and scope filters are applied to the targets before their configs are read, so
+//! narrow queries stay cheap. Rows come in inventory order, which doesn't change between pages unless a config does.
+
+use crate::inventory::{self, InventoryEntry};
+use crate::{server_aliases, server_tags};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tauri::command;
+
+pub(crate) const DEFAULT_LIMIT: usize = 100;
+pub(crate) const MAX_LIMIT: usize = 1000;
+
+/// Every list is "any of"; empty lists and `None` match everything
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ServerQuery {
+    pub clients: Vec<String>,
+    /// "Global", a Claude Code project or the config path of a project-level client
+    pub scopes: Vec<String>,
+    /// stdio, http or sse
+    pub transports: Vec<String>,
+    /// Credential expiry: "ok", "credentials_expiring" or "credentials_expired"
+    pub credential_health: Vec<String>,
+    pub tags: Vec<String>,
+    pub enabled: Option<bool>,
+    /// Fields of each row to return; all of them when empty
+    pub fields: Vec<String>,
+    pub offset: usize,
+    /// `DEFAULT_LIMIT` when unset, at most `MAX_LIMIT`
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct QueryPage {
+    /// Rows matching the filters, on all pages
+    pub total: usize,
+    pub offset: usize,
+    pub items: Vec<Value>,
+    /// Offset of the next page, `None` on the last one
+    pub next_offset: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct QueryRow {
+    #[serde(flatten)]
+    entry: InventoryEntry,
+    tags: Vec<String>,
+}
+
+/// Whether `value` passes an "any of" filter
+pub(crate) fn matches_any(filter: &[String], value: &str) -> bool {
+    filter.is_empty() || filter.iter().any(|wanted| wanted == value)
+}
+
+/// Whether a server with the tags `has_tag` accepts passes a tag filter, which is
+/// case-insensitive like the tags themselves
+pub(crate) fn matches_tags(filter: &[String], has_tag: impl Fn(&str) -> bool) -> bool {
+    filter.is_empty()
+        || filter
+            .iter()
+            .filter_map(|tag| server_tags::normalize(tag))
+            .any(|tag| has_tag(&tag))
+}
+
+/// One page of `items`: the total count, the items from `offset`, at most `limit` of them
+/// (`DEFAULT_LIMIT` when unset, capped at `MAX_LIMIT`), and the offset of the next page
+pub(crate) fn paginate<T>(
+    items: Vec<T>,
+    offset: usize,
+    limit: Option<usize>,
+) -> (usize, Vec<T>, Option<usize>) {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let total = items.len();
+    let page: Vec<T> = items.into_iter().skip(offset).take(limit).collect();
+    let end = offset + page.len();
+    (total, page, (end < total).then_some(end))
+}
+
+/// Whether a row, as JSON, passes the row filters of `query`
+pub(crate) fn matches(query: &ServerQuery, row: &Value) -> bool {
+    let text = |key: &str| row[key].as_str().unwrap_or_default().to_string();
+    let tags: Vec<&str> = row["tags"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+    matches_any(&query.clients, &text("client"))
+        && matches_any(&query.scopes, &text("scope"))
+        && matches_any(&query.transports, &text("transport"))
+        && matches_any(&query.credential_health, &text("credential_health"))
+        && query
+            .enabled
+            .is_none_or(|enabled| row["enabled"] == enabled)
+        && matches_tags(&query.tags, |tag| tags.contains(&tag))
+}
+
+/// `row` with only `fields`; fails on a field rows don't have
+pub(crate) fn select(row: Value, fields: &[String]) -> Result<Value, String> {
+    let Value::Object(mut row) = row else {
+        return Ok(row);
+    };
+    if fields.is_empty() {
+        return Ok(Value::Object(row));
+    }
+    if let Some(unknown) = fields.iter().find(|field| !row.contains_key(*field)) {
+        return Err(format!(
+            "Unknown field '{}'; rows have {}",
+            unknown,
+            row.keys().cloned().collect::<Vec<_>>().join(", ")
+        ));
+    }
+    let mut selected = Map::new();
+    for field in fields {
+        if let Some(value) = row.shift_remove(field) {
+            selected.insert(field.clone(), value);
+        }
+    }
+    Ok(Value::Object(selected))
+}
+
+/// The page of `rows` that `query` asks for
+pub(crate) fn run(rows: Vec<Value>, query: &ServerQuery) -> Result<QueryPage, String> {
+    let matching: Vec<Value> = rows.into_iter().filter(|row| matches(query, row)).collect();
+    let (total, page, next_offset) = paginate(matching, query.offset, query.limit);
+    let items = page
+        .into_iter()
+        .map(|row| select(row, &query.fields))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(QueryPage {
+        total,
+        offset: query.offset,
+        next_offset,
+        items,
+    })
+}
+
+/// Servers of every client and scope matching `request`, one page at a time
+#[command]
+pub async fn query(request: ServerQuery) -> Result<QueryPage, String> {
+    let entries = inventory::collect_rows(|target| {
+        matches_any(&request.clients, &target.client)
+            && matches_any(&request.scopes, &inventory::scope_label(target))
+    })
+    .await;
+    let aliases = server_aliases::load();
+    let tags = server_tags::load();
+    let rows = entries
+        .into_iter()
+        .map(|entry| {
+            let row = QueryRow {
+                tags: tags
+                    .tags_of(&aliases, &entry.client, &entry.name)
+                    .into_iter()
+                    .collect(),
+                entry,
+            };
+            serde_json::to_value(row).map_err(|e| format!("Failed to serialize server: {}", e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    run(rows, &request)
+}
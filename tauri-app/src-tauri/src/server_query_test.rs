@@ -0,0 +1,85 @@
+// Server query tests
+use crate::server_aliases::ServerAliases;
+use crate::server_query::{run, ServerQuery};
+use crate::server_tags::ServerTags;
+use serde_json::{json, Value};
+
+fn row(client: &str, name: &str, transport: &str, tags: &[&str]) -> Value {
+    json!({
+        "client": client,
+        "scope": "Global",
+        "name": name,
+        "transport": transport,
+        "enabled": true,
+        "credential_health": "ok",
+        "tags": tags,
+    })
+}
+
+#[test]
+fn test_query_filters_selects_and_pages() {
+    let rows = vec![
+        row("cursor", "github", "http", &["work"]),
+        row("claude_code", "github", "http", &["work"]),
+        row("cursor", "fetch", "stdio", &[]),
+        row("vscode", "sentry", "http", &["work", "errors"]),
+    ];
+    let query = ServerQuery {
+        transports: vec!["http".to_string()],
+        tags: vec!["Work".to_string()],
+        fields: vec!["client".to_string(), "name".to_string()],
+        limit: Some(2),
+        ..Default::default()
+    };
+    let page = run(rows.clone(), &query).unwrap();
+    assert_eq!(page.total, 3);
+    assert_eq!(page.next_offset, Some(2));
+    assert_eq!(
+        page.items,
+        vec![
+            json!({ "client": "cursor", "name": "github" }),
+            json!({ "client": "claude_code", "name": "github" }),
+        ]
+    );
+
+    let last = run(
+        rows.clone(),
+        &ServerQuery {
+            offset: 2,
+            ..query.clone()
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        last.items,
+        vec![json!({ "client": "vscode", "name": "sentry" })]
+    );
+    assert_eq!(last.next_offset, None);
+
+    let unknown = ServerQuery {
+        fields: vec!["secret".to_string()],
+        ..Default::default()
+    };
+    assert!(run(rows, &unknown).is_err());
+}
+
+#[test]
+fn test_tags_follow_the_logical_server() {
+    let mut aliases = ServerAliases::default();
+    aliases
+        .set("github", "cursor", Some("GitHub Tools"))
+        .unwrap();
+    let mut tags = ServerTags::default();
+    tags.set("github", &[" Work ".to_string(), "".to_string()])
+        .unwrap();
+
+    let tagged = |client: &str, name: &str| -> Vec<String> {
+        tags.tags_of(&aliases, client, name).into_iter().collect()
+    };
+    assert_eq!(tagged("cursor", "GitHub Tools"), ["work"]);
+    assert_eq!(tagged("claude_code", "github"), ["work"]);
+    assert!(tagged("cursor", "fetch").is_empty());
+
+    tags.set("github", &[]).unwrap();
+    assert!(tags.servers.is_empty());
+}
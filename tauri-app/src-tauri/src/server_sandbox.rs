@@ -0,0 +1,306 @@
+//! Spawning server processes from the app itself
+//!
+//! Test launches run a server's command with a restricted environment (the variables a
+//! launcher needs plus the server's own `env`, so unrelated secrets in the app's environment
+//! don't leak), in a scratch working directory and under the resource limits from
+//! `SandboxSettings`. The process and everything it starts are put in one group (a process
+//! group on unix, a Job Object on Windows) so `kill_tree` and dropping the handle take down
+//! `npx`/`uvx` children too, instead of orphaning them.
+
+use crate::settings::SandboxSettings;
+use crate::{platform_access, server_package};
+use serde_json::Value;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::process::{Child, Command};
+
+static SCRATCH_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Parent environment variables passed through; matched case-insensitively
+const INHERITED_ENV: [&str; 27] = [
+    "PATH",
+    "HOME",
+    "USER",
+    "USERNAME",
+    "LOGNAME",
+    "SHELL",
+    "TERM",
+    "LANG",
+    "TMPDIR",
+    "TEMP",
+    "TMP",
+    "SYSTEMROOT",
+    "SYSTEMDRIVE",
+    "WINDIR",
+    "COMSPEC",
+    "PATHEXT",
+    "APPDATA",
+    "LOCALAPPDATA",
+    "USERPROFILE",
+    "PROGRAMDATA",
+    "PROGRAMFILES",
+    "HTTP_PROXY",
+    "HTTPS_PROXY",
+    "NO_PROXY",
+    "SSL_CERT_FILE",
+    "NODE_EXTRA_CA_CERTS",
+    "DOCKER_HOST",
+];
+
+/// Prefixes of parent variables passed through (locale and XDG dirs)
+const INHERITED_ENV_PREFIXES: [&str; 2] = ["LC_", "XDG_"];
+
+/// Fails unless the program `command` runs is in the allowlist
+pub(crate) fn check_command(command: &str, settings: &SandboxSettings) -> Result<(), String> {
+    let program = server_package::program_name(command);
+    if settings
+        .allowed_commands
+        .iter()
+        .any(|allowed| allowed.eq_ignore_ascii_case(program))
+    {
+        Ok(())
+    } else {
+        Err(format!(
+            "'{}' is not in the allowed commands for test launches",
+            program
+        ))
+    }
+}
+
+/// Environment of a sandboxed process: allowlisted parent variables, then the server's `env`
+pub(crate) fn sandbox_env(
+    server_env: &Value,
+    parent: impl IntoIterator<Item = (String, String)>,
+) -> Vec<(String, String)> {
+    let mut env: Vec<(String, String)> = parent
+        .into_iter()
+        .filter(|(key, _)| {
+            let upper = key.to_uppercase();
+            INHERITED_ENV.contains(&upper.as_str())
+                || INHERITED_ENV_PREFIXES.iter().any(|p| upper.starts_with(p))
+        })
+        .collect();
+    if let Some(server_env) = server_env.as_object() {
+        for (key, value) in server_env {
+            let Some(value) = value.as_str() else {
+                continue;
+            };
+            env.retain(|(k, _)| !k.eq_ignore_ascii_case(key));
+            env.push((key.clone(), value.to_string()));
+        }
+    }
+    env
+}
+
+fn scratch_dir() -> Result<PathBuf, String> {
+    let dir = std::env::temp_dir()
+        .join("mcp-linker-sandbox")
+        .join(format!(
+            "{}-{}",
+            std::process::id(),
+            SCRATCH_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create scratch directory: {}", e))?;
+    Ok(dir)
+}
+
+fn string_args(config: &Value) -> Vec<String> {
+    config["args"]
+        .as_array()
+        .map(|args| {
+            args.iter()
+                .filter_map(|a| a.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// A running sandboxed server; the whole process tree is killed when this is dropped
+pub(crate) struct SandboxedProcess {
+    pub child: Child,
+    scratch: PathBuf,
+    #[cfg(windows)]
+    job: windows_job::Job,
+}
+
+impl SandboxedProcess {
+    /// Kill the process and everything it started
+    pub fn kill_tree(&mut self) {
+        #[cfg(unix)]
+        if let Some(pid) = self.child.id() {
+            // The child leads its own process group, so its id is the group id
+            unsafe {
+                libc::killpg(pid as libc::pid_t, libc::SIGKILL);
+            }
+        }
+        #[cfg(windows)]
+        self.job.terminate();
+        let _ = self.child.start_kill();
+    }
+}
+
+impl Drop for SandboxedProcess {
+    fn drop(&mut self) {
+        self.kill_tree();
+        let _ = std::fs::remove_dir_all(&self.scratch);
+    }
+}
+
+/// Spawn the stdio server described by `config` with piped stdin/stdout/stderr
+pub(crate) fn spawn(
+    config: &Value,
+    settings: &SandboxSettings,
+) -> Result<SandboxedProcess, String> {
+    let command = config["command"]
+        .as_str()
+        .filter(|c| !c.trim().is_empty())
+        .ok_or("Only stdio servers with a command can be launched")?;
+    check_command(command, settings)?;
+    let scratch = scratch_dir()?;
+
+    let mut cmd = Command::new(platform_access::resolve_launcher(command));
+    cmd.args(string_args(config))
+        .env_clear()
+        .envs(sandbox_env(&config["env"], std::env::vars()))
+        .current_dir(&scratch)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    #[cfg(unix)]
+    {
+        let memory_bytes = settings.memory_limit_mb.saturating_mul(1024 * 1024);
+        let cpu_secs = settings.cpu_limit_secs;
+        cmd.process_group(0);
+        // Runs in the forked child before exec; only async-signal-safe calls
+        unsafe {
+            cmd.pre_exec(move || {
+                // Not RLIMIT_AS: V8 reserves far more address space than it uses, so Node
+                // servers would fail to start under any realistic cap
+                if memory_bytes > 0 {
+                    let limit = libc::rlimit {
+                        rlim_cur: memory_bytes as libc::rlim_t,
+                        rlim_max: memory_bytes as libc::rlim_t,
+                    };
+                    libc::setrlimit(libc::RLIMIT_DATA, &limit);
+                }
+                if cpu_secs > 0 {
+                    let limit = libc::rlimit {
+                        rlim_cur: cpu_secs as libc::rlim_t,
+                        rlim_max: cpu_secs as libc::rlim_t,
+                    };
+                    libc::setrlimit(libc::RLIMIT_CPU, &limit);
+                }
+                Ok(())
+            });
+        }
+    }
+
+    let child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            let _ = std::fs::remove_dir_all(&scratch);
+            return Err(format!("Failed to start '{}': {}", command, e));
+        }
+    };
+
+    #[cfg(windows)]
+    let job = {
+        let job = windows_job::Job::new(settings.memory_limit_mb, settings.cpu_limit_secs)?;
+        if let Some(handle) = child.raw_handle() {
+            job.assign(handle)?;
+        }
+        job
+    };
+
+    println!(
+        "[Sandbox] Started {} (pid {})",
+        command,
+        child.id().unwrap_or_default()
+    );
+    Ok(SandboxedProcess {
+        child,
+        scratch,
+        #[cfg(windows)]
+        job,
+    })
+}
+
+#[cfg(windows)]
+mod windows_job {
+    use std::os::windows::io::RawHandle;
+    use windows_sys::Win32::Foundation::{CloseHandle, HANDLE};
+    use windows_sys::Win32::System::JobObjects::{
+        AssignProcessToJobObject, CreateJobObjectW, JobObjectExtendedLimitInformation,
+        SetInformationJobObject, TerminateJobObject, JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
+        JOB_OBJECT_LIMIT_JOB_TIME, JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE,
+        JOB_OBJECT_LIMIT_PROCESS_MEMORY,
+    };
+
+    /// Job Object handle, stored as an integer so the owning process handle stays `Send`
+    pub struct Job(isize);
+
+    impl Job {
+        pub fn new(memory_limit_mb: u64, cpu_limit_secs: u64) -> Result<Self, String> {
+            unsafe {
+                let handle = CreateJobObjectW(std::ptr::null(), std::ptr::null());
+                if handle.is_null() {
+                    return Err("Failed to create job object".to_string());
+                }
+                let job = Job(handle as isize);
+                let mut info: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = std::mem::zeroed();
+                info.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+                if memory_limit_mb > 0 {
+                    info.BasicLimitInformation.LimitFlags |= JOB_OBJECT_LIMIT_PROCESS_MEMORY;
+                    info.ProcessMemoryLimit = (memory_limit_mb * 1024 * 1024) as usize;
+                }
+                if cpu_limit_secs > 0 {
+                    // 100ns units
+                    info.BasicLimitInformation.LimitFlags |= JOB_OBJECT_LIMIT_JOB_TIME;
+                    info.BasicLimitInformation.PerJobUserTimeLimit =
+                        (cpu_limit_secs * 10_000_000) as i64;
+                }
+                let ok = SetInformationJobObject(
+                    job.handle(),
+                    JobObjectExtendedLimitInformation,
+                    &info as *const _ as *const std::ffi::c_void,
+                    std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+                );
+                if ok == 0 {
+                    return Err("Failed to set job object limits".to_string());
+                }
+                Ok(job)
+            }
+        }
+
+        fn handle(&self) -> HANDLE {
+            self.0 as HANDLE
+        }
+
+        pub fn assign(&self, process: RawHandle) -> Result<(), String> {
+            let ok = unsafe { AssignProcessToJobObject(self.handle(), process as HANDLE) };
+            if ok == 0 {
+                return Err("Failed to assign process to job object".to_string());
+            }
+            Ok(())
+        }
+
+        pub fn terminate(&self) {
+            unsafe {
+                TerminateJobObject(self.handle(), 1);
+            }
+        }
+    }
+
+    impl Drop for Job {
+        fn drop(&mut self) {
+            // KILL_ON_JOB_CLOSE takes down anything still running
+            unsafe {
+                CloseHandle(self.handle());
+            }
+        }
+    }
+}
@@ -0,0 +1,31 @@
+// Sandbox environment and command allowlist tests
+use crate::server_sandbox::{check_command, sandbox_env};
+use crate::settings::SandboxSettings;
+use serde_json::json;
+
+#[test]
+fn test_sandbox_env_strips_unrelated_variables() {
+    let parent = vec![
+        ("PATH".to_string(), "/usr/bin".to_string()),
+        ("LC_ALL".to_string(), "C".to_string()),
+        ("AWS_SECRET_ACCESS_KEY".to_string(), "secret".to_string()),
+        ("GITHUB_TOKEN".to_string(), "ghp_parent".to_string()),
+    ];
+    let env = sandbox_env(&json!({ "GITHUB_TOKEN": "ghp_server" }), parent);
+    let value = |key: &str| env.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str());
+    assert_eq!(value("PATH"), Some("/usr/bin"));
+    assert_eq!(value("LC_ALL"), Some("C"));
+    assert_eq!(value("AWS_SECRET_ACCESS_KEY"), None);
+    // The server's own env is always passed
+    assert_eq!(value("GITHUB_TOKEN"), Some("ghp_server"));
+    assert_eq!(env.iter().filter(|(k, _)| k == "GITHUB_TOKEN").count(), 1);
+}
+
+#[test]
+fn test_check_command() {
+    let settings = SandboxSettings::default();
+    assert!(check_command("npx", &settings).is_ok());
+    assert!(check_command("/opt/homebrew/bin/uvx", &settings).is_ok());
+    assert!(check_command("C:\\Program Files\\nodejs\\npx.cmd", &settings).is_ok());
+    assert!(check_command("bash", &settings).is_err());
+}
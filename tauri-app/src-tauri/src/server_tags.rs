@@ -0,0 +1,116 @@
+//! User-assigned tags of servers
+//!
+//! Tags belong to the logical server (see `server_aliases`), so a server tagged once carries
+//! the tag in every client it is configured in. They are stored in server_tags.json in the app
+//! data dir, trimmed and lowercased so "Work" and "work " are the same tag.
+
+use crate::server_aliases::{self, ServerAliases};
+use crate::settings::app_config_dir;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::command;
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ServerTags {
+    /// Logical name -> tags
+    #[serde(default)]
+    pub servers: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl ServerTags {
+    /// Tags of the server `client` has as `name`
+    pub(crate) fn tags_of(
+        &self,
+        aliases: &ServerAliases,
+        client: &str,
+        name: &str,
+    ) -> BTreeSet<String> {
+        self.servers
+            .get(&aliases.logical_name(client, name))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Replace the tags of `logical`; no tags drop its entry
+    pub(crate) fn set(&mut self, logical: &str, tags: &[String]) -> Result<(), String> {
+        if logical.trim().is_empty() {
+            return Err("Server name is required".to_string());
+        }
+        let tags: BTreeSet<String> = tags.iter().filter_map(|tag| normalize(tag)).collect();
+        if tags.is_empty() {
+            self.servers.remove(logical);
+        } else {
+            self.servers.insert(logical.to_string(), tags);
+        }
+        Ok(())
+    }
+}
+
+/// A tag as stored, `None` for blank ones
+pub(crate) fn normalize(tag: &str) -> Option<String> {
+    Some(tag.trim().to_lowercase()).filter(|tag| !tag.is_empty())
+}
+
+fn tags_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("server_tags.json"))
+}
+
+fn read_tags() -> Result<ServerTags, String> {
+    let path = tags_path()?;
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse server tags: {}", e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(ServerTags::default()),
+        Err(e) => Err(format!("Failed to read server tags: {}", e)),
+    }
+}
+
+fn write_tags(tags: &ServerTags) -> Result<(), String> {
+    let path = tags_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(tags)
+        .map_err(|e| format!("Failed to serialize server tags: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write server tags: {}", e))
+}
+
+/// The tags, empty when they can't be read
+pub(crate) fn load() -> ServerTags {
+    read_tags().unwrap_or_else(|e| {
+        println!("[Tags] {}", e);
+        ServerTags::default()
+    })
+}
+
+#[command]
+pub async fn list_server_tags() -> Result<ServerTags, String> {
+    read_tags()
+}
+
+/// Replace the tags of a server; `name` is its logical name, or its name in `client` when
+/// given. No tags clear them.
+#[command]
+pub async fn set_server_tags(
+    name: String,
+    client: Option<String>,
+    tags: Vec<String>,
+) -> Result<ServerTags, String> {
+    let logical = match client {
+        Some(client) => server_aliases::load().logical_name(&client, &name),
+        None => name,
+    };
+    let _guard = LOCK
+        .lock()
+        .map_err(|e| format!("Failed to lock server tags: {}", e))?;
+    let mut stored = read_tags()?;
+    stored.set(&logical, &tags)?;
+    write_tags(&stored)?;
+    Ok(stored)
+}
@@ -0,0 +1,277 @@
+//! Server startup and tool-call timeouts
+//!
+//! Clients spell these differently: Codex has per-server `startup_timeout_sec` and
+//! `tool_timeout_sec`, Cline and Roo Code a per-server `timeout` in seconds, and Claude Code
+//! only the `MCP_TIMEOUT` / `MCP_TOOL_TIMEOUT` environment variables (milliseconds), which
+//! apply to every server of a scope. Timeouts are exposed here in milliseconds and converted
+//! on write.
+
+use crate::claude_code_commands::GLOBAL_PROJECT_ID;
+use crate::claude_settings::{self, SettingsScope};
+use crate::client_target::{self, ClientTarget};
+use crate::codex;
+use crate::op_recorder::{self, RecordedOp};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::command;
+
+const MIN_TIMEOUT_MS: u64 = 100;
+const MAX_TIMEOUT_MS: u64 = 60 * 60 * 1000;
+/// Cline rejects `timeout` outside 1..=3600 seconds
+const CLINE_MAX_TIMEOUT_SECS: u64 = 3600;
+
+const CLAUDE_STARTUP_ENV: &str = "MCP_TIMEOUT";
+const CLAUDE_TOOL_ENV: &str = "MCP_TOOL_TIMEOUT";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ServerTimeouts {
+    /// Time allowed for the server to start and answer `initialize`
+    #[serde(default)]
+    pub startup_ms: Option<u64>,
+    /// Time allowed for a single tool call
+    #[serde(default)]
+    pub tool_ms: Option<u64>,
+}
+
+/// Timeouts as entered by the user: a number of milliseconds or a string with a unit
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct TimeoutInput {
+    #[serde(default)]
+    pub startup: Option<Value>,
+    #[serde(default)]
+    pub tool: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TimeoutSupport {
+    pub client: String,
+    pub startup: bool,
+    pub tool: bool,
+    /// False when the client only has one setting for all servers of a scope
+    pub per_server: bool,
+    /// Where the values are written, for display
+    pub fields: Vec<&'static str>,
+}
+
+pub(crate) fn support(client: &str) -> Option<TimeoutSupport> {
+    let (startup, tool, per_server, fields) = match client {
+        "codex" => (
+            true,
+            true,
+            true,
+            vec!["startup_timeout_sec", "tool_timeout_sec"],
+        ),
+        "cline" | "roo_code" => (false, true, true, vec!["timeout"]),
+        "claude_code" => (
+            true,
+            true,
+            false,
+            vec!["env.MCP_TIMEOUT", "env.MCP_TOOL_TIMEOUT"],
+        ),
+        _ => return None,
+    };
+    Some(TimeoutSupport {
+        client: client.to_string(),
+        startup,
+        tool,
+        per_server,
+        fields,
+    })
+}
+
+/// Parse `1500`, `"1500ms"`, `"30s"` or `"2m"` into milliseconds. A bare number is taken as
+/// milliseconds, and rejected when it is small enough that seconds were probably meant.
+pub(crate) fn parse_timeout_ms(input: &Value) -> Result<u64, String> {
+    let (amount, unit) = match input {
+        Value::Number(n) => (n.as_f64().unwrap_or(-1.0), ""),
+        Value::String(s) => {
+            let s = s.trim();
+            let split = s
+                .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+                .unwrap_or(s.len());
+            let amount = s[..split]
+                .parse::<f64>()
+                .map_err(|_| format!("Invalid timeout '{}'", s))?;
+            (amount, s[split..].trim())
+        }
+        _ => return Err("Timeout must be a number or a string like \"30s\"".to_string()),
+    };
+    let ms = match unit {
+        "" | "ms" => amount,
+        "s" | "sec" => amount * 1000.0,
+        "m" | "min" => amount * 60_000.0,
+        other => return Err(format!("Unknown timeout unit '{}'; use ms, s or m", other)),
+    };
+    if unit.is_empty() && ms > 0.0 && ms < 1000.0 {
+        return Err(format!(
+            "Timeout {} is in milliseconds; write \"{}s\" for seconds or \"{}ms\" to confirm",
+            amount, amount, amount
+        ));
+    }
+    if !(MIN_TIMEOUT_MS as f64..=MAX_TIMEOUT_MS as f64).contains(&ms) {
+        return Err(format!(
+            "Timeout must be between {}ms and {} minutes",
+            MIN_TIMEOUT_MS,
+            MAX_TIMEOUT_MS / 60_000
+        ));
+    }
+    Ok(ms.round() as u64)
+}
+
+fn parse_input(input: &TimeoutInput) -> Result<ServerTimeouts, String> {
+    let parse = |v: &Option<Value>| match v {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(s)) if s.trim().is_empty() => Ok(None),
+        Some(v) => parse_timeout_ms(v).map(Some),
+    };
+    Ok(ServerTimeouts {
+        startup_ms: parse(&input.startup)?,
+        tool_ms: parse(&input.tool)?,
+    })
+}
+
+fn seconds(ms: Option<u64>) -> Option<f64> {
+    ms.map(|ms| ms as f64 / 1000.0)
+}
+
+fn millis(seconds: Option<f64>) -> Option<u64> {
+    seconds.map(|s| (s * 1000.0).round() as u64)
+}
+
+/// User scope for Claude Code servers, otherwise the project's git-ignored local settings
+fn claude_settings_path(target: &ClientTarget) -> Result<std::path::PathBuf, String> {
+    if target.working_dir() == GLOBAL_PROJECT_ID {
+        claude_settings::settings_path(SettingsScope::User, None)
+    } else {
+        claude_settings::settings_path(SettingsScope::Local, Some(target.working_dir()))
+    }
+}
+
+async fn server_entry(target: &ClientTarget, name: &str) -> Result<Value, String> {
+    client_target::list_servers(target)
+        .await?
+        .get(name)
+        .cloned()
+        .ok_or_else(|| format!("Server '{}' not found", name))
+}
+
+fn require_name(name: Option<String>) -> Result<String, String> {
+    name.filter(|n| !n.is_empty())
+        .ok_or_else(|| "Server name is required for this client".to_string())
+}
+
+#[command]
+pub async fn get_timeout_support(client: String) -> Result<Option<TimeoutSupport>, String> {
+    Ok(support(&client))
+}
+
+/// Current timeouts. `name` is ignored for Claude Code, whose timeouts cover the whole scope.
+#[command]
+pub async fn get_server_timeouts(
+    target: ClientTarget,
+    name: Option<String>,
+) -> Result<ServerTimeouts, String> {
+    match target.client.as_str() {
+        "claude_code" => {
+            let settings = claude_settings::read_settings(&claude_settings_path(&target)?)?;
+            let env = |key: &str| {
+                settings
+                    .get("env")
+                    .and_then(|env| env.get(key))
+                    .and_then(|v| v.as_str())
+                    .and_then(|v| v.trim().parse().ok())
+            };
+            Ok(ServerTimeouts {
+                startup_ms: env(CLAUDE_STARTUP_ENV),
+                tool_ms: env(CLAUDE_TOOL_ENV),
+            })
+        }
+        "codex" => {
+            let entry = server_entry(&target, &require_name(name)?).await?;
+            Ok(ServerTimeouts {
+                startup_ms: millis(entry["startup_timeout_sec"].as_f64()),
+                tool_ms: millis(entry["tool_timeout_sec"].as_f64()),
+            })
+        }
+        "cline" | "roo_code" => {
+            let entry = server_entry(&target, &require_name(name)?).await?;
+            Ok(ServerTimeouts {
+                startup_ms: None,
+                tool_ms: millis(entry["timeout"].as_f64()),
+            })
+        }
+        other => Err(format!("{} has no timeout settings", other)),
+    }
+}
+
+/// Replace both timeouts; an empty or missing value clears that timeout
+#[command]
+pub async fn set_server_timeouts(
+    target: ClientTarget,
+    name: Option<String>,
+    timeouts: TimeoutInput,
+) -> Result<ServerTimeouts, String> {
+    let support = support(&target.client)
+        .ok_or_else(|| format!("{} has no timeout settings", target.client))?;
+    let mut parsed = parse_input(&timeouts)?;
+    if parsed.startup_ms.is_some() && !support.startup {
+        return Err(format!("{} has no startup timeout", target.client));
+    }
+
+    match target.client.as_str() {
+        "claude_code" => {
+            let path = claude_settings_path(&target)?;
+            let mut settings = claude_settings::read_settings(&path)?;
+            let env = settings.entry("env").or_insert_with(|| json!({}));
+            let env = env
+                .as_object_mut()
+                .ok_or("'env' in Claude settings is not an object")?;
+            for (key, ms) in [
+                (CLAUDE_STARTUP_ENV, parsed.startup_ms),
+                (CLAUDE_TOOL_ENV, parsed.tool_ms),
+            ] {
+                match ms {
+                    Some(ms) => {
+                        env.insert(key.to_string(), json!(ms.to_string()));
+                    }
+                    None => {
+                        env.shift_remove(key);
+                    }
+                }
+            }
+            if env.is_empty() {
+                settings.shift_remove("env");
+            }
+            claude_settings::write_settings(&path, &settings)?;
+        }
+        "codex" => {
+            let name = require_name(name)?;
+            codex::set_timeouts(&name, seconds(parsed.startup_ms), seconds(parsed.tool_ms)).await?;
+            // Disabled Codex servers aren't listed, so there is nothing to record for them
+            if let Ok(config) = server_entry(&target, &name).await {
+                op_recorder::record(target, RecordedOp::UpsertServer { name, config });
+            }
+        }
+        _ => {
+            let name = require_name(name)?;
+            let mut config = server_entry(&target, &name).await?;
+            let entry = config
+                .as_object_mut()
+                .ok_or_else(|| format!("Server '{}' is not an object", name))?;
+            match parsed.tool_ms {
+                Some(ms) => {
+                    // Cline stores whole seconds
+                    let secs = ms.div_ceil(1000).clamp(1, CLINE_MAX_TIMEOUT_SECS);
+                    entry.insert("timeout".to_string(), json!(secs));
+                    parsed.tool_ms = Some(secs * 1000);
+                }
+                None => {
+                    entry.shift_remove("timeout");
+                }
+            }
+            client_target::upsert_server(&target, &name, config.clone()).await?;
+            op_recorder::record(target, RecordedOp::UpsertServer { name, config });
+        }
+    }
+    Ok(parsed)
+}
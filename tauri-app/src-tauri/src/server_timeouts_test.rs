@@ -0,0 +1,18 @@
+// Timeout unit parsing tests
+use crate::server_timeouts::parse_timeout_ms;
+use serde_json::json;
+
+#[test]
+fn test_parse_timeout_units() {
+    assert_eq!(parse_timeout_ms(&json!(15000)), Ok(15000));
+    assert_eq!(parse_timeout_ms(&json!("30s")), Ok(30000));
+    assert_eq!(parse_timeout_ms(&json!("1.5 s")), Ok(1500));
+    assert_eq!(parse_timeout_ms(&json!("2m")), Ok(120000));
+    assert_eq!(parse_timeout_ms(&json!("500ms")), Ok(500));
+    // A bare 30 is almost always meant as seconds
+    assert!(parse_timeout_ms(&json!(30)).is_err());
+    assert!(parse_timeout_ms(&json!("30")).is_err());
+    assert!(parse_timeout_ms(&json!("10h")).is_err());
+    assert!(parse_timeout_ms(&json!("90m")).is_err());
+    assert!(parse_timeout_ms(&json!(true)).is_err());
+}
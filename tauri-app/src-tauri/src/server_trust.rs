@@ -0,0 +1,264 @@
+//! Per-server auto-approval ("trust")
+//!
+//! Gemini CLI has `trust: true` (no confirmation for any tool), Cline `autoApprove` and Roo
+//! Code `alwaysAllow` (lists of tool names), and Claude Code permission rules in settings.json
+//! (`mcp__<server>` for every tool, `mcp__<server>__<tool>` for one). Claude Code rules are
+//! written to the user settings for global servers and to the project's git-ignored local
+//! settings otherwise, so trust is never committed by accident.
+//!
+//! Every change is written to the audit log together with the warnings returned to the caller.
+
+use crate::audit_log;
+use crate::claude_code_commands::GLOBAL_PROJECT_ID;
+use crate::claude_settings::{self, SettingsScope};
+use crate::client_target::{self, ClientTarget};
+use crate::op_recorder::{self, RecordedOp};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use tauri::command;
+
+/// Tool names containing these are called out when auto-approved
+const RISKY_TOOL_WORDS: [&str; 8] = [
+    "delete", "remove", "write", "exec", "run", "send", "push", "deploy",
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ServerTrust {
+    /// Every tool runs without confirmation
+    #[serde(default)]
+    pub all_tools: bool,
+    /// Tools that run without confirmation
+    #[serde(default)]
+    pub tools: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TrustChange {
+    pub trust: ServerTrust,
+    pub warnings: Vec<String>,
+}
+
+/// Target of the `client`/`scope` pair the trust commands take
+pub(crate) fn target_for(client: &str, scope: Option<&str>) -> ClientTarget {
+    if client == "claude_code" {
+        ClientTarget::claude_code(scope.unwrap_or(GLOBAL_PROJECT_ID))
+    } else {
+        ClientTarget::client(client, scope)
+    }
+}
+
+/// Config key of the per-server tool list
+fn tool_list_key(client: &str) -> Option<&'static str> {
+    match client {
+        "cline" => Some("autoApprove"),
+        "roo_code" => Some("alwaysAllow"),
+        _ => None,
+    }
+}
+
+pub(crate) fn trust_warnings(name: &str, trust: &ServerTrust) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if trust.all_tools {
+        warnings.push(format!(
+            "Every tool of '{}' will run without confirmation, including tools added by future \
+             server versions",
+            name
+        ));
+    }
+    let risky: Vec<&str> = trust
+        .tools
+        .iter()
+        .filter(|tool| {
+            let lower = tool.to_lowercase();
+            RISKY_TOOL_WORDS.iter().any(|w| lower.contains(w))
+        })
+        .map(|tool| tool.as_str())
+        .collect();
+    if !risky.is_empty() {
+        warnings.push(format!(
+            "Auto-approved tools that can change data or run code: {}",
+            risky.join(", ")
+        ));
+    }
+    warnings
+}
+
+fn claude_settings_path(target: &ClientTarget) -> Result<PathBuf, String> {
+    if target.working_dir() == GLOBAL_PROJECT_ID {
+        claude_settings::settings_path(SettingsScope::User, None)
+    } else {
+        claude_settings::settings_path(SettingsScope::Local, Some(target.working_dir()))
+    }
+}
+
+fn allow_rules(settings: &Map<String, Value>) -> Vec<String> {
+    settings
+        .get("permissions")
+        .and_then(|p| p.get("allow"))
+        .and_then(|a| a.as_array())
+        .map(|rules| {
+            rules
+                .iter()
+                .filter_map(|r| r.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Trust expressed by Claude Code allow rules for `name`
+pub(crate) fn trust_from_rules(name: &str, rules: &[String]) -> ServerTrust {
+    let prefix = format!("mcp__{}", name);
+    let mut trust = ServerTrust::default();
+    for rule in rules {
+        if *rule == prefix || *rule == format!("{}__*", prefix) {
+            trust.all_tools = true;
+        } else if let Some(tool) = rule.strip_prefix(&format!("{}__", prefix)) {
+            trust.tools.push(tool.to_string());
+        }
+    }
+    trust
+}
+
+/// Replace the allow rules for `name`, keeping every other rule in place
+pub(crate) fn apply_rules(name: &str, rules: &[String], trust: &ServerTrust) -> Vec<String> {
+    let prefix = format!("mcp__{}", name);
+    let tool_prefix = format!("{}__", prefix);
+    let mut kept: Vec<String> = rules
+        .iter()
+        .filter(|r| **r != prefix && !r.starts_with(&tool_prefix))
+        .cloned()
+        .collect();
+    if trust.all_tools {
+        kept.push(prefix);
+    } else {
+        kept.extend(trust.tools.iter().map(|t| format!("{}{}", tool_prefix, t)));
+    }
+    kept
+}
+
+async fn server_entry(target: &ClientTarget, name: &str) -> Result<Value, String> {
+    client_target::list_servers(target)
+        .await?
+        .get(name)
+        .cloned()
+        .ok_or_else(|| format!("Server '{}' not found", name))
+}
+
+async fn read_trust(target: &ClientTarget, name: &str) -> Result<ServerTrust, String> {
+    if target.is_claude_code() {
+        let settings = claude_settings::read_settings(&claude_settings_path(target)?)?;
+        return Ok(trust_from_rules(name, &allow_rules(&settings)));
+    }
+    let entry = server_entry(target, name).await?;
+    if target.client == "gemini" {
+        return Ok(ServerTrust {
+            all_tools: entry["trust"].as_bool().unwrap_or(false),
+            tools: Vec::new(),
+        });
+    }
+    let key = tool_list_key(&target.client)
+        .ok_or_else(|| format!("{} has no auto-approval setting", target.client))?;
+    Ok(ServerTrust {
+        all_tools: false,
+        tools: entry[key]
+            .as_array()
+            .map(|tools| {
+                tools
+                    .iter()
+                    .filter_map(|t| t.as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    })
+}
+
+#[command]
+pub async fn get_server_trust(
+    name: String,
+    client: String,
+    scope: Option<String>,
+) -> Result<ServerTrust, String> {
+    read_trust(&target_for(&client, scope.as_deref()), &name).await
+}
+
+/// Set which tools of a server run without confirmation. `scope` is the Claude Code project
+/// (default global) or the config path of a project-level client.
+#[command]
+pub async fn set_server_trust(
+    name: String,
+    client: String,
+    scope: Option<String>,
+    value: ServerTrust,
+) -> Result<TrustChange, String> {
+    let target = target_for(&client, scope.as_deref());
+    let mut trust = value;
+    let mut seen = HashSet::new();
+    trust
+        .tools
+        .retain(|t| !t.trim().is_empty() && seen.insert(t.clone()));
+
+    if target.is_claude_code() {
+        let path = claude_settings_path(&target)?;
+        let mut settings = claude_settings::read_settings(&path)?;
+        let rules = apply_rules(&name, &allow_rules(&settings), &trust);
+        let permissions = settings.entry("permissions").or_insert_with(|| json!({}));
+        let permissions = permissions
+            .as_object_mut()
+            .ok_or("'permissions' in Claude settings is not an object")?;
+        permissions.insert("allow".to_string(), json!(rules));
+        claude_settings::write_settings(&path, &settings)?;
+    } else {
+        let mut config = server_entry(&target, &name).await?;
+        let entry = config
+            .as_object_mut()
+            .ok_or_else(|| format!("Server '{}' is not an object", name))?;
+        if client == "gemini" {
+            if !trust.tools.is_empty() {
+                return Err("Gemini CLI can only trust all tools of a server".to_string());
+            }
+            if trust.all_tools {
+                entry.insert("trust".to_string(), json!(true));
+            } else {
+                entry.shift_remove("trust");
+            }
+        } else {
+            let key = tool_list_key(&client)
+                .ok_or_else(|| format!("{} has no auto-approval setting", client))?;
+            if trust.all_tools {
+                return Err(format!("{} needs the tools to auto-approve listed", client));
+            }
+            if trust.tools.is_empty() {
+                entry.shift_remove(key);
+            } else {
+                entry.insert(key.to_string(), json!(trust.tools));
+            }
+        }
+        client_target::upsert_server(&target, &name, config.clone()).await?;
+        op_recorder::record(
+            target.clone(),
+            RecordedOp::UpsertServer {
+                name: name.clone(),
+                config,
+            },
+        );
+    }
+
+    let warnings = trust_warnings(&name, &trust);
+    let detail = if trust.all_tools {
+        "all tools".to_string()
+    } else if trust.tools.is_empty() {
+        "none".to_string()
+    } else {
+        trust.tools.join(", ")
+    };
+    audit_log::log(
+        "set_trust",
+        &target,
+        Some(&name),
+        Some(detail),
+        warnings.clone(),
+    );
+    Ok(TrustChange { trust, warnings })
+}
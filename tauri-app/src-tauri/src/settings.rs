@@ -0,0 +1,396 @@
+//! Backend settings persisted to settings.json in the profile's app data dir
+//!
+//! Missing keys fall back to their defaults, so older settings files keep loading after new
+//! options are added. Settings saved through the app are also published on a watch channel:
+//! subsystems that hold on to settings (debouncers, watchers, log retention) follow it with
+//! `on_change` instead of keeping the values they started with.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::future::Future;
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::command;
+use tokio::sync::watch;
+
+/// What to do when an added server's name is already taken in the target scope
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictPolicy {
+    /// Refuse the add and leave the existing entry untouched
+    #[default]
+    Error,
+    /// Replace the existing entry
+    Overwrite,
+    /// Keep the existing entry and add the new one as `<name>-2`, `<name>-3`, ...
+    KeepBoth,
+}
+
+/// Opt-in read-only status page on 127.0.0.1
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct StatusServerSettings {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl Default for StatusServerSettings {
+    fn default() -> Self {
+        StatusServerSettings {
+            enabled: false,
+            port: 47821,
+        }
+    }
+}
+
+/// Limits for server processes the app spawns itself (see `server_sandbox`)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct SandboxSettings {
+    /// Programs that may be launched, by file name
+    pub allowed_commands: Vec<String>,
+    /// Memory cap in MiB (committed memory on Windows, data segment on unix); 0 for none
+    pub memory_limit_mb: u64,
+    /// CPU time cap in seconds; 0 for none
+    pub cpu_limit_secs: u64,
+    /// Test launches are killed, with their child processes, after this long
+    pub launch_timeout_secs: u64,
+}
+
+impl Default for SandboxSettings {
+    fn default() -> Self {
+        SandboxSettings {
+            allowed_commands: [
+                "npx", "bunx", "pnpm", "yarn", "node", "bun", "deno", "uvx", "uv", "pipx",
+                "python", "python3", "docker", "podman",
+            ]
+            .iter()
+            .map(|c| c.to_string())
+            .collect(),
+            memory_limit_mb: 0,
+            cpu_limit_secs: 300,
+            launch_timeout_secs: 30,
+        }
+    }
+}
+
+impl SandboxSettings {
+    pub(crate) fn launch_timeout(&self) -> Duration {
+        Duration::from_secs(self.launch_timeout_secs.max(1))
+    }
+}
+
+/// Backoff between attempts of a failed request
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// Attempts including the first one; 1 disables retries
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled for each further one
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 4,
+            initial_backoff_ms: 2000,
+            max_backoff_ms: 30_000,
+        }
+    }
+}
+
+/// Timeouts and retries of the app's own HTTP requests (see `network`)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct NetworkSettings {
+    pub connect_timeout_secs: u64,
+    /// Time allowed for a whole request, including the response body
+    pub request_timeout_secs: u64,
+    pub retry: RetryPolicy,
+}
+
+impl Default for NetworkSettings {
+    fn default() -> Self {
+        NetworkSettings {
+            connect_timeout_secs: 10,
+            request_timeout_secs: 15,
+            retry: RetryPolicy::default(),
+        }
+    }
+}
+
+/// Debounce window for events under a file or directory
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PathDebounce {
+    /// Absolute, or relative to the home directory with a leading `~/`
+    pub path: String,
+    pub debounce_ms: u64,
+}
+
+/// Coalescing of file-watch events (see `watch_debounce`)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct WatchSettings {
+    /// Quiet time after the last event for a path before it is delivered; 0 delivers every
+    /// event as it comes
+    pub debounce_ms: u64,
+    /// A path that never goes quiet is still delivered this often
+    pub max_wait_ms: u64,
+    /// Windows for particular paths; the most specific one wins over `debounce_ms`
+    pub paths: Vec<PathDebounce>,
+}
+
+impl Default for WatchSettings {
+    fn default() -> Self {
+        WatchSettings {
+            debounce_ms: 200,
+            max_wait_ms: 2000,
+            // Claude Code rewrites its whole config several times per change
+            paths: vec![PathDebounce {
+                path: "~/.claude.json".to_string(),
+                debounce_ms: 500,
+            }],
+        }
+    }
+}
+
+/// A WASM module that turns pasted text of some format into servers (see `transform_plugins`)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct TransformPlugin {
+    pub id: String,
+    pub name: String,
+    /// Path of the `.wasm` file
+    pub path: String,
+    /// Formats or file extensions it handles, shown in the import dialog
+    #[serde(default)]
+    pub formats: Vec<String>,
+}
+
+/// Registered transform plugins and the limits every run is held to
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct TransformSettings {
+    pub plugins: Vec<TransformPlugin>,
+    /// Instructions a run may execute before it is stopped
+    pub max_fuel: u64,
+    pub memory_limit_mb: u64,
+    pub max_input_kb: u64,
+}
+
+impl Default for TransformSettings {
+    fn default() -> Self {
+        TransformSettings {
+            plugins: Vec::new(),
+            max_fuel: 500_000_000,
+            memory_limit_mb: 64,
+            max_input_kb: 1024,
+        }
+    }
+}
+
+/// How test sessions answer a server's `sampling/createMessage` (see `sampling`)
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SamplingMode {
+    /// Sampling isn't advertised
+    Off,
+    /// Reply with `canned_response`
+    #[default]
+    Canned,
+    /// Forward to the Anthropic Messages API; billed to the configured key
+    Api,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct SamplingSettings {
+    pub mode: SamplingMode,
+    pub canned_response: String,
+    /// Id of the API key in the secrets store; `api` mode does nothing without it
+    pub api_key_secret: Option<String>,
+    pub api_url: String,
+    pub model: String,
+    /// Upper bound on the tokens a server may ask for
+    pub max_tokens: u32,
+}
+
+impl Default for SamplingSettings {
+    fn default() -> Self {
+        SamplingSettings {
+            mode: SamplingMode::Canned,
+            canned_response: "Sampled response from MCP Linker's test client.".to_string(),
+            api_key_secret: None,
+            api_url: "https://api.anthropic.com/v1/messages".to_string(),
+            model: "claude-sonnet-4-5".to_string(),
+            max_tokens: 1024,
+        }
+    }
+}
+
+/// What counts as a secret when configs are exported, remembered or logged (see `redaction`)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+#[serde(default)]
+pub struct RedactionSettings {
+    /// Regexes for env var, header and flag names that hold secrets, on top of the built-in
+    /// markers; matched case-insensitively
+    pub key_patterns: Vec<String>,
+    /// Regexes for secret values, redacted wherever they appear in args and logged text
+    pub value_patterns: Vec<String>,
+    /// Names that are never secret even though they match, e.g. `AUTH_MODE`
+    pub allow_keys: Vec<String>,
+}
+
+/// Size caps of the app's own logs in the app data dir (see `log_rotation`)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct LogRetentionSettings {
+    /// A log is rotated before the write that would take it past this; 0 never rotates
+    pub max_file_kb: u64,
+    /// Rotated files kept per log; older ones are deleted, and with 0 a full log starts over
+    pub max_rotated_files: usize,
+    /// All logs together, live files included; the oldest rotated files are deleted first.
+    /// 0 leaves the total uncapped
+    pub max_total_kb: u64,
+}
+
+impl Default for LogRetentionSettings {
+    fn default() -> Self {
+        LogRetentionSettings {
+            max_file_kb: 1024,
+            max_rotated_files: 5,
+            max_total_kb: 8 * 1024,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct AppSettings {
+    pub conflict_policy: ConflictPolicy,
+    pub status_server: StatusServerSettings,
+    pub sandbox: SandboxSettings,
+    pub network: NetworkSettings,
+    pub watch: WatchSettings,
+    pub transforms: TransformSettings,
+    pub sampling: SamplingSettings,
+    pub redaction: RedactionSettings,
+    pub log_retention: LogRetentionSettings,
+    /// Test-launch newly added servers and cache their tool lists (see `server_metadata`)
+    pub warm_up_after_add: bool,
+    /// Annotate written servers with `"_managedBy": "mcp-linker"` in clients that keep unknown
+    /// keys (see `ownership_marks`)
+    pub write_ownership_markers: bool,
+    /// Ed25519 public keys (base64) whose role bundles are accepted (see `role_presets`)
+    pub trusted_role_keys: Vec<String>,
+    /// After each Claude Code write, check it with `claude mcp get` (see `claude_cli_verify`)
+    pub verify_with_claude_cli: bool,
+    /// Claude Code config in use, "native" or "wsl:<distro>" (see `claude_location`)
+    pub claude_config_location: Option<String>,
+    /// Language of catalog entries, e.g. "de" or "pt-BR"; English when unset or untranslated
+    /// (see `catalog_locale`)
+    pub locale: Option<String>,
+}
+
+/// ~/.config/mcplinker, shared with the mcplinker server history
+pub(crate) fn base_config_dir() -> Result<PathBuf, String> {
+    let home_dir = crate::demo_mode::home_dir().ok_or("Unable to find home directory")?;
+    Ok(home_dir.join(".config").join("mcplinker"))
+}
+
+/// App data dir of the active profile; `base_config_dir` for the default profile
+pub(crate) fn app_config_dir() -> Result<PathBuf, String> {
+    crate::profile::profile_dir()
+}
+
+/// Settings as last loaded or saved; loaded on first use, after the profile is chosen
+static CURRENT: Lazy<watch::Sender<AppSettings>> =
+    Lazy::new(|| watch::Sender::new(load_settings()));
+
+fn settings_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("settings.json"))
+}
+
+/// Load settings, falling back to defaults when the file is missing or unreadable
+pub(crate) fn load_settings() -> AppSettings {
+    let Ok(path) = settings_path() else {
+        return AppSettings::default();
+    };
+    let Ok(content) = fs::read_to_string(&path) else {
+        return AppSettings::default();
+    };
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        println!("[Settings] Ignoring invalid {}: {}", path.display(), e);
+        AppSettings::default()
+    })
+}
+
+pub(crate) fn save_settings(settings: &AppSettings) -> Result<(), String> {
+    let path = settings_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create settings directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write settings: {}", e))?;
+    publish(settings);
+    Ok(())
+}
+
+/// Make `settings` current; false when they already were
+fn publish(settings: &AppSettings) -> bool {
+    CURRENT.send_if_modified(|current| {
+        if current == settings {
+            return false;
+        }
+        *current = settings.clone();
+        true
+    })
+}
+
+/// The current settings, without reading settings.json
+pub(crate) fn current() -> AppSettings {
+    CURRENT.borrow().clone()
+}
+
+/// A receiver marked changed whenever saved settings differ from the previous ones
+pub(crate) fn subscribe() -> watch::Receiver<AppSettings> {
+    CURRENT.subscribe()
+}
+
+/// Run `apply` with the part of the settings `select` picks each time that part changes,
+/// for as long as the app runs. Not run for the settings current at the call.
+pub(crate) fn on_change<T, F, Fut>(select: fn(&AppSettings) -> T, apply: F)
+where
+    T: PartialEq + Clone + Send + 'static,
+    F: Fn(T) -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let mut receiver = subscribe();
+    tauri::async_runtime::spawn(async move {
+        let mut last = select(&receiver.borrow_and_update());
+        while receiver.changed().await.is_ok() {
+            let next = select(&receiver.borrow_and_update());
+            if next != last {
+                last = next.clone();
+                apply(next).await;
+            }
+        }
+    });
+}
+
+#[command]
+pub async fn get_settings() -> Result<AppSettings, String> {
+    Ok(load_settings())
+}
+
+#[command]
+pub async fn update_settings(settings: AppSettings) -> Result<AppSettings, String> {
+    crate::redaction::Rules::compile(&settings.redaction)?;
+    save_settings(&settings)?;
+    crate::status_page::apply_settings(&settings.status_server).await?;
+    Ok(settings)
+}
@@ -0,0 +1,374 @@
+//! Integrity check run at startup
+//!
+//! Client configs, their backups and the app's own stores are parsed, the server metadata
+//! cache is checked against what is configured, and the write journal is checked for
+//! interrupted operations. Only what can be rebuilt is repaired: unreadable caches are moved
+//! aside (as `<file>.corrupt.<timestamp>`), stale metadata entries are dropped, and journal
+//! records whose writes all landed are cleared. Everything else is left as found and
+//! reported. The report is published as a `startup_report` event and kept for `get_startup_report`.
+
+use crate::claude_code_commands::get_claude_config_path;
+use crate::client::ClientConfig;
+use crate::client_target::{self, ClientTarget, JSON_CLIENTS};
+use crate::event_bus::{self, AppEventKind, EventScope};
+use crate::settings::app_config_dir;
+use crate::{app_state, claude_disabled, server_metadata, unicode_path, write_journal};
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{command, AppHandle, Runtime};
+
+/// App data files that are only caches and can be rebuilt
+const CACHES: [&str; 4] = [
+    "server_metadata.json",
+    "registry_cache.json",
+    "package_metadata.json",
+    "advisories.json",
+];
+
+static LAST_REPORT: Mutex<Option<StartupReport>> = Mutex::new(None);
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckArea {
+    ClientConfig,
+    Backup,
+    AppStore,
+    Cache,
+    Metadata,
+    Journal,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueOutcome {
+    Repaired,
+    NeedsAttention,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct StartupIssue {
+    pub area: CheckArea,
+    /// File path, or the journal record id
+    pub subject: String,
+    pub outcome: IssueOutcome,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct StartupReport {
+    pub checked_at: String,
+    /// Number of files and records looked at
+    pub checked: usize,
+    pub issues: Vec<StartupIssue>,
+}
+
+impl StartupReport {
+    fn issue(&mut self, area: CheckArea, subject: String, outcome: IssueOutcome, message: String) {
+        self.issues.push(StartupIssue {
+            area,
+            subject,
+            outcome,
+            message,
+        });
+    }
+
+    pub fn needs_attention(&self) -> usize {
+        self.issues
+            .iter()
+            .filter(|i| i.outcome == IssueOutcome::NeedsAttention)
+            .count()
+    }
+}
+
+fn parse_json(content: &str) -> Result<(), String> {
+    serde_json::from_str::<serde_json::Value>(content)
+        .map(|_| ())
+        .map_err(|e| format!("Invalid JSON: {}", e))
+}
+
+fn parse_toml(content: &str) -> Result<(), String> {
+    content
+        .parse::<toml_edit::DocumentMut>()
+        .map(|_| ())
+        .map_err(|e| format!("Invalid TOML: {}", e))
+}
+
+/// Parse the file at `path`; `None` when it doesn't exist
+fn check_file(path: &Path, parse: fn(&str) -> Result<(), String>) -> Option<Result<(), String>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Some(parse(&content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => Some(Err(format!("Failed to read: {}", e))),
+    }
+}
+
+/// Backups `create_backup` left next to `path`, newest first
+pub(crate) fn backups_of(path: &Path) -> Vec<PathBuf> {
+    let Some(entries) = path.parent().and_then(|dir| fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+    let mut backups: Vec<(std::time::SystemTime, PathBuf)> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|candidate| unicode_path::is_backup_of(path, candidate))
+        .filter_map(|candidate| Some((fs::metadata(&candidate).ok()?.modified().ok()?, candidate)))
+        .collect();
+    backups.sort_by(|a, b| b.0.cmp(&a.0));
+    backups.into_iter().map(|(_, path)| path).collect()
+}
+
+fn check_client_configs(report: &mut StartupReport, claude_path: Option<PathBuf>) {
+    let mut configs: Vec<(PathBuf, fn(&str) -> Result<(), String>)> = JSON_CLIENTS
+        .iter()
+        .map(|client| {
+            let path = ClientConfig::new(client, None).get_path().to_path_buf();
+            (path, parse_json as fn(&str) -> Result<(), String>)
+        })
+        .collect();
+    if let Some(path) = claude_path {
+        configs.push((path, parse_json));
+    }
+    if let Ok(path) = crate::config::get_config_path() {
+        configs.push((path, parse_toml));
+    }
+
+    for (path, parse) in configs {
+        let Some(result) = check_file(&path, parse) else {
+            continue;
+        };
+        report.checked += 1;
+        let backups = backups_of(&path);
+        for backup in &backups {
+            report.checked += 1;
+            if let Some(Err(e)) = check_file(backup, parse) {
+                report.issue(
+                    CheckArea::Backup,
+                    backup.display().to_string(),
+                    IssueOutcome::NeedsAttention,
+                    format!("Backup can't be used for a restore: {}", e),
+                );
+            }
+        }
+        if let Err(e) = result {
+            let usable = backups
+                .iter()
+                .find(|backup| matches!(check_file(backup, parse), Some(Ok(()))));
+            let hint = match usable {
+                Some(backup) => format!("; a readable backup is at {}", backup.display()),
+                None => String::new(),
+            };
+            report.issue(
+                CheckArea::ClientConfig,
+                path.display().to_string(),
+                IssueOutcome::NeedsAttention,
+                format!("{}{}", e, hint),
+            );
+        }
+    }
+
+    report.checked += 1;
+    if let Err(e) = claude_disabled::read_disabled_file() {
+        report.issue(
+            CheckArea::ClientConfig,
+            "~/.claude.disabled.json".to_string(),
+            IssueOutcome::NeedsAttention,
+            e,
+        );
+    }
+}
+
+/// Move an unreadable cache out of the way so it is rebuilt
+fn set_aside(path: &Path) -> Result<PathBuf, String> {
+    let aside = path.with_file_name(format!(
+        "{}.corrupt.{}",
+        path.file_name().unwrap_or_default().to_string_lossy(),
+        Utc::now().timestamp()
+    ));
+    fs::rename(path, &aside).map_err(|e| format!("Failed to move it aside: {}", e))?;
+    Ok(aside)
+}
+
+fn check_app_files(report: &mut StartupReport) -> Result<(), String> {
+    let dir = app_config_dir()?;
+    for name in app_state::STATE_FILES.into_iter().chain(["secrets.json"]) {
+        let path = dir.join(name);
+        if let Some(result) = check_file(&path, parse_json) {
+            report.checked += 1;
+            if let Err(e) = result {
+                report.issue(
+                    CheckArea::AppStore,
+                    path.display().to_string(),
+                    IssueOutcome::NeedsAttention,
+                    e,
+                );
+            }
+        }
+    }
+    for name in CACHES {
+        let path = dir.join(name);
+        let Some(result) = check_file(&path, parse_json) else {
+            continue;
+        };
+        report.checked += 1;
+        if let Err(e) = result {
+            match set_aside(&path) {
+                Ok(aside) => report.issue(
+                    CheckArea::Cache,
+                    path.display().to_string(),
+                    IssueOutcome::Repaired,
+                    format!("{}; moved to {} and will be rebuilt", e, aside.display()),
+                ),
+                Err(move_error) => report.issue(
+                    CheckArea::Cache,
+                    path.display().to_string(),
+                    IssueOutcome::NeedsAttention,
+                    format!("{}; {}", e, move_error),
+                ),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Active and disabled server names of every target that could be listed
+async fn configured_servers() -> HashMap<ClientTarget, HashSet<String>> {
+    let mut configured = HashMap::new();
+    for target in client_target::known_targets().await {
+        let (Ok(active), Ok(disabled)) = (
+            client_target::list_servers(&target).await,
+            client_target::list_disabled_servers(&target).await,
+        ) else {
+            continue;
+        };
+        let names = active.keys().chain(disabled.keys()).cloned().collect();
+        configured.insert(target, names);
+    }
+    configured
+}
+
+async fn check_metadata(report: &mut StartupReport) {
+    report.checked += 1;
+    match server_metadata::prune(&configured_servers().await) {
+        Ok(0) => {}
+        Ok(removed) => report.issue(
+            CheckArea::Metadata,
+            "server_metadata.json".to_string(),
+            IssueOutcome::Repaired,
+            format!(
+                "Removed {} duplicate or stale server metadata entries",
+                removed
+            ),
+        ),
+        Err(e) => report.issue(
+            CheckArea::Metadata,
+            "server_metadata.json".to_string(),
+            IssueOutcome::NeedsAttention,
+            e,
+        ),
+    }
+}
+
+fn check_journal(report: &mut StartupReport) {
+    let (dropped, interrupted) = match write_journal::drop_landed() {
+        Ok(result) => result,
+        Err(e) => {
+            report.issue(
+                CheckArea::Journal,
+                "write_journal.json".to_string(),
+                IssueOutcome::NeedsAttention,
+                e,
+            );
+            return;
+        }
+    };
+    report.checked += dropped + interrupted.len();
+    if dropped > 0 {
+        report.issue(
+            CheckArea::Journal,
+            "write_journal.json".to_string(),
+            IssueOutcome::Repaired,
+            format!("Cleared {} record(s) of writes that had completed", dropped),
+        );
+    }
+    for operation in interrupted {
+        report.issue(
+            CheckArea::Journal,
+            operation.entry.id.clone(),
+            IssueOutcome::NeedsAttention,
+            format!(
+                "'{}' was interrupted ({} of {} steps, {} file(s) touched); complete or roll \
+                 it back",
+                operation.entry.label,
+                operation.entry.completed,
+                operation.entry.steps.len(),
+                operation.entry.files.len()
+            ),
+        );
+    }
+}
+
+pub(crate) async fn run() -> StartupReport {
+    let mut report = StartupReport {
+        checked_at: Utc::now().to_rfc3339(),
+        checked: 0,
+        issues: Vec::new(),
+    };
+    // Journal first: completed records would otherwise be reported as interrupted
+    check_journal(&mut report);
+    check_client_configs(&mut report, get_claude_config_path(None).await.ok());
+    if let Err(e) = check_app_files(&mut report) {
+        report.issue(
+            CheckArea::AppStore,
+            "app data".to_string(),
+            IssueOutcome::NeedsAttention,
+            e,
+        );
+    }
+    check_metadata(&mut report).await;
+    report
+}
+
+/// Run the check in the background and publish `startup_report`
+pub fn spawn_startup_check<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        let report = run().await;
+        println!(
+            "[Startup] Checked {} item(s): {} repaired, {} need attention",
+            report.checked,
+            report.issues.len() - report.needs_attention(),
+            report.needs_attention()
+        );
+        event_bus::publish(
+            &app,
+            AppEventKind::StartupReport,
+            EventScope::none(),
+            &report,
+        );
+        if let Ok(mut last) = LAST_REPORT.lock() {
+            *last = Some(report);
+        }
+    });
+}
+
+/// The report of the check run at startup, `None` while it is still running
+#[command]
+pub async fn get_startup_report() -> Result<Option<StartupReport>, String> {
+    Ok(LAST_REPORT
+        .lock()
+        .map_err(|e| format!("Failed to lock startup report: {}", e))?
+        .clone())
+}
+
+/// Run the check again, e.g. after fixing what it reported
+#[command]
+pub async fn run_startup_check() -> Result<StartupReport, String> {
+    let report = run().await;
+    if let Ok(mut last) = LAST_REPORT.lock() {
+        *last = Some(report.clone());
+    }
+    Ok(report)
+}
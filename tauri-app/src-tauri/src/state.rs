@@ -1,5 +1,5 @@
 use notify::RecommendedWatcher;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -20,3 +20,25 @@ impl Default for WatchState {
         Self::new()
     }
 }
+
+/// Watcher on ~/.claude.json shared by all project list subscribers
+pub struct ProjectWatchState {
+    pub watcher: Arc<Mutex<Option<(RecommendedWatcher, usize)>>>,
+    /// Project list last sent to the frontend
+    pub known: Arc<Mutex<BTreeSet<String>>>,
+}
+
+impl ProjectWatchState {
+    pub fn new() -> Self {
+        Self {
+            watcher: Arc::new(Mutex::new(None)),
+            known: Arc::new(Mutex::new(BTreeSet::new())),
+        }
+    }
+}
+
+impl Default for ProjectWatchState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
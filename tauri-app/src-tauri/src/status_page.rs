@@ -0,0 +1,514 @@
+//! Read-only status page for scripts and monitoring
+//!
+//! When enabled in settings, a small HTTP server on 127.0.0.1 serves `/status.json`, a
+//! minimal HTML view at `/` and Prometheus metrics at `/metrics`. It only ever reads: detected
+//! clients, server counts per scope, credential health, the newest config backup and the
+//! resource usage of running servers. Only `GET` and `HEAD` are accepted, and a connection that
+//! doesn't finish its request headers within `READ_TIMEOUT` is dropped.
+
+use crate::claude_code_commands::{get_claude_config_path, GLOBAL_PROJECT_ID};
+use crate::client::ClientConfig;
+use crate::client_target::JSON_CLIENTS;
+use crate::codex::McpServerConfig;
+use crate::credential_expiry::{self, CredentialStatus};
+use crate::json_manager::utils::{
+    get_key_by_client, is_cherrystudio_client, is_per_server_disabled_client,
+};
+use crate::mcp_processes::{self, ProcessUsage};
+use crate::settings::{self, StatusServerSettings};
+use crate::{
+    claude_disabled, client_reload, codex, config, config_backups, metrics, profile, unicode_path,
+};
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::command;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Mutex;
+
+/// Requests larger than this are rejected before parsing
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+/// Time a connection gets to send its request headers
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+static SERVER: Lazy<Mutex<Option<(u16, JoinHandle<()>)>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientState {
+    Ok,
+    /// No config file at the expected location
+    Missing,
+    /// The config file exists but can't be parsed
+    Invalid,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ScopeStatus {
+    pub scope: String,
+    pub active: usize,
+    pub disabled: usize,
+    pub credentials_expiring: usize,
+    pub credentials_expired: usize,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ClientStatus {
+    pub client: String,
+    pub path: String,
+    pub state: ClientState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub scopes: Vec<ScopeStatus>,
+    /// When the config was last backed up (see `config_backups`)
+    pub last_backup_at: Option<String>,
+    /// Changed since it last reloaded, as far as the app knows (see `client_reload`)
+    pub pending_reload: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct StatusReport {
+    pub generated_at: String,
+    pub profile: Option<String>,
+    pub clients: Vec<ClientStatus>,
+    pub expiring_credentials: usize,
+    /// Running MCP server process trees (see `mcp_processes`)
+    pub running_servers: Vec<ProcessUsage>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct StatusServerInfo {
+    pub running: bool,
+    pub url: Option<String>,
+}
+
+fn read_config(path: &Path) -> Result<Option<Value>, String> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map(Some)
+            .map_err(|e| format!("Failed to parse config: {}", e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("Failed to read config: {}", e)),
+    }
+}
+
+pub(crate) fn count_health(
+    scope: &mut ScopeStatus,
+    statuses: &HashMap<(Option<String>, String), CredentialStatus>,
+    working_dir: Option<&str>,
+    names: impl Iterator<Item = String>,
+) {
+    for name in names {
+        match statuses.get(&(working_dir.map(|d| d.to_string()), name)) {
+            Some(CredentialStatus::CredentialsExpiring) => scope.credentials_expiring += 1,
+            Some(CredentialStatus::CredentialsExpired) => scope.credentials_expired += 1,
+            None => {}
+        }
+    }
+}
+
+fn status_for(client: &str, path: PathBuf, state: ClientState) -> ClientStatus {
+    ClientStatus {
+        client: client.to_string(),
+        last_backup_at: config_backups::last_backup_at(&path),
+        path: path.display().to_string(),
+        state,
+        error: None,
+        scopes: Vec::new(),
+        pending_reload: client_reload::is_pending(client),
+    }
+}
+
+fn json_client_status(client: &str) -> Option<ClientStatus> {
+    let path = ClientConfig::new(client, None).get_path().to_path_buf();
+    if path.as_os_str().is_empty() {
+        return None;
+    }
+    let json = match read_config(&path) {
+        Ok(Some(json)) => json,
+        Ok(None) => return Some(status_for(client, path, ClientState::Missing)),
+        Err(e) => {
+            let mut status = status_for(client, path, ClientState::Invalid);
+            status.error = Some(e);
+            return Some(status);
+        }
+    };
+
+    let servers = json
+        .get(get_key_by_client(client))
+        .and_then(|v| v.as_object())
+        .cloned()
+        .unwrap_or_default();
+    let flagged_off = |cfg: &Value| {
+        (is_per_server_disabled_client(client)
+            && cfg.get("disabled").and_then(|v| v.as_bool()) == Some(true))
+            || (is_cherrystudio_client(client)
+                && cfg.get("isActive").and_then(|v| v.as_bool()) == Some(false))
+    };
+    let mut scope = ScopeStatus {
+        scope: GLOBAL_PROJECT_ID.to_string(),
+        active: servers.values().filter(|cfg| !flagged_off(cfg)).count(),
+        disabled: servers.values().filter(|cfg| flagged_off(cfg)).count()
+            + json
+                .get("__disabled")
+                .and_then(|v| v.as_object())
+                .map_or(0, |m| m.len()),
+        ..Default::default()
+    };
+    let statuses = credential_expiry::statuses_for_client(client);
+    count_health(&mut scope, &statuses, None, servers.keys().cloned());
+
+    let mut status = status_for(client, path, ClientState::Ok);
+    status.scopes.push(scope);
+    Some(status)
+}
+
+fn claude_code_status(path: PathBuf) -> ClientStatus {
+    let config = match read_config(&path) {
+        Ok(Some(config)) => config,
+        Ok(None) => return status_for("claude_code", path, ClientState::Missing),
+        Err(e) => {
+            let mut status = status_for("claude_code", path, ClientState::Invalid);
+            status.error = Some(e);
+            return status;
+        }
+    };
+    let disabled = claude_disabled::read_disabled_file().unwrap_or_default();
+    let statuses = credential_expiry::statuses_for_client("claude_code");
+
+    let mut scopes: Vec<(String, Option<&Value>)> =
+        vec![(GLOBAL_PROJECT_ID.to_string(), config.get("mcpServers"))];
+    if let Some(projects) = config.get("projects").and_then(|p| p.as_object()) {
+        for (dir, project) in projects {
+            scopes.push((dir.clone(), project.get("mcpServers")));
+        }
+    }
+
+    let mut status = status_for("claude_code", path, ClientState::Ok);
+    for (dir, servers) in scopes {
+        let servers = servers.and_then(|s| s.as_object());
+        let disabled_count = disabled
+            .pointer(&crate::json_pointer::join(&[
+                "projects",
+                &unicode_path::resolve_project_key(&disabled, &dir),
+            ]))
+            .and_then(|v| v.as_object())
+            .map_or(0, |m| m.len());
+        let active = servers.map_or(0, |s| s.len());
+        if active == 0 && disabled_count == 0 {
+            continue;
+        }
+        let mut scope = ScopeStatus {
+            scope: dir.clone(),
+            active,
+            disabled: disabled_count,
+            ..Default::default()
+        };
+        if let Some(servers) = servers {
+            count_health(&mut scope, &statuses, Some(&dir), servers.keys().cloned());
+        }
+        status.scopes.push(scope);
+    }
+    status
+}
+
+type CodexServers = Result<HashMap<String, McpServerConfig>, String>;
+
+fn codex_status(path: PathBuf, servers: CodexServers, disabled: CodexServers) -> ClientStatus {
+    if !path.exists() {
+        return status_for("codex", path, ClientState::Missing);
+    }
+    let (servers, disabled) = match (servers, disabled) {
+        (Ok(servers), Ok(disabled)) => (servers, disabled),
+        (Err(e), _) | (_, Err(e)) => {
+            let mut status = status_for("codex", path, ClientState::Invalid);
+            status.error = Some(e);
+            return status;
+        }
+    };
+    let mut status = status_for("codex", path, ClientState::Ok);
+    status.scopes.push(ScopeStatus {
+        scope: GLOBAL_PROJECT_ID.to_string(),
+        active: servers.len(),
+        disabled: disabled.len(),
+        ..Default::default()
+    });
+    status
+}
+
+pub(crate) async fn build_report() -> Result<StatusReport, String> {
+    let claude_path = get_claude_config_path(None).await?;
+    let codex_path = config::get_config_path()?;
+    let codex_servers = (
+        codex::read_mcp_servers().await,
+        codex::list_disabled().await,
+    );
+    // The rest reads config files, credential and backup records synchronously
+    let (clients, expiring_credentials) = tokio::task::spawn_blocking(move || {
+        let mut clients: Vec<ClientStatus> = JSON_CLIENTS
+            .iter()
+            .filter_map(|client| json_client_status(client))
+            .collect();
+        clients.push(claude_code_status(claude_path));
+        clients.push(codex_status(codex_path, codex_servers.0, codex_servers.1));
+        let expiring = credential_expiry::expiring_credentials()
+            .map(|c| c.len())
+            .unwrap_or(0);
+        (clients, expiring)
+    })
+    .await
+    .map_err(|e| format!("Failed to run blocking task for status report: {}", e))?;
+    Ok(StatusReport {
+        generated_at: Utc::now().to_rfc3339(),
+        profile: profile::active_profile().map(|p| p.to_string()),
+        clients,
+        expiring_credentials,
+        running_servers: mcp_processes::resource_usage().await,
+    })
+}
+
+pub(crate) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub(crate) fn render_html(report: &StatusReport) -> String {
+    let mut rows = String::new();
+    for client in &report.clients {
+        let state = match client.state {
+            ClientState::Ok => "ok",
+            ClientState::Missing => "missing",
+            ClientState::Invalid => "invalid",
+        };
+        let scopes = if client.scopes.is_empty() {
+            "-".to_string()
+        } else {
+            client
+                .scopes
+                .iter()
+                .map(|s| {
+                    format!(
+                        "{}: {} active, {} disabled{}",
+                        escape_html(&s.scope),
+                        s.active,
+                        s.disabled,
+                        if s.credentials_expired + s.credentials_expiring > 0 {
+                            format!(
+                                " ({} expired, {} expiring credentials)",
+                                s.credentials_expired, s.credentials_expiring
+                            )
+                        } else {
+                            String::new()
+                        }
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("<br>")
+        };
+        rows.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            escape_html(&client.client),
+            state,
+            scopes,
+            client.last_backup_at.as_deref().unwrap_or("-"),
+            escape_html(&client.path),
+        ));
+    }
+    let mut processes = String::new();
+    for usage in &report.running_servers {
+        let server = usage
+            .server
+            .as_ref()
+            .map(|s| format!("{} ({})", s.name, s.client))
+            .unwrap_or_else(|| usage.name.clone());
+        processes.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{:.1}%</td><td>{:.1} MiB</td><td>{}s</td>\
+             <td>{}</td></tr>\n",
+            escape_html(&server),
+            usage.pid,
+            usage.cpu_percent,
+            usage.memory_bytes as f64 / (1024.0 * 1024.0),
+            usage.uptime_secs,
+            if usage.orphaned { "orphaned" } else { "" },
+        ));
+    }
+    format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>MCP Linker status</title></head>\
+         <body><h1>MCP Linker status</h1><p>Generated {}{}; {} expiring credentials. \
+         <a href=\"/status.json\">JSON</a></p>\n<table border=\"1\" cellpadding=\"4\">\
+         <tr><th>Client</th><th>State</th><th>Servers</th><th>Last backup</th><th>Path</th></tr>\n\
+         {}</table>\n<h2>Running servers</h2>\n<table border=\"1\" cellpadding=\"4\">\
+         <tr><th>Server</th><th>PID</th><th>CPU</th><th>Memory</th><th>Uptime</th><th></th></tr>\n\
+         {}</table></body></html>\n",
+        report.generated_at,
+        report
+            .profile
+            .as_deref()
+            .map(|p| format!(" for profile {}", escape_html(p)))
+            .unwrap_or_default(),
+        report.expiring_credentials,
+        rows,
+        processes
+    )
+}
+
+/// (status line, content type, body) for a request path
+async fn route(path: &str) -> (&'static str, &'static str, String) {
+    let path = path.split('?').next().unwrap_or(path);
+    match path {
+        "/" | "/status" | "/status.json" => match build_report().await {
+            Ok(report) if path == "/status.json" => (
+                "200 OK",
+                "application/json",
+                serde_json::to_string_pretty(&report).unwrap_or_default(),
+            ),
+            Ok(report) => ("200 OK", "text/html; charset=utf-8", render_html(&report)),
+            Err(e) => ("500 Internal Server Error", "text/plain", e),
+        },
+        "/metrics" => match build_report().await {
+            Ok(report) => (
+                "200 OK",
+                "text/plain; version=0.0.4",
+                metrics::render(&report),
+            ),
+            Err(e) => ("500 Internal Server Error", "text/plain", e),
+        },
+        _ => ("404 Not Found", "text/plain", "Not found\n".to_string()),
+    }
+}
+
+/// Serve one request, giving up on clients that take longer than `read_timeout` to send it
+pub(crate) async fn handle_connection(
+    mut stream: TcpStream,
+    read_timeout: Duration,
+) -> std::io::Result<()> {
+    let mut buffer = vec![0u8; MAX_REQUEST_BYTES];
+    let mut read = 0;
+    let deadline = tokio::time::Instant::now() + read_timeout;
+    while !buffer[..read].windows(4).any(|w| w == b"\r\n\r\n") {
+        if read == buffer.len() {
+            return write_response(&mut stream, "431 Request Header Fields Too Large", "", "")
+                .await;
+        }
+        let n = match tokio::time::timeout_at(deadline, stream.read(&mut buffer[read..])).await {
+            Ok(n) => n?,
+            Err(_) => {
+                return write_response(&mut stream, "408 Request Timeout", "text/plain", "").await
+            }
+        };
+        if n == 0 {
+            return Ok(());
+        }
+        read += n;
+    }
+    let request = String::from_utf8_lossy(&buffer[..read]);
+    let mut parts = request.lines().next().unwrap_or("").split_whitespace();
+    let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or("/"));
+    if method != "GET" && method != "HEAD" {
+        return write_response(&mut stream, "405 Method Not Allowed", "text/plain", "").await;
+    }
+    // Refuse other Host names so a web page can't read this through DNS rebinding
+    let host_ok = request
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("host"))
+        .map(|(_, value)| value.trim())
+        .is_some_and(|host| {
+            let name = host.rsplit_once(':').map_or(host, |(name, _)| name);
+            name == "127.0.0.1" || name == "localhost"
+        });
+    if !host_ok {
+        return write_response(&mut stream, "403 Forbidden", "text/plain", "").await;
+    }
+    let (status, content_type, body) = route(path).await;
+    let body = if method == "HEAD" { "" } else { body.as_str() };
+    write_response(&mut stream, status, content_type, body).await
+}
+
+async fn write_response(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &str,
+) -> std::io::Result<()> {
+    let head = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\n\
+         Connection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+async fn start(port: u16) -> Result<(), String> {
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .await
+        .map_err(|e| format!("Failed to bind status page to 127.0.0.1:{}: {}", port, e))?;
+    let handle = tauri::async_runtime::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    tauri::async_runtime::spawn(async move {
+                        if let Err(e) = handle_connection(stream, READ_TIMEOUT).await {
+                            println!("[StatusPage] Connection error: {}", e);
+                        }
+                    });
+                }
+                Err(e) => println!("[StatusPage] Accept failed: {}", e),
+            }
+        }
+    });
+    *SERVER.lock().await = Some((port, handle));
+    println!("[StatusPage] Serving on http://127.0.0.1:{}/", port);
+    Ok(())
+}
+
+/// Start, stop or move the server to match `settings`
+pub(crate) async fn apply_settings(settings: &StatusServerSettings) -> Result<(), String> {
+    let running_port = SERVER.lock().await.as_ref().map(|(port, _)| *port);
+    if settings.enabled && running_port == Some(settings.port) {
+        return Ok(());
+    }
+    if let Some((port, handle)) = SERVER.lock().await.take() {
+        handle.abort();
+        println!("[StatusPage] Stopped server on port {}", port);
+    }
+    if settings.enabled {
+        start(settings.port).await?;
+    }
+    Ok(())
+}
+
+/// Start the server at launch if the setting is on
+pub fn spawn_status_page() {
+    tauri::async_runtime::spawn(async {
+        if let Err(e) = apply_settings(&settings::load_settings().status_server).await {
+            println!("[StatusPage] {}", e);
+        }
+    });
+}
+
+#[command]
+pub async fn get_status_report() -> Result<StatusReport, String> {
+    build_report().await
+}
+
+#[command]
+pub async fn get_status_server() -> Result<StatusServerInfo, String> {
+    let port = SERVER.lock().await.as_ref().map(|(port, _)| *port);
+    Ok(StatusServerInfo {
+        running: port.is_some(),
+        url: port.map(|port| format!("http://127.0.0.1:{}/", port)),
+    })
+}
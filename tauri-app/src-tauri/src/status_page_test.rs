@@ -0,0 +1,130 @@
+// Status page rendering and request handling tests
+use crate::credential_expiry::CredentialStatus;
+use crate::status_page::{
+    count_health, escape_html, handle_connection, render_html, ClientState, ClientStatus,
+    ScopeStatus, StatusReport,
+};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+fn report() -> StatusReport {
+    StatusReport {
+        generated_at: "2025-01-01T00:00:00Z".to_string(),
+        profile: Some("<work>".to_string()),
+        clients: vec![ClientStatus {
+            client: "cursor".to_string(),
+            path: "/home/a&b/.cursor/mcp.json".to_string(),
+            state: ClientState::Ok,
+            error: None,
+            scopes: vec![ScopeStatus {
+                scope: "Global".to_string(),
+                active: 2,
+                disabled: 1,
+                credentials_expiring: 1,
+                credentials_expired: 0,
+            }],
+            last_backup_at: None,
+            pending_reload: false,
+        }],
+        expiring_credentials: 1,
+        running_servers: Vec::new(),
+    }
+}
+
+/// Send `request` to the handler and return the response
+async fn respond(request: &str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        handle_connection(stream, Duration::from_secs(5))
+            .await
+            .unwrap();
+    });
+    let mut client = TcpStream::connect(addr).await.unwrap();
+    client.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    client.read_to_string(&mut response).await.unwrap();
+    server.await.unwrap();
+    response
+}
+
+#[test]
+fn test_escape_html() {
+    assert_eq!(
+        escape_html(r#"<a href="x">&</a>"#),
+        "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;"
+    );
+}
+
+#[test]
+fn test_render_html_escapes_report_values() {
+    let html = render_html(&report());
+    assert!(html.contains(" for profile &lt;work&gt;"));
+    assert!(html.contains("/home/a&amp;b/.cursor/mcp.json"));
+    assert!(html.contains("Global: 2 active, 1 disabled (0 expired, 1 expiring credentials)"));
+}
+
+#[test]
+fn test_count_health() {
+    let statuses = HashMap::from([
+        (
+            (None, "github".to_string()),
+            CredentialStatus::CredentialsExpired,
+        ),
+        (
+            (Some("/work".to_string()), "jira".to_string()),
+            CredentialStatus::CredentialsExpiring,
+        ),
+    ]);
+    let mut global = ScopeStatus::default();
+    let names = ["github", "jira", "fs"].map(|n| n.to_string());
+    count_health(&mut global, &statuses, None, names.clone().into_iter());
+    assert_eq!(
+        (global.credentials_expired, global.credentials_expiring),
+        (1, 0)
+    );
+
+    let mut project = ScopeStatus::default();
+    count_health(&mut project, &statuses, Some("/work"), names.into_iter());
+    assert_eq!(
+        (project.credentials_expired, project.credentials_expiring),
+        (0, 1)
+    );
+}
+
+#[tokio::test]
+async fn test_requests_are_restricted() {
+    let not_found = respond("GET /nope HTTP/1.1\r\nHost: 127.0.0.1:7777\r\n\r\n").await;
+    assert!(not_found.starts_with("HTTP/1.1 404 Not Found"));
+
+    let post = respond("POST /status.json HTTP/1.1\r\nHost: localhost\r\n\r\n").await;
+    assert!(post.starts_with("HTTP/1.1 405 Method Not Allowed"));
+
+    let rebound = respond("GET /status.json HTTP/1.1\r\nHost: evil.example\r\n\r\n").await;
+    assert!(rebound.starts_with("HTTP/1.1 403 Forbidden"));
+
+    let no_host = respond("GET /status.json HTTP/1.1\r\n\r\n").await;
+    assert!(no_host.starts_with("HTTP/1.1 403 Forbidden"));
+}
+
+#[tokio::test]
+async fn test_slow_requests_time_out() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        handle_connection(stream, Duration::from_millis(100))
+            .await
+            .unwrap();
+    });
+    let mut client = TcpStream::connect(addr).await.unwrap();
+    // Headers that never end
+    client.write_all(b"GET / HTTP/1.1\r\n").await.unwrap();
+    let mut response = String::new();
+    client.read_to_string(&mut response).await.unwrap();
+    server.await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 408 Request Timeout"));
+}
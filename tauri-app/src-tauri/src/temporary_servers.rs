@@ -0,0 +1,201 @@
+//! Claude Code servers added for a while, for one-off experiments
+//!
+//! `add_temporary_server` adds a server like `claude_mcp_add` (never replacing an existing
+//! one) and notes it in temporary_servers.json in the app data dir with its expiry. It is
+//! removed when its TTL runs out, or at the next start when the app was closed before then.
+//! A server whose entry was edited in the meantime is left in place, as is one
+//! `keep_temporary_server` was called for.
+
+use crate::claude_code_commands::{self, ClaudeCodeResponse, ClaudeCodeServer};
+use crate::settings::{app_config_dir, ConflictPolicy};
+use chrono::{DateTime, Duration, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{command, AppHandle, Runtime};
+
+/// Longest TTL accepted, a week
+pub(crate) const MAX_TTL_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Identifies this run; entries of earlier runs are removed at startup
+static SESSION: Lazy<String> = Lazy::new(|| Utc::now().timestamp_millis().to_string());
+static LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TemporaryServer {
+    pub name: String,
+    pub working_dir: String,
+    /// Entry as written, to tell whether it was edited since
+    pub config: Value,
+    pub added_at: String,
+    pub expires_at: String,
+    /// Run that added it
+    pub session: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct TemporaryFile {
+    #[serde(default)]
+    servers: Vec<TemporaryServer>,
+}
+
+fn temporary_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("temporary_servers.json"))
+}
+
+fn read_temporary() -> Result<TemporaryFile, String> {
+    let path = temporary_path()?;
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse temporary servers: {}", e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(TemporaryFile::default()),
+        Err(e) => Err(format!("Failed to read temporary servers: {}", e)),
+    }
+}
+
+fn write_temporary(file: &TemporaryFile) -> Result<(), String> {
+    let path = temporary_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(file)
+        .map_err(|e| format!("Failed to serialize temporary servers: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write temporary servers: {}", e))
+}
+
+fn update<T>(f: impl FnOnce(&mut Vec<TemporaryServer>) -> T) -> Result<T, String> {
+    let _guard = LOCK
+        .lock()
+        .map_err(|e| format!("Failed to lock temporary servers: {}", e))?;
+    let mut file = read_temporary()?;
+    let result = f(&mut file.servers);
+    write_temporary(&file)?;
+    Ok(result)
+}
+
+/// Whether `entry` is due for removal at `now`; entries of earlier runs always are
+pub(crate) fn is_due(entry: &TemporaryServer, now: DateTime<Utc>, session: &str) -> bool {
+    entry.session != session
+        || DateTime::parse_from_rfc3339(&entry.expires_at).map_or(true, |at| at <= now)
+}
+
+/// Remove the due entries' servers, unless they were edited since they were added
+async fn remove_due<R: Runtime>(app: &AppHandle<R>) -> Result<usize, String> {
+    let now = Utc::now();
+    let due = update(|servers| {
+        let (due, kept): (Vec<_>, Vec<_>) = servers
+            .drain(..)
+            .partition(|entry| is_due(entry, now, &SESSION));
+        *servers = kept;
+        due
+    })?;
+    let mut removed = 0;
+    for entry in due {
+        let current = claude_code_commands::written_config(&entry.name, &entry.working_dir).await;
+        match current {
+            Ok(Some(config)) if config == entry.config => {
+                let result = claude_code_commands::claude_mcp_remove(
+                    app.clone(),
+                    entry.name.clone(),
+                    entry.working_dir.clone(),
+                )
+                .await;
+                match result {
+                    Ok(_) => removed += 1,
+                    Err(e) => println!("[Temporary] Failed to remove {}: {}", entry.name, e),
+                }
+            }
+            Ok(Some(_)) => println!(
+                "[Temporary] Keeping {}: it was edited after it was added",
+                entry.name
+            ),
+            Ok(None) => {}
+            Err(e) => println!("[Temporary] Failed to check {}: {}", entry.name, e),
+        }
+    }
+    Ok(removed)
+}
+
+fn spawn_expiry<R: Runtime>(app: AppHandle<R>, ttl_secs: u64) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(ttl_secs)).await;
+        if let Err(e) = remove_due(&app).await {
+            println!("[Temporary] {}", e);
+        }
+    });
+}
+
+/// Remove the temporary servers left by earlier runs
+pub fn spawn_temporary_cleanup<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        match remove_due(&app).await {
+            Ok(0) => {}
+            Ok(removed) => println!("[Temporary] Removed {} temporary server(s)", removed),
+            Err(e) => println!("[Temporary] {}", e),
+        }
+    });
+}
+
+/// Add `server` to Claude Code for `ttl` seconds
+#[command]
+pub async fn add_temporary_server<R: Runtime>(
+    app: AppHandle<R>,
+    server: ClaudeCodeServer,
+    working_dir: String,
+    ttl: u64,
+) -> Result<ClaudeCodeResponse, String> {
+    if ttl == 0 || ttl > MAX_TTL_SECS {
+        return Err(format!(
+            "TTL must be between 1 and {} seconds",
+            MAX_TTL_SECS
+        ));
+    }
+    let response = claude_code_commands::claude_mcp_add(
+        app.clone(),
+        server,
+        working_dir.clone(),
+        Some(ConflictPolicy::Error),
+        None,
+    )
+    .await?;
+    let Some(name) = response.name.clone() else {
+        return Ok(response);
+    };
+    let config = claude_code_commands::written_config(&name, &working_dir)
+        .await?
+        .ok_or_else(|| format!("Server '{}' was not written", name))?;
+    let now = Utc::now();
+    let entry = TemporaryServer {
+        name,
+        working_dir,
+        config,
+        added_at: now.to_rfc3339(),
+        expires_at: (now + Duration::seconds(ttl as i64)).to_rfc3339(),
+        session: SESSION.clone(),
+    };
+    update(|servers| {
+        servers.retain(|s| !(s.name == entry.name && s.working_dir == entry.working_dir));
+        servers.push(entry);
+    })?;
+    spawn_expiry(app, ttl);
+    Ok(response)
+}
+
+#[command]
+pub async fn list_temporary_servers() -> Result<Vec<TemporaryServer>, String> {
+    Ok(read_temporary()?.servers)
+}
+
+/// Keep a temporary server for good; returns whether it was temporary
+#[command]
+pub async fn keep_temporary_server(name: String, working_dir: String) -> Result<bool, String> {
+    update(|servers| {
+        let before = servers.len();
+        servers.retain(|s| !(s.name == name && s.working_dir == working_dir));
+        servers.len() != before
+    })
+}
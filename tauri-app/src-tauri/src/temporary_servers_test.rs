@@ -0,0 +1,39 @@
+// Temporary server tests
+use crate::temporary_servers::{is_due, TemporaryServer};
+use chrono::{Duration, Utc};
+use serde_json::json;
+
+fn entry(expires_in: Duration, session: &str) -> TemporaryServer {
+    let now = Utc::now();
+    TemporaryServer {
+        name: "scratch".to_string(),
+        working_dir: "Global".to_string(),
+        config: json!({ "type": "stdio", "command": "npx" }),
+        added_at: now.to_rfc3339(),
+        expires_at: (now + expires_in).to_rfc3339(),
+        session: session.to_string(),
+    }
+}
+
+#[test]
+fn test_is_due_after_ttl() {
+    let now = Utc::now();
+    assert!(!is_due(&entry(Duration::minutes(5), "run"), now, "run"));
+    assert!(is_due(&entry(Duration::minutes(-1), "run"), now, "run"));
+}
+
+#[test]
+fn test_is_due_when_added_by_an_earlier_run() {
+    assert!(is_due(
+        &entry(Duration::hours(1), "earlier"),
+        Utc::now(),
+        "run"
+    ));
+}
+
+#[test]
+fn test_is_due_with_unreadable_expiry() {
+    let mut entry = entry(Duration::hours(1), "run");
+    entry.expires_at = "soon".to_string();
+    assert!(is_due(&entry, Utc::now(), "run"));
+}
@@ -0,0 +1,131 @@
+//! Per-server test presets: the roots a server is tested against
+//!
+//! Filesystem-type servers only work inside the roots the client offers them, so testing one
+//! without roots says nothing. The directories chosen for a configured server are kept in
+//! test_presets.json in the app data dir and offered as its roots whenever it is inspected or
+//! its tools are called; `test_server_launch` takes roots for unsaved configs.
+
+use crate::client_target::ClientTarget;
+use crate::settings::app_config_dir;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::command;
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TestPreset {
+    pub target: ClientTarget,
+    pub name: String,
+    /// Absolute directory paths
+    pub roots: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct PresetsFile {
+    #[serde(default)]
+    presets: Vec<TestPreset>,
+}
+
+fn presets_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("test_presets.json"))
+}
+
+fn read_presets() -> Result<PresetsFile, String> {
+    let path = presets_path()?;
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse test presets: {}", e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(PresetsFile::default()),
+        Err(e) => Err(format!("Failed to read test presets: {}", e)),
+    }
+}
+
+fn write_presets(file: &PresetsFile) -> Result<(), String> {
+    let path = presets_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(file)
+        .map_err(|e| format!("Failed to serialize test presets: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write test presets: {}", e))
+}
+
+/// `roots/list` entry for a directory
+pub(crate) fn root_entry(path: &Path) -> Result<Value, String> {
+    let uri = url::Url::from_directory_path(path)
+        .map_err(|_| format!("Root must be an absolute path: {}", path.display()))?;
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string());
+    Ok(json!({ "uri": uri.as_str().trim_end_matches('/'), "name": name }))
+}
+
+/// Check that every root is an existing directory
+pub(crate) fn checked_roots(roots: &[String]) -> Result<Vec<PathBuf>, String> {
+    roots
+        .iter()
+        .map(|root| {
+            let path = PathBuf::from(root);
+            if path.is_absolute() && path.is_dir() {
+                Ok(path)
+            } else {
+                Err(format!("Root is not a directory: {}", root))
+            }
+        })
+        .collect()
+}
+
+/// Roots saved for a configured server; none when it has no preset
+pub(crate) fn preset_roots(target: &ClientTarget, name: &str) -> Vec<PathBuf> {
+    let presets = read_presets().unwrap_or_else(|e| {
+        println!("[TestPresets] {}", e);
+        PresetsFile::default()
+    });
+    presets
+        .presets
+        .into_iter()
+        .find(|p| &p.target == target && p.name == name)
+        .map(|p| p.roots.into_iter().map(PathBuf::from).collect())
+        .unwrap_or_default()
+}
+
+#[command]
+pub async fn get_test_roots(target: ClientTarget, name: String) -> Result<Vec<String>, String> {
+    Ok(read_presets()?
+        .presets
+        .into_iter()
+        .find(|p| p.target == target && p.name == name)
+        .map(|p| p.roots)
+        .unwrap_or_default())
+}
+
+/// Save the roots `name` is tested against; an empty list removes its preset
+#[command]
+pub async fn set_test_roots(
+    target: ClientTarget,
+    name: String,
+    roots: Vec<String>,
+) -> Result<Vec<String>, String> {
+    checked_roots(&roots)?;
+    let _guard = LOCK
+        .lock()
+        .map_err(|e| format!("Failed to lock test presets: {}", e))?;
+    let mut file = read_presets()?;
+    file.presets
+        .retain(|p| !(p.target == target && p.name == name));
+    if !roots.is_empty() {
+        file.presets.push(TestPreset {
+            target,
+            name,
+            roots: roots.clone(),
+        });
+    }
+    write_presets(&file)?;
+    Ok(roots)
+}
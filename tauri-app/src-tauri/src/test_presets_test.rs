@@ -0,0 +1,24 @@
+// Test preset tests
+use crate::test_presets::{checked_roots, root_entry};
+use serde_json::json;
+use std::path::Path;
+
+#[cfg(unix)]
+#[test]
+fn test_root_entry() {
+    assert_eq!(
+        root_entry(Path::new("/home/me/My Project")).unwrap(),
+        json!({ "uri": "file:///home/me/My%20Project", "name": "My Project" })
+    );
+    assert!(root_entry(Path::new("relative/dir")).is_err());
+}
+
+#[test]
+fn test_checked_roots() {
+    let dir = std::env::temp_dir();
+    let roots = vec![dir.display().to_string()];
+    assert_eq!(checked_roots(&roots).unwrap(), vec![dir.clone()]);
+    let missing = dir.join("mcp-linker-no-such-root");
+    assert!(checked_roots(&[missing.display().to_string()]).is_err());
+    assert!(checked_roots(&["relative".to_string()]).is_err());
+}
@@ -0,0 +1,292 @@
+//! Searchable index of the tools every configured server provides
+//!
+//! The index is built from the `server_metadata` cache. Entries that are missing or stale are
+//! fetched when the index is requested, a few servers at a time; servers that can't be
+//! launched (remote servers, failed launches) are listed separately with the reason.
+//!
+//! Tools with the same name on several servers one client sees confuse the model and waste
+//! context, so `find_duplicate_tools` reports them with a suggested fix.
+
+use crate::claude_code_commands::GLOBAL_PROJECT_ID;
+use crate::client_target::{self, ClientTarget};
+use crate::server_metadata::{self, ServerMetadata, ToolInfo};
+use chrono::Utc;
+use futures::stream::{self, StreamExt};
+use serde::Serialize;
+use serde_json::Value;
+use tauri::command;
+
+/// Servers launched at once while refreshing the index
+const REFRESH_CONCURRENCY: usize = 4;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct UnindexedServer {
+    pub target: ClientTarget,
+    pub name: String,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ToolIndex {
+    pub servers: Vec<ServerMetadata>,
+    pub unindexed: Vec<UnindexedServer>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ToolMatch {
+    pub target: ClientTarget,
+    pub server: String,
+    pub tool: ToolInfo,
+    pub score: u32,
+}
+
+/// Relevance of `tool` for the lowercase `terms`; `None` unless every term matches somewhere
+pub(crate) fn score(tool: &ToolInfo, terms: &[String]) -> Option<u32> {
+    let name = tool.name.to_lowercase();
+    let title = tool.title.as_deref().unwrap_or_default().to_lowercase();
+    let description = tool
+        .description
+        .as_deref()
+        .unwrap_or_default()
+        .to_lowercase();
+    terms.iter().try_fold(0, |total, term| {
+        let points = if name == *term {
+            5
+        } else if name.contains(term.as_str()) {
+            3
+        } else if title.contains(term.as_str()) {
+            2
+        } else if description.contains(term.as_str()) {
+            1
+        } else {
+            return None;
+        };
+        Some(total + points)
+    })
+}
+
+/// Matches of `query` among the indexed tools, best first
+pub(crate) fn search(index: &ToolIndex, query: &str) -> Vec<ToolMatch> {
+    let terms: Vec<String> = query.split_whitespace().map(|t| t.to_lowercase()).collect();
+    let mut matches: Vec<ToolMatch> = index
+        .servers
+        .iter()
+        .flat_map(|server| {
+            server.tools.iter().filter_map(|tool| {
+                Some(ToolMatch {
+                    score: score(tool, &terms)?,
+                    target: server.target.clone(),
+                    server: server.name.clone(),
+                    tool: tool.clone(),
+                })
+            })
+        })
+        .collect();
+    matches.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.tool.name.cmp(&b.tool.name))
+            .then_with(|| a.server.cmp(&b.server))
+    });
+    matches
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ToolProvider {
+    pub target: ClientTarget,
+    pub server: String,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum OverlapFix {
+    /// The servers expose the same tool (same description and schema); one is enough
+    Disable {
+        keep: ToolProvider,
+        disable: Vec<ToolProvider>,
+    },
+    /// Different tools sharing a name; prefix each with its server when proxying them
+    Rename {
+        renames: Vec<(ToolProvider, String)>,
+    },
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DuplicateTool {
+    /// Target whose client sees all the providers
+    pub context: ClientTarget,
+    pub tool: String,
+    pub providers: Vec<ToolProvider>,
+    pub suggestion: OverlapFix,
+}
+
+/// Servers active together in `target`: Claude Code projects also get the global servers
+fn shares_context(context: &ClientTarget, server: &ServerMetadata) -> bool {
+    server.target == *context
+        || (context.is_claude_code()
+            && server.target.is_claude_code()
+            && server.target.working_dir() == GLOBAL_PROJECT_ID)
+}
+
+fn tool_name_fragment(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Tool names exposed by more than one server the same client sees
+pub(crate) fn find_duplicates(index: &ToolIndex) -> Vec<DuplicateTool> {
+    let mut contexts: Vec<&ClientTarget> = Vec::new();
+    for server in &index.servers {
+        if !contexts.contains(&&server.target) {
+            contexts.push(&server.target);
+        }
+    }
+    let mut duplicates = Vec::new();
+    for context in contexts {
+        let mut by_name: Vec<(&str, Vec<(&ServerMetadata, &ToolInfo)>)> = Vec::new();
+        for server in index.servers.iter().filter(|s| shares_context(context, s)) {
+            for tool in &server.tools {
+                match by_name.iter_mut().find(|(name, _)| *name == tool.name) {
+                    Some((_, providers)) => providers.push((server, tool)),
+                    None => by_name.push((&tool.name, vec![(server, tool)])),
+                }
+            }
+        }
+        for (name, providers) in by_name {
+            // Global Claude Code overlaps are reported once, for the global scope itself
+            if providers.len() < 2 || providers.iter().all(|(s, _)| s.target != *context) {
+                continue;
+            }
+            let identical = providers.windows(2).all(|pair| {
+                pair[0].1.description == pair[1].1.description
+                    && pair[0].1.input_schema == pair[1].1.input_schema
+            });
+            let provider = |server: &ServerMetadata| ToolProvider {
+                target: server.target.clone(),
+                server: server.name.clone(),
+            };
+            let suggestion = if identical {
+                OverlapFix::Disable {
+                    keep: provider(providers[0].0),
+                    disable: providers[1..].iter().map(|(s, _)| provider(s)).collect(),
+                }
+            } else {
+                OverlapFix::Rename {
+                    renames: providers
+                        .iter()
+                        .map(|(s, _)| {
+                            (
+                                provider(s),
+                                format!("{}_{}", tool_name_fragment(&s.name), name),
+                            )
+                        })
+                        .collect(),
+                }
+            };
+            duplicates.push(DuplicateTool {
+                context: context.clone(),
+                tool: name.to_string(),
+                providers: providers.iter().map(|(s, _)| provider(s)).collect(),
+                suggestion,
+            });
+        }
+    }
+    duplicates
+}
+
+async fn configured_servers() -> Vec<(ClientTarget, String, Value)> {
+    let mut servers = Vec::new();
+    for target in client_target::known_targets().await {
+        let Ok(configs) = client_target::list_servers(&target).await else {
+            continue;
+        };
+        for (name, config) in configs {
+            servers.push((target.clone(), name, config));
+        }
+    }
+    servers
+}
+
+/// Index of all configured servers, fetching stale entries first when `refresh` is set
+pub(crate) async fn tool_index(refresh: bool) -> ToolIndex {
+    let (cached, failures) = server_metadata::cached();
+    let now = Utc::now();
+    let mut index = ToolIndex::default();
+    let mut stale = Vec::new();
+    for (target, name, config) in configured_servers().await {
+        if config["command"].as_str().is_none() {
+            index.unindexed.push(UnindexedServer {
+                target,
+                name,
+                error: "Remote servers are not inspected".to_string(),
+            });
+            continue;
+        }
+        let entry = cached.iter().find(|m| m.target == target && m.name == name);
+        let failure = failures
+            .iter()
+            .find(|f| f.target == target && f.name == name);
+        let fresh =
+            |at: &str, hash: &str| !refresh || !server_metadata::is_stale(at, hash, &config, now);
+        match (entry, failure) {
+            (Some(entry), _) if fresh(&entry.fetched_at, &entry.config_hash) => {
+                index.servers.push(entry.clone())
+            }
+            (None, Some(failure)) if fresh(&failure.failed_at, &failure.config_hash) => {
+                index.unindexed.push(UnindexedServer {
+                    target,
+                    name,
+                    error: failure.error.clone(),
+                })
+            }
+            (None, None) if !refresh => index.unindexed.push(UnindexedServer {
+                target,
+                name,
+                error: "Not inspected yet".to_string(),
+            }),
+            _ => stale.push((target, name, config)),
+        }
+    }
+
+    let refreshed: Vec<_> = stream::iter(stale)
+        .map(|(target, name, config)| async move {
+            let result = server_metadata::refresh(&target, &name, &config).await;
+            (target, name, result)
+        })
+        .buffer_unordered(REFRESH_CONCURRENCY)
+        .collect()
+        .await;
+    for (target, name, result) in refreshed {
+        match result {
+            Ok(entry) => index.servers.push(entry),
+            Err(error) => index.unindexed.push(UnindexedServer {
+                target,
+                name,
+                error,
+            }),
+        }
+    }
+    index
+}
+
+/// Find which configured servers provide a tool, e.g. "bigquery query". Stale entries are
+/// refreshed first unless `refresh` is false.
+#[command]
+pub async fn search_tools(query: String, refresh: Option<bool>) -> Result<Vec<ToolMatch>, String> {
+    let index = tool_index(refresh.unwrap_or(true)).await;
+    Ok(search(&index, &query))
+}
+
+/// Every indexed server with its tools, plus the servers that couldn't be indexed
+#[command]
+pub async fn get_tool_index(refresh: Option<bool>) -> Result<ToolIndex, String> {
+    Ok(tool_index(refresh.unwrap_or(true)).await)
+}
+
+/// Tool names provided by several servers of the same client, with a suggested fix
+#[command]
+pub async fn find_duplicate_tools(refresh: Option<bool>) -> Result<Vec<DuplicateTool>, String> {
+    let index = tool_index(refresh.unwrap_or(true)).await;
+    Ok(find_duplicates(&index))
+}
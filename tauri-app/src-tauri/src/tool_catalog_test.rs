@@ -0,0 +1,117 @@
+// Tool catalog search tests
+use crate::client_target::ClientTarget;
+use crate::server_handshake::ServerInfo;
+use crate::server_metadata::{ServerMetadata, ToolInfo};
+use crate::tool_catalog::{find_duplicates, score, search, OverlapFix, ToolIndex};
+use serde_json::Value;
+
+fn tool(name: &str, description: &str) -> ToolInfo {
+    ToolInfo {
+        name: name.to_string(),
+        title: None,
+        description: Some(description.to_string()),
+        input_schema: Value::Null,
+    }
+}
+
+fn server(name: &str, tools: Vec<ToolInfo>) -> ServerMetadata {
+    server_in(ClientTarget::client("cursor", None), name, tools)
+}
+
+fn server_in(target: ClientTarget, name: &str, tools: Vec<ToolInfo>) -> ServerMetadata {
+    ServerMetadata {
+        target,
+        name: name.to_string(),
+        fetched_at: "2026-01-01T00:00:00Z".to_string(),
+        config_hash: String::new(),
+        server: ServerInfo::default(),
+        tools,
+    }
+}
+
+#[test]
+fn test_score() {
+    let query = tool("query", "Run a SQL query against BigQuery");
+    let terms =
+        |q: &str| -> Vec<String> { q.split_whitespace().map(|t| t.to_lowercase()).collect() };
+    assert_eq!(score(&query, &terms("query")), Some(5));
+    assert_eq!(score(&query, &terms("BigQuery")), Some(1));
+    assert_eq!(score(&query, &terms("bigquery query")), Some(6));
+    assert_eq!(score(&query, &terms("bigquery postgres")), None);
+}
+
+#[test]
+fn test_search_ranks_across_servers() {
+    let index = ToolIndex {
+        servers: vec![
+            server(
+                "bigquery",
+                vec![tool("execute_query", "Query BigQuery datasets")],
+            ),
+            server(
+                "docs",
+                vec![tool("search", "Search documentation for a query string")],
+            ),
+            server("fs", vec![tool("read_file", "Read a file")]),
+        ],
+        unindexed: Vec::new(),
+    };
+    let matches = search(&index, "bigquery");
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].server, "bigquery");
+
+    let matches = search(&index, "query");
+    let servers: Vec<&str> = matches.iter().map(|m| m.server.as_str()).collect();
+    assert_eq!(servers, vec!["bigquery", "docs"]);
+}
+
+#[test]
+fn test_find_duplicates() {
+    let index = ToolIndex {
+        servers: vec![
+            server("fetch", vec![tool("fetch", "Fetch a URL")]),
+            server("fetch-copy", vec![tool("fetch", "Fetch a URL")]),
+            server("browser", vec![tool("fetch", "Load a page in a browser")]),
+            server_in(
+                ClientTarget::client("vscode", None),
+                "fetch",
+                vec![tool("fetch", "Fetch a URL")],
+            ),
+        ],
+        unindexed: Vec::new(),
+    };
+    let duplicates = find_duplicates(&index);
+    assert_eq!(duplicates.len(), 1);
+    assert_eq!(duplicates[0].tool, "fetch");
+    assert_eq!(duplicates[0].providers.len(), 3);
+    match &duplicates[0].suggestion {
+        OverlapFix::Rename { renames } => {
+            let names: Vec<&str> = renames.iter().map(|(_, n)| n.as_str()).collect();
+            assert_eq!(
+                names,
+                vec!["fetch_fetch", "fetch_copy_fetch", "browser_fetch"]
+            );
+        }
+        other => panic!("expected renames, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_find_duplicates_claude_global_and_project() {
+    let global = ClientTarget::claude_code("Global");
+    let project = ClientTarget::claude_code("/work/app");
+    let index = ToolIndex {
+        servers: vec![
+            server_in(global.clone(), "fetch", vec![tool("fetch", "Fetch a URL")]),
+            server_in(project.clone(), "fetch", vec![tool("fetch", "Fetch a URL")]),
+        ],
+        unindexed: Vec::new(),
+    };
+    let duplicates = find_duplicates(&index);
+    assert_eq!(duplicates.len(), 1);
+    assert_eq!(duplicates[0].context, project);
+    assert!(matches!(
+        &duplicates[0].suggestion,
+        OverlapFix::Disable { keep, disable } if keep.target == global && disable.len() == 1
+    ));
+}
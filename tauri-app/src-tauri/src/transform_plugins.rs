@@ -0,0 +1,162 @@
+//! WASM transform plugins for import formats
+//!
+//! A plugin is a WASM module, registered in `TransformSettings`, that turns pasted text of
+//! some format into `mcpServers` entries. It exports `memory`, `alloc(len) -> ptr` and
+//! `transform(ptr, len) -> i64`; the result packs the pointer (high 32 bits) and length (low
+//! 32 bits) of UTF-8 JSON, either `{"mcpServers": {...}}` or `{"error": "..."}`.
+//!
+//! Modules run in the wasmi interpreter with no imports at all, so they can't reach files,
+//! the network or the clock. Each run gets a fuel budget, a memory cap and an input size cap
+//! from the settings; a module that exceeds one is stopped and its output discarded. Nothing
+//! is written: the returned servers go through the usual add flow.
+
+use crate::settings::{load_settings, TransformPlugin, TransformSettings};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fs;
+use tauri::command;
+use wasmi::{Config, Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+/// Longest output accepted from a module
+const MAX_OUTPUT_BYTES: usize = 4 * 1024 * 1024;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TransformResult {
+    pub plugin: String,
+    pub servers: Map<String, Value>,
+    /// Entries the module returned that aren't servers
+    pub skipped: Vec<String>,
+}
+
+/// Pointer and length of the `transform` result
+pub(crate) fn unpack_result(packed: i64) -> (u32, u32) {
+    ((packed as u64 >> 32) as u32, packed as u64 as u32)
+}
+
+/// Servers in a module's output, and the names of entries that aren't servers
+pub(crate) fn parse_output(output: &str) -> Result<(Map<String, Value>, Vec<String>), String> {
+    let value: Value =
+        serde_json::from_str(output).map_err(|e| format!("Plugin returned invalid JSON: {}", e))?;
+    if let Some(error) = value.get("error").and_then(|e| e.as_str()) {
+        return Err(format!("Plugin could not read the input: {}", error));
+    }
+    let entries = value
+        .get("mcpServers")
+        .and_then(|s| s.as_object())
+        .ok_or_else(|| "Plugin output has no mcpServers object".to_string())?;
+    let mut servers = Map::new();
+    let mut skipped = Vec::new();
+    for (name, entry) in entries {
+        let launchable = entry["command"].is_string() || entry["url"].is_string();
+        if name.trim().is_empty() || !launchable {
+            skipped.push(name.clone());
+        } else {
+            servers.insert(name.clone(), entry.clone());
+        }
+    }
+    Ok((servers, skipped))
+}
+
+fn wasm_error(e: impl std::fmt::Display) -> String {
+    format!("Plugin failed: {}", e)
+}
+
+/// Run `transform` of the module in `wasm` on `input` within `limits`
+pub(crate) fn run_module(
+    wasm: &[u8],
+    input: &str,
+    limits: &TransformSettings,
+) -> Result<String, String> {
+    if input.len() as u64 > limits.max_input_kb * 1024 {
+        return Err(format!(
+            "Input is larger than the {} KiB plugins accept",
+            limits.max_input_kb
+        ));
+    }
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, wasm).map_err(|e| format!("Invalid WASM module: {}", e))?;
+
+    let store_limits = StoreLimitsBuilder::new()
+        .memory_size((limits.memory_limit_mb * 1024 * 1024) as usize)
+        .instances(1)
+        .build();
+    let mut store: Store<StoreLimits> = Store::new(&engine, store_limits);
+    store.limiter(|state| state);
+    store.set_fuel(limits.max_fuel).map_err(wasm_error)?;
+
+    // No host functions: a module that imports anything fails here
+    let linker = Linker::<StoreLimits>::new(&engine);
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .and_then(|pre| pre.start(&mut store))
+        .map_err(|e| format!("Plugin can't be started: {}", e))?;
+    let memory = instance
+        .get_memory(&store, "memory")
+        .ok_or_else(|| "Plugin exports no memory".to_string())?;
+    let alloc = instance
+        .get_typed_func::<i32, i32>(&store, "alloc")
+        .map_err(wasm_error)?;
+    let transform = instance
+        .get_typed_func::<(i32, i32), i64>(&store, "transform")
+        .map_err(wasm_error)?;
+
+    let len = input.len() as i32;
+    let ptr = alloc.call(&mut store, len).map_err(wasm_error)?;
+    memory
+        .write(&mut store, ptr as u32 as usize, input.as_bytes())
+        .map_err(wasm_error)?;
+    let packed = transform.call(&mut store, (ptr, len)).map_err(wasm_error)?;
+
+    let (out_ptr, out_len) = unpack_result(packed);
+    if out_len as usize > MAX_OUTPUT_BYTES {
+        return Err("Plugin output is too large".to_string());
+    }
+    let mut output = vec![0u8; out_len as usize];
+    memory
+        .read(&store, out_ptr as usize, &mut output)
+        .map_err(wasm_error)?;
+    String::from_utf8(output).map_err(|_| "Plugin output is not UTF-8".to_string())
+}
+
+fn find_plugin(settings: &TransformSettings, id: &str) -> Result<TransformPlugin, String> {
+    settings
+        .plugins
+        .iter()
+        .find(|p| p.id == id)
+        .cloned()
+        .ok_or_else(|| format!("Transform plugin '{}' is not registered", id))
+}
+
+#[command]
+pub async fn list_transform_plugins() -> Result<Vec<TransformPlugin>, String> {
+    Ok(load_settings().transforms.plugins)
+}
+
+/// Turn `input` into servers with the plugin `plugin_id`. Nothing is written.
+#[command]
+pub async fn run_transform_plugin(
+    plugin_id: String,
+    input: String,
+) -> Result<TransformResult, String> {
+    let settings = load_settings().transforms;
+    let plugin = find_plugin(&settings, &plugin_id)?;
+    let wasm =
+        fs::read(&plugin.path).map_err(|e| format!("Failed to read {}: {}", plugin.path, e))?;
+    let output = tokio::task::spawn_blocking(move || run_module(&wasm, &input, &settings))
+        .await
+        .map_err(|e| format!("Failed to run plugin task: {}", e))??;
+    let (servers, skipped) = parse_output(&output)?;
+    println!(
+        "[Transform] {} returned {} server(s), skipped {}",
+        plugin.id,
+        servers.len(),
+        skipped.len()
+    );
+    Ok(TransformResult {
+        plugin: plugin.id,
+        servers,
+        skipped,
+    })
+}
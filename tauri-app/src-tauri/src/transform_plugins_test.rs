@@ -0,0 +1,47 @@
+// WASM transform plugin tests
+use crate::settings::TransformSettings;
+use crate::transform_plugins::{parse_output, run_module, unpack_result};
+
+#[test]
+fn test_unpack_result() {
+    let packed = ((1024u64 << 32) | 77) as i64;
+    assert_eq!(unpack_result(packed), (1024, 77));
+    let high = ((0xffff_0000u64 << 32) | 5) as i64;
+    assert_eq!(unpack_result(high), (0xffff_0000, 5));
+}
+
+#[test]
+fn test_parse_output_keeps_launchable_entries() {
+    let output = r#"{"mcpServers": {
+        "fs": { "command": "npx", "args": ["-y", "fs-server"] },
+        "remote": { "url": "https://example.com/mcp" },
+        "broken": { "args": ["x"] }
+    }}"#;
+    let (servers, skipped) = parse_output(output).unwrap();
+    assert_eq!(servers.keys().collect::<Vec<_>>(), vec!["fs", "remote"]);
+    assert_eq!(skipped, vec!["broken".to_string()]);
+}
+
+#[test]
+fn test_parse_output_errors() {
+    assert!(parse_output(r#"{"error": "unknown format"}"#)
+        .unwrap_err()
+        .contains("unknown format"));
+    assert!(parse_output(r#"{"servers": []}"#).is_err());
+    assert!(parse_output("not json").is_err());
+}
+
+#[test]
+fn test_run_module_rejects_oversized_input_and_invalid_modules() {
+    let limits = TransformSettings {
+        max_input_kb: 1,
+        ..TransformSettings::default()
+    };
+    let input = "x".repeat(2048);
+    assert!(run_module(b"\0asm", &input, &limits)
+        .unwrap_err()
+        .contains("larger"));
+    assert!(run_module(b"not wasm", "{}", &limits)
+        .unwrap_err()
+        .contains("Invalid WASM"));
+}
@@ -0,0 +1,524 @@
+//! "Why is my server not working": the existing checks run in order for one server
+//!
+//! `troubleshoot_server` looks at a configured server step by step: whether its entry exists,
+//! is enabled and matches the client's schema (`config_schema`), whether the program it runs
+//! is installed (`bootstrap`, PATH), whether it starts and answers the handshake in the
+//! sandbox (`server_handshake`), what the client logged about it (`client_logs`) and whether
+//! Claude Code permissions block it (`removal_impact`). A step that can't say anything is
+//! skipped, and later steps are skipped once an earlier one rules the server out. Every
+//! finding becomes a cause with a suggested fix; causes are ranked by how likely they explain
+//! the failure.
+
+use crate::bootstrap::{self, RuntimeId};
+use crate::claude_settings::{self, SettingsScope};
+use crate::client_target::{self, ClientTarget};
+use crate::server_handshake::{self, ClientOptions};
+use crate::settings::load_settings;
+use crate::{client_logs, config_schema, platform_access, quarantine, removal_impact};
+use crate::{server_package, server_sandbox};
+use serde::Serialize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Likelihood {
+    High,
+    Medium,
+    Low,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Step {
+    Config,
+    Runtime,
+    Handshake,
+    Logs,
+    Permissions,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StepStatus {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct StepResult {
+    pub step: Step,
+    pub status: StepStatus,
+    pub detail: String,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct Cause {
+    pub step: Step,
+    pub likelihood: Likelihood,
+    pub summary: String,
+    pub fix: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TroubleshootReport {
+    pub name: String,
+    pub target: ClientTarget,
+    pub steps: Vec<StepResult>,
+    /// Most likely first
+    pub causes: Vec<Cause>,
+}
+
+struct Pipeline {
+    steps: Vec<StepResult>,
+    causes: Vec<Cause>,
+}
+
+impl Pipeline {
+    fn passed(&mut self, step: Step, detail: impl Into<String>) {
+        self.record(step, StepStatus::Passed, detail);
+    }
+
+    fn skipped(&mut self, step: Step, detail: impl Into<String>) {
+        self.record(step, StepStatus::Skipped, detail);
+    }
+
+    fn failed(&mut self, step: Step, detail: impl Into<String>, causes: Vec<Cause>) {
+        self.record(step, StepStatus::Failed, detail);
+        self.causes.extend(causes);
+    }
+
+    fn record(&mut self, step: Step, status: StepStatus, detail: impl Into<String>) {
+        self.steps.push(StepResult {
+            step,
+            status,
+            detail: detail.into(),
+        });
+    }
+}
+
+fn cause(step: Step, likelihood: Likelihood, summary: String, fix: &str) -> Cause {
+    Cause {
+        step,
+        likelihood,
+        summary,
+        fix: fix.to_string(),
+    }
+}
+
+/// Causes most likely first, keeping the order of the steps among equals
+pub(crate) fn rank(mut causes: Vec<Cause>) -> Vec<Cause> {
+    causes.sort_by_key(|c| c.likelihood);
+    causes
+}
+
+/// The runtime a launcher belongs to
+pub(crate) fn runtime_for(program: &str) -> Option<RuntimeId> {
+    match program {
+        "node" | "npx" | "npm" | "pnpm" | "yarn" | "corepack" => Some(RuntimeId::Node),
+        "uv" | "uvx" => Some(RuntimeId::Uv),
+        "docker" => Some(RuntimeId::Docker),
+        _ => None,
+    }
+}
+
+/// Where `command` is found: as given when it is a path, otherwise through PATH
+fn find_program(command: &str) -> Option<PathBuf> {
+    let given = Path::new(command);
+    if given.components().count() > 1 {
+        return given.is_file().then(|| given.to_path_buf());
+    }
+    let dirs: Vec<PathBuf> = std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).collect())
+        .unwrap_or_default();
+    let extensions: Vec<String> = if cfg!(windows) {
+        std::env::var("PATHEXT")
+            .unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_string())
+            .split(';')
+            .map(|ext| ext.to_ascii_lowercase())
+            .chain([String::new()])
+            .collect()
+    } else {
+        vec![String::new()]
+    };
+    platform_access::find_launcher(command, &dirs, &extensions, Path::is_file)
+}
+
+/// What a failed launch most likely means, from its error and stderr
+pub(crate) fn launch_causes(error: &str, stderr: &[String]) -> Vec<Cause> {
+    let output = format!("{}\n{}", error, stderr.join("\n"));
+    let lower = output.to_lowercase();
+    let has = |needles: &[&str]| needles.iter().any(|n| lower.contains(n));
+    let mut causes = Vec::new();
+    if has(&[
+        "cannot find module",
+        "modulenotfounderror",
+        "404 not found",
+        "e404",
+        "no matching distribution",
+        "not found in registry",
+    ]) {
+        causes.push(cause(
+            Step::Handshake,
+            Likelihood::High,
+            "The server package can't be found or installed".to_string(),
+            "Check the package name and version in the args, and that the registry is reachable",
+        ));
+    }
+    if has(&[
+        "api key",
+        "api_key",
+        "invalid token",
+        "unauthorized",
+        "forbidden",
+        "credentials",
+    ]) {
+        causes.push(cause(
+            Step::Handshake,
+            Likelihood::High,
+            "The server is missing credentials or they were rejected".to_string(),
+            "Set the API key or token the server expects in its env",
+        ));
+    }
+    if has(&["eaddrinuse", "address already in use"]) {
+        causes.push(cause(
+            Step::Handshake,
+            Likelihood::Medium,
+            "The port the server listens on is taken".to_string(),
+            "Stop the other process using the port or configure another one",
+        ));
+    }
+    if has(&["eacces", "permission denied", "operation not permitted"]) {
+        causes.push(cause(
+            Step::Handshake,
+            Likelihood::Medium,
+            "The server isn't allowed to read or run something it needs".to_string(),
+            "Check the permissions of the paths in its args and env",
+        ));
+    }
+    if has(&["no handshake response"]) {
+        causes.push(cause(
+            Step::Handshake,
+            Likelihood::Medium,
+            "The server started but didn't answer the MCP handshake in time".to_string(),
+            "Make sure it speaks MCP over stdio and doesn't wait for input; raise the launch timeout for slow first installs",
+        ));
+    }
+    if causes.is_empty() {
+        causes.push(cause(
+            Step::Handshake,
+            Likelihood::High,
+            format!("The server fails to start: {}", error),
+            "Run the command in a terminal to see the full error",
+        ));
+    }
+    causes
+}
+
+/// The entry, whether it is disabled, or `None` when the target has no such server
+async fn find_entry(target: &ClientTarget, name: &str) -> Result<Option<(Value, bool)>, String> {
+    if let Some(entry) = client_target::list_servers(target).await?.remove(name) {
+        let disabled = entry["disabled"].as_bool() == Some(true);
+        return Ok(Some((entry, disabled)));
+    }
+    Ok(client_target::list_disabled_servers(target)
+        .await?
+        .remove(name)
+        .map(|entry| (entry, true)))
+}
+
+async fn check_config(p: &mut Pipeline, target: &ClientTarget, name: &str) -> Option<Value> {
+    let found = match find_entry(target, name).await {
+        Ok(found) => found,
+        Err(e) => {
+            p.failed(
+                Step::Config,
+                e.clone(),
+                vec![cause(
+                    Step::Config,
+                    Likelihood::High,
+                    format!("The config of {} can't be read: {}", target.label(), e),
+                    "Fix or restore the config file; the startup check can set a broken one aside",
+                )],
+            );
+            return None;
+        }
+    };
+    let Some((entry, disabled)) = found else {
+        p.failed(
+            Step::Config,
+            format!("No server '{}' in {}", name, target.label()),
+            vec![cause(
+                Step::Config,
+                Likelihood::High,
+                format!("'{}' isn't configured in {}", name, target.label()),
+                "Add the server to this scope, or check you are looking at the right project",
+            )],
+        );
+        return None;
+    };
+    let mut causes = Vec::new();
+    if disabled {
+        causes.push(cause(
+            Step::Config,
+            Likelihood::High,
+            format!("'{}' is disabled", name),
+            "Enable the server",
+        ));
+    }
+    // Disabled entries may sit in a store of their own, outside the schema
+    match config_schema::validate_server_entry(&target.client, name, &entry) {
+        Ok(errors) if !errors.is_empty() && !disabled => causes.extend(errors.iter().map(|e| {
+            cause(
+                Step::Config,
+                Likelihood::High,
+                format!("Invalid entry at {}: {}", e.pointer, e.message),
+                "Correct the field in the server's config",
+            )
+        })),
+        Ok(_) => {}
+        Err(e) => println!("[Troubleshoot] Schema check skipped: {}", e),
+    }
+    if causes.is_empty() {
+        p.passed(Step::Config, "The entry exists, is enabled and is valid");
+    } else {
+        p.failed(
+            Step::Config,
+            format!("{} problem(s) in the entry", causes.len()),
+            causes,
+        );
+    }
+    Some(entry)
+}
+
+/// Whether the program of a stdio entry is installed; true for remote entries
+async fn check_runtime(p: &mut Pipeline, entry: &Value) -> bool {
+    let Some(command) = entry["command"].as_str() else {
+        p.skipped(Step::Runtime, "Remote servers don't need a local runtime");
+        return true;
+    };
+    let program = server_package::program_name(command);
+    if let Some(runtime) = runtime_for(program) {
+        let status = bootstrap::bootstrap_check()
+            .await
+            .ok()
+            .and_then(|s| s.runtimes.into_iter().find(|r| r.runtime == runtime));
+        match status {
+            Some(status) if !status.found => {
+                let fix = match &status.install_command {
+                    Some(install) => format!("Install it, e.g. with `{}`", install.join(" ")),
+                    None => "Install it and restart the client".to_string(),
+                };
+                p.failed(
+                    Step::Runtime,
+                    format!("{:?} isn't installed", runtime),
+                    vec![Cause {
+                        step: Step::Runtime,
+                        likelihood: Likelihood::High,
+                        summary: format!(
+                            "'{}' needs {:?}, which isn't installed",
+                            program, runtime
+                        ),
+                        fix,
+                    }],
+                );
+                return false;
+            }
+            Some(status) => {
+                p.passed(
+                    Step::Runtime,
+                    format!(
+                        "{:?} {} is installed",
+                        runtime,
+                        status.version.unwrap_or_default()
+                    ),
+                );
+                return true;
+            }
+            None => {}
+        }
+    }
+    match find_program(command) {
+        Some(path) => {
+            p.passed(
+                Step::Runtime,
+                format!("{} is at {}", program, path.display()),
+            );
+            true
+        }
+        None => {
+            p.failed(
+                Step::Runtime,
+                format!("'{}' isn't on the PATH", command),
+                vec![cause(
+                    Step::Runtime,
+                    Likelihood::High,
+                    format!("'{}' can't be found", command),
+                    "Install it, or use its full path as the command; GUI clients may not see the PATH of your shell",
+                )],
+            );
+            false
+        }
+    }
+}
+
+async fn check_handshake(p: &mut Pipeline, entry: &Value) {
+    let Some(command) = entry["command"].as_str() else {
+        p.skipped(Step::Handshake, "Only stdio servers are test-launched");
+        return;
+    };
+    let sandbox = load_settings().sandbox;
+    if let Err(e) = server_sandbox::check_command(command, &sandbox) {
+        p.skipped(Step::Handshake, e);
+        return;
+    }
+    match server_handshake::connect(entry, &sandbox, ClientOptions::default()).await {
+        Ok(session) => {
+            let info = &session.info;
+            p.passed(
+                Step::Handshake,
+                format!(
+                    "{} {} answered the handshake",
+                    info.name.as_deref().unwrap_or("The server"),
+                    info.version.as_deref().unwrap_or_default()
+                ),
+            );
+        }
+        Err((error, stderr)) => {
+            let causes = launch_causes(&error, &stderr);
+            p.failed(Step::Handshake, error, causes);
+        }
+    }
+}
+
+async fn check_logs(p: &mut Pipeline, target: &ClientTarget, name: &str) {
+    if client_logs::log_dir(&target.client).is_err() {
+        p.skipped(
+            Step::Logs,
+            format!("No MCP logs are known for {}", target.client),
+        );
+        return;
+    }
+    let report = match client_logs::diagnose_client_logs(target.client.clone()).await {
+        Ok(reports) => reports.into_iter().find(|r| r.server == name),
+        Err(e) => {
+            p.skipped(Step::Logs, e);
+            return;
+        }
+    };
+    match report.and_then(|r| r.errors.last().cloned()) {
+        Some(last) => p.failed(
+            Step::Logs,
+            format!("The client logged errors for '{}'", name),
+            vec![cause(
+                Step::Logs,
+                Likelihood::Medium,
+                format!("The client's last error for '{}': {}", name, last.message),
+                "Fix what the error names, then restart the client",
+            )],
+        ),
+        None => p.passed(Step::Logs, format!("No errors logged for '{}'", name)),
+    }
+}
+
+/// `disabledMcpjsonServers` of the project settings naming `name`
+fn rejected_project_server(working_dir: &str, name: &str) -> bool {
+    [SettingsScope::Project, SettingsScope::Local]
+        .into_iter()
+        .filter_map(|scope| claude_settings::settings_path(scope, Some(working_dir)).ok())
+        .filter_map(|path| claude_settings::read_settings(&path).ok())
+        .any(|settings| {
+            settings
+                .get("disabledMcpjsonServers")
+                .and_then(Value::as_array)
+                .is_some_and(|names| names.iter().any(|n| n == name))
+        })
+}
+
+async fn check_permissions(p: &mut Pipeline, target: &ClientTarget, name: &str) {
+    if !target.is_claude_code() {
+        p.skipped(Step::Permissions, "Only Claude Code has permission rules");
+        return;
+    }
+    let mut causes = Vec::new();
+    if let Ok(impact) =
+        removal_impact::analyze_server_removal(target.clone(), name.to_string()).await
+    {
+        causes.extend(
+            impact
+                .permissions
+                .iter()
+                .filter(|r| r.list == "deny")
+                .map(|r| {
+                    cause(
+                        Step::Permissions,
+                        Likelihood::High,
+                        format!("Claude Code denies `{}` ({:?} settings)", r.rule, r.scope),
+                        "Remove the deny rule from the settings",
+                    )
+                }),
+        );
+    }
+    if rejected_project_server(target.working_dir(), name) {
+        causes.push(cause(
+            Step::Permissions,
+            Likelihood::High,
+            format!("'{}' of the project's .mcp.json was rejected", name),
+            "Remove it from disabledMcpjsonServers, or approve it when Claude Code asks",
+        ));
+    }
+    if causes.is_empty() {
+        p.passed(Step::Permissions, "No rule blocks the server");
+    } else {
+        p.failed(
+            Step::Permissions,
+            format!("{} rule(s) block the server", causes.len()),
+            causes,
+        );
+    }
+}
+
+/// Check server `name` of `working_dir` ("Global" or a project; a config path for other
+/// clients) step by step and rank the likely causes of its failure. `client` defaults to
+/// Claude Code. The handshake test starts the server in the sandbox.
+#[command]
+pub async fn troubleshoot_server(
+    name: String,
+    working_dir: String,
+    client: Option<String>,
+) -> Result<TroubleshootReport, String> {
+    let target = quarantine::target_for(client.as_deref(), &working_dir);
+    let mut p = Pipeline {
+        steps: Vec::new(),
+        causes: Vec::new(),
+    };
+    match check_config(&mut p, &target, &name).await {
+        Some(entry) => {
+            if check_runtime(&mut p, &entry).await {
+                check_handshake(&mut p, &entry).await;
+            } else {
+                p.skipped(Step::Handshake, "The runtime is missing");
+            }
+        }
+        None => {
+            p.skipped(Step::Runtime, "The server has no usable entry");
+            p.skipped(Step::Handshake, "The server has no usable entry");
+        }
+    }
+    check_logs(&mut p, &target, &name).await;
+    check_permissions(&mut p, &target, &name).await;
+    println!(
+        "[Troubleshoot] {} on {}: {} cause(s)",
+        name,
+        target.label(),
+        p.causes.len()
+    );
+    Ok(TroubleshootReport {
+        name,
+        target,
+        steps: p.steps,
+        causes: rank(p.causes),
+    })
+}
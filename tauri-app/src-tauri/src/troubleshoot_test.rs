@@ -0,0 +1,39 @@
+// Troubleshooting pipeline tests
+use crate::bootstrap::RuntimeId;
+use crate::troubleshoot::{launch_causes, rank, runtime_for, Likelihood, Step};
+
+#[test]
+fn test_launch_causes_from_stderr() {
+    let causes = launch_causes(
+        "Server exited with status 1",
+        &["Error: Cannot find module '@x/missing'".to_string()],
+    );
+    assert_eq!(causes.len(), 1);
+    assert!(causes[0].summary.contains("package"));
+
+    let causes = launch_causes(
+        "Server exited with status 1",
+        &["GITHUB_TOKEN missing: 401 Unauthorized".to_string()],
+    );
+    assert!(causes[0].summary.contains("credentials"));
+
+    let causes = launch_causes("Server exited with status 2", &[]);
+    assert_eq!(causes[0].likelihood, Likelihood::High);
+    assert!(causes[0].summary.ends_with("status 2"));
+}
+
+#[test]
+fn test_rank_puts_likely_causes_first() {
+    let mut causes = launch_causes("No handshake response within 30s", &[]);
+    causes.extend(launch_causes(
+        "x",
+        &["ModuleNotFoundError: No module named 'mcp'".to_string()],
+    ));
+    let ranked = rank(causes);
+    assert_eq!(ranked[0].likelihood, Likelihood::High);
+    assert_eq!(ranked[1].likelihood, Likelihood::Medium);
+    assert_eq!(ranked[1].step, Step::Handshake);
+
+    assert_eq!(runtime_for("uvx"), Some(RuntimeId::Uv));
+    assert_eq!(runtime_for("python3"), None);
+}
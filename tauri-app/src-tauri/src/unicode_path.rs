@@ -0,0 +1,82 @@
+//! Matching and naming paths that aren't plain ASCII
+//!
+//! `~/.claude.json` keys projects by the path Claude Code was started in, and that string
+//! doesn't always match the one the UI hands us for the same folder: macOS file dialogs
+//! return decomposed (NFD) names where the shell gave composed (NFC) ones, which breaks
+//! CJK and accented usernames, and Windows paths differ in case and separators. Lookups try
+//! the exact key first and fall back to a normalized comparison; new keys are stored as given.
+
+use serde_json::{Map, Value};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use unicode_normalization::UnicodeNormalization;
+
+/// Windows paths compare case-insensitively and with either separator
+fn is_windows_path(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    path.starts_with(r"\\")
+        || (bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':')
+}
+
+/// Comparison form of a project path: NFC, no trailing separator, and for Windows paths
+/// forward slashes and lowercase
+pub(crate) fn normalize_project_path(path: &str) -> String {
+    let mut normalized: String = path.nfc().collect();
+    if is_windows_path(&normalized) {
+        normalized = normalized.replace('\\', "/").to_lowercase();
+    }
+    while normalized.len() > 1 && normalized.ends_with('/') && !normalized.ends_with(":/") {
+        normalized.pop();
+    }
+    normalized
+}
+
+pub(crate) fn same_project_path(a: &str, b: &str) -> bool {
+    a == b || normalize_project_path(a) == normalize_project_path(b)
+}
+
+/// The key in `projects` that refers to `working_dir`, preferring an exact match
+pub(crate) fn find_project_key<'a>(
+    projects: &'a Map<String, Value>,
+    working_dir: &str,
+) -> Option<&'a str> {
+    if let Some((key, _)) = projects.get_key_value(working_dir) {
+        return Some(key);
+    }
+    let wanted = normalize_project_path(working_dir);
+    projects
+        .keys()
+        .find(|key| normalize_project_path(key) == wanted)
+        .map(|key| key.as_str())
+}
+
+/// The key to read or write `working_dir` under in a config with a `projects` object; the
+/// existing spelling when there is one, otherwise `working_dir` itself
+pub(crate) fn resolve_project_key(config: &Value, working_dir: &str) -> String {
+    config
+        .get("projects")
+        .and_then(|p| p.as_object())
+        .and_then(|projects| find_project_key(projects, working_dir))
+        .unwrap_or(working_dir)
+        .to_string()
+}
+
+/// `<file name>.backup.<suffix>` next to `path`. Built on the OS string so names that
+/// aren't valid UTF-8 survive, and without touching the file's own extension.
+pub(crate) fn backup_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = path.file_name().map(OsString::from).unwrap_or_default();
+    file_name.push(format!(".backup.{}", suffix));
+    path.with_file_name(file_name)
+}
+
+/// Whether `candidate` is a backup `backup_path` made for `path`
+pub(crate) fn is_backup_of(path: &Path, candidate: &Path) -> bool {
+    let (Some(name), Some(candidate)) = (path.file_name(), candidate.file_name()) else {
+        return false;
+    };
+    let mut prefix = OsString::from(name);
+    prefix.push(".backup.");
+    candidate
+        .as_encoded_bytes()
+        .starts_with(prefix.as_encoded_bytes())
+}
@@ -0,0 +1,79 @@
+// Unicode path handling tests
+use crate::unicode_path::{
+    backup_path, find_project_key, is_backup_of, normalize_project_path, resolve_project_key,
+    same_project_path,
+};
+use serde_json::json;
+use std::path::Path;
+
+// "がくせい" composed (NFC) and decomposed (NFD, as macOS file dialogs return it)
+const NFC_USER: &str = "/Users/\u{304C}\u{304F}\u{305B}\u{3044}/app";
+const NFD_USER: &str = "/Users/\u{304B}\u{3099}\u{304F}\u{305B}\u{3044}/app";
+
+#[test]
+fn test_normalize_project_path() {
+    assert_ne!(NFC_USER, NFD_USER);
+    assert_eq!(normalize_project_path(NFD_USER), NFC_USER);
+    assert!(same_project_path(NFC_USER, NFD_USER));
+    assert!(same_project_path("/home/张伟/项目/", "/home/张伟/项目"));
+    assert!(same_project_path(
+        "/home/me/🚀 launch",
+        "/home/me/🚀 launch/"
+    ));
+    assert!(same_project_path(
+        r"C:\Users\田中\Projects\demo",
+        "c:/users/田中/projects/demo/"
+    ));
+    // Unix paths stay case-sensitive
+    assert!(!same_project_path("/home/me/App", "/home/me/app"));
+    assert_eq!(normalize_project_path("/"), "/");
+    assert_eq!(normalize_project_path(r"C:\"), "c:/");
+}
+
+#[test]
+fn test_find_project_key() {
+    let config = json!({
+        "projects": {
+            NFC_USER: { "mcpServers": {} },
+            r"C:\Users\田中\demo": { "mcpServers": {} },
+        }
+    });
+    let projects = config["projects"].as_object().unwrap();
+    assert_eq!(find_project_key(projects, NFD_USER), Some(NFC_USER));
+    assert_eq!(
+        find_project_key(projects, "c:/users/田中/demo"),
+        Some(r"C:\Users\田中\demo")
+    );
+    assert_eq!(find_project_key(projects, "/home/other"), None);
+
+    assert_eq!(resolve_project_key(&config, NFD_USER), NFC_USER);
+    assert_eq!(resolve_project_key(&config, "/home/new"), "/home/new");
+    assert_eq!(resolve_project_key(&json!({}), NFD_USER), NFD_USER);
+}
+
+#[test]
+fn test_find_project_key_prefers_exact_match() {
+    let config = json!({ "projects": { NFD_USER: {}, NFC_USER: {} } });
+    let projects = config["projects"].as_object().unwrap();
+    assert_eq!(find_project_key(projects, NFD_USER), Some(NFD_USER));
+    assert_eq!(find_project_key(projects, NFC_USER), Some(NFC_USER));
+}
+
+#[test]
+fn test_backup_path() {
+    let config = Path::new("/home/김민준/.claude.json");
+    let backup = backup_path(config, "1700000000");
+    assert_eq!(
+        backup,
+        Path::new("/home/김민준/.claude.json.backup.1700000000")
+    );
+    assert!(is_backup_of(config, &backup));
+    assert!(!is_backup_of(
+        config,
+        Path::new("/home/김민준/.claude.json")
+    ));
+    assert_eq!(
+        backup_path(Path::new("/home/me/📁/config.toml"), "1"),
+        Path::new("/home/me/📁/config.toml.backup.1")
+    );
+}
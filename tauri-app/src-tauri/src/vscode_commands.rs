@@ -0,0 +1,294 @@
+//! VS Code's MCP servers in the `ClaudeCodeServer` model
+//!
+//! VS Code reads the `servers` of a workspace's .vscode/mcp.json and of mcp.json in the user
+//! profile. Values may reference `${input:<id>}`, which VS Code prompts for once and stores
+//! itself; the prompts are declared in the file's `inputs`. Adding or updating a server
+//! declares the inputs it references that are missing (as password prompts when they stand
+//! for a secret), and removing one drops the inputs no other server references. These commands
+//! take the same `project_dir` as the Claude Code ones, "Global" meaning the user profile.
+
+use crate::claude_code_commands::{
+    self, is_global_config, AddOutcome, ClaudeCodeResponse, ClaudeCodeServer,
+};
+use crate::client::ClientConfig;
+use crate::client_target::{self, ClientTarget};
+use crate::json_manager::JsonManager;
+use crate::{mcp_crud, redaction};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeSet;
+use tauri::{command, AppHandle, Runtime};
+
+fn vscode_path(project_dir: &str) -> Option<String> {
+    (!is_global_config(project_dir)).then(|| project_dir.to_string())
+}
+
+/// A VS Code entry as a `ClaudeCodeServer`
+pub(crate) fn from_vscode_entry(name: &str, config: &Value) -> Result<ClaudeCodeServer, String> {
+    let mut server = claude_code_commands::parse_server_config(name, config)?;
+    if config["type"].is_null() && server.command.is_none() && server.url.is_some() {
+        server.r#type = "http".to_string();
+    }
+    Ok(server)
+}
+
+/// A `ClaudeCodeServer` as a VS Code entry
+pub(crate) fn to_vscode_entry(server: &ClaudeCodeServer) -> Result<Value, String> {
+    let mut entry = Map::new();
+    entry.insert("type".into(), json!(server.r#type));
+    match server.r#type.as_str() {
+        "stdio" => {
+            let command = server
+                .command
+                .as_ref()
+                .ok_or("Command is required for stdio servers")?;
+            entry.insert("command".into(), json!(command));
+            if let Some(args) = server.args.as_ref().filter(|a| !a.is_empty()) {
+                entry.insert("args".into(), json!(args));
+            }
+            if let Some(env) = server.env.as_ref().filter(|e| !e.is_empty()) {
+                entry.insert("env".into(), json!(env));
+            }
+        }
+        "http" | "sse" => {
+            let url = server
+                .url
+                .as_ref()
+                .ok_or("URL is required for remote servers")?;
+            entry.insert("url".into(), json!(url));
+            if let Some(headers) = server.headers.as_ref().filter(|h| !h.is_empty()) {
+                entry.insert("headers".into(), json!(headers));
+            }
+        }
+        other => return Err(format!("Unsupported server type '{}'", other)),
+    }
+    Ok(Value::Object(entry))
+}
+
+/// Ids of the `${input:<id>}` references in `text`
+fn input_ids(text: &str) -> Vec<String> {
+    text.split("${input:")
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}'))
+        .map(|(id, _)| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .collect()
+}
+
+/// Inputs an entry references, each with the env var or header it is used for, if any
+pub(crate) fn input_refs(entry: &Value) -> Vec<(String, Option<String>)> {
+    let mut refs = Vec::new();
+    for field in ["env", "headers"] {
+        for (key, value) in entry[field].as_object().into_iter().flatten() {
+            let ids = input_ids(value.as_str().unwrap_or_default());
+            refs.extend(ids.into_iter().map(|id| (id, Some(key.clone()))));
+        }
+    }
+    let texts = entry["args"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .chain(std::iter::once(&entry["url"]))
+        .chain(std::iter::once(&entry["command"]))
+        .filter_map(Value::as_str);
+    for text in texts {
+        refs.extend(input_ids(text).into_iter().map(|id| (id, None)));
+    }
+    refs
+}
+
+fn declared_inputs(document: &Value) -> BTreeSet<String> {
+    document["inputs"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|input| input["id"].as_str().map(str::to_string))
+        .collect()
+}
+
+/// Declarations for the inputs `entry` of server `name` references that `document` lacks
+pub(crate) fn missing_inputs(document: &Value, name: &str, entry: &Value) -> Vec<Value> {
+    let mut declared = declared_inputs(document);
+    let rules = redaction::rules();
+    let mut inputs = Vec::new();
+    for (id, key) in input_refs(entry) {
+        if !declared.insert(id.clone()) {
+            continue;
+        }
+        let password =
+            rules.is_secret_key(&id) || key.as_deref().is_some_and(|k| rules.is_secret_key(k));
+        let description = match &key {
+            Some(key) => format!("{} for {}", key, name),
+            None => format!("{} for {}", id, name),
+        };
+        inputs.push(json!({
+            "type": "promptString",
+            "id": id,
+            "description": description,
+            "password": password,
+        }));
+    }
+    inputs
+}
+
+/// Inputs declared in `document` that no server references
+pub(crate) fn unused_inputs(document: &Value) -> BTreeSet<String> {
+    let mut used = BTreeSet::new();
+    for section in ["servers", "__disabled"] {
+        for entry in document[section]
+            .as_object()
+            .into_iter()
+            .flat_map(|s| s.values())
+        {
+            used.extend(input_refs(entry).into_iter().map(|(id, _)| id));
+        }
+    }
+    declared_inputs(document)
+        .into_iter()
+        .filter(|id| !used.contains(id))
+        .collect()
+}
+
+/// Declare the inputs the saved `entry` references, or drop the ones `removed` left unused
+async fn update_inputs(
+    project_dir: &str,
+    name: &str,
+    entry: Option<&Value>,
+    removed: Option<&Value>,
+) -> Result<(), String> {
+    let path = ClientConfig::new("vscode", vscode_path(project_dir).as_deref())
+        .get_path()
+        .to_path_buf();
+    let mut document = JsonManager::read_json_file(&path).await?;
+    let mut inputs: Vec<Value> = document["inputs"].as_array().cloned().unwrap_or_default();
+    let original = inputs.clone();
+    if let Some(entry) = entry {
+        inputs.extend(missing_inputs(&document, name, entry));
+    }
+    if let Some(removed) = removed {
+        let referenced: BTreeSet<String> =
+            input_refs(removed).into_iter().map(|(id, _)| id).collect();
+        let unused = unused_inputs(&document);
+        inputs.retain(|input| {
+            input["id"]
+                .as_str()
+                .map_or(true, |id| !(referenced.contains(id) && unused.contains(id)))
+        });
+    }
+    if inputs == original {
+        return Ok(());
+    }
+    match document.as_object_mut() {
+        Some(fields) if inputs.is_empty() => {
+            fields.shift_remove("inputs");
+        }
+        Some(fields) => {
+            fields.insert("inputs".to_string(), Value::Array(inputs));
+        }
+        None => return Ok(()),
+    }
+    JsonManager::write_json_file(&path, &document).await
+}
+
+async fn find_server(project_dir: &str, name: &str) -> Result<Option<Value>, String> {
+    let target = ClientTarget::client("vscode", vscode_path(project_dir).as_deref());
+    let mut servers = client_target::list_servers(&target).await?;
+    servers.extend(client_target::list_disabled_servers(&target).await?);
+    Ok(servers.get(name).cloned())
+}
+
+/// List the MCP servers of VS Code's user profile ("Global") or of a workspace
+#[command]
+pub async fn vscode_mcp_list(project_dir: String) -> Result<Vec<ClaudeCodeServer>, String> {
+    let target = ClientTarget::client("vscode", vscode_path(&project_dir).as_deref());
+    client_target::list_servers(&target)
+        .await?
+        .iter()
+        .map(|(name, config)| from_vscode_entry(name, config))
+        .collect()
+}
+
+/// Add a server to VS Code; fails when the name is taken
+#[command]
+pub async fn vscode_mcp_add<R: Runtime>(
+    app: AppHandle<R>,
+    request: ClaudeCodeServer,
+    project_dir: String,
+) -> Result<ClaudeCodeResponse, String> {
+    let entry = to_vscode_entry(&request)?;
+    if find_server(&project_dir, &request.name).await?.is_some() {
+        return Err(format!("Server '{}' already exists", request.name));
+    }
+    mcp_crud::add_mcp_server(
+        app,
+        "vscode".to_string(),
+        vscode_path(&project_dir),
+        request.name.clone(),
+        entry.clone(),
+    )
+    .await?;
+    update_inputs(&project_dir, &request.name, Some(&entry), None).await?;
+    Ok(ClaudeCodeResponse {
+        success: true,
+        message: format!(
+            "Server '{}' added to VS Code ({})",
+            request.name, project_dir
+        ),
+        name: Some(request.name),
+        outcome: Some(AddOutcome::Added),
+        warnings: Vec::new(),
+    })
+}
+
+/// Replace an existing VS Code server, active or disabled
+#[command]
+pub async fn vscode_mcp_update(
+    request: ClaudeCodeServer,
+    project_dir: String,
+) -> Result<ClaudeCodeResponse, String> {
+    let entry = to_vscode_entry(&request)?;
+    let previous = find_server(&project_dir, &request.name)
+        .await?
+        .ok_or_else(|| format!("Server '{}' not found", request.name))?;
+    mcp_crud::update_mcp_server(
+        "vscode".to_string(),
+        vscode_path(&project_dir),
+        request.name.clone(),
+        entry.clone(),
+    )
+    .await?;
+    update_inputs(&project_dir, &request.name, Some(&entry), Some(&previous)).await?;
+    Ok(ClaudeCodeResponse {
+        success: true,
+        message: format!(
+            "Server '{}' updated in VS Code ({})",
+            request.name, project_dir
+        ),
+        name: Some(request.name),
+        outcome: Some(AddOutcome::Overwritten),
+        warnings: Vec::new(),
+    })
+}
+
+#[command]
+pub async fn vscode_mcp_remove(
+    name: String,
+    project_dir: String,
+) -> Result<ClaudeCodeResponse, String> {
+    let previous = find_server(&project_dir, &name)
+        .await?
+        .ok_or_else(|| format!("Server '{}' not found", name))?;
+    mcp_crud::remove_mcp_server(
+        "vscode".to_string(),
+        vscode_path(&project_dir),
+        name.clone(),
+    )
+    .await?;
+    update_inputs(&project_dir, &name, None, Some(&previous)).await?;
+    Ok(ClaudeCodeResponse {
+        success: true,
+        message: format!("Server '{}' removed from VS Code ({})", name, project_dir),
+        name: Some(name),
+        outcome: None,
+        warnings: Vec::new(),
+    })
+}
@@ -0,0 +1,66 @@
+// VS Code command tests
+use crate::vscode_commands::{
+    from_vscode_entry, input_refs, missing_inputs, to_vscode_entry, unused_inputs,
+};
+use serde_json::json;
+
+#[test]
+fn test_vscode_entry_round_trip() {
+    let entry = json!({ "type": "stdio", "command": "npx", "args": ["-y", "@x/fs"] });
+    let server = from_vscode_entry("fs", &entry).unwrap();
+    assert_eq!(server.r#type, "stdio");
+    assert_eq!(to_vscode_entry(&server).unwrap(), entry);
+
+    let remote = from_vscode_entry("docs", &json!({ "url": "https://x/mcp" })).unwrap();
+    assert_eq!(remote.r#type, "http");
+    assert_eq!(
+        to_vscode_entry(&remote).unwrap(),
+        json!({ "type": "http", "url": "https://x/mcp" })
+    );
+}
+
+#[test]
+fn test_input_refs_finds_every_reference() {
+    let entry = json!({
+        "command": "npx",
+        "args": ["--workspace", "${input:workspace}"],
+        "env": { "GITHUB_TOKEN": "${input:github-token}", "MODE": "read" },
+    });
+    let refs = input_refs(&entry);
+    assert!(refs.contains(&("github-token".to_string(), Some("GITHUB_TOKEN".to_string()))));
+    assert!(refs.contains(&("workspace".to_string(), None)));
+    assert_eq!(refs.len(), 2);
+}
+
+#[test]
+fn test_missing_inputs_declares_secret_prompts() {
+    let document = json!({
+        "inputs": [{ "type": "promptString", "id": "workspace" }],
+        "servers": {},
+    });
+    let entry = json!({
+        "command": "npx",
+        "args": ["${input:workspace}"],
+        "env": { "GITHUB_TOKEN": "${input:gh}" },
+    });
+    let inputs = missing_inputs(&document, "github", &entry);
+    assert_eq!(inputs.len(), 1);
+    assert_eq!(inputs[0]["id"], "gh");
+    assert_eq!(inputs[0]["password"], true);
+    assert_eq!(inputs[0]["description"], "GITHUB_TOKEN for github");
+}
+
+#[test]
+fn test_unused_inputs() {
+    let document = json!({
+        "inputs": [
+            { "type": "promptString", "id": "gh" },
+            { "type": "promptString", "id": "old" },
+        ],
+        "servers": { "github": { "command": "x", "env": { "T": "${input:gh}" } } },
+    });
+    assert_eq!(
+        unused_inputs(&document).into_iter().collect::<Vec<_>>(),
+        vec!["old".to_string()]
+    );
+}
@@ -0,0 +1,155 @@
+//! Coalescing of file-watch events
+//!
+//! Claude Code and editors rewrite files in bursts (truncate, write, rename over), and notify
+//! reports every step. A `Debouncer` holds the events of a path until the path has been quiet
+//! for its window (`WatchSettings`), merging each new event into the pending one, and then
+//! delivers a single event with the final state. A path that keeps changing is still delivered
+//! every `max_wait_ms`, so a storm is rate limited rather than held back indefinitely. The
+//! windows follow the current settings (see `settings::subscribe`); an event already pending
+//! keeps the window it started with.
+
+use crate::event_bus::scope_matches;
+use crate::settings::{self, AppSettings, WatchSettings};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+struct Pending<T> {
+    value: T,
+    first: Instant,
+    last: Instant,
+    window: Duration,
+}
+
+type Flush<T> = Arc<dyn Fn(PathBuf, T) + Send + Sync>;
+
+pub(crate) struct Debouncer<T> {
+    settings: watch::Receiver<AppSettings>,
+    merge: fn(T, T) -> T,
+    flush: Flush<T>,
+    pending: Arc<Mutex<HashMap<PathBuf, Pending<T>>>>,
+}
+
+/// When a pending event is delivered: after `window` of quiet, but no later than `max_wait`
+/// after its first event
+pub(crate) fn due_at(
+    first: Instant,
+    last: Instant,
+    window: Duration,
+    max_wait: Duration,
+) -> Instant {
+    (last + window).min(first + max_wait.max(window))
+}
+
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), crate::demo_mode::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest).to_string_lossy().to_string(),
+        _ => path.to_string(),
+    }
+}
+
+/// Window of the most specific `paths` entry containing `path`, else `debounce_ms`
+pub(crate) fn window_for(settings: &WatchSettings, path: &Path) -> Duration {
+    let path = path.to_string_lossy();
+    let ms = settings
+        .paths
+        .iter()
+        .map(|entry| (expand_home(&entry.path), entry.debounce_ms))
+        .filter(|(dir, _)| scope_matches(dir, &path))
+        .max_by_key(|(dir, _)| dir.len())
+        .map_or(settings.debounce_ms, |(_, ms)| ms);
+    Duration::from_millis(ms)
+}
+
+/// Kind of a coalesced fs change, given the pending kind and the one that followed
+pub(crate) fn merge_kind(pending: String, next: String) -> String {
+    match (pending.as_str(), next.as_str()) {
+        (_, "access") => pending,
+        ("create", "modify") => pending,
+        // Removed and written again: replaced in place
+        ("remove", "create" | "modify") => "modify".to_string(),
+        _ => next,
+    }
+}
+
+impl<T: Send + 'static> Debouncer<T> {
+    /// `merge` folds a new event into the pending one; `flush` receives the coalesced event
+    pub(crate) fn new(
+        merge: fn(T, T) -> T,
+        flush: impl Fn(PathBuf, T) + Send + Sync + 'static,
+    ) -> Self {
+        Debouncer {
+            settings: settings::subscribe(),
+            merge,
+            flush: Arc::new(flush),
+            pending: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub(crate) fn push(&self, path: PathBuf, value: T) {
+        let window = window_for(&self.settings.borrow().watch, &path);
+        if window.is_zero() {
+            (self.flush)(path, value);
+            return;
+        }
+        let now = Instant::now();
+        let Ok(mut pending) = self.pending.lock() else {
+            return;
+        };
+        match pending.remove(&path) {
+            Some(earlier) => {
+                let merged = Pending {
+                    value: (self.merge)(earlier.value, value),
+                    last: now,
+                    ..earlier
+                };
+                pending.insert(path, merged);
+            }
+            None => {
+                let first = Pending {
+                    value,
+                    first: now,
+                    last: now,
+                    window,
+                };
+                pending.insert(path.clone(), first);
+                drop(pending);
+                self.schedule(path);
+            }
+        }
+    }
+
+    /// Deliver the pending event of `path` once it is due
+    fn schedule(&self, path: PathBuf) {
+        let pending = self.pending.clone();
+        let flush = self.flush.clone();
+        let settings = self.settings.clone();
+        tauri::async_runtime::spawn(async move {
+            loop {
+                let max_wait = Duration::from_millis(settings.borrow().watch.max_wait_ms);
+                let wait = {
+                    let Ok(mut map) = pending.lock() else {
+                        return;
+                    };
+                    let Some(entry) = map.get(&path) else {
+                        return;
+                    };
+                    let due = due_at(entry.first, entry.last, entry.window, max_wait);
+                    let now = Instant::now();
+                    if due <= now {
+                        let Some(entry) = map.remove(&path) else {
+                            return;
+                        };
+                        drop(map);
+                        flush(path, entry.value);
+                        return;
+                    }
+                    due - now
+                };
+                tokio::time::sleep(wait).await;
+            }
+        });
+    }
+}
@@ -0,0 +1,75 @@
+// File-watch debouncing tests
+use crate::settings::{PathDebounce, WatchSettings};
+use crate::watch_debounce::{due_at, merge_kind, window_for};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+fn settings() -> WatchSettings {
+    WatchSettings {
+        debounce_ms: 200,
+        max_wait_ms: 2000,
+        paths: vec![
+            PathDebounce {
+                path: "/home/me/project".to_string(),
+                debounce_ms: 1000,
+            },
+            PathDebounce {
+                path: "/home/me/project/logs".to_string(),
+                debounce_ms: 0,
+            },
+        ],
+    }
+}
+
+#[test]
+fn test_window_for_prefers_most_specific_path() {
+    let settings = settings();
+    let ms = |path: &str| window_for(&settings, Path::new(path)).as_millis();
+    assert_eq!(ms("/home/me/project/src/main.rs"), 1000);
+    assert_eq!(ms("/home/me/project/logs/today.log"), 0);
+    assert_eq!(ms("/home/me/project-two/a.json"), 200);
+    assert_eq!(ms("/tmp/a.json"), 200);
+}
+
+#[test]
+fn test_window_for_expands_home() {
+    let Some(home) = dirs::home_dir() else {
+        return;
+    };
+    let settings = WatchSettings::default();
+    assert_eq!(
+        window_for(&settings, &home.join(".claude.json")).as_millis(),
+        500
+    );
+    assert_eq!(
+        window_for(&settings, &home.join(".cursor/mcp.json")).as_millis(),
+        200
+    );
+}
+
+#[test]
+fn test_due_at() {
+    let first = Instant::now();
+    let window = Duration::from_millis(200);
+    let max_wait = Duration::from_millis(2000);
+    // Quiet after the last event
+    let last = first + Duration::from_millis(500);
+    assert_eq!(due_at(first, last, window, max_wait), last + window);
+    // Still changing: capped at max_wait after the first event
+    let last = first + Duration::from_millis(1900);
+    assert_eq!(due_at(first, last, window, max_wait), first + max_wait);
+    // A max_wait shorter than the window doesn't cut the window short
+    let short = Duration::from_millis(50);
+    assert_eq!(due_at(first, first, window, short), first + window);
+}
+
+#[test]
+fn test_merge_kind() {
+    let merge = |a: &str, b: &str| merge_kind(a.to_string(), b.to_string());
+    assert_eq!(merge("create", "modify"), "create");
+    assert_eq!(merge("create", "remove"), "remove");
+    assert_eq!(merge("remove", "create"), "modify");
+    assert_eq!(merge("modify", "access"), "modify");
+    assert_eq!(merge("access", "modify"), "modify");
+    assert_eq!(merge("modify", "remove"), "remove");
+}
@@ -0,0 +1,320 @@
+//! Outgoing webhooks for config events
+//!
+//! Hooks are stored in webhooks.json in the app data dir; their signing secrets live in the
+//! encrypted secrets store. Events are queued and delivered by a background worker, with
+//! retries and exponential backoff. A body is signed with HMAC-SHA256 of the raw JSON in the
+//! `X-MCPLinker-Signature: sha256=<hex>` header when the hook has a secret.
+
+use crate::client_target::ClientTarget;
+use crate::network;
+use crate::op_recorder::RecordedOp;
+use crate::settings::{self, app_config_dir, RetryPolicy};
+use crate::{metrics, secrets};
+use chrono::Utc;
+use once_cell::sync::OnceCell;
+use ring::hmac;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::PathBuf;
+use tauri::command;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+
+static QUEUE: OnceCell<UnboundedSender<Delivery>> = OnceCell::new();
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    /// A server was added or its entry replaced
+    ServerAdded,
+    ServerRemoved,
+    ServerDisabled,
+    ServerEnabled,
+    /// The set of expiring or expired credentials changed. Only credential expiry (see
+    /// `credential_expiry`) raises this; server process and handshake health don't.
+    HealthChanged,
+    /// A drift check found configs that differ from the baseline or desired state
+    DriftDetected,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Webhook {
+    pub id: String,
+    pub url: String,
+    /// Events to deliver; all events when empty
+    #[serde(default)]
+    pub events: Vec<WebhookEvent>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Whether a signing secret is stored for this hook
+    #[serde(default)]
+    pub signed: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct WebhooksFile {
+    #[serde(default)]
+    webhooks: Vec<Webhook>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Delivery {
+    pub webhook: Webhook,
+    pub payload: Value,
+}
+
+fn webhooks_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("webhooks.json"))
+}
+
+fn secret_id(id: &str) -> String {
+    format!("webhook/{}", id)
+}
+
+fn read_webhooks() -> Result<WebhooksFile, String> {
+    let path = webhooks_path()?;
+    match fs::read_to_string(&path) {
+        Ok(content) => {
+            serde_json::from_str(&content).map_err(|e| format!("Failed to parse webhooks: {}", e))
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(WebhooksFile::default()),
+        Err(e) => Err(format!("Failed to read webhooks: {}", e)),
+    }
+}
+
+fn write_webhooks(file: &WebhooksFile) -> Result<(), String> {
+    let path = webhooks_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(file)
+        .map_err(|e| format!("Failed to serialize webhooks: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write webhooks: {}", e))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub(crate) fn sign(secret: &str, body: &[u8]) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    format!("sha256={}", to_hex(hmac::sign(&key, body).as_ref()))
+}
+
+/// Whether `webhook` gets `event`
+pub(crate) fn subscribed(webhook: &Webhook, event: WebhookEvent) -> bool {
+    webhook.enabled && (webhook.events.is_empty() || webhook.events.contains(&event))
+}
+
+/// Queue `event` for every enabled hook subscribed to it
+pub(crate) fn emit(event: WebhookEvent, data: Value) {
+    let Some(queue) = QUEUE.get() else {
+        return;
+    };
+    let webhooks = match read_webhooks() {
+        Ok(file) => file.webhooks,
+        Err(e) => {
+            println!("[Webhooks] {}", e);
+            return;
+        }
+    };
+    let payload = json!({
+        "event": event,
+        "occurred_at": Utc::now().to_rfc3339(),
+        "data": data,
+    });
+    for webhook in webhooks.into_iter().filter(|w| subscribed(w, event)) {
+        let _ = queue.send(Delivery {
+            webhook,
+            payload: payload.clone(),
+        });
+    }
+}
+
+/// Queue the event for a command-level server mutation
+pub(crate) fn emit_mutation(target: &ClientTarget, op: &RecordedOp) {
+    let (event, name) = match op {
+        RecordedOp::UpsertServer { name, .. } => (WebhookEvent::ServerAdded, name),
+        RecordedOp::RemoveServer { name } => (WebhookEvent::ServerRemoved, name),
+        RecordedOp::DisableServer { name } => (WebhookEvent::ServerDisabled, name),
+        RecordedOp::EnableServer { name } => (WebhookEvent::ServerEnabled, name),
+    };
+    // Server configs can hold secrets, so only names are sent
+    emit(
+        event,
+        json!({
+            "client": target.client,
+            "working_dir": target.working_dir,
+            "path": target.path,
+            "server": name,
+        }),
+    );
+}
+
+async fn deliver_once(client: &reqwest::Client, delivery: &Delivery) -> Result<(), String> {
+    let body = serde_json::to_vec(&delivery.payload)
+        .map_err(|e| format!("Failed to serialize payload: {}", e))?;
+    let mut request = client
+        .post(&delivery.webhook.url)
+        .header("Content-Type", "application/json")
+        .header("User-Agent", "mcp-linker-webhooks");
+    if delivery.webhook.signed {
+        if let Some(secret) = secrets::get_secret(&secret_id(&delivery.webhook.id))? {
+            request = request.header("X-MCPLinker-Signature", sign(&secret, &body));
+        }
+    }
+    let response = request
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("HTTP {}", response.status()))
+    }
+}
+
+pub(crate) async fn deliver(
+    client: reqwest::Client,
+    delivery: Delivery,
+    retry: &RetryPolicy,
+) -> Result<(), String> {
+    let attempts = retry.max_attempts.max(1);
+    let mut last_error = String::new();
+    for attempt in 0..attempts {
+        if attempt > 0 {
+            tokio::time::sleep(network::backoff_delay(retry, attempt)).await;
+        }
+        match deliver_once(&client, &delivery).await {
+            Ok(()) => return Ok(()),
+            Err(e) => last_error = e,
+        }
+    }
+    Err(format!(
+        "gave up after {} attempts: {}",
+        attempts, last_error
+    ))
+}
+
+/// Start the delivery worker. Events emitted before it runs are dropped.
+pub fn spawn_webhook_worker() {
+    let (sender, mut receiver) = unbounded_channel::<Delivery>();
+    if QUEUE.set(sender).is_err() {
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        while let Some(delivery) = receiver.recv().await {
+            // Settings are read per delivery, so changed timeouts apply right away
+            let network = settings::load_settings().network;
+            let client = match network::http_client(&network) {
+                Ok(client) => client,
+                Err(e) => {
+                    println!("[Webhooks] {}", e);
+                    continue;
+                }
+            };
+            // Retries of one hook don't hold up the others
+            tauri::async_runtime::spawn(async move {
+                let id = delivery.webhook.id.clone();
+                if let Err(e) = deliver(client, delivery, &network.retry).await {
+                    metrics::inc(metrics::WEBHOOK_FAILURES, &[]);
+                    println!("[Webhooks] Delivery to '{}' {}", id, e);
+                }
+            });
+        }
+    });
+}
+
+#[command]
+pub async fn list_webhooks() -> Result<Vec<Webhook>, String> {
+    Ok(read_webhooks()?.webhooks)
+}
+
+/// Check the id and URL of a hook before it's saved
+pub(crate) fn check_webhook(id: &str, url: &str) -> Result<(), String> {
+    if id.trim().is_empty() {
+        return Err("Webhook id is required".to_string());
+    }
+    let parsed = url::Url::parse(url).map_err(|e| format!("Invalid webhook URL: {}", e))?;
+    if !matches!(parsed.scheme(), "https" | "http") {
+        return Err("Webhook URL must use http or https".to_string());
+    }
+    Ok(())
+}
+
+/// Create or update a hook. `secret` replaces the signing secret; an empty string removes it
+/// and omitting it keeps the current one.
+#[command]
+pub async fn save_webhook(
+    id: String,
+    url: String,
+    events: Vec<WebhookEvent>,
+    enabled: Option<bool>,
+    secret: Option<String>,
+) -> Result<Webhook, String> {
+    check_webhook(&id, &url)?;
+
+    let mut file = read_webhooks()?;
+    let existing = file.webhooks.iter().find(|w| w.id == id).cloned();
+    let signed = match secret.as_deref() {
+        Some("") => {
+            secrets::remove_secret(&secret_id(&id))?;
+            false
+        }
+        Some(secret) => {
+            secrets::put_secret(&secret_id(&id), secret)?;
+            true
+        }
+        None => existing.as_ref().is_some_and(|w| w.signed),
+    };
+    let webhook = Webhook {
+        id: id.clone(),
+        url,
+        events,
+        enabled: enabled
+            .or(existing.as_ref().map(|w| w.enabled))
+            .unwrap_or(true),
+        signed,
+    };
+    match file.webhooks.iter_mut().find(|w| w.id == id) {
+        Some(slot) => *slot = webhook.clone(),
+        None => file.webhooks.push(webhook.clone()),
+    }
+    write_webhooks(&file)?;
+    Ok(webhook)
+}
+
+#[command]
+pub async fn delete_webhook(id: String) -> Result<bool, String> {
+    let mut file = read_webhooks()?;
+    let before = file.webhooks.len();
+    file.webhooks.retain(|w| w.id != id);
+    if file.webhooks.len() == before {
+        return Ok(false);
+    }
+    write_webhooks(&file)?;
+    secrets::remove_secret(&secret_id(&id))?;
+    Ok(true)
+}
+
+/// Send a `ping` event to one hook right away, without retries
+#[command]
+pub async fn test_webhook(id: String) -> Result<(), String> {
+    let webhook = read_webhooks()?
+        .webhooks
+        .into_iter()
+        .find(|w| w.id == id)
+        .ok_or_else(|| format!("Webhook '{}' not found", id))?;
+    let client = network::http_client(&settings::load_settings().network)?;
+    let delivery = Delivery {
+        webhook,
+        payload: json!({ "event": "ping", "occurred_at": Utc::now().to_rfc3339() }),
+    };
+    deliver_once(&client, &delivery).await
+}
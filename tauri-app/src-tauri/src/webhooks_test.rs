@@ -0,0 +1,147 @@
+// Webhook signing, filtering and delivery tests
+use crate::settings::RetryPolicy;
+use crate::webhooks::{check_webhook, deliver, sign, subscribed, Delivery, Webhook, WebhookEvent};
+use serde_json::json;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+fn webhook(url: &str, events: Vec<WebhookEvent>, enabled: bool) -> Webhook {
+    Webhook {
+        id: "hook".to_string(),
+        url: url.to_string(),
+        events,
+        enabled,
+        signed: false,
+    }
+}
+
+/// Answer every request with 500 until `failures` requests were seen, then with 200
+fn serve(failures: usize) -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    let seen = Arc::new(AtomicUsize::new(0));
+    let counter = seen.clone();
+    std::thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while let Ok(n) = stream.read(&mut buf) {
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                    let length = head
+                        .lines()
+                        .find_map(|l| {
+                            l.to_ascii_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|v| v.trim().parse::<usize>().unwrap_or(0))
+                        })
+                        .unwrap_or(0);
+                    if body.len() >= length {
+                        break;
+                    }
+                }
+                if n == 0 {
+                    break;
+                }
+            }
+            let status = if counter.fetch_add(1, Ordering::SeqCst) < failures {
+                "500 Internal Server Error"
+            } else {
+                "200 OK"
+            };
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                status
+            );
+        }
+    });
+    (url, seen)
+}
+
+/// Loopback requests must not go through a proxy from the environment
+fn client() -> reqwest::Client {
+    reqwest::Client::builder().no_proxy().build().unwrap()
+}
+
+fn fast_retry(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy {
+        max_attempts,
+        initial_backoff_ms: 10,
+        max_backoff_ms: 20,
+    }
+}
+
+#[test]
+fn test_signature_matches_rfc4231_vector() {
+    assert_eq!(
+        sign("Jefe", b"what do ya want for nothing?"),
+        "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    );
+}
+
+#[test]
+fn test_event_filtering() {
+    let all = webhook("https://hooks.example/all", Vec::new(), true);
+    assert!(subscribed(&all, WebhookEvent::ServerAdded));
+    assert!(subscribed(&all, WebhookEvent::DriftDetected));
+
+    let removals = webhook(
+        "https://hooks.example/removals",
+        vec![WebhookEvent::ServerRemoved],
+        true,
+    );
+    assert!(subscribed(&removals, WebhookEvent::ServerRemoved));
+    assert!(!subscribed(&removals, WebhookEvent::ServerAdded));
+
+    let disabled = webhook("https://hooks.example/off", Vec::new(), false);
+    assert!(!subscribed(&disabled, WebhookEvent::ServerAdded));
+}
+
+#[test]
+fn test_check_webhook() {
+    assert!(check_webhook("slack", "https://hooks.example/services/1").is_ok());
+    assert!(check_webhook("local", "http://localhost:8080/hook").is_ok());
+    assert_eq!(
+        check_webhook("  ", "https://hooks.example").unwrap_err(),
+        "Webhook id is required"
+    );
+    assert!(check_webhook("slack", "not a url")
+        .unwrap_err()
+        .starts_with("Invalid webhook URL"));
+    assert_eq!(
+        check_webhook("ftp", "ftp://hooks.example/upload").unwrap_err(),
+        "Webhook URL must use http or https"
+    );
+}
+
+#[tokio::test]
+async fn test_delivery_retries_until_success() {
+    let (url, seen) = serve(2);
+    let delivery = Delivery {
+        webhook: webhook(&url, Vec::new(), true),
+        payload: json!({ "event": "server_added" }),
+    };
+    deliver(client(), delivery, &fast_retry(4)).await.unwrap();
+    assert_eq!(seen.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_delivery_gives_up_after_max_attempts() {
+    let (url, seen) = serve(usize::MAX);
+    let delivery = Delivery {
+        webhook: webhook(&url, Vec::new(), true),
+        payload: json!({ "event": "server_added" }),
+    };
+    let error = deliver(client(), delivery, &fast_retry(3))
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        "gave up after 3 attempts: HTTP 500 Internal Server Error"
+    );
+    assert_eq!(seen.load(Ordering::SeqCst), 3);
+}
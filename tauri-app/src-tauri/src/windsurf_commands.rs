@@ -0,0 +1,160 @@
+//! Windsurf's MCP servers in the `ClaudeCodeServer` model
+//!
+//! Windsurf has a single user config, ~/.codeium/windsurf/mcp_config.json (see
+//! `ClientConfig`). Its entries have no `type`, and remote servers are reached through
+//! `serverUrl` rather than `url`. `entry_from_windsurf` and `entry_to_windsurf` convert
+//! between that shape and the `url` one of the other clients, for copy and sync; the commands
+//! write through the generic client CRUD so warm-up and recording apply.
+
+use crate::claude_code_commands::{self, AddOutcome, ClaudeCodeResponse, ClaudeCodeServer};
+use crate::client_target::{self, ClientTarget};
+use crate::mcp_crud;
+use serde_json::{json, Map, Value};
+use tauri::{command, AppHandle, Runtime};
+
+/// A Windsurf entry with `url` in place of `serverUrl`
+pub(crate) fn entry_from_windsurf(config: &Value) -> Value {
+    let mut entry = config.clone();
+    if let Some(fields) = entry.as_object_mut() {
+        if let Some(url) = fields.shift_remove("serverUrl") {
+            fields.entry("url").or_insert(url);
+        }
+    }
+    entry
+}
+
+/// An entry of another client in Windsurf's shape: `serverUrl`, no `type`
+pub(crate) fn entry_to_windsurf(config: &Value) -> Value {
+    let mut entry = config.clone();
+    if let Some(fields) = entry.as_object_mut() {
+        fields.shift_remove("type");
+        if let Some(url) = fields.shift_remove("url") {
+            fields.entry("serverUrl").or_insert(url);
+        }
+    }
+    entry
+}
+
+/// A Windsurf entry as a `ClaudeCodeServer`
+pub(crate) fn from_windsurf_entry(name: &str, config: &Value) -> Result<ClaudeCodeServer, String> {
+    let entry = entry_from_windsurf(config);
+    let mut server = claude_code_commands::parse_server_config(name, &entry)?;
+    if server.command.is_none() && server.url.is_some() {
+        server.r#type = "http".to_string();
+    }
+    Ok(server)
+}
+
+/// A `ClaudeCodeServer` as a Windsurf entry
+pub(crate) fn to_windsurf_entry(server: &ClaudeCodeServer) -> Result<Value, String> {
+    let mut entry = Map::new();
+    match server.r#type.as_str() {
+        "stdio" => {
+            let command = server
+                .command
+                .as_ref()
+                .ok_or("Command is required for stdio servers")?;
+            entry.insert("command".into(), json!(command));
+            if let Some(args) = server.args.as_ref().filter(|a| !a.is_empty()) {
+                entry.insert("args".into(), json!(args));
+            }
+            if let Some(env) = server.env.as_ref().filter(|e| !e.is_empty()) {
+                entry.insert("env".into(), json!(env));
+            }
+        }
+        "http" | "sse" => {
+            let url = server
+                .url
+                .as_ref()
+                .ok_or("URL is required for remote servers")?;
+            entry.insert("serverUrl".into(), json!(url));
+            if let Some(headers) = server.headers.as_ref().filter(|h| !h.is_empty()) {
+                entry.insert("headers".into(), json!(headers));
+            }
+        }
+        other => return Err(format!("Unsupported server type '{}'", other)),
+    }
+    Ok(Value::Object(entry))
+}
+
+fn windsurf_target() -> ClientTarget {
+    ClientTarget::client("windsurf", None)
+}
+
+async fn exists(name: &str) -> Result<bool, String> {
+    let target = windsurf_target();
+    Ok(client_target::list_servers(&target)
+        .await?
+        .contains_key(name)
+        || client_target::list_disabled_servers(&target)
+            .await?
+            .contains_key(name))
+}
+
+#[command]
+pub async fn windsurf_mcp_list() -> Result<Vec<ClaudeCodeServer>, String> {
+    client_target::list_servers(&windsurf_target())
+        .await?
+        .iter()
+        .map(|(name, config)| from_windsurf_entry(name, config))
+        .collect()
+}
+
+/// Add a server to Windsurf; fails when the name is taken
+#[command]
+pub async fn windsurf_mcp_add<R: Runtime>(
+    app: AppHandle<R>,
+    request: ClaudeCodeServer,
+) -> Result<ClaudeCodeResponse, String> {
+    let entry = to_windsurf_entry(&request)?;
+    if exists(&request.name).await? {
+        return Err(format!("Server '{}' already exists", request.name));
+    }
+    mcp_crud::add_mcp_server(
+        app,
+        "windsurf".to_string(),
+        None,
+        request.name.clone(),
+        entry,
+    )
+    .await?;
+    Ok(ClaudeCodeResponse {
+        success: true,
+        message: format!("Server '{}' added to Windsurf", request.name),
+        name: Some(request.name),
+        outcome: Some(AddOutcome::Added),
+        warnings: Vec::new(),
+    })
+}
+
+/// Replace an existing Windsurf server, active or disabled
+#[command]
+pub async fn windsurf_mcp_update(request: ClaudeCodeServer) -> Result<ClaudeCodeResponse, String> {
+    let entry = to_windsurf_entry(&request)?;
+    if !exists(&request.name).await? {
+        return Err(format!("Server '{}' not found", request.name));
+    }
+    mcp_crud::update_mcp_server("windsurf".to_string(), None, request.name.clone(), entry).await?;
+    Ok(ClaudeCodeResponse {
+        success: true,
+        message: format!("Server '{}' updated in Windsurf", request.name),
+        name: Some(request.name),
+        outcome: Some(AddOutcome::Overwritten),
+        warnings: Vec::new(),
+    })
+}
+
+#[command]
+pub async fn windsurf_mcp_remove(name: String) -> Result<ClaudeCodeResponse, String> {
+    if !exists(&name).await? {
+        return Err(format!("Server '{}' not found", name));
+    }
+    mcp_crud::remove_mcp_server("windsurf".to_string(), None, name.clone()).await?;
+    Ok(ClaudeCodeResponse {
+        success: true,
+        message: format!("Server '{}' removed from Windsurf", name),
+        name: Some(name),
+        outcome: None,
+        warnings: Vec::new(),
+    })
+}
@@ -0,0 +1,60 @@
+// Windsurf command tests
+use crate::server_copy::entry_for;
+use crate::windsurf_commands::{
+    entry_from_windsurf, entry_to_windsurf, from_windsurf_entry, to_windsurf_entry,
+};
+use serde_json::json;
+
+#[test]
+fn test_windsurf_remote_entries_use_server_url() {
+    let server = from_windsurf_entry(
+        "docs",
+        &json!({ "serverUrl": "https://x/mcp", "headers": {} }),
+    )
+    .unwrap();
+    assert_eq!(server.r#type, "http");
+    assert_eq!(server.url.as_deref(), Some("https://x/mcp"));
+    assert_eq!(
+        to_windsurf_entry(&server).unwrap(),
+        json!({ "serverUrl": "https://x/mcp" })
+    );
+}
+
+#[test]
+fn test_windsurf_stdio_entry_round_trip() {
+    let entry = json!({ "command": "npx", "args": ["-y", "@x/fs"] });
+    let server = from_windsurf_entry("fs", &entry).unwrap();
+    assert_eq!(server.r#type, "stdio");
+    assert_eq!(to_windsurf_entry(&server).unwrap(), entry);
+}
+
+#[test]
+fn test_entry_shape_conversion() {
+    let claude_code = json!({ "type": "http", "url": "https://x/mcp" });
+    let windsurf = entry_to_windsurf(&claude_code);
+    assert_eq!(windsurf, json!({ "serverUrl": "https://x/mcp" }));
+    assert_eq!(
+        entry_from_windsurf(&windsurf),
+        json!({ "url": "https://x/mcp" })
+    );
+}
+
+#[test]
+fn test_copy_entries_to_and_from_windsurf() {
+    assert_eq!(
+        entry_for(
+            "windsurf",
+            &json!({ "type": "sse", "url": "https://x/sse" })
+        )
+        .unwrap(),
+        json!({ "serverUrl": "https://x/sse" })
+    );
+    assert_eq!(
+        entry_for(
+            "claude_code",
+            &json!({ "serverUrl": "https://x/mcp", "disabled": false })
+        )
+        .unwrap(),
+        json!({ "url": "https://x/mcp" })
+    );
+}
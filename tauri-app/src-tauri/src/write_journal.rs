@@ -0,0 +1,465 @@
+//! Write-ahead journal for config writes
+//!
+//! Before a managed config file is written, its current content and the content about to be
+//! written are saved to write_journal.json in the app data dir and synced to disk. The record
+//! is dropped once the write returns, so one left over from an earlier run means the app
+//! stopped mid-write, e.g. on power loss. Multi-step operations (script replays, client
+//! syncs) are journaled as a whole: their planned steps are saved up front, finished steps
+//! are counted, and every file they touch keeps its content from before the operation. Only
+//! writes made from inside `Batch::run` join a batch; commands running at the same time keep
+//! their own records.
+//!
+//! `get_interrupted_operations` lists what earlier runs left behind and
+//! `resolve_interrupted_operation` completes one (finishing the cut-off write and running the
+//! remaining steps), rolls it back to the saved contents, or discards the record. The startup
+//! check (`startup_check`) reports them, after dropping records whose writes had all landed.
+
+use crate::op_recorder::{self, RecordedStep};
+use crate::settings::app_config_dir;
+use crate::{claude_scan, disk_space};
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::future::Future;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tauri::command;
+
+/// Identifies this run, so its own open records aren't reported as interrupted
+static SESSION: Lazy<String> = Lazy::new(|| Utc::now().timestamp_millis().to_string());
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+/// Serializes read-modify-write cycles on the journal file
+static LOCK: Mutex<()> = Mutex::new(());
+
+tokio::task_local! {
+    /// Record id of the batch the current task runs in (see `Batch::run`)
+    static CURRENT_BATCH: String;
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FileSnapshot {
+    pub path: PathBuf,
+    /// Content before the operation, `None` when the file didn't exist
+    pub before: Option<String>,
+    /// Content the operation last meant to write
+    pub after: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JournalEntry {
+    pub id: String,
+    pub session: String,
+    pub label: String,
+    pub started_at: String,
+    #[serde(default)]
+    pub steps: Vec<RecordedStep>,
+    #[serde(default)]
+    pub completed: usize,
+    #[serde(default)]
+    pub files: Vec<FileSnapshot>,
+    /// File whose write had started but not returned
+    #[serde(default)]
+    pub in_flight: Option<PathBuf>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct JournalFile {
+    #[serde(default)]
+    entries: Vec<JournalEntry>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct InterruptedOperation {
+    #[serde(flatten)]
+    pub entry: JournalEntry,
+    /// False when there is nothing left to finish, e.g. an operation without recorded steps
+    /// that was between writes
+    pub can_complete: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Resolution {
+    Complete,
+    Rollback,
+    Discard,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ResolutionReport {
+    pub id: String,
+    pub resolution: Resolution,
+    pub files_written: Vec<String>,
+    pub steps_applied: usize,
+    pub errors: Vec<String>,
+}
+
+fn journal_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("write_journal.json"))
+}
+
+fn read_journal() -> Result<JournalFile, String> {
+    let path = journal_path()?;
+    match fs::read_to_string(&path) {
+        Ok(content) => {
+            serde_json::from_str(&content).map_err(|e| format!("Failed to parse journal: {}", e))
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(JournalFile::default()),
+        Err(e) => Err(format!("Failed to read journal: {}", e)),
+    }
+}
+
+/// Write through a synced temp file, so the journal itself is never half-written
+fn write_journal(file: &JournalFile) -> Result<(), String> {
+    let path = journal_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(file)
+        .map_err(|e| format!("Failed to serialize journal: {}", e))?;
+    disk_space::ensure_space(&path, content.len() as u64)?;
+    let tmp_path = path.with_extension("json.tmp");
+    let mut tmp =
+        fs::File::create(&tmp_path).map_err(|e| format!("Failed to write journal: {}", e))?;
+    tmp.write_all(content.as_bytes())
+        .and_then(|_| tmp.sync_all())
+        .map_err(|e| format!("Failed to write journal: {}", e))?;
+    fs::rename(&tmp_path, &path).map_err(|e| format!("Failed to write journal: {}", e))
+}
+
+fn update<T>(change: impl FnOnce(&mut Vec<JournalEntry>) -> T) -> Result<T, String> {
+    let _guard = LOCK
+        .lock()
+        .map_err(|e| format!("Failed to lock journal: {}", e))?;
+    let mut file = read_journal()?;
+    let result = change(&mut file.entries);
+    write_journal(&file)?;
+    Ok(result)
+}
+
+fn new_entry(label: String, steps: Vec<RecordedStep>) -> JournalEntry {
+    JournalEntry {
+        id: format!(
+            "{}-{}",
+            Utc::now().timestamp_millis(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ),
+        session: SESSION.clone(),
+        label,
+        started_at: Utc::now().to_rfc3339(),
+        steps,
+        completed: 0,
+        files: Vec::new(),
+        in_flight: None,
+    }
+}
+
+/// Note the write of `after` to `path`. The first snapshot of a file keeps its `before`, so
+/// a rollback restores the state from before the whole operation.
+pub(crate) fn record_write(
+    entry: &mut JournalEntry,
+    path: &Path,
+    before: Option<String>,
+    after: &str,
+) {
+    match entry.files.iter_mut().find(|f| f.path == path) {
+        Some(snapshot) => snapshot.after = after.to_string(),
+        None => entry.files.push(FileSnapshot {
+            path: path.to_path_buf(),
+            before,
+            after: after.to_string(),
+        }),
+    }
+    entry.in_flight = Some(path.to_path_buf());
+}
+
+pub(crate) fn can_complete(entry: &JournalEntry) -> bool {
+    entry.in_flight.is_some() || entry.completed < entry.steps.len()
+}
+
+/// A journaled write; call `finish` once the write has returned
+pub(crate) struct PendingWrite {
+    id: Option<String>,
+    in_batch: bool,
+}
+
+/// Journal that `after` is about to be written to `path`. Journal failures are logged and
+/// don't block the write.
+pub(crate) fn begin_write(path: &Path, after: &str) -> PendingWrite {
+    let before = match fs::read_to_string(path) {
+        Ok(content) => Some(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => {
+            println!(
+                "[Journal] Not journaling {}: failed to read it: {}",
+                path.display(),
+                e
+            );
+            return PendingWrite {
+                id: None,
+                in_batch: false,
+            };
+        }
+    };
+    let batch = current_batch();
+    let in_batch = batch.is_some();
+    let result = update(|entries| {
+        let index = batch
+            .as_ref()
+            .and_then(|id| entries.iter().position(|e| e.id == *id))
+            .unwrap_or_else(|| {
+                entries.push(new_entry(format!("Write {}", path.display()), Vec::new()));
+                entries.len() - 1
+            });
+        record_write(&mut entries[index], path, before, after);
+        entries[index].id.clone()
+    });
+    match result {
+        Ok(id) => PendingWrite {
+            id: Some(id),
+            in_batch,
+        },
+        Err(e) => {
+            println!("[Journal] Not journaling {}: {}", path.display(), e);
+            PendingWrite {
+                id: None,
+                in_batch: false,
+            }
+        }
+    }
+}
+
+impl PendingWrite {
+    pub(crate) fn finish(self) {
+        let Some(id) = self.id else {
+            return;
+        };
+        let result = update(|entries| {
+            if self.in_batch {
+                if let Some(entry) = entries.iter_mut().find(|e| e.id == id) {
+                    entry.in_flight = None;
+                }
+            } else {
+                entries.retain(|e| e.id != id);
+            }
+        });
+        if let Err(e) = result {
+            println!("[Journal] Failed to close record {}: {}", id, e);
+        }
+    }
+}
+
+/// Write `contents` to `path` under the journal
+pub(crate) fn write(path: &Path, contents: &str) -> std::io::Result<()> {
+    let pending = begin_write(path, contents);
+    let result = fs::write(path, contents);
+    pending.finish();
+    result
+}
+
+fn current_batch() -> Option<String> {
+    CURRENT_BATCH.try_with(|id| id.clone()).ok()
+}
+
+/// Wrap `f` so it runs in the current task's batch, for work moved to `spawn_blocking`
+pub(crate) fn carry_batch<T>(f: impl FnOnce() -> T) -> impl FnOnce() -> T {
+    let batch = current_batch();
+    move || match batch {
+        Some(id) => CURRENT_BATCH.sync_scope(id, f),
+        None => f(),
+    }
+}
+
+/// A multi-step operation; its record is dropped with it
+pub(crate) struct Batch {
+    id: Option<String>,
+}
+
+/// Journal an operation made of `steps`, or of untracked steps when empty. Called from a
+/// task already running in a batch, this returns a no-op batch and the writes join the outer
+/// record.
+pub(crate) fn begin_batch(label: &str, steps: Vec<RecordedStep>) -> Batch {
+    if current_batch().is_some() {
+        return Batch { id: None };
+    }
+    let entry = new_entry(label.to_string(), steps);
+    let id = entry.id.clone();
+    match update(|entries| entries.push(entry)) {
+        Ok(()) => Batch { id: Some(id) },
+        Err(e) => {
+            println!("[Journal] Not journaling '{}': {}", label, e);
+            Batch { id: None }
+        }
+    }
+}
+
+impl Batch {
+    /// Run `f` with its writes joining this batch. A no-op batch runs it unchanged.
+    pub(crate) async fn run<F: Future>(&self, f: F) -> F::Output {
+        match &self.id {
+            Some(id) => CURRENT_BATCH.scope(id.clone(), f).await,
+            None => f.await,
+        }
+    }
+
+    /// Snapshots of the files written so far; empty for a no-op batch
+    pub(crate) fn files(&self) -> Vec<FileSnapshot> {
+        let Some(id) = &self.id else {
+            return Vec::new();
+        };
+        read_journal()
+            .map(|file| {
+                file.entries
+                    .into_iter()
+                    .find(|e| e.id == *id)
+                    .map(|e| e.files)
+                    .unwrap_or_default()
+            })
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn step_done(&self) {
+        let Some(id) = &self.id else {
+            return;
+        };
+        let result = update(|entries| {
+            if let Some(entry) = entries.iter_mut().find(|e| e.id == *id) {
+                entry.completed += 1;
+            }
+        });
+        if let Err(e) = result {
+            println!("[Journal] Failed to update record {}: {}", id, e);
+        }
+    }
+}
+
+impl Drop for Batch {
+    fn drop(&mut self) {
+        let Some(id) = self.id.take() else {
+            return;
+        };
+        if let Err(e) = update(|entries| entries.retain(|e| e.id != id)) {
+            println!("[Journal] Failed to close record {}: {}", id, e);
+        }
+    }
+}
+
+fn interrupted() -> Result<Vec<InterruptedOperation>, String> {
+    Ok(read_journal()?
+        .entries
+        .into_iter()
+        .filter(|e| e.session != *SESSION)
+        .map(|entry| InterruptedOperation {
+            can_complete: can_complete(&entry),
+            entry,
+        })
+        .collect())
+}
+
+/// Whether every write of `entry` landed and no step is left, given the files' contents now
+pub(crate) fn has_landed(entry: &JournalEntry, current: impl Fn(&Path) -> Option<String>) -> bool {
+    entry.completed >= entry.steps.len()
+        && entry
+            .files
+            .iter()
+            .all(|f| current(&f.path).as_deref() == Some(f.after.as_str()))
+}
+
+/// Drop records of earlier runs whose writes all landed, i.e. only the cleanup was lost.
+/// Returns how many were dropped and the operations that remain interrupted.
+pub(crate) fn drop_landed() -> Result<(usize, Vec<InterruptedOperation>), String> {
+    let dropped = update(|entries| {
+        let before = entries.len();
+        entries.retain(|e| {
+            e.session == *SESSION || !has_landed(e, |path| fs::read_to_string(path).ok())
+        });
+        before - entries.len()
+    })?;
+    Ok((dropped, interrupted()?))
+}
+
+pub(crate) fn restore(snapshot: &FileSnapshot, content: Option<&str>) -> Result<(), String> {
+    let result = match content {
+        Some(content) => fs::write(&snapshot.path, content),
+        None => match fs::remove_file(&snapshot.path) {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            other => other,
+        },
+    };
+    claude_scan::invalidate(&snapshot.path);
+    result.map_err(|e| format!("Failed to restore {}: {}", snapshot.path.display(), e))
+}
+
+#[command]
+pub async fn get_interrupted_operations() -> Result<Vec<InterruptedOperation>, String> {
+    interrupted()
+}
+
+/// Finish, undo or forget an interrupted operation. Completing rewrites the file that was
+/// being written and runs the steps that hadn't finished; rolling back puts every touched
+/// file back as it was before the operation.
+#[command]
+pub async fn resolve_interrupted_operation(
+    id: String,
+    resolution: Resolution,
+) -> Result<ResolutionReport, String> {
+    let entry = interrupted()?
+        .into_iter()
+        .map(|op| op.entry)
+        .find(|e| e.id == id)
+        .ok_or_else(|| format!("No interrupted operation '{}'", id))?;
+    let mut report = ResolutionReport {
+        id: id.clone(),
+        resolution,
+        files_written: Vec::new(),
+        steps_applied: 0,
+        errors: Vec::new(),
+    };
+    match resolution {
+        Resolution::Complete => {
+            let cut_off = entry
+                .in_flight
+                .as_ref()
+                .and_then(|path| entry.files.iter().find(|f| f.path == *path));
+            if let Some(snapshot) = cut_off {
+                match restore(snapshot, Some(&snapshot.after)) {
+                    Ok(()) => report
+                        .files_written
+                        .push(snapshot.path.display().to_string()),
+                    Err(e) => report.errors.push(e),
+                }
+            }
+            for step in entry.steps.iter().skip(entry.completed) {
+                match op_recorder::replay_step(&step.target, &step.op).await {
+                    Ok(()) => report.steps_applied += 1,
+                    Err(e) => report
+                        .errors
+                        .push(format!("{}: {}", step.target.label(), e)),
+                }
+            }
+        }
+        Resolution::Rollback => {
+            for snapshot in entry.files.iter().rev() {
+                match restore(snapshot, snapshot.before.as_deref()) {
+                    Ok(()) => report
+                        .files_written
+                        .push(snapshot.path.display().to_string()),
+                    Err(e) => report.errors.push(e),
+                }
+            }
+        }
+        Resolution::Discard => {}
+    }
+    update(|entries| entries.retain(|e| e.id != id))?;
+    println!(
+        "[Journal] Resolved '{}' ({:?}, {} error(s))",
+        entry.label,
+        resolution,
+        report.errors.len()
+    );
+    Ok(report)
+}
@@ -0,0 +1,123 @@
+// Write journal tests
+use crate::client_target::ClientTarget;
+use crate::op_recorder::{RecordedOp, RecordedStep};
+use crate::write_journal::{
+    begin_batch, can_complete, has_landed, record_write, write, JournalEntry,
+};
+use std::path::Path;
+
+fn entry(steps: usize) -> JournalEntry {
+    JournalEntry {
+        id: "1-0".to_string(),
+        session: "earlier".to_string(),
+        label: "Replay script".to_string(),
+        started_at: "2026-01-01T00:00:00Z".to_string(),
+        steps: (0..steps)
+            .map(|i| RecordedStep {
+                target: ClientTarget::client("cursor", None),
+                op: RecordedOp::RemoveServer {
+                    name: format!("server-{}", i),
+                },
+                recorded_at: "2026-01-01T00:00:00Z".to_string(),
+            })
+            .collect(),
+        completed: 0,
+        files: Vec::new(),
+        in_flight: None,
+    }
+}
+
+#[test]
+fn test_record_write_keeps_first_before() {
+    let mut entry = entry(0);
+    let path = Path::new("/tmp/mcp.json");
+    record_write(&mut entry, path, Some("{}".to_string()), r#"{"a":1}"#);
+    record_write(
+        &mut entry,
+        path,
+        Some(r#"{"a":1}"#.to_string()),
+        r#"{"a":2}"#,
+    );
+    record_write(&mut entry, Path::new("/tmp/new.json"), None, "{}");
+
+    assert_eq!(entry.files.len(), 2);
+    assert_eq!(entry.files[0].before.as_deref(), Some("{}"));
+    assert_eq!(entry.files[0].after, r#"{"a":2}"#);
+    assert_eq!(entry.files[1].before, None);
+    assert_eq!(entry.in_flight.as_deref(), Some(Path::new("/tmp/new.json")));
+}
+
+#[test]
+fn test_can_complete() {
+    let mut batch = entry(2);
+    assert!(can_complete(&batch));
+    batch.completed = 2;
+    assert!(!can_complete(&batch));
+
+    let mut single = entry(0);
+    assert!(!can_complete(&single));
+    record_write(&mut single, Path::new("/tmp/mcp.json"), None, "{}");
+    assert!(can_complete(&single));
+}
+
+#[test]
+fn test_has_landed() {
+    let mut single = entry(0);
+    record_write(&mut single, Path::new("/tmp/mcp.json"), None, "{}");
+    assert!(has_landed(&single, |_| Some("{}".to_string())));
+    assert!(!has_landed(&single, |_| Some("{".to_string())));
+    assert!(!has_landed(&single, |_| None));
+
+    // Files match, but steps were left
+    let mut batch = entry(1);
+    record_write(&mut batch, Path::new("/tmp/mcp.json"), None, "{}");
+    assert!(!has_landed(&batch, |_| Some("{}".to_string())));
+}
+
+#[tokio::test]
+async fn test_only_writes_inside_the_batch_join_it() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let inside = temp_dir.path().join("inside.json");
+    let outside = temp_dir.path().join("outside.json");
+    let batch = begin_batch("Journal test batch", Vec::new());
+    batch
+        .run(async {
+            write(&inside, "1").unwrap();
+            // Another command writing while the batch is open runs in its own task
+            let other = outside.clone();
+            tokio::spawn(async move { write(&other, "2") })
+                .await
+                .unwrap()
+                .unwrap();
+            // Blocking work carried over keeps the batch
+            let carried = inside.clone();
+            tokio::task::spawn_blocking(crate::write_journal::carry_batch(move || {
+                write(&carried, "3")
+            }))
+            .await
+            .unwrap()
+            .unwrap();
+        })
+        .await;
+
+    let files = batch.files();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].path, inside);
+    assert_eq!(files[0].before, None);
+    assert_eq!(files[0].after, "3");
+}
+
+#[tokio::test]
+async fn test_nested_batches_join_the_outer_one() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join("nested.json");
+    let outer = begin_batch("Journal test outer", Vec::new());
+    outer
+        .run(async {
+            let inner = begin_batch("Journal test inner", Vec::new());
+            inner.run(async { write(&path, "{}").unwrap() }).await;
+            assert!(inner.files().is_empty());
+        })
+        .await;
+    assert_eq!(outer.files().len(), 1);
+}
@@ -0,0 +1,131 @@
+//! In-place edits of one top-level key of a YAML file
+//!
+//! Clients that keep their servers in YAML (Continue, Goose) share the file with settings this
+//! app knows nothing about, and a YAML serializer would drop the comments and reformat everything on
+//! a round trip. An edit replaces only the lines of the top-level block for one key, renders
+//! the new value in its place, and checks that the result parses to the original document
+//! with only that key changed.
+
+use serde_json::{json, Value};
+
+/// The YAML document as JSON, `label` naming it in errors; an empty file is an empty object
+pub(crate) fn parse(content: &str, label: &str) -> Result<Value, String> {
+    if content.trim().is_empty() {
+        return Ok(json!({}));
+    }
+    match serde_norway::from_str::<Value>(content) {
+        Ok(Value::Object(doc)) => Ok(Value::Object(doc)),
+        Ok(_) => Err(format!("{} is not a YAML mapping", label)),
+        Err(e) => Err(format!("Failed to parse {}: {}", label, e)),
+    }
+}
+
+/// Lines of `content` holding the top-level `key` block, without the blank and comment lines
+/// that end it (those belong to the next key)
+fn block_range(lines: &[&str], key: &str) -> Option<(usize, usize)> {
+    let start = lines.iter().position(|line| {
+        line.strip_prefix(key)
+            .is_some_and(|rest| rest.trim_start().starts_with(':'))
+    })?;
+    let mut end = start + 1;
+    while end < lines.len() {
+        let line = lines[end];
+        let inside = line.trim().is_empty()
+            || line.starts_with([' ', '\t', '#'])
+            || (line.starts_with('-') && !line.starts_with("---"));
+        if !inside {
+            break;
+        }
+        end += 1;
+    }
+    while end > start + 1 && (lines[end - 1].trim().is_empty() || lines[end - 1].starts_with('#')) {
+        end -= 1;
+    }
+    Some((start, end))
+}
+
+/// The `key` block for `value`, every line after the first indented by `indent`
+fn render_block(key: &str, value: &Value, indent: &str) -> Result<String, String> {
+    let empty = match value {
+        Value::Array(items) => items.is_empty(),
+        Value::Object(members) => members.is_empty(),
+        _ => false,
+    };
+    if empty {
+        return Ok(format!("{}: {}\n", key, value));
+    }
+    let rendered = serde_norway::to_string(&json!({ key: value }))
+        .map_err(|e| format!("Failed to serialize {}: {}", key, e))?;
+    let mut lines = rendered.lines();
+    let mut block = format!("{}\n", lines.next().unwrap_or_default());
+    for line in lines {
+        block.push_str(indent);
+        block.push_str(line);
+        block.push('\n');
+    }
+    Ok(block)
+}
+
+/// `content` with its top-level `key` block replaced by `value`; a missing block is appended.
+/// serde_norway writes list items at the key's column, so a list takes the indent of the items
+/// in the file, `new_indent` when there are none.
+pub(crate) fn splice(
+    content: &str,
+    key: &str,
+    value: &Value,
+    new_indent: &str,
+) -> Result<String, String> {
+    let original = parse(content, "config")?;
+    let lines: Vec<&str> = content.lines().collect();
+    let list_indent = |items: &[&str]| {
+        items
+            .iter()
+            .find(|line| line.trim_start().starts_with('-'))
+            .map_or(new_indent, |line| {
+                &line[..line.len() - line.trim_start().len()]
+            })
+    };
+    let edited = match block_range(&lines, key) {
+        Some((start, end)) => {
+            let indent = if value.is_array() {
+                list_indent(&lines[start + 1..end])
+            } else {
+                ""
+            };
+            let mut edited: String = lines[..start].iter().map(|l| format!("{}\n", l)).collect();
+            edited.push_str(&render_block(key, value, indent)?);
+            for line in &lines[end..] {
+                edited.push_str(line);
+                edited.push('\n');
+            }
+            if !content.ends_with('\n') && end < lines.len() {
+                edited.pop();
+            }
+            edited
+        }
+        None => {
+            let indent = if value.is_array() { new_indent } else { "" };
+            let separator = if content.is_empty() || content.ends_with('\n') {
+                ""
+            } else {
+                "\n"
+            };
+            format!(
+                "{}{}{}",
+                content,
+                separator,
+                render_block(key, value, indent)?
+            )
+        }
+    };
+
+    // Nothing but `key` may change
+    let mut expected = original;
+    expected[key] = value.clone();
+    let result =
+        parse(&edited, "config").map_err(|e| format!("Edit would leave invalid config: {}", e))?;
+    if result != expected {
+        return Err(format!("Edit would change more than {} of the config", key));
+    }
+    Ok(edited)
+}
@@ -0,0 +1,183 @@
+//! Zed's MCP servers, the `context_servers` of its settings.json
+//!
+//! Zed reads context servers from the user settings.json (~/.config/zed, or %APPDATA%\Zed on
+//! Windows) and from a project's .zed/settings.json. Both are hand-edited files that may hold
+//! comments, so they are changed in place through `jsonc` instead of being rewritten. Custom
+//! entries look like `{ "source": "custom", "command": ..., "args": [...], "env": {...} }`,
+//! remote ones have `url` and `headers`, and older settings nest the launch as
+//! `command: { path, args, env }`. Servers a Zed extension provides have no launch of their own
+//! and are left out of the list.
+
+use crate::claude_code_commands::{
+    self, is_global_config, AddOutcome, ClaudeCodeResponse, ClaudeCodeServer,
+};
+use crate::demo_mode::home_dir;
+use crate::{jsonc, write_journal};
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::command;
+
+const SERVERS_KEY: &str = "context_servers";
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// settings.json of the user ("Global") or of a project
+pub(crate) fn zed_settings_path(project_dir: &str) -> Result<PathBuf, String> {
+    if !is_global_config(project_dir) {
+        return Ok(PathBuf::from(project_dir)
+            .join(".zed")
+            .join("settings.json"));
+    }
+    let dir = if cfg!(target_os = "macos") {
+        home_dir().map(|home| home.join(".config").join("zed"))
+    } else if cfg!(target_os = "windows") {
+        crate::demo_mode::config_dir().map(|config| config.join("Zed"))
+    } else {
+        crate::demo_mode::config_dir().map(|config| config.join("zed"))
+    };
+    Ok(dir
+        .ok_or("Failed to locate the Zed config directory")?
+        .join("settings.json"))
+}
+
+/// A Zed entry as a `ClaudeCodeServer`
+pub(crate) fn from_zed_entry(name: &str, config: &Value) -> Result<ClaudeCodeServer, String> {
+    let mut entry = config.clone();
+    if let Some(launch) = config["command"].as_object() {
+        entry["command"] = launch.get("path").cloned().unwrap_or(Value::Null);
+        entry["args"] = launch.get("args").cloned().unwrap_or(Value::Null);
+        entry["env"] = launch.get("env").cloned().unwrap_or(Value::Null);
+    }
+    if !entry["command"].is_string() && !entry["url"].is_string() {
+        return Err(format!("'{}' is provided by a Zed extension", name));
+    }
+    let mut server = claude_code_commands::parse_server_config(name, &entry)?;
+    if entry["command"].is_null() && entry["type"].is_null() {
+        server.r#type = "http".to_string();
+    }
+    Ok(server)
+}
+
+/// A `ClaudeCodeServer` as a Zed entry
+pub(crate) fn to_zed_entry(server: &ClaudeCodeServer) -> Result<Value, String> {
+    let mut entry = Map::new();
+    match server.r#type.as_str() {
+        "stdio" => {
+            let command = server
+                .command
+                .as_ref()
+                .ok_or("Command is required for stdio servers")?;
+            entry.insert("source".into(), json!("custom"));
+            entry.insert("command".into(), json!(command));
+            entry.insert(
+                "args".into(),
+                json!(server.args.clone().unwrap_or_default()),
+            );
+            entry.insert("env".into(), json!(server.env.clone().unwrap_or_default()));
+        }
+        "http" | "sse" => {
+            let url = server
+                .url
+                .as_ref()
+                .ok_or("URL is required for remote servers")?;
+            entry.insert("url".into(), json!(url));
+            if let Some(headers) = server.headers.as_ref().filter(|h| !h.is_empty()) {
+                entry.insert("headers".into(), json!(headers));
+            }
+        }
+        other => return Err(format!("Unsupported server type '{}'", other)),
+    }
+    Ok(Value::Object(entry))
+}
+
+/// Content of the settings file, `None` when it doesn't exist
+fn read_settings(path: &Path) -> Result<Option<String>, String> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("Failed to read Zed settings: {}", e)),
+    }
+}
+
+fn write_settings(path: &Path, content: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create Zed config directory: {}", e))?;
+    }
+    write_journal::write(path, content).map_err(|e| format!("Failed to write Zed settings: {}", e))
+}
+
+/// List the servers of Zed's user settings ("Global") or of a project's
+#[command]
+pub async fn zed_mcp_list(project_dir: String) -> Result<Vec<ClaudeCodeServer>, String> {
+    let path = zed_settings_path(&project_dir)?;
+    let Some(content) = read_settings(&path)? else {
+        return Ok(Vec::new());
+    };
+    let settings = jsonc::parse(&content)?;
+    let mut servers = Vec::new();
+    for (name, config) in settings[SERVERS_KEY].as_object().into_iter().flatten() {
+        match from_zed_entry(name, config) {
+            Ok(server) => servers.push(server),
+            Err(e) => println!("[Zed] Skipping {}: {}", name, e),
+        }
+    }
+    Ok(servers)
+}
+
+/// Add a server to Zed's settings, leaving the rest of the file untouched; fails when the
+/// name is taken
+#[command]
+pub async fn zed_mcp_add(
+    request: ClaudeCodeServer,
+    project_dir: String,
+) -> Result<ClaudeCodeResponse, String> {
+    let entry = to_zed_entry(&request)?;
+    let path = zed_settings_path(&project_dir)?;
+    let _guard = LOCK
+        .lock()
+        .map_err(|e| format!("Failed to lock Zed settings: {}", e))?;
+    let content = read_settings(&path)?.unwrap_or_else(|| "{}\n".to_string());
+    if jsonc::parse(&content)?[SERVERS_KEY]
+        .get(&request.name)
+        .is_some()
+    {
+        return Err(format!("Server '{}' already exists", request.name));
+    }
+    let edited = jsonc::set_member(&content, &[SERVERS_KEY], &request.name, &entry)?;
+    write_settings(&path, &edited)?;
+    println!("[Zed] Added {} to {}", request.name, path.display());
+    Ok(ClaudeCodeResponse {
+        success: true,
+        message: format!("Server '{}' added to Zed ({})", request.name, project_dir),
+        name: Some(request.name),
+        outcome: Some(AddOutcome::Added),
+        warnings: Vec::new(),
+    })
+}
+
+/// Remove a server from Zed's settings, leaving the rest of the file untouched
+#[command]
+pub async fn zed_mcp_remove(
+    name: String,
+    project_dir: String,
+) -> Result<ClaudeCodeResponse, String> {
+    let path = zed_settings_path(&project_dir)?;
+    let _guard = LOCK
+        .lock()
+        .map_err(|e| format!("Failed to lock Zed settings: {}", e))?;
+    let content = read_settings(&path)?.ok_or_else(|| format!("Server '{}' not found", name))?;
+    let edited = jsonc::remove_member(&content, &[SERVERS_KEY], &name)?
+        .ok_or_else(|| format!("Server '{}' not found", name))?;
+    write_settings(&path, &edited)?;
+    println!("[Zed] Removed {} from {}", name, path.display());
+    Ok(ClaudeCodeResponse {
+        success: true,
+        message: format!("Server '{}' removed from Zed ({})", name, project_dir),
+        name: Some(name),
+        outcome: None,
+        warnings: Vec::new(),
+    })
+}
@@ -0,0 +1,36 @@
+// Zed command tests
+use crate::zed_commands::{from_zed_entry, to_zed_entry};
+use serde_json::json;
+
+#[test]
+fn test_zed_custom_entry_round_trip() {
+    let entry = json!({
+        "source": "custom",
+        "command": "npx",
+        "args": ["-y", "@x/fs"],
+        "env": { "ROOT": "/tmp" }
+    });
+    let server = from_zed_entry("fs", &entry).unwrap();
+    assert_eq!(server.r#type, "stdio");
+    assert_eq!(to_zed_entry(&server).unwrap(), entry);
+}
+
+#[test]
+fn test_zed_legacy_and_remote_entries() {
+    let legacy = from_zed_entry(
+        "old",
+        &json!({ "command": { "path": "uvx", "args": ["mcp-x"], "env": {} }, "settings": {} }),
+    )
+    .unwrap();
+    assert_eq!(legacy.command.as_deref(), Some("uvx"));
+    assert_eq!(legacy.args, Some(vec!["mcp-x".to_string()]));
+
+    let remote = from_zed_entry("docs", &json!({ "url": "https://x/mcp" })).unwrap();
+    assert_eq!(remote.r#type, "http");
+    assert_eq!(
+        to_zed_entry(&remote).unwrap(),
+        json!({ "url": "https://x/mcp" })
+    );
+
+    assert!(from_zed_entry("ext", &json!({ "source": "extension", "settings": {} })).is_err());
+}
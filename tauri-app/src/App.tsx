@@ -1,3 +1,4 @@
+import ConfigLocationPicker from "@/components/claude-code/ConfigLocationPicker";
 import { GlobalDialog } from "@/components/common/GlobalDialog";
 import CommandChecker from "@/components/settings/CommandChecker";
 import { UpdateChecker } from "@/components/UpdateChecker";
@@ -36,6 +37,7 @@ function App() {
           />
           <LicenseNag />
           <UpdateChecker />
+          <ConfigLocationPicker />
         </ThemeProvider>
         <CommandChecker />
       </McpRefreshProvider>
@@ -12,8 +12,12 @@ interface ServerFormData {
   url: string;
   command: string;
   args: string;
+  conflictPolicy: ConflictChoice;
 }
 
+// "default" leaves the choice to the conflict_policy setting
+type ConflictChoice = "default" | "error" | "overwrite" | "keep_both";
+
 interface AddServerDialogProps {
   onAddServer: (formData: ServerFormData) => Promise<boolean>;
   open?: boolean;
@@ -29,7 +33,8 @@ export default function AddServerDialog({ onAddServer, open: controlledOpen, onO
     type: "http",
     url: "",
     command: "",
-    args: ""
+    args: "",
+    conflictPolicy: "default"
   });
 
   const handleAddServer = async () => {
@@ -41,7 +46,8 @@ export default function AddServerDialog({ onAddServer, open: controlledOpen, onO
         type: "http",
         url: "",
         command: "",
-        args: ""
+        args: "",
+        conflictPolicy: "default"
       });
     }
   };
@@ -141,6 +147,26 @@ export default function AddServerDialog({ onAddServer, open: controlledOpen, onO
               </div>
             </>
           )}
+
+          <div className="grid gap-2">
+            <Label htmlFor="conflictPolicy">If the name is taken</Label>
+            <Select
+              value={formData.conflictPolicy}
+              onValueChange={(value: ConflictChoice) =>
+                setFormData(prev => ({ ...prev, conflictPolicy: value }))
+              }
+            >
+              <SelectTrigger id="conflictPolicy">
+                <SelectValue />
+              </SelectTrigger>
+              <SelectContent>
+                <SelectItem value="default">Use my default</SelectItem>
+                <SelectItem value="error">Don't add it</SelectItem>
+                <SelectItem value="overwrite">Replace the existing server</SelectItem>
+                <SelectItem value="keep_both">Keep both (add as name-2)</SelectItem>
+              </SelectContent>
+            </Select>
+          </div>
         </div>
 
         <DialogFooter>
@@ -0,0 +1,119 @@
+import { Button } from "@/components/ui/button";
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from "@/components/ui/dialog";
+import { useCCProjectStore } from "@/stores/ccProject";
+import { useClientPathStore } from "@/stores/clientPathStore";
+import {
+  ClaudeConfigLocation,
+  listClaudeConfigLocations,
+  migrateStoredReferences,
+  setClaudeConfigLocation,
+} from "@/utils/claudeLocationMigration";
+import { useEffect, useState } from "react";
+
+/**
+ * Asks which ~/.claude.json to manage when Windows and WSL both have one and none is chosen
+ * yet; Claude Code commands fail until then. Also runs the stored-reference migration once the
+ * location is known.
+ */
+export function ConfigLocationPicker() {
+  const [locations, setLocations] = useState<ClaudeConfigLocation[]>([]);
+  const [choice, setChoice] = useState<string | null>(null);
+  const [open, setOpen] = useState(false);
+  const [saving, setSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const { setProjects, setSelectedProject } = useCCProjectStore();
+  const { setClientPath } = useClientPathStore();
+
+  const applyLocation = (id: string) => {
+    if (migrateStoredReferences(id)) {
+      // The stored project belongs to another config
+      setClientPath("claude_code", null);
+      setProjects([]);
+      setSelectedProject("");
+    }
+  };
+
+  useEffect(() => {
+    listClaudeConfigLocations()
+      .then((report) => {
+        if (report.needs_choice) {
+          const existing = report.locations.filter((l) => l.exists);
+          setLocations(existing);
+          setChoice(existing[0]?.id ?? null);
+          setOpen(true);
+        } else if (report.selected) {
+          applyLocation(report.selected);
+        }
+      })
+      .catch((e) => console.error("Failed to list Claude Code config locations:", e));
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, []);
+
+  const onConfirm = async () => {
+    if (!choice) return;
+    setSaving(true);
+    setError(null);
+    try {
+      await setClaudeConfigLocation(choice);
+      applyLocation(choice);
+      setOpen(false);
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  if (!open) return null;
+
+  return (
+    // Closing without a choice keeps Claude Code commands failing, so it asks again next start
+    <Dialog open={open} onOpenChange={setOpen}>
+      <DialogContent className="sm:max-w-[560px]">
+        <DialogHeader>
+          <DialogTitle>Choose your Claude Code config</DialogTitle>
+          <DialogDescription>
+            Claude Code is set up in more than one place. Pick the ~/.claude.json MCP Linker
+            should manage; you can change it later.
+          </DialogDescription>
+        </DialogHeader>
+
+        <div className="space-y-2">
+          {locations.map((location) => (
+            <button
+              key={location.id}
+              type="button"
+              onClick={() => setChoice(location.id)}
+              className={`w-full rounded-md border p-3 text-left ${
+                choice === location.id ? "border-primary bg-muted/60" : "bg-muted/20"
+              }`}
+            >
+              <div className="text-sm font-medium">{location.label}</div>
+              <div className="text-xs text-muted-foreground break-all">{location.path}</div>
+            </button>
+          ))}
+        </div>
+
+        {error ? <div className="text-sm text-destructive">{error}</div> : null}
+
+        <DialogFooter>
+          <Button variant="outline" onClick={() => setOpen(false)}>
+            Later
+          </Button>
+          <Button onClick={onConfirm} disabled={!choice || saving}>
+            Use this config
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
+
+export default ConfigLocationPicker;
@@ -249,16 +249,20 @@ export function FileViewer({
     };
   }, [filePath]);
 
-  // Listen to fs_change to detect disk updates for the open file
+  // Subscribe to fs_change events for the open file to detect disk updates
   useEffect(() => {
     let unlisten: UnlistenFn | null = null;
+    let subscription: string | null = null;
     const setup = async () => {
-      unlisten = await listen<{ path: string; kind: string }>(
-        "fs_change",
+      if (!filePath) return;
+      const target = canonicalFile || filePath;
+      subscription = await invoke<string>("subscribe_events", {
+        filter: { kinds: ["fs_change"], scopes: [target] },
+      });
+      unlisten = await listen<{ payload: { path: string; kind: string } }>(
+        `bus:${subscription}`,
         async (event) => {
-          const changed = event.payload.path;
-          if (!filePath) return;
-          const target = canonicalFile || filePath;
+          const changed = event.payload.payload.path;
           if (changed === target) {
             // If user hasn’t modified content, auto-reload; otherwise show a banner
             if (currentContent === content) {
@@ -273,6 +277,11 @@ export function FileViewer({
     setup();
     return () => {
       if (unlisten) unlisten();
+      if (subscription) {
+        invoke("unsubscribe_events", { subscriptionId: subscription }).catch(
+          () => {},
+        );
+      }
     };
   }, [filePath, canonicalFile, content, currentContent]);
 
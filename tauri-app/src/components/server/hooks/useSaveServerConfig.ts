@@ -50,6 +50,8 @@ export function useSaveServerConfig() {
         await invoke("claude_mcp_add", {
           request: req,
           workingDir: selectedProject,
+          // Saving the dialog replaces the selected server, like the other clients
+          conflictPolicy: "overwrite",
         });
       } else {
         await invoke("add_mcp_server", {
@@ -28,6 +28,7 @@ interface ServerFormData {
   command: string;
   args: string;
   env?: Record<string, string>;
+  conflictPolicy?: "default" | "error" | "overwrite" | "keep_both";
 }
 
 export function useClaudeCodeManagement() {
@@ -81,7 +82,7 @@ export function useClaudeCodeManagement() {
 
   const addServer = async (formData: ServerFormData) => {
     try {
-      const { name, type, url, command, args, env } = formData;
+      const { name, type, url, command, args, env, conflictPolicy } = formData;
       
       if (!name.trim()) {
         toast({
@@ -128,7 +129,9 @@ export function useClaudeCodeManagement() {
 
       const response = await invoke<{success: boolean, message: string}>("claude_mcp_add", { 
         request, 
-        workingDir: selectedProject 
+        workingDir: selectedProject,
+        // Taken names follow the conflict_policy setting unless the form picks a policy
+        conflictPolicy: conflictPolicy && conflictPolicy !== "default" ? conflictPolicy : undefined
       });
       
       if (response.success) {
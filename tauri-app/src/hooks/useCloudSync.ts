@@ -3,7 +3,7 @@ import {
   uploadConfigsToCloud,
 } from "@/lib/cloud-sync";
 import { useClientPathStore } from "@/stores/clientPathStore";
-import { ServerTableData } from "@/types";
+import { BatchReport, ServerTableData } from "@/types";
 import { invoke } from "@tauri-apps/api/core";
 import { useCallback, useState } from "react";
 import { toast } from "sonner";
@@ -51,26 +51,26 @@ export const useCloudSync = (
       setIsSyncing(true);
       const serverConfigs = await downloadConfigsFromCloud();
 
-      // Iterate over downloaded configurations and save them locally
-      for (const config of serverConfigs) {
-        // The backend add_mcp_server handles both adding and updating
-        const _serverItem = {
-          clientName: selectedClient,
-          path: selectedPath || "",
-          serverName: config.name,
-        };
-        try {
-          await invoke("enable_mcp_server", _serverItem);
-        } catch (error) {}
-        const serverItem = {
-          clientName: selectedClient,
-          path: selectedPath || "",
-          serverName: config.name,
-          serverConfig: config,
-        };
-        try {
-          await invoke("add_mcp_server", serverItem);
-        } catch (error) {}
+      // Saved as one batch: enabling first brings back disabled servers, and the
+      // upsert adds or updates the rest. Enabling a server that isn't disabled fails
+      // harmlessly, so only failed upserts are reported.
+      const target = { client: selectedClient, path: selectedPath || null };
+      const ops = serverConfigs.flatMap((config: any) => [
+        { op: "enable_server", target, name: config.name },
+        { op: "upsert_server", target, name: config.name, config },
+      ]);
+      const report = await invoke<BatchReport>("execute_batch", {
+        ops,
+        label: "Cloud download",
+      });
+      const failed = report.results.filter(
+        (result) => !result.ok && ops[result.index].op === "upsert_server",
+      );
+      if (failed.length > 0) {
+        toast.error(
+          `${failed.length} configuration(s) could not be saved: ${failed[0].error}`,
+        );
+        return;
       }
 
       toast.success("Configurations downloaded from cloud successfully.");
@@ -6,6 +6,12 @@ import { invoke } from "@tauri-apps/api/core";
 import { useCallback, useEffect, useState } from "react";
 import { toast } from "sonner";
 
+// Servers a sync couldn't write under their source name (Claude Code targets only)
+interface SyncReport {
+  renamed: Record<string, string>;
+  failed: Record<string, string>;
+}
+
 export function useMcpConfig(
   selectedClient: string,
   selectedPath: string | null,
@@ -186,6 +192,7 @@ export function useMcpConfig(
                     },
               },
               workingDir: selectedProject,
+              conflictPolicy: "overwrite",
             }),
             "Configuration updated successfully",
             "Failed to update configuration",
@@ -272,7 +279,7 @@ export function useMcpConfig(
       try {
         if (selectedClient === "claude_code") {
           if (!selectedProject) throw new Error("Please select a Claude Code project");
-          await executeMcpOperation(
+          const enabled: { name: string } = await executeMcpOperation(
             invoke("claude_enable_server", {
               workingDir: selectedProject,
               name: key,
@@ -280,6 +287,9 @@ export function useMcpConfig(
             "Server enabled successfully",
             "Failed to enable server",
           );
+          if (enabled?.name && enabled.name !== key) {
+            toast.warning(`Server '${key}' was enabled as '${enabled.name}'`);
+          }
         } else {
           await executeMcpOperation(
             invoke("enable_mcp_server", {
@@ -345,7 +355,7 @@ export function useMcpConfig(
         const fromPath = getClientPath(fromClient);
         const toPath = getClientPath(toClient);
 
-        await executeMcpOperation(
+        const report: SyncReport = await executeMcpOperation(
           invoke("sync_mcp_config", {
             fromClient,
             toClient,
@@ -356,6 +366,12 @@ export function useMcpConfig(
           `Configuration synced from ${fromClient} to ${toClient} successfully`,
           "Failed to sync configuration",
         );
+        for (const [from, to] of Object.entries(report?.renamed ?? {})) {
+          toast.warning(`Server '${from}' was synced as '${to}'`);
+        }
+        for (const [name, reason] of Object.entries(report?.failed ?? {})) {
+          toast.error(`Server '${name}' was not synced: ${reason}`);
+        }
         await loadConfig();
       } catch (error) {
         const errorMessage =
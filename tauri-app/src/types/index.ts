@@ -43,3 +43,20 @@ export interface Nav {
   icon: JSX.Element;
   path?: string;
 }
+
+// Outcome of one operation of an execute_batch call
+export type BatchOpResult = {
+  index: number;
+  ok: boolean;
+  error?: string;
+  warnings?: string[];
+};
+
+// Report returned by execute_batch
+export type BatchReport = {
+  results: BatchOpResult[];
+  applied: number;
+  failed: number;
+  skipped: number;
+  undo_id: string | null;
+};
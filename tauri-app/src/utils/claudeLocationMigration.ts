@@ -0,0 +1,48 @@
+// Claude Code config locations ("native", "wsl:<distro>") and the one-time migration that
+// tags stored Claude Code scope references with the location they were created against
+import { invoke } from "@tauri-apps/api/core";
+
+export interface ClaudeConfigLocation {
+  id: string;
+  label: string;
+  path: string;
+  exists: boolean;
+}
+
+export interface LocationReport {
+  locations: ClaudeConfigLocation[];
+  selected: string | null;
+  needs_choice: boolean;
+}
+
+// Stored "Global" or project scope the Claude Code pages and the sync bridge use
+const CLAUDE_SCOPE_KEY = "clientPath_claude_code";
+// Explicit location ID the stored scope belongs to
+export const CLAUDE_LOCATION_KEY = "claudeConfigLocation";
+
+export const listClaudeConfigLocations = () =>
+  invoke<LocationReport>("list_claude_config_locations");
+
+export const setClaudeConfigLocation = (id: string) =>
+  invoke<LocationReport>("set_claude_config_location", { id });
+
+/**
+ * Tag the stored Claude Code scope with `location`. References from before locations had IDs
+ * meant the config the backend resolved to, which is the recorded `location`, so they keep
+ * pointing at it. A scope stored for another location doesn't exist in this config and is
+ * dropped. Returns true when the stored scope was dropped.
+ */
+export function migrateStoredReferences(location: string): boolean {
+  if (typeof window === "undefined") return false;
+  try {
+    const tagged = localStorage.getItem(CLAUDE_LOCATION_KEY);
+    localStorage.setItem(CLAUDE_LOCATION_KEY, location);
+    if (tagged !== null && tagged !== location) {
+      localStorage.removeItem(CLAUDE_SCOPE_KEY);
+      return true;
+    }
+  } catch (_) {
+    // ignore storage errors
+  }
+  return false;
+}